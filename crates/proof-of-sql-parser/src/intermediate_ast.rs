@@ -4,7 +4,7 @@
 * https://docs.rs/vervolg/latest/vervolg/ast/enum.Statement.html
 ***/
 
-use crate::{intermediate_decimal::IntermediateDecimal, Identifier};
+use crate::{intermediate_decimal::IntermediateDecimal, posql_time::PoSQLTimestamp, Identifier};
 use serde::{Deserialize, Serialize};
 
 /// Representation of a SetExpression, a collection of rows, each having one or more columns.
@@ -20,10 +20,37 @@ pub enum SetExpression {
         /// If None, no filter is applied
         where_expr: Option<Box<Expression>>,
         /// Group by expressions e.g. `a` in `SELECT a, COUNT(*) FROM table GROUP BY a`
-        group_by: Vec<Identifier>,
+        ///
+        /// An integer literal is the position of a result expression,
+        /// e.g. `1` in `SELECT a + 1, COUNT(*) FROM table GROUP BY 1` refers to `a + 1`
+        group_by: Vec<Box<Expression>>,
+        /// Filter expression over the groups e.g. `COUNT(*) > 1` in
+        /// `SELECT a, COUNT(*) FROM table GROUP BY a HAVING COUNT(*) > 1`
+        /// If None, no filter is applied
+        having: Option<Box<Expression>>,
+        /// Whether duplicate rows are removed from the result e.g. `SELECT DISTINCT a FROM table`
+        distinct: bool,
+    },
+    /// Rows of two set expressions e.g. `SELECT a FROM t1 UNION ALL SELECT a FROM t2`
+    Union {
+        /// The set expression whose rows come first
+        left: Box<SetExpression>,
+        /// The query whose rows come last
+        right: Box<SetExpression>,
+        /// Whether duplicate rows are kept, i.e. `UNION ALL` rather than `UNION`
+        all: bool,
     },
 }
 
+/// A named query of a `WITH` clause e.g. `recent AS (SELECT a FROM table WHERE b > 5)`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct CommonTableExpression {
+    /// The name the query is referenced by in later queries e.g. `recent`
+    pub name: Identifier,
+    /// The query whose rows make up the named table
+    pub query: Box<SetExpression>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 /// What to select in a query
 pub enum SelectResultExpr {
@@ -70,9 +97,60 @@ pub enum TableExpression {
         table: Identifier,
         /// Namespace / schema for the table
         schema: Option<Identifier>,
+        /// The name that the columns of the table are qualified by instead of the table name e.g. `t` in
+        /// `SELECT t.a FROM sxt.table AS t`
+        alias: Option<Identifier>,
+    },
+    /// The inner join of two row sets on the equality of a column from each
+    Join {
+        /// The row set on the left of the `JOIN`
+        left: Box<TableExpression>,
+        /// The table on the right of the `JOIN`
+        right: Box<TableExpression>,
+        /// The join condition e.g. `a.k = b.k` in `a JOIN b ON a.k = b.k`
+        on: Box<JoinCondition>,
     },
 }
 
+/// A column qualified by the name or the alias of its table e.g. `a.k`, and possibly by its schema e.g. `sxt.a.k`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct QualifiedColumn {
+    /// The schema of the table of the column, if given
+    pub schema: Option<Identifier>,
+    /// The table of the column
+    pub table: Identifier,
+    /// The name of the column
+    pub column: Identifier,
+}
+
+/// A column of a join condition, which only has to be qualified by its table if both tables have a column of its name
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub enum JoinColumn {
+    /// A column that is not qualified e.g. `k`
+    Column(Identifier),
+    /// A column qualified by its table e.g. `a.k`
+    Qualified(QualifiedColumn),
+}
+
+impl JoinColumn {
+    /// The name of the column
+    pub fn column(&self) -> Identifier {
+        match self {
+            JoinColumn::Column(column) => *column,
+            JoinColumn::Qualified(qualified) => qualified.column,
+        }
+    }
+}
+
+/// The condition of a join, which equates a column from each side e.g. `a.k = b.k`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct JoinCondition {
+    /// The column on the left of the `=`
+    pub left: JoinColumn,
+    /// The column on the right of the `=`
+    pub right: JoinColumn,
+}
+
 /// Binary operators for simple expressions
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
 pub enum BinaryOperator {
@@ -109,6 +187,29 @@ pub enum BinaryOperator {
 pub enum UnaryOperator {
     /// Logical inversion
     Not,
+
+    /// Null check `IS NULL`
+    IsNull,
+
+    /// Non-null check `IS NOT NULL`
+    IsNotNull,
+}
+
+/// Data types that an expression can be cast to
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DataType {
+    /// `BOOLEAN`
+    Boolean,
+    /// `SMALLINT`
+    SmallInt,
+    /// `INT`
+    Int,
+    /// `BIGINT`
+    BigInt,
+    /// `INT128`
+    Int128,
+    /// `DECIMAL(precision, scale)`
+    Decimal75(u8, i8),
 }
 
 // Aggregation operators
@@ -123,6 +224,10 @@ pub enum AggregationOperator {
     Sum,
     /// Count
     Count,
+    /// Count of distinct values
+    CountDistinct,
+    /// Average
+    Avg,
     /// Return the first value
     First,
 }
@@ -134,6 +239,8 @@ impl std::fmt::Display for AggregationOperator {
             AggregationOperator::Min => write!(f, "min"),
             AggregationOperator::Sum => write!(f, "sum"),
             AggregationOperator::Count => write!(f, "count"),
+            AggregationOperator::CountDistinct => write!(f, "count distinct"),
+            AggregationOperator::Avg => write!(f, "avg"),
             AggregationOperator::First => write!(f, "first"),
         }
     }
@@ -148,6 +255,9 @@ pub enum Expression {
     /// Column
     Column(Identifier),
 
+    /// Column qualified by its table e.g. `t.a`, which always has a table
+    QualifiedColumn(QualifiedColumn),
+
     /// Unary operation
     Unary {
         /// The unary operator
@@ -166,6 +276,62 @@ pub enum Expression {
         right: Box<Expression>,
     },
 
+    /// Membership in a list of literals e.g. `a IN (1, 2, 3)`
+    InList {
+        /// The expression to look up
+        expr: Box<Expression>,
+        /// The literals to look the expression up in
+        list: Vec<Literal>,
+    },
+
+    /// Membership in the result of a subquery e.g. `a IN (SELECT b FROM table)`
+    InSubquery {
+        /// The expression to look up
+        expr: Box<Expression>,
+        /// The subquery, whose single result column the expression is looked up in
+        subquery: Box<SetExpression>,
+    },
+
+    /// Whether a subquery has any rows e.g. `EXISTS (SELECT b FROM table)`
+    Exists(Box<SetExpression>),
+
+    /// The single value of a subquery with a single row and column e.g. `(SELECT MAX(b) FROM table)`
+    Subquery(Box<SetExpression>),
+
+    /// Range check e.g. `a BETWEEN 1 AND 10`
+    Between {
+        /// The expression to check
+        expr: Box<Expression>,
+        /// The lower bound, inclusive
+        low: Box<Expression>,
+        /// The upper bound, inclusive
+        high: Box<Expression>,
+    },
+
+    /// Pattern match e.g. `a LIKE 'abc%'`
+    Like {
+        /// The expression to match
+        expr: Box<Expression>,
+        /// The pattern, where `%` matches any sequence of characters and `_` matches any single character
+        pattern: String,
+    },
+
+    /// Conditional expression e.g. `CASE WHEN a > 1 THEN 'big' ELSE 'small' END`
+    Case {
+        /// The conditions, in order, each with the result of the rows where it is the first true condition
+        when_then: Vec<(Box<Expression>, Box<Expression>)>,
+        /// The result of the rows where no condition is true, which is `NULL` if absent
+        else_expr: Option<Box<Expression>>,
+    },
+
+    /// Conversion of an expression to another type e.g. `CAST(a AS DECIMAL(10, 2))`
+    Cast {
+        /// The expression to convert
+        expr: Box<Expression>,
+        /// The type to convert to
+        data_type: DataType,
+    },
+
     /// * expression
     Wildcard,
 
@@ -176,6 +342,27 @@ pub enum Expression {
         /// The expression to aggregate
         expr: Box<Expression>,
     },
+
+    /// A window function e.g. `SUM(a) OVER (PARTITION BY b ORDER BY c)`
+    Window {
+        /// The function that is computed for each row over the rows of its partition
+        function: WindowFunction,
+        /// The columns whose values split the rows into partitions, or none for a single partition
+        partition_by: Vec<Identifier>,
+        /// The order of the rows within each partition
+        order_by: Vec<OrderBy>,
+    },
+}
+
+/// Window functions e.g. `ROW_NUMBER()` in `ROW_NUMBER() OVER (ORDER BY a)`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub enum WindowFunction {
+    /// The 1-based position of the row within its partition
+    RowNumber,
+    /// The 1-based position within its partition of the first row that is ordered like the row
+    Rank,
+    /// The running sum of an expression, over the rows of the partition up to the row and the rows ordered like it
+    Sum(Box<Expression>),
 }
 
 impl Expression {
@@ -211,6 +398,22 @@ impl Expression {
         })
     }
 
+    /// Create a new COUNT(DISTINCT)
+    pub fn count_distinct(self) -> Box<Self> {
+        Box::new(Expression::Aggregation {
+            op: AggregationOperator::CountDistinct,
+            expr: Box::new(self),
+        })
+    }
+
+    /// Create a new AVG()
+    pub fn avg(self) -> Box<Self> {
+        Box::new(Expression::Aggregation {
+            op: AggregationOperator::Avg,
+            expr: Box::new(self),
+        })
+    }
+
     /// Create a new FIRST()
     pub fn first(self) -> Box<Self> {
         Box::new(Expression::Aggregation {
@@ -328,6 +531,10 @@ pub enum Literal {
     VarChar(String),
     /// Decimal Literal
     Decimal(IntermediateDecimal),
+    /// Timestamp Literal
+    Timestamp(PoSQLTimestamp),
+    /// Date Literal, as the number of days since the Unix epoch
+    Date(i32),
 }
 
 impl From<bool> for Literal {
@@ -379,6 +586,12 @@ impl From<IntermediateDecimal> for Literal {
     }
 }
 
+impl From<PoSQLTimestamp> for Literal {
+    fn from(val: PoSQLTimestamp) -> Self {
+        Literal::Timestamp(val)
+    }
+}
+
 /// Helper function to append an item to a vector
 pub(crate) fn append<T>(list: Vec<T>, item: T) -> Vec<T> {
     let mut result = list;
@@ -1,6 +1,11 @@
 use crate::{
-    intermediate_ast::OrderByDirection::{Asc, Desc},
+    intermediate_ast::{
+        DataType, Expression, Literal,
+        OrderByDirection::{Asc, Desc},
+        WindowFunction,
+    },
     intermediate_decimal::IntermediateDecimal,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone, PoSQLTimestamp},
     sql::*,
     test_utility::*,
     SelectStatement,
@@ -226,6 +231,89 @@ fn we_can_parse_a_query_with_a_column_equals_a_decimal() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_a_query_with_a_timestamp_range() {
+    let ast = "SELECT A FROM SXT_TAB WHERE T >= TIMESTAMP '2024-01-01T00:00:00Z' \
+               AND T <= timestamp '2024-01-01T00:00:00.5+01:00'"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(
+                ge(
+                    col("t"),
+                    lit(PoSQLTimestamp::new(
+                        1_704_067_200,
+                        PoSQLTimeUnit::Second,
+                        PoSQLTimeZone::Utc,
+                    )),
+                ),
+                le(
+                    col("t"),
+                    lit(PoSQLTimestamp::new(
+                        1_704_063_600_500,
+                        PoSQLTimeUnit::Millisecond,
+                        PoSQLTimeZone::FixedOffset(3600),
+                    )),
+                ),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_a_column_equals_a_date() {
+    let ast = "SELECT A FROM SXT_TAB WHERE D = DATE '2024-01-01'"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            equal(col("d"), lit(Literal::Date(19_723))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_columns_named_timestamp_or_date() {
+    let ast = "SELECT timestamp, date FROM SXT_TAB WHERE date = timestamp"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["timestamp", "date"]),
+            tab(None, "sxt_tab"),
+            equal(col("date"), col("timestamp")),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_invalid_timestamp_or_date_literals() {
+    for query in [
+        "SELECT A FROM SXT_TAB WHERE T = TIMESTAMP '2024-01-01'",
+        "SELECT A FROM SXT_TAB WHERE T = TIMESTAMP '2024-01-01T25:00:00Z'",
+        "SELECT A FROM SXT_TAB WHERE D = DATE '2024-02-30'",
+    ] {
+        assert!(query.parse::<SelectStatement>().is_err());
+    }
+}
+
 #[test]
 fn we_can_parse_a_query_with_two_result_columns() {
     let ast = "Select a,  b froM sxt_tab where C = D + 1 and E = F and G"
@@ -369,15 +457,15 @@ fn we_can_parse_a_query_with_one_not_equals_filter_expression() {
 }
 
 #[test]
-fn we_can_parse_a_query_with_one_logical_not_filter_expression() {
-    let ast = "select a from sxt_tab where not (b = d + 3)"
+fn we_can_parse_a_query_with_null_checks() {
+    let ast = "select a from sxt_tab where b IS NULL or c is not null"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
         query(
             cols_res(&["a"]),
             tab(None, "sxt_tab"),
-            not(equal(col("b"), col("d") + lit(3))),
+            or(is_null(col("b")), is_not_null(col("c"))),
             vec![],
         ),
         vec![],
@@ -387,46 +475,30 @@ fn we_can_parse_a_query_with_one_logical_not_filter_expression() {
 }
 
 #[test]
-fn we_can_parse_a_query_with_one_logical_and_filter_expression() {
-    let ast = "select a from sxt_tab where (b = 3) and c"
+fn we_can_parse_null_checks_with_more_precedence_priority_than_comparisons_and_less_than_arithmetic(
+) {
+    let ast = "select a from sxt_tab where not b = c + d is null"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = "select a from sxt_tab where not (b = ((c + d) is null))"
         .parse::<SelectStatement>()
         .unwrap();
-    let expected_ast = select(
-        query(
-            cols_res(&["a"]),
-            tab(None, "sxt_tab"),
-            and(equal(col("b"), lit(3)), col("c")),
-            vec![],
-        ),
-        vec![],
-        None,
-    );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_a_query_with_one_logical_and_filter_expression_with_both_left_and_right_side_equal_to_string_literals(
-) {
-    let ast = "select bid_in_usd_over_1e2 from sxt.options_quote where type = 'call' and security = 'eth'".parse::<SelectStatement>().unwrap();
-    let expected_ast = select(
-        query(
-            cols_res(&["bid_in_usd_over_1e2"]),
-            tab(Some("sxt"), "options_quote"),
-            and(
-                equal(col("type"), lit("call")),
-                equal(col("security"), lit("eth")),
-            ),
-            vec![],
-        ),
-        vec![],
-        None,
-    );
-    assert_eq!(ast, expected_ast);
+fn we_cannot_parse_a_null_check_without_null() {
+    assert!("select a from sxt_tab where b is not"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where b is 3"
+        .parse::<SelectStatement>()
+        .is_err());
 }
 
 #[test]
-fn we_can_parse_a_query_with_one_logical_or_filter_expression() {
-    let ast = "select a from sxt_tab where (b = 3) or (c = -2.34)"
+fn we_can_parse_a_query_with_in_lists() {
+    let ast = "select a from sxt_tab where b IN (1, -2) and c not in ('x', 'y') or d in (1.5)"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
@@ -434,10 +506,21 @@ fn we_can_parse_a_query_with_one_logical_or_filter_expression() {
             cols_res(&["a"]),
             tab(None, "sxt_tab"),
             or(
-                equal(col("b"), lit(3)),
-                equal(
-                    col("c"),
-                    lit(IntermediateDecimal::try_from("-2.34").unwrap()),
+                and(
+                    in_list(col("b"), vec![Literal::BigInt(1), Literal::BigInt(-2)]),
+                    not(in_list(
+                        col("c"),
+                        vec![
+                            Literal::VarChar("x".to_string()),
+                            Literal::VarChar("y".to_string()),
+                        ],
+                    )),
+                ),
+                in_list(
+                    col("d"),
+                    vec![Literal::Decimal(
+                        IntermediateDecimal::try_from("1.5").unwrap(),
+                    )],
                 ),
             ),
             vec![],
@@ -449,8 +532,32 @@ fn we_can_parse_a_query_with_one_logical_or_filter_expression() {
 }
 
 #[test]
-fn we_can_parse_a_query_with_two_logical_and_not_filter_expressions() {
-    let ast = "select a from sxt_tab where (b = 3) and (not (c = d - 2))"
+fn we_can_parse_in_lists_with_the_precedence_of_comparisons() {
+    let ast = "select a from sxt_tab where not b + 1 in (2, 3)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = "select a from sxt_tab where not ((b + 1) in (2, 3))"
+        .parse::<SelectStatement>()
+        .unwrap();
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_in_lists_that_are_empty_or_not_made_of_literals() {
+    assert!("select a from sxt_tab where b in ()"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where b in (c, 1)"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where b in 1"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_between_ranges() {
+    let ast = "select a from sxt_tab where b BETWEEN 1 and c + 2 and d not between -1.5 and e"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
@@ -458,8 +565,12 @@ fn we_can_parse_a_query_with_two_logical_and_not_filter_expressions() {
             cols_res(&["a"]),
             tab(None, "sxt_tab"),
             and(
-                equal(col("b"), lit(3)),
-                not(equal(col("c"), col("d") - lit(2))),
+                between(col("b"), lit(1), col("c") + lit(2)),
+                not(between(
+                    col("d"),
+                    lit(IntermediateDecimal::try_from("-1.5").unwrap()),
+                    col("e"),
+                )),
             ),
             vec![],
         ),
@@ -470,21 +581,22 @@ fn we_can_parse_a_query_with_two_logical_and_not_filter_expressions() {
 }
 
 #[test]
-fn we_can_parse_a_query_with_three_logical_not_and_or_filter_expressions() {
-    let ast = "select a from sxt_tab where not ((b = 3 * 7) and  ((f = 45) or (c + 2 * d = -2)))"
+fn we_can_parse_between_ranges_with_the_precedence_of_comparisons() {
+    let ast = "select a from sxt_tab where not b - 1 between c and d or e"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = "select a from sxt_tab where (not ((b - 1) between c and d)) or e"
+        .parse::<SelectStatement>()
+        .unwrap();
+    assert_eq!(ast, expected_ast);
+    let ast = "select a from sxt_tab where b between (c and d) and e"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
         query(
             cols_res(&["a"]),
             tab(None, "sxt_tab"),
-            not(and(
-                equal(col("b"), lit(3) * lit(7)),
-                or(
-                    equal(col("f"), lit(45)),
-                    equal(col("c") + lit(2) * col("d"), lit(-2)),
-                ),
-            )),
+            between(col("b"), and(col("c"), col("d")), col("e")),
             vec![],
         ),
         vec![],
@@ -494,30 +606,70 @@ fn we_can_parse_a_query_with_three_logical_not_and_or_filter_expressions() {
 }
 
 #[test]
-fn we_can_parse_a_query_with_the_minimum_i128_value_as_the_equal_filter_literal() {
-    let ast = ("select a from sxt_tab where b = ".to_owned() + &std::i128::MIN.to_string())
+fn we_cannot_parse_between_ranges_without_both_bounds() {
+    assert!("select a from sxt_tab where b between 1"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where b between and 2"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where b between 1 or 2"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_like_patterns() {
+    let ast = "select a from sxt_tab where b LIKE 'ac%' and c not like 'x_z'"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
         query(
             cols_res(&["a"]),
             tab(None, "sxt_tab"),
-            equal(col("b"), lit(std::i128::MIN)),
+            and(like(col("b"), "ac%"), not(like(col("c"), "x_z"))),
             vec![],
         ),
         vec![],
         None,
     );
     assert_eq!(ast, expected_ast);
+}
 
-    let ast = "select a from sxt_tab where b = -170141183460469231731687303715884105728"
+#[test]
+fn we_cannot_parse_like_patterns_that_are_not_string_literals() {
+    assert!("select a from sxt_tab where b like c"
         .parse::<SelectStatement>()
-        .unwrap();
+        .is_err());
+    assert!("select a from sxt_tab where b like 1"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where b like"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_case_expressions() {
+    let ast =
+        "select CASE WHEN a >= 100 THEN 'big' WHEN a >= 10 THEN 'medium' ELSE 'small' END as size \
+        from sxt_tab where case when b then c end = 1"
+            .parse::<SelectStatement>()
+            .unwrap();
     let expected_ast = select(
         query(
-            cols_res(&["a"]),
+            vec![col_res(
+                case(
+                    vec![
+                        (ge(col("a"), lit(100)), lit("big")),
+                        (ge(col("a"), lit(10)), lit("medium")),
+                    ],
+                    Some(lit("small")),
+                ),
+                "size",
+            )],
             tab(None, "sxt_tab"),
-            equal(col("b"), lit(std::i128::MIN)),
+            equal(case(vec![(col("b"), col("c"))], None), lit(1)),
             vec![],
         ),
         vec![],
@@ -527,25 +679,30 @@ fn we_can_parse_a_query_with_the_minimum_i128_value_as_the_equal_filter_literal(
 }
 
 #[test]
-fn we_cannot_parse_a_query_with_the_literals_overflowing() {
-    // note: see the minus sign in front of the literal, causing the overflow
-    assert!(
-        ("select a from sxt_tab where b = -".to_owned() + &std::i128::MIN.to_string())
+fn we_can_parse_simple_case_expressions_as_equalities() {
+    let ast =
+        "select a from sxt_tab where case b + 1 when 1 then true when c then d else false end"
             .parse::<SelectStatement>()
-            .is_err()
-    );
+            .unwrap();
+    let expected_ast = "select a from sxt_tab where case when b + 1 = 1 then true when b + 1 = c then d else false end"
+        .parse::<SelectStatement>()
+        .unwrap();
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_a_query_with_the_maximum_i128_value_as_the_equal_filter_literal() {
-    let ast = ("select a from sxt_tab where b = ".to_owned() + &std::i128::MAX.to_string())
+fn we_can_parse_nested_case_expressions() {
+    let ast = "select a from sxt_tab where case when b then case when c then d else e end end"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
         query(
             cols_res(&["a"]),
             tab(None, "sxt_tab"),
-            equal(col("b"), lit(std::i128::MAX)),
+            case(
+                vec![(col("b"), case(vec![(col("c"), col("d"))], Some(col("e"))))],
+                None,
+            ),
             vec![],
         ),
         vec![],
@@ -555,15 +712,40 @@ fn we_can_parse_a_query_with_the_maximum_i128_value_as_the_equal_filter_literal(
 }
 
 #[test]
-fn we_can_parse_a_query_and_rename_a_result_column_using_the_as_keyword() {
-    let ast = "select a as a_rename from sxt_tab where b = 4 + d"
+fn we_cannot_parse_case_expressions_without_when_or_end() {
+    assert!("select a from sxt_tab where case else b end"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where case when b then c"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!(
+        "select a from sxt_tab where case when b then c else d else e end"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+}
+
+#[test]
+fn we_can_parse_a_query_with_casts() {
+    let ast = "select CAST(a AS BIGINT) as a, cast(b + 1 as decimal(10, 2)) as b from sxt_tab \
+        where cast(c as Boolean) and cast(d as numeric(5)) >= cast(e as int)"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
         query(
-            vec![col_res(col("a"), "a_rename")],
+            vec![
+                col_res(cast(col("a"), DataType::BigInt), "a"),
+                col_res(cast(col("b") + lit(1), DataType::Decimal75(10, 2)), "b"),
+            ],
             tab(None, "sxt_tab"),
-            equal(col("b"), lit(4) + col("d")),
+            and(
+                cast(col("c"), DataType::Boolean),
+                ge(
+                    cast(col("d"), DataType::Decimal75(5, 0)),
+                    cast(col("e"), DataType::Int),
+                ),
+            ),
             vec![],
         ),
         vec![],
@@ -573,58 +755,313 @@ fn we_can_parse_a_query_and_rename_a_result_column_using_the_as_keyword() {
 }
 
 #[test]
-fn we_can_parse_a_query_and_rename_a_result_column_without_using_the_as_keyword() {
-    let parsed_ast = "select a a_rename from sxt_tab where b >= c + 4"
+fn we_can_parse_casts_to_all_supported_types() {
+    for (type_name, data_type) in [
+        ("boolean", DataType::Boolean),
+        ("BOOL", DataType::Boolean),
+        ("smallint", DataType::SmallInt),
+        ("int", DataType::Int),
+        ("INTEGER", DataType::Int),
+        ("bigint", DataType::BigInt),
+        ("int128", DataType::Int128),
+        ("decimal75(75, -3)", DataType::Decimal75(75, -3)),
+    ] {
+        let ast = format!("select a from sxt_tab where cast(b as {type_name}) = 1")
+            .parse::<SelectStatement>()
+            .unwrap();
+        let expected_ast = select(
+            query(
+                cols_res(&["a"]),
+                tab(None, "sxt_tab"),
+                equal(cast(col("b"), data_type), lit(1)),
+                vec![],
+            ),
+            vec![],
+            None,
+        );
+        assert_eq!(ast, expected_ast);
+    }
+}
+
+#[test]
+fn we_cannot_parse_casts_to_unsupported_types() {
+    for type_name in [
+        "varchar",
+        "text",
+        "decimal",
+        "bigint(10)",
+        "decimal(256, 2)",
+        "decimal(10, 128)",
+        "decimal(10, 2, 1)",
+    ] {
+        assert!(
+            format!("select a from sxt_tab where cast(b as {type_name}) = 1")
+                .parse::<SelectStatement>()
+                .is_err()
+        );
+    }
+    assert!("select a from sxt_tab where cast(b) = 1"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_one_logical_not_filter_expression() {
+    let ast = "select a from sxt_tab where not (b = d + 3)"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
         query(
-            vec![col_res(col("a"), "a_rename")],
+            cols_res(&["a"]),
             tab(None, "sxt_tab"),
-            ge(col("b"), col("c") + lit(4)),
+            not(equal(col("b"), col("d") + lit(3))),
             vec![],
         ),
         vec![],
         None,
     );
-    assert_eq!(parsed_ast, expected_ast);
-}
-
-#[test]
-fn we_can_parse_logical_not_with_more_precedence_priority_than_logical_and() {
-    let parsed_ast = "select a from sxt_tab where a = 3 and not b = 2.53"
-        .parse::<SelectStatement>()
-        .unwrap();
-    let expected_ast = "select a from sxt_tab where (a = 3) and (not b = 2.53)"
-        .parse::<SelectStatement>()
-        .unwrap();
-    assert_eq!(parsed_ast, expected_ast);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_logical_not_with_less_precedence_priority_than_equal_operator() {
-    let parsed_ast = "select a from sxt_tab where not b = d"
+fn we_can_parse_a_query_with_one_logical_and_filter_expression() {
+    let ast = "select a from sxt_tab where (b = 3) and c"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
         query(
             cols_res(&["a"]),
             tab(None, "sxt_tab"),
-            not(equal(col("b"), col("d"))),
+            and(equal(col("b"), lit(3)), col("c")),
             vec![],
         ),
         vec![],
         None,
     );
-    assert_eq!(parsed_ast, expected_ast);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_logical_and_with_more_precedence_priority_than_logical_or() {
-    let ast = "select a from sxt_tab where a = -1 or c = -3 and a = 3"
-        .parse::<SelectStatement>()
-        .unwrap();
-    let expected_ast = "select a from sxt_tab where a = -1 or (c = -3 and a = 3)"
+fn we_can_parse_a_query_with_one_logical_and_filter_expression_with_both_left_and_right_side_equal_to_string_literals(
+) {
+    let ast = "select bid_in_usd_over_1e2 from sxt.options_quote where type = 'call' and security = 'eth'".parse::<SelectStatement>().unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["bid_in_usd_over_1e2"]),
+            tab(Some("sxt"), "options_quote"),
+            and(
+                equal(col("type"), lit("call")),
+                equal(col("security"), lit("eth")),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_one_logical_or_filter_expression() {
+    let ast = "select a from sxt_tab where (b = 3) or (c = -2.34)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            or(
+                equal(col("b"), lit(3)),
+                equal(
+                    col("c"),
+                    lit(IntermediateDecimal::try_from("-2.34").unwrap()),
+                ),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_two_logical_and_not_filter_expressions() {
+    let ast = "select a from sxt_tab where (b = 3) and (not (c = d - 2))"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(
+                equal(col("b"), lit(3)),
+                not(equal(col("c"), col("d") - lit(2))),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_three_logical_not_and_or_filter_expressions() {
+    let ast = "select a from sxt_tab where not ((b = 3 * 7) and  ((f = 45) or (c + 2 * d = -2)))"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            not(and(
+                equal(col("b"), lit(3) * lit(7)),
+                or(
+                    equal(col("f"), lit(45)),
+                    equal(col("c") + lit(2) * col("d"), lit(-2)),
+                ),
+            )),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_the_minimum_i128_value_as_the_equal_filter_literal() {
+    let ast = ("select a from sxt_tab where b = ".to_owned() + &std::i128::MIN.to_string())
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            equal(col("b"), lit(std::i128::MIN)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+
+    let ast = "select a from sxt_tab where b = -170141183460469231731687303715884105728"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            equal(col("b"), lit(std::i128::MIN)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_the_literals_overflowing() {
+    // note: see the minus sign in front of the literal, causing the overflow
+    assert!(
+        ("select a from sxt_tab where b = -".to_owned() + &std::i128::MIN.to_string())
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+}
+
+#[test]
+fn we_can_parse_a_query_with_the_maximum_i128_value_as_the_equal_filter_literal() {
+    let ast = ("select a from sxt_tab where b = ".to_owned() + &std::i128::MAX.to_string())
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            equal(col("b"), lit(std::i128::MAX)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_and_rename_a_result_column_using_the_as_keyword() {
+    let ast = "select a as a_rename from sxt_tab where b = 4 + d"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(col("a"), "a_rename")],
+            tab(None, "sxt_tab"),
+            equal(col("b"), lit(4) + col("d")),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_and_rename_a_result_column_without_using_the_as_keyword() {
+    let parsed_ast = "select a a_rename from sxt_tab where b >= c + 4"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(col("a"), "a_rename")],
+            tab(None, "sxt_tab"),
+            ge(col("b"), col("c") + lit(4)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_logical_not_with_more_precedence_priority_than_logical_and() {
+    let parsed_ast = "select a from sxt_tab where a = 3 and not b = 2.53"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = "select a from sxt_tab where (a = 3) and (not b = 2.53)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_logical_not_with_less_precedence_priority_than_equal_operator() {
+    let parsed_ast = "select a from sxt_tab where not b = d"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            not(equal(col("b"), col("d"))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(parsed_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_logical_and_with_more_precedence_priority_than_logical_or() {
+    let ast = "select a from sxt_tab where a = -1 or c = -3 and a = 3"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = "select a from sxt_tab where a = -1 or (c = -3 and a = 3)"
         .parse::<SelectStatement>()
         .unwrap();
     assert_eq!(ast, expected_ast);
@@ -834,17 +1271,21 @@ fn we_cannot_parse_a_query_with_select_tablename_followed_by_star() {
 }
 
 #[test]
-fn we_cannot_parse_a_query_with_schemas_followed_by_column_and_table_names() {
-    assert!("select tab.a from tab".parse::<SelectStatement>().is_err());
-    assert!("select tab.a from eth.tab"
-        .parse::<SelectStatement>()
-        .is_err());
-    assert!("select eth.tab.a from eth.tab"
-        .parse::<SelectStatement>()
-        .is_err());
-    assert!("select a from eth.tab where tab.b = 3"
+fn we_can_parse_a_query_with_schemas_followed_by_column_and_table_names() {
+    let ast = "select eth.tab.a from eth.tab where tab.b = 3"
         .parse::<SelectStatement>()
-        .is_err());
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(qcol("eth.tab.a"), "a")],
+            tab(Some("eth"), "tab"),
+            equal(qcol("tab.b"), lit(3)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
@@ -1030,25 +1471,85 @@ fn we_can_parse_a_query_with_filter_gt() {
 }
 
 #[test]
-fn we_cannot_parse_a_query_with_inner_join_keyword() {
-    assert!(
-        "select tab1.a from tab1 join tab2 on tab1.c = tab2.c where tab2.b > 4"
-            .parse::<SelectStatement>()
-            .is_err()
+fn we_can_parse_a_join_with_qualified_result_columns() {
+    let ast = "select tab1.a from tab1 join tab2 on tab1.c = tab2.c where tab2.b > 4"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(qcol("tab1.a"), "a")],
+            join(tab(None, "tab1"), tab(None, "tab2"), "tab1.c", "tab2.c"),
+            not(le(qcol("tab2.b"), lit(4))),
+            vec![],
+        ),
+        vec![],
+        None,
     );
+    assert_eq!(ast, expected_ast);
 }
 
-// Case when
 #[test]
-fn we_cannot_parse_a_query_with_case_when_keyword() {
-    assert!(
-        "select case when a == 2 then 3 else 5 from tab where b <= 4"
-            .parse::<SelectStatement>()
-            .is_err()
-    );
-}
-
-//////////////////////
+fn we_can_parse_columns_qualified_by_table_aliases_or_schemas() {
+    let expected_ast = select(
+        query(
+            vec![col_res(qcol("t.a"), "a"), col_res(qcol("sxt.tab.b"), "c")],
+            aliased_tab(Some("sxt"), "tab", "t"),
+            equal(qcol("t.b"), lit(4)),
+            vec![qcol("t.a")],
+        ),
+        vec![],
+        None,
+    );
+    for sql in [
+        "select t.a, sxt.tab.b as c from sxt.tab as t where t.b = 4 group by t.a",
+        "SELECT T.A, SXT.TAB.B C FROM SXT.TAB T WHERE T.B = 4 GROUP BY T.A",
+    ] {
+        assert_eq!(sql.parse::<SelectStatement>().unwrap(), expected_ast);
+    }
+    let ast = "select * from fact as f join eth.dim d on f.k = eth.dim.k"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res_all()],
+            join(
+                aliased_tab(None, "fact", "f"),
+                aliased_tab(Some("eth"), "dim", "d"),
+                "f.k",
+                "eth.dim.k",
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_columns_with_more_than_three_parts_or_aliases_of_joins() {
+    for sql in [
+        "select a.b.c.d from t",
+        "select a. from t",
+        "select a from t as",
+        "select a from t as u v",
+        "select a from (t join u on t.k = u.k) as v",
+    ] {
+        assert!(sql.parse::<SelectStatement>().is_err(), "{sql}");
+    }
+}
+
+// Case when
+#[test]
+fn we_cannot_parse_a_query_with_case_when_keyword() {
+    assert!(
+        "select case when a == 2 then 3 else 5 from tab where b <= 4"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+}
+
+//////////////////////
 // Invalid SQLs
 //////////////////////
 #[test]
@@ -1146,6 +1647,27 @@ fn we_can_parse_a_simple_group_by_clause_using_the_wildcard() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_a_group_by_clause_with_expressions_and_positions() {
+    let ast = "select a + 1 as b, count(*) as c from tab group by a + 1, 2, case when d >= 0 then 'x' else 'y' end"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res(col("a") + lit(1), "b"), count_all_res("c")],
+            tab(None, "tab"),
+            vec![
+                col("a") + lit(1),
+                lit(2),
+                case(vec![(ge(col("d"), lit(0)), lit("x"))], Some(lit("y"))),
+            ],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_a_group_by_clause_containing_multiple_aggregations() {
     let ast = "select min(a), max(a) as max_a, count(a), count(*) count_all from tab group by a, b"
@@ -1193,6 +1715,85 @@ fn we_can_parse_a_group_by_clause_containing_multiple_aggregations_where_clause_
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_averages_in_the_result_expr() {
+    let ast = "select a, avg(b), AVG(c) as avg_c, avg(2 * d) from tab where e = 1 group by a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(col("a"), "a"),
+                avg_res(col("b"), "__avg__"),
+                avg_res(col("c"), "avg_c"),
+                col_res((lit(2) * col("d")).avg(), "__avg__"),
+            ],
+            tab(None, "tab"),
+            equal(col("e"), lit(1)),
+            group_by(&["a"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+    assert!("select avg(*) from tab".parse::<SelectStatement>().is_err());
+}
+
+#[test]
+fn we_can_parse_distinct_counts_in_the_result_expr() {
+    let ast = "select a, count(distinct b), COUNT(DISTINCT c) as n from tab group by a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a"), "a"),
+                count_distinct_res(col("b"), "__count_distinct__"),
+                count_distinct_res(col("c"), "n"),
+            ],
+            tab(None, "tab"),
+            group_by(&["a"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+    assert!("select count(distinct *) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_select_distinct() {
+    let ast = "SELECT DISTINCT a, b FROM tab WHERE c = 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        distinct(query(
+            vec![col_res(col("a"), "a"), col_res(col("b"), "b")],
+            tab(None, "tab"),
+            equal(col("c"), lit(1)),
+            vec![],
+        )),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+
+    let ast = "select distinct * from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        distinct(query_all(vec![col_res_all()], tab(None, "tab"), vec![])),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+    assert!("select a distinct from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
 #[test]
 fn we_cannot_parse_a_group_by_clause_after_order_by() {
     assert!("select a from tab order by a group by a"
@@ -1207,6 +1808,46 @@ fn we_cannot_parse_a_group_by_clause_before_where_expr() {
         .is_err());
 }
 
+#[test]
+fn we_can_parse_a_having_clause() {
+    let ast = "select a, count(*) as c from tab where b = 1 group by a having count(*) > 10 and sum(b) >= a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        having(
+            query(
+                vec![col_res(col("a"), "a"), count_all_res("c")],
+                tab(None, "tab"),
+                equal(col("b"), lit(1)),
+                group_by(&["a"]),
+            ),
+            and(
+                not(le(Expression::Wildcard.count(), lit(10))),
+                ge(col("b").sum(), col("a")),
+            ),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+
+    let ast = "select sum(a) from tab having sum(a) >= 1 order by __sum__ limit 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        having(
+            query_all(vec![sum_res(col("a"), "__sum__")], tab(None, "tab"), vec![]),
+            ge(col("a").sum(), lit(1)),
+        ),
+        order("__sum__", Asc),
+        slice(1, 0),
+    );
+    assert_eq!(ast, expected_ast);
+    assert!("select a from tab having a >= 1 group by a"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
 #[test]
 fn we_can_parse_a_aggregations_without_group_by_although_it_is_semantically_incorrect() {
     let ast = "select f as f_col, min(a), max(a) as max_a, count(a), count(*) count_all from tab"
@@ -1436,3 +2077,299 @@ fn we_can_use_aggregation_inside_another_aggregation() {
     );
     assert_eq!(ast, expected_ast);
 }
+
+#[test]
+fn we_can_parse_a_join_of_two_tables() {
+    let ast = "select a, b from fact join eth.dim on fact.k = dim.dk where a > 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a", "b"]),
+            join(
+                tab(None, "fact"),
+                tab(Some("eth"), "dim"),
+                "fact.k",
+                "dim.dk",
+            ),
+            not(le(col("a"), lit(2))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_inner_joins_with_unqualified_and_parenthesized_conditions() {
+    let expected_ast = select(
+        query_all(
+            vec![col_res_all()],
+            join(tab(None, "a"), tab(None, "b"), "k", "b.k"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    for sql in [
+        "select * from a join b on k = b.k",
+        "SELECT * FROM a INNER JOIN b ON (k = b.k)",
+        "select * from (a) inner join (b) on k=b.k",
+    ] {
+        assert_eq!(sql.parse::<SelectStatement>().unwrap(), expected_ast);
+    }
+}
+
+#[test]
+fn we_can_parse_a_chain_of_joins() {
+    let ast = "select * from a join b on a.x = b.x join c on b.y = c.y"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res_all()],
+            join(
+                join(tab(None, "a"), tab(None, "b"), "a.x", "b.x"),
+                tab(None, "c"),
+                "b.y",
+                "c.y",
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_joins_without_an_equality_condition() {
+    assert!("select * from a join b".parse::<SelectStatement>().is_err());
+    assert!("select * from a join b on a.x"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select * from a join b on a.x > b.x"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select * from a join b on a.x = 1"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_unions_of_queries() {
+    let ast = "select a from t1 union all select b from t2 where b = 1 UNION select a from t3 order by a limit 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        union(
+            union_all(
+                query_all(vec![col_res(col("a"), "a")], tab(None, "t1"), vec![]),
+                query(
+                    vec![col_res(col("b"), "b")],
+                    tab(None, "t2"),
+                    equal(col("b"), lit(1)),
+                    vec![],
+                ),
+            ),
+            query_all(vec![col_res(col("a"), "a")], tab(None, "t3"), vec![]),
+        ),
+        order("a", Asc),
+        slice(2, 0),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_union_with_a_missing_query() {
+    assert!("select a from t1 union".parse::<SelectStatement>().is_err());
+    assert!("select a from t1 union all all select a from t2"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("union select a from t1".parse::<SelectStatement>().is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_in_subqueries() {
+    let ast = "select a from t1 where b in (select c from t2 where d = 'x') and e not IN (SELECT f FROM t3)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "t1"),
+            and(
+                in_subquery(
+                    col("b"),
+                    query(
+                        cols_res(&["c"]),
+                        tab(None, "t2"),
+                        equal(col("d"), lit("x")),
+                        vec![],
+                    ),
+                ),
+                not(in_subquery(
+                    col("e"),
+                    query_all(cols_res(&["f"]), tab(None, "t3"), vec![]),
+                )),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_exists_and_scalar_subqueries() {
+    let ast = "select a from t1 where not exists (select c from t2) and b > (select max(c) from t2) or (select count(*) from t3) = 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = "select a from t1 where ((not (exists (select c from t2))) and (b > ((select max(c) from t2)))) or (((select count(*) from t3)) = 2)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    assert_eq!(ast, expected_ast);
+    let ast = "select a from t1 where exists (select c from t2) and b = (select max(c) from t2)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "t1"),
+            and(
+                exists(query_all(cols_res(&["c"]), tab(None, "t2"), vec![])),
+                equal(
+                    col("b"),
+                    subquery(query_all(
+                        vec![max_res(col("c"), "__max__")],
+                        tab(None, "t2"),
+                        vec![],
+                    )),
+                ),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_subqueries_that_are_unions_or_ordered() {
+    assert!(
+        "select a from t1 where b in (select c from t2 union select c from t3)"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+    assert!("select a from t1 where b in (select c from t2 order by c)"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from t1 where exists select c from t2"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_common_table_expressions() {
+    let ast = "WITH recent AS (select a, b from t1 where b >= 1), top as (select a from recent union all select a from t2) select a from top order by a limit 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = with(
+        vec![
+            cte(
+                "recent",
+                query(
+                    cols_res(&["a", "b"]),
+                    tab(None, "t1"),
+                    ge(col("b"), lit(1)),
+                    vec![],
+                ),
+            ),
+            cte(
+                "top",
+                union_all(
+                    query_all(cols_res(&["a"]), tab(None, "recent"), vec![]),
+                    query_all(cols_res(&["a"]), tab(None, "t2"), vec![]),
+                ),
+            ),
+        ],
+        select(
+            query_all(cols_res(&["a"]), tab(None, "top"), vec![]),
+            order("a", Asc),
+            slice(2, 0),
+        ),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_malformed_common_table_expressions() {
+    assert!("with select a from t1".parse::<SelectStatement>().is_err());
+    assert!("with r as select a from t1 select a from r"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("with r (select a from t1) select a from r"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("with r as (select a from t1 order by a) select a from r"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("with r as (select a from t1)"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!(
+        "select a from t1 where a in (with r as (select a from t2) select a from r)"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+}
+
+#[test]
+fn we_can_parse_window_functions() {
+    let ast = "select a, row_number() over (partition by b, c order by d desc) as n, \
+               RANK() OVER (ORDER BY d) r, sum(a + 1) over (partition by b) as s, sum(a) over () from t"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a"), "a"),
+                col_res(
+                    window(WindowFunction::RowNumber, &["b", "c"], order("d", Desc)),
+                    "n",
+                ),
+                col_res(window(WindowFunction::Rank, &[], order("d", Asc)), "r"),
+                col_res(
+                    window(WindowFunction::Sum(col("a") + lit(1)), &["b"], vec![]),
+                    "s",
+                ),
+                col_res(
+                    window(WindowFunction::Sum(col("a")), &[], vec![]),
+                    "__expr__",
+                ),
+            ],
+            tab(None, "t"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_malformed_window_functions() {
+    for query in [
+        "select row_number() from t",
+        "select row_number(a) over () from t",
+        "select rank() over (order by a partition by b) from t",
+        "select sum(a) over (partition by a + 1) from t",
+        "select max(a) over () from t",
+        "select sum(a) over order by b from t",
+    ] {
+        assert!(query.parse::<SelectStatement>().is_err());
+    }
+}
@@ -2,6 +2,7 @@
 
 /// Module for handling an intermediate decimal type received from the lexer.
 pub mod intermediate_decimal;
+pub mod posql_time;
 #[macro_use]
 extern crate lalrpop_util;
 
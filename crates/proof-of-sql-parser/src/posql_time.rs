@@ -0,0 +1,281 @@
+//! Time types of Proof of SQL, as received from the lexer.
+//!
+//! A timestamp is stored as the number of units (e.g. milliseconds) since the Unix epoch, in UTC.
+//! Its time zone only tells how the timestamp should be displayed.
+//! A date is stored as the number of days since the Unix epoch.
+use chrono::{DateTime, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+/// Errors related to the processing of time values in proof-of-sql
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PoSQLTimeError {
+    /// Represents an error encountered during the parsing of a timestamp or date string.
+    #[error("Unable to parse time: {0}")]
+    ParsingError(String),
+    /// Error occurs when a timestamp cannot be represented as a 64-bit number of its units.
+    #[error("Timestamp out of range")]
+    OutOfRange,
+    /// Error occurs when a time zone is not supported.
+    #[error("Invalid time zone: {0}")]
+    InvalidTimeZone(String),
+}
+
+/// The unit of a timestamp
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum PoSQLTimeUnit {
+    /// Seconds, i.e. 10^0 seconds
+    Second,
+    /// Milliseconds, i.e. 10^-3 seconds
+    Millisecond,
+    /// Microseconds, i.e. 10^-6 seconds
+    Microsecond,
+    /// Nanoseconds, i.e. 10^-9 seconds
+    Nanosecond,
+}
+
+impl PoSQLTimeUnit {
+    /// Returns the number of decimal digits of a second that this unit stands for.
+    ///
+    /// This is the scale of a timestamp in seconds, as for decimals.
+    pub fn scale(&self) -> i8 {
+        match self {
+            PoSQLTimeUnit::Second => 0,
+            PoSQLTimeUnit::Millisecond => 3,
+            PoSQLTimeUnit::Microsecond => 6,
+            PoSQLTimeUnit::Nanosecond => 9,
+        }
+    }
+}
+
+impl fmt::Display for PoSQLTimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoSQLTimeUnit::Second => write!(f, "seconds"),
+            PoSQLTimeUnit::Millisecond => write!(f, "milliseconds"),
+            PoSQLTimeUnit::Microsecond => write!(f, "microseconds"),
+            PoSQLTimeUnit::Nanosecond => write!(f, "nanoseconds"),
+        }
+    }
+}
+
+/// The time zone of a timestamp
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum PoSQLTimeZone {
+    /// Coordinated Universal Time
+    Utc,
+    /// A fixed offset from UTC, in seconds
+    FixedOffset(i32),
+}
+
+impl PoSQLTimeZone {
+    /// Returns the time zone with the given offset from UTC, in seconds.
+    pub fn from_offset(offset: i32) -> Self {
+        match offset {
+            0 => PoSQLTimeZone::Utc,
+            _ => PoSQLTimeZone::FixedOffset(offset),
+        }
+    }
+
+    /// Returns the offset of this time zone from UTC, in seconds.
+    pub fn offset(&self) -> i32 {
+        match self {
+            PoSQLTimeZone::Utc => 0,
+            PoSQLTimeZone::FixedOffset(offset) => *offset,
+        }
+    }
+}
+
+/// Displays the time zone as an offset, e.g. `+00:00` or `-05:30`.
+impl fmt::Display for PoSQLTimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let offset = self.offset();
+        let sign = if offset < 0 { '-' } else { '+' };
+        let minutes = offset.unsigned_abs() / 60;
+        write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+/// Parses `UTC`, `Z` or an offset like `+01:00` into a time zone.
+impl FromStr for PoSQLTimeZone {
+    type Err = PoSQLTimeError;
+
+    fn from_str(time_zone: &str) -> Result<Self, Self::Err> {
+        if matches!(time_zone, "UTC" | "utc" | "Z" | "z") {
+            return Ok(PoSQLTimeZone::Utc);
+        }
+        let invalid = || PoSQLTimeError::InvalidTimeZone(time_zone.to_owned());
+        let (sign, offset) = if let Some(offset) = time_zone.strip_prefix('+') {
+            (1, offset)
+        } else if let Some(offset) = time_zone.strip_prefix('-') {
+            (-1, offset)
+        } else {
+            return Err(invalid());
+        };
+        let (hours, minutes) = offset.split_once(':').ok_or_else(invalid)?;
+        let hours: i32 = hours.parse().map_err(|_| invalid())?;
+        let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+        if hours.abs() > 23 || !(0..60).contains(&minutes) {
+            return Err(invalid());
+        }
+        Ok(PoSQLTimeZone::from_offset(
+            sign * (hours * 3600 + minutes * 60),
+        ))
+    }
+}
+
+/// A timestamp literal
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct PoSQLTimestamp {
+    timestamp: i64,
+    unit: PoSQLTimeUnit,
+    timezone: PoSQLTimeZone,
+}
+
+impl PoSQLTimestamp {
+    /// Creates a timestamp from a number of units since the Unix epoch.
+    pub fn new(timestamp: i64, unit: PoSQLTimeUnit, timezone: PoSQLTimeZone) -> Self {
+        Self {
+            timestamp,
+            unit,
+            timezone,
+        }
+    }
+
+    /// Returns the number of units since the Unix epoch.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the unit of this timestamp.
+    pub fn unit(&self) -> PoSQLTimeUnit {
+        self.unit
+    }
+
+    /// Returns the time zone of this timestamp.
+    pub fn timezone(&self) -> PoSQLTimeZone {
+        self.timezone
+    }
+}
+
+/// Parses an RFC 3339 timestamp, e.g. `2024-01-01T00:00:00Z`.
+///
+/// The unit is the coarsest one that represents the timestamp exactly,
+/// and the time zone is the offset of the timestamp.
+impl FromStr for PoSQLTimestamp {
+    type Err = PoSQLTimeError;
+
+    fn from_str(timestamp_str: &str) -> Result<Self, Self::Err> {
+        let datetime = DateTime::parse_from_rfc3339(timestamp_str)
+            .map_err(|error| PoSQLTimeError::ParsingError(error.to_string()))?;
+        let nanos = datetime.timestamp_subsec_nanos();
+        let unit = if nanos == 0 {
+            PoSQLTimeUnit::Second
+        } else if nanos % 1_000_000 == 0 {
+            PoSQLTimeUnit::Millisecond
+        } else if nanos % 1_000 == 0 {
+            PoSQLTimeUnit::Microsecond
+        } else {
+            PoSQLTimeUnit::Nanosecond
+        };
+        let units_per_second = 10_i64.pow(unit.scale() as u32);
+        let nanos_per_unit = 1_000_000_000 / units_per_second;
+        let timestamp = datetime
+            .timestamp()
+            .checked_mul(units_per_second)
+            .and_then(|timestamp| timestamp.checked_add(i64::from(nanos) / nanos_per_unit))
+            .ok_or(PoSQLTimeError::OutOfRange)?;
+        let timezone = PoSQLTimeZone::from_offset(datetime.offset().local_minus_utc());
+        Ok(PoSQLTimestamp::new(timestamp, unit, timezone))
+    }
+}
+
+/// Parses a date, e.g. `2024-01-01`, into the number of days since the Unix epoch.
+pub fn parse_date(date_str: &str) -> Result<i32, PoSQLTimeError> {
+    let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .map_err(|error| PoSQLTimeError::ParsingError(error.to_string()))?;
+    // The default date is the Unix epoch.
+    let days = date.signed_duration_since(NaiveDate::default()).num_days();
+    i32::try_from(days).map_err(|_| PoSQLTimeError::OutOfRange)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_parse_timestamps_with_the_coarsest_exact_unit() {
+        let cases = [
+            ("1970-01-01T00:00:00Z", 0, PoSQLTimeUnit::Second),
+            ("2024-01-01T00:00:00Z", 1_704_067_200, PoSQLTimeUnit::Second),
+            (
+                "2024-01-01T00:00:00.5Z",
+                1_704_067_200_500,
+                PoSQLTimeUnit::Millisecond,
+            ),
+            (
+                "2024-01-01T00:00:00.000001Z",
+                1_704_067_200_000_001,
+                PoSQLTimeUnit::Microsecond,
+            ),
+            (
+                "2024-01-01T00:00:00.000000001Z",
+                1_704_067_200_000_000_001,
+                PoSQLTimeUnit::Nanosecond,
+            ),
+            ("1969-12-31T23:59:59Z", -1, PoSQLTimeUnit::Second),
+        ];
+        for (timestamp_str, timestamp, unit) in cases {
+            assert_eq!(
+                timestamp_str.parse::<PoSQLTimestamp>().unwrap(),
+                PoSQLTimestamp::new(timestamp, unit, PoSQLTimeZone::Utc)
+            );
+        }
+    }
+
+    #[test]
+    fn we_can_parse_timestamps_with_an_offset() {
+        let timestamp: PoSQLTimestamp = "2024-01-01T01:00:00+01:00".parse().unwrap();
+        assert_eq!(timestamp.timestamp(), 1_704_067_200);
+        assert_eq!(timestamp.timezone(), PoSQLTimeZone::FixedOffset(3600));
+        assert_eq!(timestamp.timezone().to_string(), "+01:00");
+    }
+
+    #[test]
+    fn we_cannot_parse_invalid_or_out_of_range_timestamps() {
+        assert!(matches!(
+            "2024-01-01".parse::<PoSQLTimestamp>(),
+            Err(PoSQLTimeError::ParsingError(_))
+        ));
+        assert!(matches!(
+            "2024-13-01T00:00:00Z".parse::<PoSQLTimestamp>(),
+            Err(PoSQLTimeError::ParsingError(_))
+        ));
+        assert_eq!(
+            "2300-01-01T00:00:00.000000001Z".parse::<PoSQLTimestamp>(),
+            Err(PoSQLTimeError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn we_can_parse_and_display_time_zones() {
+        assert_eq!("UTC".parse(), Ok(PoSQLTimeZone::Utc));
+        assert_eq!("+00:00".parse(), Ok(PoSQLTimeZone::Utc));
+        assert_eq!("-05:30".parse(), Ok(PoSQLTimeZone::FixedOffset(-19800)));
+        assert_eq!(PoSQLTimeZone::FixedOffset(-19800).to_string(), "-05:30");
+        assert_eq!(PoSQLTimeZone::Utc.to_string(), "+00:00");
+        for invalid in ["America/New_York", "+24:00", "05:00", "+05"] {
+            assert!(invalid.parse::<PoSQLTimeZone>().is_err());
+        }
+    }
+
+    #[test]
+    fn we_can_parse_dates() {
+        assert_eq!(parse_date("1970-01-01"), Ok(0));
+        assert_eq!(parse_date("2024-01-01"), Ok(19_723));
+        assert_eq!(parse_date("1969-12-31"), Ok(-1));
+        assert!(parse_date("2024-02-30").is_err());
+        assert!(parse_date("2024-01-01T00:00:00Z").is_err());
+    }
+}
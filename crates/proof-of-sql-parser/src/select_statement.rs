@@ -1,4 +1,7 @@
-use super::intermediate_ast::{OrderBy, SetExpression, Slice, TableExpression};
+use super::intermediate_ast::{
+    CommonTableExpression, Expression, OrderBy, SetExpression, Slice, TableExpression,
+    WindowFunction,
+};
 use crate::{sql::SelectStatementParser, Identifier, ParseError, ParseResult, ResourceId};
 use serde::{Deserialize, Serialize};
 use std::{fmt, ops::Deref, str::FromStr};
@@ -6,6 +9,9 @@ use std::{fmt, ops::Deref, str::FromStr};
 /// Representation of a select statement, that is, the only type of queries allowed.
 #[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct SelectStatement {
+    /// the named queries of a `WITH` clause, which later ones and `expr` may select from
+    pub ctes: Vec<CommonTableExpression>,
+
     /// the query expression
     pub expr: Box<SetExpression>,
 
@@ -21,8 +27,8 @@ impl fmt::Debug for SelectStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "SelectStatement \n[{:#?},\n{:#?},\n{:#?}\n]",
-            self.expr, self.order_by, self.slice
+            "SelectStatement \n[{:#?},\n{:#?},\n{:#?},\n{:#?}\n]",
+            self.ctes, self.expr, self.order_by, self.slice
         )
     }
 }
@@ -39,19 +45,108 @@ impl SelectStatement {
     /// as this would imply the caller to always know beforehand the referenced
     /// schemas.
     ///
+    /// Unqualified references to a common table expression are not table references
+    /// themselves, but the tables its query references are.
+    ///
     /// Return:
     /// - The vector with all tables referenced by the intermediate ast, encoded as resource ids.
     pub fn get_table_references(&self, default_schema: Identifier) -> Vec<ResourceId> {
-        let set_expression: &SetExpression = &(self.expr);
-
-        match set_expression {
-            SetExpression::Query {
-                result_exprs: _,
-                from,
-                where_expr: _,
-                group_by: _,
-            } => convert_table_expr_to_resource_id_vector(&from[..], default_schema),
+        let mut tables = Vec::new();
+        let mut ctes = Vec::with_capacity(self.ctes.len());
+        for cte in &self.ctes {
+            push_set_expression_table_references(&mut tables, &cte.query, default_schema, &ctes);
+            ctes.push(cte.name);
+        }
+        push_set_expression_table_references(&mut tables, &self.expr, default_schema, &ctes);
+        tables
+    }
+}
+
+fn push_set_expression_table_references(
+    tables: &mut Vec<ResourceId>,
+    set_expression: &SetExpression,
+    default_schema: Identifier,
+    ctes: &[Identifier],
+) {
+    match set_expression {
+        SetExpression::Query {
+            result_exprs: _,
+            from,
+            where_expr,
+            group_by: _,
+            having: _,
+            distinct: _,
+        } => {
+            tables.extend(convert_table_expr_to_resource_id_vector(
+                &from[..],
+                default_schema,
+                ctes,
+            ));
+            if let Some(where_expr) = where_expr {
+                push_expression_table_references(tables, where_expr, default_schema, ctes);
+            }
+        }
+        SetExpression::Union { left, right, .. } => {
+            push_set_expression_table_references(tables, left, default_schema, ctes);
+            push_set_expression_table_references(tables, right, default_schema, ctes);
+        }
+    }
+}
+
+/// Pushes the tables referenced by the subqueries of an expression
+fn push_expression_table_references(
+    tables: &mut Vec<ResourceId>,
+    expr: &Expression,
+    default_schema: Identifier,
+    ctes: &[Identifier],
+) {
+    match expr {
+        Expression::InSubquery { expr, subquery } => {
+            push_expression_table_references(tables, expr, default_schema, ctes);
+            push_set_expression_table_references(tables, subquery, default_schema, ctes);
+        }
+        Expression::Exists(subquery) | Expression::Subquery(subquery) => {
+            push_set_expression_table_references(tables, subquery, default_schema, ctes);
+        }
+        Expression::Unary { expr, .. }
+        | Expression::InList { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::Aggregation { expr, .. } => {
+            push_expression_table_references(tables, expr, default_schema, ctes);
+        }
+        Expression::Binary { left, right, .. } => {
+            push_expression_table_references(tables, left, default_schema, ctes);
+            push_expression_table_references(tables, right, default_schema, ctes);
+        }
+        Expression::Between { expr, low, high } => {
+            for expr in [expr, low, high] {
+                push_expression_table_references(tables, expr, default_schema, ctes);
+            }
+        }
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => {
+            for (when, then) in when_then {
+                push_expression_table_references(tables, when, default_schema, ctes);
+                push_expression_table_references(tables, then, default_schema, ctes);
+            }
+            if let Some(else_expr) = else_expr {
+                push_expression_table_references(tables, else_expr, default_schema, ctes);
+            }
+        }
+        Expression::Window {
+            function: WindowFunction::Sum(expr),
+            ..
+        } => {
+            push_expression_table_references(tables, expr, default_schema, ctes);
         }
+        Expression::Window { .. }
+        | Expression::Literal(_)
+        | Expression::Column(_)
+        | Expression::QualifiedColumn(_)
+        | Expression::Wildcard => {}
     }
 }
 
@@ -68,25 +163,42 @@ impl FromStr for SelectStatement {
 fn convert_table_expr_to_resource_id_vector(
     table_expressions: &[Box<TableExpression>],
     default_schema: Identifier,
+    ctes: &[Identifier],
 ) -> Vec<ResourceId> {
     let mut tables = Vec::new();
 
     for table_expression in table_expressions.iter() {
-        let table_ref: &TableExpression = table_expression.deref();
+        push_table_references(&mut tables, table_expression.deref(), default_schema, ctes);
+    }
 
-        match table_ref {
-            TableExpression::Named { table, schema } => {
-                let schema = schema
-                    .as_ref()
-                    .map(|schema| schema.as_str())
-                    .unwrap_or_else(|| default_schema.name());
+    tables
+}
 
-                tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
-            }
+fn push_table_references(
+    tables: &mut Vec<ResourceId>,
+    table_ref: &TableExpression,
+    default_schema: Identifier,
+    ctes: &[Identifier],
+) {
+    match table_ref {
+        TableExpression::Named {
+            table,
+            schema: None,
+            ..
+        } if ctes.contains(table) => {}
+        TableExpression::Named { table, schema, .. } => {
+            let schema = schema
+                .as_ref()
+                .map(|schema| schema.as_str())
+                .unwrap_or_else(|| default_schema.name());
+
+            tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
+        }
+        TableExpression::Join { left, right, .. } => {
+            push_table_references(tables, left, default_schema, ctes);
+            push_table_references(tables, right, default_schema, ctes);
         }
     }
-
-    tables
 }
 
 #[cfg(test)]
@@ -129,4 +241,86 @@ mod tests {
 
         assert_eq!(ref_tables, [ResourceId::try_new("schema", "tab").unwrap()]);
     }
+
+    #[test]
+    fn we_can_get_the_table_references_of_a_join() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM TAB JOIN SCHEMA.DIM ON TAB.K = DIM.K")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "dim").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_table_references_of_a_union() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM TAB UNION ALL SELECT A FROM SCHEMA.OTHER UNION SELECT A FROM TAB")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "other").unwrap(),
+                ResourceId::try_new("eth", "tab").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_table_references_of_subqueries() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM TAB WHERE A IN (SELECT B FROM SCHEMA.OTHER) AND EXISTS (SELECT C FROM DIM WHERE C > (SELECT MAX(D) FROM LAST))")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "other").unwrap(),
+                ResourceId::try_new("eth", "dim").unwrap(),
+                ResourceId::try_new("eth", "last").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_table_references_of_common_table_expressions() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("WITH RECENT AS (SELECT A FROM TAB), TOP AS (SELECT A FROM RECENT JOIN SCHEMA.RECENT ON RECENT.A = RECENT.B) SELECT A FROM TOP WHERE A IN (SELECT A FROM RECENT)")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "recent").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn a_table_named_like_a_later_common_table_expression_is_a_table_reference() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("WITH A AS (SELECT X FROM B), B AS (SELECT X FROM A) SELECT X FROM B")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(ref_tables, [ResourceId::try_new("eth", "b").unwrap()]);
+    }
 }
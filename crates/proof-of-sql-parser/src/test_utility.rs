@@ -1,4 +1,4 @@
-use crate::{intermediate_ast::*, Identifier, SelectStatement};
+use crate::{intermediate_ast::*, SelectStatement};
 
 pub fn equal(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     Box::new(Expression::Binary {
@@ -31,6 +31,65 @@ pub fn not(expr: Box<Expression>) -> Box<Expression> {
     })
 }
 
+pub fn is_null(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::IsNull,
+        expr,
+    })
+}
+
+pub fn is_not_null(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::IsNotNull,
+        expr,
+    })
+}
+
+pub fn in_list(expr: Box<Expression>, list: Vec<Literal>) -> Box<Expression> {
+    Box::new(Expression::InList { expr, list })
+}
+
+pub fn in_subquery(expr: Box<Expression>, subquery: Box<SetExpression>) -> Box<Expression> {
+    Box::new(Expression::InSubquery { expr, subquery })
+}
+
+pub fn exists(subquery: Box<SetExpression>) -> Box<Expression> {
+    Box::new(Expression::Exists(subquery))
+}
+
+pub fn subquery(subquery: Box<SetExpression>) -> Box<Expression> {
+    Box::new(Expression::Subquery(subquery))
+}
+
+pub fn between(
+    expr: Box<Expression>,
+    low: Box<Expression>,
+    high: Box<Expression>,
+) -> Box<Expression> {
+    Box::new(Expression::Between { expr, low, high })
+}
+
+pub fn like(expr: Box<Expression>, pattern: &str) -> Box<Expression> {
+    Box::new(Expression::Like {
+        expr,
+        pattern: pattern.to_string(),
+    })
+}
+
+pub fn case(
+    when_then: Vec<(Box<Expression>, Box<Expression>)>,
+    else_expr: Option<Box<Expression>>,
+) -> Box<Expression> {
+    Box::new(Expression::Case {
+        when_then,
+        else_expr,
+    })
+}
+
+pub fn cast(expr: Box<Expression>, data_type: DataType) -> Box<Expression> {
+    Box::new(Expression::Cast { expr, data_type })
+}
+
 pub fn and(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     Box::new(Expression::Binary {
         op: BinaryOperator::And,
@@ -51,6 +110,55 @@ pub fn tab(schema: Option<&str>, name: &str) -> Box<TableExpression> {
     Box::new(TableExpression::Named {
         table: name.parse().unwrap(),
         schema: schema.map(|schema| schema.parse().unwrap()),
+        alias: None,
+    })
+}
+
+pub fn aliased_tab(schema: Option<&str>, name: &str, alias: &str) -> Box<TableExpression> {
+    Box::new(TableExpression::Named {
+        table: name.parse().unwrap(),
+        schema: schema.map(|schema| schema.parse().unwrap()),
+        alias: Some(alias.parse().unwrap()),
+    })
+}
+
+pub fn qualified_col(name: &str) -> QualifiedColumn {
+    let mut parts = name.rsplit('.').map(|part| part.parse().unwrap());
+    let column = parts.next().unwrap();
+    let table = parts.next().unwrap();
+    let schema = parts.next();
+    QualifiedColumn {
+        schema,
+        table,
+        column,
+    }
+}
+
+pub fn join_col(name: &str) -> JoinColumn {
+    if name.contains('.') {
+        JoinColumn::Qualified(qualified_col(name))
+    } else {
+        JoinColumn::Column(name.parse().unwrap())
+    }
+}
+
+pub fn qcol(name: &str) -> Box<Expression> {
+    Box::new(Expression::QualifiedColumn(qualified_col(name)))
+}
+
+pub fn join(
+    left: Box<TableExpression>,
+    right: Box<TableExpression>,
+    left_column: &str,
+    right_column: &str,
+) -> Box<TableExpression> {
+    Box::new(TableExpression::Join {
+        left,
+        right,
+        on: Box::new(JoinCondition {
+            left: join_col(left_column),
+            right: join_col(right_column),
+        }),
     })
 }
 
@@ -109,6 +217,18 @@ pub fn sum_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
     })
 }
 
+pub fn window(
+    function: WindowFunction,
+    partition_by: &[&str],
+    order_by: Vec<OrderBy>,
+) -> Box<Expression> {
+    Box::new(Expression::Window {
+        function,
+        partition_by: partition_by.iter().map(|id| id.parse().unwrap()).collect(),
+        order_by,
+    })
+}
+
 pub fn count_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
     SelectResultExpr::AliasedResultExpr(AliasedResultExpr {
         expr: Expression::Aggregation {
@@ -120,6 +240,28 @@ pub fn count_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
     })
 }
 
+pub fn count_distinct_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
+    SelectResultExpr::AliasedResultExpr(AliasedResultExpr {
+        expr: Expression::Aggregation {
+            op: AggregationOperator::CountDistinct,
+            expr,
+        }
+        .into(),
+        alias: alias.parse().unwrap(),
+    })
+}
+
+pub fn avg_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
+    SelectResultExpr::AliasedResultExpr(AliasedResultExpr {
+        expr: Expression::Aggregation {
+            op: AggregationOperator::Avg,
+            expr,
+        }
+        .into(),
+        alias: alias.parse().unwrap(),
+    })
+}
+
 pub fn count_all_res(alias: &str) -> SelectResultExpr {
     SelectResultExpr::AliasedResultExpr(AliasedResultExpr {
         expr: Expression::Aggregation {
@@ -131,30 +273,68 @@ pub fn count_all_res(alias: &str) -> SelectResultExpr {
     })
 }
 
+#[allow(clippy::vec_box)]
 pub fn query(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
     where_expr: Box<Expression>,
-    group_by: Vec<Identifier>,
+    group_by: Vec<Box<Expression>>,
 ) -> Box<SetExpression> {
     Box::new(SetExpression::Query {
         result_exprs,
         from: vec![tab],
         where_expr: Some(where_expr),
         group_by,
+        having: None,
+        distinct: false,
     })
 }
 
+#[allow(clippy::vec_box)]
 pub fn query_all(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
-    group_by: Vec<Identifier>,
+    group_by: Vec<Box<Expression>>,
 ) -> Box<SetExpression> {
     Box::new(SetExpression::Query {
         result_exprs,
         from: vec![tab],
         where_expr: None,
         group_by,
+        having: None,
+        distinct: false,
+    })
+}
+
+pub fn distinct(mut expr: Box<SetExpression>) -> Box<SetExpression> {
+    let SetExpression::Query { distinct, .. } = expr.as_mut() else {
+        panic!("only queries can be distinct");
+    };
+    *distinct = true;
+    expr
+}
+
+pub fn having(mut expr: Box<SetExpression>, condition: Box<Expression>) -> Box<SetExpression> {
+    let SetExpression::Query { having, .. } = expr.as_mut() else {
+        panic!("only queries can have a HAVING clause");
+    };
+    *having = Some(condition);
+    expr
+}
+
+pub fn union(left: Box<SetExpression>, right: Box<SetExpression>) -> Box<SetExpression> {
+    Box::new(SetExpression::Union {
+        left,
+        right,
+        all: false,
+    })
+}
+
+pub fn union_all(left: Box<SetExpression>, right: Box<SetExpression>) -> Box<SetExpression> {
+    Box::new(SetExpression::Union {
+        left,
+        right,
+        all: true,
     })
 }
 
@@ -164,12 +344,24 @@ pub fn select(
     slice: Option<Slice>,
 ) -> SelectStatement {
     SelectStatement {
+        ctes: vec![],
         expr,
         order_by,
         slice,
     }
 }
 
+pub fn with(ctes: Vec<CommonTableExpression>, statement: SelectStatement) -> SelectStatement {
+    SelectStatement { ctes, ..statement }
+}
+
+pub fn cte(name: &str, query: Box<SetExpression>) -> CommonTableExpression {
+    CommonTableExpression {
+        name: name.parse().unwrap(),
+        query,
+    }
+}
+
 pub fn order(id: &str, direction: OrderByDirection) -> Vec<OrderBy> {
     vec![OrderBy {
         expr: id.parse().unwrap(),
@@ -194,6 +386,7 @@ pub fn slice(number_rows: u64, offset_value: i64) -> Option<Slice> {
     })
 }
 
-pub fn group_by(ids: &[&str]) -> Vec<Identifier> {
-    ids.iter().map(|id| id.parse().unwrap()).collect()
+#[allow(clippy::vec_box)]
+pub fn group_by(ids: &[&str]) -> Vec<Box<Expression>> {
+    ids.iter().map(|id| col(id)).collect()
 }
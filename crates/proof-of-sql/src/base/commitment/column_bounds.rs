@@ -207,6 +207,10 @@ pub enum ColumnBounds {
     BigInt(Bounds<i64>),
     /// The bounds of an Int128 column.
     Int128(Bounds<i128>),
+    /// The bounds of a TimeStamp column, in the units of the column.
+    TimeStamp(Bounds<i64>),
+    /// The bounds of a Date column.
+    Date(Bounds<i32>),
 }
 
 impl ColumnBounds {
@@ -219,6 +223,10 @@ impl ColumnBounds {
             CommittableColumn::Int(ints) => ColumnBounds::Int(Bounds::from_iter(*ints)),
             CommittableColumn::BigInt(ints) => ColumnBounds::BigInt(Bounds::from_iter(*ints)),
             CommittableColumn::Int128(ints) => ColumnBounds::Int128(Bounds::from_iter(*ints)),
+            CommittableColumn::TimeStamp(_, _, times) => {
+                ColumnBounds::TimeStamp(Bounds::from_iter(*times))
+            }
+            CommittableColumn::Date(dates) => ColumnBounds::Date(Bounds::from_iter(*dates)),
             CommittableColumn::Boolean(_)
             | CommittableColumn::Decimal75(_, _, _)
             | CommittableColumn::Scalar(_)
@@ -244,6 +252,12 @@ impl ColumnBounds {
             (ColumnBounds::Int128(bounds_a), ColumnBounds::Int128(bounds_b)) => {
                 Ok(ColumnBounds::Int128(bounds_a.union(bounds_b)))
             }
+            (ColumnBounds::TimeStamp(bounds_a), ColumnBounds::TimeStamp(bounds_b)) => {
+                Ok(ColumnBounds::TimeStamp(bounds_a.union(bounds_b)))
+            }
+            (ColumnBounds::Date(bounds_a), ColumnBounds::Date(bounds_b)) => {
+                Ok(ColumnBounds::Date(bounds_a.union(bounds_b)))
+            }
             (bounds_a, bounds_b) => {
                 Err(ColumnBoundsMismatch(Box::new(bounds_a), Box::new(bounds_b)))
             }
@@ -269,6 +283,12 @@ impl ColumnBounds {
             (ColumnBounds::Int128(bounds_a), ColumnBounds::Int128(bounds_b)) => {
                 Ok(ColumnBounds::Int128(bounds_a.difference(bounds_b)))
             }
+            (ColumnBounds::TimeStamp(bounds_a), ColumnBounds::TimeStamp(bounds_b)) => {
+                Ok(ColumnBounds::TimeStamp(bounds_a.difference(bounds_b)))
+            }
+            (ColumnBounds::Date(bounds_a), ColumnBounds::Date(bounds_b)) => {
+                Ok(ColumnBounds::Date(bounds_a.difference(bounds_b)))
+            }
 
             (_, _) => Err(ColumnBoundsMismatch(Box::new(self), Box::new(other))),
         }
@@ -280,6 +300,7 @@ mod tests {
     use super::*;
     use crate::base::{database::OwnedColumn, math::decimal::Precision, scalar::Curve25519Scalar};
     use itertools::Itertools;
+    use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 
     #[test]
     fn we_can_construct_bounds_by_method() {
@@ -507,6 +528,32 @@ mod tests {
             ColumnBounds::Int128(Bounds::Sharp(BoundsInner { min: 0, max: 3 }))
         );
 
+        let timestamp_column = OwnedColumn::<Curve25519Scalar>::TimeStamp(
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            [1_704_067_200, -1, 0].to_vec(),
+        );
+        let committable_timestamp_column = CommittableColumn::from(&timestamp_column);
+        let timestamp_column_bounds = ColumnBounds::from_column(&committable_timestamp_column);
+        assert_eq!(
+            timestamp_column_bounds,
+            ColumnBounds::TimeStamp(Bounds::Sharp(BoundsInner {
+                min: -1,
+                max: 1_704_067_200
+            }))
+        );
+
+        let date_column = OwnedColumn::<Curve25519Scalar>::Date([19_723, 0, -1].to_vec());
+        let committable_date_column = CommittableColumn::from(&date_column);
+        let date_column_bounds = ColumnBounds::from_column(&committable_date_column);
+        assert_eq!(
+            date_column_bounds,
+            ColumnBounds::Date(Bounds::Sharp(BoundsInner {
+                min: -1,
+                max: 19_723
+            }))
+        );
+
         let decimal75_column = OwnedColumn::<Curve25519Scalar>::Decimal75(
             Precision::new(1).unwrap(),
             0,
@@ -561,6 +608,17 @@ mod tests {
             int128_a.try_union(int128_b).unwrap(),
             ColumnBounds::Int128(Bounds::Bounded(BoundsInner { min: 1, max: 6 }))
         );
+
+        let timestamp_a = ColumnBounds::TimeStamp(Bounds::Sharp(BoundsInner { min: 1, max: 3 }));
+        let timestamp_b = ColumnBounds::TimeStamp(Bounds::Sharp(BoundsInner { min: 4, max: 6 }));
+        assert_eq!(
+            timestamp_a.try_union(timestamp_b).unwrap(),
+            ColumnBounds::TimeStamp(Bounds::Sharp(BoundsInner { min: 1, max: 6 }))
+        );
+
+        let date_a = ColumnBounds::Date(Bounds::Sharp(BoundsInner { min: 1, max: 3 }));
+        let date_b = ColumnBounds::Date(Bounds::Empty);
+        assert_eq!(date_a.try_union(date_b).unwrap(), date_a);
     }
 
     #[test]
@@ -570,6 +628,8 @@ mod tests {
         let int = ColumnBounds::Int(Bounds::Sharp(BoundsInner { min: -10, max: 10 }));
         let bigint = ColumnBounds::BigInt(Bounds::Sharp(BoundsInner { min: 1, max: 3 }));
         let int128 = ColumnBounds::Int128(Bounds::Sharp(BoundsInner { min: 4, max: 6 }));
+        let timestamp = ColumnBounds::TimeStamp(Bounds::Sharp(BoundsInner { min: 1, max: 3 }));
+        let date = ColumnBounds::Date(Bounds::Sharp(BoundsInner { min: 1, max: 3 }));
 
         let bounds = [
             (no_order, "NoOrder"),
@@ -577,6 +637,8 @@ mod tests {
             (int, "Int"),
             (bigint, "BigInt"),
             (int128, "Int128"),
+            (timestamp, "TimeStamp"),
+            (date, "Date"),
         ];
 
         for ((bound_a, name_a), (bound_b, name_b)) in bounds.iter().tuple_combinations() {
@@ -618,6 +680,17 @@ mod tests {
             int128_a.try_difference(int128_b).unwrap(),
             ColumnBounds::Int128(Bounds::Bounded(BoundsInner { min: 1, max: 4 }))
         );
+
+        let timestamp_a = ColumnBounds::TimeStamp(Bounds::Sharp(BoundsInner { min: 1, max: 3 }));
+        let timestamp_b = ColumnBounds::TimeStamp(Bounds::Sharp(BoundsInner { min: 4, max: 6 }));
+        assert_eq!(
+            timestamp_a.try_difference(timestamp_b).unwrap(),
+            timestamp_a
+        );
+
+        let date_a = ColumnBounds::Date(Bounds::Sharp(BoundsInner { min: 1, max: 3 }));
+        let date_b = ColumnBounds::Date(Bounds::Empty);
+        assert_eq!(date_a.try_difference(date_b).unwrap(), date_a);
     }
 
     #[test]
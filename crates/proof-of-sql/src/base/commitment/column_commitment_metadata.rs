@@ -40,6 +40,8 @@ impl ColumnCommitmentMetadata {
             | (ColumnType::Int, ColumnBounds::Int(_))
             | (ColumnType::BigInt, ColumnBounds::BigInt(_))
             | (ColumnType::Int128, ColumnBounds::Int128(_))
+            | (ColumnType::TimeStamp(..), ColumnBounds::TimeStamp(_))
+            | (ColumnType::Date, ColumnBounds::Date(_))
             | (
                 ColumnType::Boolean
                 | ColumnType::VarChar
@@ -76,6 +78,14 @@ impl ColumnCommitmentMetadata {
                 BoundsInner::try_new(i128::MIN, i128::MAX)
                     .expect("i128::MIN and i128::MAX are valid bounds for Int128"),
             )),
+            ColumnType::TimeStamp(..) => ColumnBounds::TimeStamp(super::Bounds::Bounded(
+                BoundsInner::try_new(i64::MIN, i64::MAX)
+                    .expect("i64::MIN and i64::MAX are valid bounds for TimeStamp"),
+            )),
+            ColumnType::Date => ColumnBounds::Date(super::Bounds::Bounded(
+                BoundsInner::try_new(i32::MIN, i32::MAX)
+                    .expect("i32::MIN and i32::MAX are valid bounds for Date"),
+            )),
             _ => ColumnBounds::NoOrder,
         };
         Self::try_new(column_type, bounds).expect("default bounds for column type are valid")
@@ -163,6 +173,7 @@ mod tests {
         commitment::column_bounds::Bounds, database::OwnedColumn, math::decimal::Precision,
         scalar::Curve25519Scalar,
     };
+    use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 
     #[test]
     fn we_can_construct_metadata() {
@@ -325,6 +336,18 @@ mod tests {
             ),
             Err(InvalidColumnCommitmentMetadata::TypeBoundsMismatch(..))
         ));
+
+        assert!(matches!(
+            ColumnCommitmentMetadata::try_new(
+                ColumnType::TimeStamp(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc),
+                ColumnBounds::BigInt(Bounds::Empty)
+            ),
+            Err(InvalidColumnCommitmentMetadata::TypeBoundsMismatch(..))
+        ));
+        assert!(matches!(
+            ColumnCommitmentMetadata::try_new(ColumnType::Date, ColumnBounds::Int(Bounds::Empty)),
+            Err(InvalidColumnCommitmentMetadata::TypeBoundsMismatch(..))
+        ));
     }
 
     #[test]
@@ -401,6 +424,29 @@ mod tests {
             &ColumnBounds::Int128(Bounds::Empty)
         );
 
+        let timestamp_column = OwnedColumn::<Curve25519Scalar>::TimeStamp(
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::FixedOffset(3600),
+            [1_704_067_200_000, 0].to_vec(),
+        );
+        let committable_timestamp_column = CommittableColumn::from(&timestamp_column);
+        let timestamp_metadata =
+            ColumnCommitmentMetadata::from_column(&committable_timestamp_column);
+        assert_eq!(
+            timestamp_metadata.column_type(),
+            &ColumnType::TimeStamp(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::FixedOffset(3600))
+        );
+        assert_eq!(
+            timestamp_metadata.bounds(),
+            &ColumnBounds::TimeStamp(Bounds::sharp(0, 1_704_067_200_000).unwrap())
+        );
+
+        let date_column = OwnedColumn::<Curve25519Scalar>::Date([].to_vec());
+        let committable_date_column = CommittableColumn::from(&date_column);
+        let date_metadata = ColumnCommitmentMetadata::from_column(&committable_date_column);
+        assert_eq!(date_metadata.column_type(), &ColumnType::Date);
+        assert_eq!(date_metadata.bounds(), &ColumnBounds::Date(Bounds::Empty));
+
         let scalar_column =
             OwnedColumn::Scalar([1, 2, 3, 4, 5].map(Curve25519Scalar::from).to_vec());
         let committable_scalar_column = CommittableColumn::from(&scalar_column);
@@ -6,6 +6,7 @@ use crate::base::{
 };
 #[cfg(feature = "blitzar")]
 use blitzar::sequence::Sequence;
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 
 /// Column data in "committable form".
 ///
@@ -37,6 +38,10 @@ pub enum CommittableColumn<'a> {
     Scalar(Vec<[u64; 4]>),
     /// Column of limbs for committing to scalars, hashed from a VarChar column.
     VarChar(Vec<[u64; 4]>),
+    /// Borrowed Timestamp column, mapped to `i64`.
+    TimeStamp(PoSQLTimeUnit, PoSQLTimeZone, &'a [i64]),
+    /// Borrowed Date column, mapped to `i32`.
+    Date(&'a [i32]),
 }
 
 impl<'a> CommittableColumn<'a> {
@@ -51,6 +56,8 @@ impl<'a> CommittableColumn<'a> {
             CommittableColumn::Scalar(col) => col.len(),
             CommittableColumn::VarChar(col) => col.len(),
             CommittableColumn::Boolean(col) => col.len(),
+            CommittableColumn::TimeStamp(_, _, col) => col.len(),
+            CommittableColumn::Date(col) => col.len(),
         }
     }
 
@@ -78,6 +85,10 @@ impl<'a> From<&CommittableColumn<'a>> for ColumnType {
             CommittableColumn::Scalar(_) => ColumnType::Scalar,
             CommittableColumn::VarChar(_) => ColumnType::VarChar,
             CommittableColumn::Boolean(_) => ColumnType::Boolean,
+            CommittableColumn::TimeStamp(unit, timezone, _) => {
+                ColumnType::TimeStamp(*unit, *timezone)
+            }
+            CommittableColumn::Date(_) => ColumnType::Date,
         }
     }
 }
@@ -99,6 +110,10 @@ impl<'a, S: Scalar> From<&Column<'a, S>> for CommittableColumn<'a> {
                 let as_limbs: Vec<_> = scalars.iter().map(RefInto::<[u64; 4]>::ref_into).collect();
                 CommittableColumn::VarChar(as_limbs)
             }
+            Column::TimeStamp(unit, timezone, times) => {
+                CommittableColumn::TimeStamp(*unit, *timezone, times)
+            }
+            Column::Date(dates) => CommittableColumn::Date(dates),
         }
     }
 }
@@ -128,6 +143,10 @@ impl<'a, S: Scalar> From<&'a OwnedColumn<S>> for CommittableColumn<'a> {
                     .map(Into::<[u64; 4]>::into)
                     .collect(),
             ),
+            OwnedColumn::TimeStamp(unit, timezone, times) => {
+                CommittableColumn::TimeStamp(*unit, *timezone, times)
+            }
+            OwnedColumn::Date(dates) => CommittableColumn::Date(dates),
         }
     }
 }
@@ -175,6 +194,8 @@ impl<'a, 'b> From<&'a CommittableColumn<'b>> for Sequence<'a> {
             CommittableColumn::Scalar(limbs) => Sequence::from(limbs),
             CommittableColumn::VarChar(limbs) => Sequence::from(limbs),
             CommittableColumn::Boolean(bools) => Sequence::from(*bools),
+            CommittableColumn::TimeStamp(_, _, times) => Sequence::from(*times),
+            CommittableColumn::Date(dates) => Sequence::from(*dates),
         }
     }
 }
@@ -779,4 +800,56 @@ mod tests {
         );
         assert_eq!(commitment_buffer[0], commitment_buffer[1]);
     }
+
+    #[test]
+    fn we_can_get_type_and_length_of_timestamp_and_date_columns() {
+        let owned_column = OwnedColumn::<Curve25519Scalar>::TimeStamp(
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::Utc,
+            vec![1_704_067_200_000, 1_704_067_200_500],
+        );
+        let committable_column = CommittableColumn::from(&owned_column);
+        assert_eq!(
+            committable_column,
+            CommittableColumn::TimeStamp(
+                PoSQLTimeUnit::Millisecond,
+                PoSQLTimeZone::Utc,
+                &[1_704_067_200_000, 1_704_067_200_500]
+            )
+        );
+        assert_eq!(committable_column.len(), 2);
+        assert_eq!(
+            committable_column.column_type(),
+            ColumnType::TimeStamp(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::Utc)
+        );
+
+        let committable_column =
+            CommittableColumn::from(&Column::<Curve25519Scalar>::Date(&[19_723]));
+        assert_eq!(committable_column, CommittableColumn::Date(&[19_723]));
+        assert_eq!(committable_column.len(), 1);
+        assert_eq!(committable_column.column_type(), ColumnType::Date);
+    }
+
+    #[test]
+    fn we_can_commit_to_timestamp_and_date_columns_through_committable_column() {
+        let times = [-1, 0, 1_704_067_200];
+        let dates = [-1, 0, 19_723];
+        let committable_columns = [
+            CommittableColumn::TimeStamp(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, &times),
+            CommittableColumn::Date(&dates),
+        ];
+        let mut commitment_buffer = [CompressedRistretto::default(); 4];
+        compute_curve25519_commitments(
+            &mut commitment_buffer,
+            &[
+                Sequence::from(&committable_columns[0]),
+                Sequence::from(times.as_slice()),
+                Sequence::from(&committable_columns[1]),
+                Sequence::from(dates.as_slice()),
+            ],
+            0,
+        );
+        assert_eq!(commitment_buffer[0], commitment_buffer[1]);
+        assert_eq!(commitment_buffer[2], commitment_buffer[3]);
+    }
 }
@@ -1,7 +1,7 @@
 use super::{TableCommitment, VecCommitmentExt};
 use crate::base::database::{
-    ColumnField, ColumnRef, ColumnType, CommitmentAccessor, MetadataAccessor, SchemaAccessor,
-    TableRef,
+    ordered_column_id, ColumnField, ColumnRef, ColumnType, CommitmentAccessor, MetadataAccessor,
+    SchemaAccessor, TableRef,
 };
 use proof_of_sql_parser::Identifier;
 use std::collections::HashMap;
@@ -117,6 +117,7 @@ where
             .column_commitments()
             .column_metadata()
             .iter()
+            .filter(|(identifier, _)| ordered_column_id(**identifier).is_none())
             .map(|(identifier, column_metadata)| (*identifier, *column_metadata.column_type()))
             .collect()
     }
@@ -4,12 +4,12 @@ use super::{
 };
 use crate::base::{
     database::{
-        ArrayRefExt, ArrowArrayToColumnConversionError, Column, ColumnField, CommitmentAccessor,
-        OwnedTable, TableRef,
+        add_order_keys, split_validity, ArrayRefExt, ArrowArrayToColumnConversionError, Column,
+        ColumnField, CommitmentAccessor, OwnedTable, TableRef,
     },
     scalar::Scalar,
 };
-use arrow::record_batch::RecordBatch;
+use arrow::{array::ArrayRef, record_batch::RecordBatch};
 use bumpalo::Bump;
 use proof_of_sql_parser::{Identifier, ParseError};
 use serde::{Deserialize, Serialize};
@@ -369,8 +369,11 @@ where
         batch: &RecordBatch,
         setup: &Setup<C>,
     ) -> Result<(), AppendRecordBatchTableCommitmentError> {
+        let arrays = split_validity(batch)
+            .map(add_order_keys)
+            .map_err(RecordBatchToColumnsError::from)?;
         match self.try_append_rows(
-            batch_to_columns::<<Decompressed<C> as Commitment>::Scalar>(batch, &Bump::new())?
+            batch_to_columns::<<Decompressed<C> as Commitment>::Scalar>(&arrays, &Bump::new())?
                 .iter()
                 .map(|(a, b)| (a, b)),
             setup,
@@ -403,8 +406,9 @@ where
         offset: usize,
         setup: &Setup<C>,
     ) -> Result<TableCommitment<C>, RecordBatchToColumnsError> {
+        let arrays = split_validity(batch).map(add_order_keys)?;
         match Self::try_from_columns_with_offset(
-            batch_to_columns::<<Decompressed<C> as Commitment>::Scalar>(batch, &Bump::new())?
+            batch_to_columns::<<Decompressed<C> as Commitment>::Scalar>(&arrays, &Bump::new())?
                 .iter()
                 .map(|(a, b)| (a, b)),
             offset,
@@ -421,19 +425,17 @@ where
     }
 }
 
+/// Converts the columns of a record batch, along with the validity columns of its nullable fields
+/// and the order key columns of its `VarChar` fields, into [`Column`]s.
 fn batch_to_columns<'a, S: Scalar + 'a>(
-    batch: &'a RecordBatch,
+    arrays: &'a [(Identifier, ArrayRef)],
     alloc: &'a Bump,
 ) -> Result<Vec<(Identifier, Column<'a, S>)>, RecordBatchToColumnsError> {
-    batch
-        .schema()
-        .fields()
-        .into_iter()
-        .zip(batch.columns())
-        .map(|(field, array)| {
-            let identifier: Identifier = field.name().parse()?;
+    arrays
+        .iter()
+        .map(|(identifier, array)| {
             let column: Column<S> = array.to_column(alloc, &(0..array.len()), None)?;
-            Ok((identifier, column))
+            Ok((*identifier, column))
         })
         .collect()
 }
@@ -461,13 +463,18 @@ mod tests {
     use super::*;
     use crate::{
         base::{
-            database::{owned_table_utility::*, OwnedColumn},
+            database::{order_key, owned_table_utility::*, OwnedColumn},
             scalar::Curve25519Scalar,
         },
         record_batch,
     };
+    use arrow::{
+        array::Int64Array,
+        datatypes::{DataType, Field, Schema},
+    };
     use curve25519_dalek::RistrettoPoint;
     use indexmap::IndexMap;
+    use std::sync::Arc;
 
     #[test]
     #[allow(clippy::reversed_empty_ranges)]
@@ -1225,6 +1232,7 @@ mod tests {
         );
 
         let b_scals = ["1".into(), "2".into(), "3".into()];
+        let b_order_keys = ["1", "2", "3"].map(order_key);
 
         let columns = [
             (
@@ -1235,6 +1243,10 @@ mod tests {
                 &"b".parse().unwrap(),
                 &Column::<Curve25519Scalar>::VarChar((&["1", "2", "3"], &b_scals)),
             ),
+            (
+                &"__order__b".parse().unwrap(),
+                &Column::<Curve25519Scalar>::Int128(&b_order_keys),
+            ),
         ];
 
         let mut expected_commitment =
@@ -1252,6 +1264,7 @@ mod tests {
         );
 
         let b_scals2 = ["4".into(), "5".into(), "6".into()];
+        let b_order_keys2 = ["4", "5", "6"].map(order_key);
 
         let columns2 = [
             (
@@ -1262,6 +1275,60 @@ mod tests {
                 &"b".parse().unwrap(),
                 &Column::<Curve25519Scalar>::VarChar((&["4", "5", "6"], &b_scals2)),
             ),
+            (
+                &"__order__b".parse().unwrap(),
+                &Column::<Curve25519Scalar>::Int128(&b_order_keys2),
+            ),
+        ];
+
+        expected_commitment.try_append_rows(columns2, &()).unwrap();
+        commitment.try_append_record_batch(&batch2, &()).unwrap();
+
+        assert_eq!(commitment, expected_commitment);
+    }
+
+    #[test]
+    fn we_can_create_and_append_table_commitments_with_record_batches_with_nulls() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int64Array::from(vec![Some(1), None, Some(3)]))],
+        )
+        .unwrap();
+
+        let columns = [
+            (
+                &"a".parse().unwrap(),
+                &Column::<Curve25519Scalar>::BigInt(&[1, 0, 3]),
+            ),
+            (
+                &"__valid__a".parse().unwrap(),
+                &Column::<Curve25519Scalar>::Boolean(&[true, false, true]),
+            ),
+        ];
+
+        let mut expected_commitment =
+            TableCommitment::<RistrettoPoint>::try_from_columns_with_offset(columns, 0, &())
+                .unwrap();
+
+        let mut commitment =
+            TableCommitment::<RistrettoPoint>::try_from_record_batch(&batch, &()).unwrap();
+
+        assert_eq!(commitment, expected_commitment);
+
+        // Batches without nulls still commit to the validity of nullable fields.
+        let batch2 =
+            RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(vec![4, 5]))]).unwrap();
+
+        let columns2 = [
+            (
+                &"a".parse().unwrap(),
+                &Column::<Curve25519Scalar>::BigInt(&[4, 5]),
+            ),
+            (
+                &"__valid__a".parse().unwrap(),
+                &Column::<Curve25519Scalar>::Boolean(&[true, true]),
+            ),
         ];
 
         expected_commitment.try_append_rows(columns2, &()).unwrap();
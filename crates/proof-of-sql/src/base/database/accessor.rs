@@ -105,9 +105,22 @@ pub trait SchemaAccessor {
     /// Lookup all the column names and their data types in the specified table
     ///
     /// Return:
-    ///   - The list of column names with their data types
+    ///   - The list of column names with their data types, without the hidden order key columns
+    ///     of `VarChar` columns, which can still be looked up with [`Self::lookup_column`]
     ///
     /// Precondition 1: the table must exist and be tamperproof.
     /// Precondition 2: `table_name` must be lowercase.
     fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)>;
+
+    /// Returns whether the column is known to be strictly increasing in the order of the rows
+    /// of the table, as an ingestion timestamp is.
+    ///
+    /// Plans whose proofs only verify on such a column, like the proven running sum, are only chosen
+    /// when this returns true. It defaults to false, so that such queries are computed in post-processing.
+    ///
+    /// Precondition 1: the table must exist and be tamperproof.
+    /// Precondition 2: `table_ref` and `column_id` must always be lowercase.
+    fn is_strictly_increasing(&self, _table_ref: TableRef, _column_id: Identifier) -> bool {
+        false
+    }
 }
@@ -1,7 +1,7 @@
 use super::scalar_and_i256_conversions::convert_i256_to_scalar;
 use crate::{
     base::{
-        database::Column,
+        database::{Column, ColumnType},
         math::decimal::Precision,
         scalar::{Curve25519Scalar, Scalar},
     },
@@ -9,21 +9,20 @@ use crate::{
 };
 use arrow::{
     array::{
-        Array, ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, Int16Array, Int32Array,
-        Int64Array, StringArray,
+        Array, ArrayRef, BooleanArray, Date32Array, Decimal128Array, Decimal256Array, Int16Array,
+        Int32Array, Int64Array, PrimitiveArray, StringArray, TimestampMicrosecondArray,
+        TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
     },
-    datatypes::{i256, DataType},
+    datatypes::{i256, ArrowPrimitiveType, DataType},
 };
 use bumpalo::Bump;
+use proof_of_sql_parser::posql_time::PoSQLTimeUnit;
 use std::ops::Range;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
 /// Errors caused by conversions between Arrow and owned types.
 pub enum ArrowArrayToColumnConversionError {
-    /// This error occurs when an array contains a non-zero number of null elements
-    #[error("arrow array must not contain nulls")]
-    ArrayContainsNulls,
     /// This error occurs when trying to convert from an unsupported arrow type.
     #[error("unsupported type: attempted conversion from ArrayRef of type {0} to OwnedColumn")]
     UnsupportedType(DataType),
@@ -40,9 +39,9 @@ pub enum ArrowArrayToColumnConversionError {
 
 /// This trait is used to provide utility functions to convert ArrayRefs into proof types (Column, Scalars, etc.)
 pub trait ArrayRefExt {
-    /// Convert an ArrayRef into a Proof of SQL Vec<Scalar>
+    /// Convert an ArrayRef into a Proof of SQL Vec<Scalar>, where every null is mapped to zero.
     ///
-    /// Note: this function must not be called from unsupported arrays or arrays with nulls.
+    /// Note: this function must not be called from unsupported arrays.
     /// It should only be used during testing.
     #[cfg(any(test, feature = "test"))]
     #[cfg(feature = "blitzar")]
@@ -62,7 +61,10 @@ pub trait ArrayRefExt {
     ///    Some types don't require this slice (see Column::BigInt). But for types requiring it,
     ///    `scals` must be provided and have a length equal to `range.len()`.
     ///
-    /// Note: this function must not be called from unsupported or nullable arrays as it will panic.
+    /// Every null is replaced by the default value of the column type. The nulls themselves
+    /// are kept in a separate validity column (see [`super::validity`]).
+    ///
+    /// Note: this function must not be called from unsupported arrays as it will panic.
     fn to_column<'a, S: Scalar>(
         &'a self,
         alloc: &'a Bump,
@@ -77,20 +79,18 @@ impl ArrayRefExt for ArrayRef {
     fn to_curve25519_scalars(
         &self,
     ) -> Result<Vec<Curve25519Scalar>, ArrowArrayToColumnConversionError> {
+        let alloc = Bump::new();
         if self.null_count() != 0 {
-            return Err(ArrowArrayToColumnConversionError::ArrayContainsNulls);
+            return Ok(self
+                .to_column::<Curve25519Scalar>(&alloc, &(0..self.len()), None)?
+                .to_scalar_with_scaling(0));
         }
 
         let result = match self.data_type() {
-            DataType::Boolean => self.as_any().downcast_ref::<BooleanArray>().map(|array| {
-                array
-                    .iter()
-                    .map(|v| {
-                        v.ok_or(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-                            .map(Into::into)
-                    })
-                    .collect()
-            }),
+            DataType::Boolean => self
+                .as_any()
+                .downcast_ref::<BooleanArray>()
+                .map(|array| array.values().iter().map(|v| Ok(v.into())).collect()),
             DataType::Int16 => self
                 .as_any()
                 .downcast_ref::<Int16Array>()
@@ -123,14 +123,14 @@ impl ArrayRefExt for ArrayRef {
                     })
             }
             DataType::Utf8 => self.as_any().downcast_ref::<StringArray>().map(|array| {
-                array
-                    .iter()
-                    .map(|v| {
-                        v.ok_or(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-                            .map(Into::into)
-                    })
+                (0..array.len())
+                    .map(|i| Ok(array.value(i).into()))
                     .collect()
             }),
+            DataType::Timestamp(_, _) | DataType::Date32 => Some(
+                self.to_column::<Curve25519Scalar>(&alloc, &(0..self.len()), None)
+                    .map(|column| column.to_scalar_with_scaling(0)),
+            ),
             _ => None,
         };
 
@@ -156,6 +156,8 @@ impl ArrayRefExt for ArrayRef {
     /// - Decimal256, converts arrow i256 columns into Decimal75(precision, scale) columns.
     /// - For `DataType::Utf8`, it extracts string values and scalar values (if `precomputed_scals`
     ///   is provided) for the specified range and returns a `VarChar` column.
+    /// - For `DataType::Timestamp` and `DataType::Date32`, it slices the array and returns the
+    ///   corresponding `TimeStamp` or `Date` column. Time zones must be offsets (see [ColumnType]).
     ///
     /// # Panics
     /// - When any range is OOB, i.e. indexing 3..6 or 5..5 on array of size 2.
@@ -165,11 +167,6 @@ impl ArrayRefExt for ArrayRef {
         range: &Range<usize>,
         precomputed_scals: Option<&'a [S]>,
     ) -> Result<Column<'a, S>, ArrowArrayToColumnConversionError> {
-        // Start by checking for nulls
-        if self.null_count() != 0 {
-            return Err(ArrowArrayToColumnConversionError::ArrayContainsNulls);
-        }
-
         // Before performing any operations, check if the range is out of bounds
         if range.end > self.len() {
             return Err(ArrowArrayToColumnConversionError::IndexOutOfBounds(
@@ -181,13 +178,9 @@ impl ArrayRefExt for ArrayRef {
         match self.data_type() {
             DataType::Boolean => {
                 if let Some(array) = self.as_any().downcast_ref::<BooleanArray>() {
-                    let boolean_slice = array
-                        .iter()
-                        .skip(range.start)
-                        .take(range.len())
-                        .collect::<Option<Vec<bool>>>()
-                        .ok_or(ArrowArrayToColumnConversionError::ArrayContainsNulls)?;
-                    let values = alloc.alloc_slice_fill_with(range.len(), |i| boolean_slice[i]);
+                    let values = alloc.alloc_slice_fill_with(range.len(), |i| {
+                        array.is_valid(range.start + i) && array.value(range.start + i)
+                    });
                     Ok(Column::Boolean(values))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType(
@@ -197,7 +190,7 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Int16 => {
                 if let Some(array) = self.as_any().downcast_ref::<Int16Array>() {
-                    Ok(Column::SmallInt(&array.values()[range.start..range.end]))
+                    Ok(Column::SmallInt(non_null_values(array, alloc, range)))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType(
                         self.data_type().clone(),
@@ -206,7 +199,7 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Int32 => {
                 if let Some(array) = self.as_any().downcast_ref::<Int32Array>() {
-                    Ok(Column::Int(&array.values()[range.start..range.end]))
+                    Ok(Column::Int(non_null_values(array, alloc, range)))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType(
                         self.data_type().clone(),
@@ -215,7 +208,7 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Int64 => {
                 if let Some(array) = self.as_any().downcast_ref::<Int64Array>() {
-                    Ok(Column::BigInt(&array.values()[range.start..range.end]))
+                    Ok(Column::BigInt(non_null_values(array, alloc, range)))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType(
                         self.data_type().clone(),
@@ -224,7 +217,7 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Decimal128(38, 0) => {
                 if let Some(array) = self.as_any().downcast_ref::<Decimal128Array>() {
-                    Ok(Column::Int128(&array.values()[range.start..range.end]))
+                    Ok(Column::Int128(non_null_values(array, alloc, range)))
                 } else {
                     Err(ArrowArrayToColumnConversionError::UnsupportedType(
                         self.data_type().clone(),
@@ -233,7 +226,7 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Decimal256(precision, scale) if *precision <= 75 => {
                 if let Some(array) = self.as_any().downcast_ref::<Decimal256Array>() {
-                    let i256_slice = &array.values()[range.start..range.end];
+                    let i256_slice = non_null_values(array, alloc, range);
                     let scalars = alloc.alloc_slice_fill_default(i256_slice.len());
                     for (scalar, value) in scalars.iter_mut().zip(i256_slice) {
                         *scalar = convert_i256_to_scalar(value).ok_or(
@@ -253,9 +246,13 @@ impl ArrayRefExt for ArrayRef {
             }
             DataType::Utf8 => {
                 if let Some(array) = self.as_any().downcast_ref::<StringArray>() {
-                    let vals = alloc
-                        .alloc_slice_fill_with(range.end - range.start, |i| -> &'a str {
-                            array.value(range.start + i)
+                    let vals =
+                        alloc.alloc_slice_fill_with(range.end - range.start, |i| -> &'a str {
+                            if array.is_valid(range.start + i) {
+                                array.value(range.start + i)
+                            } else {
+                                ""
+                            }
                         });
 
                     let scals = if let Some(scals) = precomputed_scals {
@@ -271,6 +268,42 @@ impl ArrayRefExt for ArrayRef {
                     ))
                 }
             }
+            DataType::Timestamp(_, _) => {
+                let column = match ColumnType::try_from(self.data_type().clone()) {
+                    Ok(ColumnType::TimeStamp(unit, timezone)) => match unit {
+                        PoSQLTimeUnit::Second => self
+                            .as_any()
+                            .downcast_ref::<TimestampSecondArray>()
+                            .map(|array| non_null_values(array, alloc, range)),
+                        PoSQLTimeUnit::Millisecond => self
+                            .as_any()
+                            .downcast_ref::<TimestampMillisecondArray>()
+                            .map(|array| non_null_values(array, alloc, range)),
+                        PoSQLTimeUnit::Microsecond => self
+                            .as_any()
+                            .downcast_ref::<TimestampMicrosecondArray>()
+                            .map(|array| non_null_values(array, alloc, range)),
+                        PoSQLTimeUnit::Nanosecond => self
+                            .as_any()
+                            .downcast_ref::<TimestampNanosecondArray>()
+                            .map(|array| non_null_values(array, alloc, range)),
+                    }
+                    .map(|values| Column::TimeStamp(unit, timezone, values)),
+                    _ => None,
+                };
+                column.ok_or_else(|| {
+                    ArrowArrayToColumnConversionError::UnsupportedType(self.data_type().clone())
+                })
+            }
+            DataType::Date32 => {
+                if let Some(array) = self.as_any().downcast_ref::<Date32Array>() {
+                    Ok(Column::Date(non_null_values(array, alloc, range)))
+                } else {
+                    Err(ArrowArrayToColumnConversionError::UnsupportedType(
+                        self.data_type().clone(),
+                    ))
+                }
+            }
             data_type => Err(ArrowArrayToColumnConversionError::UnsupportedType(
                 data_type.clone(),
             )),
@@ -278,6 +311,28 @@ impl ArrayRefExt for ArrayRef {
     }
 }
 
+/// Returns the values of the array in the range, where every null is replaced by the default value.
+///
+/// The values are only copied when the array has nulls, since the values behind them are arbitrary.
+fn non_null_values<'a, T: ArrowPrimitiveType>(
+    array: &'a PrimitiveArray<T>,
+    alloc: &'a Bump,
+    range: &Range<usize>,
+) -> &'a [T::Native] {
+    let values = &array.values()[range.start..range.end];
+    if array.null_count() == 0 {
+        values
+    } else {
+        alloc.alloc_slice_fill_with(values.len(), |i| {
+            if array.is_valid(range.start + i) {
+                values[i]
+            } else {
+                T::Native::default()
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "blitzar")]
 mod tests {
@@ -326,10 +381,13 @@ mod tests {
         let alloc = Bump::new();
         let array: ArrayRef = Arc::new(StringArray::from(vec![Some("hello"), None, Some("test")]));
         let result = array.to_column::<Curve25519Scalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        let expected_vals = vec!["hello", "", "test"];
+        let expected_scals: Vec<Curve25519Scalar> =
+            expected_vals.iter().map(|&v| v.into()).collect();
+        assert_eq!(
+            result.unwrap(),
+            Column::VarChar((expected_vals.as_slice(), expected_scals.as_slice()))
+        );
     }
 
     #[test]
@@ -431,10 +489,21 @@ mod tests {
         let array: ArrayRef = Arc::new(builder.finish().with_precision_and_scale(75, 0).unwrap());
 
         let result = array.to_column::<Curve25519Scalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        let expected_scalars: Vec<Curve25519Scalar> = vec![
+            convert_i256_to_scalar(
+                &i256::from_str("100000000000000000000000000000000000000").unwrap(),
+            )
+            .unwrap(),
+            Curve25519Scalar::from(0),
+            convert_i256_to_scalar(
+                &i256::from_str("4200000000000000000000000000000000000000").unwrap(),
+            )
+            .unwrap(),
+        ];
+        assert_eq!(
+            result.unwrap(),
+            Column::Decimal75(Precision::new(75).unwrap(), 0, &expected_scalars)
+        );
     }
 
     #[test]
@@ -479,10 +548,7 @@ mod tests {
         );
 
         let result = array.to_column::<DoryScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        assert_eq!(result.unwrap(), Column::Int128(&[100, 0, 4200]));
     }
 
     #[test]
@@ -545,10 +611,7 @@ mod tests {
         let alloc = Bump::new();
         let array: ArrayRef = Arc::new(BooleanArray::from(vec![Some(true), None, Some(true)]));
         let result = array.to_column::<Curve25519Scalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        assert_eq!(result.unwrap(), Column::Boolean(&[true, false, true]));
     }
 
     #[test]
@@ -585,10 +648,7 @@ mod tests {
         let alloc = Bump::new();
         let array: ArrayRef = Arc::new(Int16Array::from(vec![Some(1), None, Some(42)]));
         let result = array.to_column::<Curve25519Scalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        assert_eq!(result.unwrap(), Column::SmallInt(&[1, 0, 42]));
     }
 
     #[test]
@@ -624,11 +684,8 @@ mod tests {
     fn we_can_convert_int32_array_with_nulls() {
         let alloc = Bump::new();
         let array: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(42)]));
-        let result = array.to_column::<Curve25519Scalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        let result = array.to_column::<Curve25519Scalar>(&alloc, &(1..3), None);
+        assert_eq!(result.unwrap(), Column::Int(&[0, 42]));
     }
 
     #[test]
@@ -752,15 +809,31 @@ mod tests {
     }
 
     #[test]
-    fn we_cannot_build_a_column_from_an_array_with_nulls_utf8() {
+    fn we_can_build_a_column_from_an_array_with_nulls_utf8() {
         let alloc = Bump::new();
         let data = vec![Some("ab"), Some("-f34"), None];
         let array: ArrayRef = Arc::new(arrow::array::StringArray::from(data.clone()));
-        let result = array.to_column::<DoryScalar>(&alloc, &(0..3), None);
-        assert!(matches!(
-            result,
-            Err(ArrowArrayToColumnConversionError::ArrayContainsNulls)
-        ));
+        let result = array.to_column::<DoryScalar>(&alloc, &(1..3), None);
+        let expected_vals = ["-f34", ""];
+        let expected_scals: Vec<DoryScalar> = expected_vals.iter().map(|&v| v.into()).collect();
+        assert_eq!(
+            result.unwrap(),
+            Column::VarChar((&expected_vals, &expected_scals))
+        );
+    }
+
+    #[test]
+    fn we_can_convert_an_array_with_nulls_into_scalars_with_zeros_for_the_nulls() {
+        let array: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(-3)]));
+        assert_eq!(
+            array.to_curve25519_scalars(),
+            Ok(vec![1.into(), 0.into(), (-3).into()])
+        );
+        let array: ArrayRef = Arc::new(StringArray::from(vec![None, Some("a")]));
+        assert_eq!(
+            array.to_curve25519_scalars(),
+            Ok(vec!["".into(), "a".into()])
+        );
     }
 
     #[test]
@@ -1,11 +1,18 @@
 use super::{LiteralValue, TableRef};
-use crate::base::{
-    math::decimal::{scale_scalar, Precision},
-    scalar::Scalar,
+use crate::{
+    base::{
+        math::decimal::{scale_scalar, Precision},
+        scalar::Scalar,
+    },
+    sql::parse::ConversionError,
 };
-use arrow::datatypes::{DataType, Field};
+use arrow::datatypes::{DataType, Field, TimeUnit};
 use bumpalo::Bump;
-use proof_of_sql_parser::Identifier;
+use proof_of_sql_parser::{
+    intermediate_ast,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    Identifier,
+};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 
@@ -37,6 +44,10 @@ pub enum Column<'a, S: Scalar> {
     ///  - the first element maps to the str values.
     ///  - the second element maps to the str hashes (see [crate::base::scalar::Scalar]).
     VarChar((&'a [&'a str], &'a [S])),
+    /// Timestamp columns, as the number of units since the Unix epoch
+    TimeStamp(PoSQLTimeUnit, PoSQLTimeZone, &'a [i64]),
+    /// Date columns, as the number of days since the Unix epoch
+    Date(&'a [i32]),
 }
 impl<'a, S: Scalar> Column<'a, S> {
     /// Provides the column type associated with the column
@@ -50,6 +61,8 @@ impl<'a, S: Scalar> Column<'a, S> {
             Self::Int128(_) => ColumnType::Int128,
             Self::Scalar(_) => ColumnType::Scalar,
             Self::Decimal75(precision, scale, _) => ColumnType::Decimal75(*precision, *scale),
+            Self::TimeStamp(unit, timezone, _) => ColumnType::TimeStamp(*unit, *timezone),
+            Self::Date(_) => ColumnType::Date,
         }
     }
     /// Returns the length of the column.
@@ -66,6 +79,8 @@ impl<'a, S: Scalar> Column<'a, S> {
             Self::Int128(col) => col.len(),
             Self::Scalar(col) => col.len(),
             Self::Decimal75(_, _, col) => col.len(),
+            Self::TimeStamp(_, _, col) => col.len(),
+            Self::Date(col) => col.len(),
         }
     }
     /// Returns `true` if the column has no elements.
@@ -105,6 +120,12 @@ impl<'a, S: Scalar> Column<'a, S> {
                 alloc.alloc_slice_fill_with(length, |_| alloc.alloc_str(string) as &str),
                 alloc.alloc_slice_fill_copy(length, *scalar),
             )),
+            LiteralValue::TimeStamp(unit, timezone, value) => Column::TimeStamp(
+                *unit,
+                *timezone,
+                alloc.alloc_slice_fill_copy(length, *value),
+            ),
+            LiteralValue::Date(value) => Column::Date(alloc.alloc_slice_fill_copy(length, *value)),
         }
     }
 
@@ -153,6 +174,14 @@ impl<'a, S: Scalar> Column<'a, S> {
                 .par_iter()
                 .map(|s| *s * scale_factor)
                 .collect::<Vec<_>>(),
+            Self::TimeStamp(_, _, col) => col
+                .par_iter()
+                .map(|i| S::from(i) * scale_factor)
+                .collect::<Vec<_>>(),
+            Self::Date(col) => col
+                .par_iter()
+                .map(|i| S::from(i) * scale_factor)
+                .collect::<Vec<_>>(),
         }
     }
 }
@@ -194,6 +223,12 @@ pub enum ColumnType {
     /// Mapped to i256
     #[serde(rename = "Decimal75", alias = "DECIMAL75", alias = "decimal75")]
     Decimal75(Precision, i8),
+    /// Mapped to i64, the number of units since the Unix epoch
+    #[serde(alias = "TIMESTAMP", alias = "timestamp")]
+    TimeStamp(PoSQLTimeUnit, PoSQLTimeZone),
+    /// Mapped to i32, the number of days since the Unix epoch
+    #[serde(alias = "DATE", alias = "date")]
+    Date,
 }
 
 impl ColumnType {
@@ -226,6 +261,8 @@ impl ColumnType {
             Self::BigInt => Some(19_u8),
             Self::Int128 => Some(39_u8),
             Self::Decimal75(precision, _) => Some(precision.value()),
+            // Timestamps are compared as decimals of seconds, so that different units can be compared.
+            Self::TimeStamp(_, _) => Some(19_u8),
             // Scalars are not in database & are only used for typeless comparisons for testing so we return 0
             // so that they do not cause errors when used in comparisons.
             Self::Scalar => Some(0_u8),
@@ -237,6 +274,7 @@ impl ColumnType {
         match self {
             Self::Decimal75(_, scale) => Some(*scale),
             Self::BigInt | Self::Int128 | Self::Scalar => Some(0),
+            Self::TimeStamp(unit, _) => Some(unit.scale()),
             _ => None,
         }
     }
@@ -256,6 +294,16 @@ impl From<&ColumnType> for DataType {
             }
             ColumnType::VarChar => DataType::Utf8,
             ColumnType::Scalar => unimplemented!("Cannot convert Scalar type to arrow type"),
+            ColumnType::TimeStamp(unit, timezone) => {
+                let unit = match unit {
+                    PoSQLTimeUnit::Second => TimeUnit::Second,
+                    PoSQLTimeUnit::Millisecond => TimeUnit::Millisecond,
+                    PoSQLTimeUnit::Microsecond => TimeUnit::Microsecond,
+                    PoSQLTimeUnit::Nanosecond => TimeUnit::Nanosecond,
+                };
+                DataType::Timestamp(unit, Some(timezone.to_string().into()))
+            }
+            ColumnType::Date => DataType::Date32,
         }
     }
 }
@@ -275,11 +323,44 @@ impl TryFrom<DataType> for ColumnType {
                 Ok(ColumnType::Decimal75(Precision::new(precision)?, scale))
             }
             DataType::Utf8 => Ok(ColumnType::VarChar),
+            DataType::Timestamp(unit, timezone) => {
+                let unit = match unit {
+                    TimeUnit::Second => PoSQLTimeUnit::Second,
+                    TimeUnit::Millisecond => PoSQLTimeUnit::Millisecond,
+                    TimeUnit::Microsecond => PoSQLTimeUnit::Microsecond,
+                    TimeUnit::Nanosecond => PoSQLTimeUnit::Nanosecond,
+                };
+                // Timestamps without a time zone are taken to be in UTC.
+                let timezone = match timezone {
+                    Some(timezone) => timezone.parse().map_err(|e| format!("{e}"))?,
+                    None => PoSQLTimeZone::Utc,
+                };
+                Ok(ColumnType::TimeStamp(unit, timezone))
+            }
+            DataType::Date32 => Ok(ColumnType::Date),
             _ => Err(format!("Unsupported arrow data type {:?}", data_type)),
         }
     }
 }
 
+/// Convert the data types that expressions are cast to in the intermediate AST to some ColumnType
+impl TryFrom<intermediate_ast::DataType> for ColumnType {
+    type Error = ConversionError;
+
+    fn try_from(data_type: intermediate_ast::DataType) -> Result<Self, Self::Error> {
+        match data_type {
+            intermediate_ast::DataType::Boolean => Ok(ColumnType::Boolean),
+            intermediate_ast::DataType::SmallInt => Ok(ColumnType::SmallInt),
+            intermediate_ast::DataType::Int => Ok(ColumnType::Int),
+            intermediate_ast::DataType::BigInt => Ok(ColumnType::BigInt),
+            intermediate_ast::DataType::Int128 => Ok(ColumnType::Int128),
+            intermediate_ast::DataType::Decimal75(precision, scale) => {
+                Ok(ColumnType::Decimal75(Precision::new(precision)?, scale))
+            }
+        }
+    }
+}
+
 /// Display the column type as a str name (in all caps)
 impl std::fmt::Display for ColumnType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -298,6 +379,10 @@ impl std::fmt::Display for ColumnType {
             }
             ColumnType::VarChar => write!(f, "VARCHAR"),
             ColumnType::Scalar => write!(f, "SCALAR"),
+            ColumnType::TimeStamp(unit, timezone) => {
+                write!(f, "TIMESTAMP(UNIT: {unit}, TIMEZONE: {timezone})")
+            }
+            ColumnType::Date => write!(f, "DATE"),
         }
     }
 }
@@ -722,4 +807,123 @@ mod tests {
         assert_eq!(column.len(), 0);
         assert!(column.is_empty());
     }
+
+    #[test]
+    fn we_can_serialize_and_deserialize_timestamp_and_date_column_types() {
+        let timestamp = ColumnType::TimeStamp(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc);
+        let timestamp_json = serde_json::to_string(&timestamp).unwrap();
+        assert_eq!(timestamp_json, r#"{"TimeStamp":["Second","Utc"]}"#);
+        assert_eq!(
+            serde_json::from_str::<ColumnType>(&timestamp_json).unwrap(),
+            timestamp
+        );
+
+        let timestamp = ColumnType::TimeStamp(
+            PoSQLTimeUnit::Nanosecond,
+            PoSQLTimeZone::FixedOffset(-19800),
+        );
+        let timestamp_json = serde_json::to_string(&timestamp).unwrap();
+        assert_eq!(
+            timestamp_json,
+            r#"{"TimeStamp":["Nanosecond",{"FixedOffset":-19800}]}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<ColumnType>(&timestamp_json).unwrap(),
+            timestamp
+        );
+
+        let date_json = serde_json::to_string(&ColumnType::Date).unwrap();
+        assert_eq!(date_json, r#""Date""#);
+        assert_eq!(
+            serde_json::from_str::<ColumnType>(r#""DATE""#).unwrap(),
+            ColumnType::Date
+        );
+    }
+
+    #[test]
+    fn we_can_convert_timestamp_and_date_column_types_to_and_from_arrow() {
+        let timestamp =
+            ColumnType::TimeStamp(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::FixedOffset(3600));
+        let data_type = DataType::from(&timestamp);
+        assert_eq!(
+            data_type,
+            DataType::Timestamp(TimeUnit::Millisecond, Some("+01:00".into()))
+        );
+        assert_eq!(ColumnType::try_from(data_type).unwrap(), timestamp);
+
+        assert_eq!(
+            ColumnType::try_from(DataType::Timestamp(TimeUnit::Second, None)).unwrap(),
+            ColumnType::TimeStamp(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc)
+        );
+        assert_eq!(
+            ColumnType::try_from(DataType::Timestamp(
+                TimeUnit::Nanosecond,
+                Some("UTC".into())
+            ))
+            .unwrap(),
+            ColumnType::TimeStamp(PoSQLTimeUnit::Nanosecond, PoSQLTimeZone::Utc)
+        );
+        assert!(ColumnType::try_from(DataType::Timestamp(
+            TimeUnit::Second,
+            Some("America/New_York".into())
+        ))
+        .is_err());
+
+        assert_eq!(DataType::from(&ColumnType::Date), DataType::Date32);
+        assert_eq!(
+            ColumnType::try_from(DataType::Date32).unwrap(),
+            ColumnType::Date
+        );
+    }
+
+    #[test]
+    fn we_can_convert_the_data_types_of_casts_to_column_types() {
+        assert_eq!(
+            ColumnType::try_from(intermediate_ast::DataType::Boolean).unwrap(),
+            ColumnType::Boolean
+        );
+        assert_eq!(
+            ColumnType::try_from(intermediate_ast::DataType::Int128).unwrap(),
+            ColumnType::Int128
+        );
+        assert_eq!(
+            ColumnType::try_from(intermediate_ast::DataType::Decimal75(75, -2)).unwrap(),
+            ColumnType::Decimal75(Precision::new(75).unwrap(), -2)
+        );
+        assert!(ColumnType::try_from(intermediate_ast::DataType::Decimal75(0, 0)).is_err());
+        assert!(ColumnType::try_from(intermediate_ast::DataType::Decimal75(76, 2)).is_err());
+    }
+
+    #[test]
+    fn we_can_scale_timestamps_of_different_units() {
+        let seconds = ColumnType::TimeStamp(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc);
+        let nanos = ColumnType::TimeStamp(PoSQLTimeUnit::Nanosecond, PoSQLTimeZone::Utc);
+        assert_eq!(seconds.scale(), Some(0));
+        assert_eq!(nanos.scale(), Some(9));
+        assert_eq!(nanos.precision_value(), Some(19));
+        assert!(!seconds.is_numeric());
+        assert_eq!(
+            seconds.to_string(),
+            "TIMESTAMP(UNIT: seconds, TIMEZONE: +00:00)"
+        );
+
+        let column = Column::<Curve25519Scalar>::TimeStamp(
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            &[1, -2],
+        );
+        assert_eq!(column.column_type(), seconds);
+        assert_eq!(
+            column.to_scalar_with_scaling(3),
+            vec![Curve25519Scalar::from(1000), Curve25519Scalar::from(-2000)]
+        );
+
+        let alloc = Bump::new();
+        let column = Column::<Curve25519Scalar>::from_literal_with_length(
+            &LiteralValue::Date(19_723),
+            2,
+            &alloc,
+        );
+        assert_eq!(column, Column::Date(&[19_723, 19_723]));
+    }
 }
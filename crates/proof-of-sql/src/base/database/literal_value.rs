@@ -1,4 +1,5 @@
 use crate::base::{database::ColumnType, math::decimal::Precision, scalar::Scalar};
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 use serde::{Deserialize, Serialize};
 
 /// Represents a literal value.
@@ -29,6 +30,10 @@ pub enum LiteralValue<S: Scalar> {
     Decimal75(Precision, i8, S),
     /// Scalar literals
     Scalar(S),
+    /// Timestamp literals, as the number of units since the Unix epoch
+    TimeStamp(PoSQLTimeUnit, PoSQLTimeZone, i64),
+    /// Date literals, as the number of days since the Unix epoch
+    Date(i32),
 }
 
 impl<S: Scalar> LiteralValue<S> {
@@ -43,6 +48,8 @@ impl<S: Scalar> LiteralValue<S> {
             Self::Int128(_) => ColumnType::Int128,
             Self::Scalar(_) => ColumnType::Scalar,
             Self::Decimal75(precision, scale, _) => ColumnType::Decimal75(*precision, *scale),
+            Self::TimeStamp(unit, timezone, _) => ColumnType::TimeStamp(*unit, *timezone),
+            Self::Date(_) => ColumnType::Date,
         }
     }
 
@@ -57,6 +64,8 @@ impl<S: Scalar> LiteralValue<S> {
             Self::Int128(i) => i.into(),
             Self::Decimal75(_, _, s) => *s,
             Self::Scalar(scalar) => *scalar,
+            Self::TimeStamp(_, _, i) => i.into(),
+            Self::Date(i) => i.into(),
         }
     }
 }
@@ -40,6 +40,17 @@ mod test_accessor_utility;
 #[cfg(any(test, feature = "test"))]
 pub use test_accessor_utility::{make_random_test_accessor_data, RandomTestAccessorDescriptor};
 
+mod validity;
+pub(crate) use validity::split_validity;
+pub use validity::{validated_column_id, validity_column_id};
+
+mod order_key;
+pub(crate) use order_key::add_order_keys;
+pub use order_key::{
+    order_key, order_key_column_id, ordered_column_id, prefix_order_key_range,
+    MAX_ORDER_KEY_LITERAL_LENGTH, ORDER_KEY_LENGTH,
+};
+
 mod owned_column;
 pub use owned_column::OwnedColumn;
 mod owned_table;
@@ -0,0 +1,207 @@
+//! `VarChar` values are committed to as hashes, which do not preserve the order of the strings.
+//! So every `VarChar` column `x` is committed to along with an `Int128` order key column named `__order__x`,
+//! which holds the [`order_key`] of each value of `x`.
+//!
+//! Order keys compare like the strings they come from in binary collation (i.e. byte by byte),
+//! up to [`ORDER_KEY_LENGTH`] bytes. So comparisons of `x` with a string of at most
+//! [`MAX_ORDER_KEY_LITERAL_LENGTH`] bytes are exactly comparisons of their order keys,
+//! which are proven like comparisons of any other integers.
+//!
+//! Note: since every committed `VarChar` column gets an order key column, the commitments of tables with
+//! `VarChar` columns have one more column per `VarChar` column than before, and commitments made before
+//! then have to be recomputed. Order key columns are hidden: they are not part of the schemas of tables.
+use arrow::{
+    array::{Array, ArrayRef, Decimal128Array, StringArray},
+    datatypes::DataType,
+};
+use proof_of_sql_parser::{Identifier, ParseError};
+use std::sync::Arc;
+
+/// The prefix of the name of every order key column.
+const ORDER_KEY_COLUMN_PREFIX: &str = "__order__";
+
+/// The number of leading bytes of a string that its order key depends on.
+///
+/// Each byte is a digit in base 257, so that the key of 15 bytes fits in an `i128`,
+/// with enough room left for the sign decomposition of the difference of two keys.
+pub const ORDER_KEY_LENGTH: usize = 15;
+
+/// The maximum length in bytes of the strings that `VarChar` columns can be compared with.
+///
+/// Comparing with a string `s` only depends on the first `s.len() + 1` bytes of the values,
+/// which must be part of their order keys.
+pub const MAX_ORDER_KEY_LITERAL_LENGTH: usize = ORDER_KEY_LENGTH - 1;
+
+/// Returns the order key of a string.
+///
+/// This is the number whose base 257 digits are the first [`ORDER_KEY_LENGTH`] bytes of the string, each plus one,
+/// padded with zeros. A string is thus greater than any of its prefixes.
+pub fn order_key(value: &str) -> i128 {
+    let bytes = value.as_bytes();
+    (0..ORDER_KEY_LENGTH).fold(0, |key, i| {
+        key * 257 + bytes.get(i).map_or(0, |byte| i128::from(*byte) + 1)
+    })
+}
+
+/// Returns the smallest and the largest order key of the strings that start with `prefix`,
+/// or `None` if `prefix` is longer than [`ORDER_KEY_LENGTH`] bytes.
+///
+/// These are exactly the strings whose order key is in this range.
+pub fn prefix_order_key_range(prefix: &str) -> Option<(i128, i128)> {
+    let free_digits = ORDER_KEY_LENGTH.checked_sub(prefix.len())?;
+    let low = order_key(prefix);
+    Some((low, low + 257_i128.pow(free_digits as u32) - 1))
+}
+
+/// Returns the identifier of the order key column of `column_id`.
+///
+/// This fails when the resulting identifier is too long.
+pub fn order_key_column_id(column_id: Identifier) -> Result<Identifier, ParseError> {
+    Identifier::try_new(format!("{ORDER_KEY_COLUMN_PREFIX}{column_id}"))
+}
+
+/// Returns the identifier of the column ordered by `column_id`, if `column_id` is an order key column.
+pub fn ordered_column_id(column_id: Identifier) -> Option<Identifier> {
+    column_id
+        .as_str()
+        .strip_prefix(ORDER_KEY_COLUMN_PREFIX)
+        .and_then(|id| Identifier::try_new(id).ok())
+}
+
+/// Returns the columns, each `Utf8` column followed by its order key column.
+///
+/// A column whose name is too long for the name of its order key column gets none, so it can not be compared.
+/// Note: nulls have the order key of the empty string, which is the value they are replaced by.
+pub(crate) fn add_order_keys(columns: Vec<(Identifier, ArrayRef)>) -> Vec<(Identifier, ArrayRef)> {
+    let mut result = Vec::with_capacity(columns.len());
+    for (identifier, array) in columns {
+        let order_key_column = order_key_column_id(identifier)
+            .ok()
+            .filter(|_| array.data_type() == &DataType::Utf8)
+            .map(|order_key_id| {
+                let strings = array.as_any().downcast_ref::<StringArray>().unwrap();
+                let order_keys = Decimal128Array::from_iter_values(
+                    strings
+                        .iter()
+                        .map(|value| order_key(value.unwrap_or_default())),
+                )
+                .with_precision_and_scale(38, 0)
+                .unwrap();
+                (order_key_id, Arc::new(order_keys) as ArrayRef)
+            });
+        result.push((identifier, array));
+        result.extend(order_key_column);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Int64Array;
+
+    #[test]
+    fn we_can_get_the_order_key_column_of_a_column_and_back() {
+        let column_id: Identifier = "a".parse().unwrap();
+        let order_key_id = order_key_column_id(column_id).unwrap();
+        assert_eq!(order_key_id.as_str(), "__order__a");
+        assert_eq!(ordered_column_id(order_key_id), Some(column_id));
+        assert_eq!(ordered_column_id(column_id), None);
+    }
+
+    #[test]
+    fn we_cannot_get_the_order_key_column_of_a_column_with_a_long_name() {
+        let column_id = Identifier::try_new("a".repeat(60)).unwrap();
+        assert!(order_key_column_id(column_id).is_err());
+    }
+
+    #[test]
+    fn order_keys_compare_like_the_strings_they_come_from() {
+        let mut strings = vec![
+            "",
+            "\0",
+            "\0\0",
+            "a",
+            "a\0",
+            "ab",
+            "abc",
+            "abd",
+            "b",
+            "SKU-0001",
+            "SKU-0010",
+            "SKU-01",
+            "Z",
+            "é",
+            "ü",
+            "\u{10FFFF}",
+        ];
+        strings.sort();
+        for pair in strings.windows(2) {
+            assert!(order_key(pair[0]) < order_key(pair[1]), "{pair:?}");
+        }
+        assert_eq!(order_key(""), 0);
+        assert_eq!(order_key("a"), 98 * 257_i128.pow(14));
+    }
+
+    #[test]
+    fn order_keys_only_depend_on_the_first_bytes_of_a_string() {
+        let prefix = "abcdefghijklmno";
+        assert_eq!(prefix.len(), ORDER_KEY_LENGTH);
+        assert_eq!(order_key(prefix), order_key(&format!("{prefix}pqr")));
+        assert!(order_key(&prefix[..MAX_ORDER_KEY_LITERAL_LENGTH]) < order_key(prefix));
+        assert!(order_key(&"\u{10FFFF}".repeat(4)) < 257_i128.pow(15));
+    }
+
+    #[test]
+    fn prefix_order_key_ranges_contain_exactly_the_strings_with_the_prefix() {
+        let (low, high) = prefix_order_key_range("ab").unwrap();
+        for string in [
+            "ab",
+            "ab\0",
+            "abc",
+            "ab\u{10FFFF}\u{10FFFF}\u{10FFFF}\u{10FFFF}",
+        ] {
+            assert!((low..=high).contains(&order_key(string)), "{string:?}");
+        }
+        for string in ["", "a", "a\u{10FFFF}", "aa", "ac", "b"] {
+            assert!(!(low..=high).contains(&order_key(string)), "{string:?}");
+        }
+        assert_eq!(prefix_order_key_range(""), Some((0, 257_i128.pow(15) - 1)));
+        let prefix = "abcdefghijklmno";
+        let (low, high) = prefix_order_key_range(prefix).unwrap();
+        assert_eq!((low, high), (order_key(prefix), order_key(prefix)));
+        assert_eq!(order_key(&format!("{prefix}pqr")), low);
+        assert_eq!(prefix_order_key_range("abcdefghijklmnop"), None);
+    }
+
+    #[test]
+    fn we_can_add_the_order_keys_of_utf8_columns() {
+        let long_name = "c".repeat(60);
+        let columns = add_order_keys(vec![
+            (
+                "a".parse().unwrap(),
+                Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef,
+            ),
+            (
+                "b".parse().unwrap(),
+                Arc::new(StringArray::from(vec![Some("x"), None, Some("")])),
+            ),
+            (
+                Identifier::try_new(&long_name).unwrap(),
+                Arc::new(StringArray::from(vec!["x", "y", "z"])),
+            ),
+        ]);
+        let names: Vec<_> = columns.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(names, ["a", "b", "__order__b", long_name.as_str()]);
+        assert_eq!(
+            columns[2]
+                .1
+                .as_any()
+                .downcast_ref::<Decimal128Array>()
+                .unwrap(),
+            &Decimal128Array::from(vec![order_key("x"), 0, 0])
+                .with_precision_and_scale(38, 0)
+                .unwrap()
+        );
+    }
+}
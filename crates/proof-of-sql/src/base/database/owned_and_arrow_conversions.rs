@@ -7,31 +7,47 @@
 //! VarChar <-> Utf8/String
 //! Int128 <-> Decimal128(38,0)
 //! Decimal75 <-> S
+//! TimeStamp <-> Timestamp
+//! Date <-> Date32
+//!
+//! Nulls are replaced by default values, and the nulls of each nullable field are kept in a separate
+//! validity column (see [`super::validity`]). Conversely, validity columns are folded back into nulls.
+//! Likewise, each `Utf8` field gets an order key column (see [`super::order_key`]), which is dropped
+//! when converting back to arrow.
 //!
 //! Note: this converts `Int128` values to `Decimal128(38,0)`, which are backed by `i128`.
 //! This is because there is no `Int128` type in Arrow.
 //! This does not check that the values are less than 39 digits.
 //! However, the actual arrow backing `i128` is the correct value.
+//!
+//! Arrow timestamps without a time zone are taken to be in UTC, and the time zone of a `TimeStamp`
+//! is always written as an offset (e.g. `+00:00`).
 use super::scalar_and_i256_conversions::convert_scalar_to_i256;
 use crate::base::{
     database::{
-        scalar_and_i256_conversions::convert_i256_to_scalar, OwnedColumn, OwnedTable,
-        OwnedTableError,
+        add_order_keys, ordered_column_id, scalar_and_i256_conversions::convert_i256_to_scalar,
+        split_validity, validated_column_id, ColumnType, OwnedColumn, OwnedTable, OwnedTableError,
     },
     math::decimal::Precision,
     scalar::Scalar,
 };
 use arrow::{
     array::{
-        ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, Int16Array, Int32Array,
-        Int64Array, StringArray,
+        make_array, Array, ArrayRef, BooleanArray, Date32Array, Decimal128Array, Decimal256Array,
+        Int16Array, Int32Array, Int64Array, PrimitiveArray, StringArray, TimestampMicrosecondArray,
+        TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
+    },
+    buffer::NullBuffer,
+    datatypes::{
+        i256, ArrowPrimitiveType, DataType, Date32Type, Schema, SchemaRef,
+        TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+        TimestampSecondType,
     },
-    datatypes::{i256, DataType, Schema, SchemaRef},
     error::ArrowError,
     record_batch::RecordBatch,
 };
 use indexmap::IndexMap;
-use proof_of_sql_parser::{Identifier, ParseError};
+use proof_of_sql_parser::{posql_time::PoSQLTimeUnit, ParseError};
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -51,9 +67,6 @@ pub enum OwnedArrowConversionError {
     #[error(transparent)]
     /// This error occurs when creating an owned table fails, which should only occur when there are zero columns.
     InvalidTable(#[from] OwnedTableError),
-    /// This error occurs when trying to convert from an Arrow array with nulls.
-    #[error("null values are not supported in OwnedColumn yet")]
-    NullNotSupportedYet,
 }
 
 impl<S: Scalar> From<OwnedColumn<S>> for ArrayRef {
@@ -79,22 +92,90 @@ impl<S: Scalar> From<OwnedColumn<S>> for ArrayRef {
             }
             OwnedColumn::Scalar(_) => unimplemented!("Cannot convert Scalar type to arrow type"),
             OwnedColumn::VarChar(col) => Arc::new(StringArray::from(col)),
+            OwnedColumn::TimeStamp(unit, timezone, col) => {
+                let timezone = timezone.to_string();
+                match unit {
+                    PoSQLTimeUnit::Second => {
+                        Arc::new(TimestampSecondArray::from(col).with_timezone(timezone))
+                    }
+                    PoSQLTimeUnit::Millisecond => {
+                        Arc::new(TimestampMillisecondArray::from(col).with_timezone(timezone))
+                    }
+                    PoSQLTimeUnit::Microsecond => {
+                        Arc::new(TimestampMicrosecondArray::from(col).with_timezone(timezone))
+                    }
+                    PoSQLTimeUnit::Nanosecond => {
+                        Arc::new(TimestampNanosecondArray::from(col).with_timezone(timezone))
+                    }
+                }
+            }
+            OwnedColumn::Date(col) => Arc::new(Date32Array::from(col)),
         }
     }
 }
 
+/// Returns the values of a primitive array, with nulls replaced by the default value.
+fn primitive_values<T: ArrowPrimitiveType>(value: &ArrayRef) -> Vec<T::Native> {
+    value
+        .as_any()
+        .downcast_ref::<PrimitiveArray<T>>()
+        .unwrap()
+        .iter()
+        .map(Option::unwrap_or_default)
+        .collect()
+}
+
+/// Returns where the values of the column validated by `validity` are not null.
+///
+/// A `BigInt` validity holds the number of non-null values aggregated into each value,
+/// so that an aggregation of nulls only is null.
+fn validity_mask<S: Scalar>(validity: &OwnedColumn<S>) -> Option<Vec<bool>> {
+    match validity {
+        OwnedColumn::Boolean(validity) => Some(validity.clone()),
+        OwnedColumn::BigInt(counts) => Some(counts.iter().map(|count| *count != 0).collect()),
+        _ => None,
+    }
+}
+
 impl<S: Scalar> TryFrom<OwnedTable<S>> for RecordBatch {
     type Error = ArrowError;
     fn try_from(value: OwnedTable<S>) -> Result<Self, Self::Error> {
         if value.is_empty() {
             Ok(RecordBatch::new_empty(SchemaRef::new(Schema::empty())))
         } else {
-            RecordBatch::try_from_iter(
-                value
-                    .into_inner()
-                    .into_iter()
-                    .map(|(identifier, owned_column)| (identifier, ArrayRef::from(owned_column))),
-            )
+            let mut table = value.into_inner();
+            let order_key_ids = Vec::from_iter(table.keys().copied().filter(|identifier| {
+                ordered_column_id(*identifier)
+                    .is_some_and(|ordered_id| table.contains_key(&ordered_id))
+            }));
+            for identifier in order_key_ids {
+                table.shift_remove(&identifier);
+            }
+            let validated_ids = Vec::from_iter(table.iter().filter_map(|(identifier, column)| {
+                validated_column_id(*identifier)
+                    .filter(|validated_id| table.contains_key(validated_id))
+                    .zip(validity_mask(column))
+                    .map(|(validated_id, mask)| (*identifier, validated_id, mask))
+            }));
+            let mut nulls = IndexMap::with_capacity(validated_ids.len());
+            for (identifier, validated_id, mask) in validated_ids {
+                table.shift_remove(&identifier);
+                nulls.insert(validated_id, NullBuffer::from(mask));
+            }
+            let columns = table
+                .into_iter()
+                .map(|(identifier, owned_column)| {
+                    let array = ArrayRef::from(owned_column);
+                    match nulls.swap_remove(&identifier) {
+                        Some(nulls) => {
+                            let data = array.into_data().into_builder().nulls(Some(nulls));
+                            Ok((identifier, make_array(data.build()?), true))
+                        }
+                        None => Ok((identifier, array, false)),
+                    }
+                })
+                .collect::<Result<Vec<_>, ArrowError>>()?;
+            RecordBatch::try_from_iter_with_nullable(columns)
         }
     }
 }
@@ -117,40 +198,44 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                     .downcast_ref::<BooleanArray>()
                     .unwrap()
                     .iter()
-                    .collect::<Option<Vec<bool>>>()
-                    .ok_or(OwnedArrowConversionError::NullNotSupportedYet)?,
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Int16 => Ok(Self::SmallInt(
                 value
                     .as_any()
                     .downcast_ref::<Int16Array>()
                     .unwrap()
-                    .values()
-                    .to_vec(),
+                    .iter()
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Int32 => Ok(Self::Int(
                 value
                     .as_any()
                     .downcast_ref::<Int32Array>()
                     .unwrap()
-                    .values()
-                    .to_vec(),
+                    .iter()
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Int64 => Ok(Self::BigInt(
                 value
                     .as_any()
                     .downcast_ref::<Int64Array>()
                     .unwrap()
-                    .values()
-                    .to_vec(),
+                    .iter()
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Decimal128(38, 0) => Ok(Self::Int128(
                 value
                     .as_any()
                     .downcast_ref::<Decimal128Array>()
                     .unwrap()
-                    .values()
-                    .to_vec(),
+                    .iter()
+                    .map(Option::unwrap_or_default)
+                    .collect(),
             )),
             DataType::Decimal256(precision, scale) if *precision <= 75 => Ok(Self::Decimal75(
                 Precision::new(*precision).expect("precision is less than 76"),
@@ -159,9 +244,10 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                     .as_any()
                     .downcast_ref::<Decimal256Array>()
                     .unwrap()
-                    .values()
                     .iter()
-                    .map(convert_i256_to_scalar)
+                    .map(|value| {
+                        value.map_or(Some(S::ZERO), |value| convert_i256_to_scalar(&value))
+                    })
                     .map(Option::unwrap)
                     .collect(),
             )),
@@ -171,9 +257,31 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
                     .downcast_ref::<StringArray>()
                     .unwrap()
                     .iter()
-                    .map(|s| s.unwrap().to_string())
+                    .map(|s| s.unwrap_or_default().to_string())
                     .collect(),
             )),
+            DataType::Timestamp(_, _) => match ColumnType::try_from(value.data_type().clone()) {
+                Ok(ColumnType::TimeStamp(unit, timezone)) => {
+                    let values = match unit {
+                        PoSQLTimeUnit::Second => primitive_values::<TimestampSecondType>(value),
+                        PoSQLTimeUnit::Millisecond => {
+                            primitive_values::<TimestampMillisecondType>(value)
+                        }
+                        PoSQLTimeUnit::Microsecond => {
+                            primitive_values::<TimestampMicrosecondType>(value)
+                        }
+                        PoSQLTimeUnit::Nanosecond => {
+                            primitive_values::<TimestampNanosecondType>(value)
+                        }
+                    };
+                    Ok(Self::TimeStamp(unit, timezone, values))
+                }
+                // The time zone is not supported
+                _ => Err(OwnedArrowConversionError::UnsupportedType(
+                    value.data_type().clone(),
+                )),
+            },
+            DataType::Date32 => Ok(Self::Date(primitive_values::<Date32Type>(value))),
             &data_type => Err(OwnedArrowConversionError::UnsupportedType(
                 data_type.clone(),
             )),
@@ -184,17 +292,11 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
 impl<S: Scalar> TryFrom<RecordBatch> for OwnedTable<S> {
     type Error = OwnedArrowConversionError;
     fn try_from(value: RecordBatch) -> Result<Self, Self::Error> {
-        let num_columns = value.num_columns();
-        let table: Result<IndexMap<_, _>, Self::Error> = value
-            .schema()
-            .fields()
+        let columns = split_validity(&value).map(add_order_keys)?;
+        let num_columns = columns.len();
+        let table: Result<IndexMap<_, _>, Self::Error> = columns
             .iter()
-            .zip(value.columns())
-            .map(|(field, array_ref)| {
-                let owned_column = OwnedColumn::try_from(array_ref)?;
-                let identifier = Identifier::try_new(field.name())?; //This may always succeed.
-                Ok((identifier, owned_column))
-            })
+            .map(|(identifier, array_ref)| Ok((*identifier, OwnedColumn::try_from(array_ref)?)))
             .collect();
         let owned_table = Self::try_new(table?)?;
         if num_columns == owned_table.num_columns() {
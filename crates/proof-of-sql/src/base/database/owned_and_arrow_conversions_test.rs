@@ -1,17 +1,21 @@
 use super::{OwnedColumn, OwnedTable};
 use crate::{
     base::{
-        database::{owned_table_utility::*, OwnedArrowConversionError},
+        database::{order_key, owned_table_utility::*, OwnedArrowConversionError},
         scalar::Curve25519Scalar,
     },
     record_batch,
 };
 use arrow::{
-    array::{ArrayRef, BooleanArray, Decimal128Array, Float32Array, Int64Array, StringArray},
+    array::{
+        ArrayRef, BooleanArray, Date32Array, Decimal128Array, Float32Array, Int64Array,
+        StringArray, TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
+    },
     datatypes::Schema,
     record_batch::RecordBatch,
 };
 use indexmap::IndexMap;
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 use std::sync::Arc;
 
 fn we_can_convert_between_owned_column_and_array_ref_impl(
@@ -70,6 +74,48 @@ fn we_can_convert_between_owned_column_and_array_ref() {
     );
 }
 
+#[test]
+fn we_can_convert_between_timestamp_or_date_owned_columns_and_array_refs() {
+    let data = vec![0, 1, -1, 1_704_067_200, i64::MIN, i64::MAX];
+    we_can_convert_between_owned_column_and_array_ref_impl(
+        OwnedColumn::TimeStamp(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, data.clone()),
+        Arc::new(TimestampSecondArray::from(data.clone()).with_timezone("+00:00")),
+    );
+    we_can_convert_between_owned_column_and_array_ref_impl(
+        OwnedColumn::TimeStamp(
+            PoSQLTimeUnit::Nanosecond,
+            PoSQLTimeZone::FixedOffset(-19800),
+            data.clone(),
+        ),
+        Arc::new(TimestampNanosecondArray::from(data).with_timezone("-05:30")),
+    );
+    let data = vec![0, 1, -1, 19_723, i32::MIN, i32::MAX];
+    we_can_convert_between_owned_column_and_array_ref_impl(
+        OwnedColumn::Date(data.clone()),
+        Arc::new(Date32Array::from(data)),
+    );
+}
+
+#[test]
+fn we_can_convert_timestamp_array_refs_without_a_time_zone_to_utc_owned_columns() {
+    let array_ref: ArrayRef = Arc::new(TimestampMillisecondArray::from(vec![Some(1), None]));
+    assert_eq!(
+        OwnedColumn::<Curve25519Scalar>::try_from(array_ref).unwrap(),
+        OwnedColumn::TimeStamp(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::Utc, vec![1, 0])
+    );
+}
+
+#[test]
+fn we_get_an_unsupported_type_error_when_trying_to_convert_from_a_timestamp_array_ref_with_a_named_time_zone(
+) {
+    let array_ref: ArrayRef =
+        Arc::new(TimestampSecondArray::from(vec![0]).with_timezone("America/New_York"));
+    assert!(matches!(
+        OwnedColumn::<Curve25519Scalar>::try_from(array_ref),
+        Err(OwnedArrowConversionError::UnsupportedType(_))
+    ));
+}
+
 #[test]
 fn we_get_an_unsupported_type_error_when_trying_to_convert_from_a_float32_array_ref_to_an_owned_column(
 ) {
@@ -101,6 +147,7 @@ fn we_can_convert_between_owned_table_and_record_batch() {
             bigint("int64", [0; 0]),
             int128("int128", [0; 0]),
             varchar("string", ["0"; 0]),
+            varchar_order_key("string", ["0"; 0]),
             boolean("boolean", [true; 0]),
         ]),
         record_batch!(
@@ -115,6 +162,7 @@ fn we_can_convert_between_owned_table_and_record_batch() {
             bigint("int64", [0, 1, 2, 3, 4, 5, 6, i64::MIN, i64::MAX]),
             int128("int128", [0, 1, 2, 3, 4, 5, 6, i128::MIN, i128::MAX]),
             varchar("string", ["0", "1", "2", "3", "4", "5", "6", "7", "8"]),
+            varchar_order_key("string", ["0", "1", "2", "3", "4", "5", "6", "7", "8"]),
             boolean(
                 "boolean",
                 [true, false, true, false, true, false, true, false, true],
@@ -129,6 +177,120 @@ fn we_can_convert_between_owned_table_and_record_batch() {
     );
 }
 
+#[test]
+fn we_can_convert_between_owned_table_and_record_batch_with_nulls() {
+    we_can_convert_between_owned_table_and_record_batch_impl(
+        owned_table([
+            bigint("int64", [1, 0, 3]),
+            boolean("__valid__int64", [true, false, true]),
+            varchar("string", ["", "b", ""]),
+            varchar_order_key("string", ["", "b", ""]),
+            boolean("__valid__string", [false, true, false]),
+            boolean("boolean", [true, false, true]),
+        ]),
+        RecordBatch::try_from_iter_with_nullable([
+            (
+                "int64",
+                Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+                true,
+            ),
+            (
+                "string",
+                Arc::new(StringArray::from(vec![None, Some("b"), None])),
+                true,
+            ),
+            (
+                "boolean",
+                Arc::new(BooleanArray::from(vec![true, false, true])),
+                false,
+            ),
+        ])
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_convert_between_owned_table_and_record_batch_with_timestamps_and_dates() {
+    we_can_convert_between_owned_table_and_record_batch_impl(
+        owned_table([
+            timestamptz(
+                "time",
+                PoSQLTimeUnit::Millisecond,
+                PoSQLTimeZone::Utc,
+                [1_704_067_200_000, 0, -1],
+            ),
+            boolean("__valid__time", [true, false, true]),
+            date("day", [19_723, 0, -1]),
+        ]),
+        RecordBatch::try_from_iter_with_nullable([
+            (
+                "time",
+                Arc::new(
+                    TimestampMillisecondArray::from(vec![Some(1_704_067_200_000), None, Some(-1)])
+                        .with_timezone("+00:00"),
+                ) as ArrayRef,
+                true,
+            ),
+            (
+                "day",
+                Arc::new(Date32Array::from(vec![19_723, 0, -1])),
+                false,
+            ),
+        ])
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_convert_an_owned_table_with_counts_of_non_null_values_to_a_record_batch_with_nulls() {
+    let owned_table = owned_table::<Curve25519Scalar>([
+        bigint("total", [7, 0, 4]),
+        bigint("__valid__total", [2, 0, 1]),
+    ]);
+    let expected_record_batch = RecordBatch::try_from_iter([(
+        "total",
+        Arc::new(Int64Array::from(vec![Some(7), None, Some(4)])) as ArrayRef,
+    )])
+    .unwrap();
+    assert_eq!(
+        RecordBatch::try_from(owned_table).unwrap(),
+        expected_record_batch
+    );
+}
+
+#[test]
+fn we_keep_validity_columns_without_their_validated_column_when_converting_to_a_record_batch() {
+    let owned_table = owned_table::<Curve25519Scalar>([boolean("__valid__a", [true, false])]);
+    assert_eq!(
+        RecordBatch::try_from(owned_table).unwrap(),
+        record_batch!("__valid__a" => [true, false])
+    );
+}
+
+#[test]
+fn we_keep_order_key_columns_without_their_ordered_column_when_converting_to_a_record_batch() {
+    let owned_table = owned_table::<Curve25519Scalar>([
+        varchar("a", ["x"]),
+        varchar_order_key("a", ["x"]),
+        varchar_order_key("b", ["y"]),
+    ]);
+    assert_eq!(
+        RecordBatch::try_from(owned_table).unwrap(),
+        RecordBatch::try_from_iter([
+            ("a", Arc::new(StringArray::from(vec!["x"])) as ArrayRef),
+            (
+                "__order__b",
+                Arc::new(
+                    Decimal128Array::from(vec![order_key("y")])
+                        .with_precision_and_scale(38, 0)
+                        .unwrap()
+                ),
+            ),
+        ])
+        .unwrap()
+    );
+}
+
 #[test]
 fn we_cannot_convert_a_record_batch_if_it_has_repeated_column_names() {
     let record_batch = record_batch!(
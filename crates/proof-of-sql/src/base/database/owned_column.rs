@@ -4,6 +4,7 @@
 /// This is the analog of an arrow Array.
 use super::ColumnType;
 use crate::base::{math::decimal::Precision, scalar::Scalar};
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[non_exhaustive]
 /// Supported types for OwnedColumn
@@ -24,6 +25,10 @@ pub enum OwnedColumn<S: Scalar> {
     Decimal75(Precision, i8, Vec<S>),
     /// Scalar columns
     Scalar(Vec<S>),
+    /// Timestamp columns, as the number of units since the Unix epoch
+    TimeStamp(PoSQLTimeUnit, PoSQLTimeZone, Vec<i64>),
+    /// Date columns, as the number of days since the Unix epoch
+    Date(Vec<i32>),
 }
 
 impl<S: Scalar> OwnedColumn<S> {
@@ -38,6 +43,8 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::Int128(col) => col.len(),
             OwnedColumn::Decimal75(_, _, col) => col.len(),
             OwnedColumn::Scalar(col) => col.len(),
+            OwnedColumn::TimeStamp(_, _, col) => col.len(),
+            OwnedColumn::Date(col) => col.len(),
         }
     }
     /// Returns true if the column is empty.
@@ -51,8 +58,25 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::Int128(col) => col.is_empty(),
             OwnedColumn::Scalar(col) => col.is_empty(),
             OwnedColumn::Decimal75(_, _, col) => col.is_empty(),
+            OwnedColumn::TimeStamp(_, _, col) => col.is_empty(),
+            OwnedColumn::Date(col) => col.is_empty(),
         }
     }
+    /// Returns the entry at the given index as a scalar, or `None` if the index is out of bounds.
+    pub fn scalar_at(&self, index: usize) -> Option<S> {
+        (index < self.len()).then(|| match self {
+            OwnedColumn::Boolean(col) => S::from(col[index]),
+            OwnedColumn::SmallInt(col) => S::from(col[index]),
+            OwnedColumn::Int(col) => S::from(col[index]),
+            OwnedColumn::BigInt(col) => S::from(col[index]),
+            OwnedColumn::VarChar(col) => S::from(&col[index]),
+            OwnedColumn::Int128(col) => S::from(col[index]),
+            OwnedColumn::Decimal75(_, _, col) => col[index],
+            OwnedColumn::Scalar(col) => col[index],
+            OwnedColumn::TimeStamp(_, _, col) => S::from(col[index]),
+            OwnedColumn::Date(col) => S::from(col[index]),
+        })
+    }
     /// Returns the type of the column.
     pub fn column_type(&self) -> ColumnType {
         match self {
@@ -66,6 +90,8 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::Decimal75(precision, scale, _) => {
                 ColumnType::Decimal75(*precision, *scale)
             }
+            OwnedColumn::TimeStamp(unit, timezone, _) => ColumnType::TimeStamp(*unit, *timezone),
+            OwnedColumn::Date(_) => ColumnType::Date,
         }
     }
 }
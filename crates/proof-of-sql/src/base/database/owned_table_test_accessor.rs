@@ -1,16 +1,17 @@
 use super::{
-    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedColumn,
-    OwnedTable, SchemaAccessor, TableRef, TestAccessor,
+    ordered_column_id, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, OwnedColumn, OwnedTable, SchemaAccessor, TableRef, TestAccessor,
 };
 use crate::base::commitment::{CommitmentEvaluationProof, VecCommitmentExt};
 use bumpalo::Bump;
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use proof_of_sql_parser::Identifier;
 
 /// A test accessor that uses OwnedTable as the underlying table type.
 /// Note: this is not optimized for performance, so should not be used for benchmarks.
 pub struct OwnedTableTestAccessor<CP: CommitmentEvaluationProof> {
     tables: IndexMap<TableRef, (OwnedTable<CP::Scalar>, usize)>,
+    strictly_increasing_columns: IndexSet<(TableRef, Identifier)>,
     alloc: Bump,
     setup: Option<CP::ProverPublicSetup>,
 }
@@ -19,6 +20,7 @@ impl<CP: CommitmentEvaluationProof> Default for OwnedTableTestAccessor<CP> {
     fn default() -> Self {
         Self {
             tables: Default::default(),
+            strictly_increasing_columns: Default::default(),
             alloc: Bump::new(),
             setup: None,
         }
@@ -32,6 +34,7 @@ where
     fn clone(&self) -> Self {
         Self {
             tables: self.tables.clone(),
+            strictly_increasing_columns: self.strictly_increasing_columns.clone(),
             setup: self.setup.clone(),
             ..Default::default()
         }
@@ -86,6 +89,8 @@ impl<CP: CommitmentEvaluationProof> DataAccessor<CP::Scalar> for OwnedTableTestA
                 Column::Decimal75(*precision, *scale, col)
             }
             OwnedColumn::Scalar(col) => Column::Scalar(col),
+            OwnedColumn::TimeStamp(unit, timezone, col) => Column::TimeStamp(*unit, *timezone, col),
+            OwnedColumn::Date(col) => Column::Date(col),
             OwnedColumn::VarChar(col) => {
                 let col: &mut [&str] = self
                     .alloc
@@ -137,9 +142,15 @@ impl<CP: CommitmentEvaluationProof> SchemaAccessor for OwnedTableTestAccessor<CP
             .0
             .inner_table()
             .iter()
+            .filter(|(id, _)| ordered_column_id(**id).is_none())
             .map(|(&id, col)| (id, col.column_type()))
             .collect()
     }
+
+    fn is_strictly_increasing(&self, table_ref: TableRef, column_id: Identifier) -> bool {
+        self.strictly_increasing_columns
+            .contains(&(table_ref, column_id))
+    }
 }
 
 impl<CP: CommitmentEvaluationProof> OwnedTableTestAccessor<CP>
@@ -152,4 +163,11 @@ where
         res.setup = Some(setup);
         res
     }
+
+    /// Declare that the column is strictly increasing in the order of the rows of the table.
+    /// The data is not checked, so that proofs relying on a wrong declaration can be tested.
+    pub fn set_strictly_increasing(&mut self, table_ref: TableRef, column_id: Identifier) {
+        self.strictly_increasing_columns
+            .insert((table_ref, column_id));
+    }
 }
@@ -13,10 +13,13 @@
 //!     decimal75("f", 12, 1, [1, 2, 3]),
 //! ]);
 //! ```
-use super::{OwnedColumn, OwnedTable};
+use super::{order_key, order_key_column_id, OwnedColumn, OwnedTable};
 use crate::base::scalar::Scalar;
 use core::ops::Deref;
-use proof_of_sql_parser::Identifier;
+use proof_of_sql_parser::{
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    Identifier,
+};
 
 /// Creates an OwnedTable from a list of (Identifier, OwnedColumn) pairs.
 /// This is a convenience wrapper around OwnedTable::try_from_iter primarily for use in tests and
@@ -171,6 +174,26 @@ pub fn varchar<S: Scalar>(
     )
 }
 
+/// Creates a (Identifier, OwnedColumn) pair for the order key column of the varchar column `name`.
+/// This is primarily intended for use in conjunction with [owned_table] and [varchar].
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = owned_table::<Curve25519Scalar>([
+///     varchar("a", ["a", "b", "c"]),
+///     varchar_order_key("a", ["a", "b", "c"]),
+/// ]);
+/// ```
+pub fn varchar_order_key<S: Scalar>(
+    name: impl Deref<Target = str>,
+    data: impl IntoIterator<Item = impl Deref<Target = str>>,
+) -> (Identifier, OwnedColumn<S>) {
+    (
+        order_key_column_id(name.parse().unwrap()).unwrap(),
+        OwnedColumn::Int128(data.into_iter().map(|value| order_key(&value)).collect()),
+    )
+}
+
 /// Creates a (Identifier, OwnedColumn) pair for a decimal75 column.
 /// This is primarily intended for use in conjunction with [owned_table].
 /// # Example
@@ -195,3 +218,44 @@ pub fn decimal75<S: Scalar>(
         ),
     )
 }
+
+/// Creates a (Identifier, OwnedColumn) pair for a timestamp column.
+/// This is primarily intended for use in conjunction with [owned_table].
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+/// let result = owned_table::<Curve25519Scalar>([
+///     timestamptz("a", PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, [1_704_067_200]),
+/// ]);
+/// ```
+pub fn timestamptz<S: Scalar>(
+    name: impl Deref<Target = str>,
+    time_unit: PoSQLTimeUnit,
+    timezone: PoSQLTimeZone,
+    data: impl IntoIterator<Item = i64>,
+) -> (Identifier, OwnedColumn<S>) {
+    (
+        name.parse().unwrap(),
+        OwnedColumn::TimeStamp(time_unit, timezone, data.into_iter().collect()),
+    )
+}
+
+/// Creates a (Identifier, OwnedColumn) pair for a date column, given as days since the Unix epoch.
+/// This is primarily intended for use in conjunction with [owned_table].
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = owned_table::<Curve25519Scalar>([
+///     date("a", [19_723]),
+/// ]);
+/// ```
+pub fn date<S: Scalar>(
+    name: impl Deref<Target = str>,
+    data: impl IntoIterator<Item = i32>,
+) -> (Identifier, OwnedColumn<S>) {
+    (
+        name.parse().unwrap(),
+        OwnedColumn::Date(data.into_iter().collect()),
+    )
+}
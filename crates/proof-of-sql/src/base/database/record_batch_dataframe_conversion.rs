@@ -1,19 +1,23 @@
 use arrow::{
     array::{
-        Array, BooleanArray, Decimal128Array, Int16Array, Int32Array, Int64Array, StringArray,
+        Array, BooleanArray, Date32Array, Decimal128Array, Int16Array, Int32Array, Int64Array,
+        StringArray, TimestampMicrosecondArray, TimestampMillisecondArray,
+        TimestampNanosecondArray,
     },
-    datatypes::{DataType, Field, Schema},
+    datatypes::{DataType, Field, Schema, TimeUnit},
     record_batch::RecordBatch,
 };
 use polars::{
     frame::DataFrame,
-    prelude::{ChunkedArray, NamedFrom},
+    prelude::{ChunkedArray, Int32Chunked, Int64Chunked, NamedFrom, NewChunkedArray},
     series::{IntoSeries, Series},
 };
 use std::sync::Arc;
 
 /// Convert a RecordBatch to a polars DataFrame
 /// Note: this explicitly does not check that Decimal128(38,0) values are 38 digits.
+///
+/// Note: polars has no second time unit, so timestamps in seconds are converted to milliseconds.
 pub fn record_batch_to_dataframe(record_batch: RecordBatch) -> Option<DataFrame> {
     let series: Option<Vec<Series>> = record_batch
         .schema()
@@ -28,8 +32,7 @@ pub fn record_batch_to_dataframe(record_batch: RecordBatch) -> Option<DataFrame>
                         .downcast_ref::<arrow::array::BooleanArray>()
                         .unwrap()
                         .iter()
-                        .collect::<Option<Vec<bool>>>()
-                        .unwrap();
+                        .collect::<Vec<_>>();
 
                     Series::new(f.name(), data)
                 }
@@ -37,7 +40,7 @@ pub fn record_batch_to_dataframe(record_batch: RecordBatch) -> Option<DataFrame>
                     let data = col
                         .as_any()
                         .downcast_ref::<arrow::array::Int16Array>()
-                        .map(|array| array.values())
+                        .map(|array| array.iter().collect::<Vec<_>>())
                         .unwrap();
 
                     Series::new(f.name(), data)
@@ -46,7 +49,7 @@ pub fn record_batch_to_dataframe(record_batch: RecordBatch) -> Option<DataFrame>
                     let data = col
                         .as_any()
                         .downcast_ref::<arrow::array::Int32Array>()
-                        .map(|array| array.values())
+                        .map(|array| array.iter().collect::<Vec<_>>())
                         .unwrap();
 
                     Series::new(f.name(), data)
@@ -55,7 +58,7 @@ pub fn record_batch_to_dataframe(record_batch: RecordBatch) -> Option<DataFrame>
                     let data = col
                         .as_any()
                         .downcast_ref::<arrow::array::Int64Array>()
-                        .map(|array| array.values())
+                        .map(|array| array.iter().collect::<Vec<_>>())
                         .unwrap();
 
                     Series::new(f.name(), data)
@@ -64,7 +67,7 @@ pub fn record_batch_to_dataframe(record_batch: RecordBatch) -> Option<DataFrame>
                     let data = col
                         .as_any()
                         .downcast_ref::<arrow::array::StringArray>()
-                        .map(|array| (0..array.len()).map(|i| array.value(i)).collect::<Vec<_>>())
+                        .map(|array| array.iter().collect::<Vec<_>>())
                         .unwrap();
 
                     Series::new(f.name(), data)
@@ -73,14 +76,41 @@ pub fn record_batch_to_dataframe(record_batch: RecordBatch) -> Option<DataFrame>
                     let data = col
                         .as_any()
                         .downcast_ref::<arrow::array::Decimal128Array>()
-                        .map(|array| array.values())
                         .unwrap();
 
-                    ChunkedArray::from_vec(f.name(), data.to_vec())
+                    ChunkedArray::from_iter_options(f.name(), data.iter())
                         .into_decimal_unchecked(Some(38), 0)
                         // Note: we make this unchecked because if record batch has values that overflow 38 digits, so should the data frame.
                         .into_series()
                 }
+                arrow::datatypes::DataType::Timestamp(time_unit, timezone) => {
+                    let data = arrow::compute::cast(col, &DataType::Int64).ok()?;
+                    let data = data.as_any().downcast_ref::<Int64Array>().unwrap();
+                    let (time_unit, factor) = match time_unit {
+                        TimeUnit::Second => (polars::datatypes::TimeUnit::Milliseconds, 1000),
+                        TimeUnit::Millisecond => (polars::datatypes::TimeUnit::Milliseconds, 1),
+                        TimeUnit::Microsecond => (polars::datatypes::TimeUnit::Microseconds, 1),
+                        TimeUnit::Nanosecond => (polars::datatypes::TimeUnit::Nanoseconds, 1),
+                    };
+                    let values = data
+                        .iter()
+                        .map(|value| match value {
+                            Some(value) => value.checked_mul(factor).map(Some),
+                            None => Some(None),
+                        })
+                        .collect::<Option<Vec<_>>>()?;
+
+                    Int64Chunked::from_iter_options(f.name(), values.into_iter())
+                        .into_datetime(time_unit, timezone.as_deref().map(str::to_string))
+                        .into_series()
+                }
+                arrow::datatypes::DataType::Date32 => {
+                    let data = col.as_any().downcast_ref::<Date32Array>().unwrap();
+
+                    Int32Chunked::from_iter_options(f.name(), data.iter())
+                        .into_date()
+                        .into_series()
+                }
                 _ => None?,
             })
         })
@@ -100,80 +130,103 @@ pub fn dataframe_to_record_batch(data: DataFrame) -> Option<RecordBatch> {
     for (field, series) in data.fields().iter().zip(data.get_columns().iter()) {
         let dt = match field.data_type() {
             polars::datatypes::DataType::Boolean => {
-                let col = series
-                    .bool()
-                    .unwrap()
-                    .into_iter()
-                    .collect::<Option<Vec<bool>>>()
-                    .unwrap();
+                let col = series.bool().unwrap().into_iter().collect::<Vec<_>>();
 
                 columns.push(Arc::new(BooleanArray::from(col)));
 
                 DataType::Boolean
             }
             polars::datatypes::DataType::Int16 => {
-                let col = series.i16().unwrap().cont_slice().unwrap();
+                let col = series.i16().unwrap().into_iter().collect::<Vec<_>>();
 
-                columns.push(Arc::new(Int16Array::from(col.to_vec())));
+                columns.push(Arc::new(Int16Array::from(col)));
 
                 DataType::Int16
             }
             polars::datatypes::DataType::Int32 => {
-                let col = series.i32().unwrap().cont_slice().unwrap();
+                let col = series.i32().unwrap().into_iter().collect::<Vec<_>>();
 
-                columns.push(Arc::new(Int32Array::from(col.to_vec())));
+                columns.push(Arc::new(Int32Array::from(col)));
 
                 DataType::Int32
             }
             polars::datatypes::DataType::Int64 => {
-                let col = series.i64().unwrap().cont_slice().unwrap();
+                let col = series.i64().unwrap().into_iter().collect::<Vec<_>>();
 
-                columns.push(Arc::new(Int64Array::from(col.to_vec())));
+                columns.push(Arc::new(Int64Array::from(col)));
 
                 DataType::Int64
             }
             // This code handles a specific case where a Polars DataFrame has an unsigned 64-bit integer (u64) data type,
             // which only occurs when using the `count` function for aggregation.
             polars::datatypes::DataType::UInt64 => {
-                // Retrieve the column as a contiguous slice of u64 values.
-                let col = series.u64().unwrap().cont_slice().unwrap();
-
                 // Cast the column to a supported i64 data type.
                 // Note that this operation should never overflow
                 // unless the database has around 2^64 rows, which is unfeasible.
-                let col = col.iter().map(|v| *v as i64).collect::<Vec<_>>();
+                let col = series
+                    .u64()
+                    .unwrap()
+                    .into_iter()
+                    .map(|v| v.map(|v| v as i64))
+                    .collect::<Vec<_>>();
 
                 columns.push(Arc::new(Int64Array::from(col)));
 
                 DataType::Int64
             }
             polars::datatypes::DataType::Utf8 => {
-                let col: Vec<_> = series
-                    .utf8()
-                    .unwrap()
-                    .into_iter()
-                    .map(|opt_v| opt_v.unwrap())
-                    .collect();
+                let col = series.utf8().unwrap().into_iter().collect::<Vec<_>>();
 
                 columns.push(Arc::new(StringArray::from(col)));
 
                 DataType::Utf8
             }
             polars::datatypes::DataType::Decimal(Some(38), Some(0)) => {
-                let col = series.decimal().unwrap().cont_slice().unwrap();
+                let col = series.decimal().unwrap().into_iter().collect::<Vec<_>>();
 
                 columns.push(Arc::new(
-                    Decimal128Array::from(col.to_vec())
+                    Decimal128Array::from(col)
                         .with_precision_and_scale(38, 0)
                         .unwrap(),
                 ));
 
                 DataType::Decimal128(38, 0)
             }
+            polars::datatypes::DataType::Datetime(time_unit, timezone) => {
+                let physical = series.to_physical_repr();
+                let col = physical.i64().unwrap().into_iter().collect::<Vec<_>>();
+                let timezone = timezone.as_deref();
+
+                match time_unit {
+                    polars::datatypes::TimeUnit::Milliseconds => columns.push(Arc::new(
+                        TimestampMillisecondArray::from(col).with_timezone_opt(timezone),
+                    )),
+                    polars::datatypes::TimeUnit::Microseconds => columns.push(Arc::new(
+                        TimestampMicrosecondArray::from(col).with_timezone_opt(timezone),
+                    )),
+                    polars::datatypes::TimeUnit::Nanoseconds => columns.push(Arc::new(
+                        TimestampNanosecondArray::from(col).with_timezone_opt(timezone),
+                    )),
+                }
+
+                columns.last().unwrap().data_type().clone()
+            }
+            polars::datatypes::DataType::Date => {
+                let physical = series.to_physical_repr();
+                let col = physical.i32().unwrap().into_iter().collect::<Vec<_>>();
+
+                columns.push(Arc::new(Date32Array::from(col)));
+
+                DataType::Date32
+            }
             _ => return None,
         };
 
-        column_fields.push(Field::new(field.name().as_str(), dt, false));
+        column_fields.push(Field::new(
+            field.name().as_str(),
+            dt,
+            series.null_count() > 0,
+        ));
     }
 
     let schema = Arc::new(Schema::new(column_fields));
@@ -236,4 +289,47 @@ mod tests {
             .unwrap();
         assert_eq!(dataframe_to_record_batch(dataframe).unwrap(), recordbatch);
     }
+
+    #[test]
+    fn we_can_convert_timestamps_and_dates_between_record_batches_and_dataframes() {
+        let timestamps = TimestampMillisecondArray::from(vec![Some(1_704_067_200_000), None])
+            .with_timezone("+00:00");
+        let dates = Date32Array::from(vec![19_723, -1]);
+        let recordbatch = RecordBatch::try_from_iter_with_nullable([
+            ("time", Arc::new(timestamps) as Arc<dyn Array>, true),
+            ("day", Arc::new(dates), false),
+        ])
+        .unwrap();
+        let dataframe = record_batch_to_dataframe(recordbatch.clone()).unwrap();
+        assert_eq!(
+            dataframe.dtypes(),
+            [
+                polars::datatypes::DataType::Datetime(
+                    polars::datatypes::TimeUnit::Milliseconds,
+                    Some("+00:00".to_string())
+                ),
+                polars::datatypes::DataType::Date
+            ]
+        );
+        assert_eq!(dataframe_to_record_batch(dataframe).unwrap(), recordbatch);
+    }
+
+    #[test]
+    fn we_convert_timestamps_in_seconds_to_milliseconds_in_dataframes() {
+        let recordbatch = RecordBatch::try_from_iter([(
+            "time",
+            Arc::new(arrow::array::TimestampSecondArray::from(vec![1, -2])) as Arc<dyn Array>,
+        )])
+        .unwrap();
+        let dataframe = record_batch_to_dataframe(recordbatch).unwrap();
+        let expected_recordbatch = RecordBatch::try_from_iter([(
+            "time",
+            Arc::new(TimestampMillisecondArray::from(vec![1000, -2000])) as Arc<dyn Array>,
+        )])
+        .unwrap();
+        assert_eq!(
+            dataframe_to_record_batch(dataframe).unwrap(),
+            expected_recordbatch
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use super::{
-    dataframe_to_record_batch, record_batch_to_dataframe, ArrayRefExt, Column, ColumnRef,
-    ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor, SchemaAccessor, TableRef,
-    TestAccessor,
+    add_order_keys, dataframe_to_record_batch, ordered_column_id, record_batch_to_dataframe,
+    split_validity, ArrayRefExt, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, SchemaAccessor, TableRef, TestAccessor,
 };
 use crate::base::scalar::{compute_commitment_for_testing, Curve25519Scalar};
 use arrow::{array::ArrayRef, datatypes::DataType, record_batch::RecordBatch};
@@ -10,7 +10,7 @@ use curve25519_dalek::ristretto::RistrettoPoint;
 use indexmap::IndexMap;
 use polars::prelude::DataFrame;
 use proof_of_sql_parser::Identifier;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// TestTable is used to simulate an in-memory table and commitment tracking table.
 #[derive(Clone)]
@@ -25,6 +25,7 @@ struct TestAccessorTable {
 pub struct RecordBatchTestAccessor {
     alloc: Bump,
     tables: HashMap<TableRef, TestAccessorTable>,
+    strictly_increasing_columns: HashSet<(TableRef, Identifier)>,
 }
 
 impl Clone for RecordBatchTestAccessor {
@@ -32,6 +33,7 @@ impl Clone for RecordBatchTestAccessor {
         Self {
             alloc: Bump::new(),
             tables: self.tables.clone(),
+            strictly_increasing_columns: self.strictly_increasing_columns.clone(),
         }
     }
 }
@@ -49,17 +51,16 @@ impl TestAccessor<RistrettoPoint> for RecordBatchTestAccessor {
         Self {
             alloc: Bump::new(),
             tables: HashMap::new(),
+            strictly_increasing_columns: HashSet::new(),
         }
     }
     fn add_table(&mut self, table_ref: TableRef, data: RecordBatch, table_offset: usize) {
         assert!(!self.tables.contains_key(&table_ref));
 
-        let columns: IndexMap<_, _> = data
-            .schema()
-            .fields()
-            .iter()
-            .zip(data.columns())
-            .map(|(f, v)| (f.name().parse().unwrap(), v.clone()))
+        let columns: IndexMap<_, _> = split_validity(&data)
+            .map(add_order_keys)
+            .unwrap()
+            .into_iter()
             .collect();
 
         let commitments = columns
@@ -89,7 +90,13 @@ impl TestAccessor<RistrettoPoint> for RecordBatchTestAccessor {
     fn get_column_names(&self, table_ref: TableRef) -> Vec<&str> {
         assert_eq!(self.tables.len(), 1);
         let table = self.tables.get(&table_ref).unwrap();
-        table.columns.keys().map(|c| c.as_str()).collect()
+        // Order key columns are derived from the data of their columns.
+        table
+            .columns
+            .keys()
+            .filter(|c| ordered_column_id(**c).is_none())
+            .map(|c| c.as_str())
+            .collect()
     }
 
     fn update_offset(&mut self, table_ref: TableRef, new_offset: usize) {
@@ -124,6 +131,14 @@ impl RecordBatchTestAccessor {
         dataframe_to_record_batch(f(&record_batch_to_dataframe(table.data.clone()).unwrap()))
             .unwrap()
     }
+
+    /// Declare that the column is strictly increasing in the order of the rows of the table
+    ///
+    /// Note: the data is not checked, so that proofs relying on a wrong declaration can be tested
+    pub fn set_strictly_increasing(&mut self, table_ref: TableRef, column_id: Identifier) {
+        self.strictly_increasing_columns
+            .insert((table_ref, column_id));
+    }
 }
 
 /// MetadataAccessor implementation for TestAccessor
@@ -166,9 +181,16 @@ impl SchemaAccessor for RecordBatchTestAccessor {
         table
             .columns
             .iter()
+            .filter(|(k, _)| ordered_column_id(**k).is_none())
             .map(|(k, dt)| (*k, DataType::try_into(dt.data_type().clone()).unwrap()))
             .collect()
     }
+
+    /// Return whether the column was declared strictly increasing with `set_strictly_increasing`
+    fn is_strictly_increasing(&self, table_ref: TableRef, column_id: Identifier) -> bool {
+        self.strictly_increasing_columns
+            .contains(&(table_ref, column_id))
+    }
 }
 
 /// CommitmentAccessor implementation for TestAccessor
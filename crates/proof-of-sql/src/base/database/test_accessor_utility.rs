@@ -1,8 +1,8 @@
 use crate::base::database::ColumnType;
 use arrow::{
     array::{
-        Array, BooleanArray, Decimal128Array, Decimal256Array, Int16Array, Int32Array, Int64Array,
-        StringArray,
+        make_array, Array, BooleanArray, Date32Array, Decimal128Array, Decimal256Array, Int16Array,
+        Int32Array, Int64Array, StringArray,
     },
     datatypes::{i256, DataType, Field, Schema},
     record_batch::RecordBatch,
@@ -115,6 +115,19 @@ pub fn make_random_test_accessor_data(
                 columns.push(Arc::new(StringArray::from(col)));
             }
             ColumnType::Scalar => unimplemented!("Scalar columns are not supported by arrow"),
+            ColumnType::TimeStamp(_, _) => {
+                let data_type = DataType::from(col_type);
+                column_fields.push(Field::new(*col_name, data_type.clone(), false));
+
+                // Timestamp arrays have the same layout as i64 arrays.
+                let data = Int64Array::from(values).into_data().into_builder();
+                columns.push(make_array(data.data_type(data_type).build().unwrap()));
+            }
+            ColumnType::Date => {
+                column_fields.push(Field::new(*col_name, DataType::Date32, false));
+                let values: Vec<i32> = values.iter().map(|x| *x as i32).collect();
+                columns.push(Arc::new(Date32Array::from(values)));
+            }
         }
     }
 
@@ -0,0 +1,133 @@
+//! Nullable columns are stored as two columns:
+//! 1. the values, where every null is replaced by the default value of the column type, and
+//! 2. a `Boolean` validity column, which is `false` exactly where the value is null.
+//!
+//! The validity column of `x` is named `__valid__x`. Since it is committed to like any other column,
+//! the commitment of a table also commits to the validity mask of each of its nullable columns.
+//! Queries pick up the validity column of every nullable column they reference.
+//!
+//! Note: every field that an arrow schema marks as nullable gets a validity column, even when it has no nulls.
+//! Arrow readers mark fields as nullable by default, so the commitments of such tables have one more column
+//! than before nullable columns were supported, and commitments made before then have to be recomputed.
+//! A nullable field whose name is too long for its validity column to be named is committed without one,
+//! as before, as long as it has no nulls.
+use arrow::{
+    array::{Array, ArrayRef, BooleanArray},
+    record_batch::RecordBatch,
+};
+use proof_of_sql_parser::{Identifier, ParseError};
+use std::sync::Arc;
+
+/// The prefix of the name of every validity column.
+const VALIDITY_COLUMN_PREFIX: &str = "__valid__";
+
+/// Returns the identifier of the validity column of `column_id`.
+///
+/// This fails when the resulting identifier is too long.
+pub fn validity_column_id(column_id: Identifier) -> Result<Identifier, ParseError> {
+    Identifier::try_new(format!("{VALIDITY_COLUMN_PREFIX}{column_id}"))
+}
+
+/// Returns the identifier of the column whose validity is stored in `column_id`, if `column_id` is a validity column.
+pub fn validated_column_id(column_id: Identifier) -> Option<Identifier> {
+    column_id
+        .as_str()
+        .strip_prefix(VALIDITY_COLUMN_PREFIX)
+        .and_then(|id| Identifier::try_new(id).ok())
+}
+
+/// Returns the columns of the record batch, each followed by its validity column when its field is nullable.
+///
+/// A nullable field whose name is too long for a validity column is only supported without nulls.
+///
+/// Note: the nulls are left in the value columns, so the conversions from arrow must replace them by default values.
+pub(crate) fn split_validity(
+    batch: &RecordBatch,
+) -> Result<Vec<(Identifier, ArrayRef)>, ParseError> {
+    let mut columns = Vec::with_capacity(batch.num_columns());
+    for (field, array) in batch.schema().fields().iter().zip(batch.columns()) {
+        let identifier = Identifier::try_new(field.name())?;
+        columns.push((identifier, array.clone()));
+        if !field.is_nullable() {
+            continue;
+        }
+        let validity_id = match validity_column_id(identifier) {
+            Ok(validity_id) => validity_id,
+            Err(_) if array.null_count() == 0 => continue,
+            Err(e) => return Err(e),
+        };
+        let validity = BooleanArray::from_iter((0..array.len()).map(|i| Some(array.is_valid(i))));
+        columns.push((validity_id, Arc::new(validity) as ArrayRef));
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::{
+        array::{Int64Array, StringArray},
+        datatypes::{DataType, Field, Schema},
+    };
+
+    #[test]
+    fn we_can_get_the_validity_column_of_a_column_and_back() {
+        let column_id: Identifier = "a".parse().unwrap();
+        let validity_id = validity_column_id(column_id).unwrap();
+        assert_eq!(validity_id.as_str(), "__valid__a");
+        assert_eq!(validated_column_id(validity_id), Some(column_id));
+        assert_eq!(validated_column_id(column_id), None);
+    }
+
+    #[test]
+    fn we_cannot_get_the_validity_column_of_a_column_with_a_long_name() {
+        let column_id = Identifier::try_new("a".repeat(60)).unwrap();
+        assert!(validity_column_id(column_id).is_err());
+    }
+
+    #[test]
+    fn we_can_split_the_validity_of_nullable_fields_off() {
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![
+                Field::new("a", DataType::Int64, true),
+                Field::new("b", DataType::Utf8, false),
+            ])),
+            vec![
+                Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])),
+                Arc::new(StringArray::from(vec!["x", "y", "z"])),
+            ],
+        )
+        .unwrap();
+        let columns = split_validity(&batch).unwrap();
+        let names: Vec<_> = columns.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(names, ["a", "__valid__a", "b"]);
+        assert_eq!(
+            columns[1]
+                .1
+                .as_any()
+                .downcast_ref::<BooleanArray>()
+                .unwrap(),
+            &BooleanArray::from(vec![true, false, true])
+        );
+    }
+
+    #[test]
+    fn we_can_split_the_validity_of_nullable_fields_with_long_names_only_without_nulls() {
+        let long_name = "a".repeat(60);
+        let batch = |values: Vec<Option<i64>>| {
+            RecordBatch::try_new(
+                Arc::new(Schema::new(vec![Field::new(
+                    long_name.as_str(),
+                    DataType::Int64,
+                    true,
+                )])),
+                vec![Arc::new(Int64Array::from(values))],
+            )
+            .unwrap()
+        };
+        let columns = split_validity(&batch(vec![Some(1), Some(2)])).unwrap();
+        let names: Vec<_> = columns.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(names, [long_name.as_str()]);
+        assert!(split_validity(&batch(vec![Some(1), None])).is_err());
+    }
+}
@@ -110,3 +110,68 @@ where
         }
     }
 }
+
+/// Given the point `point` (or `a`) with length nu, we can evaluate the lagrange basis of length 2^nu at that point.
+/// This is what [super::compute_evaluation_vector] does.
+/// Call the resulting evaluation vector A. This function computes `sum i * A[i] for i in 0..length`. That is:
+/// ```text
+/// 0 * (1-a[0])(1-a[1])...(1-a[nu-1]) +
+/// 1 * (a[0])(1-a[1])...(1-a[nu-1]) +
+/// 2 * (1-a[0])(a[1])...(1-a[nu-1]) +
+/// 3 * (a[0])(a[1])...(1-a[nu-1]) + ...
+/// ```
+/// In other words, this is the evaluation of the row index column `0, 1, ..., length - 1`.
+pub fn compute_truncated_lagrange_basis_index_sum<F>(length: usize, point: &[F]) -> F
+where
+    F: One + Zero + Mul<Output = F> + Add<Output = F> + Sub<Output = F> + Copy,
+{
+    compute_truncated_lagrange_basis_index_sum_impl(length, point).1
+}
+
+// The returned value from this function is (part_sum, part_index_sum, full_index_sum, full_length).
+// The sums are the truncated basis sum and the truncated index sum.
+// The full index sum is what the index sum would be if it were not truncated, and full_length is 2^nu.
+fn compute_truncated_lagrange_basis_index_sum_impl<F>(length: usize, point: &[F]) -> (F, F, F, F)
+where
+    F: One + Zero + Mul<Output = F> + Add<Output = F> + Sub<Output = F> + Copy,
+{
+    let nu = point.len();
+    if nu == 0 {
+        assert!(length <= 1);
+        let part_sum = if length == 1 { F::one() } else { F::zero() };
+        (part_sum, F::zero(), F::zero(), F::one())
+    } else {
+        let first_half_term = F::one() - point[nu - 1];
+        let second_half_term = point[nu - 1];
+        let half_full_length = 1 << (nu - 1);
+        let sub_part_length = if length >= half_full_length {
+            length - half_full_length
+        } else {
+            length
+        };
+        let (sub_part_sum, sub_part_index_sum, sub_full_index_sum, sub_full_length) =
+            compute_truncated_lagrange_basis_index_sum_impl(sub_part_length, &point[..nu - 1]);
+        // Every index in the second half is offset by the length of the first half.
+        let (part_sum, part_index_sum) = if length >= half_full_length {
+            (
+                first_half_term + sub_part_sum * second_half_term,
+                sub_full_index_sum * first_half_term
+                    + (sub_full_length * sub_part_sum + sub_part_index_sum) * second_half_term,
+            )
+        } else {
+            (
+                sub_part_sum * first_half_term,
+                sub_part_index_sum * first_half_term,
+            )
+        };
+        // The full sum of the sub-iteration is always 1.
+        let full_index_sum = sub_full_index_sum * first_half_term
+            + (sub_full_length + sub_full_index_sum) * second_half_term;
+        (
+            part_sum,
+            part_index_sum,
+            full_index_sum,
+            sub_full_length + sub_full_length,
+        )
+    }
+}
@@ -1,7 +1,7 @@
 use crate::base::{
     polynomial::{
-        compute_evaluation_vector, compute_truncated_lagrange_basis_inner_product,
-        compute_truncated_lagrange_basis_sum,
+        compute_evaluation_vector, compute_truncated_lagrange_basis_index_sum,
+        compute_truncated_lagrange_basis_inner_product, compute_truncated_lagrange_basis_sum,
     },
     scalar::Curve25519Scalar,
 };
@@ -338,6 +338,16 @@ fn compute_truncated_lagrange_basis_inner_product_gives_correct_values_with_3_va
     assert_eq!(compute_truncated_lagrange_basis_inner_product(0, &a, &b), 0);
 }
 
+#[test]
+fn compute_truncated_lagrange_basis_index_sum_gives_correct_values_with_2_variables_using_i32() {
+    let point: Vec<i32> = vec![2, 5];
+    assert_eq!(compute_truncated_lagrange_basis_index_sum(4, &point), 12); // This is 0+1(2)(1-5)+2(1-2)(5)+3(2)(5)
+    assert_eq!(compute_truncated_lagrange_basis_index_sum(3, &point), -18); // This is 0+1(2)(1-5)+2(1-2)(5)
+    assert_eq!(compute_truncated_lagrange_basis_index_sum(2, &point), -8); // This is 0+1(2)(1-5)
+    assert_eq!(compute_truncated_lagrange_basis_index_sum(1, &point), 0);
+    assert_eq!(compute_truncated_lagrange_basis_index_sum(0, &point), 0);
+}
+
 #[test]
 fn compute_truncated_lagrange_basis_sum_matches_sum_of_result_from_compute_evaluation_vector() {
     use ark_std::rand::{
@@ -401,3 +411,35 @@ fn compute_truncated_lagrange_basis_inner_product_matches_inner_product_of_resul
         // -----------------------------------------------------------
     }
 }
+
+#[test]
+fn compute_truncated_lagrange_basis_index_sum_matches_index_weighted_sum_of_result_from_compute_evaluation_vector(
+) {
+    use ark_std::rand::{
+        distributions::{Distribution, Uniform},
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let dist = Uniform::new(2, 10);
+    for _ in 0..20 {
+        let variables = dist.sample(&mut rng);
+        let length = Uniform::new((1 << (variables - 1)) + 1, 1 << variables).sample(&mut rng);
+        let point: Vec<_> = iter::repeat_with(|| Curve25519Scalar::rand(&mut rng))
+            .take(variables)
+            .collect();
+        let mut eval_vec = vec![Curve25519Scalar::zero(); length];
+        compute_evaluation_vector(&mut eval_vec, &point);
+        // ---------------- This is the actual test --------------------
+        assert_eq!(
+            compute_truncated_lagrange_basis_index_sum(length, &point),
+            eval_vec
+                .into_iter()
+                .enumerate()
+                .map(|(i, x)| Curve25519Scalar::from(i as u64) * x)
+                .sum()
+        );
+        // -----------------------------------------------------------
+    }
+}
@@ -102,6 +102,8 @@ impl<S: Scalar> MultilinearExtension<S> for Column<'_, S> {
             Column::VarChar((_, c)) => c.inner_product(evaluation_vec),
             Column::Int128(c) => c.inner_product(evaluation_vec),
             Column::Decimal75(_, _, c) => c.inner_product(evaluation_vec),
+            Column::TimeStamp(_, _, c) => c.inner_product(evaluation_vec),
+            Column::Date(c) => c.inner_product(evaluation_vec),
         }
     }
 
@@ -115,6 +117,8 @@ impl<S: Scalar> MultilinearExtension<S> for Column<'_, S> {
             Column::VarChar((_, c)) => c.mul_add(res, multiplier),
             Column::Int128(c) => c.mul_add(res, multiplier),
             Column::Decimal75(_, _, c) => c.mul_add(res, multiplier),
+            Column::TimeStamp(_, _, c) => c.mul_add(res, multiplier),
+            Column::Date(c) => c.mul_add(res, multiplier),
         }
     }
 
@@ -128,6 +132,8 @@ impl<S: Scalar> MultilinearExtension<S> for Column<'_, S> {
             Column::VarChar((_, c)) => c.to_sumcheck_term(num_vars),
             Column::Int128(c) => c.to_sumcheck_term(num_vars),
             Column::Decimal75(_, _, c) => c.to_sumcheck_term(num_vars),
+            Column::TimeStamp(_, _, c) => c.to_sumcheck_term(num_vars),
+            Column::Date(c) => c.to_sumcheck_term(num_vars),
         }
     }
 
@@ -141,6 +147,8 @@ impl<S: Scalar> MultilinearExtension<S> for Column<'_, S> {
             Column::VarChar((_, c)) => MultilinearExtension::<S>::id(c),
             Column::Int128(c) => MultilinearExtension::<S>::id(c),
             Column::Decimal75(_, _, c) => MultilinearExtension::<S>::id(c),
+            Column::TimeStamp(_, _, c) => MultilinearExtension::<S>::id(c),
+            Column::Date(c) => MultilinearExtension::<S>::id(c),
         }
     }
 }
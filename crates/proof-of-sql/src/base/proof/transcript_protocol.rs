@@ -154,6 +154,10 @@ pub enum MessageLabel {
     TableLength,
     /// Represents an offset for a generator.
     GeneratorOffset,
+    /// Represents the commitments sent along with the result of a query.
+    QueryFirstRoundCommit,
+    /// Represents the columns sent in the clear along with the result of a query.
+    QueryPublicColumns,
 }
 
 impl MessageLabel {
@@ -182,6 +186,8 @@ impl MessageLabel {
             MessageLabel::ProofExpr => b"proofexpr v1",
             MessageLabel::TableLength => b"tablelength v1",
             MessageLabel::GeneratorOffset => b"generatoroffset v1",
+            MessageLabel::QueryFirstRoundCommit => b"queryfirstroundcommit v1",
+            MessageLabel::QueryPublicColumns => b"querypubliccolumns v1",
         }
     }
 }
@@ -280,6 +280,10 @@ fn compute_dory_commitment(
         CommittableColumn::Scalar(column) => compute_dory_commitment_impl(column, offset, setup),
         CommittableColumn::VarChar(column) => compute_dory_commitment_impl(column, offset, setup),
         CommittableColumn::Boolean(column) => compute_dory_commitment_impl(column, offset, setup),
+        CommittableColumn::TimeStamp(_, _, column) => {
+            compute_dory_commitment_impl(column, offset, setup)
+        }
+        CommittableColumn::Date(column) => compute_dory_commitment_impl(column, offset, setup),
     }
 }
 
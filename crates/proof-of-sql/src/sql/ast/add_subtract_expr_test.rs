@@ -0,0 +1,149 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType, OwnedTable,
+            OwnedTableTestAccessor, RandomTestAccessorDescriptor, TestAccessor,
+        },
+        math::decimal::Precision,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::{test_utility::*, ProvableExpr, ProvableExprPlan},
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
+};
+use bumpalo::Bump;
+use curve25519_dalek::RistrettoPoint;
+use polars::prelude::*;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let descr = RandomTestAccessorDescriptor {
+        min_rows: 1,
+        max_rows: 20,
+        min_value: -3,
+        max_value: 3,
+    };
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let cols = [
+        ("a", ColumnType::BigInt),
+        ("b", ColumnType::VarChar),
+        ("c", ColumnType::BigInt),
+        ("d", ColumnType::BigInt),
+    ];
+    for _ in 0..20 {
+        let data = make_random_test_accessor_data(&mut rng, &cols, &descr);
+        let data = OwnedTable::<Curve25519Scalar>::try_from(data).unwrap();
+        let filter_val = Uniform::new(descr.min_value, descr.max_value + 1).sample(&mut rng);
+
+        let t = "sxt.t".parse().unwrap();
+        let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+        accessor.add_table(t, data.clone(), offset);
+        let ast = dense_filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            equal(
+                column(t, "a", &accessor),
+                subtract(column(t, "c", &accessor), const_bigint(filter_val)),
+            ),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        let expected =
+            data.apply_polars_filter(&["a", "b"], col("a").eq(col("c") - lit(filter_val)));
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(75);
+}
+
+#[test]
+fn we_can_prove_a_query_comparing_a_sum_of_columns() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 4, 5]),
+        int128("b", [4, 3, 2, 1, 0]),
+        varchar("c", ["t", "ru", "e", "s", "true"]),
+        bigint("d", [5, 5, 6, 5, 4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a", "c"], &accessor),
+        tab(t),
+        equal(
+            add(column(t, "a", &accessor), column(t, "b", &accessor)),
+            column(t, "d", &accessor),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("a", [1, 2, 4]), varchar("c", ["t", "ru", "s"])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_an_inequality_on_a_difference_of_decimal_columns() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 4]),
+        decimal75("b", 5, 2, [150, 275, 300, -100]),
+        decimal75("c", 4, 1, [10, 10, 20, 0]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // b - c <= 0.5
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        lte(
+            subtract(column(t, "b", &accessor), column(t, "c", &accessor)),
+            const_decimal75(2, 1, 5),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("a", [1, 4])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_an_add_subtract_expr_using_result_evaluate() {
+    let data = owned_table([
+        smallint("a", [1_i16, 2, 3, 4]),
+        int("b", [0_i32, 1, 0, 1]),
+        decimal75("c", 4, 1, [0, 5, 10, -15]),
+    ]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let add_expr: ProvableExprPlan<RistrettoPoint> = subtract(
+        add(column(t, "a", &accessor), column(t, "b", &accessor)),
+        column(t, "c", &accessor),
+    );
+    let alloc = Bump::new();
+    let res = add_expr.result_evaluate(4, &alloc, &accessor);
+    let expected_res_scalar = [10, 25, 20, 65]
+        .iter()
+        .map(|v| Curve25519Scalar::from(*v))
+        .collect::<Vec<_>>();
+    let expected_res = Column::Decimal75(Precision::new(13).unwrap(), 1, &expected_res_scalar);
+    assert_eq!(res, expected_res);
+}
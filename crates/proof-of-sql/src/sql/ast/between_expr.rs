@@ -0,0 +1,152 @@
+use super::{
+    count_or, count_sign, prover_evaluate_or, prover_evaluate_sign, result_evaluate_or,
+    result_evaluate_sign, scale_and_subtract, scale_and_subtract_eval, verifier_evaluate_or,
+    verifier_evaluate_sign, ProvableExpr, ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, VerificationBuilder},
+};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Provable AST expression for a range check e.g. `a BETWEEN low AND high`
+///
+/// Writing this as `a >= low AND a <= high` proves two inequalities, each of which
+/// needs a zero check on top of its sign decomposition, because the sign of zero is ambiguous.
+/// Instead, this decomposes `2 * (a - low) + 1` and `2 * (high - a) + 1`. Both are odd, so never zero,
+/// and the row is out of range exactly when one of them is negative.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BetweenExpr<C: Commitment> {
+    expr: Box<ProvableExprPlan<C>>,
+    low: Box<ProvableExprPlan<C>>,
+    high: Box<ProvableExprPlan<C>>,
+}
+
+impl<C: Commitment> BetweenExpr<C> {
+    /// Create a new BETWEEN expression
+    pub fn new(
+        expr: Box<ProvableExprPlan<C>>,
+        low: Box<ProvableExprPlan<C>>,
+        high: Box<ProvableExprPlan<C>>,
+    ) -> Self {
+        Self { expr, low, high }
+    }
+}
+
+/// Computes `2 * diff + 1`, which is never zero.
+fn odd_shift<'a, S: Scalar>(alloc: &'a Bump, diff: &[S]) -> &'a [S] {
+    alloc.alloc_slice_fill_with(diff.len(), |i| S::TWO * diff[i] + S::ONE)
+}
+
+impl<C: Commitment> ProvableExpr<C> for BetweenExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        self.low.count(builder)?;
+        self.high.count(builder)?;
+        count_sign(builder)?;
+        count_sign(builder)?;
+        count_or(builder);
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "BetweenExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let expr_column = self.expr.result_evaluate(table_length, alloc, accessor);
+        let low_column = self.low.result_evaluate(table_length, alloc, accessor);
+        let high_column = self.high.result_evaluate(table_length, alloc, accessor);
+        let low_diff = scale_and_subtract(alloc, expr_column.clone(), low_column, false)
+            .expect("Failed to scale and subtract");
+        let high_diff = scale_and_subtract(alloc, high_column, expr_column, false)
+            .expect("Failed to scale and subtract");
+
+        // sign(2 * (expr - low) + 1) == -1, i.e. expr < low
+        let below = result_evaluate_sign(table_length, alloc, odd_shift(alloc, low_diff));
+
+        // sign(2 * (high - expr) + 1) == -1, i.e. expr > high
+        let above = result_evaluate_sign(table_length, alloc, odd_shift(alloc, high_diff));
+
+        // !(below || above)
+        let outside = result_evaluate_or(table_length, alloc, below, above);
+        Column::Boolean(alloc.alloc_slice_fill_with(table_length, |i| !outside[i]))
+    }
+
+    #[tracing::instrument(name = "BetweenExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let expr_column = self.expr.prover_evaluate(builder, alloc, accessor);
+        let low_column = self.low.prover_evaluate(builder, alloc, accessor);
+        let high_column = self.high.prover_evaluate(builder, alloc, accessor);
+        let low_diff = scale_and_subtract(alloc, expr_column.clone(), low_column, false)
+            .expect("Failed to scale and subtract");
+        let high_diff = scale_and_subtract(alloc, high_column, expr_column, false)
+            .expect("Failed to scale and subtract");
+
+        // sign(2 * (expr - low) + 1) == -1, i.e. expr < low
+        let below = prover_evaluate_sign(builder, alloc, odd_shift(alloc, low_diff));
+
+        // sign(2 * (high - expr) + 1) == -1, i.e. expr > high
+        let above = prover_evaluate_sign(builder, alloc, odd_shift(alloc, high_diff));
+
+        // !(below || above)
+        let outside = prover_evaluate_or(builder, alloc, below, above);
+        Column::Boolean(alloc.alloc_slice_fill_with(outside.len(), |i| !outside[i]))
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let expr_eval = self.expr.verifier_evaluate(builder, accessor)?;
+        let low_eval = self.low.verifier_evaluate(builder, accessor)?;
+        let high_eval = self.high.verifier_evaluate(builder, accessor)?;
+        let expr_scale = self.expr.data_type().scale().unwrap_or(0);
+        let low_scale = self.low.data_type().scale().unwrap_or(0);
+        let high_scale = self.high.data_type().scale().unwrap_or(0);
+        let low_diff_eval = scale_and_subtract_eval(expr_eval, low_eval, expr_scale, low_scale)
+            .expect("Failed to scale and subtract");
+        let high_diff_eval = scale_and_subtract_eval(high_eval, expr_eval, high_scale, expr_scale)
+            .expect("Failed to scale and subtract");
+
+        // sign(2 * (expr - low) + 1) == -1, i.e. expr < low
+        let below =
+            verifier_evaluate_sign(builder, C::Scalar::TWO * low_diff_eval + one_eval, one_eval)?;
+
+        // sign(2 * (high - expr) + 1) == -1, i.e. expr > high
+        let above = verifier_evaluate_sign(
+            builder,
+            C::Scalar::TWO * high_diff_eval + one_eval,
+            one_eval,
+        )?;
+
+        // !(below || above)
+        Ok(one_eval - verifier_evaluate_or(builder, &below, &above))
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+        self.low.get_column_references(columns);
+        self.high.get_column_references(columns);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType,
+            OwnedTableTestAccessor, RandomTestAccessorDescriptor, RecordBatchTestAccessor,
+            TestAccessor,
+        },
+    },
+    record_batch,
+    sql::{
+        ast::{
+            test_expr::TestExprNode,
+            test_utility::{
+                between, cols_expr_plan, column, const_bigint, const_decimal75, const_varchar,
+                dense_filter, not, tab,
+            },
+            ProvableExpr, ProvableExprPlan,
+        },
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
+};
+use arrow::record_batch::RecordBatch;
+use bumpalo::Bump;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use polars::prelude::*;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+fn create_test_between_expr(
+    table_ref: &str,
+    results: &[&str],
+    filter_col: &str,
+    low: i64,
+    high: i64,
+    data: RecordBatch,
+    offset: usize,
+) -> TestExprNode {
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    let t = table_ref.parse().unwrap();
+    accessor.add_table(t, data, offset);
+    let between_expr = between(
+        column(t, filter_col, &accessor),
+        const_bigint(low),
+        const_bigint(high),
+    );
+    let df_filter = col(filter_col)
+        .gt_eq(lit(low))
+        .and(col(filter_col).lt_eq(lit(high)));
+    TestExprNode::new(t, results, between_expr, df_filter, accessor)
+}
+
+#[test]
+fn we_can_prove_a_between_query_with_no_rows() {
+    let data = record_batch!(
+        "a" => Vec::<i64>::new(),
+        "b" => Vec::<i64>::new(),
+    );
+    let test_expr = create_test_between_expr("sxt.t", &["a"], "b", -1, 1, data, 0);
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => Vec::<i64>::new());
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_including_both_bounds() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3, 4, 5, 6],
+        "b" => [-3_i64, -2, 0, 4, 5, 9],
+    );
+    let test_expr = create_test_between_expr("sxt.t", &["a"], "b", -2, 5, data, 0);
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => [2_i64, 3, 4, 5]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_with_a_single_value_range_and_a_non_zero_offset() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3],
+        "b" => [7_i64, 8, 7],
+    );
+    let test_expr = create_test_between_expr("sxt.t", &["a"], "b", 7, 7, data, 43);
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => [1_i64, 3]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_with_an_empty_range() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3],
+        "b" => [4_i64, 5, 6],
+    );
+    let test_expr = create_test_between_expr("sxt.t", &["a"], "b", 6, 4, data, 0);
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => Vec::<i64>::new());
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_with_column_bounds() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3, 4],
+        "lo" => [0_i64, 5, -3, 2],
+        "hi" => [10_i64, 6, -1, 2],
+        "x" => [10_i64, 4, -2, 3],
+    );
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let between_expr = between(
+        column(t, "x", &accessor),
+        column(t, "lo", &accessor),
+        column(t, "hi", &accessor),
+    );
+    let df_filter = col("x").gt_eq(col("lo")).and(col("x").lt_eq(col("hi")));
+    let test_expr = TestExprNode::new(t, &["a"], between_expr, df_filter, accessor);
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => [1_i64, 3]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_with_extreme_values() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3, 4],
+        "b" => [i64::MIN, i64::MAX, 0, i64::MIN + 1],
+    );
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let between_expr = between(
+        column(t, "b", &accessor),
+        const_bigint(i64::MIN + 1),
+        const_bigint(i64::MAX),
+    );
+    let df_filter = col("b")
+        .gt_eq(lit(i64::MIN + 1))
+        .and(col("b").lt_eq(lit(i64::MAX)));
+    let test_expr = TestExprNode::new(t, &["a"], between_expr, df_filter, accessor);
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => [2_i64, 3, 4]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_with_bounds_of_other_scales() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 4, 5]),
+        decimal75("d", 10, 2, [149, 150, 200, 275, 276]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // d BETWEEN 1.5 AND 2.75
+    let expr = dense_filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        between(
+            column(t, "d", &accessor),
+            const_decimal75(2, 1, 15),
+            const_decimal75(3, 2, 275),
+        ),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("a", [2, 3, 4])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_not_between_query() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3, 4],
+        "b" => [1_i64, 2, 3, 4],
+    );
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let not_between_expr = not(between(
+        column(t, "b", &accessor),
+        const_bigint(2),
+        const_bigint(3),
+    ));
+    let df_filter = col("b")
+        .gt_eq(lit(2_i64))
+        .and(col("b").lt_eq(lit(3_i64)))
+        .not();
+    let test_expr = TestExprNode::new(t, &["a"], not_between_expr, df_filter, accessor);
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => [1_i64, 4]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_create_a_between_expr_with_mismatched_bounds() {
+    let data = owned_table([bigint("b", [1, 2]), varchar("s", ["x", "y"])]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_between(
+            column(t, "b", &accessor),
+            const_bigint(1),
+            const_varchar("z"),
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_between(
+            column(t, "s", &accessor),
+            const_varchar("a"),
+            const_varchar("z"),
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let descr = RandomTestAccessorDescriptor {
+        min_rows: 1,
+        max_rows: 20,
+        min_value: -3,
+        max_value: 3,
+    };
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let cols = [
+        ("a", ColumnType::BigInt),
+        ("b", ColumnType::VarChar),
+        ("c", ColumnType::BigInt),
+    ];
+    for _ in 0..20 {
+        let data = make_random_test_accessor_data(&mut rng, &cols, &descr);
+        let bound_dist = Uniform::new(descr.min_value, descr.max_value + 1);
+        let low = bound_dist.sample(&mut rng);
+        let high = bound_dist.sample(&mut rng);
+        let test_expr =
+            create_test_between_expr("sxt.t", &["a", "b", "c"], "c", low, high, data, offset);
+        let res = test_expr.verify_expr();
+        let expected_res = test_expr.query_table();
+        assert_eq!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_non_zero_offset() {
+    test_random_tables_with_given_offset(123);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_between_expr_using_result_evaluate() {
+    let data = owned_table([bigint("a", [-1, 0, 5, 6]), bigint("b", [0, 1, 2, 3])]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let between_expr: ProvableExprPlan<RistrettoPoint> =
+        between(column(t, "a", &accessor), const_bigint(0), const_bigint(5));
+    let alloc = Bump::new();
+    let res = between_expr.result_evaluate(4, &alloc, &accessor);
+    let expected_res = Column::Boolean(&[false, true, true, false]);
+    assert_eq!(res, expected_res);
+}
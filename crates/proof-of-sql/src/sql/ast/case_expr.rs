@@ -0,0 +1,296 @@
+use super::{try_common_column_type, ProvableExpr, ProvableExprPlan};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        math::decimal::scale_scalar,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use bumpalo::Bump;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Provable AST expression for a conditional e.g. `CASE WHEN a >= 100 THEN 'big' ELSE 'small' END`
+///
+/// The conditions are boolean columns, i.e. selectors, so the result is folded from the last branch to the first:
+/// with `acc` the result of the later branches, the result of a branch is `acc + when * (then - acc)`.
+/// Each step is committed to as an intermediate MLE and checked by a subpolynomial.
+///
+/// Note: a missing `ELSE` is not represented here. The results of such rows are `NULL`,
+/// which is tracked by the validity of the expression, so any branch may be used as the `ELSE`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CaseExpr<C: Commitment> {
+    when_then: Vec<(ProvableExprPlan<C>, ProvableExprPlan<C>)>,
+    else_expr: Box<ProvableExprPlan<C>>,
+}
+
+impl<C: Commitment> CaseExpr<C> {
+    /// Create a new CASE expression
+    pub fn new(
+        when_then: Vec<(ProvableExprPlan<C>, ProvableExprPlan<C>)>,
+        else_expr: Box<ProvableExprPlan<C>>,
+    ) -> Self {
+        Self {
+            when_then,
+            else_expr,
+        }
+    }
+
+    /// The possible results, ending with the `ELSE` result.
+    fn results(&self) -> impl Iterator<Item = &ProvableExprPlan<C>> {
+        self.when_then
+            .iter()
+            .map(|(_, then)| then)
+            .chain([self.else_expr.as_ref()])
+    }
+
+    /// The scales by which the possible results are scaled up to the scale of the result, in the order of `results`.
+    fn result_scalings(&self) -> Vec<i8> {
+        let scale = ProvableExpr::<C>::data_type(self).scale().unwrap_or(0);
+        self.results()
+            .map(|result| scale - result.data_type().scale().unwrap_or(0))
+            .collect()
+    }
+}
+
+/// Returns, for each row, the index of the first true condition, or the number of conditions if none is true.
+fn branch_indexes(conditions: &[&[bool]], table_length: usize) -> Vec<usize> {
+    (0..table_length)
+        .map(|i| {
+            conditions
+                .iter()
+                .position(|condition| condition[i])
+                .unwrap_or(conditions.len())
+        })
+        .collect()
+}
+
+/// Picks the value of each row from the column of its branch.
+fn select_slice<'a, S: Scalar, T: Copy>(
+    columns: &[Column<'a, S>],
+    indexes: &[usize],
+    alloc: &'a Bump,
+    as_slice: impl Fn(&Column<'a, S>) -> Option<&'a [T]>,
+) -> &'a [T] {
+    let slices: Vec<&'a [T]> = columns
+        .iter()
+        .map(|column| as_slice(column).expect("all results have the same type"))
+        .collect();
+    alloc.alloc_slice_fill_with(indexes.len(), |i| slices[indexes[i]][i])
+}
+
+/// Builds the result column of the given type from the columns of the possible results and the scalars of the result,
+/// which are already selected.
+fn select_column<'a, S: Scalar>(
+    data_type: ColumnType,
+    columns: &[Column<'a, S>],
+    indexes: &[usize],
+    scalars: &'a [S],
+    alloc: &'a Bump,
+) -> Column<'a, S> {
+    match data_type {
+        ColumnType::Decimal75(precision, scale) => Column::Decimal75(precision, scale, scalars),
+        ColumnType::Scalar => Column::Scalar(scalars),
+        ColumnType::Boolean => {
+            Column::Boolean(select_slice(columns, indexes, alloc, |c| match c {
+                Column::Boolean(c) => Some(*c),
+                _ => None,
+            }))
+        }
+        ColumnType::SmallInt => {
+            Column::SmallInt(select_slice(columns, indexes, alloc, |c| match c {
+                Column::SmallInt(c) => Some(*c),
+                _ => None,
+            }))
+        }
+        ColumnType::Int => Column::Int(select_slice(columns, indexes, alloc, |c| match c {
+            Column::Int(c) => Some(*c),
+            _ => None,
+        })),
+        ColumnType::BigInt => Column::BigInt(select_slice(columns, indexes, alloc, |c| match c {
+            Column::BigInt(c) => Some(*c),
+            _ => None,
+        })),
+        ColumnType::Int128 => Column::Int128(select_slice(columns, indexes, alloc, |c| match c {
+            Column::Int128(c) => Some(*c),
+            _ => None,
+        })),
+        ColumnType::VarChar => Column::VarChar((
+            select_slice(columns, indexes, alloc, |c| match c {
+                Column::VarChar((c, _)) => Some(*c),
+                _ => None,
+            }),
+            scalars,
+        )),
+        ColumnType::TimeStamp(unit, timezone) => Column::TimeStamp(
+            unit,
+            timezone,
+            select_slice(columns, indexes, alloc, |c| match c {
+                Column::TimeStamp(_, _, c) => Some(*c),
+                _ => None,
+            }),
+        ),
+        ColumnType::Date => Column::Date(select_slice(columns, indexes, alloc, |c| match c {
+            Column::Date(c) => Some(*c),
+            _ => None,
+        })),
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for CaseExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        for (when, then) in &self.when_then {
+            when.count(builder)?;
+            then.count(builder)?;
+        }
+        self.else_expr.count(builder)?;
+        builder.count_subpolynomials(self.when_then.len());
+        builder.count_intermediate_mles(self.when_then.len());
+        builder.count_degree(3);
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        self.results()
+            .map(|result| result.data_type())
+            .try_fold(self.else_expr.data_type(), try_common_column_type)
+            .expect("Failed to find a common type of the results")
+    }
+
+    #[tracing::instrument(name = "CaseExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let mut conditions = Vec::with_capacity(self.when_then.len());
+        let mut columns = Vec::with_capacity(self.when_then.len() + 1);
+        for (when, then) in &self.when_then {
+            let when_column = when.result_evaluate(table_length, alloc, accessor);
+            conditions.push(when_column.as_boolean().expect("condition is not boolean"));
+            columns.push(then.result_evaluate(table_length, alloc, accessor));
+        }
+        columns.push(
+            self.else_expr
+                .result_evaluate(table_length, alloc, accessor),
+        );
+
+        let indexes = branch_indexes(&conditions, table_length);
+        let scaled: Vec<_> = columns
+            .iter()
+            .zip(self.result_scalings())
+            .map(|(column, scaling)| column.to_scalar_with_scaling(scaling))
+            .collect();
+        let scalars = alloc.alloc_slice_fill_with(table_length, |i| scaled[indexes[i]][i]);
+        select_column(
+            ProvableExpr::<C>::data_type(self),
+            &columns,
+            &indexes,
+            scalars,
+            alloc,
+        )
+    }
+
+    #[tracing::instrument(name = "CaseExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let mut conditions = Vec::with_capacity(self.when_then.len());
+        let mut columns = Vec::with_capacity(self.when_then.len() + 1);
+        for (when, then) in &self.when_then {
+            let when_column = when.prover_evaluate(builder, alloc, accessor);
+            conditions.push(when_column.as_boolean().expect("condition is not boolean"));
+            columns.push(then.prover_evaluate(builder, alloc, accessor));
+        }
+        columns.push(self.else_expr.prover_evaluate(builder, alloc, accessor));
+        let table_length = columns[0].len();
+        let scaled: Vec<&'a [C::Scalar]> = columns
+            .iter()
+            .zip(self.result_scalings())
+            .map(|(column, scaling)| {
+                alloc.alloc_slice_copy(&column.to_scalar_with_scaling(scaling)) as &[_]
+            })
+            .collect();
+
+        let mut acc: &'a [C::Scalar] = scaled[self.when_then.len()];
+        for (when, then) in conditions.iter().zip(&scaled[..self.when_then.len()]).rev() {
+            // case_result = when ? then : acc
+            let case_result: &'a [C::Scalar] =
+                alloc.alloc_slice_fill_with(
+                    table_length,
+                    |i| if when[i] { then[i] } else { acc[i] },
+                );
+            builder.produce_intermediate_mle(case_result);
+
+            // subpolynomial: case_result - acc - when * then + when * acc
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (C::Scalar::one(), vec![Box::new(case_result)]),
+                    (-C::Scalar::one(), vec![Box::new(acc)]),
+                    (-C::Scalar::one(), vec![Box::new(*when), Box::new(*then)]),
+                    (C::Scalar::one(), vec![Box::new(*when), Box::new(acc)]),
+                ],
+            );
+            acc = case_result;
+        }
+
+        let indexes = branch_indexes(&conditions, table_length);
+        select_column(
+            ProvableExpr::<C>::data_type(self),
+            &columns,
+            &indexes,
+            acc,
+            alloc,
+        )
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let mut conditions = Vec::with_capacity(self.when_then.len());
+        let mut results = Vec::with_capacity(self.when_then.len() + 1);
+        for (when, then) in &self.when_then {
+            conditions.push(when.verifier_evaluate(builder, accessor)?);
+            results.push(then.verifier_evaluate(builder, accessor)?);
+        }
+        results.push(self.else_expr.verifier_evaluate(builder, accessor)?);
+        let scaled = results
+            .into_iter()
+            .zip(self.result_scalings())
+            .map(|(result, scaling)| scale_scalar(result, scaling))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Failed to scale the results");
+
+        let mut acc = scaled[self.when_then.len()];
+        for (when, then) in conditions.iter().zip(&scaled[..self.when_then.len()]).rev() {
+            // case_result
+            let case_result = builder.consume_intermediate_mle();
+
+            // subpolynomial: case_result - acc - when * then + when * acc
+            let eval = builder.mle_evaluations.random_evaluation
+                * (case_result - acc - *when * *then + *when * acc);
+            builder.produce_sumcheck_subpolynomial_evaluation(&eval);
+            acc = case_result;
+        }
+        Ok(acc)
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        for (when, then) in &self.when_then {
+            when.get_column_references(columns);
+            then.get_column_references(columns);
+        }
+        self.else_expr.get_column_references(columns);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType, OwnedTable,
+            OwnedTableTestAccessor, RandomTestAccessorDescriptor, TestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::{test_utility::*, ProvableExpr, ProvableExprPlan},
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
+};
+use bumpalo::Bump;
+use curve25519_dalek::RistrettoPoint;
+use polars::prelude::{col, lit, when};
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let descr = RandomTestAccessorDescriptor {
+        min_rows: 1,
+        max_rows: 20,
+        min_value: -3,
+        max_value: 3,
+    };
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let cols = [
+        ("a", ColumnType::BigInt),
+        ("b", ColumnType::VarChar),
+        ("c", ColumnType::BigInt),
+        ("d", ColumnType::BigInt),
+    ];
+    for _ in 0..20 {
+        let data = make_random_test_accessor_data(&mut rng, &cols, &descr);
+        let data = OwnedTable::<Curve25519Scalar>::try_from(data).unwrap();
+        let when_val = Uniform::new(descr.min_value, descr.max_value + 1).sample(&mut rng);
+        let filter_val = Uniform::new(descr.min_value, descr.max_value + 1).sample(&mut rng);
+
+        let t = "sxt.t".parse().unwrap();
+        let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+        accessor.add_table(t, data.clone(), offset);
+        let ast = dense_filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            equal(
+                case(
+                    vec![(
+                        gte(column(t, "a", &accessor), const_bigint(when_val)),
+                        column(t, "c", &accessor),
+                    )],
+                    column(t, "d", &accessor),
+                ),
+                const_bigint(filter_val),
+            ),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        let expected = data.apply_polars_filter(
+            &["a", "b"],
+            when(col("a").gt_eq(lit(when_val)))
+                .then(col("c"))
+                .otherwise(col("d"))
+                .eq(lit(filter_val)),
+        );
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(75);
+}
+
+#[test]
+fn we_can_prove_a_case_expression_with_several_branches_in_the_result() {
+    let data = owned_table([
+        bigint("a", [1, 5, 10, 50, 200, 100]),
+        boolean("b", [true, false, true, false, true, false]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // SELECT CASE WHEN a >= 100 THEN 'big' WHEN a >= 10 THEN 'medium' ELSE 'small' END AS size,
+    //   CASE WHEN b THEN a ELSE 0 END AS c FROM t WHERE a != 5
+    let ast = dense_filter(
+        vec![
+            aliased_plan(
+                case(
+                    vec![
+                        (
+                            gte(column(t, "a", &accessor), const_bigint(100)),
+                            const_varchar("big"),
+                        ),
+                        (
+                            gte(column(t, "a", &accessor), const_bigint(10)),
+                            const_varchar("medium"),
+                        ),
+                    ],
+                    const_varchar("small"),
+                ),
+                "size",
+            ),
+            aliased_plan(
+                case(
+                    vec![(column(t, "b", &accessor), column(t, "a", &accessor))],
+                    const_bigint(0),
+                ),
+                "c",
+            ),
+        ],
+        tab(t),
+        not(equal(column(t, "a", &accessor), const_bigint(5))),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        varchar("size", ["small", "medium", "medium", "big", "big"]),
+        bigint("c", [1, 10, 0, 200, 0]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_case_expression_with_results_of_different_numeric_types() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 4]),
+        decimal75("d", 5, 1, [15, -25, 0, 1]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // SELECT CASE WHEN a >= 3 THEN a ELSE d END AS x FROM t WHERE CASE WHEN a = 4 THEN false ELSE true END
+    let ast = dense_filter(
+        vec![aliased_plan(
+            case(
+                vec![(
+                    gte(column(t, "a", &accessor), const_bigint(3)),
+                    column(t, "a", &accessor),
+                )],
+                column(t, "d", &accessor),
+            ),
+            "x",
+        )],
+        tab(t),
+        case(
+            vec![(
+                equal(column(t, "a", &accessor), const_bigint(4)),
+                const_bool(false),
+            )],
+            const_bool(true),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([decimal75("x", 20, 1, [15, -25, 30])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_case_expr_using_result_evaluate() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 4]),
+        bigint("b", [10, 20, 30, 40]),
+        boolean("c", [true, false, false, true]),
+    ]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let case_expr: ProvableExprPlan<RistrettoPoint> = case(
+        vec![
+            (column(t, "c", &accessor), column(t, "a", &accessor)),
+            (
+                gte(column(t, "a", &accessor), const_bigint(3)),
+                const_bigint(-1),
+            ),
+        ],
+        column(t, "b", &accessor),
+    );
+    let alloc = Bump::new();
+    let res = case_expr.result_evaluate(4, &alloc, &accessor);
+    assert_eq!(res, Column::BigInt(&[1, 20, -1, 4]));
+}
+
+#[test]
+fn we_cannot_create_a_case_expression_with_invalid_types() {
+    let data = owned_table([bigint("a", [1, 2]), varchar("b", ["x", "y"])]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_case(
+            vec![(column(t, "a", &accessor), column(t, "a", &accessor))],
+            column(t, "a", &accessor),
+        ),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_case(
+            vec![(const_bool(true), column(t, "b", &accessor))],
+            column(t, "a", &accessor),
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+}
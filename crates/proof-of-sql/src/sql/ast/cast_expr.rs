@@ -0,0 +1,361 @@
+use super::{
+    count_equals_zero, count_sign, prover_evaluate_equals_zero, prover_evaluate_sign,
+    result_evaluate_equals_zero, verifier_evaluate_equals_zero, verifier_evaluate_sign,
+    ProvableExpr, ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        math::decimal::scale_scalar,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        parse::{ConversionError, ConversionResult},
+        proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+    },
+};
+use bumpalo::Bump;
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Provable AST expression for a conversion to another type e.g. `CAST(a AS DECIMAL(10, 2))`
+///
+/// Integers, decimals and booleans can be cast to each other as long as no decimal places are dropped.
+/// A number is cast to a boolean by comparing it with zero. Otherwise, the value is kept and only scaled up
+/// to the new scale, which is free to prove. When the new type can not hold every value of the old type,
+/// the distances of the values to the bounds of the new type are also proven to be non-negative,
+/// so that the proof of an overflowing cast does not verify.
+///
+/// A cast that may overflow can be guarded by a boolean expression, e.g. the `WHERE` clause of the query or
+/// the condition of the `CASE` branch that the cast is the result of. The value of the cast is then only used
+/// where the guard is true, so it is set to zero elsewhere and only proven to be within the bounds where the guard is true.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CastExpr<C: Commitment> {
+    expr: Box<ProvableExprPlan<C>>,
+    data_type: ColumnType,
+    guard: Option<Box<ProvableExprPlan<C>>>,
+}
+
+impl<C: Commitment> CastExpr<C> {
+    /// Create a new CAST expression
+    pub fn new(expr: Box<ProvableExprPlan<C>>, data_type: ColumnType) -> Self {
+        Self {
+            expr,
+            data_type,
+            guard: None,
+        }
+    }
+
+    /// Create a new CAST expression whose value is only used where the guard is true
+    ///
+    /// The guard is dropped if the cast can not overflow, as there is nothing to check then.
+    pub fn new_guarded(
+        expr: Box<ProvableExprPlan<C>>,
+        data_type: ColumnType,
+        guard: Box<ProvableExprPlan<C>>,
+    ) -> Self {
+        let mut cast = Self::new(expr, data_type);
+        if !cast.is_number_to_boolean() && cast.bounds().is_some() {
+            cast.guard = Some(guard);
+        }
+        cast
+    }
+
+    /// Whether a number is cast to a boolean, which is proven by comparing it with zero.
+    fn is_number_to_boolean(&self) -> bool {
+        self.data_type == ColumnType::Boolean && self.expr.data_type() != ColumnType::Boolean
+    }
+
+    /// The number of decimal places added by the cast.
+    fn scaling(&self) -> i8 {
+        try_get_cast_scaling(self.expr.data_type(), self.data_type)
+            .expect("The cast was checked when the plan was built")
+    }
+
+    /// The bounds that the scaled values are proven to be within, if the new type may overflow.
+    fn bounds(&self) -> Option<(C::Scalar, C::Scalar)> {
+        get_bounds_to_check(self.expr.data_type(), self.data_type, self.scaling()).map(
+            |(min, max)| {
+                (
+                    C::Scalar::try_from(min).expect("Bounds of types fit in a scalar"),
+                    C::Scalar::try_from(max).expect("Bounds of types fit in a scalar"),
+                )
+            },
+        )
+    }
+}
+
+/// Returns the smallest and the largest value of a type, in units of its scale,
+/// or `None` if it can not be cast.
+fn get_value_range(data_type: ColumnType) -> Option<(BigInt, BigInt)> {
+    match data_type {
+        ColumnType::Boolean => Some((0.into(), 1.into())),
+        ColumnType::SmallInt => Some((i16::MIN.into(), i16::MAX.into())),
+        ColumnType::Int => Some((i32::MIN.into(), i32::MAX.into())),
+        ColumnType::BigInt => Some((i64::MIN.into(), i64::MAX.into())),
+        ColumnType::Int128 => Some((i128::MIN.into(), i128::MAX.into())),
+        ColumnType::Decimal75(precision, _) => {
+            let max = BigInt::from(10).pow(precision.value().into()) - 1;
+            Some((-max.clone(), max))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the number of decimal places added by a cast, or an error if the cast is not supported.
+fn try_get_cast_scaling(from: ColumnType, to: ColumnType) -> ConversionResult<i8> {
+    if get_value_range(from).is_none() || get_value_range(to).is_none() {
+        return Err(ConversionError::InvalidCast { from, to });
+    }
+    if to == ColumnType::Boolean {
+        return Ok(0);
+    }
+    let scaling = to.scale().unwrap_or(0) as i16 - from.scale().unwrap_or(0) as i16;
+    if scaling < 0 {
+        return Err(ConversionError::DecimalRoundingError(format!(
+            "Casting '{from}' to '{to}' would drop decimal places"
+        )));
+    }
+    i8::try_from(scaling).map_err(|_| ConversionError::InvalidCast { from, to })
+}
+
+/// Returns the bounds of the new type if it can not hold every value of the old type after scaling.
+fn get_bounds_to_check(from: ColumnType, to: ColumnType, scaling: i8) -> Option<(BigInt, BigInt)> {
+    if to == ColumnType::Boolean {
+        return None;
+    }
+    let (from_min, from_max) = get_value_range(from)?;
+    let (to_min, to_max) = get_value_range(to)?;
+    let factor = BigInt::from(10).pow(scaling as u32);
+    (from_min * &factor < to_min || from_max * factor > to_max).then_some((to_min, to_max))
+}
+
+/// Checks that an expression of one type can be cast to another type.
+pub(crate) fn check_cast_column_types(from: ColumnType, to: ColumnType) -> ConversionResult<()> {
+    try_get_cast_scaling(from, to).map(|_| ())
+}
+
+/// Checks that an expression of one type can be cast to another type in a provable way.
+///
+/// The range of the values is proven by sign decompositions, which only accept values of at most 129 bits,
+/// so casts that may overflow are only provable to types whose range is that small.
+pub(crate) fn check_provable_cast_column_types(
+    from: ColumnType,
+    to: ColumnType,
+) -> ConversionResult<()> {
+    let scaling = try_get_cast_scaling(from, to)?;
+    if let Some((min, max)) = get_bounds_to_check(from, to, scaling) {
+        if max - min >= BigInt::from(1) << 129 {
+            return Err(ConversionError::Unprovable(format!(
+                "Casting '{from}' to '{to}' may overflow and the range of '{to}' is too large to check"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Wraps the scaled values in a column of the new numeric type.
+///
+/// Values that do not fit in an integer type become zero, as a proof of them does not verify anyway.
+fn scalars_to_column<'a, S: Scalar>(
+    data_type: ColumnType,
+    scalars: &'a [S],
+    alloc: &'a Bump,
+) -> Column<'a, S> {
+    match data_type {
+        ColumnType::SmallInt => {
+            Column::SmallInt(alloc.alloc_slice_fill_with(scalars.len(), |i| {
+                scalars[i].try_into().unwrap_or_default()
+            }))
+        }
+        ColumnType::Int => {
+            Column::Int(alloc.alloc_slice_fill_with(scalars.len(), |i| {
+                scalars[i].try_into().unwrap_or_default()
+            }))
+        }
+        ColumnType::BigInt => {
+            Column::BigInt(alloc.alloc_slice_fill_with(scalars.len(), |i| {
+                scalars[i].try_into().unwrap_or_default()
+            }))
+        }
+        ColumnType::Int128 => {
+            Column::Int128(alloc.alloc_slice_fill_with(scalars.len(), |i| {
+                scalars[i].try_into().unwrap_or_default()
+            }))
+        }
+        ColumnType::Decimal75(precision, scale) => Column::Decimal75(precision, scale, scalars),
+        _ => panic!("Numbers are only cast to numeric or boolean types"),
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for CastExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        if self.is_number_to_boolean() {
+            count_equals_zero(builder);
+        } else if self.bounds().is_some() {
+            if let Some(guard) = &self.guard {
+                guard.count(builder)?;
+                builder.count_subpolynomials(1);
+                builder.count_intermediate_mles(1);
+                builder.count_degree(3);
+            }
+            count_sign(builder)?;
+            count_sign(builder)?;
+        }
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        self.data_type
+    }
+
+    #[tracing::instrument(name = "CastExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        if column.column_type() == self.data_type {
+            return column;
+        }
+        if self.is_number_to_boolean() {
+            let scalars = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+            // !(expr == 0)
+            let equals_zero = result_evaluate_equals_zero(table_length, alloc, scalars);
+            return Column::Boolean(alloc.alloc_slice_fill_with(table_length, |i| !equals_zero[i]));
+        }
+        let scalars = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(self.scaling()));
+        if let Some(guard) = &self.guard {
+            let guard = guard.result_evaluate(table_length, alloc, accessor);
+            let guard = guard.as_boolean().expect("guard is not boolean");
+            let guarded = alloc.alloc_slice_fill_with(table_length, |i| {
+                if guard[i] {
+                    scalars[i]
+                } else {
+                    Zero::zero()
+                }
+            });
+            return scalars_to_column(self.data_type, guarded, alloc);
+        }
+        scalars_to_column(self.data_type, scalars, alloc)
+    }
+
+    #[tracing::instrument(name = "CastExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.prover_evaluate(builder, alloc, accessor);
+        if column.column_type() == self.data_type {
+            return column;
+        }
+        let table_length = column.len();
+        if self.is_number_to_boolean() {
+            let scalars = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+            // !(expr == 0)
+            let equals_zero = prover_evaluate_equals_zero(builder, alloc, scalars);
+            return Column::Boolean(alloc.alloc_slice_fill_with(table_length, |i| !equals_zero[i]));
+        }
+        let mut scalars: &'a [C::Scalar] =
+            alloc.alloc_slice_copy(&column.to_scalar_with_scaling(self.scaling()));
+        if let Some((min, max)) = self.bounds() {
+            let guard: &'a [bool] = match &self.guard {
+                Some(guard) => {
+                    let guard_column = guard.prover_evaluate(builder, alloc, accessor);
+                    let guard = guard_column.as_boolean().expect("guard is not boolean");
+                    // guarded = guard ? expr : 0
+                    let guarded: &'a [C::Scalar] = alloc.alloc_slice_fill_with(table_length, |i| {
+                        if guard[i] {
+                            scalars[i]
+                        } else {
+                            Zero::zero()
+                        }
+                    });
+                    builder.produce_intermediate_mle(guarded);
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        vec![
+                            (C::Scalar::one(), vec![Box::new(guarded)]),
+                            (-C::Scalar::one(), vec![Box::new(guard), Box::new(scalars)]),
+                        ],
+                    );
+                    scalars = guarded;
+                    guard
+                }
+                None => alloc.alloc_slice_fill_copy(table_length, true),
+            };
+            let weight = |i: usize| {
+                if guard[i] {
+                    C::Scalar::one()
+                } else {
+                    Zero::zero()
+                }
+            };
+
+            // sign(guarded - guard * min) == 0
+            let above_min =
+                alloc.alloc_slice_fill_with(table_length, |i| scalars[i] - weight(i) * min);
+            prover_evaluate_sign(builder, alloc, above_min);
+
+            // sign(guard * max - guarded) == 0
+            let below_max =
+                alloc.alloc_slice_fill_with(table_length, |i| weight(i) * max - scalars[i]);
+            prover_evaluate_sign(builder, alloc, below_max);
+        }
+        scalars_to_column(self.data_type, scalars, alloc)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let eval = self.expr.verifier_evaluate(builder, accessor)?;
+        if self.is_number_to_boolean() {
+            // !(expr == 0)
+            return Ok(one_eval - verifier_evaluate_equals_zero(builder, eval));
+        }
+        let mut eval = scale_scalar(eval, self.scaling()).expect("Scaling is non-negative");
+        if let Some((min, max)) = self.bounds() {
+            let guard_eval = match &self.guard {
+                Some(guard) => {
+                    let guard_eval = guard.verifier_evaluate(builder, accessor)?;
+                    // guarded = guard ? expr : 0
+                    let guarded_eval = builder.consume_intermediate_mle();
+                    let guarded_subpolynomial_eval = builder.mle_evaluations.random_evaluation
+                        * (guarded_eval - guard_eval * eval);
+                    builder.produce_sumcheck_subpolynomial_evaluation(&guarded_subpolynomial_eval);
+                    eval = guarded_eval;
+                    guard_eval
+                }
+                None => one_eval,
+            };
+            // sign(guarded - guard * min) == 0 and sign(guard * max - guarded) == 0
+            for diff_eval in [eval - min * guard_eval, max * guard_eval - eval] {
+                if !verifier_evaluate_sign(builder, diff_eval, one_eval)?.is_zero() {
+                    return Err(ProofError::VerificationError(
+                        "cast value is out of the range of its type",
+                    ));
+                }
+            }
+        }
+        Ok(eval)
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+        if let Some(guard) = &self.guard {
+            guard.get_column_references(columns);
+        }
+    }
+}
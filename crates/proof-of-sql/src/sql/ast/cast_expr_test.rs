@@ -0,0 +1,281 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType, OwnedTable,
+            OwnedTableTestAccessor, RandomTestAccessorDescriptor, TestAccessor,
+        },
+        math::decimal::Precision,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::{test_utility::*, ProvableExpr, ProvableExprPlan},
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
+};
+use bumpalo::Bump;
+use curve25519_dalek::RistrettoPoint;
+use polars::prelude::{col, lit};
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+fn decimal_type(precision: u8, scale: i8) -> ColumnType {
+    ColumnType::Decimal75(Precision::new(precision).unwrap(), scale)
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let descr = RandomTestAccessorDescriptor {
+        min_rows: 1,
+        max_rows: 20,
+        min_value: -3,
+        max_value: 3,
+    };
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let cols = [
+        ("a", ColumnType::BigInt),
+        ("b", ColumnType::VarChar),
+        ("c", ColumnType::BigInt),
+    ];
+    for _ in 0..20 {
+        let data = make_random_test_accessor_data(&mut rng, &cols, &descr);
+        let data = OwnedTable::<Curve25519Scalar>::try_from(data).unwrap();
+        let filter_val = Uniform::new(descr.min_value, descr.max_value + 1).sample(&mut rng);
+
+        let t = "sxt.t".parse().unwrap();
+        let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+        accessor.add_table(t, data.clone(), offset);
+        let ast = dense_filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            and(
+                equal(
+                    cast(column(t, "a", &accessor), ColumnType::Int),
+                    const_bigint(filter_val),
+                ),
+                cast(column(t, "c", &accessor), ColumnType::Boolean),
+            ),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        let expected = data.apply_polars_filter(
+            &["a", "b"],
+            col("a").eq(lit(filter_val)).and(col("c").neq(lit(0))),
+        );
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(75);
+}
+
+#[test]
+fn we_can_prove_casts_that_can_not_overflow() {
+    let data = owned_table([
+        smallint("a", [-3_i16, 0, 7]),
+        int("b", [100, -200, 300]),
+        boolean("c", [true, false, true]),
+        decimal75("d", 5, 2, [125, -250, 0]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![
+            aliased_plan(cast(column(t, "a", &accessor), ColumnType::BigInt), "a"),
+            aliased_plan(cast(column(t, "b", &accessor), decimal_type(12, 2)), "b"),
+            aliased_plan(cast(column(t, "c", &accessor), ColumnType::Int), "c"),
+            aliased_plan(cast(column(t, "d", &accessor), decimal_type(6, 3)), "d"),
+            aliased_plan(cast(column(t, "d", &accessor), ColumnType::Boolean), "e"),
+        ],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [-3, 0, 7]),
+        decimal75("b", 12, 2, [10000, -20000, 30000]),
+        int("c", [1, 0, 1]),
+        decimal75("d", 6, 3, [1250, -2500, 0]),
+        boolean("e", [true, true, false]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_narrowing_casts_of_values_within_range() {
+    let data = owned_table([
+        bigint("a", [i64::from(i16::MIN), 0, i64::from(i16::MAX)]),
+        decimal75("d", 5, 2, [-999, 0, 999]),
+        int128("i", [i128::from(i64::MIN), -1, i128::from(i64::MAX)]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![
+            aliased_plan(cast(column(t, "a", &accessor), ColumnType::SmallInt), "a"),
+            aliased_plan(cast(column(t, "d", &accessor), decimal_type(4, 3)), "d"),
+            aliased_plan(cast(column(t, "i", &accessor), ColumnType::BigInt), "i"),
+        ],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        smallint("a", [i16::MIN, 0, i16::MAX]),
+        decimal75("d", 4, 3, [-9990, 0, 9990]),
+        bigint("i", [i64::MIN, -1, i64::MAX]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_verify_narrowing_casts_that_overflow() {
+    for (values, data_type) in [
+        ([1, i64::from(i16::MAX) + 1], ColumnType::SmallInt),
+        ([i64::from(i16::MIN) - 1, 1], ColumnType::SmallInt),
+        ([i64::from(i32::MAX) + 1, 0], ColumnType::Int),
+        ([0, 10_000], decimal_type(6, 2)),
+        ([-10_000, 0], decimal_type(6, 2)),
+    ] {
+        let data = owned_table([bigint("a", values)]);
+        let t = "sxt.t".parse().unwrap();
+        let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+        accessor.add_table(t, data, 0);
+        let ast = dense_filter(
+            vec![aliased_plan(
+                cast(column(t, "a", &accessor), data_type),
+                "a",
+            )],
+            tab(t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        assert!(verifiable_res.verify(&ast, &accessor, &()).is_err());
+    }
+}
+
+#[test]
+fn we_only_check_the_range_of_narrowing_casts_where_they_are_guarded() {
+    let data = owned_table([bigint("a", [1, 40_000, -300, 70_000])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let ast = dense_filter(
+        vec![aliased_plan(
+            guarded_cast(
+                column(t, "a", &accessor),
+                ColumnType::SmallInt,
+                lte(column(t, "a", &accessor), const_bigint(999)),
+            ),
+            "a",
+        )],
+        tab(t),
+        lte(column(t, "a", &accessor), const_bigint(999)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([smallint("a", [1_i16, -300])]);
+    assert_eq!(res, expected);
+
+    // The guard is true for a value that does not fit.
+    let ast = dense_filter(
+        vec![aliased_plan(
+            guarded_cast(
+                column(t, "a", &accessor),
+                ColumnType::SmallInt,
+                lte(column(t, "a", &accessor), const_bigint(40_000)),
+            ),
+            "a",
+        )],
+        tab(t),
+        lte(column(t, "a", &accessor), const_bigint(999)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(verifiable_res.verify(&ast, &accessor, &()).is_err());
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_cast_expr_using_result_evaluate() {
+    let data = owned_table([bigint("a", [-2, 0, 3]), boolean("b", [true, false, true])]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let alloc = Bump::new();
+    let cast_expr: ProvableExprPlan<RistrettoPoint> =
+        cast(column(t, "a", &accessor), ColumnType::Int);
+    let res = cast_expr.result_evaluate(3, &alloc, &accessor);
+    assert_eq!(res, Column::Int(&[-2, 0, 3]));
+    let cast_expr: ProvableExprPlan<RistrettoPoint> =
+        cast(column(t, "a", &accessor), ColumnType::Boolean);
+    let res = cast_expr.result_evaluate(3, &alloc, &accessor);
+    assert_eq!(res, Column::Boolean(&[true, false, true]));
+    let cast_expr: ProvableExprPlan<RistrettoPoint> =
+        cast(column(t, "b", &accessor), ColumnType::SmallInt);
+    let res = cast_expr.result_evaluate(3, &alloc, &accessor);
+    assert_eq!(res, Column::SmallInt(&[1, 0, 1]));
+}
+
+#[test]
+fn we_cannot_create_a_cast_expr_between_invalid_types() {
+    let data = owned_table([
+        bigint("a", [1, 2]),
+        varchar("b", ["x", "y"]),
+        decimal75("c", 5, 2, [1, 2]),
+        decimal75("d", 75, 0, [1, 2]),
+    ]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_cast(
+            column(t, "b", &accessor),
+            ColumnType::BigInt
+        ),
+        Err(ConversionError::InvalidCast { .. })
+    ));
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_cast(
+            column(t, "a", &accessor),
+            ColumnType::VarChar
+        ),
+        Err(ConversionError::InvalidCast { .. })
+    ));
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_cast(
+            column(t, "c", &accessor),
+            ColumnType::BigInt
+        ),
+        Err(ConversionError::DecimalRoundingError(_))
+    ));
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_cast(
+            column(t, "d", &accessor),
+            decimal_type(40, 0)
+        ),
+        Err(ConversionError::Unprovable(_))
+    ));
+    assert!(ProvableExprPlan::<RistrettoPoint>::try_new_cast(
+        column(t, "d", &accessor),
+        ColumnType::Int128
+    )
+    .is_ok());
+}
@@ -1,8 +1,9 @@
 use super::{
+    count_sign,
     dense_filter_util::{fold_columns, fold_vals},
     filter_columns,
     provable_expr_plan::ProvableExprPlan,
-    ColumnExpr, ProvableExpr, TableExpr,
+    prover_evaluate_sign, verifier_evaluate_sign, AliasedProvableExprPlan, ProvableExpr, TableExpr,
 };
 use crate::{
     base::{
@@ -10,17 +11,21 @@ use crate::{
         database::{
             Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
         },
+        polynomial::{
+            compute_truncated_lagrange_basis_index_sum, compute_truncated_lagrange_basis_sum,
+        },
         proof::ProofError,
         scalar::Scalar,
         slice_ops,
     },
     sql::proof::{
-        CountBuilder, HonestProver, Indexes, ProofBuilder, ProofExpr, ProverEvaluate,
-        ProverHonestyMarker, ResultBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+        CountBuilder, FirstRoundBuilder, HonestProver, Indexes, ProofBuilder, ProofExpr,
+        ProverEvaluate, ProverHonestyMarker, ResultBuilder, SumcheckSubpolynomialType,
+        VerificationBuilder,
     },
 };
 use bumpalo::Bump;
-use core::iter::repeat_with;
+use core::{iter::repeat_with, ops::Range};
 use num_traits::{One, Zero};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashSet, marker::PhantomData};
@@ -31,28 +36,49 @@ use std::{collections::HashSet, marker::PhantomData};
 /// ```
 ///
 /// This differs from the [`FilterExpr`] in that the result is not a sparse table.
+///
+/// The result expressions may be arbitrary provable expressions (e.g. `a + b AS c`),
+/// in which case the computed columns are proven as part of the filter.
+///
+/// The filter may also be sliced, i.e. followed by `LIMIT <limit> OFFSET <offset>`, in which case only the window of
+/// the selected rows in the order of the table is sent.
+/// Before any challenges are drawn, the prover publishes the bounds of the window in the table, `start` and `end`,
+/// as well as the number of selected rows before `start`. The proof then establishes, using sign decompositions of
+/// the row indexes, that
+/// 1. the window is made of the selected rows between `start` and `end`,
+/// 2. there are `offset` selected rows before `start`, unless every selected row comes before it, and
+/// 3. no selected row comes after `end` when the result has fewer than `limit` rows.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct OstensibleDenseFilterExpr<C: Commitment, H: ProverHonestyMarker> {
-    pub(super) results: Vec<ColumnExpr<C>>,
+    pub(super) aliased_results: Vec<AliasedProvableExprPlan<C>>,
     pub(super) table: TableExpr,
     pub(super) where_clause: ProvableExprPlan<C>,
+    /// The `(limit, offset)` of the window of selected rows that is sent, if the filter is sliced.
+    pub(super) slice: Option<(u64, u64)>,
     phantom: PhantomData<H>,
 }
 
 impl<C: Commitment, H: ProverHonestyMarker> OstensibleDenseFilterExpr<C, H> {
     /// Creates a new dense_filter expression.
     pub fn new(
-        results: Vec<ColumnExpr<C>>,
+        aliased_results: Vec<AliasedProvableExprPlan<C>>,
         table: TableExpr,
         where_clause: ProvableExprPlan<C>,
     ) -> Self {
         Self {
-            results,
+            aliased_results,
             table,
             where_clause,
+            slice: None,
             phantom: PhantomData,
         }
     }
+
+    /// Only keeps the `limit` selected rows starting at `offset` in the result.
+    pub fn with_slice(mut self, limit: u64, offset: u64) -> Self {
+        self.slice = Some((limit, offset));
+        self
+    }
 }
 
 impl<C: Commitment, H: ProverHonestyMarker> ProofExpr<C> for OstensibleDenseFilterExpr<C, H>
@@ -65,14 +91,21 @@ where
         _accessor: &dyn MetadataAccessor,
     ) -> Result<(), ProofError> {
         self.where_clause.count(builder)?;
-        for expr in self.results.iter() {
-            expr.count(builder)?;
+        for aliased_expr in self.aliased_results.iter() {
+            aliased_expr.expr.count(builder)?;
             builder.count_result_columns(1);
         }
         builder.count_intermediate_mles(2);
         builder.count_subpolynomials(3);
         builder.count_degree(3);
         builder.count_post_result_challenges(2);
+        if self.slice.is_some() {
+            builder.count_public_columns(1);
+            builder.count_intermediate_mles(1);
+            builder.count_subpolynomials(4);
+            count_sign(builder)?;
+            count_sign(builder)?;
+        }
         Ok(())
     }
 
@@ -94,9 +127,9 @@ where
         let selection_eval = self.where_clause.verifier_evaluate(builder, accessor)?;
         // 2. columns
         let columns_evals = Vec::from_iter(
-            self.results
+            self.aliased_results
                 .iter()
-                .map(|expr| expr.verifier_evaluate(builder, accessor))
+                .map(|aliased_expr| aliased_expr.expr.verifier_evaluate(builder, accessor))
                 .collect::<Result<Vec<_>, _>>()?,
         );
         // 3. indexes
@@ -105,8 +138,15 @@ where
             .result_indexes_evaluation
             .ok_or(ProofError::VerificationError("invalid indexes"))?;
         // 4. filtered_columns
-        let filtered_columns_evals =
-            Vec::from_iter(repeat_with(|| builder.consume_result_mle()).take(self.results.len()));
+        let filtered_columns_evals = Vec::from_iter(
+            repeat_with(|| builder.consume_result_mle()).take(self.aliased_results.len()),
+        );
+
+        // 5. window
+        let window_eval = match self.slice {
+            Some(slice) => verify_slice(builder, selection_eval, slice)?,
+            None => selection_eval,
+        };
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
@@ -116,24 +156,23 @@ where
             alpha,
             beta,
             columns_evals,
-            selection_eval,
+            window_eval,
             filtered_columns_evals,
         )
     }
 
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
-        let mut columns = Vec::with_capacity(self.results.len());
-        for col in self.results.iter() {
-            columns.push(col.get_column_field());
-        }
-        columns
+        self.aliased_results
+            .iter()
+            .map(|aliased_expr| ColumnField::new(aliased_expr.alias, aliased_expr.expr.data_type()))
+            .collect()
     }
 
     fn get_column_references(&self) -> HashSet<ColumnRef> {
         let mut columns = HashSet::new();
 
-        for col in self.results.iter() {
-            columns.insert(col.get_column_reference());
+        for aliased_expr in self.aliased_results.iter() {
+            aliased_expr.expr.get_column_references(&mut columns);
         }
 
         self.where_clause.get_column_references(&mut columns);
@@ -162,11 +201,15 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for DenseFilterExpr<C> {
             .expect("selection is not boolean");
 
         // 2. columns
-        let columns = Vec::from_iter(
-            self.results
-                .iter()
-                .map(|expr| expr.result_evaluate(builder.table_length(), alloc, accessor)),
-        );
+        let columns = Vec::from_iter(self.aliased_results.iter().map(|aliased_expr| {
+            aliased_expr
+                .expr
+                .result_evaluate(builder.table_length(), alloc, accessor)
+        }));
+        let selection = match self.slice {
+            Some(slice) => slice_window::<C::Scalar>(alloc, selection, slice).0,
+            None => selection,
+        };
         // Compute filtered_columns and indexes
         let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
         // 3. set indexes
@@ -178,6 +221,29 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for DenseFilterExpr<C> {
         builder.request_post_result_challenges(2);
     }
 
+    #[tracing::instrument(
+        name = "DenseFilterExpr::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        if let Some(slice) = self.slice {
+            let table_length = accessor.get_length(self.table.table_ref);
+            let selection_column: Column<'a, C::Scalar> =
+                self.where_clause
+                    .result_evaluate(table_length, alloc, accessor);
+            let selection = selection_column
+                .as_boolean()
+                .expect("selection is not boolean");
+            builder.produce_public_column(slice_window(alloc, selection, slice).1);
+        }
+    }
+
     #[tracing::instrument(name = "DenseFilterExpr::prover_evaluate", level = "debug", skip_all)]
     #[allow(unused_variables)]
     fn prover_evaluate<'a>(
@@ -195,10 +261,19 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for DenseFilterExpr<C> {
 
         // 2. columns
         let columns = Vec::from_iter(
-            self.results
+            self.aliased_results
                 .iter()
-                .map(|expr| expr.prover_evaluate(builder, alloc, accessor)),
+                .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, accessor)),
         );
+        // 3. window
+        let selection = match self.slice {
+            Some(slice) => {
+                let (window, bounds) = slice_window(alloc, selection, slice);
+                prove_slice(builder, alloc, (selection, window), &bounds, slice);
+                window
+            }
+            None => selection,
+        };
         // Compute filtered_columns and indexes
         let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
 
@@ -218,7 +293,7 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for DenseFilterExpr<C> {
     }
 }
 
-fn verify_filter<C: Commitment>(
+pub(super) fn verify_filter<C: Commitment>(
     builder: &mut VerificationBuilder<C>,
     alpha: C::Scalar,
     beta: C::Scalar,
@@ -226,13 +301,28 @@ fn verify_filter<C: Commitment>(
     s_eval: C::Scalar,
     d_evals: Vec<C::Scalar>,
 ) -> Result<(), ProofError> {
-    let one_eval = builder.mle_evaluations.one_evaluation;
-    let rand_eval = builder.mle_evaluations.random_evaluation;
-
     let chi_eval = match builder.mle_evaluations.result_indexes_evaluation {
         Some(eval) => eval,
         None => return Err(ProofError::VerificationError("Result indexes not valid.")),
     };
+    verify_filter_on_rows(builder, alpha, beta, c_evals, s_eval, d_evals, chi_eval);
+    Ok(())
+}
+
+/// Verifies that the selected rows are the rows of the result whose indicator is `chi`.
+///
+/// See [`prove_filter_on_rows`].
+pub(super) fn verify_filter_on_rows<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    alpha: C::Scalar,
+    beta: C::Scalar,
+    c_evals: Vec<C::Scalar>,
+    s_eval: C::Scalar,
+    d_evals: Vec<C::Scalar>,
+    chi_eval: C::Scalar,
+) {
+    let one_eval = builder.mle_evaluations.one_evaluation;
+    let rand_eval = builder.mle_evaluations.random_evaluation;
 
     let c_fold_eval = alpha * one_eval + fold_vals(beta, &c_evals);
     let d_bar_fold_eval = alpha * one_eval + fold_vals(beta, &d_evals);
@@ -251,8 +341,6 @@ fn verify_filter<C: Commitment>(
     builder.produce_sumcheck_subpolynomial_evaluation(
         &(rand_eval * (d_bar_fold_eval * d_star_eval - chi_eval)),
     );
-
-    Ok(())
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -265,10 +353,25 @@ pub(super) fn prove_filter<'a, S: Scalar + 'a>(
     s: &'a [bool],
     d: &[Column<S>],
     m: usize,
+) {
+    prove_filter_on_rows(builder, alloc, alpha, beta, c, s, d, 0..m);
+}
+
+/// Proves that the selected rows of `c` are the rows of the result `d` within `result_rows`, as a multiset.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn prove_filter_on_rows<'a, S: Scalar + 'a>(
+    builder: &mut ProofBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    c: &[Column<S>],
+    s: &'a [bool],
+    d: &[Column<S>],
+    result_rows: Range<usize>,
 ) {
     let n = builder.table_length();
     let chi = alloc.alloc_slice_fill_copy(n, false);
-    chi[..m].fill(true);
+    chi[result_rows.clone()].fill(true);
 
     let c_fold = alloc.alloc_slice_fill_copy(n, alpha);
     fold_columns(c_fold, One::one(), beta, c);
@@ -276,10 +379,10 @@ pub(super) fn prove_filter<'a, S: Scalar + 'a>(
     fold_columns(d_bar_fold, One::one(), beta, d);
 
     let c_star = alloc.alloc_slice_copy(c_fold);
-    let d_star = alloc.alloc_slice_copy(d_bar_fold);
-    d_star[m..].fill(Zero::zero());
+    let d_star = alloc.alloc_slice_fill_copy(n, Zero::zero());
+    d_star[result_rows.clone()].copy_from_slice(&d_bar_fold[result_rows.clone()]);
     slice_ops::batch_inversion(c_star);
-    slice_ops::batch_inversion(&mut d_star[..m]);
+    slice_ops::batch_inversion(&mut d_star[result_rows]);
 
     builder.produce_intermediate_mle(c_star as &[_]);
     builder.produce_intermediate_mle(d_star as &[_]);
@@ -317,3 +420,177 @@ pub(super) fn prove_filter<'a, S: Scalar + 'a>(
         ],
     );
 }
+
+/// Returns the window of `limit` selected rows starting at `offset`, in the order of the table, along with its
+/// bounds, i.e. the first row of the window, the row after its last row and the number of selected rows before the
+/// window.
+fn slice_window<'a, S: Scalar>(
+    alloc: &'a Bump,
+    selection: &[bool],
+    (limit, offset): (u64, u64),
+) -> (&'a [bool], Vec<S>) {
+    let selected_rows = Vec::from_iter((0..selection.len()).filter(|&i| selection[i]));
+    let first = (offset as usize).min(selected_rows.len());
+    let last = first
+        .saturating_add(limit as usize)
+        .min(selected_rows.len());
+    let window = alloc.alloc_slice_fill_copy(selection.len(), false);
+    for &i in &selected_rows[first..last] {
+        window[i] = true;
+    }
+    let start = selected_rows.get(first).copied().unwrap_or(selection.len());
+    let end = if last > first {
+        selected_rows[last - 1] + 1
+    } else {
+        start
+    };
+    let bounds = [start, end, first].map(|bound| S::from(bound as i128));
+    (window, bounds.to_vec())
+}
+
+/// Verifies that the window is the slice of the selected rows.
+///
+/// See [`prove_slice`].
+fn verify_slice<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    selection_eval: C::Scalar,
+    (limit, offset): (u64, u64),
+) -> Result<C::Scalar, ProofError> {
+    let table_length = builder.table_length();
+    let one_eval = builder.mle_evaluations.one_evaluation;
+    let rand_eval = builder.mle_evaluations.random_evaluation;
+    let point = builder.mle_evaluations.evaluation_point;
+
+    let &[start, end, skipped] = builder.consume_public_column() else {
+        return Err(ProofError::VerificationError("invalid slice bounds"));
+    };
+    if start > end || end > C::Scalar::from(table_length as i128) {
+        return Err(ProofError::VerificationError("invalid slice bounds"));
+    }
+    if skipped > C::Scalar::from(i128::from(offset)) {
+        return Err(ProofError::VerificationError(
+            "more selected rows than the offset come before the slice",
+        ));
+    }
+    // The sliced filter is the root node of the proof plan, so the number of rows of the result can be read in the clear.
+    let result_len = builder
+        .result_table()
+        .ok_or(ProofError::VerificationError(
+            "slice result could not be decoded",
+        ))?
+        .num_rows();
+    if result_len as u64 > limit {
+        return Err(ProofError::VerificationError(
+            "slice result has more rows than the limit",
+        ));
+    }
+    let missing_rows = C::Scalar::from(i128::from(limit) - result_len as i128);
+    let unskipped_rows = C::Scalar::from(i128::from(offset)) - skipped;
+
+    let index_eval = compute_truncated_lagrange_basis_index_sum(table_length, point);
+    let first_row_eval = compute_truncated_lagrange_basis_sum(table_length.min(1), point);
+
+    // before_start = sign(index - start), before_end = sign(index - end)
+    let before_start_eval =
+        verifier_evaluate_sign(builder, index_eval - start * one_eval, one_eval)?;
+    let before_end_eval = verifier_evaluate_sign(builder, index_eval - end * one_eval, one_eval)?;
+
+    // window - selection * before_end + selection * before_start = 0
+    let window_eval = builder.consume_intermediate_mle();
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval
+            * (window_eval - selection_eval * before_end_eval
+                + selection_eval * before_start_eval)),
+    );
+
+    // sum selection * before_start - skipped * first_row = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(selection_eval * before_start_eval - skipped * first_row_eval),
+    );
+
+    // sum unskipped_rows * (selection - selection * before_start) = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(unskipped_rows * (selection_eval - selection_eval * before_start_eval)),
+    );
+
+    // sum missing_rows * (selection - selection * before_end) = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(missing_rows * (selection_eval - selection_eval * before_end_eval)),
+    );
+
+    Ok(window_eval)
+}
+
+/// Proves that the window is the slice of the selected rows.
+///
+/// Given the bounds `start` and `end` of the window in the table and the number of `skipped` selected rows,
+/// this proves that
+/// 1. `window` is `selection * (before_end - before_start)`, where `before_start` and `before_end` are the sign bits
+///    of `index - start` and `index - end`,
+/// 2. `skipped` selected rows come before `start`,
+/// 3. no selected row comes after `start`, unless `skipped` is `offset`, and
+/// 4. no selected row comes after `end`, unless the result has `limit` rows.
+fn prove_slice<'a, S: Scalar>(
+    builder: &mut ProofBuilder<'a, S>,
+    alloc: &'a Bump,
+    (selection, window): (&'a [bool], &'a [bool]),
+    bounds: &[S],
+    (limit, offset): (u64, u64),
+) {
+    let table_length = selection.len();
+    let (start, end, skipped) = (bounds[0], bounds[1], bounds[2]);
+    let result_len = window.iter().filter(|&&row| row).count();
+    let missing_rows = S::from(i128::from(limit) - result_len as i128);
+    let unskipped_rows = S::from(i128::from(offset)) - skipped;
+    let first_row = alloc.alloc_slice_fill_with(table_length, |i| i == 0);
+
+    // before_start = sign(index - start), before_end = sign(index - end)
+    let start_diff = alloc.alloc_slice_fill_with(table_length, |i| S::from(i as i128) - start);
+    let before_start = prover_evaluate_sign(builder, alloc, start_diff);
+    let end_diff = alloc.alloc_slice_fill_with(table_length, |i| S::from(i as i128) - end);
+    let before_end = prover_evaluate_sign(builder, alloc, end_diff);
+
+    // window - selection * before_end + selection * before_start = 0
+    builder.produce_intermediate_mle(window);
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(window)]),
+            (-S::one(), vec![Box::new(selection), Box::new(before_end)]),
+            (S::one(), vec![Box::new(selection), Box::new(before_start)]),
+        ],
+    );
+
+    // sum selection * before_start - skipped * first_row = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), vec![Box::new(selection), Box::new(before_start)]),
+            (-skipped, vec![Box::new(first_row as &[_])]),
+        ],
+    );
+
+    // sum unskipped_rows * (selection - selection * before_start) = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (unskipped_rows, vec![Box::new(selection)]),
+            (
+                -unskipped_rows,
+                vec![Box::new(selection), Box::new(before_start)],
+            ),
+        ],
+    );
+
+    // sum missing_rows * (selection - selection * before_end) = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (missing_rows, vec![Box::new(selection)]),
+            (
+                -missing_rows,
+                vec![Box::new(selection), Box::new(before_end)],
+            ),
+        ],
+    );
+}
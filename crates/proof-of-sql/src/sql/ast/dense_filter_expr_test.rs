@@ -18,7 +18,10 @@ use crate::{
         ast::{
             // Making this explicit to ensure that we don't accidentally use the
             // sparse filter for these tests
-            test_utility::{cols_expr, column, const_int128, dense_filter, equal, tab},
+            test_utility::{
+                add, aliased_plan, cols_expr_plan, column, const_bigint, const_int128,
+                const_varchar, dense_filter, equal, multiply, sliced_dense_filter, subtract, tab,
+            },
             ColumnExpr,
             DenseFilterExpr,
             LiteralExpr,
@@ -42,16 +45,22 @@ fn we_can_correctly_fetch_the_query_result_schema() {
     let table_ref = TableRef::new(ResourceId::try_new("sxt", "sxt_tab").unwrap());
     let provable_ast = DenseFilterExpr::<RistrettoPoint>::new(
         vec![
-            ColumnExpr::new(ColumnRef::new(
-                table_ref,
-                Identifier::try_new("a").unwrap(),
-                ColumnType::BigInt,
-            )),
-            ColumnExpr::new(ColumnRef::new(
-                table_ref,
-                Identifier::try_new("b").unwrap(),
-                ColumnType::BigInt,
-            )),
+            aliased_plan(
+                ProvableExprPlan::Column(ColumnExpr::new(ColumnRef::new(
+                    table_ref,
+                    Identifier::try_new("a").unwrap(),
+                    ColumnType::BigInt,
+                ))),
+                "a",
+            ),
+            aliased_plan(
+                ProvableExprPlan::Column(ColumnExpr::new(ColumnRef::new(
+                    table_ref,
+                    Identifier::try_new("b").unwrap(),
+                    ColumnType::BigInt,
+                ))),
+                "b",
+            ),
         ],
         TableExpr { table_ref },
         ProvableExprPlan::try_new_equals(
@@ -81,21 +90,76 @@ fn we_can_correctly_fetch_the_query_result_schema() {
     );
 }
 
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_of_computed_columns() {
+    let table_ref = TableRef::new(ResourceId::try_new("sxt", "sxt_tab").unwrap());
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    accessor.add_table(
+        table_ref,
+        record_batch!(
+            "a" => [1_i64],
+            "b" => [2_i64],
+        ),
+        0,
+    );
+    let provable_ast = DenseFilterExpr::<RistrettoPoint>::new(
+        vec![
+            aliased_plan(
+                add(
+                    column(table_ref, "a", &accessor),
+                    column(table_ref, "b", &accessor),
+                ),
+                "c",
+            ),
+            aliased_plan(
+                equal(column(table_ref, "a", &accessor), const_bigint(5)),
+                "d",
+            ),
+        ],
+        TableExpr { table_ref },
+        equal(column(table_ref, "b", &accessor), const_bigint(2)),
+    );
+
+    assert_eq!(
+        provable_ast.get_column_result_fields(),
+        vec![
+            ColumnField::new(
+                "c".parse().unwrap(),
+                ColumnType::Decimal75(Precision::new(20).unwrap(), 0)
+            ),
+            ColumnField::new("d".parse().unwrap(), ColumnType::Boolean),
+        ]
+    );
+    assert_eq!(
+        provable_ast.get_column_references(),
+        HashSet::from([
+            ColumnRef::new(table_ref, "a".parse().unwrap(), ColumnType::BigInt),
+            ColumnRef::new(table_ref, "b".parse().unwrap(), ColumnType::BigInt),
+        ])
+    );
+}
+
 #[test]
 fn we_can_correctly_fetch_all_the_referenced_columns() {
     let table_ref = TableRef::new(ResourceId::try_new("sxt", "sxt_tab").unwrap());
     let provable_ast = DenseFilterExpr::new(
         vec![
-            ColumnExpr::new(ColumnRef::new(
-                table_ref,
-                Identifier::try_new("a").unwrap(),
-                ColumnType::BigInt,
-            )),
-            ColumnExpr::new(ColumnRef::new(
-                table_ref,
-                Identifier::try_new("f").unwrap(),
-                ColumnType::BigInt,
-            )),
+            aliased_plan(
+                ProvableExprPlan::Column(ColumnExpr::new(ColumnRef::new(
+                    table_ref,
+                    Identifier::try_new("a").unwrap(),
+                    ColumnType::BigInt,
+                ))),
+                "a",
+            ),
+            aliased_plan(
+                ProvableExprPlan::Column(ColumnExpr::new(ColumnRef::new(
+                    table_ref,
+                    Identifier::try_new("f").unwrap(),
+                    ColumnType::BigInt,
+                ))),
+                "f",
+            ),
         ],
         TableExpr { table_ref },
         not::<RistrettoPoint>(and(
@@ -170,7 +234,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_basic_dense_filter() {
     let mut accessor = RecordBatchTestAccessor::new_empty();
     accessor.add_table(t, data, 0);
     let where_clause = equal(column(t, "a", &accessor), const_int128(5_i128));
-    let expr = dense_filter(cols_expr(t, &["b"], &accessor), tab(t), where_clause);
+    let expr = dense_filter(cols_expr_plan(t, &["b"], &accessor), tab(t), where_clause);
     let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
     let res = res
         .verify(&expr, &accessor, &())
@@ -197,7 +261,7 @@ fn we_can_get_an_empty_result_from_a_basic_dense_filter_on_an_empty_table_using_
     let where_clause: ProvableExprPlan<RistrettoPoint> =
         equal(column(t, "a", &accessor), const_int128(999));
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         where_clause,
     );
@@ -242,7 +306,7 @@ fn we_can_get_an_empty_result_from_a_basic_dense_filter_using_result_evaluate()
     let where_clause: ProvableExprPlan<RistrettoPoint> =
         equal(column(t, "a", &accessor), const_int128(999));
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         where_clause,
     );
@@ -287,7 +351,7 @@ fn we_can_get_no_columns_from_a_basic_dense_filter_with_no_selected_columns_usin
     accessor.add_table(t, data, 0);
     let where_clause: ProvableExprPlan<RistrettoPoint> =
         equal(column(t, "a", &accessor), const_int128(5));
-    let expr = dense_filter(cols_expr(t, &[], &accessor), tab(t), where_clause);
+    let expr = dense_filter(cols_expr_plan(t, &[], &accessor), tab(t), where_clause);
     let alloc = Bump::new();
     let mut builder = ResultBuilder::new(5);
     expr.result_evaluate(&mut builder, &alloc, &accessor);
@@ -315,7 +379,7 @@ fn we_can_get_the_correct_result_from_a_basic_dense_filter_using_result_evaluate
     let where_clause: ProvableExprPlan<RistrettoPoint> =
         equal(column(t, "a", &accessor), const_int128(5));
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         where_clause,
     );
@@ -357,7 +421,7 @@ fn we_can_prove_a_dense_filter_on_an_empty_table() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         equal(column(t, "a", &accessor), const_int128(106)),
     );
@@ -386,7 +450,7 @@ fn we_can_prove_a_dense_filter_with_empty_results() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         equal(column(t, "a", &accessor), const_int128(106)),
     );
@@ -415,7 +479,7 @@ fn we_can_prove_a_dense_filter() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = dense_filter(
-        cols_expr(t, &["b", "c", "d", "e"], &accessor),
+        cols_expr_plan(t, &["b", "c", "d", "e"], &accessor),
         tab(t),
         equal(column(t, "a", &accessor), const_int128(105)),
     );
@@ -430,3 +494,111 @@ fn we_can_prove_a_dense_filter() {
     ]);
     assert_eq!(res, expected);
 }
+
+#[test]
+fn we_can_prove_a_dense_filter_with_computed_result_columns() {
+    let data = owned_table([
+        bigint("a", [101, 104, 105, 102, 105]),
+        bigint("b", [1, 2, 3, 4, 5]),
+        int128("c", [1, 2, 3, 4, 5]),
+        varchar("d", ["1", "2", "3", "4", "5"]),
+        decimal75("e", 3, 1, [10, 20, 30, 40, 55]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = dense_filter(
+        vec![
+            aliased_plan(
+                add(column(t, "b", &accessor), column(t, "c", &accessor)),
+                "b_plus_c",
+            ),
+            aliased_plan(
+                multiply(column(t, "e", &accessor), const_bigint(2)),
+                "two_e",
+            ),
+            aliased_plan(
+                subtract(column(t, "a", &accessor), column(t, "e", &accessor)),
+                "a_minus_e",
+            ),
+            aliased_plan(equal(column(t, "d", &accessor), const_varchar("3")), "is_3"),
+            aliased_plan(const_bigint(7), "seven"),
+        ],
+        tab(t),
+        equal(column(t, "a", &accessor), const_int128(105)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        decimal75("b_plus_c", 40, 0, [6, 10]),
+        decimal75("two_e", 22, 1, [60, 110]),
+        decimal75("a_minus_e", 21, 1, [1020, 995]),
+        boolean("is_3", [true, false]),
+        bigint("seven", [7, 7]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_sliced_dense_filter() {
+    let data = owned_table([
+        bigint("a", [1, 0, 1, 1, 0, 1, 1]),
+        bigint("b", [1, 2, 3, 4, 5, 6, 7]),
+        varchar("c", ["1", "2", "3", "4", "5", "6", "7"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    for (limit, offset, expected) in [
+        (2, 0, [1, 3].as_slice()),
+        (2, 1, &[3, 4]),
+        (3, 2, &[4, 6, 7]),
+        (4, 3, &[6, 7]),
+        (u64::MAX, 1, &[3, 4, 6, 7]),
+        (2, 5, &[]),
+        (0, 1, &[]),
+    ] {
+        let expr = sliced_dense_filter(
+            cols_expr_plan(t, &["b", "c"], &accessor),
+            tab(t),
+            equal(column(t, "a", &accessor), const_bigint(1)),
+            limit,
+            offset,
+        );
+        let res = VerifiableQueryResult::new(&expr, &accessor, &());
+        exercise_verification(&res, &expr, &accessor, t);
+        let res = res.verify(&expr, &accessor, &()).unwrap().table;
+        let expected = owned_table([
+            bigint("b", expected.to_vec()),
+            varchar("c", expected.iter().map(ToString::to_string)),
+        ]);
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_sliced_dense_filter_against_another_slice() {
+    let data = owned_table([
+        bigint("a", [1, 0, 1, 1, 0, 1]),
+        bigint("b", [1, 2, 3, 4, 5, 6]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let sliced_expr = |limit, offset| {
+        sliced_dense_filter(
+            cols_expr_plan(t, &["b"], &accessor),
+            tab(t),
+            equal(column(t, "a", &accessor), const_bigint(1)),
+            limit,
+            offset,
+        )
+    };
+    let expr = sliced_expr(2, 1);
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    assert!(res.verify(&sliced_expr(2, 0), &accessor, &()).is_err());
+    assert!(res.verify(&sliced_expr(2, 2), &accessor, &()).is_err());
+    assert!(res.verify(&sliced_expr(1, 1), &accessor, &()).is_err());
+    assert!(res.verify(&sliced_expr(3, 1), &accessor, &()).is_err());
+}
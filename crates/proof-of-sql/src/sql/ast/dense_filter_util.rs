@@ -65,6 +65,14 @@ pub fn filter_column_by_index<'a, S: Scalar>(
             *scale,
             alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])),
         ),
+        Column::TimeStamp(unit, timezone, col) => Column::TimeStamp(
+            *unit,
+            *timezone,
+            alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])),
+        ),
+        Column::Date(col) => {
+            Column::Date(alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])))
+        }
     }
 }
 
@@ -1,45 +1,69 @@
 use super::{
-    aggregate_columns, fold_columns, fold_vals, group_by_util::AggregatedColumns,
-    provable_expr_plan::ProvableExprPlan, ColumnExpr, ProvableExpr, TableExpr,
+    aggregate_columns, count_sign, fold_columns, fold_vals,
+    group_by_util::{extremum_witness, AggregatedColumns, Extremum, ExtremumWitness},
+    provable_expr_plan::ProvableExprPlan,
+    prover_evaluate_sign, verifier_evaluate_sign, AliasedProvableExprPlan, ColumnExpr,
+    ProvableExpr, TableExpr,
 };
 use crate::{
     base::{
         commitment::Commitment,
         database::{
             Column, ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
-            MetadataAccessor,
+            MetadataAccessor, OwnedColumn, OwnedTable,
         },
+        polynomial::{compute_truncated_lagrange_basis_sum, MultilinearExtension},
         proof::ProofError,
         scalar::Scalar,
         slice_ops,
     },
     sql::proof::{
-        CountBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate, ResultBuilder,
-        SumcheckSubpolynomialType, VerificationBuilder,
+        CountBuilder, FirstRoundBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate,
+        ResultBuilder, SumcheckSubpolynomialType, VerificationBuilder,
     },
 };
 use bumpalo::Bump;
 use core::iter::repeat_with;
-use num_traits::One;
+use num_traits::{One, Zero};
 use proof_of_sql_parser::Identifier;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 /// Provable expressions for queries of the form
 /// ```ignore
 ///     SELECT <group_by_expr1>, ..., <group_by_exprM>,
 ///         SUM(<sum_expr1>.0) as <sum_expr1>.1, ..., SUM(<sum_exprN>.0) as <sum_exprN>.1,
+///         MIN(<min_expr1>.0) as <min_expr1>.1, ..., MIN(<min_exprP>.0) as <min_exprP>.1,
+///         MAX(<max_expr1>.0) as <max_expr1>.1, ..., MAX(<max_exprQ>.0) as <max_exprQ>.1,
 ///         COUNT(*) as count_alias
 ///     FROM <table>
 ///     WHERE <where_clause>
 ///     GROUP BY <group_by_expr1>, ..., <group_by_exprM>
 /// ```
 ///
+/// Each `<group_by_exprI>` is a provable expression, e.g. a column or a `CASE` bucket, whose result is named by its alias.
+/// Each `<sum_exprI>.0` is a provable expression as well, e.g. `price * qty`, and `<sum_exprI>.1` is the field of its sum.
+///
 /// Note: if `group_by_exprs` is empty, then the query is equivalent to removing the `GROUP BY` clause.
+/// In that case, the result is a single row, with a count of zero if no row is selected.
+/// The only exception is when there is a `MIN` or `MAX`, in which case the result is empty if no row is selected.
+/// Either way, the post-processing step then turns the result into the single row of SQL, where the aggregations
+/// other than counts are `NULL`.
+///
+/// For each `MIN` (resp. `MAX`) the prover commits, before any challenges are drawn, to the minimum (resp. maximum)
+/// of the group of every selected row and to a single row per group where it is attained.
+/// The proof then establishes that
+/// 1. the committed minimums are the ones of the result, by making them part of each row's group,
+/// 2. no selected row is smaller (resp. larger) than its group's minimum (resp. maximum), using a sign decomposition, and
+/// 3. every group of the result has a selected row that is equal to its minimum (resp. maximum).
+///
+/// The groups of the result are checked to be unique and non-empty in the clear.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct GroupByExpr<C: Commitment> {
-    pub(super) group_by_exprs: Vec<ColumnExpr<C>>,
-    pub(super) sum_expr: Vec<(ColumnExpr<C>, ColumnField)>,
+    pub(super) group_by_exprs: Vec<AliasedProvableExprPlan<C>>,
+    pub(super) sum_expr: Vec<(ProvableExprPlan<C>, ColumnField)>,
+    pub(super) min_expr: Vec<(ColumnExpr<C>, ColumnField)>,
+    pub(super) max_expr: Vec<(ColumnExpr<C>, ColumnField)>,
     pub(super) count_alias: Identifier,
     pub(super) table: TableExpr,
     pub(super) where_clause: ProvableExprPlan<C>,
@@ -48,8 +72,10 @@ pub struct GroupByExpr<C: Commitment> {
 impl<C: Commitment> GroupByExpr<C> {
     /// Creates a new group_by expression.
     pub fn new(
-        group_by_exprs: Vec<ColumnExpr<C>>,
-        sum_expr: Vec<(ColumnExpr<C>, ColumnField)>,
+        group_by_exprs: Vec<AliasedProvableExprPlan<C>>,
+        sum_expr: Vec<(ProvableExprPlan<C>, ColumnField)>,
+        min_expr: Vec<(ColumnExpr<C>, ColumnField)>,
+        max_expr: Vec<(ColumnExpr<C>, ColumnField)>,
         count_alias: Identifier,
         table: TableExpr,
         where_clause: ProvableExprPlan<C>,
@@ -57,11 +83,31 @@ impl<C: Commitment> GroupByExpr<C> {
         Self {
             group_by_exprs,
             sum_expr,
+            min_expr,
+            max_expr,
             table,
             count_alias,
             where_clause,
         }
     }
+
+    /// Returns the alias of the column that counts the rows of each group.
+    pub fn count_alias(&self) -> Identifier {
+        self.count_alias
+    }
+
+    /// Returns true if the result always consists of a single group, even when no row is selected.
+    /// This is the case for a query without `GROUP BY`, unless it has a `MIN` or `MAX`, which are undefined for zero rows.
+    fn has_single_group(&self) -> bool {
+        self.group_by_exprs.is_empty() && self.min_expr.is_empty() && self.max_expr.is_empty()
+    }
+
+    /// The `MIN` expressions followed by the `MAX` expressions.
+    fn extremum_exprs(&self) -> impl Iterator<Item = (Extremum, &ColumnExpr<C>)> {
+        let mins = self.min_expr.iter().map(|expr| (Extremum::Min, &expr.0));
+        let maxes = self.max_expr.iter().map(|expr| (Extremum::Max, &expr.0));
+        mins.chain(maxes)
+    }
 }
 
 impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
@@ -71,19 +117,28 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
         _accessor: &dyn MetadataAccessor,
     ) -> Result<(), ProofError> {
         self.where_clause.count(builder)?;
-        for expr in self.group_by_exprs.iter() {
-            expr.count(builder)?;
+        for aliased_expr in self.group_by_exprs.iter() {
+            aliased_expr.expr.count(builder)?;
             builder.count_result_columns(1);
         }
         for expr in self.sum_expr.iter() {
             expr.0.count(builder)?;
             builder.count_result_columns(1);
         }
+        for (_, expr) in self.extremum_exprs() {
+            expr.count(builder)?;
+            builder.count_result_columns(1);
+        }
         builder.count_result_columns(1);
         builder.count_intermediate_mles(2);
         builder.count_subpolynomials(3);
         builder.count_degree(3);
         builder.count_post_result_challenges(2);
+        for _ in self.extremum_exprs() {
+            builder.count_first_round_mles(2);
+            count_sign(builder)?;
+            builder.count_subpolynomials(2);
+        }
         Ok(())
     }
 
@@ -95,7 +150,6 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
         accessor.get_offset(self.table.table_ref)
     }
 
-    #[allow(unused_variables)]
     fn verifier_evaluate(
         &self,
         builder: &mut VerificationBuilder<C>,
@@ -107,55 +161,121 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
         let group_by_evals = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.verifier_evaluate(builder, accessor))
+            .map(|aliased_expr| aliased_expr.expr.verifier_evaluate(builder, accessor))
             .collect::<Result<Vec<_>, _>>()?;
         let aggregate_evals = self
             .sum_expr
             .iter()
             .map(|expr| expr.0.verifier_evaluate(builder, accessor))
             .collect::<Result<Vec<_>, _>>()?;
-        // 3. indexes
+        let extremum_evals = self
+            .extremum_exprs()
+            .map(|(_, expr)| expr.verifier_evaluate(builder, accessor))
+            .collect::<Result<Vec<_>, _>>()?;
+        // 3. extremum witnesses
+        let extremum_witness_evals = Vec::from_iter(
+            repeat_with(|| {
+                (
+                    builder.consume_first_round_mle(),
+                    builder.consume_first_round_mle(),
+                )
+            })
+            .take(extremum_evals.len()),
+        );
+        // 4. indexes
         let indexes_eval = builder
             .mle_evaluations
             .result_indexes_evaluation
             .ok_or(ProofError::VerificationError("invalid indexes"))?;
-        // 4. filtered_columns
+        // The GroupByExpr is the root node of the proof plan, so the result can be checked in the clear.
+        let result = builder.result_table().ok_or(ProofError::VerificationError(
+            "group by result could not be decoded",
+        ))?;
+        if indexes_eval
+            != compute_truncated_lagrange_basis_sum(
+                result.num_rows(),
+                builder.mle_evaluations.evaluation_point,
+            )
+        {
+            return Err(ProofError::VerificationError(
+                "group by result must be dense",
+            ));
+        }
+        if !has_unique_groups(result, self.group_by_exprs.len()) {
+            return Err(ProofError::VerificationError(
+                "group by result has duplicate groups",
+            ));
+        }
+        if !has_valid_counts(result, self.has_single_group()) {
+            return Err(ProofError::VerificationError(
+                "group by result has missing or empty groups",
+            ));
+        }
+        // 5. filtered_columns
 
         let group_by_result_columns_evals = Vec::from_iter(
             repeat_with(|| builder.consume_result_mle()).take(self.group_by_exprs.len()),
         );
         let sum_result_columns_evals =
             Vec::from_iter(repeat_with(|| builder.consume_result_mle()).take(self.sum_expr.len()));
+        let extremum_result_columns_evals =
+            Vec::from_iter(repeat_with(|| builder.consume_result_mle()).take(extremum_evals.len()));
         let count_column_eval = builder.consume_result_mle();
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
 
-        verify_group_by(
+        // The extremum of each row's group is part of the group.
+        let g_in_evals = Vec::from_iter(
+            group_by_evals.into_iter().chain(
+                extremum_witness_evals
+                    .iter()
+                    .map(|(extrema_eval, _)| *extrema_eval),
+            ),
+        );
+        let g_out_evals = Vec::from_iter(
+            group_by_result_columns_evals
+                .into_iter()
+                .chain(extremum_result_columns_evals),
+        );
+        let (g_in_star_eval, g_out_star_eval) = verify_group_by(
             builder,
             alpha,
             beta,
-            (group_by_evals, aggregate_evals, where_eval),
-            (
-                group_by_result_columns_evals,
-                sum_result_columns_evals,
-                count_column_eval,
-            ),
-        )
-
-        // todo!: check that the group_by results are unique.
-        //        When the GroupByExpr is the root node of the Proof plan,
-        //        this can be done by simply looking at the results returned by the prover.
+            (g_in_evals, aggregate_evals, where_eval),
+            (g_out_evals, sum_result_columns_evals, count_column_eval),
+        )?;
+
+        for (((extremum, _), value_eval), (extrema_eval, attained_eval)) in self
+            .extremum_exprs()
+            .zip(extremum_evals)
+            .zip(extremum_witness_evals)
+        {
+            verify_extremum(
+                builder,
+                extremum,
+                (value_eval, where_eval, g_in_star_eval),
+                (extrema_eval, attained_eval),
+                (g_out_star_eval, indexes_eval),
+            )?;
+        }
+        Ok(())
     }
 
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
         let mut fields = Vec::new();
-        for col in self.group_by_exprs.iter() {
-            fields.push(col.get_column_field());
+        for aliased_expr in self.group_by_exprs.iter() {
+            fields.push(ColumnField::new(
+                aliased_expr.alias,
+                aliased_expr.expr.data_type(),
+            ));
         }
         for col in self.sum_expr.iter() {
             fields.push(col.1);
         }
+        for col in self.min_expr.iter().chain(self.max_expr.iter()) {
+            fields.push(col.1);
+        }
         fields.push(ColumnField::new(self.count_alias, ColumnType::BigInt));
         fields
     }
@@ -163,11 +283,14 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
     fn get_column_references(&self) -> HashSet<ColumnRef> {
         let mut columns = HashSet::new();
 
-        for col in self.group_by_exprs.iter() {
-            columns.insert(col.get_column_reference());
+        for aliased_expr in self.group_by_exprs.iter() {
+            aliased_expr.expr.get_column_references(&mut columns);
         }
-        for col in self.sum_expr.iter() {
-            columns.insert(col.0.get_column_reference());
+        for expr in self.sum_expr.iter() {
+            expr.0.get_column_references(&mut columns);
+        }
+        for (_, col) in self.extremum_exprs() {
+            columns.insert(col.get_column_reference());
         }
 
         self.where_clause.get_column_references(&mut columns);
@@ -194,22 +317,39 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
             .expect("selection is not boolean");
 
         // 2. columns
-        let group_by_columns = Vec::from_iter(
-            self.group_by_exprs
-                .iter()
-                .map(|expr| expr.result_evaluate(builder.table_length(), alloc, accessor)),
-        );
+        let group_by_columns = Vec::from_iter(self.group_by_exprs.iter().map(|aliased_expr| {
+            aliased_expr
+                .expr
+                .result_evaluate(builder.table_length(), alloc, accessor)
+        }));
         let sum_columns = Vec::from_iter(self.sum_expr.iter().map(|expr| {
             expr.0
                 .result_evaluate(builder.table_length(), alloc, accessor)
         }));
+        let min_columns = Vec::from_iter(self.min_expr.iter().map(|expr| {
+            expr.0
+                .result_evaluate(builder.table_length(), alloc, accessor)
+        }));
+        let max_columns = Vec::from_iter(self.max_expr.iter().map(|expr| {
+            expr.0
+                .result_evaluate(builder.table_length(), alloc, accessor)
+        }));
         // Compute filtered_columns and indexes
         let AggregatedColumns {
             group_by_columns: group_by_result_columns,
             sum_columns: sum_result_columns,
+            min_columns: min_result_columns,
+            max_columns: max_result_columns,
             count_column,
-        } = aggregate_columns(alloc, &group_by_columns, &sum_columns, selection)
-            .expect("columns should be aggregatable");
+        } = aggregate_columns(
+            alloc,
+            &group_by_columns,
+            &sum_columns,
+            &min_columns,
+            &max_columns,
+            selection,
+        )
+        .expect("columns should be aggregatable");
         // 3. set indexes
         builder.set_result_indexes(Indexes::Dense(0..(count_column.len() as u64)));
         // 4. set filtered_columns
@@ -219,12 +359,43 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
         for col in sum_result_columns {
             builder.produce_result_column(col);
         }
+        for col in min_result_columns.into_iter().chain(max_result_columns) {
+            builder.produce_result_column(col);
+        }
         builder.produce_result_column(count_column);
         builder.request_post_result_challenges(2);
     }
 
+    #[tracing::instrument(name = "GroupByExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let table_length = accessor.get_length(self.table.table_ref);
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause
+                .result_evaluate(table_length, alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+        let group_by_columns = Vec::from_iter(self.group_by_exprs.iter().map(|aliased_expr| {
+            aliased_expr
+                .expr
+                .result_evaluate(table_length, alloc, accessor)
+        }));
+        for (extremum, expr) in self.extremum_exprs() {
+            let column = expr.result_evaluate(table_length, alloc, accessor);
+            let ExtremumWitness { extrema, attained } =
+                extremum_witness(alloc, &group_by_columns, &column, extremum, selection)
+                    .expect("columns should be aggregatable");
+            builder.produce_first_round_mle(extrema);
+            builder.produce_first_round_mle(attained);
+        }
+    }
+
     #[tracing::instrument(name = "GroupByExpr::prover_evaluate", level = "debug", skip_all)]
-    #[allow(unused_variables)]
     fn prover_evaluate<'a>(
         &self,
         builder: &mut ProofBuilder<'a, C::Scalar>,
@@ -242,42 +413,121 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
         let group_by_columns = Vec::from_iter(
             self.group_by_exprs
                 .iter()
-                .map(|expr| expr.prover_evaluate(builder, alloc, accessor)),
+                .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, accessor)),
         );
         let sum_columns = Vec::from_iter(
             self.sum_expr
                 .iter()
                 .map(|expr| expr.0.prover_evaluate(builder, alloc, accessor)),
         );
+        let extremum_columns = Vec::from_iter(
+            self.extremum_exprs()
+                .map(|(_, expr)| expr.prover_evaluate(builder, alloc, accessor)),
+        );
+        // 3. extremum witnesses
+        let extremum_witnesses = Vec::from_iter(self.extremum_exprs().zip(&extremum_columns).map(
+            |((extremum, _), column)| {
+                let witness =
+                    extremum_witness(alloc, &group_by_columns, column, extremum, selection)
+                        .expect("columns should be aggregatable");
+                builder.produce_first_round_mle(witness.extrema);
+                builder.produce_first_round_mle(witness.attained);
+                witness
+            },
+        ));
         // Compute filtered_columns and indexes
+        let (min_columns, max_columns) = extremum_columns.split_at(self.min_expr.len());
         let AggregatedColumns {
             group_by_columns: group_by_result_columns,
             sum_columns: sum_result_columns,
+            min_columns: min_result_columns,
+            max_columns: max_result_columns,
             count_column,
-        } = aggregate_columns(alloc, &group_by_columns, &sum_columns, selection)
-            .expect("columns should be aggregatable");
+        } = aggregate_columns(
+            alloc,
+            &group_by_columns,
+            &sum_columns,
+            min_columns,
+            max_columns,
+            selection,
+        )
+        .expect("columns should be aggregatable");
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
 
-        prove_group_by(
+        // The extremum of each row's group is part of the group.
+        let g_in = Vec::from_iter(
+            group_by_columns.iter().cloned().chain(
+                extremum_witnesses
+                    .iter()
+                    .map(|witness| Column::Scalar(witness.extrema)),
+            ),
+        );
+        let g_out = Vec::from_iter(
+            group_by_result_columns
+                .into_iter()
+                .chain(min_result_columns)
+                .chain(max_result_columns),
+        );
+        let (g_in_star, g_out_star) = prove_group_by(
             builder,
             alloc,
             alpha,
             beta,
-            (&group_by_columns, &sum_columns, selection),
-            (&group_by_result_columns, &sum_result_columns, count_column),
+            (&g_in, &sum_columns, selection),
+            (&g_out, &sum_result_columns, count_column),
         );
+
+        for (((extremum, _), column), witness) in self
+            .extremum_exprs()
+            .zip(&extremum_columns)
+            .zip(extremum_witnesses)
+        {
+            prove_extremum(
+                builder,
+                alloc,
+                extremum,
+                (column, selection, g_in_star),
+                witness,
+                (g_out_star, count_column.len()),
+            );
+        }
+    }
+}
+
+/// Returns true if the count column, which is the last column of the result, is valid.
+///
+/// If `single_group` is true, the result must have exactly one row, whatever its count.
+/// Otherwise, every group of the result must have at least one selected row.
+fn has_valid_counts<S: Scalar>(result: &OwnedTable<S>, single_group: bool) -> bool {
+    match result.inner_table().values().last() {
+        Some(_) if single_group => result.num_rows() == 1,
+        Some(OwnedColumn::BigInt(counts)) => counts.iter().all(|&count| count > 0),
+        _ => false,
     }
 }
 
+/// Returns true if no two rows of the result have the same values in the first `num_group_by_columns` columns.
+fn has_unique_groups<S: Scalar>(result: &OwnedTable<S>, num_group_by_columns: usize) -> bool {
+    let group_by_columns = Vec::from_iter(result.inner_table().values().take(num_group_by_columns));
+    let mut groups = BTreeSet::new();
+    (0..result.num_rows()).all(|i| {
+        groups.insert(Vec::from_iter(group_by_columns.iter().map(|column| {
+            column
+                .scalar_at(i)
+                .expect("all the columns of a table have the same length")
+        })))
+    })
+}
+
 fn verify_group_by<C: Commitment>(
     builder: &mut VerificationBuilder<C>,
     alpha: C::Scalar,
     beta: C::Scalar,
     (g_in_evals, sum_in_evals, sel_in_eval): (Vec<C::Scalar>, Vec<C::Scalar>, C::Scalar),
     (g_out_evals, sum_out_evals, count_out_eval): (Vec<C::Scalar>, Vec<C::Scalar>, C::Scalar),
-) -> Result<(), ProofError> {
+) -> Result<(C::Scalar, C::Scalar), ProofError> {
     let one_eval = builder.mle_evaluations.one_evaluation;
     let rand_eval = builder.mle_evaluations.random_evaluation;
 
@@ -309,7 +559,7 @@ fn verify_group_by<C: Commitment>(
         &(rand_eval * (g_out_star_eval * g_out_bar_fold_eval - one_eval)),
     );
 
-    Ok(())
+    Ok((g_in_star_eval, g_out_star_eval))
 }
 
 pub fn prove_group_by<'a, S: Scalar>(
@@ -319,7 +569,7 @@ pub fn prove_group_by<'a, S: Scalar>(
     beta: S,
     (g_in, sum_in, sel_in): (&[Column<S>], &[Column<S>], &'a [bool]),
     (g_out, sum_out, count_out): (&[Column<S>], &[&'a [S]], &'a [i64]),
-) {
+) -> (&'a [S], &'a [S]) {
     let n = builder.table_length();
     let m_out = count_out.len();
 
@@ -349,8 +599,10 @@ pub fn prove_group_by<'a, S: Scalar>(
     g_out_star[m_out..].fill(alpha.inv().expect("alpha should never be 0"));
     slice_ops::batch_inversion(&mut g_out_star[..m_out]);
 
-    builder.produce_intermediate_mle(g_in_star as &[_]);
-    builder.produce_intermediate_mle(g_out_star as &[_]);
+    let g_in_star: &[_] = g_in_star;
+    let g_out_star: &[_] = g_out_star;
+    builder.produce_intermediate_mle(g_in_star);
+    builder.produce_intermediate_mle(g_out_star);
 
     // sum g_in_star * sel_in * sum_in_fold - g_out_star * sum_out_bar_fold = 0
     builder.produce_sumcheck_subpolynomial(
@@ -359,17 +611,14 @@ pub fn prove_group_by<'a, S: Scalar>(
             (
                 S::one(),
                 vec![
-                    Box::new(g_in_star as &[_]),
+                    Box::new(g_in_star),
                     Box::new(sel_in),
                     Box::new(sum_in_fold as &[_]),
                 ],
             ),
             (
                 -S::one(),
-                vec![
-                    Box::new(g_out_star as &[_]),
-                    Box::new(sum_out_bar_fold as &[_]),
-                ],
+                vec![Box::new(g_out_star), Box::new(sum_out_bar_fold as &[_])],
             ),
         ],
     );
@@ -380,7 +629,7 @@ pub fn prove_group_by<'a, S: Scalar>(
         vec![
             (
                 S::one(),
-                vec![Box::new(g_in_star as &[_]), Box::new(g_in_fold as &[_])],
+                vec![Box::new(g_in_star), Box::new(g_in_fold as &[_])],
             ),
             (-S::one(), vec![]),
         ],
@@ -392,12 +641,102 @@ pub fn prove_group_by<'a, S: Scalar>(
         vec![
             (
                 S::one(),
-                vec![
-                    Box::new(g_out_star as &[_]),
-                    Box::new(g_out_bar_fold as &[_]),
-                ],
+                vec![Box::new(g_out_star), Box::new(g_out_bar_fold as &[_])],
             ),
             (-S::one(), vec![]),
         ],
     );
+
+    (g_in_star, g_out_star)
+}
+
+/// Verifies that `MIN(<column>)` (resp. `MAX(<column>)`) is correct for every group of the result.
+///
+/// See [`prove_extremum`].
+fn verify_extremum<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    extremum: Extremum,
+    (value_eval, sel_in_eval, g_in_star_eval): (C::Scalar, C::Scalar, C::Scalar),
+    (extrema_eval, attained_eval): (C::Scalar, C::Scalar),
+    (g_out_star_eval, indexes_eval): (C::Scalar, C::Scalar),
+) -> Result<(), ProofError> {
+    let one_eval = builder.mle_evaluations.one_evaluation;
+    let rand_eval = builder.mle_evaluations.random_evaluation;
+
+    // diff = value - extrema for MIN and extrema - value for MAX
+    let diff_eval = match extremum {
+        Extremum::Min => value_eval - extrema_eval,
+        Extremum::Max => extrema_eval - value_eval,
+    };
+    // sign(diff) == 0
+    let sign_eval = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
+    if sign_eval != Zero::zero() {
+        return Err(ProofError::VerificationError(
+            "a selected row is beyond the minimum or maximum of its group",
+        ));
+    }
+
+    // sum attained * sel_in * g_in_star - g_out_star * indexes = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(attained_eval * sel_in_eval * g_in_star_eval - g_out_star_eval * indexes_eval),
+    );
+
+    // attained * diff = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(&(rand_eval * attained_eval * diff_eval));
+
+    Ok(())
+}
+
+/// Proves that `MIN(<column>)` (resp. `MAX(<column>)`) is correct for every group of the result.
+///
+/// The committed `extrema` are part of each row's group, so they are the ones of the result.
+/// This proves that
+/// 1. `diff`, which is `column - extrema` for `MIN` and `extrema - column` for `MAX`, is never negative and
+/// 2. the rows marked as `attained` are selected rows where `diff` is zero, one for each group of the result.
+fn prove_extremum<'a, S: Scalar>(
+    builder: &mut ProofBuilder<'a, S>,
+    alloc: &'a Bump,
+    extremum: Extremum,
+    (column, sel_in, g_in_star): (&Column<'a, S>, &'a [bool], &'a [S]),
+    ExtremumWitness { extrema, attained }: ExtremumWitness<'a, S>,
+    (g_out_star, m_out): (&'a [S], usize),
+) {
+    // diff = column - extrema for MIN and extrema - column for MAX
+    let diff: &[_] = match extremum {
+        Extremum::Min => {
+            let diff = alloc.alloc_slice_fill_iter(extrema.iter().map(|&e| -e));
+            column.mul_add(diff, &S::one());
+            diff
+        }
+        Extremum::Max => {
+            let diff = alloc.alloc_slice_copy(extrema);
+            column.mul_add(diff, &-S::one());
+            diff
+        }
+    };
+
+    // sign(diff) == 0
+    prover_evaluate_sign(builder, alloc, diff);
+
+    // sum attained * sel_in * g_in_star - g_out_star * indexes = 0
+    let indexes = alloc.alloc_slice_fill_copy(m_out, true);
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (
+                S::one(),
+                vec![Box::new(attained), Box::new(sel_in), Box::new(g_in_star)],
+            ),
+            (
+                -S::one(),
+                vec![Box::new(g_out_star), Box::new(indexes as &[_])],
+            ),
+        ],
+    );
+
+    // attained * diff = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![(S::one(), vec![Box::new(attained), Box::new(diff)])],
+    );
 }
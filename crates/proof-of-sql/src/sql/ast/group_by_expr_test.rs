@@ -1,14 +1,22 @@
 use super::test_utility::{
-    and, cols_expr, column, const_int128, const_varchar, equal, group_by, sums_expr, tab,
+    add, aliased_cols_expr, aliased_plan, and, case, cols_expr_plan, column, const_bigint,
+    const_bool, const_int128, const_varchar, equal, group_by, group_by_with_min_max, gte, multiply,
+    sums_expr, tab,
 };
 use crate::{
     base::{
         commitment::InnerProductProof,
-        database::{owned_table_utility::*, ColumnType, OwnedTableTestAccessor, TestAccessor},
+        database::{
+            owned_table_utility::*, ColumnField, ColumnType, OwnedTableTestAccessor, TestAccessor,
+        },
         scalar::Curve25519Scalar,
     },
-    sql::proof::{exercise_verification, VerifiableQueryResult},
+    sql::proof::{
+        exercise_verification, Indexes, ProvableQueryResult, ProvableResultColumn,
+        VerifiableQueryResult,
+    },
 };
+use curve25519_dalek::RistrettoPoint;
 
 #[test]
 fn we_can_prove_a_simple_group_by_with_bigint_columns() {
@@ -21,7 +29,7 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = group_by(
-        cols_expr(t, &["a"], &accessor),
+        cols_expr_plan(t, &["a"], &accessor),
         sums_expr(t, &["c"], &["sum_c"], &[ColumnType::BigInt], &accessor),
         "__count__",
         tab(t),
@@ -38,6 +46,98 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
     assert_eq!(res, expected);
 }
 
+#[test]
+fn we_can_prove_a_group_by_on_expressions() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("b", [99, 99, 99, 99, 0]),
+        bigint("c", [101, 102, 103, 104, 105]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // SELECT a * 10 + 1 AS k, CASE WHEN c >= 103 THEN 'high' ELSE 'low' END AS bucket, SUM(c) AS sum_c, COUNT(*) AS __count__
+    //  FROM sxt.t WHERE b = 99 GROUP BY a * 10 + 1, bucket
+    let expr = group_by(
+        vec![
+            aliased_plan(
+                add(
+                    multiply(column(t, "a", &accessor), const_bigint(10)),
+                    const_bigint(1),
+                ),
+                "k",
+            ),
+            aliased_plan(
+                case(
+                    vec![(
+                        gte(column(t, "c", &accessor), const_bigint(103)),
+                        const_varchar("high"),
+                    )],
+                    const_varchar("low"),
+                ),
+                "bucket",
+            ),
+        ],
+        sums_expr(t, &["c"], &["sum_c"], &[ColumnType::BigInt], &accessor),
+        "__count__",
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("k", [11, 11, 21, 21]),
+        varchar("bucket", ["high", "low", "high", "low"]),
+        bigint("sum_c", [104, 101, 103, 102]),
+        bigint("__count__", [1, 1, 1, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_group_by_with_sums_over_expressions() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("b", [99, 99, 99, 99, 0]),
+        bigint("c", [101, 102, 103, 104, 105]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // SELECT a, SUM(a * c + 1) AS s, COUNT(CASE WHEN c >= 103 THEN 1 END) AS high, COUNT(*) AS __count__
+    //  FROM sxt.t WHERE b = 99 GROUP BY a
+    let expr = group_by(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![
+            (
+                add(
+                    multiply(column(t, "a", &accessor), column(t, "c", &accessor)),
+                    const_bigint(1),
+                ),
+                ColumnField::new("s".parse().unwrap(), ColumnType::BigInt),
+            ),
+            (
+                gte(column(t, "c", &accessor), const_bigint(103)),
+                ColumnField::new("high".parse().unwrap(), ColumnType::BigInt),
+            ),
+        ],
+        "__count__",
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2]),
+        bigint("s", [101 + 1 + 104 + 1, 2 * 102 + 1 + 2 * 103 + 1]),
+        bigint("high", [1, 1]),
+        bigint("__count__", [2, 2]),
+    ]);
+    assert_eq!(res, expected);
+}
+
 #[test]
 fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     let scalar_filter_data: Vec<Curve25519Scalar> = [
@@ -122,7 +222,7 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     //  FROM sxt.t WHERE int128_filter = 1020 AND varchar_filter = 'f2'
     //  GROUP BY scalar_group, int128_group, bigint_group
     let expr = group_by(
-        cols_expr(
+        cols_expr_plan(
             t,
             &["scalar_group", "int128_group", "bigint_group"],
             &accessor,
@@ -184,3 +284,217 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     ]);
     assert_eq!(res, expected);
 }
+
+#[test]
+fn we_can_prove_a_group_by_with_min_and_max() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2, 1, 3]),
+        bigint("b", [99, 99, 99, 99, 0, 99, 99]),
+        bigint("c", [101, -102, 103, 104, 105, 99, 7]),
+        int128("d", [5, 6, 7, 8, 9, 10, 11]),
+        smallint("e", [-3_i16, 4, 4, 2, -8, 2, 0]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by_with_min_max(
+        cols_expr_plan(t, &["a"], &accessor),
+        sums_expr(t, &["c"], &["sum_c"], &[ColumnType::BigInt], &accessor),
+        aliased_cols_expr(t, &["c", "e"], &["min_c", "min_e"], &accessor),
+        aliased_cols_expr(t, &["d", "e"], &["max_d", "max_e"], &accessor),
+        "__count__",
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("sum_c", [101 + 104 + 99, -102 + 103, 7]),
+        bigint("min_c", [99, -102, 7]),
+        smallint("min_e", [-3_i16, 4, 0]),
+        int128("max_d", [10, 7, 11]),
+        smallint("max_e", [2_i16, 4, 0]),
+        bigint("__count__", [3, 2, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_min_and_max_without_group_by_columns() {
+    let data = owned_table([int("a", [4, -7, 12, 0, 3]), bigint("b", [1, 2, 3, 4, 5])]);
+    let t = "sxt.t".parse().unwrap();
+    for offset in [0, 3] {
+        let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+        accessor.add_table(t, data.clone(), offset);
+        let expr = group_by_with_min_max(
+            vec![],
+            vec![],
+            aliased_cols_expr(t, &["a"], &["min_a"], &accessor),
+            aliased_cols_expr(t, &["a"], &["max_a"], &accessor),
+            "__count__",
+            tab(t),
+            gte(column(t, "b", &accessor), const_int128(2)),
+        );
+        let res = VerifiableQueryResult::new(&expr, &accessor, &());
+        exercise_verification(&res, &expr, &accessor, t);
+        let res = res.verify(&expr, &accessor, &()).unwrap().table;
+        let expected = owned_table([
+            int("min_a", [-7]),
+            int("max_a", [12]),
+            bigint("__count__", [4]),
+        ]);
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_prove_a_min_and_max_with_no_selected_rows() {
+    let data = owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 5, 6])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by_with_min_max(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![],
+        aliased_cols_expr(t, &["b"], &["min_b"], &accessor),
+        aliased_cols_expr(t, &["b"], &["max_b"], &accessor),
+        "__count__",
+        tab(t),
+        const_bool(false),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [0; 0]),
+        bigint("min_b", [0; 0]),
+        bigint("max_b", [0; 0]),
+        bigint("__count__", [0; 0]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_verify_a_group_by_with_a_wrong_min_or_max() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 1]),
+        bigint("b", [10, 20, 30, 40, 50]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by_with_min_max::<RistrettoPoint>(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![],
+        aliased_cols_expr(t, &["b"], &["min_b"], &accessor),
+        aliased_cols_expr(t, &["b"], &["max_b"], &accessor),
+        "__count__",
+        tab(t),
+        gte(column(t, "b", &accessor), const_int128(20)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    assert!(res.verify(&expr, &accessor, &()).is_ok());
+
+    let tampered_results: [[&[i64]; 4]; 5] = [
+        // a min that is not attained
+        [&[1, 2], &[39, 20], &[50, 30], &[2, 2]],
+        // a min that is larger than a selected row
+        [&[1, 2], &[50, 20], &[50, 30], &[2, 2]],
+        // a max that is smaller than a selected row
+        [&[1, 2], &[40, 20], &[40, 30], &[2, 2]],
+        // a min taken from a row that is not selected
+        [&[1, 2], &[40, 10], &[50, 30], &[2, 2]],
+        // a group that is split in two
+        [&[1, 1, 2], &[40, 50, 20], &[40, 50, 30], &[1, 1, 2]],
+    ];
+    for columns in tampered_results {
+        let mut res_p = res.clone();
+        let indexes = Indexes::Dense(0..(columns[0].len() as u64));
+        let columns = columns.map(|column| Box::new(column) as Box<dyn ProvableResultColumn>);
+        res_p.provable_result = Some(ProvableQueryResult::new(&indexes, &columns));
+        assert!(res_p.verify(&expr, &accessor, &()).is_err());
+    }
+}
+
+#[test]
+fn we_can_prove_an_aggregation_without_group_by_columns() {
+    let data = owned_table([bigint("a", [1, 2, 3, 4, 5]), bigint("b", [1, 0, 1, 0, 1])]);
+    let t = "sxt.t".parse().unwrap();
+    for offset in [0, 3] {
+        let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+        accessor.add_table(t, data.clone(), offset);
+        let expr = group_by(
+            vec![],
+            sums_expr(t, &["a"], &["sum_a"], &[ColumnType::BigInt], &accessor),
+            "num",
+            tab(t),
+            equal(column(t, "b", &accessor), const_int128(1)),
+        );
+        let res = VerifiableQueryResult::new(&expr, &accessor, &());
+        exercise_verification(&res, &expr, &accessor, t);
+        let res = res.verify(&expr, &accessor, &()).unwrap().table;
+        let expected = owned_table([bigint("sum_a", [1 + 3 + 5]), bigint("num", [3])]);
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_prove_an_aggregation_without_group_by_columns_and_with_no_selected_rows() {
+    let data = owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 5, 6])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        vec![],
+        sums_expr(t, &["a"], &["sum_a"], &[ColumnType::BigInt], &accessor),
+        "num",
+        tab(t),
+        const_bool(false),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("sum_a", [0]), bigint("num", [0])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_verify_a_group_by_with_missing_or_empty_groups() {
+    let data = owned_table([bigint("a", [1, 2, 2, 1, 1]), bigint("b", [1, 0, 1, 0, 1])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+
+    // Without group by columns, the result must be exactly one row, even if no row is selected.
+    let expr = group_by::<RistrettoPoint>(
+        vec![],
+        sums_expr(t, &["a"], &["sum_a"], &[ColumnType::BigInt], &accessor),
+        "num",
+        tab(t),
+        const_bool(false),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    assert!(res.verify(&expr, &accessor, &()).is_ok());
+    let mut res_p = res.clone();
+    let columns: [Box<dyn ProvableResultColumn>; 2] = [Box::new([0_i64; 0]), Box::new([0_i64; 0])];
+    res_p.provable_result = Some(ProvableQueryResult::new(&Indexes::Dense(0..0), &columns));
+    assert!(res_p.verify(&expr, &accessor, &()).is_err());
+
+    // With group by columns, a group without selected rows must not be in the result.
+    let expr = group_by::<RistrettoPoint>(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![],
+        "num",
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(1)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    assert!(res.verify(&expr, &accessor, &()).is_ok());
+    let mut res_p = res.clone();
+    let columns: [Box<dyn ProvableResultColumn>; 2] =
+        [Box::new(&[1_i64, 2, 3][..]), Box::new(&[2_i64, 1, 0][..])];
+    res_p.provable_result = Some(ProvableQueryResult::new(&Indexes::Dense(0..3), &columns));
+    assert!(res_p.verify(&expr, &accessor, &()).is_err());
+}
@@ -1,10 +1,11 @@
 //! Contains the utility functions for the `GroupByExpr` node.
 
 use super::filter_column_by_index;
-use crate::base::{database::Column, scalar::Scalar};
+use crate::base::{database::Column, polynomial::MultilinearExtension, scalar::Scalar};
 use bumpalo::Bump;
 use core::cmp::Ordering;
 use itertools::Itertools;
+use num_traits::One;
 use rayon::prelude::ParallelSliceMut;
 use thiserror::Error;
 
@@ -15,9 +16,31 @@ pub struct AggregatedColumns<'a, S: Scalar> {
     pub group_by_columns: Vec<Column<'a, S>>,
     /// Resulting sums of the groups for the columns in `sum_columns_in`.
     pub sum_columns: Vec<&'a [S]>,
+    /// Resulting minimums of the groups for the columns in `min_columns_in`.
+    pub min_columns: Vec<Column<'a, S>>,
+    /// Resulting maximums of the groups for the columns in `max_columns_in`.
+    pub max_columns: Vec<Column<'a, S>>,
     /// The number of rows in each group.
     pub count_column: &'a [i64],
 }
+
+/// Whether an aggregation picks the smallest or the largest value of each group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extremum {
+    /// `MIN`
+    Min,
+    /// `MAX`
+    Max,
+}
+
+/// The witness that the prover commits to in order to prove a `MIN` or `MAX` aggregation.
+pub struct ExtremumWitness<'a, S: Scalar> {
+    /// For each selected row, the extremum of its group. For every other row, the value of the row itself.
+    pub extrema: &'a [S],
+    /// Marks, for each group, a single selected row whose value is the extremum of the group.
+    pub attained: &'a [bool],
+}
+
 #[derive(Error, Debug)]
 pub enum AggregateColumnsError {
     #[error("Column length mismatch")]
@@ -26,72 +49,189 @@ pub enum AggregateColumnsError {
 
 /// This is a function that gives the result of a group by query similar to the following:
 /// ```sql
-///     SELECT <group_by[0]>, <group_by[1]>, ..., SUM(<sum_columns[0]>), SUM(<sum_columns[1]>), ..., COUNT(*)
+///     SELECT <group_by[0]>, <group_by[1]>, ..., SUM(<sum_columns[0]>), SUM(<sum_columns[1]>), ...,
+///         MIN(<min_columns[0]>), ..., MAX(<max_columns[0]>), ..., COUNT(*)
 ///         WHERE selection GROUP BY <group_by[0]>, <group_by[1]>, ...
 /// ```
 ///
-/// This function takes a selection vector and a set of group_by and sum columns and returns
+/// This function takes a selection vector and a set of group_by, sum, min and max columns and returns
 /// the given columns aggregated by the group_by columns only for the selected rows.
+///
+/// If there are no group_by columns, the result has a single row, unless no row is selected and
+/// there are min or max columns, in which case the result is empty.
 pub fn aggregate_columns<'a, S: Scalar>(
     alloc: &'a Bump,
     group_by_columns_in: &[Column<'a, S>],
     sum_columns_in: &[Column<S>],
+    min_columns_in: &[Column<'a, S>],
+    max_columns_in: &[Column<'a, S>],
     selection_column_in: &[bool],
 ) -> Result<AggregatedColumns<'a, S>, AggregateColumnsError> {
-    for col in group_by_columns_in {
-        if col.len() != selection_column_in.len() {
-            return Err(AggregateColumnsError::ColumnLengthMismatch);
-        }
-    }
-    for col in sum_columns_in {
+    for col in sum_columns_in
+        .iter()
+        .chain(min_columns_in)
+        .chain(max_columns_in)
+    {
         if col.len() != selection_column_in.len() {
             return Err(AggregateColumnsError::ColumnLengthMismatch);
         }
     }
-
-    // `filtered_indexes` is a vector of indexes of the rows that are selected. We sort this vector
-    // so that all the rows in the same group are next to each other.
-    let mut filtered_indexes = Vec::from_iter(
-        selection_column_in
-            .iter()
-            .enumerate()
-            .filter(|&(_, &b)| b)
-            .map(|(i, _)| i),
-    );
-    filtered_indexes
-        .par_sort_unstable_by(|&a, &b| compare_indexes_by_columns(group_by_columns_in, a, b));
+    let (filtered_indexes, mut counts) =
+        group_selected_indexes(group_by_columns_in, selection_column_in)?;
 
     // `group_by_result_indexes` gives a single index for each group in `filtered_indexes`. It does
     // not matter which index is chosen for each group, so we choose the first one. This is only used
     // to extract the `group_by_columns_out`, which is the same for all elements in the group.
-    let (counts, group_by_result_indexes): (Vec<_>, Vec<_>) = filtered_indexes
-        .iter()
-        .dedup_by_with_count(|&&a, &&b| {
-            compare_indexes_by_columns(group_by_columns_in, a, b) == Ordering::Equal
-        })
-        .multiunzip();
+    let group_by_result_indexes = Vec::from_iter(counts.iter().scan(0, |start, &count| {
+        let index = filtered_indexes[*start];
+        *start += count;
+        Some(index)
+    }));
     let group_by_columns_out = Vec::from_iter(
         group_by_columns_in
             .iter()
             .map(|column| filter_column_by_index(alloc, column, &group_by_result_indexes)),
     );
 
+    // Without group_by columns, all the selected rows form a single group, which is kept even when no
+    // row is selected so that the sums and the count are zero. There is no minimum or maximum of
+    // zero rows, so the group is dropped in that case when there are min or max columns.
+    if group_by_columns_in.is_empty()
+        && counts.is_empty()
+        && min_columns_in.is_empty()
+        && max_columns_in.is_empty()
+    {
+        counts.push(0);
+    }
+
     // This calls the `sum_aggregate_column_by_index_counts` function on each column in `sum_columns`
     // and gives a vector of `S` slices
     let sum_columns_out = Vec::from_iter(sum_columns_in.iter().map(|column| {
         sum_aggregate_column_by_index_counts(alloc, column, &counts, &filtered_indexes)
     }));
 
+    // The minimums and maximums are the values at the row of each group where they are attained.
+    let extremum_columns_out = |columns_in: &[Column<'a, S>], extremum| {
+        Vec::from_iter(columns_in.iter().map(|column| {
+            let indexes = extremum_indexes(column, extremum, &counts, &filtered_indexes);
+            filter_column_by_index(alloc, column, &indexes)
+        }))
+    };
+    let min_columns_out = extremum_columns_out(min_columns_in, Extremum::Min);
+    let max_columns_out = extremum_columns_out(max_columns_in, Extremum::Max);
+
     // Cast the counts to something compatible with BigInt.
     let count_column_out = alloc.alloc_slice_fill_iter(counts.into_iter().map(|c| c as i64));
 
     Ok(AggregatedColumns {
         group_by_columns: group_by_columns_out,
         sum_columns: sum_columns_out,
+        min_columns: min_columns_out,
+        max_columns: max_columns_out,
         count_column: count_column_out,
     })
 }
 
+/// This function computes the witness needed to prove the result of `MIN(<column>)` (or `MAX(<column>)`)
+/// grouped by `group_by_columns_in` over the selected rows.
+///
+/// See [`ExtremumWitness`] for the content of the witness.
+pub fn extremum_witness<'a, S: Scalar>(
+    alloc: &'a Bump,
+    group_by_columns_in: &[Column<'a, S>],
+    column: &Column<S>,
+    extremum: Extremum,
+    selection_column_in: &[bool],
+) -> Result<ExtremumWitness<'a, S>, AggregateColumnsError> {
+    if column.len() != selection_column_in.len() {
+        return Err(AggregateColumnsError::ColumnLengthMismatch);
+    }
+    let (filtered_indexes, counts) =
+        group_selected_indexes(group_by_columns_in, selection_column_in)?;
+    let extremum_indexes = extremum_indexes(column, extremum, &counts, &filtered_indexes);
+
+    let values = alloc.alloc_slice_fill_default(column.len());
+    column.mul_add(values, &One::one());
+    let extrema = alloc.alloc_slice_copy(values);
+    let attained = alloc.alloc_slice_fill_copy(column.len(), false);
+    let mut start = 0;
+    for (&count, &extremum_index) in counts.iter().zip(extremum_indexes.iter()) {
+        for &i in &filtered_indexes[start..start + count] {
+            extrema[i] = values[extremum_index];
+        }
+        attained[extremum_index] = true;
+        start += count;
+    }
+    Ok(ExtremumWitness { extrema, attained })
+}
+
+/// Returns the indexes of the selected rows, sorted so that all the rows in the same group are next to
+/// each other, along with the number of rows in each group.
+fn group_selected_indexes<S: Scalar>(
+    group_by_columns_in: &[Column<S>],
+    selection_column_in: &[bool],
+) -> Result<(Vec<usize>, Vec<usize>), AggregateColumnsError> {
+    for col in group_by_columns_in {
+        if col.len() != selection_column_in.len() {
+            return Err(AggregateColumnsError::ColumnLengthMismatch);
+        }
+    }
+
+    // `filtered_indexes` is a vector of indexes of the rows that are selected. We sort this vector
+    // so that all the rows in the same group are next to each other.
+    let mut filtered_indexes = Vec::from_iter(
+        selection_column_in
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| b)
+            .map(|(i, _)| i),
+    );
+    filtered_indexes
+        .par_sort_unstable_by(|&a, &b| compare_indexes_by_columns(group_by_columns_in, a, b));
+
+    let counts = Vec::from_iter(
+        filtered_indexes
+            .iter()
+            .dedup_by_with_count(|&&a, &&b| {
+                compare_indexes_by_columns(group_by_columns_in, a, b) == Ordering::Equal
+            })
+            .map(|(count, _)| count),
+    );
+    Ok((filtered_indexes, counts))
+}
+
+/// Returns, for each group, the index of a row where `column` attains its minimum (or maximum) within the group.
+/// The `counts` slice contains the number of elements in each group and the `indexes` slice
+/// contains the indexes of the elements in `column`.
+pub(super) fn extremum_indexes<S: Scalar>(
+    column: &Column<S>,
+    extremum: Extremum,
+    counts: &[usize],
+    indexes: &[usize],
+) -> Vec<usize> {
+    let preferred = match extremum {
+        Extremum::Min => Ordering::Less,
+        Extremum::Max => Ordering::Greater,
+    };
+    let column = core::slice::from_ref(column);
+    let mut start = 0;
+    Vec::from_iter(counts.iter().map(|&count| {
+        let group = &indexes[start..start + count];
+        start += count;
+        group
+            .iter()
+            .copied()
+            .reduce(|best, i| {
+                if compare_indexes_by_columns(column, i, best) == preferred {
+                    i
+                } else {
+                    best
+                }
+            })
+            .expect("groups are never empty")
+    }))
+}
+
 /// Returns a slice with the lifetime of `alloc` that contains the grouped sums of `column`.
 /// The `counts` slice contains the number of elements in each group and the `indexes` slice
 /// contains the indexes of the elements in `column`.
@@ -114,6 +254,9 @@ pub(super) fn sum_aggregate_column_by_index_counts<'a, S: Scalar>(
         }
         Column::Scalar(col) => sum_aggregate_slice_by_index_counts(alloc, col, counts, indexes),
         Column::VarChar(_) => unimplemented!("Cannot sum varchar columns"),
+        Column::TimeStamp(_, _, _) | Column::Date(_) => {
+            unimplemented!("Cannot sum timestamp or date columns")
+        }
     }
 }
 
@@ -172,10 +315,18 @@ pub(super) fn compare_indexes_by_columns<S: Scalar>(
             Column::Int(col) => col[i].cmp(&col[j]),
             Column::BigInt(col) => col[i].cmp(&col[j]),
             Column::Int128(col) => col[i].cmp(&col[j]),
-            Column::Decimal75(_, _, _) => todo!("TODO: unimplemented"),
+            Column::Decimal75(_, _, col) => signed_cmp(&col[i], &col[j]),
             Column::Scalar(col) => col[i].cmp(&col[j]),
             Column::VarChar((col, _)) => col[i].cmp(col[j]),
+            Column::TimeStamp(_, _, col) => col[i].cmp(&col[j]),
+            Column::Date(col) => col[i].cmp(&col[j]),
         })
         .find(|&ord| ord != Ordering::Equal)
         .unwrap_or(Ordering::Equal)
 }
+
+/// Compares two scalars as signed values, i.e. treating values above `S::MAX_SIGNED` as negative.
+fn signed_cmp<S: Scalar>(a: &S, b: &S) -> Ordering {
+    let is_nonnegative = |x: &S| *x <= S::MAX_SIGNED;
+    (is_nonnegative(a), a).cmp(&(is_nonnegative(b), b))
+}
@@ -1,12 +1,13 @@
 use super::{
     aggregate_columns,
     group_by_util::{
-        compare_indexes_by_columns, sum_aggregate_column_by_index_counts,
-        sum_aggregate_slice_by_index_counts,
+        compare_indexes_by_columns, extremum_indexes, extremum_witness,
+        sum_aggregate_column_by_index_counts, sum_aggregate_slice_by_index_counts,
+        AggregateColumnsError, Extremum,
     },
 };
 use crate::{
-    base::{database::Column, scalar::Curve25519Scalar},
+    base::{database::Column, math::decimal::Precision, scalar::Curve25519Scalar},
     proof_primitive::dory::DoryScalar,
 };
 use bumpalo::Bump;
@@ -22,13 +23,22 @@ fn we_can_aggregate_empty_columns() {
     let sum_columns = &[column_c.clone(), column_d.clone()];
     let selection = &[];
     let alloc = Bump::new();
-    let aggregate_result = aggregate_columns(&alloc, group_by, sum_columns, selection)
-        .expect("Aggregation should succeed");
+    let aggregate_result = aggregate_columns(
+        &alloc,
+        group_by,
+        sum_columns,
+        &[column_c.clone()],
+        &[column_d.clone()],
+        selection,
+    )
+    .expect("Aggregation should succeed");
     assert_eq!(
         aggregate_result.group_by_columns,
         vec![Column::BigInt(&[]), Column::VarChar((&[], &[]))]
     );
     assert_eq!(aggregate_result.sum_columns, vec![&[], &[]]);
+    assert_eq!(aggregate_result.min_columns, vec![Column::Int128(&[])]);
+    assert_eq!(aggregate_result.max_columns, vec![Column::Scalar(&[])]);
     assert_eq!(aggregate_result.count_column, &[0i64; 0]);
 }
 
@@ -52,8 +62,15 @@ fn we_can_aggregate_columns() {
     let group_by = &[column_a.clone(), column_b.clone()];
     let sum_columns = &[column_c.clone(), column_d.clone()];
     let alloc = Bump::new();
-    let aggregate_result = aggregate_columns(&alloc, group_by, sum_columns, selection)
-        .expect("Aggregation should succeed");
+    let aggregate_result = aggregate_columns(
+        &alloc,
+        group_by,
+        sum_columns,
+        &[column_c.clone()],
+        &[column_d.clone()],
+        selection,
+    )
+    .expect("Aggregation should succeed");
     let scals_res = [
         Curve25519Scalar::from("Cat"),
         Curve25519Scalar::from("Dog"),
@@ -84,12 +101,145 @@ fn we_can_aggregate_columns() {
             Curve25519Scalar::from(202 + 210),
         ],
     ];
+    let expected_min_result = &[Column::Int128(&[105, 106, 103, 104, 101, 102])];
+    let scals_max = [205, 206, 207, 208, 211, 210].map(Curve25519Scalar::from);
+    let expected_max_result = &[Column::Scalar(&scals_max)];
     let expected_count_result = &[1, 1, 2, 2, 3, 2];
     assert_eq!(aggregate_result.group_by_columns, expected_group_by_result);
     assert_eq!(aggregate_result.sum_columns, expected_sum_result);
+    assert_eq!(aggregate_result.min_columns, expected_min_result);
+    assert_eq!(aggregate_result.max_columns, expected_max_result);
     assert_eq!(aggregate_result.count_column, expected_count_result);
 }
 
+#[test]
+fn we_can_aggregate_columns_without_group_by_columns() {
+    let column = Column::BigInt::<Curve25519Scalar>(&[1, 2, 3, 4]);
+    let alloc = Bump::new();
+    let aggregate_result = aggregate_columns(
+        &alloc,
+        &[],
+        &[column.clone()],
+        &[],
+        &[],
+        &[true, false, true, false],
+    )
+    .expect("Aggregation should succeed");
+    assert!(aggregate_result.group_by_columns.is_empty());
+    assert_eq!(aggregate_result.sum_columns, vec![&[4.into()]]);
+    assert_eq!(aggregate_result.count_column, &[2]);
+
+    // Without selected rows, there is still a single row of zeros...
+    let aggregate_result = aggregate_columns(&alloc, &[], &[column.clone()], &[], &[], &[false; 4])
+        .expect("Aggregation should succeed");
+    assert_eq!(aggregate_result.sum_columns, vec![&[0.into()]]);
+    assert_eq!(aggregate_result.count_column, &[0]);
+
+    // ...unless there is a min or a max, which are undefined for no rows.
+    let aggregate_result = aggregate_columns(
+        &alloc,
+        &[],
+        &[column.clone()],
+        &[column.clone()],
+        &[],
+        &[false; 4],
+    )
+    .expect("Aggregation should succeed");
+    assert_eq!(aggregate_result.sum_columns, vec![&[]]);
+    assert_eq!(aggregate_result.min_columns, vec![Column::BigInt(&[])]);
+    assert_eq!(aggregate_result.count_column, &[0i64; 0]);
+}
+
+#[test]
+fn we_cannot_aggregate_columns_of_different_lengths() {
+    let group_by = &[Column::BigInt::<Curve25519Scalar>(&[1, 2])];
+    let selection = &[true, false];
+    let alloc = Bump::new();
+    assert!(matches!(
+        aggregate_columns(
+            &alloc,
+            group_by,
+            &[],
+            &[Column::BigInt(&[1, 2, 3])],
+            &[],
+            selection
+        ),
+        Err(AggregateColumnsError::ColumnLengthMismatch)
+    ));
+    assert!(matches!(
+        extremum_witness(
+            &alloc,
+            group_by,
+            &Column::BigInt(&[1]),
+            Extremum::Max,
+            selection
+        ),
+        Err(AggregateColumnsError::ColumnLengthMismatch)
+    ));
+}
+
+#[test]
+fn we_can_compute_extremum_indexes() {
+    let column = Column::<Curve25519Scalar>::Int(&[5, 3, 4, 9, 1, 3]);
+    let counts = &[2, 3, 1];
+    let indexes = &[0, 2, 1, 3, 5, 4];
+    assert_eq!(
+        extremum_indexes(&column, Extremum::Min, counts, indexes),
+        vec![2, 1, 4]
+    );
+    assert_eq!(
+        extremum_indexes(&column, Extremum::Max, counts, indexes),
+        vec![0, 3, 4]
+    );
+    assert!(extremum_indexes(&column, Extremum::Min, &[], &[]).is_empty());
+}
+
+#[test]
+fn we_can_compute_extremum_witnesses() {
+    let group_by = &[Column::<Curve25519Scalar>::BigInt(&[1, 2, 1, 2, 1])];
+    let column = Column::BigInt(&[5, 3, 4, 9, 1]);
+    let selection = &[true, true, true, true, false];
+    let alloc = Bump::new();
+
+    let min_witness = extremum_witness(&alloc, group_by, &column, Extremum::Min, selection)
+        .expect("Aggregation should succeed");
+    assert_eq!(
+        min_witness.extrema,
+        [4, 3, 4, 3, 1].map(Curve25519Scalar::from)
+    );
+    assert_eq!(min_witness.attained, [false, true, true, false, false]);
+
+    let max_witness = extremum_witness(&alloc, group_by, &column, Extremum::Max, selection)
+        .expect("Aggregation should succeed");
+    assert_eq!(
+        max_witness.extrema,
+        [5, 9, 5, 9, 1].map(Curve25519Scalar::from)
+    );
+    assert_eq!(max_witness.attained, [true, false, false, true, false]);
+}
+
+#[test]
+fn we_can_compute_extremum_witnesses_without_group_by_columns() {
+    let column = Column::<Curve25519Scalar>::SmallInt(&[5, 3, 4, -9, 1]);
+    let selection = &[true, true, true, false, true];
+    let alloc = Bump::new();
+    let witness = extremum_witness(&alloc, &[], &column, Extremum::Min, selection)
+        .expect("Aggregation should succeed");
+    assert_eq!(
+        witness.extrema,
+        [1, 1, 1, -9, 1].map(Curve25519Scalar::from)
+    );
+    assert_eq!(witness.attained, [false, false, false, false, true]);
+
+    let witness = extremum_witness(&alloc, &[], &column, Extremum::Max, &[false; 5])
+        .expect("Aggregation should succeed");
+    assert_eq!(
+        witness.extrema,
+        [5, 3, 4, -9, 1].map(Curve25519Scalar::from)
+    );
+    assert_eq!(witness.attained, [false; 5]);
+}
+
 #[test]
 fn we_can_compare_indexes_by_columns_with_no_columns() {
     let columns: &[Column<Curve25519Scalar>; 0] = &[];
@@ -273,3 +423,20 @@ fn we_can_sum_aggregate_columns_by_counts() {
     let result = sum_aggregate_column_by_index_counts(&alloc, &columns_c, counts, indexes);
     assert_eq!(result, expected);
 }
+#[test]
+fn we_can_compare_indexes_by_columns_for_decimal_columns_as_signed_values() {
+    let scals_a: Vec<Curve25519Scalar> = [-300, 200, -300, 0, -100, 200]
+        .iter()
+        .map(|&v: &i64| v.into())
+        .collect();
+    let column_a = Column::Decimal75(Precision::new(10).unwrap(), 2, &scals_a);
+
+    let columns = &[column_a];
+    assert_eq!(compare_indexes_by_columns(columns, 0, 1), Ordering::Less);
+    assert_eq!(compare_indexes_by_columns(columns, 1, 0), Ordering::Greater);
+    assert_eq!(compare_indexes_by_columns(columns, 0, 2), Ordering::Equal);
+    assert_eq!(compare_indexes_by_columns(columns, 0, 4), Ordering::Less);
+    assert_eq!(compare_indexes_by_columns(columns, 4, 3), Ordering::Less);
+    assert_eq!(compare_indexes_by_columns(columns, 3, 5), Ordering::Less);
+    assert_eq!(compare_indexes_by_columns(columns, 1, 5), Ordering::Equal);
+}
@@ -0,0 +1,211 @@
+use super::{
+    count_equals_zero, prover_evaluate_equals_zero, verifier_evaluate_equals_zero, ProvableExpr,
+    ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, LiteralValue},
+        math::decimal::scale_scalar,
+        polynomial::MultilinearExtension,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        CountBuilder, ProofBuilder, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
+        VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The largest number of literals that an IN list can have.
+pub(crate) const MAX_IN_LIST_LENGTH: usize = 256;
+
+/// The number of literals whose factors are multiplied in a single constraint, which bounds its degree.
+const IN_LIST_CHUNK_LENGTH: usize = 8;
+
+/// Provable AST expression for a membership test in a list of literals e.g. `a IN (1, 2, 3)`
+///
+/// Rather than proving one equality per literal, this proves that
+/// `(a - c_1) * (a - c_2) * ... * (a - c_n)` is zero, which only takes
+/// one zero check. The product is built up by one constraint per chunk of
+/// [`IN_LIST_CHUNK_LENGTH`] literals, each multiplying the product of the previous chunks
+/// by the factors of its own literals, so that the degree of the constraints stays bounded.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InListExpr<C: Commitment> {
+    expr: Box<ProvableExprPlan<C>>,
+    list: Vec<LiteralValue<C::Scalar>>,
+}
+
+impl<C: Commitment> InListExpr<C> {
+    /// Create a new IN expression
+    ///
+    /// Note: `list` must not be empty.
+    pub fn new(expr: Box<ProvableExprPlan<C>>, list: Vec<LiteralValue<C::Scalar>>) -> Self {
+        assert!(!list.is_empty(), "IN lists must not be empty");
+        Self { expr, list }
+    }
+
+    /// The scale that the expression and the literals are compared at
+    fn max_scale(&self) -> i8 {
+        self.list
+            .iter()
+            .map(|literal| literal.column_type().scale().unwrap_or(0))
+            .fold(self.expr.data_type().scale().unwrap_or(0), i8::max)
+    }
+
+    /// The upscaling of the expression to the common scale
+    fn expr_upscale(&self) -> i8 {
+        self.max_scale() - self.expr.data_type().scale().unwrap_or(0)
+    }
+
+    /// The literals of the list, scaled to the common scale
+    fn scaled_list(&self) -> Vec<C::Scalar> {
+        let max_scale = self.max_scale();
+        self.list
+            .iter()
+            .map(|literal| {
+                let upscale = max_scale - literal.column_type().scale().unwrap_or(0);
+                scale_scalar(literal.to_scalar(), upscale).expect("Failed to scale literal")
+            })
+            .collect()
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for InListExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        let num_chunks = self.list.len().div_ceil(IN_LIST_CHUNK_LENGTH);
+        builder.count_subpolynomials(num_chunks);
+        builder.count_intermediate_mles(num_chunks);
+        for (index, chunk) in self.list.chunks(IN_LIST_CHUNK_LENGTH).enumerate() {
+            // The constraints of the chunks after the first one also multiply by the previous product.
+            builder.count_degree(chunk.len() + 1 + usize::from(index > 0));
+        }
+        count_equals_zero(builder);
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "InListExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let values = self
+            .expr
+            .result_evaluate(table_length, alloc, accessor)
+            .to_scalar_with_scaling(self.expr_upscale());
+        let list = self.scaled_list();
+        Column::Boolean(alloc.alloc_slice_fill_with(table_length, |i| list.contains(&values[i])))
+    }
+
+    #[tracing::instrument(name = "InListExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let table_length = builder.table_length();
+        let values: &[_] = alloc.alloc_slice_copy(
+            &self
+                .expr
+                .prover_evaluate(builder, alloc, accessor)
+                .to_scalar_with_scaling(self.expr_upscale()),
+        );
+        let list = self.scaled_list();
+        let ones: &[_] = alloc.alloc_slice_fill_copy(table_length, true);
+
+        let mut previous: Option<&'a [C::Scalar]> = None;
+        for chunk in list.chunks(IN_LIST_CHUNK_LENGTH) {
+            // product = previous * (values - c_1) * ... * (values - c_k)
+            let product: &'a [_] = alloc.alloc_slice_fill_with(table_length, |i| {
+                let chunk_product: C::Scalar = chunk.iter().map(|c| values[i] - *c).product();
+                previous.map_or(chunk_product, |previous| previous[i] * chunk_product)
+            });
+            builder.produce_intermediate_mle(product);
+
+            // subpolynomial: product - previous * sum_k coefficient_k * values^k
+            let mut terms: Vec<SumcheckSubpolynomialTerm<'a, C::Scalar>> =
+                vec![(C::Scalar::ONE, vec![Box::new(product)])];
+            for (power, coefficient) in vanishing_polynomial(chunk).into_iter().enumerate() {
+                let mut factors: Vec<Box<dyn MultilinearExtension<C::Scalar> + 'a>> =
+                    (0..power).map(|_| Box::new(values) as Box<_>).collect();
+                match previous {
+                    Some(previous) => factors.push(Box::new(previous)),
+                    None if power == 0 => factors.push(Box::new(ones)),
+                    None => {}
+                }
+                terms.push((-coefficient, factors));
+            }
+            builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, terms);
+            previous = Some(product);
+        }
+        let product = previous.expect("IN lists are not empty");
+
+        Column::Boolean(prover_evaluate_equals_zero(builder, alloc, product))
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let expr_eval = self.expr.verifier_evaluate(builder, accessor)?;
+        let values_eval =
+            scale_scalar(expr_eval, self.expr_upscale()).expect("Failed to scale expression");
+        let one_eval = builder.mle_evaluations.one_evaluation;
+
+        let mut previous_eval: Option<C::Scalar> = None;
+        for chunk in self.scaled_list().chunks(IN_LIST_CHUNK_LENGTH) {
+            let product_eval = builder.consume_intermediate_mle();
+
+            // subpolynomial: product - previous * sum_k coefficient_k * values^k
+            let polynomial_eval = vanishing_polynomial(chunk)
+                .into_iter()
+                .enumerate()
+                .map(|(power, coefficient)| {
+                    let eval = (0..power).fold(coefficient, |acc, _| acc * values_eval);
+                    match previous_eval {
+                        Some(previous_eval) => eval * previous_eval,
+                        None if power == 0 => eval * one_eval,
+                        None => eval,
+                    }
+                })
+                .sum::<C::Scalar>();
+            let eval = builder.mle_evaluations.random_evaluation * (product_eval - polynomial_eval);
+            builder.produce_sumcheck_subpolynomial_evaluation(&eval);
+            previous_eval = Some(product_eval);
+        }
+        let product_eval = previous_eval.expect("IN lists are not empty");
+
+        Ok(verifier_evaluate_equals_zero(builder, product_eval))
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
+
+/// Returns the coefficients of `(x - roots[0]) * ... * (x - roots[n - 1])`, from the constant one up.
+fn vanishing_polynomial<S: Scalar>(roots: &[S]) -> Vec<S> {
+    let mut coefficients = vec![S::ONE];
+    for root in roots {
+        // Multiply by `x - root`: shift the coefficients up and subtract `root` times them.
+        let mut next = vec![S::ZERO; coefficients.len() + 1];
+        for (power, coefficient) in coefficients.iter().enumerate() {
+            next[power + 1] += *coefficient;
+            next[power] -= *root * *coefficient;
+        }
+        coefficients = next;
+    }
+    coefficients
+}
@@ -0,0 +1,314 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType,
+            LiteralValue, OwnedTableTestAccessor, RandomTestAccessorDescriptor,
+            RecordBatchTestAccessor, TestAccessor,
+        },
+        math::decimal::Precision,
+        scalar::Curve25519Scalar,
+    },
+    record_batch,
+    sql::{
+        ast::{
+            test_expr::TestExprNode,
+            test_utility::{cols_expr_plan, column, dense_filter, in_list, not, tab},
+            ProvableExpr, ProvableExprPlan, MAX_IN_LIST_LENGTH,
+        },
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
+};
+use arrow::record_batch::RecordBatch;
+use bumpalo::Bump;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use polars::prelude::*;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+fn create_test_in_list_expr(
+    table_ref: &str,
+    results: &[&str],
+    filter_col: &str,
+    filter_vals: Vec<LiteralValue<Curve25519Scalar>>,
+    df_filter: Expr,
+    data: RecordBatch,
+    offset: usize,
+) -> TestExprNode {
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    let t = table_ref.parse().unwrap();
+    accessor.add_table(t, data, offset);
+    let in_list_expr = in_list(column(t, filter_col, &accessor), filter_vals);
+    TestExprNode::new(t, results, in_list_expr, df_filter, accessor)
+}
+
+fn bigints(values: &[i64]) -> Vec<LiteralValue<Curve25519Scalar>> {
+    values.iter().copied().map(LiteralValue::BigInt).collect()
+}
+
+fn varchars(values: &[&str]) -> Vec<LiteralValue<Curve25519Scalar>> {
+    values
+        .iter()
+        .map(|value| LiteralValue::VarChar((value.to_string(), (*value).into())))
+        .collect()
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_no_rows() {
+    let data = record_batch!(
+        "a" => Vec::<i64>::new(),
+        "b" => Vec::<i64>::new(),
+    );
+    let test_expr = create_test_in_list_expr(
+        "sxt.t",
+        &["a"],
+        "b",
+        bigints(&[1, 2]),
+        col("b").eq(lit(1_i64)).or(col("b").eq(lit(2_i64))),
+        data,
+        0,
+    );
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => Vec::<i64>::new());
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_on_a_bigint_column() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3, 4, 5],
+        "b" => [10_i64, -20, 30, 20, 0],
+    );
+    let test_expr = create_test_in_list_expr(
+        "sxt.t",
+        &["a"],
+        "b",
+        bigints(&[20, 0, -20]),
+        col("b")
+            .eq(lit(20_i64))
+            .or(col("b").eq(lit(0_i64)))
+            .or(col("b").eq(lit(-20_i64))),
+        data,
+        0,
+    );
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => [2_i64, 4, 5]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_more_literals_than_fit_in_one_constraint() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3, 4, 5],
+        "b" => [10_i64, 15, 190, 200, -10],
+    );
+    let list: Vec<i64> = (0..20).map(|i| i * 10).collect();
+    let test_expr = create_test_in_list_expr(
+        "sxt.t",
+        &["a"],
+        "b",
+        bigints(&list),
+        list.iter()
+            .map(|value| col("b").eq(lit(*value)))
+            .reduce(|acc, expr| acc.or(expr))
+            .unwrap(),
+        data,
+        0,
+    );
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => [1_i64, 3]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_a_single_literal_and_a_non_zero_offset() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3],
+        "b" => [7_i64, 8, 7],
+    );
+    let test_expr = create_test_in_list_expr(
+        "sxt.t",
+        &["a"],
+        "b",
+        bigints(&[7]),
+        col("b").eq(lit(7_i64)),
+        data,
+        43,
+    );
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => [1_i64, 3]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_on_a_varchar_column() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3, 4],
+        "s" => ["open", "closed", "pending", "open"],
+    );
+    let test_expr = create_test_in_list_expr(
+        "sxt.t",
+        &["a", "s"],
+        "s",
+        varchars(&["open", "pending", "archived"]),
+        col("s")
+            .eq(lit("open"))
+            .or(col("s").eq(lit("pending")))
+            .or(col("s").eq(lit("archived"))),
+        data,
+        0,
+    );
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!(
+        "a" => [1_i64, 3, 4],
+        "s" => ["open", "pending", "open"],
+    );
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_literals_of_other_scales() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 4]),
+        decimal75("d", 10, 2, [150, 200, 275, 0]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // d IN (1.5, 2, 2.7)
+    let expr = dense_filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        in_list(
+            column(t, "d", &accessor),
+            vec![
+                LiteralValue::Decimal75(Precision::new(2).unwrap(), 1, 15.into()),
+                LiteralValue::BigInt(2),
+                LiteralValue::Decimal75(Precision::new(2).unwrap(), 1, 27.into()),
+            ],
+        ),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("a", [1, 2])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_not_in_list_query() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 3, 4],
+        "b" => [1_i64, 2, 3, 4],
+    );
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let not_in_list_expr = not(in_list(column(t, "b", &accessor), bigints(&[2, 4])));
+    let df_filter = col("b").eq(lit(2_i64)).or(col("b").eq(lit(4_i64))).not();
+    let test_expr = TestExprNode::new(t, &["a"], not_in_list_expr, df_filter, accessor);
+    let res = test_expr.verify_expr();
+    let expected_res = record_batch!("a" => [1_i64, 3]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_create_an_in_list_expr_with_mismatched_no_or_too_many_literals() {
+    let data = owned_table([bigint("b", [1, 2]), varchar("s", ["x", "y"])]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_in_list(
+            column(t, "b", &accessor),
+            vec![LiteralValue::BigInt(1), LiteralValue::Boolean(true)],
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_in_list(
+            column(t, "s", &accessor),
+            bigints(&[1]),
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_in_list(column(t, "b", &accessor), vec![]),
+        Err(ConversionError::Unprovable(_))
+    ));
+    let list: Vec<i64> = (0..=MAX_IN_LIST_LENGTH as i64).collect();
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_in_list(
+            column(t, "b", &accessor),
+            bigints(&list)
+        ),
+        Err(ConversionError::Unprovable(_))
+    ));
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let descr = RandomTestAccessorDescriptor {
+        min_rows: 1,
+        max_rows: 20,
+        min_value: -3,
+        max_value: 3,
+    };
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let cols = [
+        ("a", ColumnType::BigInt),
+        ("b", ColumnType::VarChar),
+        ("c", ColumnType::BigInt),
+    ];
+    for _ in 0..20 {
+        let data = make_random_test_accessor_data(&mut rng, &cols, &descr);
+        let list_length = Uniform::new(1, 5).sample(&mut rng);
+        let filter_vals: Vec<i64> = (0..list_length)
+            .map(|_| Uniform::new(descr.min_value, descr.max_value + 1).sample(&mut rng))
+            .collect();
+        let df_filter = filter_vals
+            .iter()
+            .map(|val| col("c").eq(lit(*val)))
+            .reduce(|acc, expr| acc.or(expr))
+            .unwrap();
+        let test_expr = create_test_in_list_expr(
+            "sxt.t",
+            &["a", "b", "c"],
+            "c",
+            bigints(&filter_vals),
+            df_filter,
+            data,
+            offset,
+        );
+        let res = test_expr.verify_expr();
+        let expected_res = test_expr.query_table();
+        assert_eq!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_non_zero_offset() {
+    test_random_tables_with_given_offset(123);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_an_in_list_expr_using_result_evaluate() {
+    let data = owned_table([bigint("a", [123, 456, 789]), bigint("b", [0, 1, 2])]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let in_list_expr: ProvableExprPlan<RistrettoPoint> =
+        in_list(column(t, "b", &accessor), bigints(&[2, 0]));
+    let alloc = Bump::new();
+    let res = in_list_expr.result_evaluate(3, &alloc, &accessor);
+    let expected_res = Column::Boolean(&[true, false, true]);
+    assert_eq!(res, expected_res);
+}
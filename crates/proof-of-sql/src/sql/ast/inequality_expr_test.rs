@@ -491,7 +491,14 @@ fn the_sign_can_be_0_or_1_for_a_constant_column_of_zeros() {
     let table_length = expr.get_length(&accessor);
     let generator_offset = expr.get_offset(&accessor);
 
-    let mut transcript = make_transcript(&expr, &provable_result, table_length, generator_offset);
+    let mut transcript = make_transcript::<RistrettoPoint>(
+        &expr,
+        &provable_result,
+        table_length,
+        generator_offset,
+        &[],
+        &[],
+    );
     transcript.challenge_scalars::<Curve25519Scalar>(&mut [], MessageLabel::PostResultChallenges);
 
     let mut builder = ProofBuilder::new(3, 2, Vec::new());
@@ -513,7 +520,14 @@ fn the_sign_can_be_0_or_1_for_a_constant_column_of_zeros() {
     let selection = [true; 3];
     result_cols[0].prover_evaluate(&mut builder, &alloc, &accessor, &selection);
 
-    let proof = QueryProof::<InnerProductProof>::new_from_builder(builder, 0, transcript, &());
+    let proof = QueryProof::<InnerProductProof>::new_from_builder(
+        builder,
+        0,
+        Vec::new(),
+        Vec::new(),
+        transcript,
+        &(),
+    );
     let res = proof
         .verify(&expr, &accessor, &provable_result, &())
         .unwrap()
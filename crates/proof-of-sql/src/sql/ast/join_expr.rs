@@ -0,0 +1,638 @@
+use super::{
+    filter_column_by_index, fold_columns, fold_vals,
+    join_util::{
+        group_rows_by_key, join_indexes, key_count_columns, matching_row_counts, shifted_scalars,
+        JoinGroups,
+    },
+    ColumnExpr, TableExpr,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            OwnedTable,
+        },
+        polynomial::{compute_truncated_lagrange_basis_sum, MultilinearExtension},
+        proof::ProofError,
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::proof::{
+        CountBuilder, FirstRoundBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate,
+        ResultBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use core::iter::{once, repeat_with};
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <left_key>, <left_column1>, ..., <right_column1>, ...
+///     FROM <left_table> JOIN <right_table> ON <left_key> = <right_key>
+/// ```
+///
+/// The result is grouped by key, in increasing order of the key's scalar value, and each group is the
+/// cartesian product of the rows of both tables with that key.
+///
+/// The two tables may live at different generator offsets. The proof is done over a domain that starts at
+/// the smaller of the two offsets, where each table is shifted by the difference between its offset and that one.
+///
+/// Along with the result, the prover sends the distinct keys of both tables and how many rows of each table
+/// have each key. It also commits, before any challenges are drawn, to the number of matching rows in the
+/// other table for every row of each table. The proof then establishes that
+/// 1. the key counts are correct, by a lookup of each row's key and matching row count into the public keys,
+/// 2. the left (resp. right) part of the result is the left (resp. right) table, with each row repeated
+///    as many times as it has matching rows, by a multiset check, and
+/// 3. each key's group is the cartesian product of its left and right rows, which is checked in the clear.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct JoinExpr<C: Commitment> {
+    pub(super) left_table: TableExpr,
+    pub(super) right_table: TableExpr,
+    pub(super) left_key: ColumnExpr<C>,
+    pub(super) right_key: ColumnExpr<C>,
+    pub(super) left_columns: Vec<ColumnExpr<C>>,
+    pub(super) right_columns: Vec<ColumnExpr<C>>,
+}
+
+impl<C: Commitment> JoinExpr<C> {
+    /// Creates a new join expression.
+    pub fn new(
+        left_table: TableExpr,
+        right_table: TableExpr,
+        left_key: ColumnExpr<C>,
+        right_key: ColumnExpr<C>,
+        left_columns: Vec<ColumnExpr<C>>,
+        right_columns: Vec<ColumnExpr<C>>,
+    ) -> Self {
+        Self {
+            left_table,
+            right_table,
+            left_key,
+            right_key,
+            left_columns,
+            right_columns,
+        }
+    }
+
+    /// The shift of each table within the domain of the proof.
+    fn get_shifts(&self, accessor: &(impl MetadataAccessor + ?Sized)) -> (usize, usize) {
+        let left_offset = accessor.get_offset(self.left_table.table_ref);
+        let right_offset = accessor.get_offset(self.right_table.table_ref);
+        let offset = core::cmp::min(left_offset, right_offset);
+        (left_offset - offset, right_offset - offset)
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for JoinExpr<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        let num_columns = self.left_columns.len() + self.right_columns.len();
+        builder.count_anchored_mles(2 + num_columns);
+        builder.count_result_columns(1 + num_columns);
+        builder.count_first_round_mles(2);
+        builder.count_public_columns(3);
+        builder.count_intermediate_mles(4);
+        builder.count_subpolynomials(8);
+        builder.count_degree(3);
+        builder.count_post_result_challenges(4);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        let (left_shift, right_shift) = self.get_shifts(accessor);
+        core::cmp::max(
+            left_shift + accessor.get_length(self.left_table.table_ref),
+            right_shift + accessor.get_length(self.right_table.table_ref),
+        )
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        core::cmp::min(
+            accessor.get_offset(self.left_table.table_ref),
+            accessor.get_offset(self.right_table.table_ref),
+        )
+    }
+
+    fn get_proof_length(&self, accessor: &dyn MetadataAccessor, result_length: usize) -> usize {
+        core::cmp::max(self.get_length(accessor), result_length)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<(), ProofError> {
+        // 1. columns
+        let left_evals = Vec::from_iter(once(&self.left_key).chain(self.left_columns.iter()).map(
+            |column| {
+                builder.consume_anchored_mle(accessor.get_commitment(column.get_column_reference()))
+            },
+        ));
+        let right_evals = Vec::from_iter(
+            once(&self.right_key)
+                .chain(self.right_columns.iter())
+                .map(|column| {
+                    builder.consume_anchored_mle(
+                        accessor.get_commitment(column.get_column_reference()),
+                    )
+                }),
+        );
+        // 2. matching row counts
+        let left_matches_eval = builder.consume_first_round_mle();
+        let right_matches_eval = builder.consume_first_round_mle();
+        // 3. keys and key counts
+        let keys = builder.consume_public_column();
+        let left_counts = builder.consume_public_column();
+        let right_counts = builder.consume_public_column();
+        // 4. result
+        //
+        // The result is checked in the clear, so the evaluations of its MLEs are not needed.
+        let num_result_columns = 1 + self.left_columns.len() + self.right_columns.len();
+        repeat_with(|| builder.consume_result_mle())
+            .take(num_result_columns)
+            .for_each(drop);
+        let result = builder.result_table().ok_or(ProofError::VerificationError(
+            "join result could not be decoded",
+        ))?;
+        let result_indexes_eval = compute_truncated_lagrange_basis_sum(
+            result.num_rows(),
+            builder.mle_evaluations.evaluation_point,
+        );
+        if builder.mle_evaluations.result_indexes_evaluation != Some(result_indexes_eval) {
+            return Err(ProofError::VerificationError("join result must be dense"));
+        }
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        let gamma = builder.consume_post_result_challenge();
+        let delta = builder.consume_post_result_challenge();
+
+        let (left_sum, right_sum) = verify_join_result(
+            result,
+            self.left_columns.len(),
+            keys,
+            left_counts,
+            right_counts,
+            alpha,
+            beta,
+            gamma,
+        )?;
+        let left_key_count_sum = key_count_sum(keys, left_counts, right_counts, beta, delta)?;
+        let right_key_count_sum = key_count_sum(keys, right_counts, left_counts, beta, delta)?;
+
+        let (left_shift, right_shift) = self.get_shifts(accessor);
+        verify_join_side(
+            builder,
+            alpha,
+            beta,
+            delta,
+            &left_evals,
+            left_matches_eval,
+            left_shift,
+            accessor.get_length(self.left_table.table_ref),
+            left_sum,
+            left_key_count_sum,
+        );
+        verify_join_side(
+            builder,
+            gamma,
+            beta,
+            delta,
+            &right_evals,
+            right_matches_eval,
+            right_shift,
+            accessor.get_length(self.right_table.table_ref),
+            right_sum,
+            right_key_count_sum,
+        );
+        Ok(())
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        once(&self.left_key)
+            .chain(self.left_columns.iter())
+            .chain(self.right_columns.iter())
+            .map(|column| column.get_column_field())
+            .collect()
+    }
+
+    fn get_column_references(&self) -> HashSet<ColumnRef> {
+        once(&self.left_key)
+            .chain(once(&self.right_key))
+            .chain(self.left_columns.iter())
+            .chain(self.right_columns.iter())
+            .map(|column| column.get_column_reference())
+            .collect()
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for JoinExpr<C> {
+    #[tracing::instrument(name = "JoinExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        builder: &mut ResultBuilder<'a>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let left_key = accessor.get_column(self.left_key.get_column_reference());
+        let right_key = accessor.get_column(self.right_key.get_column_reference());
+        let groups = group_rows_by_key(
+            shifted_scalars(alloc, &left_key, 0, left_key.len()),
+            shifted_scalars(alloc, &right_key, 0, right_key.len()),
+        );
+        let (left_indexes, right_indexes) = join_indexes(&groups);
+
+        builder.set_result_indexes(Indexes::Dense(0..(left_indexes.len() as u64)));
+        for column in once(&self.left_key).chain(self.left_columns.iter()) {
+            let column = accessor.get_column(column.get_column_reference());
+            builder.produce_result_column(filter_column_by_index(alloc, &column, &left_indexes));
+        }
+        for column in self.right_columns.iter() {
+            let column = accessor.get_column(column.get_column_reference());
+            builder.produce_result_column(filter_column_by_index(alloc, &column, &right_indexes));
+        }
+        builder.request_post_result_challenges(4);
+    }
+
+    #[tracing::instrument(name = "JoinExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let (left_matches, right_matches, groups) = self.matching_row_counts(alloc, accessor);
+        builder.produce_first_round_mle(left_matches);
+        builder.produce_first_round_mle(right_matches);
+
+        let (keys, left_counts, right_counts) = key_count_columns(&groups);
+        builder.produce_public_column(keys);
+        builder.produce_public_column(left_counts);
+        builder.produce_public_column(right_counts);
+    }
+
+    #[tracing::instrument(name = "JoinExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let (left_shift, right_shift) = self.get_shifts(accessor);
+        // 1. columns
+        let left_columns = self.produce_shifted_columns(
+            builder,
+            alloc,
+            accessor,
+            once(&self.left_key).chain(self.left_columns.iter()),
+            left_shift,
+        );
+        let right_columns = self.produce_shifted_columns(
+            builder,
+            alloc,
+            accessor,
+            once(&self.right_key).chain(self.right_columns.iter()),
+            right_shift,
+        );
+        // 2. matching row counts
+        let (left_matches, right_matches, _) = self.matching_row_counts(alloc, accessor);
+        builder.produce_first_round_mle(left_matches);
+        builder.produce_first_round_mle(right_matches);
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        let gamma = builder.consume_post_result_challenge();
+        let delta = builder.consume_post_result_challenge();
+
+        prove_join_side(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            delta,
+            &left_columns,
+            left_matches,
+            left_shift,
+            accessor.get_length(self.left_table.table_ref),
+        );
+        prove_join_side(
+            builder,
+            alloc,
+            gamma,
+            beta,
+            delta,
+            &right_columns,
+            right_matches,
+            right_shift,
+            accessor.get_length(self.right_table.table_ref),
+        );
+    }
+}
+
+impl<C: Commitment> JoinExpr<C> {
+    /// Computes, for each row of each table, the number of rows of the other table with the same key,
+    /// lined up with the tables within the domain of the proof.
+    fn matching_row_counts<'a>(
+        &self,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> (&'a [i64], &'a [i64], JoinGroups<C::Scalar>) {
+        let (left_shift, right_shift) = self.get_shifts(accessor);
+        let left_key = accessor.get_column(self.left_key.get_column_reference());
+        let right_key = accessor.get_column(self.right_key.get_column_reference());
+        let groups = group_rows_by_key(
+            shifted_scalars(alloc, &left_key, 0, left_key.len()),
+            shifted_scalars(alloc, &right_key, 0, right_key.len()),
+        );
+        let left_matches = matching_row_counts(
+            alloc,
+            groups
+                .values()
+                .map(|(left_rows, right_rows)| (&left_rows[..], &right_rows[..])),
+            left_shift,
+            left_key.len(),
+        );
+        let right_matches = matching_row_counts(
+            alloc,
+            groups
+                .values()
+                .map(|(left_rows, right_rows)| (&right_rows[..], &left_rows[..])),
+            right_shift,
+            right_key.len(),
+        );
+        (left_matches, right_matches, groups)
+    }
+
+    /// Produces the given columns as anchored MLEs, lined up with their table within the domain of the proof.
+    fn produce_shifted_columns<'a, 'b>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+        columns: impl Iterator<Item = &'b ColumnExpr<C>>,
+        shift: usize,
+    ) -> Vec<&'a [C::Scalar]>
+    where
+        C: 'b,
+    {
+        columns
+            .map(|column| {
+                let column: Column<'a, C::Scalar> =
+                    accessor.get_column(column.get_column_reference());
+                let column = shifted_scalars(alloc, &column, shift, column.len());
+                builder.produce_anchored_mle(column);
+                column
+            })
+            .collect()
+    }
+}
+
+/// Checks the result of the join in the clear against the public keys and key counts.
+///
+/// Returns the sums of `1 / (alpha + fold(left part of row))` and `1 / (gamma + fold(right part of row))`
+/// over the rows of the result, where the left (resp. right) part of a row is the key followed by the
+/// left (resp. right) columns.
+#[allow(clippy::too_many_arguments)]
+fn verify_join_result<S: Scalar>(
+    result: &OwnedTable<S>,
+    num_left_columns: usize,
+    keys: &[S],
+    left_counts: &[S],
+    right_counts: &[S],
+    alpha: S,
+    beta: S,
+    gamma: S,
+) -> Result<(S, S), ProofError> {
+    if keys.len() != left_counts.len() || keys.len() != right_counts.len() {
+        return Err(ProofError::VerificationError(
+            "join keys and key counts have different lengths",
+        ));
+    }
+    // The keys must be sorted and distinct, so that each key has exactly one count.
+    if keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(ProofError::VerificationError(
+            "join keys are not strictly increasing",
+        ));
+    }
+
+    let columns = Vec::from_iter(result.inner_table().values());
+    let mut group_sums = vec![(S::zero(), S::zero(), S::zero()); keys.len()];
+    let (mut left_sum, mut right_sum, mut product_sum) = (S::zero(), S::zero(), S::zero());
+    for row in 0..result.num_rows() {
+        let row = Vec::from_iter(
+            columns
+                .iter()
+                .map(|column| column.scalar_at(row).expect("row is in bounds")),
+        );
+        let key_index = keys
+            .binary_search(&row[0])
+            .map_err(|_| ProofError::VerificationError("join result has an unknown key"))?;
+        let left_inv = (alpha + fold_vals(beta, &row[..=num_left_columns]))
+            .inv()
+            .ok_or(ProofError::VerificationError(
+                "join challenge is degenerate",
+            ))?;
+        let right_row =
+            Vec::from_iter(once(row[0]).chain(row[1 + num_left_columns..].iter().copied()));
+        let right_inv =
+            (gamma + fold_vals(beta, &right_row))
+                .inv()
+                .ok_or(ProofError::VerificationError(
+                    "join challenge is degenerate",
+                ))?;
+        let group_sum = &mut group_sums[key_index];
+        group_sum.0 += S::one();
+        group_sum.1 += left_inv;
+        group_sum.2 += right_inv;
+        left_sum += left_inv;
+        right_sum += right_inv;
+        product_sum += left_inv * right_inv;
+    }
+
+    // Each group of `left_count * right_count` rows must be the cartesian product of its left and right parts.
+    // Because the left part of the group repeats each left row `right_count` times and the right part
+    // repeats each right row `left_count` times, this holds exactly when
+    // `sum(left_inv * right_inv) = (sum(left_inv) / right_count) * (sum(right_inv) / left_count)`.
+    let mut expected_product_sum = S::zero();
+    for ((num_rows, group_left_sum, group_right_sum), (&left_count, &right_count)) in group_sums
+        .into_iter()
+        .zip(left_counts.iter().zip(right_counts.iter()))
+    {
+        if num_rows != left_count * right_count {
+            return Err(ProofError::VerificationError(
+                "join result has the wrong number of rows for a key",
+            ));
+        }
+        if let Some(inv_num_rows) = num_rows.inv() {
+            expected_product_sum += group_left_sum * group_right_sum * inv_num_rows;
+        }
+    }
+    if product_sum != expected_product_sum {
+        return Err(ProofError::VerificationError(
+            "join result is not a cartesian product",
+        ));
+    }
+    Ok((left_sum, right_sum))
+}
+
+/// Computes `sum(counts[i] / (delta + fold(keys[i], matching_counts[i])))`,
+/// i.e. what the rows of a table sum to in the lookup of each row's key and matching row count.
+fn key_count_sum<S: Scalar>(
+    keys: &[S],
+    counts: &[S],
+    matching_counts: &[S],
+    beta: S,
+    delta: S,
+) -> Result<S, ProofError> {
+    keys.iter()
+        .zip(counts.iter().zip(matching_counts.iter()))
+        .map(|(&key, (&count, &matching_count))| {
+            (delta + fold_vals(beta, &[key, matching_count]))
+                .inv()
+                .map(|inv| count * inv)
+                .ok_or(ProofError::VerificationError(
+                    "join challenge is degenerate",
+                ))
+        })
+        .sum()
+}
+
+/// Verifies the constraints on one table of the join. See [`prove_join_side`].
+#[allow(clippy::too_many_arguments)]
+fn verify_join_side<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    alpha: C::Scalar,
+    beta: C::Scalar,
+    delta: C::Scalar,
+    c_evals: &[C::Scalar],
+    m_eval: C::Scalar,
+    shift: usize,
+    length: usize,
+    sum: C::Scalar,
+    key_count_sum: C::Scalar,
+) {
+    let one_eval = builder.mle_evaluations.one_evaluation;
+    let rand_eval = builder.mle_evaluations.random_evaluation;
+    let evaluation_point = builder.mle_evaluations.evaluation_point;
+    let chi_eval = compute_truncated_lagrange_basis_sum(shift + length, evaluation_point)
+        - compute_truncated_lagrange_basis_sum(shift, evaluation_point);
+    let e0_eval = compute_truncated_lagrange_basis_sum(1, evaluation_point);
+
+    let c_fold_eval = alpha * one_eval + fold_vals(beta, c_evals);
+    let k_fold_eval = delta * one_eval + fold_vals(beta, &[c_evals[0], m_eval]);
+    let c_star_eval = builder.consume_intermediate_mle();
+    let k_star_eval = builder.consume_intermediate_mle();
+
+    // c_fold * c_star - chi = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval * (c_fold_eval * c_star_eval - chi_eval)),
+    );
+
+    // k_fold * k_star - chi = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval * (k_fold_eval * k_star_eval - chi_eval)),
+    );
+
+    // sum m * c_star - sum * e0 = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(&(m_eval * c_star_eval - sum * e0_eval));
+
+    // sum k_star - key_count_sum * e0 = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(&(k_star_eval - key_count_sum * e0_eval));
+}
+
+/// Proves the constraints on one table of the join, where
+/// * `c` are the key and the selected columns of the table,
+/// * `m` is the number of matching rows in the other table for each row,
+/// * `chi` is the indicator of the rows of the table within the domain of the proof, and
+/// * `e0` is the indicator of the first row of the domain.
+///
+/// The constraints are
+/// * `c_star = chi / (alpha + fold(c))`,
+/// * `k_star = chi / (delta + fold(key, m))`,
+/// * `sum(m * c_star)` is the sum of `1 / (alpha + fold(row))` over this table's part of the result, and
+/// * `sum(k_star)` is the sum of `count / (delta + fold(key, matching_count))` over the public keys.
+#[allow(clippy::too_many_arguments)]
+fn prove_join_side<'a, S: Scalar + 'a>(
+    builder: &mut ProofBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    delta: S,
+    c: &[&'a [S]],
+    m: &'a [i64],
+    shift: usize,
+    length: usize,
+) {
+    let n = builder.table_length();
+    let chi = alloc.alloc_slice_fill_copy(n, false);
+    chi[shift..shift + length].fill(true);
+    let e0 = alloc.alloc_slice_fill_copy(1, true);
+
+    let c_fold = alloc.alloc_slice_fill_copy(n, alpha);
+    fold_columns(c_fold, One::one(), beta, c);
+    let k_fold = alloc.alloc_slice_fill_copy(n, delta);
+    fold_columns(k_fold, One::one(), beta, &[c[0]]);
+    m.mul_add(k_fold, &beta);
+
+    let c_star = alloc.alloc_slice_copy(c_fold);
+    let k_star = alloc.alloc_slice_copy(k_fold);
+    for star in [&mut *c_star, &mut *k_star] {
+        star[..shift].fill(Zero::zero());
+        star[shift + length..].fill(Zero::zero());
+        slice_ops::batch_inversion(&mut star[shift..shift + length]);
+    }
+    let sum = (m as &[_]).inner_product(c_star);
+    let key_count_sum = k_star.iter().copied().sum::<S>();
+
+    builder.produce_intermediate_mle(c_star as &[_]);
+    builder.produce_intermediate_mle(k_star as &[_]);
+
+    // c_fold * c_star - chi = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (
+                S::one(),
+                vec![Box::new(c_star as &[_]), Box::new(c_fold as &[_])],
+            ),
+            (-S::one(), vec![Box::new(chi as &[_])]),
+        ],
+    );
+
+    // k_fold * k_star - chi = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (
+                S::one(),
+                vec![Box::new(k_star as &[_]), Box::new(k_fold as &[_])],
+            ),
+            (-S::one(), vec![Box::new(chi as &[_])]),
+        ],
+    );
+
+    // sum m * c_star - sum * e0 = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), vec![Box::new(m), Box::new(c_star as &[_])]),
+            (-sum, vec![Box::new(e0 as &[_])]),
+        ],
+    );
+
+    // sum k_star - key_count_sum * e0 = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), vec![Box::new(k_star as &[_])]),
+            (-key_count_sum, vec![Box::new(e0 as &[_])]),
+        ],
+    );
+}
@@ -0,0 +1,263 @@
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnRef, ColumnType, OwnedColumn, OwnedTable,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::test_utility::{col_expr, cols_expr, join, tab, two_table_accessor},
+        proof::{
+            exercise_verification, Indexes, ProofExpr, ProvableQueryResult, ProvableResultColumn,
+            VerifiableQueryResult,
+        },
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::Identifier;
+use std::collections::HashSet;
+
+fn facts() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("k", [3, 1, 3, 5, 1, 2]),
+        bigint("amount", [10, 20, 30, 40, 50, 60]),
+        varchar("note", ["a", "b", "c", "d", "e", "f"]),
+    ])
+}
+
+fn dims() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("dk", [1, 4, 3, 1]),
+        int128("weight", [100, 200, 300, 400]),
+    ])
+}
+
+#[test]
+fn we_can_correctly_fetch_the_result_fields_and_column_references() {
+    let (accessor, l, r) = two_table_accessor(facts(), 0, dims(), 0);
+    let expr = join::<RistrettoPoint>(
+        tab(l),
+        tab(r),
+        col_expr(l, "k", &accessor),
+        col_expr(r, "dk", &accessor),
+        cols_expr(l, &["amount", "note"], &accessor),
+        cols_expr(r, &["weight"], &accessor),
+    );
+    assert_eq!(
+        expr.get_column_result_fields(),
+        vec![
+            ColumnField::new("k".parse().unwrap(), ColumnType::BigInt),
+            ColumnField::new("amount".parse().unwrap(), ColumnType::BigInt),
+            ColumnField::new("note".parse().unwrap(), ColumnType::VarChar),
+            ColumnField::new("weight".parse().unwrap(), ColumnType::Int128),
+        ]
+    );
+    assert_eq!(
+        expr.get_column_references(),
+        HashSet::from([
+            ColumnRef::new(l, "k".parse().unwrap(), ColumnType::BigInt),
+            ColumnRef::new(l, "amount".parse().unwrap(), ColumnType::BigInt),
+            ColumnRef::new(l, "note".parse().unwrap(), ColumnType::VarChar),
+            ColumnRef::new(r, "dk".parse().unwrap(), ColumnType::BigInt),
+            ColumnRef::new(r, "weight".parse().unwrap(), ColumnType::Int128),
+        ])
+    );
+}
+
+#[test]
+fn we_can_prove_a_join() {
+    let (accessor, l, r) = two_table_accessor(facts(), 0, dims(), 0);
+    let expr = join(
+        tab(l),
+        tab(r),
+        col_expr(l, "k", &accessor),
+        col_expr(r, "dk", &accessor),
+        cols_expr(l, &["amount", "note"], &accessor),
+        cols_expr(r, &["weight"], &accessor),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, l);
+    exercise_verification(&res, &expr, &accessor, r);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("k", [1, 1, 1, 1, 3, 3]),
+        bigint("amount", [20, 20, 50, 50, 10, 30]),
+        varchar("note", ["b", "b", "e", "e", "a", "c"]),
+        int128("weight", [100, 400, 100, 400, 300, 300]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_join_of_tables_with_different_offsets() {
+    for (left_offset, right_offset) in [(0, 3), (5, 0), (2, 2), (7, 9)] {
+        let (accessor, l, r) = two_table_accessor(facts(), left_offset, dims(), right_offset);
+        let expr = join(
+            tab(r),
+            tab(l),
+            col_expr(r, "dk", &accessor),
+            col_expr(l, "k", &accessor),
+            cols_expr(r, &["weight"], &accessor),
+            cols_expr(l, &["note"], &accessor),
+        );
+        let res = VerifiableQueryResult::new(&expr, &accessor, &());
+        exercise_verification(&res, &expr, &accessor, l);
+        exercise_verification(&res, &expr, &accessor, r);
+        let res = res.verify(&expr, &accessor, &()).unwrap().table;
+        let expected = owned_table([
+            bigint("dk", [1, 1, 1, 1, 3, 3]),
+            int128("weight", [100, 100, 400, 400, 300, 300]),
+            varchar("note", ["b", "e", "b", "e", "a", "c"]),
+        ]);
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_prove_a_join_whose_result_is_longer_than_both_tables() {
+    let left_data = owned_table([bigint("a", [1, 1, 1, 2]), bigint("b", [1, 2, 3, 4])]);
+    let right_data = owned_table([bigint("c", [1, 1, 1]), bigint("d", [5, 6, 7])]);
+    let (accessor, l, r) = two_table_accessor(left_data, 1, right_data, 0);
+    let expr = join(
+        tab(l),
+        tab(r),
+        col_expr(l, "a", &accessor),
+        col_expr(r, "c", &accessor),
+        cols_expr(l, &["b"], &accessor),
+        cols_expr(r, &["d"], &accessor),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, l);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 1, 1, 1, 1, 1, 1, 1, 1]),
+        bigint("b", [1, 1, 1, 2, 2, 2, 3, 3, 3]),
+        bigint("d", [5, 6, 7, 5, 6, 7, 5, 6, 7]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_join_with_varchar_keys() {
+    let left_data = owned_table([
+        varchar("name", ["x", "y", "z", "y"]),
+        bigint("a", [1, 2, 3, 4]),
+    ]);
+    let right_data = owned_table([varchar("label", ["y", "w", "z"]), bigint("b", [5, 6, 7])]);
+    let (accessor, l, r) = two_table_accessor(left_data, 0, right_data, 0);
+    let expr = join(
+        tab(l),
+        tab(r),
+        col_expr(l, "name", &accessor),
+        col_expr(r, "label", &accessor),
+        cols_expr(l, &["a"], &accessor),
+        cols_expr(r, &["b"], &accessor),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, l);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let mut rows: Vec<(String, i64, i64)> = match (
+        &res.inner_table()[&"name".parse::<Identifier>().unwrap()],
+        &res.inner_table()[&"a".parse::<Identifier>().unwrap()],
+        &res.inner_table()[&"b".parse::<Identifier>().unwrap()],
+    ) {
+        (OwnedColumn::VarChar(names), OwnedColumn::BigInt(a), OwnedColumn::BigInt(b)) => names
+            .iter()
+            .zip(a.iter().zip(b.iter()))
+            .map(|(name, (a, b))| (name.clone(), *a, *b))
+            .collect(),
+        _ => panic!("unexpected result column types"),
+    };
+    // The result is grouped by the scalar value of the key, which is not the lexicographic order.
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            ("y".to_string(), 2, 5),
+            ("y".to_string(), 4, 5),
+            ("z".to_string(), 3, 7),
+        ]
+    );
+}
+
+#[test]
+fn we_can_prove_a_join_with_no_matching_rows() {
+    let left_data = owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 5, 6])]);
+    let right_data = owned_table([bigint("c", [7, 8]), bigint("d", [9, 10])]);
+    let (accessor, l, r) = two_table_accessor(left_data, 0, right_data, 4);
+    let expr = join(
+        tab(l),
+        tab(r),
+        col_expr(l, "a", &accessor),
+        col_expr(r, "c", &accessor),
+        cols_expr(l, &["b"], &accessor),
+        cols_expr(r, &["d"], &accessor),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, l);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [0; 0]),
+        bigint("b", [0; 0]),
+        bigint("d", [0; 0]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_join_with_an_empty_table() {
+    let left_data = owned_table([bigint("a", [0; 0]), bigint("b", [0; 0])]);
+    let right_data = owned_table([bigint("c", [7, 8]), bigint("d", [9, 10])]);
+    let (accessor, l, r) = two_table_accessor(left_data, 0, right_data, 0);
+    let expr = join(
+        tab(l),
+        tab(r),
+        col_expr(l, "a", &accessor),
+        col_expr(r, "c", &accessor),
+        cols_expr(l, &["b"], &accessor),
+        cols_expr(r, &["d"], &accessor),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, r);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [0; 0]),
+        bigint("b", [0; 0]),
+        bigint("d", [0; 0]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_verify_a_join_result_that_is_missing_or_duplicating_rows() {
+    let left_data = owned_table([bigint("a", [1, 2, 1]), bigint("b", [4, 5, 6])]);
+    let right_data = owned_table([bigint("c", [1, 1, 2]), bigint("d", [7, 8, 9])]);
+    let (accessor, l, r) = two_table_accessor(left_data, 0, right_data, 0);
+    let expr = join::<RistrettoPoint>(
+        tab(l),
+        tab(r),
+        col_expr(l, "a", &accessor),
+        col_expr(r, "c", &accessor),
+        cols_expr(l, &["b"], &accessor),
+        cols_expr(r, &["d"], &accessor),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    assert!(res.verify(&expr, &accessor, &()).is_ok());
+
+    let tampered_results: [(&[i64], &[i64], &[i64]); 3] = [
+        // missing a row
+        (&[1, 1, 1, 2], &[4, 4, 6, 5], &[7, 8, 7, 9]),
+        // a row repeated in place of another row with the same key
+        (&[1, 1, 1, 1, 2], &[4, 4, 6, 6, 5], &[7, 8, 7, 7, 9]),
+        // left and right rows with the same key that are not paired as a cartesian product
+        (&[1, 1, 1, 1, 2], &[4, 4, 6, 6, 5], &[7, 7, 8, 8, 9]),
+    ];
+    for (a, b, d) in tampered_results {
+        let mut res_p = res.clone();
+        let indexes = Indexes::Dense(0..(a.len() as u64));
+        let columns: [Box<dyn ProvableResultColumn>; 3] = [Box::new(a), Box::new(b), Box::new(d)];
+        res_p.provable_result = Some(ProvableQueryResult::new(&indexes, &columns));
+        assert!(res_p.verify(&expr, &accessor, &()).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! Contains the utility functions for the `JoinExpr` node.
+
+use crate::base::{polynomial::MultilinearExtension, scalar::Scalar};
+use bumpalo::Bump;
+use num_traits::{One, Zero};
+use std::collections::BTreeMap;
+
+/// The rows of the left and right tables that have a given key.
+pub type JoinGroups<S> = BTreeMap<S, (Vec<usize>, Vec<usize>)>;
+
+/// This function takes the keys of the left and right tables and groups the row indexes of both
+/// tables by key. Every key that appears in either table gets a group, and the groups are sorted by key.
+pub fn group_rows_by_key<S: Scalar>(left_keys: &[S], right_keys: &[S]) -> JoinGroups<S> {
+    let mut groups: JoinGroups<S> = BTreeMap::new();
+    for (i, key) in left_keys.iter().enumerate() {
+        groups.entry(*key).or_default().0.push(i);
+    }
+    for (i, key) in right_keys.iter().enumerate() {
+        groups.entry(*key).or_default().1.push(i);
+    }
+    groups
+}
+
+/// This function returns the indexes of the left and right rows that make up each row of the join.
+///
+/// The rows of the join are grouped by key, in the same order as `groups`, and each group
+/// is the cartesian product of the left and right rows with that key.
+pub fn join_indexes<S: Scalar>(groups: &JoinGroups<S>) -> (Vec<usize>, Vec<usize>) {
+    groups
+        .values()
+        .flat_map(|(left_rows, right_rows)| {
+            left_rows.iter().flat_map(move |&left_row| {
+                right_rows
+                    .iter()
+                    .map(move |&right_row| (left_row, right_row))
+            })
+        })
+        .unzip()
+}
+
+/// This function returns, for each row of a table, the number of rows of the other table that have the same key.
+///
+/// `groups` gives, for each key, the rows of the table and the matching rows of the other table.
+/// The counts are placed after `shift` zeros, so that the result lines up with the table within the domain of the proof.
+pub fn matching_row_counts<'a, 'b>(
+    alloc: &'a Bump,
+    groups: impl IntoIterator<Item = (&'b [usize], &'b [usize])>,
+    shift: usize,
+    length: usize,
+) -> &'a [i64] {
+    let counts = alloc.alloc_slice_fill_copy(shift + length, 0_i64);
+    for (rows, matching_rows) in groups {
+        for &row in rows {
+            counts[shift + row] = matching_rows.len() as i64;
+        }
+    }
+    counts
+}
+
+/// This function returns the public columns describing `groups`:
+/// the keys, the number of left rows for each key and the number of right rows for each key.
+pub fn key_count_columns<S: Scalar>(groups: &JoinGroups<S>) -> (Vec<S>, Vec<S>, Vec<S>) {
+    let keys = groups.keys().copied().collect();
+    let (left_counts, right_counts) = groups
+        .values()
+        .map(|(left_rows, right_rows)| {
+            (
+                S::from(left_rows.len() as i64),
+                S::from(right_rows.len() as i64),
+            )
+        })
+        .unzip();
+    (keys, left_counts, right_counts)
+}
+
+/// This function converts a column into scalars and places them after `shift` zeros,
+/// so that the column lines up with its table within the domain of the proof.
+pub fn shifted_scalars<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: &impl MultilinearExtension<S>,
+    shift: usize,
+    length: usize,
+) -> &'a [S] {
+    let res = alloc.alloc_slice_fill_copy(shift + length, Zero::zero());
+    column.mul_add(&mut res[shift..], &One::one());
+    res
+}
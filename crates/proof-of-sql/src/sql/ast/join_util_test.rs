@@ -0,0 +1,104 @@
+use super::join_util::{
+    group_rows_by_key, join_indexes, key_count_columns, matching_row_counts, shifted_scalars,
+};
+use crate::base::{database::Column, scalar::Curve25519Scalar};
+use bumpalo::Bump;
+
+#[test]
+fn we_can_group_rows_by_key() {
+    let left_keys: [Curve25519Scalar; 5] = [3.into(), 1.into(), 3.into(), 5.into(), 1.into()];
+    let right_keys: [Curve25519Scalar; 4] = [1.into(), 4.into(), 3.into(), 1.into()];
+    let groups = group_rows_by_key(&left_keys, &right_keys);
+    assert_eq!(
+        groups.into_iter().collect::<Vec<_>>(),
+        vec![
+            (1.into(), (vec![1, 4], vec![0, 3])),
+            (3.into(), (vec![0, 2], vec![2])),
+            (4.into(), (vec![], vec![1])),
+            (5.into(), (vec![3], vec![])),
+        ]
+    );
+}
+
+#[test]
+fn we_can_group_rows_by_key_with_empty_tables() {
+    let keys: [Curve25519Scalar; 2] = [2.into(), 2.into()];
+    assert!(group_rows_by_key::<Curve25519Scalar>(&[], &[]).is_empty());
+    assert_eq!(
+        group_rows_by_key(&keys, &[])
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![(2.into(), (vec![0, 1], vec![]))]
+    );
+}
+
+#[test]
+fn we_can_compute_join_indexes_as_a_cartesian_product_per_key() {
+    let left_keys: [Curve25519Scalar; 5] = [3.into(), 1.into(), 3.into(), 5.into(), 1.into()];
+    let right_keys: [Curve25519Scalar; 4] = [1.into(), 4.into(), 3.into(), 1.into()];
+    let groups = group_rows_by_key(&left_keys, &right_keys);
+    assert_eq!(
+        join_indexes(&groups),
+        (vec![1, 1, 4, 4, 0, 2], vec![0, 3, 0, 3, 2, 2])
+    );
+}
+
+#[test]
+fn we_can_compute_shifted_matching_row_counts() {
+    let left_keys: [Curve25519Scalar; 5] = [3.into(), 1.into(), 3.into(), 5.into(), 1.into()];
+    let right_keys: [Curve25519Scalar; 4] = [1.into(), 4.into(), 3.into(), 1.into()];
+    let groups = group_rows_by_key(&left_keys, &right_keys);
+    let alloc = Bump::new();
+    let left_counts = matching_row_counts(
+        &alloc,
+        groups
+            .values()
+            .map(|(left_rows, right_rows)| (&left_rows[..], &right_rows[..])),
+        0,
+        5,
+    );
+    assert_eq!(left_counts, [1, 2, 1, 0, 2]);
+    let right_counts = matching_row_counts(
+        &alloc,
+        groups
+            .values()
+            .map(|(left_rows, right_rows)| (&right_rows[..], &left_rows[..])),
+        2,
+        4,
+    );
+    assert_eq!(right_counts, [0, 0, 2, 0, 2, 2]);
+}
+
+#[test]
+fn we_can_compute_key_count_columns() {
+    let left_keys: [Curve25519Scalar; 5] = [3.into(), 1.into(), 3.into(), 5.into(), 1.into()];
+    let right_keys: [Curve25519Scalar; 4] = [1.into(), 4.into(), 3.into(), 1.into()];
+    let groups = group_rows_by_key(&left_keys, &right_keys);
+    let (keys, left_counts, right_counts) = key_count_columns(&groups);
+    assert_eq!(
+        keys,
+        Vec::<Curve25519Scalar>::from([1.into(), 3.into(), 4.into(), 5.into()])
+    );
+    assert_eq!(
+        left_counts,
+        Vec::<Curve25519Scalar>::from([2.into(), 2.into(), 0.into(), 1.into()])
+    );
+    assert_eq!(
+        right_counts,
+        Vec::<Curve25519Scalar>::from([2.into(), 1.into(), 1.into(), 0.into()])
+    );
+}
+
+#[test]
+fn we_can_shift_columns_into_scalars() {
+    let alloc = Bump::new();
+    let column: Column<Curve25519Scalar> = Column::BigInt(&[1, -2, 3]);
+    assert_eq!(
+        shifted_scalars(&alloc, &column, 2, column.len()),
+        [0.into(), 0.into(), 1.into(), (-2).into(), 3.into()]
+    );
+    assert_eq!(
+        shifted_scalars(&alloc, &column, 0, column.len()),
+        [1.into(), (-2).into(), 3.into()]
+    );
+}
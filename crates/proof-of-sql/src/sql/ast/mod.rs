@@ -19,6 +19,9 @@ mod bitwise_verification_test;
 mod provable_expr_plan;
 pub(crate) use provable_expr_plan::ProvableExprPlan;
 
+mod aliased_provable_expr_plan;
+pub(crate) use aliased_provable_expr_plan::AliasedProvableExprPlan;
+
 mod provable_expr;
 pub(crate) use provable_expr::ProvableExpr;
 #[cfg(all(test, feature = "blitzar"))]
@@ -52,11 +55,49 @@ mod not_expr_test;
 mod comparison_util;
 pub(crate) use comparison_util::{scale_and_subtract, scale_and_subtract_eval};
 
+mod numerical_util;
+pub(crate) use numerical_util::{
+    add_subtract_columns, add_subtract_evals, multiply_columns, scalars_to_numerical_column,
+    try_add_subtract_column_types, try_average_column_type, try_common_column_type,
+    try_multiply_column_types,
+};
+
 mod equals_expr;
 use equals_expr::*;
 #[cfg(all(test, feature = "blitzar"))]
 mod equals_expr_test;
 
+mod in_list_expr;
+use in_list_expr::{InListExpr, MAX_IN_LIST_LENGTH};
+#[cfg(all(test, feature = "blitzar"))]
+mod in_list_expr_test;
+
+mod between_expr;
+use between_expr::BetweenExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod between_expr_test;
+
+mod case_expr;
+use case_expr::CaseExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod case_expr_test;
+
+mod cast_expr;
+pub(crate) use cast_expr::check_cast_column_types;
+use cast_expr::{check_provable_cast_column_types, CastExpr};
+#[cfg(all(test, feature = "blitzar"))]
+mod cast_expr_test;
+
+mod add_subtract_expr;
+use add_subtract_expr::AddSubtractExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod add_subtract_expr_test;
+
+mod multiply_expr;
+use multiply_expr::MultiplyExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod multiply_expr_test;
+
 mod sign_expr;
 use sign_expr::*;
 #[cfg(all(test, feature = "blitzar"))]
@@ -103,5 +144,40 @@ use group_by_util::aggregate_columns;
 #[cfg(test)]
 mod group_by_util_test;
 
+mod top_k_expr;
+pub(crate) use top_k_expr::TopKExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod top_k_expr_test;
+
+mod running_sum_expr;
+pub(crate) use running_sum_expr::RunningSumExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod running_sum_expr_test;
+
+mod join_expr;
+pub(crate) use join_expr::JoinExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod join_expr_test;
+
+mod join_util;
+#[cfg(test)]
+mod join_util_test;
+
+mod union_expr;
+pub(crate) use union_expr::UnionExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod union_expr_test;
+
+mod union_util;
+#[cfg(test)]
+mod union_util_test;
+
+mod subquery_expr;
+pub(crate) use subquery_expr::{
+    SubqueryAggregation, SubqueryComparison, SubqueryCondition, SubqueryFilterExpr,
+};
+#[cfg(all(test, feature = "blitzar"))]
+mod subquery_expr_test;
+
 mod proof_plan;
 pub use proof_plan::ProofPlan;
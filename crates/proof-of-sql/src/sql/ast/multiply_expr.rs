@@ -0,0 +1,112 @@
+use super::{
+    multiply_columns, scalars_to_numerical_column, try_multiply_column_types, ProvableExpr,
+    ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        proof::ProofError,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use bumpalo::Bump;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Provable numerical * expression
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct MultiplyExpr<C: Commitment> {
+    lhs: Box<ProvableExprPlan<C>>,
+    rhs: Box<ProvableExprPlan<C>>,
+}
+
+impl<C: Commitment> MultiplyExpr<C> {
+    /// Create numerical * expression
+    pub fn new(lhs: Box<ProvableExprPlan<C>>, rhs: Box<ProvableExprPlan<C>>) -> Self {
+        Self { lhs, rhs }
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for MultiplyExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.lhs.count(builder)?;
+        self.rhs.count(builder)?;
+        builder.count_subpolynomials(1);
+        builder.count_intermediate_mles(1);
+        builder.count_degree(3);
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        try_multiply_column_types(self.lhs.data_type(), self.rhs.data_type())
+            .expect("Failed to multiply column types")
+    }
+
+    #[tracing::instrument(name = "MultiplyExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let lhs_column: Column<'a, C::Scalar> =
+            self.lhs.result_evaluate(table_length, alloc, accessor);
+        let rhs_column: Column<'a, C::Scalar> =
+            self.rhs.result_evaluate(table_length, alloc, accessor);
+        let res = multiply_columns(&lhs_column, &rhs_column, alloc);
+        scalars_to_numerical_column(ProvableExpr::<C>::data_type(self), res)
+    }
+
+    #[tracing::instrument(name = "MultiplyExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let lhs_column: Column<'a, C::Scalar> = self.lhs.prover_evaluate(builder, alloc, accessor);
+        let rhs_column: Column<'a, C::Scalar> = self.rhs.prover_evaluate(builder, alloc, accessor);
+
+        // lhs_times_rhs
+        let lhs_times_rhs: &'a [C::Scalar] = multiply_columns(&lhs_column, &rhs_column, alloc);
+        builder.produce_intermediate_mle(lhs_times_rhs);
+
+        // subpolynomial: lhs_times_rhs - lhs * rhs
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::one(), vec![Box::new(lhs_times_rhs)]),
+                (
+                    -C::Scalar::one(),
+                    vec![Box::new(lhs_column), Box::new(rhs_column)],
+                ),
+            ],
+        );
+        scalars_to_numerical_column(ProvableExpr::<C>::data_type(self), lhs_times_rhs)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let lhs = self.lhs.verifier_evaluate(builder, accessor)?;
+        let rhs = self.rhs.verifier_evaluate(builder, accessor)?;
+
+        // lhs_times_rhs
+        let lhs_times_rhs = builder.consume_intermediate_mle();
+
+        // subpolynomial: lhs_times_rhs - lhs * rhs
+        let eval = builder.mle_evaluations.random_evaluation * (lhs_times_rhs - lhs * rhs);
+        builder.produce_sumcheck_subpolynomial_evaluation(&eval);
+
+        Ok(lhs_times_rhs)
+    }
+
+    fn get_column_references(&self, columns: &mut HashSet<ColumnRef>) {
+        self.lhs.get_column_references(columns);
+        self.rhs.get_column_references(columns);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            make_random_test_accessor_data, owned_table_utility::*, Column, ColumnType, OwnedTable,
+            OwnedTableTestAccessor, RandomTestAccessorDescriptor, TestAccessor,
+        },
+        math::decimal::Precision,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::{test_utility::*, ProvableExpr, ProvableExprPlan},
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
+};
+use bumpalo::Bump;
+use curve25519_dalek::RistrettoPoint;
+use polars::prelude::*;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let descr = RandomTestAccessorDescriptor {
+        min_rows: 1,
+        max_rows: 20,
+        min_value: -3,
+        max_value: 3,
+    };
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let cols = [
+        ("a", ColumnType::BigInt),
+        ("b", ColumnType::VarChar),
+        ("c", ColumnType::BigInt),
+        ("d", ColumnType::BigInt),
+    ];
+    for _ in 0..20 {
+        let data = make_random_test_accessor_data(&mut rng, &cols, &descr);
+        let data = OwnedTable::<Curve25519Scalar>::try_from(data).unwrap();
+        let filter_val = Uniform::new(descr.min_value, descr.max_value + 1).sample(&mut rng);
+
+        let t = "sxt.t".parse().unwrap();
+        let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+        accessor.add_table(t, data.clone(), offset);
+        let ast = dense_filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            gte(
+                multiply(column(t, "c", &accessor), column(t, "d", &accessor)),
+                multiply(column(t, "a", &accessor), const_bigint(filter_val)),
+            ),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        let expected = data.apply_polars_filter(
+            &["a", "b"],
+            (col("c") * col("d")).gt_eq(col("a") * lit(filter_val)),
+        );
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(75);
+}
+
+#[test]
+fn we_can_prove_a_query_with_nested_arithmetic() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 4, 5]),
+        int128("b", [4, 3, 2, 1, 0]),
+        decimal75("c", 3, 1, [10, 14, 20, 5, 0]),
+        bigint("d", [9, 9, 12, 7, 5]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // (a + b) * c = d - a
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a", "b"], &accessor),
+        tab(t),
+        equal(
+            multiply(
+                add(column(t, "a", &accessor), column(t, "b", &accessor)),
+                column(t, "c", &accessor),
+            ),
+            subtract(column(t, "d", &accessor), column(t, "a", &accessor)),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("a", [2, 5]), int128("b", [3, 0])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_multiply_expr_using_result_evaluate() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 4]),
+        decimal75("b", 4, 2, [0, 125, -150, 1]),
+    ]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let multiply_expr: ProvableExprPlan<RistrettoPoint> =
+        multiply(column(t, "a", &accessor), column(t, "b", &accessor));
+    let alloc = Bump::new();
+    let res = multiply_expr.result_evaluate(4, &alloc, &accessor);
+    let expected_res_scalar = [0, 250, -450, 4]
+        .iter()
+        .map(|v| Curve25519Scalar::from(*v))
+        .collect::<Vec<_>>();
+    let expected_res = Column::Decimal75(Precision::new(23).unwrap(), 2, &expected_res_scalar);
+    assert_eq!(res, expected_res);
+}
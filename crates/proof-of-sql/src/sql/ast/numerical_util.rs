@@ -0,0 +1,411 @@
+use crate::{
+    base::{
+        database::{Column, ColumnType},
+        math::decimal::{scale_scalar, Precision, MAX_SUPPORTED_PRECISION},
+        scalar::Scalar,
+    },
+    sql::parse::{type_check_binary_operation, ConversionError, ConversionResult},
+};
+use bumpalo::Bump;
+use proof_of_sql_parser::intermediate_ast::BinaryOperator;
+
+/// Check that the operands of a numerical operation are numeric and return their
+/// precisions and scales, treating integers as decimals with a scale of 0.
+fn try_get_precisions_and_scales(
+    lhs: ColumnType,
+    rhs: ColumnType,
+    operator: BinaryOperator,
+) -> ConversionResult<((i16, i16), (i16, i16))> {
+    if !type_check_binary_operation(&lhs, &rhs, operator) {
+        return Err(ConversionError::DataTypeMismatch(
+            lhs.to_string(),
+            rhs.to_string(),
+        ));
+    }
+    let lhs_precision = lhs.precision_value().expect("Numeric types have precision") as i16;
+    let rhs_precision = rhs.precision_value().expect("Numeric types have precision") as i16;
+    let lhs_scale = lhs.scale().unwrap_or(0) as i16;
+    let rhs_scale = rhs.scale().unwrap_or(0) as i16;
+    Ok(((lhs_precision, lhs_scale), (rhs_precision, rhs_scale)))
+}
+
+/// Build a `Decimal75` type from a raw precision and scale.
+fn try_new_decimal_type(precision: i16, scale: i16) -> ConversionResult<ColumnType> {
+    let precision_value = u8::try_from(precision).unwrap_or(u8::MAX);
+    let precision = Precision::new(precision_value)
+        .map_err(|_| ConversionError::InvalidPrecision(precision_value))?;
+    let scale = i8::try_from(scale).map_err(|_| {
+        ConversionError::InvalidExpression(format!("Decimal scale {} is out of range", scale))
+    })?;
+    Ok(ColumnType::Decimal75(precision, scale))
+}
+
+/// Determine the output type of an addition or subtraction operation.
+///
+/// Results are always exact: integers are treated as decimals with a scale of 0 and
+/// the result is a `Decimal75` with the larger of the two scales and enough precision
+/// to hold any sum or difference of the operands. Typeless `Scalar` operands produce a `Scalar`.
+pub(crate) fn try_add_subtract_column_types(
+    lhs: ColumnType,
+    rhs: ColumnType,
+    operator: BinaryOperator,
+) -> ConversionResult<ColumnType> {
+    let ((lhs_precision, lhs_scale), (rhs_precision, rhs_scale)) =
+        try_get_precisions_and_scales(lhs, rhs, operator)?;
+    if lhs == ColumnType::Scalar || rhs == ColumnType::Scalar {
+        return Ok(ColumnType::Scalar);
+    }
+    let scale = lhs_scale.max(rhs_scale);
+    let precision = (lhs_precision - lhs_scale).max(rhs_precision - rhs_scale) + scale + 1;
+    try_new_decimal_type(precision, scale)
+}
+
+/// Determine the output type of a multiplication operation.
+///
+/// Results are always exact: integers are treated as decimals with a scale of 0 and
+/// the result is a `Decimal75` whose precision and scale are the sums of those of the operands.
+/// Typeless `Scalar` operands produce a `Scalar`.
+pub(crate) fn try_multiply_column_types(
+    lhs: ColumnType,
+    rhs: ColumnType,
+) -> ConversionResult<ColumnType> {
+    let ((lhs_precision, lhs_scale), (rhs_precision, rhs_scale)) =
+        try_get_precisions_and_scales(lhs, rhs, BinaryOperator::Multiply)?;
+    if lhs == ColumnType::Scalar || rhs == ColumnType::Scalar {
+        return Ok(ColumnType::Scalar);
+    }
+    try_new_decimal_type(lhs_precision + rhs_precision, lhs_scale + rhs_scale)
+}
+
+/// Determine the common type of the possible results of a `CASE` expression.
+///
+/// Results of the same type keep it. Otherwise both must be numeric, and the result is a `Decimal75`
+/// with the larger of the two scales and enough precision to hold either of them.
+/// Typeless `Scalar` operands produce a `Scalar`.
+pub(crate) fn try_common_column_type(
+    lhs: ColumnType,
+    rhs: ColumnType,
+) -> ConversionResult<ColumnType> {
+    if lhs == rhs {
+        return Ok(lhs);
+    }
+    let ((lhs_precision, lhs_scale), (rhs_precision, rhs_scale)) =
+        try_get_precisions_and_scales(lhs, rhs, BinaryOperator::Add)?;
+    if lhs == ColumnType::Scalar || rhs == ColumnType::Scalar {
+        return Ok(ColumnType::Scalar);
+    }
+    let scale = lhs_scale.max(rhs_scale);
+    let precision = (lhs_precision - lhs_scale).max(rhs_precision - rhs_scale) + scale;
+    try_new_decimal_type(precision, scale)
+}
+
+/// The number of decimal places that an average has beyond those of the averaged values.
+const AVERAGE_ADDED_SCALE: i16 = 6;
+
+/// Determine the output type of an `AVG` aggregation.
+///
+/// Averages are exact decimals: integers are treated as decimals with a scale of 0 and the average
+/// has 6 more decimal places than the averaged values, which are rounded half away from zero.
+/// An average is never larger than the largest averaged value, so the precision only needs room for
+/// the integer digits of the values. When that exceeds 75 digits, the scale is reduced instead.
+pub(crate) fn try_average_column_type(column_type: ColumnType) -> ConversionResult<ColumnType> {
+    if !matches!(
+        column_type,
+        ColumnType::SmallInt
+            | ColumnType::Int
+            | ColumnType::BigInt
+            | ColumnType::Int128
+            | ColumnType::Decimal75(_, _)
+    ) {
+        return Err(ConversionError::non_numeric_expr_in_agg(
+            column_type.to_string(),
+            "avg".to_string(),
+        ));
+    }
+    let precision = column_type
+        .precision_value()
+        .expect("Numeric types have precision") as i16;
+    let scale = column_type.scale().unwrap_or(0) as i16;
+    let integer_digits = precision - scale;
+    let average_scale =
+        (scale.max(0) + AVERAGE_ADDED_SCALE).min(MAX_SUPPORTED_PRECISION as i16 - integer_digits);
+    try_new_decimal_type(integer_digits + average_scale, average_scale)
+}
+
+/// Wrap scalar values in a column of the given numerical result type.
+pub(crate) fn scalars_to_numerical_column<S: Scalar>(
+    column_type: ColumnType,
+    values: &[S],
+) -> Column<'_, S> {
+    match column_type {
+        ColumnType::Decimal75(precision, scale) => Column::Decimal75(precision, scale, values),
+        ColumnType::Scalar => Column::Scalar(values),
+        _ => panic!("Numerical operations only produce decimal or scalar columns"),
+    }
+}
+
+/// Add or subtract two columns after scaling them to the same scale.
+pub(crate) fn add_subtract_columns<'a, S: Scalar>(
+    lhs: Column<'a, S>,
+    rhs: Column<'a, S>,
+    lhs_scale: i8,
+    rhs_scale: i8,
+    alloc: &'a Bump,
+    is_subtract: bool,
+) -> &'a [S] {
+    let lhs_len = lhs.len();
+    let rhs_len = rhs.len();
+    assert_eq!(lhs_len, rhs_len, "lhs and rhs should have the same length");
+    let max_scale = lhs_scale.max(rhs_scale);
+    let lhs_scalar = lhs.to_scalar_with_scaling(max_scale - lhs_scale);
+    let rhs_scalar = rhs.to_scalar_with_scaling(max_scale - rhs_scale);
+    alloc.alloc_slice_fill_with(lhs_len, |i| {
+        if is_subtract {
+            lhs_scalar[i] - rhs_scalar[i]
+        } else {
+            lhs_scalar[i] + rhs_scalar[i]
+        }
+    })
+}
+
+/// The counterpart of `add_subtract_columns` for evaluating decimal expressions.
+pub(crate) fn add_subtract_evals<S: Scalar>(
+    lhs_eval: S,
+    rhs_eval: S,
+    lhs_scale: i8,
+    rhs_scale: i8,
+    is_subtract: bool,
+) -> ConversionResult<S> {
+    let max_scale = lhs_scale.max(rhs_scale);
+    let scaled_lhs_eval = scale_scalar(lhs_eval, max_scale - lhs_scale)?;
+    let scaled_rhs_eval = scale_scalar(rhs_eval, max_scale - rhs_scale)?;
+    Ok(if is_subtract {
+        scaled_lhs_eval - scaled_rhs_eval
+    } else {
+        scaled_lhs_eval + scaled_rhs_eval
+    })
+}
+
+/// Multiply two columns together. No scaling is needed since the scales add up.
+pub(crate) fn multiply_columns<'a, S: Scalar>(
+    lhs: &Column<'a, S>,
+    rhs: &Column<'a, S>,
+    alloc: &'a Bump,
+) -> &'a [S] {
+    let lhs_len = lhs.len();
+    let rhs_len = rhs.len();
+    assert_eq!(lhs_len, rhs_len, "lhs and rhs should have the same length");
+    let lhs_scalar = lhs.to_scalar_with_scaling(0);
+    let rhs_scalar = rhs.to_scalar_with_scaling(0);
+    alloc.alloc_slice_fill_with(lhs_len, |i| lhs_scalar[i] * rhs_scalar[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::scalar::Curve25519Scalar;
+
+    #[test]
+    fn we_can_get_the_output_type_of_add_and_subtract() {
+        assert_eq!(
+            try_add_subtract_column_types(
+                ColumnType::BigInt,
+                ColumnType::BigInt,
+                BinaryOperator::Add
+            ),
+            Ok(ColumnType::Decimal75(Precision::new(20).unwrap(), 0))
+        );
+        assert_eq!(
+            try_add_subtract_column_types(
+                ColumnType::Decimal75(Precision::new(10).unwrap(), 3),
+                ColumnType::Int,
+                BinaryOperator::Subtract
+            ),
+            Ok(ColumnType::Decimal75(Precision::new(14).unwrap(), 3))
+        );
+        assert_eq!(
+            try_add_subtract_column_types(
+                ColumnType::Decimal75(Precision::new(10).unwrap(), -2),
+                ColumnType::Decimal75(Precision::new(5).unwrap(), 1),
+                BinaryOperator::Add
+            ),
+            Ok(ColumnType::Decimal75(Precision::new(14).unwrap(), 1))
+        );
+        assert_eq!(
+            try_add_subtract_column_types(
+                ColumnType::Scalar,
+                ColumnType::BigInt,
+                BinaryOperator::Add
+            ),
+            Ok(ColumnType::Scalar)
+        );
+    }
+
+    #[test]
+    fn we_cannot_add_or_subtract_invalid_types() {
+        assert!(matches!(
+            try_add_subtract_column_types(
+                ColumnType::VarChar,
+                ColumnType::BigInt,
+                BinaryOperator::Add
+            ),
+            Err(ConversionError::DataTypeMismatch(_, _))
+        ));
+        assert!(matches!(
+            try_add_subtract_column_types(
+                ColumnType::Boolean,
+                ColumnType::Boolean,
+                BinaryOperator::Subtract
+            ),
+            Err(ConversionError::DataTypeMismatch(_, _))
+        ));
+        assert_eq!(
+            try_add_subtract_column_types(
+                ColumnType::Decimal75(Precision::new(75).unwrap(), 4),
+                ColumnType::BigInt,
+                BinaryOperator::Add
+            ),
+            Err(ConversionError::InvalidPrecision(76))
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_output_type_of_multiply() {
+        assert_eq!(
+            try_multiply_column_types(ColumnType::BigInt, ColumnType::SmallInt),
+            Ok(ColumnType::Decimal75(Precision::new(24).unwrap(), 0))
+        );
+        assert_eq!(
+            try_multiply_column_types(
+                ColumnType::Decimal75(Precision::new(10).unwrap(), 3),
+                ColumnType::Decimal75(Precision::new(5).unwrap(), -1),
+            ),
+            Ok(ColumnType::Decimal75(Precision::new(15).unwrap(), 2))
+        );
+        assert_eq!(
+            try_multiply_column_types(ColumnType::Int128, ColumnType::Int128),
+            Err(ConversionError::InvalidPrecision(78))
+        );
+        assert!(matches!(
+            try_multiply_column_types(ColumnType::VarChar, ColumnType::Int),
+            Err(ConversionError::DataTypeMismatch(_, _))
+        ));
+    }
+
+    #[test]
+    fn we_can_get_the_common_type_of_case_results() {
+        assert_eq!(
+            try_common_column_type(ColumnType::VarChar, ColumnType::VarChar),
+            Ok(ColumnType::VarChar)
+        );
+        assert_eq!(
+            try_common_column_type(ColumnType::BigInt, ColumnType::BigInt),
+            Ok(ColumnType::BigInt)
+        );
+        assert_eq!(
+            try_common_column_type(ColumnType::BigInt, ColumnType::Int),
+            Ok(ColumnType::Decimal75(Precision::new(19).unwrap(), 0))
+        );
+        assert_eq!(
+            try_common_column_type(
+                ColumnType::Decimal75(Precision::new(10).unwrap(), 3),
+                ColumnType::SmallInt
+            ),
+            Ok(ColumnType::Decimal75(Precision::new(10).unwrap(), 3))
+        );
+        assert_eq!(
+            try_common_column_type(ColumnType::Int128, ColumnType::Scalar),
+            Ok(ColumnType::Scalar)
+        );
+        assert!(matches!(
+            try_common_column_type(ColumnType::VarChar, ColumnType::BigInt),
+            Err(ConversionError::DataTypeMismatch(_, _))
+        ));
+        assert!(matches!(
+            try_common_column_type(ColumnType::Boolean, ColumnType::Date),
+            Err(ConversionError::DataTypeMismatch(_, _))
+        ));
+    }
+
+    #[test]
+    fn we_can_get_the_output_type_of_average() {
+        assert_eq!(
+            try_average_column_type(ColumnType::BigInt),
+            Ok(ColumnType::Decimal75(Precision::new(25).unwrap(), 6))
+        );
+        assert_eq!(
+            try_average_column_type(ColumnType::SmallInt),
+            Ok(ColumnType::Decimal75(Precision::new(11).unwrap(), 6))
+        );
+        assert_eq!(
+            try_average_column_type(ColumnType::Decimal75(Precision::new(10).unwrap(), 3)),
+            Ok(ColumnType::Decimal75(Precision::new(16).unwrap(), 9))
+        );
+        assert_eq!(
+            try_average_column_type(ColumnType::Decimal75(Precision::new(10).unwrap(), -2)),
+            Ok(ColumnType::Decimal75(Precision::new(18).unwrap(), 6))
+        );
+        assert_eq!(
+            try_average_column_type(ColumnType::Decimal75(Precision::new(72).unwrap(), 1)),
+            Ok(ColumnType::Decimal75(Precision::new(75).unwrap(), 4))
+        );
+        assert!(matches!(
+            try_average_column_type(ColumnType::VarChar),
+            Err(ConversionError::InvalidExpression(_))
+        ));
+        assert!(matches!(
+            try_average_column_type(ColumnType::Boolean),
+            Err(ConversionError::InvalidExpression(_))
+        ));
+    }
+
+    #[test]
+    fn we_can_add_subtract_and_multiply_columns() {
+        let alloc = Bump::new();
+        let lhs: Column<Curve25519Scalar> = Column::BigInt(&[1, 2, 3]);
+        let rhs_values = [
+            Curve25519Scalar::from(10),
+            Curve25519Scalar::from(-20),
+            Curve25519Scalar::from(35),
+        ];
+        let rhs = Column::Decimal75(Precision::new(5).unwrap(), 1, &rhs_values);
+        assert_eq!(
+            add_subtract_columns(lhs.clone(), rhs.clone(), 0, 1, &alloc, false),
+            &[
+                Curve25519Scalar::from(20),
+                Curve25519Scalar::from(0),
+                Curve25519Scalar::from(65)
+            ]
+        );
+        assert_eq!(
+            add_subtract_columns(lhs.clone(), rhs.clone(), 0, 1, &alloc, true),
+            &[
+                Curve25519Scalar::from(0),
+                Curve25519Scalar::from(40),
+                Curve25519Scalar::from(-5)
+            ]
+        );
+        assert_eq!(
+            multiply_columns(&lhs, &rhs, &alloc),
+            &[
+                Curve25519Scalar::from(10),
+                Curve25519Scalar::from(-40),
+                Curve25519Scalar::from(105)
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_add_and_subtract_evaluations() {
+        let lhs = Curve25519Scalar::from(7);
+        let rhs = Curve25519Scalar::from(3);
+        assert_eq!(
+            add_subtract_evals(lhs, rhs, 2, 0, false).unwrap(),
+            Curve25519Scalar::from(307)
+        );
+        assert_eq!(
+            add_subtract_evals(lhs, rhs, 0, 1, true).unwrap(),
+            Curve25519Scalar::from(67)
+        );
+    }
+}
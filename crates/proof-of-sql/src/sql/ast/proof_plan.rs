@@ -1,4 +1,7 @@
-use super::{DenseFilterExpr, FilterExpr, GroupByExpr};
+use super::{
+    DenseFilterExpr, FilterExpr, GroupByExpr, JoinExpr, RunningSumExpr, SubqueryFilterExpr,
+    TopKExpr, UnionExpr,
+};
 use crate::{
     base::commitment::Commitment,
     sql::proof::{ProofExpr, ProverEvaluate},
@@ -28,6 +31,38 @@ pub enum ProofPlan<C: Commitment> {
     ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
     /// ```
     DenseFilter(DenseFilterExpr<C>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <left_key>, <left_column1>, ..., <right_column1>, ...
+    ///     FROM <left_table> JOIN <right_table> ON <left_key> = <right_key>
+    /// ```
+    Join(Box<JoinExpr<C>>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
+    ///     ORDER BY <result_exprI> [ASC | DESC] LIMIT <limit>
+    /// ```
+    TopK(TopKExpr<C>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table1> WHERE <where_clause1>
+    ///     UNION ALL
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table2> WHERE <where_clause2>
+    /// ```
+    Union(UnionExpr<C>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
+    ///     AND <expr> [NOT] IN (SELECT <subquery_expr> FROM <subquery_table> WHERE <subquery_where_clause>)
+    ///     AND ...
+    /// ```
+    SubqueryFilter(SubqueryFilterExpr<C>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., SUM(<sum_expr>) OVER (ORDER BY <order_by>), ..., <result_exprN>
+    ///     FROM <table> WHERE <where_clause>
+    /// ```
+    RunningSum(RunningSumExpr<C>),
 }
 
 impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
@@ -40,6 +75,11 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.count(builder, accessor),
             ProofPlan::GroupBy(expr) => expr.count(builder, accessor),
             ProofPlan::DenseFilter(expr) => expr.count(builder, accessor),
+            ProofPlan::Join(expr) => expr.count(builder, accessor),
+            ProofPlan::TopK(expr) => expr.count(builder, accessor),
+            ProofPlan::Union(expr) => expr.count(builder, accessor),
+            ProofPlan::SubqueryFilter(expr) => expr.count(builder, accessor),
+            ProofPlan::RunningSum(expr) => expr.count(builder, accessor),
         }
     }
 
@@ -48,6 +88,11 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.get_length(accessor),
             ProofPlan::GroupBy(expr) => expr.get_length(accessor),
             ProofPlan::DenseFilter(expr) => expr.get_length(accessor),
+            ProofPlan::Join(expr) => expr.get_length(accessor),
+            ProofPlan::TopK(expr) => expr.get_length(accessor),
+            ProofPlan::Union(expr) => expr.get_length(accessor),
+            ProofPlan::SubqueryFilter(expr) => expr.get_length(accessor),
+            ProofPlan::RunningSum(expr) => expr.get_length(accessor),
         }
     }
 
@@ -56,6 +101,28 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.get_offset(accessor),
             ProofPlan::GroupBy(expr) => expr.get_offset(accessor),
             ProofPlan::DenseFilter(expr) => expr.get_offset(accessor),
+            ProofPlan::Join(expr) => expr.get_offset(accessor),
+            ProofPlan::TopK(expr) => expr.get_offset(accessor),
+            ProofPlan::Union(expr) => expr.get_offset(accessor),
+            ProofPlan::SubqueryFilter(expr) => expr.get_offset(accessor),
+            ProofPlan::RunningSum(expr) => expr.get_offset(accessor),
+        }
+    }
+
+    fn get_proof_length(
+        &self,
+        accessor: &dyn crate::base::database::MetadataAccessor,
+        result_length: usize,
+    ) -> usize {
+        match self {
+            ProofPlan::Filter(expr) => expr.get_proof_length(accessor, result_length),
+            ProofPlan::GroupBy(expr) => expr.get_proof_length(accessor, result_length),
+            ProofPlan::DenseFilter(expr) => expr.get_proof_length(accessor, result_length),
+            ProofPlan::Join(expr) => expr.get_proof_length(accessor, result_length),
+            ProofPlan::TopK(expr) => expr.get_proof_length(accessor, result_length),
+            ProofPlan::Union(expr) => expr.get_proof_length(accessor, result_length),
+            ProofPlan::SubqueryFilter(expr) => expr.get_proof_length(accessor, result_length),
+            ProofPlan::RunningSum(expr) => expr.get_proof_length(accessor, result_length),
         }
     }
 
@@ -69,6 +136,11 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.verifier_evaluate(builder, accessor),
             ProofPlan::GroupBy(expr) => expr.verifier_evaluate(builder, accessor),
             ProofPlan::DenseFilter(expr) => expr.verifier_evaluate(builder, accessor),
+            ProofPlan::Join(expr) => expr.verifier_evaluate(builder, accessor),
+            ProofPlan::TopK(expr) => expr.verifier_evaluate(builder, accessor),
+            ProofPlan::Union(expr) => expr.verifier_evaluate(builder, accessor),
+            ProofPlan::SubqueryFilter(expr) => expr.verifier_evaluate(builder, accessor),
+            ProofPlan::RunningSum(expr) => expr.verifier_evaluate(builder, accessor),
         }
     }
 
@@ -77,6 +149,11 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.get_column_result_fields(),
             ProofPlan::GroupBy(expr) => expr.get_column_result_fields(),
             ProofPlan::DenseFilter(expr) => expr.get_column_result_fields(),
+            ProofPlan::Join(expr) => expr.get_column_result_fields(),
+            ProofPlan::TopK(expr) => expr.get_column_result_fields(),
+            ProofPlan::Union(expr) => expr.get_column_result_fields(),
+            ProofPlan::SubqueryFilter(expr) => expr.get_column_result_fields(),
+            ProofPlan::RunningSum(expr) => expr.get_column_result_fields(),
         }
     }
 
@@ -85,6 +162,11 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.get_column_references(),
             ProofPlan::GroupBy(expr) => expr.get_column_references(),
             ProofPlan::DenseFilter(expr) => expr.get_column_references(),
+            ProofPlan::Join(expr) => expr.get_column_references(),
+            ProofPlan::TopK(expr) => expr.get_column_references(),
+            ProofPlan::Union(expr) => expr.get_column_references(),
+            ProofPlan::SubqueryFilter(expr) => expr.get_column_references(),
+            ProofPlan::RunningSum(expr) => expr.get_column_references(),
         }
     }
 }
@@ -101,6 +183,30 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.result_evaluate(builder, alloc, accessor),
             ProofPlan::GroupBy(expr) => expr.result_evaluate(builder, alloc, accessor),
             ProofPlan::DenseFilter(expr) => expr.result_evaluate(builder, alloc, accessor),
+            ProofPlan::Join(expr) => expr.result_evaluate(builder, alloc, accessor),
+            ProofPlan::TopK(expr) => expr.result_evaluate(builder, alloc, accessor),
+            ProofPlan::Union(expr) => expr.result_evaluate(builder, alloc, accessor),
+            ProofPlan::SubqueryFilter(expr) => expr.result_evaluate(builder, alloc, accessor),
+            ProofPlan::RunningSum(expr) => expr.result_evaluate(builder, alloc, accessor),
+        }
+    }
+
+    #[tracing::instrument(name = "ProofPlan::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut crate::sql::proof::FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a bumpalo::Bump,
+        accessor: &'a dyn crate::base::database::DataAccessor<C::Scalar>,
+    ) {
+        match self {
+            ProofPlan::Filter(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            ProofPlan::GroupBy(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            ProofPlan::DenseFilter(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            ProofPlan::Join(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            ProofPlan::TopK(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            ProofPlan::Union(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            ProofPlan::SubqueryFilter(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            ProofPlan::RunningSum(expr) => expr.first_round_evaluate(builder, alloc, accessor),
         }
     }
 
@@ -115,6 +221,11 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for ProofPlan<C> {
             ProofPlan::Filter(expr) => expr.prover_evaluate(builder, alloc, accessor),
             ProofPlan::GroupBy(expr) => expr.prover_evaluate(builder, alloc, accessor),
             ProofPlan::DenseFilter(expr) => expr.prover_evaluate(builder, alloc, accessor),
+            ProofPlan::Join(expr) => expr.prover_evaluate(builder, alloc, accessor),
+            ProofPlan::TopK(expr) => expr.prover_evaluate(builder, alloc, accessor),
+            ProofPlan::Union(expr) => expr.prover_evaluate(builder, alloc, accessor),
+            ProofPlan::SubqueryFilter(expr) => expr.prover_evaluate(builder, alloc, accessor),
+            ProofPlan::RunningSum(expr) => expr.prover_evaluate(builder, alloc, accessor),
         }
     }
 }
@@ -1,5 +1,8 @@
 use super::{
-    AndExpr, ColumnExpr, EqualsExpr, InequalityExpr, LiteralExpr, NotExpr, OrExpr, ProvableExpr,
+    check_provable_cast_column_types, try_add_subtract_column_types, try_common_column_type,
+    try_multiply_column_types, AddSubtractExpr, AndExpr, BetweenExpr, CaseExpr, CastExpr,
+    ColumnExpr, EqualsExpr, InListExpr, InequalityExpr, LiteralExpr, MultiplyExpr, NotExpr, OrExpr,
+    ProvableExpr, MAX_IN_LIST_LENGTH,
 };
 use crate::{
     base::{
@@ -34,6 +37,18 @@ pub enum ProvableExprPlan<C: Commitment> {
     Equals(EqualsExpr<C>),
     /// Provable AST expression for an inequality expression
     Inequality(InequalityExpr<C>),
+    /// Provable AST expression for a membership test in a list of literals
+    InList(InListExpr<C>),
+    /// Provable AST expression for a range check
+    Between(BetweenExpr<C>),
+    /// Provable numeric + / - expression
+    AddSubtract(AddSubtractExpr<C>),
+    /// Provable numeric * expression
+    Multiply(MultiplyExpr<C>),
+    /// Provable CASE expression
+    Case(CaseExpr<C>),
+    /// Provable CAST expression
+    Cast(CastExpr<C>),
 }
 impl<C: Commitment> ProvableExprPlan<C> {
     /// Create column expression
@@ -109,6 +124,140 @@ impl<C: Commitment> ProvableExprPlan<C> {
         }
     }
 
+    /// Create a new IN expression
+    pub fn try_new_in_list(
+        expr: ProvableExprPlan<C>,
+        list: Vec<LiteralValue<C::Scalar>>,
+    ) -> ConversionResult<Self> {
+        if list.is_empty() {
+            return Err(ConversionError::Unprovable(
+                "IN lists must not be empty".to_string(),
+            ));
+        }
+        if list.len() > MAX_IN_LIST_LENGTH {
+            return Err(ConversionError::Unprovable(format!(
+                "IN lists can have at most {MAX_IN_LIST_LENGTH} literals"
+            )));
+        }
+        let expr_datatype = expr.data_type();
+        for literal in &list {
+            let literal_datatype = literal.column_type();
+            if !type_check_binary_operation(
+                &expr_datatype,
+                &literal_datatype,
+                BinaryOperator::Equal,
+            ) {
+                return Err(ConversionError::DataTypeMismatch(
+                    expr_datatype.to_string(),
+                    literal_datatype.to_string(),
+                ));
+            }
+        }
+        Ok(Self::InList(InListExpr::new(Box::new(expr), list)))
+    }
+
+    /// Create a new BETWEEN expression
+    pub fn try_new_between(
+        expr: ProvableExprPlan<C>,
+        low: ProvableExprPlan<C>,
+        high: ProvableExprPlan<C>,
+    ) -> ConversionResult<Self> {
+        let expr_datatype = expr.data_type();
+        for bound_datatype in [low.data_type(), high.data_type()] {
+            if !type_check_binary_operation(
+                &expr_datatype,
+                &bound_datatype,
+                BinaryOperator::LessThanOrEqual,
+            ) {
+                return Err(ConversionError::DataTypeMismatch(
+                    expr_datatype.to_string(),
+                    bound_datatype.to_string(),
+                ));
+            }
+        }
+        Ok(Self::Between(BetweenExpr::new(
+            Box::new(expr),
+            Box::new(low),
+            Box::new(high),
+        )))
+    }
+
+    /// Create a new add expression
+    pub fn try_new_add(
+        lhs: ProvableExprPlan<C>,
+        rhs: ProvableExprPlan<C>,
+    ) -> ConversionResult<Self> {
+        try_add_subtract_column_types(lhs.data_type(), rhs.data_type(), BinaryOperator::Add)?;
+        Ok(Self::AddSubtract(AddSubtractExpr::new(
+            Box::new(lhs),
+            Box::new(rhs),
+            false,
+        )))
+    }
+
+    /// Create a new subtract expression
+    pub fn try_new_subtract(
+        lhs: ProvableExprPlan<C>,
+        rhs: ProvableExprPlan<C>,
+    ) -> ConversionResult<Self> {
+        try_add_subtract_column_types(lhs.data_type(), rhs.data_type(), BinaryOperator::Subtract)?;
+        Ok(Self::AddSubtract(AddSubtractExpr::new(
+            Box::new(lhs),
+            Box::new(rhs),
+            true,
+        )))
+    }
+
+    /// Create a new multiply expression
+    pub fn try_new_multiply(
+        lhs: ProvableExprPlan<C>,
+        rhs: ProvableExprPlan<C>,
+    ) -> ConversionResult<Self> {
+        try_multiply_column_types(lhs.data_type(), rhs.data_type())?;
+        Ok(Self::Multiply(MultiplyExpr::new(
+            Box::new(lhs),
+            Box::new(rhs),
+        )))
+    }
+
+    /// Create a new CASE expression
+    pub fn try_new_case(
+        when_then: Vec<(ProvableExprPlan<C>, ProvableExprPlan<C>)>,
+        else_expr: ProvableExprPlan<C>,
+    ) -> ConversionResult<Self> {
+        when_then
+            .iter()
+            .try_fold(else_expr.data_type(), |data_type, (when, then)| {
+                when.check_data_type(ColumnType::Boolean)?;
+                try_common_column_type(data_type, then.data_type())
+            })?;
+        Ok(Self::Case(CaseExpr::new(when_then, Box::new(else_expr))))
+    }
+
+    /// Create a new CAST expression
+    pub fn try_new_cast(
+        expr: ProvableExprPlan<C>,
+        data_type: ColumnType,
+    ) -> ConversionResult<Self> {
+        check_provable_cast_column_types(expr.data_type(), data_type)?;
+        Ok(Self::Cast(CastExpr::new(Box::new(expr), data_type)))
+    }
+
+    /// Create a new CAST expression whose value is only used, and checked to be in range, where the guard is true
+    pub fn try_new_guarded_cast(
+        expr: ProvableExprPlan<C>,
+        data_type: ColumnType,
+        guard: ProvableExprPlan<C>,
+    ) -> ConversionResult<Self> {
+        check_provable_cast_column_types(expr.data_type(), data_type)?;
+        guard.check_data_type(ColumnType::Boolean)?;
+        Ok(Self::Cast(CastExpr::new_guarded(
+            Box::new(expr),
+            data_type,
+            Box::new(guard),
+        )))
+    }
+
     /// Check that the plan has the correct data type
     fn check_data_type(&self, data_type: ColumnType) -> ConversionResult<()> {
         if self.data_type() == data_type {
@@ -132,6 +281,12 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Literal(expr) => ProvableExpr::<C>::count(expr, builder),
             ProvableExprPlan::Equals(expr) => ProvableExpr::<C>::count(expr, builder),
             ProvableExprPlan::Inequality(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::InList(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Between(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::AddSubtract(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Multiply(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Case(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Cast(expr) => ProvableExpr::<C>::count(expr, builder),
         }
     }
 
@@ -139,11 +294,17 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
         match self {
             ProvableExprPlan::Column(expr) => expr.data_type(),
             ProvableExprPlan::Literal(expr) => ProvableExpr::<C>::data_type(expr),
+            ProvableExprPlan::AddSubtract(expr) => ProvableExpr::<C>::data_type(expr),
+            ProvableExprPlan::Multiply(expr) => ProvableExpr::<C>::data_type(expr),
+            ProvableExprPlan::Case(expr) => ProvableExpr::<C>::data_type(expr),
+            ProvableExprPlan::Cast(expr) => ProvableExpr::<C>::data_type(expr),
             ProvableExprPlan::And(_)
             | ProvableExprPlan::Or(_)
             | ProvableExprPlan::Not(_)
             | ProvableExprPlan::Equals(_)
-            | ProvableExprPlan::Inequality(_) => ColumnType::Boolean,
+            | ProvableExprPlan::Inequality(_)
+            | ProvableExprPlan::InList(_)
+            | ProvableExprPlan::Between(_) => ColumnType::Boolean,
         }
     }
 
@@ -175,6 +336,24 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Inequality(expr) => {
                 ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
             }
+            ProvableExprPlan::InList(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Between(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::AddSubtract(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Multiply(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Case(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Cast(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
         }
     }
 
@@ -206,6 +385,24 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Inequality(expr) => {
                 ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
             }
+            ProvableExprPlan::InList(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Between(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::AddSubtract(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Multiply(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Case(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Cast(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
         }
     }
 
@@ -224,6 +421,12 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Literal(expr) => expr.verifier_evaluate(builder, accessor),
             ProvableExprPlan::Equals(expr) => expr.verifier_evaluate(builder, accessor),
             ProvableExprPlan::Inequality(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::InList(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Between(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::AddSubtract(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Multiply(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Case(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Cast(expr) => expr.verifier_evaluate(builder, accessor),
         }
     }
 
@@ -244,6 +447,20 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Inequality(expr) => {
                 ProvableExpr::<C>::get_column_references(expr, columns)
             }
+            ProvableExprPlan::InList(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::Between(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::AddSubtract(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::Multiply(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::Case(expr) => ProvableExpr::<C>::get_column_references(expr, columns),
+            ProvableExprPlan::Cast(expr) => ProvableExpr::<C>::get_column_references(expr, columns),
         }
     }
 }
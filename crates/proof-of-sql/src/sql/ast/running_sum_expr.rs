@@ -0,0 +1,540 @@
+use super::{
+    count_sign,
+    dense_filter_expr::{prove_filter, verify_filter},
+    filter_columns, fold_columns, fold_vals,
+    provable_expr_plan::ProvableExprPlan,
+    prover_evaluate_sign, verifier_evaluate_sign, AliasedProvableExprPlan, ColumnExpr,
+    ProvableExpr, TableExpr,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+        },
+        polynomial::{
+            compute_truncated_lagrange_basis_index_sum, compute_truncated_lagrange_basis_sum,
+        },
+        proof::ProofError,
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::proof::{
+        CountBuilder, FirstRoundBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate,
+        ResultBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use core::iter::repeat_with;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <result_expr1>, ..., SUM(<sum_expr>) OVER (ORDER BY <order_by>) AS <alias>, ..., <result_exprN>
+///     FROM <table> WHERE <where_clause>
+/// ```
+///
+/// The result is the selected rows in the order of the table, along with the running sum of `<sum_expr>` over them.
+/// The `<order_by>` column must be strictly increasing in the order of the table, so that the running sum in its
+/// order is the running sum in the order of the table, and no two rows are peers of each other.
+///
+/// Before any challenges are drawn, the prover commits to the running `sums` over all the rows of the table, where
+/// the rows that are not selected add nothing, and to the `shifted_sums` and `shifted_keys`, which are the running
+/// sums and the keys shifted down by one row, starting with zero. The proof then establishes that
+/// 1. `sums - shifted_sums - selection * sum_expr` is zero,
+/// 2. the shifted columns are the columns shifted down by one row, by checking that the multiset of the
+///    rows `(i, shifted_sums[i], shifted_keys[i])` is the multiset of the rows `(i + 1, sums[i], keys[i])`
+///    for every row but the last, with the row `(0, 0, 0)`,
+/// 3. `keys - shifted_keys - 1` is never negative after the first row, using a sign decomposition, and
+/// 4. the selected rows of the results and the running sums are the result, like for a [`super::DenseFilterExpr`].
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunningSumExpr<C: Commitment> {
+    pub(super) aliased_results: Vec<AliasedProvableExprPlan<C>>,
+    pub(super) table: TableExpr,
+    pub(super) where_clause: ProvableExprPlan<C>,
+    pub(super) order_by: ColumnExpr<C>,
+    pub(super) sum_expr: AliasedProvableExprPlan<C>,
+    pub(super) sum_index: usize,
+}
+
+impl<C: Commitment> RunningSumExpr<C> {
+    /// Creates a new running sum expression, whose running sum is the result column at `sum_index`.
+    pub fn new(
+        aliased_results: Vec<AliasedProvableExprPlan<C>>,
+        table: TableExpr,
+        where_clause: ProvableExprPlan<C>,
+        order_by: ColumnExpr<C>,
+        sum_expr: AliasedProvableExprPlan<C>,
+        sum_index: usize,
+    ) -> Self {
+        Self {
+            aliased_results,
+            table,
+            where_clause,
+            order_by,
+            sum_expr,
+            sum_index,
+        }
+    }
+
+    /// Inserts the running sums among the other result columns.
+    fn with_sums<T>(&self, mut columns: Vec<T>, sums: T) -> Vec<T> {
+        columns.insert(self.sum_index.min(columns.len()), sums);
+        columns
+    }
+}
+
+/// Returns the running sums of the selected values, and the running sums and the keys shifted down by one row.
+fn running_sums<'a, S: Scalar>(
+    alloc: &'a Bump,
+    selection: &[bool],
+    values: &Column<'a, S>,
+    keys: &Column<'a, S>,
+) -> (&'a [S], &'a [S], &'a [S]) {
+    let values = values.to_scalar_with_scaling(0);
+    let keys = keys.to_scalar_with_scaling(0);
+    let n = selection.len();
+    let sums = alloc.alloc_slice_fill_copy(n, S::zero());
+    let mut sum = S::zero();
+    for i in 0..n {
+        if selection[i] {
+            sum += values[i];
+        }
+        sums[i] = sum;
+    }
+    let shifted_sums = alloc.alloc_slice_fill_with(n, |i| match i {
+        0 => S::zero(),
+        i => sums[i - 1],
+    });
+    let shifted_keys = alloc.alloc_slice_fill_with(n, |i| match i {
+        0 => S::zero(),
+        i => keys[i - 1],
+    });
+    (sums, shifted_sums, shifted_keys)
+}
+
+impl<C: Commitment> ProofExpr<C> for RunningSumExpr<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        self.where_clause.count(builder)?;
+        for aliased_expr in self.aliased_results.iter() {
+            aliased_expr.expr.count(builder)?;
+            builder.count_result_columns(1);
+        }
+        self.order_by.count(builder)?;
+        self.sum_expr.expr.count(builder)?;
+        builder.count_result_columns(1);
+        builder.count_first_round_mles(3);
+        builder.count_intermediate_mles(5);
+        builder.count_subpolynomials(8);
+        builder.count_degree(3);
+        builder.count_post_result_challenges(4);
+        count_sign(builder)?;
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_length(self.table.table_ref)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_offset(self.table.table_ref)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<(), ProofError> {
+        // 1. selection
+        let selection_eval = self.where_clause.verifier_evaluate(builder, accessor)?;
+        // 2. columns
+        let columns_evals = self
+            .aliased_results
+            .iter()
+            .map(|aliased_expr| aliased_expr.expr.verifier_evaluate(builder, accessor))
+            .collect::<Result<Vec<_>, _>>()?;
+        let key_eval = self.order_by.verifier_evaluate(builder, accessor)?;
+        let value_eval = self.sum_expr.expr.verifier_evaluate(builder, accessor)?;
+        // 3. running sums
+        let sums_eval = builder.consume_first_round_mle();
+        let shifted_sums_eval = builder.consume_first_round_mle();
+        let shifted_keys_eval = builder.consume_first_round_mle();
+        // 4. filtered_columns
+        let filtered_columns_evals = Vec::from_iter(
+            repeat_with(|| builder.consume_result_mle()).take(self.aliased_results.len() + 1),
+        );
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        let gamma = builder.consume_post_result_challenge();
+        let delta = builder.consume_post_result_challenge();
+
+        verify_filter(
+            builder,
+            alpha,
+            beta,
+            self.with_sums(columns_evals, sums_eval),
+            selection_eval,
+            filtered_columns_evals,
+        )?;
+        verify_running_sum(
+            builder,
+            (gamma, delta),
+            (selection_eval, value_eval, key_eval),
+            (sums_eval, shifted_sums_eval, shifted_keys_eval),
+        )
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        let fields = Vec::from_iter(self.aliased_results.iter().map(|aliased_expr| {
+            ColumnField::new(aliased_expr.alias, aliased_expr.expr.data_type())
+        }));
+        // The running sums are of the type of the summed expression, like the sums of a `GroupByExpr`
+        // and the running sums computed in post-processing, so a sum that does not fit fails to decode.
+        self.with_sums(
+            fields,
+            ColumnField::new(self.sum_expr.alias, self.sum_expr.expr.data_type()),
+        )
+    }
+
+    fn get_column_references(&self) -> HashSet<ColumnRef> {
+        let mut columns = HashSet::new();
+
+        for aliased_expr in self.aliased_results.iter() {
+            aliased_expr.expr.get_column_references(&mut columns);
+        }
+        self.order_by.get_column_references(&mut columns);
+        self.sum_expr.expr.get_column_references(&mut columns);
+
+        self.where_clause.get_column_references(&mut columns);
+
+        columns
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for RunningSumExpr<C> {
+    #[tracing::instrument(name = "RunningSumExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        builder: &mut ResultBuilder<'a>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let table_length = builder.table_length();
+        // 1. selection
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause
+                .result_evaluate(table_length, alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+
+        // 2. columns
+        let columns = Vec::from_iter(self.aliased_results.iter().map(|aliased_expr| {
+            aliased_expr
+                .expr
+                .result_evaluate(table_length, alloc, accessor)
+        }));
+        let keys = self.order_by.result_evaluate(table_length, alloc, accessor);
+        let values = self
+            .sum_expr
+            .expr
+            .result_evaluate(table_length, alloc, accessor);
+        // 3. running sums
+        let (sums, _, _) = running_sums(alloc, selection, &values, &keys);
+        // Compute filtered_columns and indexes
+        let columns = self.with_sums(columns, Column::Scalar(sums));
+        let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
+        // 4. set indexes
+        builder.set_result_indexes(Indexes::Dense(0..(result_len as u64)));
+        // 5. set filtered_columns
+        for col in filtered_columns {
+            builder.produce_result_column(col);
+        }
+        builder.request_post_result_challenges(4);
+    }
+
+    #[tracing::instrument(
+        name = "RunningSumExpr::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let table_length = accessor.get_length(self.table.table_ref);
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause
+                .result_evaluate(table_length, alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+        let keys = self.order_by.result_evaluate(table_length, alloc, accessor);
+        let values = self
+            .sum_expr
+            .expr
+            .result_evaluate(table_length, alloc, accessor);
+        let (sums, shifted_sums, shifted_keys) = running_sums(alloc, selection, &values, &keys);
+        builder.produce_first_round_mle(sums);
+        builder.produce_first_round_mle(shifted_sums);
+        builder.produce_first_round_mle(shifted_keys);
+    }
+
+    #[tracing::instrument(name = "RunningSumExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        // 1. selection
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause.prover_evaluate(builder, alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+
+        // 2. columns
+        let columns = Vec::from_iter(
+            self.aliased_results
+                .iter()
+                .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, accessor)),
+        );
+        let keys = self.order_by.prover_evaluate(builder, alloc, accessor);
+        let values = self.sum_expr.expr.prover_evaluate(builder, alloc, accessor);
+        // 3. running sums
+        let (sums, shifted_sums, shifted_keys) = running_sums(alloc, selection, &values, &keys);
+        builder.produce_first_round_mle(sums);
+        builder.produce_first_round_mle(shifted_sums);
+        builder.produce_first_round_mle(shifted_keys);
+        // Compute filtered_columns and indexes
+        let columns = self.with_sums(columns, Column::Scalar(sums));
+        let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        let gamma = builder.consume_post_result_challenge();
+        let delta = builder.consume_post_result_challenge();
+
+        prove_filter::<C::Scalar>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &columns,
+            selection,
+            &filtered_columns,
+            result_len,
+        );
+        prove_running_sum(
+            builder,
+            alloc,
+            (gamma, delta),
+            (selection, values, keys),
+            (sums, shifted_sums, shifted_keys),
+        );
+    }
+}
+
+/// Verifies that the running sums are those of the selected values in the order of the keys.
+///
+/// See [`prove_running_sum`].
+fn verify_running_sum<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    (alpha, beta): (C::Scalar, C::Scalar),
+    (selection_eval, value_eval, key_eval): (C::Scalar, C::Scalar, C::Scalar),
+    (sums_eval, shifted_sums_eval, shifted_keys_eval): (C::Scalar, C::Scalar, C::Scalar),
+) -> Result<(), ProofError> {
+    let one_eval = builder.mle_evaluations.one_evaluation;
+    let rand_eval = builder.mle_evaluations.random_evaluation;
+    let table_length = builder.mle_evaluations.table_length;
+    let point = builder.mle_evaluations.evaluation_point;
+    let index_eval = compute_truncated_lagrange_basis_index_sum(table_length, point);
+    let first_row_eval = compute_truncated_lagrange_basis_sum(table_length.min(1), point);
+    let last_row_eval =
+        one_eval - compute_truncated_lagrange_basis_sum(table_length.saturating_sub(1), point);
+
+    // sums - shifted_sums - selection * value = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval * (sums_eval - shifted_sums_eval - selection_eval * value_eval)),
+    );
+
+    // shifted_fold * shifted_star - 1 = 0
+    let shifted_fold_eval =
+        alpha * one_eval + fold_vals(beta, &[index_eval, shifted_sums_eval, shifted_keys_eval]);
+    let shifted_star_eval = builder.consume_intermediate_mle();
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval * (shifted_fold_eval * shifted_star_eval - one_eval)),
+    );
+
+    // fold * star - 1 = 0
+    let fold_eval =
+        alpha * one_eval + fold_vals(beta, &[index_eval + one_eval, sums_eval, key_eval]);
+    let star_eval = builder.consume_intermediate_mle();
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval * (fold_eval * star_eval - one_eval)),
+    );
+
+    // sum alpha * (shifted_star - star + star * last_row) - first_row = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(alpha * (shifted_star_eval - star_eval + star_eval * last_row_eval) - first_row_eval),
+    );
+
+    // diff - (1 - first_row) * (key - shifted_key - 1) = 0
+    let diff_eval = builder.consume_intermediate_mle();
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval
+            * (diff_eval - key_eval
+                + shifted_keys_eval
+                + first_row_eval * (key_eval - shifted_keys_eval)
+                + one_eval
+                - first_row_eval)),
+    );
+
+    // sign(diff) == 0
+    let sign_eval = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
+    if sign_eval != Zero::zero() {
+        return Err(ProofError::VerificationError(
+            "the keys of a running sum must be strictly increasing",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Proves that the running sums are those of the selected values in the order of the keys.
+///
+/// This proves that
+/// 1. `sums - shifted_sums - selection * values` is zero,
+/// 2. `shifted_sums` and `shifted_keys` are `sums` and `keys` shifted down by one row, with a log-derivative check
+///    of the multisets of their rows, which are folded with the row indexes, and
+/// 3. `diff`, which is `(1 - first_row) * (keys - shifted_keys - 1)`, is never negative.
+fn prove_running_sum<'a, S: Scalar>(
+    builder: &mut ProofBuilder<'a, S>,
+    alloc: &'a Bump,
+    (alpha, beta): (S, S),
+    (selection, values, keys): (&'a [bool], Column<'a, S>, Column<'a, S>),
+    (sums, shifted_sums, shifted_keys): (&'a [S], &'a [S], &'a [S]),
+) {
+    let n = builder.table_length();
+    let indexes = alloc.alloc_slice_fill_with(n, |i| i as i64);
+    let next_indexes = alloc.alloc_slice_fill_with(n, |i| i as i64 + 1);
+    let first_row = alloc.alloc_slice_fill_with(n, |i| i == 0);
+    let last_row = alloc.alloc_slice_fill_with(n, |i| i + 1 == n);
+
+    // sums - shifted_sums - selection * values = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(sums)]),
+            (-S::one(), vec![Box::new(shifted_sums)]),
+            (-S::one(), vec![Box::new(selection), Box::new(values)]),
+        ],
+    );
+
+    // shifted_fold * shifted_star - 1 = 0
+    let shifted_fold = alloc.alloc_slice_fill_copy(n, alpha);
+    fold_columns(
+        shifted_fold,
+        One::one(),
+        beta,
+        &[
+            Column::BigInt(indexes),
+            Column::Scalar(shifted_sums),
+            Column::Scalar(shifted_keys),
+        ],
+    );
+    let shifted_star = alloc.alloc_slice_copy(shifted_fold);
+    slice_ops::batch_inversion(shifted_star);
+    builder.produce_intermediate_mle(shifted_star as &[_]);
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (
+                S::one(),
+                vec![
+                    Box::new(shifted_star as &[_]),
+                    Box::new(shifted_fold as &[_]),
+                ],
+            ),
+            (-S::one(), vec![]),
+        ],
+    );
+
+    // fold * star - 1 = 0
+    let fold = alloc.alloc_slice_fill_copy(n, alpha);
+    fold_columns(
+        fold,
+        One::one(),
+        beta,
+        &[
+            Column::BigInt(next_indexes),
+            Column::Scalar(sums),
+            keys.clone(),
+        ],
+    );
+    let star = alloc.alloc_slice_copy(fold);
+    slice_ops::batch_inversion(star);
+    builder.produce_intermediate_mle(star as &[_]);
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (
+                S::one(),
+                vec![Box::new(star as &[_]), Box::new(fold as &[_])],
+            ),
+            (-S::one(), vec![]),
+        ],
+    );
+
+    // sum alpha * (shifted_star - star + star * last_row) - first_row = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (alpha, vec![Box::new(shifted_star as &[_])]),
+            (-alpha, vec![Box::new(star as &[_])]),
+            (
+                alpha,
+                vec![Box::new(star as &[_]), Box::new(last_row as &[_])],
+            ),
+            (-S::one(), vec![Box::new(first_row as &[_])]),
+        ],
+    );
+
+    // diff = (1 - first_row) * (keys - shifted_keys - 1)
+    let key_scalars = keys.to_scalar_with_scaling(0);
+    let diff = alloc.alloc_slice_fill_with(n, |i| match i {
+        0 => S::zero(),
+        i => key_scalars[i] - shifted_keys[i] - S::one(),
+    });
+    builder.produce_intermediate_mle(diff as &[_]);
+
+    // diff - (1 - first_row) * (keys - shifted_keys - 1) = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(diff as &[_])]),
+            (-S::one(), vec![Box::new(keys.clone())]),
+            (S::one(), vec![Box::new(shifted_keys)]),
+            (S::one(), vec![Box::new(first_row as &[_]), Box::new(keys)]),
+            (
+                -S::one(),
+                vec![Box::new(first_row as &[_]), Box::new(shifted_keys)],
+            ),
+            (S::one(), vec![]),
+            (-S::one(), vec![Box::new(first_row as &[_])]),
+        ],
+    );
+
+    // sign(diff) == 0
+    prover_evaluate_sign(builder, alloc, diff);
+}
@@ -0,0 +1,115 @@
+use super::test_utility::{
+    add, aliased_plan, col_expr, cols_expr_plan, column, const_bigint, gte, running_sum, tab,
+};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    },
+    sql::proof::{exercise_verification, VerifiableQueryResult},
+};
+
+#[test]
+fn we_can_prove_a_running_sum_over_the_selected_rows() {
+    let data = owned_table([
+        bigint("ts", [1, 2, 4, 7, 8, 9]),
+        bigint("a", [5, -3, 1, 7, 2, 4]),
+        bigint("b", [1, 1, 0, 1, 0, 1]),
+        varchar("c", ["e", "i", "a", "g", "c", "h"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // SELECT c, SUM(a) OVER (ORDER BY ts) AS s, ts FROM sxt.t WHERE b >= 1
+    let expr = running_sum(
+        cols_expr_plan(t, &["c", "ts"], &accessor),
+        tab(t),
+        gte(column(t, "b", &accessor), const_bigint(1)),
+        col_expr(t, "ts", &accessor),
+        aliased_plan(column(t, "a", &accessor), "s"),
+        1,
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        varchar("c", ["e", "i", "g", "h"]),
+        bigint("s", [5, 2, 9, 13]),
+        bigint("ts", [1, 2, 7, 9]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_running_sum_of_an_expression_over_all_or_no_rows() {
+    let data = owned_table([bigint("ts", [10, 20, 30]), bigint("a", [1, 2, 3])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    for (where_value, expected) in [
+        (
+            0,
+            owned_table([bigint("ts", [10, 20, 30]), bigint("s", [11, 23, 36])]),
+        ),
+        (4, owned_table([bigint("ts", [0; 0]), bigint("s", [0; 0])])),
+    ] {
+        // SELECT ts, SUM(a + 10) OVER (ORDER BY ts) AS s FROM sxt.t WHERE a >= <where_value>
+        let expr = running_sum(
+            cols_expr_plan(t, &["ts"], &accessor),
+            tab(t),
+            gte(column(t, "a", &accessor), const_bigint(where_value)),
+            col_expr(t, "ts", &accessor),
+            aliased_plan(add(column(t, "a", &accessor), const_bigint(10)), "s"),
+            1,
+        );
+        let res = VerifiableQueryResult::new(&expr, &accessor, &());
+        exercise_verification(&res, &expr, &accessor, t);
+        let res = res.verify(&expr, &accessor, &()).unwrap().table;
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_cannot_prove_a_running_sum_over_keys_that_are_not_strictly_increasing() {
+    for keys in [[1, 3, 3, 4], [1, 3, 2, 4]] {
+        let data = owned_table([bigint("ts", keys), bigint("a", [1, 2, 3, 4])]);
+        let t = "sxt.t".parse().unwrap();
+        let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+        accessor.add_table(t, data, 0);
+        let expr = running_sum(
+            vec![],
+            tab(t),
+            gte(column(t, "a", &accessor), const_bigint(0)),
+            col_expr(t, "ts", &accessor),
+            aliased_plan(column(t, "a", &accessor), "s"),
+            0,
+        );
+        let res = VerifiableQueryResult::new(&expr, &accessor, &());
+        assert!(res.verify(&expr, &accessor, &()).is_err());
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_running_sum_against_another_ordering_column() {
+    let data = owned_table([
+        bigint("ts", [1, 2, 3, 4]),
+        bigint("u", [5, 6, 7, 8]),
+        bigint("a", [1, 2, 3, 4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let running_sum_expr = |order_by| {
+        running_sum(
+            vec![],
+            tab(t),
+            gte(column(t, "a", &accessor), const_bigint(2)),
+            col_expr(t, order_by, &accessor),
+            aliased_plan(column(t, "a", &accessor), "s"),
+            0,
+        )
+    };
+    let res = VerifiableQueryResult::new(&running_sum_expr("ts"), &accessor, &());
+    assert!(res.verify(&running_sum_expr("ts"), &accessor, &()).is_ok());
+    assert!(res.verify(&running_sum_expr("u"), &accessor, &()).is_err());
+}
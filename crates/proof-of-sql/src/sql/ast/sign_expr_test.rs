@@ -84,8 +84,18 @@ fn we_can_verify_a_constant_decomposition() {
     );
     let one_eval = sumcheck_evaluations.one_evaluation;
 
-    let mut builder: VerificationBuilder<RistrettoPoint> =
-        VerificationBuilder::new(0, sumcheck_evaluations, &dists, &[], &[], &[], Vec::new());
+    let mut builder: VerificationBuilder<RistrettoPoint> = VerificationBuilder::new(
+        0,
+        sumcheck_evaluations,
+        &dists,
+        &[],
+        &[],
+        &[],
+        Vec::new(),
+        &[],
+        &[],
+        None,
+    );
     let data_eval = (&data).evaluate_at_point(&evaluation_point);
     let eval = verifier_evaluate_sign(&mut builder, data_eval, one_eval).unwrap();
     assert_eq!(eval, Curve25519Scalar::zero());
@@ -109,8 +119,18 @@ fn verification_of_constant_data_fails_if_the_commitment_doesnt_match_the_bit_di
     );
     let one_eval = sumcheck_evaluations.one_evaluation;
 
-    let mut builder: VerificationBuilder<RistrettoPoint> =
-        VerificationBuilder::new(0, sumcheck_evaluations, &dists, &[], &[], &[], Vec::new());
+    let mut builder: VerificationBuilder<RistrettoPoint> = VerificationBuilder::new(
+        0,
+        sumcheck_evaluations,
+        &dists,
+        &[],
+        &[],
+        &[],
+        Vec::new(),
+        &[],
+        &[],
+        None,
+    );
     let data_eval = Curve25519Scalar::from(2) * (&data).evaluate_at_point(&evaluation_point);
     assert!(verifier_evaluate_sign(&mut builder, data_eval, one_eval).is_err());
 }
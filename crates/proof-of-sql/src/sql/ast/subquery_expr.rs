@@ -0,0 +1,933 @@
+use super::{
+    count_equals_zero, count_or, count_sign,
+    dense_filter_expr::{prove_filter, verify_filter},
+    filter_columns, fold_columns, fold_vals,
+    join_util::{group_rows_by_key, key_count_columns, matching_row_counts, JoinGroups},
+    prover_evaluate_equals_zero, prover_evaluate_or, prover_evaluate_sign,
+    result_evaluate_equals_zero, result_evaluate_sign,
+    union_util::ShiftedAccessor,
+    verifier_evaluate_equals_zero, verifier_evaluate_or, verifier_evaluate_sign, DenseFilterExpr,
+    ProvableExpr, ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            TableRef,
+        },
+        polynomial::{compute_truncated_lagrange_basis_sum, MultilinearExtension},
+        proof::ProofError,
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::proof::{
+        CountBuilder, FirstRoundBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate,
+        ResultBuilder, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use core::iter::{once, repeat_with};
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+/// The aggregation of a scalar subquery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubqueryAggregation {
+    /// `COUNT(*)`, which is never null
+    Count,
+    /// `SUM(<result_expr>)`, which is null when no row is selected
+    Sum,
+}
+
+/// The comparison of an expression of the outer query with the value of a scalar subquery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubqueryComparison {
+    /// `=`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    LessThan,
+    /// `<=`
+    LessThanOrEqual,
+    /// `>`
+    GreaterThan,
+    /// `>=`
+    GreaterThanOrEqual,
+}
+
+impl SubqueryComparison {
+    /// Whether the comparison needs the sign of the difference, rather than only whether it is zero.
+    fn is_ordering(self) -> bool {
+        !matches!(self, Self::Equal | Self::NotEqual)
+    }
+
+    /// The coefficients `(c_one, c_eq, c_le)` such that the comparison is `c_one + c_eq * eq + c_le * le`,
+    /// where `eq` and `le` say whether the difference is zero and whether it is not positive.
+    fn coefficients<S: Scalar>(self) -> (S, S, S) {
+        let (one, zero) = (S::one(), S::zero());
+        match self {
+            Self::Equal => (zero, one, zero),
+            Self::NotEqual => (one, -one, zero),
+            Self::LessThan => (zero, -one, one),
+            Self::LessThanOrEqual => (zero, zero, one),
+            Self::GreaterThan => (one, zero, -one),
+            Self::GreaterThanOrEqual => (one, one, -one),
+        }
+    }
+}
+
+/// A condition of the WHERE clause on the result of an uncorrelated subquery.
+///
+/// The subquery is a dense filter with a single result expression. `EXISTS` is expressed as `0 IN (SELECT 0 ...)`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum SubqueryCondition<C: Commitment> {
+    /// `<expr> [NOT] IN (SELECT <result_expr> FROM <table> WHERE <where_clause>)`
+    In {
+        /// The expression of the outer query that is looked up
+        expr: ProvableExprPlan<C>,
+        /// The subquery, whose result is looked up in
+        subquery: DenseFilterExpr<C>,
+        /// Whether the condition is `NOT IN`
+        negated: bool,
+    },
+    /// `<expr> <op> (SELECT <aggregation>(<result_expr>) FROM <table> WHERE <where_clause>)`
+    ///
+    /// The result expression of a `COUNT(*)` is the literal `1`.
+    Compare {
+        /// The expression of the outer query that is compared
+        expr: ProvableExprPlan<C>,
+        /// The comparison
+        op: SubqueryComparison,
+        /// The aggregation of the result expression of the subquery
+        aggregation: SubqueryAggregation,
+        /// The subquery, whose result is aggregated
+        subquery: DenseFilterExpr<C>,
+    },
+}
+
+impl<C: Commitment> SubqueryCondition<C> {
+    fn expr(&self) -> &ProvableExprPlan<C> {
+        match self {
+            Self::In { expr, .. } | Self::Compare { expr, .. } => expr,
+        }
+    }
+
+    fn subquery(&self) -> &DenseFilterExpr<C> {
+        match self {
+            Self::In { subquery, .. } | Self::Compare { subquery, .. } => subquery,
+        }
+    }
+
+    /// The result expression of the subquery.
+    fn subquery_expr(&self) -> &ProvableExprPlan<C> {
+        &self.subquery().aliased_results[0].expr
+    }
+}
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <result_expr1>, ..., <result_exprN> FROM <table>
+///     WHERE <where_clause> AND <condition1> AND ... AND <conditionM>
+/// ```
+/// where each condition is an uncorrelated subquery condition, i.e. `<expr> [NOT] IN (SELECT ...)`,
+/// `[NOT] EXISTS (SELECT ...)` or `<expr> <op> (SELECT COUNT(*) | SUM(...) ...)`.
+///
+/// As for a [`super::JoinExpr`], the proof is done over a domain that starts at the smallest offset of the tables,
+/// where each table is shifted by the difference between its offset and that one, and padded with zeros.
+///
+/// For an `IN` condition, the prover sends every distinct value of the outer expression and of the selected
+/// rows of the subquery, along with how many outer rows and selected subquery rows have each value. It also
+/// commits, before any challenges are drawn, to the number of matching subquery rows for every outer row.
+/// The proof then establishes, by lookups into the public values, that
+/// 1. the counts of selected subquery rows are correct, and
+/// 2. the number of matching rows of each outer row is the count of its value,
+///
+/// so that an outer row satisfies the condition exactly when that number is not zero.
+///
+/// For a comparison, the prover sends the value of the aggregation along with the number of selected rows of the
+/// subquery, which are proven by sums over the subquery's table. The comparison of each outer row is then proven
+/// like an [`super::InequalityExpr`], and is false when the aggregation is null.
+///
+/// The rows that satisfy the WHERE clause and every condition are then filtered as by a [`DenseFilterExpr`].
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SubqueryFilterExpr<C: Commitment> {
+    pub(super) filter: DenseFilterExpr<C>,
+    pub(super) conditions: Vec<SubqueryCondition<C>>,
+}
+
+impl<C: Commitment> SubqueryFilterExpr<C> {
+    /// Creates a new filter of the rows of a dense filter that is not sliced by subquery conditions.
+    pub fn new(filter: DenseFilterExpr<C>, conditions: Vec<SubqueryCondition<C>>) -> Self {
+        Self { filter, conditions }
+    }
+
+    /// The outer table, followed by the table of each subquery.
+    fn tables(&self) -> impl Iterator<Item = TableRef> + '_ {
+        once(self.filter.table.table_ref).chain(
+            self.conditions
+                .iter()
+                .map(|condition| condition.subquery().table.table_ref),
+        )
+    }
+
+    /// The shift of each table within the domain of the proof, in the order of [`Self::tables`].
+    fn get_shifts(&self, accessor: &(impl MetadataAccessor + ?Sized)) -> Vec<usize> {
+        let offsets = Vec::from_iter(
+            self.tables()
+                .map(|table_ref| accessor.get_offset(table_ref)),
+        );
+        let offset = offsets.iter().copied().min().unwrap_or(0);
+        offsets
+            .into_iter()
+            .map(|table_offset| table_offset - offset)
+            .collect()
+    }
+
+    /// Computes, for an `IN` condition, the number of matching selected subquery rows for each outer row,
+    /// lined up with the outer table within the domain of the proof, and the rows of each value.
+    fn membership_counts<'a>(
+        &self,
+        condition: &SubqueryCondition<C>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> (&'a [i64], JoinGroups<C::Scalar>) {
+        let outer_length = accessor.get_length(self.filter.table.table_ref);
+        let expr = condition
+            .expr()
+            .result_evaluate(outer_length, alloc, accessor)
+            .to_scalar_with_scaling(0);
+        let groups = group_rows_by_key(&expr, &subquery_values(condition, alloc, accessor));
+        let counts = matching_row_counts(
+            alloc,
+            groups
+                .values()
+                .map(|(rows, matching_rows)| (&rows[..], &matching_rows[..])),
+            self.get_shifts(accessor)[0],
+            outer_length,
+        );
+        (counts, groups)
+    }
+
+    /// Computes the rows of the outer table that satisfy every subquery condition.
+    fn conditions_result<'a>(
+        &self,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> &'a [bool] {
+        let outer_length = accessor.get_length(self.filter.table.table_ref);
+        let result = alloc.alloc_slice_fill_copy(outer_length, true);
+        for condition in self.conditions.iter() {
+            let expr = condition
+                .expr()
+                .result_evaluate(outer_length, alloc, accessor)
+                .to_scalar_with_scaling(0);
+            let values = subquery_values(condition, alloc, accessor);
+            match condition {
+                SubqueryCondition::In { negated, .. } => {
+                    let values = BTreeSet::from_iter(values);
+                    for (row, value) in result.iter_mut().zip(expr) {
+                        *row &= values.contains(&value) != *negated;
+                    }
+                }
+                SubqueryCondition::Compare {
+                    op, aggregation, ..
+                } => {
+                    if *aggregation == SubqueryAggregation::Sum && values.is_empty() {
+                        result.fill(false);
+                        continue;
+                    }
+                    let value = values.iter().copied().sum::<C::Scalar>();
+                    let diff: &'a [C::Scalar] =
+                        alloc.alloc_slice_fill_iter(expr.iter().map(|&x| x - value));
+                    let equals_zero = result_evaluate_equals_zero(outer_length, alloc, diff);
+                    let sign = result_evaluate_sign(outer_length, alloc, diff);
+                    let coefficients = op.coefficients::<C::Scalar>();
+                    for (i, row) in result.iter_mut().enumerate() {
+                        let le = equals_zero[i] || sign[i];
+                        *row &= satisfies(coefficients, equals_zero[i], le);
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Whether a row satisfies `c_one + c_eq * eq + c_le * le`, see [`SubqueryComparison::coefficients`].
+fn satisfies<S: Scalar>((c_one, c_eq, c_le): (S, S, S), eq: bool, le: bool) -> bool {
+    c_one + c_eq * S::from(eq) + c_le * S::from(le) != S::zero()
+}
+
+/// Computes the values of the result expression of the selected rows of the subquery of a condition.
+fn subquery_values<'a, C: Commitment>(
+    condition: &SubqueryCondition<C>,
+    alloc: &'a Bump,
+    accessor: &'a dyn DataAccessor<C::Scalar>,
+) -> Vec<C::Scalar> {
+    let subquery = condition.subquery();
+    let length = accessor.get_length(subquery.table.table_ref);
+    let selection_column: Column<'a, C::Scalar> = subquery
+        .where_clause
+        .result_evaluate(length, alloc, accessor);
+    let selection = selection_column
+        .as_boolean()
+        .expect("selection is not boolean");
+    condition
+        .subquery_expr()
+        .result_evaluate(length, alloc, accessor)
+        .to_scalar_with_scaling(0)
+        .into_iter()
+        .zip(selection)
+        .filter_map(|(value, &selected)| selected.then_some(value))
+        .collect()
+}
+
+/// Computes the public column of a comparison, i.e. the sum of the result expression of the
+/// selected rows of the subquery, followed by their number.
+fn aggregate_column<S: Scalar>(values: &[S]) -> Vec<S> {
+    vec![
+        values.iter().copied().sum::<S>(),
+        S::from(values.len() as i64),
+    ]
+}
+
+impl<C: Commitment> ProofExpr<C> for SubqueryFilterExpr<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        if self.filter.slice.is_some() {
+            return Err(ProofError::VerificationError("invalid subquery filter"));
+        }
+        self.filter.where_clause.count(builder)?;
+        for aliased_expr in self.filter.aliased_results.iter() {
+            aliased_expr.expr.count(builder)?;
+            builder.count_result_columns(1);
+        }
+        for condition in self.conditions.iter() {
+            let subquery = condition.subquery();
+            if subquery.slice.is_some() || subquery.aliased_results.len() != 1 {
+                return Err(ProofError::VerificationError("invalid subquery"));
+            }
+            condition.expr().count(builder)?;
+            subquery.where_clause.count(builder)?;
+            condition.subquery_expr().count(builder)?;
+        }
+        builder.count_intermediate_mles(1);
+        builder.count_subpolynomials(1);
+        for condition in self.conditions.iter() {
+            match condition {
+                SubqueryCondition::In { .. } => {
+                    builder.count_first_round_mles(1);
+                    builder.count_public_columns(3);
+                    builder.count_intermediate_mles(2);
+                    builder.count_subpolynomials(4);
+                    count_equals_zero(builder);
+                }
+                SubqueryCondition::Compare { op, .. } => {
+                    builder.count_public_columns(1);
+                    builder.count_subpolynomials(2);
+                    count_equals_zero(builder);
+                    if op.is_ordering() {
+                        count_sign(builder)?;
+                        count_or(builder);
+                    }
+                }
+            }
+            builder.count_intermediate_mles(1);
+            builder.count_subpolynomials(1);
+        }
+        // The outer rows are filtered as by a `DenseFilterExpr`.
+        builder.count_intermediate_mles(2);
+        builder.count_subpolynomials(3);
+        builder.count_degree(3);
+        builder.count_post_result_challenges(4);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.get_shifts(accessor)
+            .into_iter()
+            .zip(self.tables())
+            .map(|(shift, table_ref)| shift + accessor.get_length(table_ref))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.tables()
+            .map(|table_ref| accessor.get_offset(table_ref))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<(), ProofError> {
+        // 1. selection and columns of the outer query
+        let selection_eval = self
+            .filter
+            .where_clause
+            .verifier_evaluate(builder, accessor)?;
+        let columns_evals = self
+            .filter
+            .aliased_results
+            .iter()
+            .map(|aliased_expr| aliased_expr.expr.verifier_evaluate(builder, accessor))
+            .collect::<Result<Vec<_>, _>>()?;
+        // 2. expressions of the conditions
+        let mut conditions_evals = Vec::with_capacity(self.conditions.len());
+        for condition in self.conditions.iter() {
+            let expr_eval = condition.expr().verifier_evaluate(builder, accessor)?;
+            let subquery_selection_eval = condition
+                .subquery()
+                .where_clause
+                .verifier_evaluate(builder, accessor)?;
+            let subquery_expr_eval = condition
+                .subquery_expr()
+                .verifier_evaluate(builder, accessor)?;
+            conditions_evals.push((expr_eval, subquery_selection_eval, subquery_expr_eval));
+        }
+        // 3. matching row counts of the `IN` conditions
+        let matches_evals =
+            Vec::from_iter(self.conditions.iter().map(|condition| match condition {
+                SubqueryCondition::In { .. } => builder.consume_first_round_mle(),
+                SubqueryCondition::Compare { .. } => Zero::zero(),
+            }));
+        // 4. public columns
+        let mut public_columns = Vec::with_capacity(self.conditions.len());
+        for condition in self.conditions.iter() {
+            public_columns.push(match condition {
+                SubqueryCondition::In { .. } => vec![
+                    builder.consume_public_column(),
+                    builder.consume_public_column(),
+                    builder.consume_public_column(),
+                ],
+                SubqueryCondition::Compare { .. } => vec![builder.consume_public_column()],
+            });
+        }
+        // 5. filtered_columns
+        let filtered_columns_evals = Vec::from_iter(
+            repeat_with(|| builder.consume_result_mle()).take(self.filter.aliased_results.len()),
+        );
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        let gamma = builder.consume_post_result_challenge();
+        let delta = builder.consume_post_result_challenge();
+
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let rand_eval = builder.mle_evaluations.random_evaluation;
+        let point = builder.mle_evaluations.evaluation_point;
+        let e0_eval = compute_truncated_lagrange_basis_sum(1, point);
+        let in_table_evals = Vec::from_iter(
+            self.get_shifts(accessor)
+                .into_iter()
+                .zip(self.tables())
+                .map(|(shift, table_ref)| {
+                    let length = accessor.get_length(table_ref);
+                    compute_truncated_lagrange_basis_sum(shift + length, point)
+                        - compute_truncated_lagrange_basis_sum(shift, point)
+                }),
+        );
+
+        // kept - selection * in_table = 0
+        let mut kept_eval = builder.consume_intermediate_mle();
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &(rand_eval * (kept_eval - selection_eval * in_table_evals[0])),
+        );
+
+        for (
+            ((condition, (expr_eval, subquery_selection_eval, subquery_expr_eval)), m_eval),
+            (public_columns, &subquery_in_table_eval),
+        ) in self
+            .conditions
+            .iter()
+            .zip(conditions_evals)
+            .zip(matches_evals)
+            .zip(public_columns.into_iter().zip(in_table_evals[1..].iter()))
+        {
+            let (c_one, c_eq, c_le, eq_eval, le_eval) = match condition {
+                SubqueryCondition::In { negated, .. } => {
+                    let (values, counts, subquery_counts) =
+                        (public_columns[0], public_columns[1], public_columns[2]);
+                    let (value_count_sum, subquery_value_sum) =
+                        value_count_sums(values, counts, subquery_counts, gamma, delta)?;
+                    let k_star_eval = builder.consume_intermediate_mle();
+                    let i_star_eval = builder.consume_intermediate_mle();
+                    let k_fold_eval = delta * one_eval + fold_vals(gamma, &[expr_eval, m_eval]);
+                    let i_fold_eval = delta * one_eval + subquery_expr_eval;
+
+                    // k_fold * k_star - in_table = 0
+                    builder.produce_sumcheck_subpolynomial_evaluation(
+                        &(rand_eval * (k_fold_eval * k_star_eval - in_table_evals[0])),
+                    );
+                    // i_fold * i_star - subquery_selection * subquery_in_table = 0
+                    builder.produce_sumcheck_subpolynomial_evaluation(
+                        &(rand_eval
+                            * (i_fold_eval * i_star_eval
+                                - subquery_selection_eval * subquery_in_table_eval)),
+                    );
+                    // sum k_star - value_count_sum * e0 = 0
+                    builder.produce_sumcheck_subpolynomial_evaluation(
+                        &(k_star_eval - value_count_sum * e0_eval),
+                    );
+                    // sum i_star - subquery_value_sum * e0 = 0
+                    builder.produce_sumcheck_subpolynomial_evaluation(
+                        &(i_star_eval - subquery_value_sum * e0_eval),
+                    );
+
+                    // An outer row is in the subquery when it has matching rows.
+                    let eq_eval = verifier_evaluate_equals_zero(builder, m_eval);
+                    let (c_one, c_eq) = if *negated {
+                        (Zero::zero(), One::one())
+                    } else {
+                        (One::one(), -C::Scalar::one())
+                    };
+                    (c_one, c_eq, Zero::zero(), eq_eval, Zero::zero())
+                }
+                SubqueryCondition::Compare {
+                    op, aggregation, ..
+                } => {
+                    let &[value, count] = public_columns[0] else {
+                        return Err(ProofError::VerificationError(
+                            "invalid subquery aggregation",
+                        ));
+                    };
+                    // sum subquery_selection * subquery_in_table * subquery_expr - value * e0 = 0
+                    builder.produce_sumcheck_subpolynomial_evaluation(
+                        &(subquery_selection_eval * subquery_in_table_eval * subquery_expr_eval
+                            - value * e0_eval),
+                    );
+                    // sum subquery_selection * subquery_in_table - count * e0 = 0
+                    builder.produce_sumcheck_subpolynomial_evaluation(
+                        &(subquery_selection_eval * subquery_in_table_eval - count * e0_eval),
+                    );
+
+                    let diff_eval = expr_eval - value * one_eval;
+                    let eq_eval = verifier_evaluate_equals_zero(builder, diff_eval);
+                    let le_eval = if op.is_ordering() {
+                        let sign_eval = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
+                        verifier_evaluate_or(builder, &eq_eval, &sign_eval)
+                    } else {
+                        Zero::zero()
+                    };
+                    // A comparison with a null is never true.
+                    let (c_one, c_eq, c_le) =
+                        if *aggregation == SubqueryAggregation::Sum && count.is_zero() {
+                            (Zero::zero(), Zero::zero(), Zero::zero())
+                        } else {
+                            op.coefficients()
+                        };
+                    (c_one, c_eq, c_le, eq_eval, le_eval)
+                }
+            };
+
+            // next_kept - kept * (c_one + c_eq * eq + c_le * le) = 0
+            let next_kept_eval = builder.consume_intermediate_mle();
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &(rand_eval
+                    * (next_kept_eval - kept_eval * (c_one + c_eq * eq_eval + c_le * le_eval))),
+            );
+            kept_eval = next_kept_eval;
+        }
+
+        verify_filter(
+            builder,
+            alpha,
+            beta,
+            columns_evals,
+            kept_eval,
+            filtered_columns_evals,
+        )
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.filter.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> HashSet<ColumnRef> {
+        let mut columns = self.filter.get_column_references();
+        for condition in self.conditions.iter() {
+            condition.expr().get_column_references(&mut columns);
+            columns.extend(condition.subquery().get_column_references());
+        }
+        columns
+    }
+}
+
+/// Computes `sum(counts[i] / (delta + fold(values[i], subquery_counts[i])))` and
+/// `sum(subquery_counts[i] / (delta + values[i]))`, i.e. what the outer rows and the selected subquery rows
+/// sum to in the lookups of their values.
+fn value_count_sums<S: Scalar>(
+    values: &[S],
+    counts: &[S],
+    subquery_counts: &[S],
+    gamma: S,
+    delta: S,
+) -> Result<(S, S), ProofError> {
+    if values.len() != counts.len() || values.len() != subquery_counts.len() {
+        return Err(ProofError::VerificationError(
+            "subquery values and counts have different lengths",
+        ));
+    }
+    // The values must be sorted and distinct, so that each value has exactly one count.
+    if values.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(ProofError::VerificationError(
+            "subquery values are not strictly increasing",
+        ));
+    }
+    let degenerate = || ProofError::VerificationError("subquery challenge is degenerate");
+    let (mut value_count_sum, mut subquery_value_sum) = (S::zero(), S::zero());
+    for ((&value, &count), &subquery_count) in values.iter().zip(counts).zip(subquery_counts) {
+        value_count_sum += count
+            * (delta + fold_vals(gamma, &[value, subquery_count]))
+                .inv()
+                .ok_or_else(degenerate)?;
+        subquery_value_sum += subquery_count * (delta + value).inv().ok_or_else(degenerate)?;
+    }
+    Ok((value_count_sum, subquery_value_sum))
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for SubqueryFilterExpr<C> {
+    #[tracing::instrument(
+        name = "SubqueryFilterExpr::result_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn result_evaluate<'a>(
+        &self,
+        builder: &mut ResultBuilder<'a>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let outer_length = accessor.get_length(self.filter.table.table_ref);
+        let selection_column: Column<'a, C::Scalar> =
+            self.filter
+                .where_clause
+                .result_evaluate(outer_length, alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+        let conditions_result = self.conditions_result(alloc, accessor);
+        let kept =
+            alloc.alloc_slice_fill_with(outer_length, |i| selection[i] && conditions_result[i]);
+        let columns = Vec::from_iter(self.filter.aliased_results.iter().map(|aliased_expr| {
+            aliased_expr
+                .expr
+                .result_evaluate(outer_length, alloc, accessor)
+        }));
+        let (filtered_columns, result_len) = filter_columns(alloc, &columns, kept);
+        builder.set_result_indexes(Indexes::Dense(0..(result_len as u64)));
+        for col in filtered_columns {
+            builder.produce_result_column(col);
+        }
+        builder.request_post_result_challenges(4);
+    }
+
+    #[tracing::instrument(
+        name = "SubqueryFilterExpr::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        for condition in self.conditions.iter() {
+            match condition {
+                SubqueryCondition::In { .. } => {
+                    let (matches, groups) = self.membership_counts(condition, alloc, accessor);
+                    builder.produce_first_round_mle(matches);
+                    let (values, counts, subquery_counts) = key_count_columns(&groups);
+                    builder.produce_public_column(values);
+                    builder.produce_public_column(counts);
+                    builder.produce_public_column(subquery_counts);
+                }
+                SubqueryCondition::Compare { .. } => {
+                    builder.produce_public_column(aggregate_column(&subquery_values(
+                        condition, alloc, accessor,
+                    )));
+                }
+            }
+        }
+    }
+
+    #[tracing::instrument(
+        name = "SubqueryFilterExpr::prover_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let n = builder.table_length();
+        let shifts = self.get_shifts(accessor);
+        let in_tables = Vec::from_iter(shifts.iter().zip(self.tables()).map(
+            |(&shift, table_ref)| -> &'a [bool] {
+                let length = accessor.get_length(table_ref);
+                alloc.alloc_slice_fill_with(n, |i| shift <= i && i < shift + length)
+            },
+        ));
+        let outer_accessor: &'a ShiftedAccessor<'a, C::Scalar> =
+            alloc.alloc(ShiftedAccessor::new(alloc, accessor, shifts[0], n));
+        // 1. selection and columns of the outer query, lined up with its table within the domain of the proof
+        let selection_column: Column<'a, C::Scalar> =
+            self.filter
+                .where_clause
+                .prover_evaluate(builder, alloc, outer_accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+        let columns = Vec::from_iter(self.filter.aliased_results.iter().map(|aliased_expr| {
+            aliased_expr
+                .expr
+                .prover_evaluate(builder, alloc, outer_accessor)
+        }));
+        // 2. expressions of the conditions
+        let mut conditions_columns = Vec::with_capacity(self.conditions.len());
+        for (condition, &shift) in self.conditions.iter().zip(shifts[1..].iter()) {
+            let subquery_accessor: &'a ShiftedAccessor<'a, C::Scalar> =
+                alloc.alloc(ShiftedAccessor::new(alloc, accessor, shift, n));
+            let expr: &'a [C::Scalar] = alloc.alloc_slice_copy(
+                &condition
+                    .expr()
+                    .prover_evaluate(builder, alloc, outer_accessor)
+                    .to_scalar_with_scaling(0),
+            );
+            let subquery_selection_column: Column<'a, C::Scalar> = condition
+                .subquery()
+                .where_clause
+                .prover_evaluate(builder, alloc, subquery_accessor);
+            let subquery_selection = subquery_selection_column
+                .as_boolean()
+                .expect("selection is not boolean");
+            let subquery_expr: &'a [C::Scalar] = alloc.alloc_slice_copy(
+                &condition
+                    .subquery_expr()
+                    .prover_evaluate(builder, alloc, subquery_accessor)
+                    .to_scalar_with_scaling(0),
+            );
+            conditions_columns.push((expr, subquery_selection, subquery_expr));
+        }
+        // 3. matching row counts of the `IN` conditions
+        let matches = Vec::from_iter(self.conditions.iter().map(|condition| -> &'a [i64] {
+            match condition {
+                SubqueryCondition::In { .. } => {
+                    let (matches, _) = self.membership_counts(condition, alloc, accessor);
+                    builder.produce_first_round_mle(matches);
+                    matches
+                }
+                SubqueryCondition::Compare { .. } => &[],
+            }
+        }));
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        let gamma = builder.consume_post_result_challenge();
+        let delta = builder.consume_post_result_challenge();
+
+        let e0: &'a [bool] = alloc.alloc_slice_fill_copy(1, true);
+
+        // kept - selection * in_table = 0
+        let mut kept: &'a [bool] =
+            alloc.alloc_slice_fill_with(n, |i| selection[i] && in_tables[0][i]);
+        builder.produce_intermediate_mle(kept);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::one(), vec![Box::new(kept)]),
+                (
+                    -C::Scalar::one(),
+                    vec![Box::new(selection), Box::new(in_tables[0])],
+                ),
+            ],
+        );
+
+        for (((condition, (expr, subquery_selection, subquery_expr)), m), &subquery_in_table) in
+            self.conditions
+                .iter()
+                .zip(conditions_columns)
+                .zip(matches)
+                .zip(in_tables[1..].iter())
+        {
+            let subquery_kept: &'a [bool] =
+                alloc.alloc_slice_fill_with(n, |i| subquery_selection[i] && subquery_in_table[i]);
+            let (c_one, c_eq, c_le, eq, le): (_, _, _, &'a [bool], &'a [bool]) = match condition {
+                SubqueryCondition::In { negated, .. } => {
+                    let k_fold = alloc.alloc_slice_fill_copy(n, delta);
+                    fold_columns(k_fold, One::one(), gamma, &[expr]);
+                    m.mul_add(k_fold, &gamma);
+                    let i_fold = alloc.alloc_slice_fill_copy(n, delta);
+                    subquery_expr.mul_add(i_fold, &One::one());
+
+                    let k_star = alloc.alloc_slice_copy(k_fold);
+                    let i_star = alloc.alloc_slice_copy(i_fold);
+                    slice_ops::batch_inversion(k_star);
+                    slice_ops::batch_inversion(i_star);
+                    for (star, in_table) in
+                        [(&mut *k_star, in_tables[0]), (&mut *i_star, subquery_kept)]
+                    {
+                        for (value, &included) in star.iter_mut().zip(in_table) {
+                            if !included {
+                                *value = Zero::zero();
+                            }
+                        }
+                    }
+                    let (k_fold, i_fold, k_star, i_star): (&'a [_], &'a [_], &'a [_], &'a [_]) =
+                        (k_fold, i_fold, k_star, i_star);
+                    let value_count_sum = k_star.iter().copied().sum::<C::Scalar>();
+                    let subquery_value_sum = i_star.iter().copied().sum::<C::Scalar>();
+
+                    builder.produce_intermediate_mle(k_star);
+                    builder.produce_intermediate_mle(i_star);
+
+                    // k_fold * k_star - in_table = 0
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        vec![
+                            (C::Scalar::one(), vec![Box::new(k_star), Box::new(k_fold)]),
+                            (-C::Scalar::one(), vec![Box::new(in_tables[0])]),
+                        ],
+                    );
+                    // i_fold * i_star - subquery_selection * subquery_in_table = 0
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        vec![
+                            (C::Scalar::one(), vec![Box::new(i_star), Box::new(i_fold)]),
+                            (
+                                -C::Scalar::one(),
+                                vec![Box::new(subquery_selection), Box::new(subquery_in_table)],
+                            ),
+                        ],
+                    );
+                    // sum k_star - value_count_sum * e0 = 0
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::ZeroSum,
+                        vec![
+                            (C::Scalar::one(), vec![Box::new(k_star)]),
+                            (-value_count_sum, vec![Box::new(e0)]),
+                        ],
+                    );
+                    // sum i_star - subquery_value_sum * e0 = 0
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::ZeroSum,
+                        vec![
+                            (C::Scalar::one(), vec![Box::new(i_star)]),
+                            (-subquery_value_sum, vec![Box::new(e0)]),
+                        ],
+                    );
+
+                    // An outer row is in the subquery when it has matching rows.
+                    let m_scalars = alloc.alloc_slice_fill_copy(n, C::Scalar::zero());
+                    m.mul_add(m_scalars, &One::one());
+                    let m_scalars: &'a [_] = m_scalars;
+                    let eq = prover_evaluate_equals_zero(builder, alloc, m_scalars);
+                    let (c_one, c_eq) = if *negated {
+                        (Zero::zero(), One::one())
+                    } else {
+                        (One::one(), -C::Scalar::one())
+                    };
+                    (c_one, c_eq, Zero::zero(), eq, &[])
+                }
+                SubqueryCondition::Compare {
+                    op, aggregation, ..
+                } => {
+                    let count = subquery_kept.iter().filter(|&&kept| kept).count();
+                    let value = subquery_expr
+                        .iter()
+                        .zip(subquery_kept)
+                        .filter_map(|(&value, &kept)| kept.then_some(value))
+                        .sum::<C::Scalar>();
+                    // sum subquery_selection * subquery_in_table * subquery_expr - value * e0 = 0
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::ZeroSum,
+                        vec![
+                            (
+                                C::Scalar::one(),
+                                vec![
+                                    Box::new(subquery_selection),
+                                    Box::new(subquery_in_table),
+                                    Box::new(subquery_expr),
+                                ],
+                            ),
+                            (-value, vec![Box::new(e0)]),
+                        ],
+                    );
+                    // sum subquery_selection * subquery_in_table - count * e0 = 0
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::ZeroSum,
+                        vec![
+                            (
+                                C::Scalar::one(),
+                                vec![Box::new(subquery_selection), Box::new(subquery_in_table)],
+                            ),
+                            (-C::Scalar::from(count as i64), vec![Box::new(e0)]),
+                        ],
+                    );
+
+                    let diff: &'a [C::Scalar] = alloc.alloc_slice_fill_with(n, |i| expr[i] - value);
+                    let eq = prover_evaluate_equals_zero(builder, alloc, diff);
+                    let le: &'a [bool] = if op.is_ordering() {
+                        let sign = prover_evaluate_sign(builder, alloc, diff);
+                        prover_evaluate_or(builder, alloc, eq, sign)
+                    } else {
+                        &[]
+                    };
+                    // A comparison with a null is never true.
+                    let (c_one, c_eq, c_le) =
+                        if *aggregation == SubqueryAggregation::Sum && count == 0 {
+                            (Zero::zero(), Zero::zero(), Zero::zero())
+                        } else {
+                            op.coefficients()
+                        };
+                    (c_one, c_eq, c_le, eq, le)
+                }
+            };
+
+            // next_kept - kept * (c_one + c_eq * eq + c_le * le) = 0
+            let next_kept: &'a [bool] = alloc.alloc_slice_fill_with(n, |i| {
+                kept[i]
+                    && satisfies(
+                        (c_one, c_eq, c_le),
+                        eq[i],
+                        le.get(i).copied().unwrap_or(false),
+                    )
+            });
+            builder.produce_intermediate_mle(next_kept);
+            let mut terms: Vec<SumcheckSubpolynomialTerm<'a, C::Scalar>> = vec![
+                (C::Scalar::one(), vec![Box::new(next_kept)]),
+                (-c_one, vec![Box::new(kept)]),
+                (-c_eq, vec![Box::new(kept), Box::new(eq)]),
+            ];
+            if !le.is_empty() {
+                terms.push((-c_le, vec![Box::new(kept), Box::new(le)]));
+            }
+            builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, terms);
+            kept = next_kept;
+        }
+
+        let (filtered_columns, result_len) = filter_columns(alloc, &columns, kept);
+        prove_filter::<C::Scalar>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &columns,
+            kept,
+            &filtered_columns,
+            result_len,
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use super::{ProofPlan, SubqueryAggregation, SubqueryComparison};
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnRef, ColumnType, OwnedTable, TableRef,
+            TestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::test_utility::{
+            aliased_plan, cols_expr_plan, column, compare_subquery, const_bigint, const_bool,
+            const_varchar, dense_filter, equal, gte, in_subquery, subquery_filter, tab,
+            two_table_accessor,
+        },
+        proof::{
+            exercise_verification, Indexes, ProofExpr, ProvableQueryResult, ProvableResultColumn,
+            VerifiableQueryResult,
+        },
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use std::collections::HashSet;
+
+fn users() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("user_id", [1, 2, 3, 4, 5, 6]),
+        bigint("score", [10, 20, 30, 40, 50, 60]),
+    ])
+}
+
+fn allowed() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("id", [2, 4, 4, 7]),
+        varchar("tier", ["gold", "gold", "silver", "gold"]),
+    ])
+}
+
+/// `SELECT id FROM allowed WHERE tier = <tier>`
+fn allowed_ids(
+    u: TableRef,
+    tier: &str,
+    accessor: &impl TestAccessor<RistrettoPoint>,
+) -> ProofPlan<RistrettoPoint> {
+    dense_filter(
+        cols_expr_plan(u, &["id"], accessor),
+        tab(u),
+        equal(column(u, "tier", accessor), const_varchar(tier)),
+    )
+}
+
+#[test]
+fn we_can_correctly_fetch_the_result_fields_and_column_references() {
+    let (accessor, t, u) = two_table_accessor(users(), 0, allowed(), 0);
+    let expr = subquery_filter(
+        dense_filter(
+            cols_expr_plan(t, &["score"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![in_subquery(
+            column(t, "user_id", &accessor),
+            allowed_ids(u, "gold", &accessor),
+            false,
+        )],
+    );
+    assert_eq!(
+        expr.get_column_result_fields(),
+        vec![ColumnField::new(
+            "score".parse().unwrap(),
+            ColumnType::BigInt
+        )]
+    );
+    assert_eq!(
+        expr.get_column_references(),
+        HashSet::from([
+            ColumnRef::new(t, "score".parse().unwrap(), ColumnType::BigInt),
+            ColumnRef::new(t, "user_id".parse().unwrap(), ColumnType::BigInt),
+            ColumnRef::new(u, "id".parse().unwrap(), ColumnType::BigInt),
+            ColumnRef::new(u, "tier".parse().unwrap(), ColumnType::VarChar),
+        ])
+    );
+}
+
+#[test]
+fn we_can_prove_an_in_subquery_with_different_offsets() {
+    for (users_offset, allowed_offset) in [(0, 0), (0, 4), (5, 0), (7, 9)] {
+        let (accessor, t, u) = two_table_accessor(users(), users_offset, allowed(), allowed_offset);
+        for (negated, expected_ids, expected_scores) in [
+            (false, &[2, 4][..], &[20, 40][..]),
+            (true, &[1, 3, 5, 6][..], &[10, 30, 50, 60][..]),
+        ] {
+            let expr = subquery_filter(
+                dense_filter(
+                    cols_expr_plan(t, &["user_id", "score"], &accessor),
+                    tab(t),
+                    const_bool(true),
+                ),
+                vec![in_subquery(
+                    column(t, "user_id", &accessor),
+                    allowed_ids(u, "gold", &accessor),
+                    negated,
+                )],
+            );
+            let res = VerifiableQueryResult::new(&expr, &accessor, &());
+            exercise_verification(&res, &expr, &accessor, t);
+            exercise_verification(&res, &expr, &accessor, u);
+            let res = res.verify(&expr, &accessor, &()).unwrap().table;
+            let expected = owned_table([
+                bigint("user_id", expected_ids.to_vec()),
+                bigint("score", expected_scores.to_vec()),
+            ]);
+            assert_eq!(res, expected);
+        }
+    }
+}
+
+#[test]
+fn we_can_prove_an_exists_subquery() {
+    let (accessor, t, u) = two_table_accessor(users(), 0, allowed(), 2);
+    for (tier, negated, expected) in [
+        ("gold", false, &[1, 2, 3, 4, 5, 6][..]),
+        ("gold", true, &[][..]),
+        ("platinum", false, &[][..]),
+        ("platinum", true, &[1, 2, 3, 4, 5, 6][..]),
+    ] {
+        let expr = subquery_filter(
+            dense_filter(
+                cols_expr_plan(t, &["user_id"], &accessor),
+                tab(t),
+                const_bool(true),
+            ),
+            vec![in_subquery(
+                const_bigint(0),
+                dense_filter(
+                    vec![aliased_plan(const_bigint(0), "__exists__")],
+                    tab(u),
+                    equal(column(u, "tier", &accessor), const_varchar(tier)),
+                ),
+                negated,
+            )],
+        );
+        let res = VerifiableQueryResult::new(&expr, &accessor, &());
+        exercise_verification(&res, &expr, &accessor, t);
+        let res = res.verify(&expr, &accessor, &()).unwrap().table;
+        let expected = owned_table([bigint("user_id", expected.to_vec())]);
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_prove_comparisons_with_a_count_or_a_sum() {
+    let (accessor, t, u) = two_table_accessor(users(), 3, allowed(), 0);
+    let tests: [(SubqueryComparison, SubqueryAggregation, &str, &[i64]); 8] = [
+        // there are 3 gold rows
+        (
+            SubqueryComparison::GreaterThan,
+            SubqueryAggregation::Count,
+            "gold",
+            &[4, 5, 6],
+        ),
+        (
+            SubqueryComparison::LessThanOrEqual,
+            SubqueryAggregation::Count,
+            "gold",
+            &[1, 2, 3],
+        ),
+        (
+            SubqueryComparison::NotEqual,
+            SubqueryAggregation::Count,
+            "gold",
+            &[1, 2, 4, 5, 6],
+        ),
+        // the count of no rows is 0
+        (
+            SubqueryComparison::GreaterThanOrEqual,
+            SubqueryAggregation::Count,
+            "platinum",
+            &[1, 2, 3, 4, 5, 6],
+        ),
+        // the gold ids sum to 13 and the silver one to 4
+        (
+            SubqueryComparison::LessThan,
+            SubqueryAggregation::Sum,
+            "gold",
+            &[1, 2, 3, 4, 5, 6],
+        ),
+        (
+            SubqueryComparison::Equal,
+            SubqueryAggregation::Sum,
+            "silver",
+            &[4],
+        ),
+        // the sum of no rows is null, which no comparison is true for
+        (
+            SubqueryComparison::NotEqual,
+            SubqueryAggregation::Sum,
+            "platinum",
+            &[],
+        ),
+        (
+            SubqueryComparison::LessThanOrEqual,
+            SubqueryAggregation::Sum,
+            "platinum",
+            &[],
+        ),
+    ];
+    for (op, aggregation, tier, expected) in tests {
+        let result_expr = match aggregation {
+            SubqueryAggregation::Count => const_bigint(1),
+            SubqueryAggregation::Sum => column(u, "id", &accessor),
+        };
+        let expr = subquery_filter(
+            dense_filter(
+                cols_expr_plan(t, &["user_id"], &accessor),
+                tab(t),
+                const_bool(true),
+            ),
+            vec![compare_subquery(
+                column(t, "user_id", &accessor),
+                op,
+                aggregation,
+                dense_filter(
+                    vec![aliased_plan(result_expr, "value")],
+                    tab(u),
+                    equal(column(u, "tier", &accessor), const_varchar(tier)),
+                ),
+            )],
+        );
+        let res = VerifiableQueryResult::new(&expr, &accessor, &());
+        exercise_verification(&res, &expr, &accessor, t);
+        exercise_verification(&res, &expr, &accessor, u);
+        let res = res.verify(&expr, &accessor, &()).unwrap().table;
+        let expected = owned_table([bigint("user_id", expected.to_vec())]);
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_prove_several_conditions_along_with_a_where_clause() {
+    let (accessor, t, u) = two_table_accessor(users(), 1, allowed(), 0);
+    // SELECT score FROM users WHERE score >= 20 AND user_id IN (SELECT id FROM allowed WHERE tier = 'gold')
+    //     AND user_id NOT IN (SELECT id FROM allowed WHERE tier = 'silver')
+    let expr = subquery_filter(
+        dense_filter(
+            cols_expr_plan(t, &["score"], &accessor),
+            tab(t),
+            gte(column(t, "score", &accessor), const_bigint(20)),
+        ),
+        vec![
+            in_subquery(
+                column(t, "user_id", &accessor),
+                allowed_ids(u, "gold", &accessor),
+                false,
+            ),
+            in_subquery(
+                column(t, "user_id", &accessor),
+                allowed_ids(u, "silver", &accessor),
+                true,
+            ),
+        ],
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    exercise_verification(&res, &expr, &accessor, u);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("score", [20])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_an_in_subquery_with_an_empty_subquery_table() {
+    let empty = owned_table([bigint("id", [0; 0]), varchar("tier", [""; 0])]);
+    let (accessor, t, u) = two_table_accessor(users(), 0, empty, 0);
+    let expr = subquery_filter(
+        dense_filter(
+            cols_expr_plan(t, &["user_id"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![in_subquery(
+            column(t, "user_id", &accessor),
+            allowed_ids(u, "gold", &accessor),
+            true,
+        )],
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("user_id", [1, 2, 3, 4, 5, 6])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_verify_a_subquery_filter_result_that_is_missing_or_adding_rows() {
+    let (accessor, t, u) = two_table_accessor(users(), 0, allowed(), 3);
+    let expr = subquery_filter::<RistrettoPoint>(
+        dense_filter(
+            cols_expr_plan(t, &["user_id"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![in_subquery(
+            column(t, "user_id", &accessor),
+            allowed_ids(u, "gold", &accessor),
+            false,
+        )],
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    assert!(res.verify(&expr, &accessor, &()).is_ok());
+
+    let tampered_results: [&[i64]; 4] = [
+        // missing a row
+        &[2],
+        // a row that is not in the subquery
+        &[2, 3],
+        // an extra row
+        &[2, 4, 4],
+        // every row
+        &[1, 2, 3, 4, 5, 6],
+    ];
+    for user_id in tampered_results {
+        let mut res_p = res.clone();
+        let indexes = Indexes::Dense(0..(user_id.len() as u64));
+        let columns: [Box<dyn ProvableResultColumn>; 1] = [Box::new(user_id)];
+        res_p.provable_result = Some(ProvableQueryResult::new(&indexes, &columns));
+        assert!(res_p.verify(&expr, &accessor, &()).is_err());
+    }
+}
@@ -1,11 +1,43 @@
 use super::{
-    ColumnExpr, DenseFilterExpr, FilterExpr, FilterResultExpr, GroupByExpr, ProofPlan,
-    ProvableExprPlan, TableExpr,
+    AliasedProvableExprPlan, ColumnExpr, DenseFilterExpr, FilterExpr, FilterResultExpr,
+    GroupByExpr, JoinExpr, ProofPlan, ProvableExprPlan, RunningSumExpr, SubqueryAggregation,
+    SubqueryComparison, SubqueryCondition, SubqueryFilterExpr, TableExpr, TopKExpr, UnionExpr,
 };
 use crate::base::{
     commitment::Commitment,
     database::{ColumnField, ColumnRef, ColumnType, LiteralValue, SchemaAccessor, TableRef},
+    math::decimal::Precision,
 };
+#[cfg(feature = "blitzar")]
+use crate::base::{
+    commitment::InnerProductProof,
+    database::{OwnedTable, OwnedTableTestAccessor, TestAccessor},
+    scalar::Curve25519Scalar,
+};
+use proof_of_sql_parser::{
+    intermediate_ast::OrderByDirection,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+
+/// Returns an accessor with a left table `sxt.l` and a right table `sxt.r`, along with their references.
+#[cfg(feature = "blitzar")]
+pub fn two_table_accessor(
+    left: OwnedTable<Curve25519Scalar>,
+    left_offset: usize,
+    right: OwnedTable<Curve25519Scalar>,
+    right_offset: usize,
+) -> (
+    OwnedTableTestAccessor<InnerProductProof>,
+    TableRef,
+    TableRef,
+) {
+    let l = "sxt.l".parse().unwrap();
+    let r = "sxt.r".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(l, left, left_offset);
+    accessor.add_table(r, right, right_offset);
+    (accessor, l, r)
+}
 
 pub fn col_ref(tab: TableRef, name: &str, accessor: &impl SchemaAccessor) -> ColumnRef {
     let name = name.parse().unwrap();
@@ -44,6 +76,43 @@ pub fn gte<C: Commitment>(
     ProvableExprPlan::try_new_inequality(left, right, false).unwrap()
 }
 
+pub fn in_list<C: Commitment>(
+    expr: ProvableExprPlan<C>,
+    list: Vec<LiteralValue<C::Scalar>>,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_in_list(expr, list).unwrap()
+}
+
+pub fn between<C: Commitment>(
+    expr: ProvableExprPlan<C>,
+    low: ProvableExprPlan<C>,
+    high: ProvableExprPlan<C>,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_between(expr, low, high).unwrap()
+}
+
+pub fn case<C: Commitment>(
+    when_then: Vec<(ProvableExprPlan<C>, ProvableExprPlan<C>)>,
+    else_expr: ProvableExprPlan<C>,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_case(when_then, else_expr).unwrap()
+}
+
+pub fn cast<C: Commitment>(
+    expr: ProvableExprPlan<C>,
+    data_type: ColumnType,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_cast(expr, data_type).unwrap()
+}
+
+pub fn guarded_cast<C: Commitment>(
+    expr: ProvableExprPlan<C>,
+    data_type: ColumnType,
+    guard: ProvableExprPlan<C>,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_guarded_cast(expr, data_type, guard).unwrap()
+}
+
 pub fn not<C: Commitment>(expr: ProvableExprPlan<C>) -> ProvableExprPlan<C> {
     ProvableExprPlan::try_new_not(expr).unwrap()
 }
@@ -62,6 +131,27 @@ pub fn or<C: Commitment>(
     ProvableExprPlan::try_new_or(left, right).unwrap()
 }
 
+pub fn add<C: Commitment>(
+    left: ProvableExprPlan<C>,
+    right: ProvableExprPlan<C>,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_add(left, right).unwrap()
+}
+
+pub fn subtract<C: Commitment>(
+    left: ProvableExprPlan<C>,
+    right: ProvableExprPlan<C>,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_subtract(left, right).unwrap()
+}
+
+pub fn multiply<C: Commitment>(
+    left: ProvableExprPlan<C>,
+    right: ProvableExprPlan<C>,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_multiply(left, right).unwrap()
+}
+
 pub fn const_bool<C: Commitment>(val: bool) -> ProvableExprPlan<C> {
     ProvableExprPlan::new_literal(LiteralValue::Boolean(val))
 }
@@ -85,6 +175,30 @@ pub fn const_scalar<C: Commitment, T: Into<C::Scalar>>(val: T) -> ProvableExprPl
     ProvableExprPlan::new_literal(LiteralValue::Scalar(val.into()))
 }
 
+pub fn const_decimal75<C: Commitment, T: Into<C::Scalar>>(
+    precision: u8,
+    scale: i8,
+    val: T,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::new_literal(LiteralValue::Decimal75(
+        Precision::new(precision).unwrap(),
+        scale,
+        val.into(),
+    ))
+}
+
+pub fn const_timestamp<C: Commitment>(
+    unit: PoSQLTimeUnit,
+    timezone: PoSQLTimeZone,
+    val: i64,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::new_literal(LiteralValue::TimeStamp(unit, timezone, val))
+}
+
+pub fn const_date<C: Commitment>(val: i32) -> ProvableExprPlan<C> {
+    ProvableExprPlan::new_literal(LiteralValue::Date(val))
+}
+
 pub fn tab(tab: TableRef) -> TableExpr {
     TableExpr { table_ref: tab }
 }
@@ -131,23 +245,166 @@ pub fn cols_expr<C: Commitment>(
         .collect()
 }
 
+pub fn aliased_plan<C: Commitment>(
+    expr: ProvableExprPlan<C>,
+    alias: &str,
+) -> AliasedProvableExprPlan<C> {
+    AliasedProvableExprPlan {
+        expr,
+        alias: alias.parse().unwrap(),
+    }
+}
+
+pub fn aliased_col_expr_plan<C: Commitment>(
+    tab: TableRef,
+    old_name: &str,
+    new_name: &str,
+    accessor: &impl SchemaAccessor,
+) -> AliasedProvableExprPlan<C> {
+    aliased_plan(column(tab, old_name, accessor), new_name)
+}
+
+pub fn col_expr_plan<C: Commitment>(
+    tab: TableRef,
+    name: &str,
+    accessor: &impl SchemaAccessor,
+) -> AliasedProvableExprPlan<C> {
+    aliased_col_expr_plan(tab, name, name, accessor)
+}
+
+pub fn cols_expr_plan<C: Commitment>(
+    tab: TableRef,
+    names: &[&str],
+    accessor: &impl SchemaAccessor,
+) -> Vec<AliasedProvableExprPlan<C>> {
+    names
+        .iter()
+        .map(|name| col_expr_plan(tab, name, accessor))
+        .collect()
+}
+
 pub fn dense_filter<C: Commitment>(
-    results: Vec<ColumnExpr<C>>,
+    results: Vec<AliasedProvableExprPlan<C>>,
     table: TableExpr,
     where_clause: ProvableExprPlan<C>,
 ) -> ProofPlan<C> {
     ProofPlan::DenseFilter(DenseFilterExpr::new(results, table, where_clause))
 }
 
+pub fn sliced_dense_filter<C: Commitment>(
+    results: Vec<AliasedProvableExprPlan<C>>,
+    table: TableExpr,
+    where_clause: ProvableExprPlan<C>,
+    limit: u64,
+    offset: u64,
+) -> ProofPlan<C> {
+    ProofPlan::DenseFilter(
+        DenseFilterExpr::new(results, table, where_clause).with_slice(limit, offset),
+    )
+}
+
+pub fn top_k<C: Commitment>(
+    results: Vec<AliasedProvableExprPlan<C>>,
+    table: TableExpr,
+    where_clause: ProvableExprPlan<C>,
+    order_index: usize,
+    direction: OrderByDirection,
+    limit: u64,
+) -> ProofPlan<C> {
+    ProofPlan::TopK(TopKExpr::new(
+        results,
+        table,
+        where_clause,
+        order_index,
+        direction,
+        limit,
+    ))
+}
+
+pub fn running_sum<C: Commitment>(
+    results: Vec<AliasedProvableExprPlan<C>>,
+    table: TableExpr,
+    where_clause: ProvableExprPlan<C>,
+    order_by: ColumnExpr<C>,
+    sum_expr: AliasedProvableExprPlan<C>,
+    sum_index: usize,
+) -> ProofPlan<C> {
+    ProofPlan::RunningSum(RunningSumExpr::new(
+        results,
+        table,
+        where_clause,
+        order_by,
+        sum_expr,
+        sum_index,
+    ))
+}
+
+pub fn union<C: Commitment>(inputs: Vec<ProofPlan<C>>) -> ProofPlan<C> {
+    ProofPlan::Union(UnionExpr::new(
+        inputs
+            .into_iter()
+            .map(|input| {
+                let ProofPlan::DenseFilter(input) = input else {
+                    panic!("the inputs of a union must be dense filters")
+                };
+                input
+            })
+            .collect(),
+    ))
+}
+
+fn dense_filter_input<C: Commitment>(plan: ProofPlan<C>) -> DenseFilterExpr<C> {
+    let ProofPlan::DenseFilter(filter) = plan else {
+        panic!("subqueries and the filter of their conditions must be dense filters")
+    };
+    filter
+}
+
+pub fn subquery_filter<C: Commitment>(
+    filter: ProofPlan<C>,
+    conditions: Vec<SubqueryCondition<C>>,
+) -> ProofPlan<C> {
+    ProofPlan::SubqueryFilter(SubqueryFilterExpr::new(
+        dense_filter_input(filter),
+        conditions,
+    ))
+}
+
+pub fn in_subquery<C: Commitment>(
+    expr: ProvableExprPlan<C>,
+    subquery: ProofPlan<C>,
+    negated: bool,
+) -> SubqueryCondition<C> {
+    SubqueryCondition::In {
+        expr,
+        subquery: dense_filter_input(subquery),
+        negated,
+    }
+}
+
+pub fn compare_subquery<C: Commitment>(
+    expr: ProvableExprPlan<C>,
+    op: SubqueryComparison,
+    aggregation: SubqueryAggregation,
+    subquery: ProofPlan<C>,
+) -> SubqueryCondition<C> {
+    SubqueryCondition::Compare {
+        expr,
+        op,
+        aggregation,
+        subquery: dense_filter_input(subquery),
+    }
+}
+
 pub fn sum_expr<C: Commitment>(
     tab: TableRef,
     name: &str,
     alias: &str,
     column_type: ColumnType,
     accessor: &impl SchemaAccessor,
-) -> (ColumnExpr<C>, ColumnField) {
+) -> (ProvableExprPlan<C>, ColumnField) {
     (
-        col_expr(tab, name, accessor),
+        column(tab, name, accessor),
         ColumnField::new(alias.parse().unwrap(), column_type),
     )
 }
@@ -158,7 +415,7 @@ pub fn sums_expr<C: Commitment>(
     aliases: &[&str],
     column_types: &[ColumnType],
     accessor: &impl SchemaAccessor,
-) -> Vec<(ColumnExpr<C>, ColumnField)> {
+) -> Vec<(ProvableExprPlan<C>, ColumnField)> {
     names
         .iter()
         .zip(aliases.iter().zip(column_types.iter()))
@@ -166,9 +423,49 @@ pub fn sums_expr<C: Commitment>(
         .collect()
 }
 
+pub fn aliased_cols_expr<C: Commitment>(
+    tab: TableRef,
+    names: &[&str],
+    aliases: &[&str],
+    accessor: &impl SchemaAccessor,
+) -> Vec<(ColumnExpr<C>, ColumnField)> {
+    names
+        .iter()
+        .zip(aliases.iter())
+        .map(|(name, alias)| {
+            let column = col_expr(tab, name, accessor);
+            let field = ColumnField::new(
+                alias.parse().unwrap(),
+                *column.get_column_reference().column_type(),
+            );
+            (column, field)
+        })
+        .collect()
+}
+
 pub fn group_by<C: Commitment>(
-    group_by_exprs: Vec<ColumnExpr<C>>,
-    sum_expr: Vec<(ColumnExpr<C>, ColumnField)>,
+    group_by_exprs: Vec<AliasedProvableExprPlan<C>>,
+    sum_expr: Vec<(ProvableExprPlan<C>, ColumnField)>,
+    count_alias: &str,
+    table: TableExpr,
+    where_clause: ProvableExprPlan<C>,
+) -> ProofPlan<C> {
+    group_by_with_min_max(
+        group_by_exprs,
+        sum_expr,
+        vec![],
+        vec![],
+        count_alias,
+        table,
+        where_clause,
+    )
+}
+
+pub fn group_by_with_min_max<C: Commitment>(
+    group_by_exprs: Vec<AliasedProvableExprPlan<C>>,
+    sum_expr: Vec<(ProvableExprPlan<C>, ColumnField)>,
+    min_expr: Vec<(ColumnExpr<C>, ColumnField)>,
+    max_expr: Vec<(ColumnExpr<C>, ColumnField)>,
     count_alias: &str,
     table: TableExpr,
     where_clause: ProvableExprPlan<C>,
@@ -176,8 +473,28 @@ pub fn group_by<C: Commitment>(
     ProofPlan::GroupBy(GroupByExpr::new(
         group_by_exprs,
         sum_expr,
+        min_expr,
+        max_expr,
         count_alias.parse().unwrap(),
         table,
         where_clause,
     ))
 }
+
+pub fn join<C: Commitment>(
+    left_table: TableExpr,
+    right_table: TableExpr,
+    left_key: ColumnExpr<C>,
+    right_key: ColumnExpr<C>,
+    left_columns: Vec<ColumnExpr<C>>,
+    right_columns: Vec<ColumnExpr<C>>,
+) -> ProofPlan<C> {
+    ProofPlan::Join(Box::new(JoinExpr::new(
+        left_table,
+        right_table,
+        left_key,
+        right_key,
+        left_columns,
+        right_columns,
+    )))
+}
@@ -0,0 +1,484 @@
+use super::{
+    count_sign,
+    dense_filter_expr::{prove_filter, verify_filter},
+    filter_columns,
+    group_by_util::compare_indexes_by_columns,
+    provable_expr_plan::ProvableExprPlan,
+    prover_evaluate_sign, verifier_evaluate_sign, AliasedProvableExprPlan, ProvableExpr, TableExpr,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            OwnedColumn,
+        },
+        polynomial::compute_truncated_lagrange_basis_sum,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        CountBuilder, FirstRoundBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate,
+        ResultBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use core::{iter::repeat_with, slice};
+use num_traits::{One, Zero};
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
+///     ORDER BY <result_exprI> [ASC | DESC] LIMIT <limit>
+/// ```
+///
+/// Only the `limit` first selected rows in the order of `<result_exprI>` are sent, in the order of the table.
+/// Rows with equal keys are picked in the order of the table, so ordering the result is left to the post-processing step.
+/// The key must be an integer, a timestamp or a date and `limit` must be positive.
+///
+/// Before any challenges are drawn, the prover commits to which selected rows are `chosen`.
+/// The chosen rows are proven to be the result like the selected rows of a [`super::DenseFilterExpr`] are.
+/// The verifier reads the `bound`, i.e. the last key of the result in the requested order, in the clear and the proof
+/// then establishes that
+/// 1. only selected rows are chosen,
+/// 2. no selected row that is not chosen comes before the `bound`, using a sign decomposition, and
+/// 3. every selected row is chosen when the result has fewer than `limit` rows.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct TopKExpr<C: Commitment> {
+    pub(super) aliased_results: Vec<AliasedProvableExprPlan<C>>,
+    pub(super) table: TableExpr,
+    pub(super) where_clause: ProvableExprPlan<C>,
+    pub(super) order_index: usize,
+    pub(super) direction: OrderByDirection,
+    pub(super) limit: u64,
+}
+
+impl<C: Commitment> TopKExpr<C> {
+    /// Creates a new top_k expression, ordered by the result expression at `order_index`.
+    pub fn new(
+        aliased_results: Vec<AliasedProvableExprPlan<C>>,
+        table: TableExpr,
+        where_clause: ProvableExprPlan<C>,
+        order_index: usize,
+        direction: OrderByDirection,
+        limit: u64,
+    ) -> Self {
+        Self {
+            aliased_results,
+            table,
+            where_clause,
+            order_index,
+            direction,
+            limit,
+        }
+    }
+
+    /// Returns the selected rows that are part of the result.
+    fn chosen_rows<'a>(
+        &self,
+        alloc: &'a Bump,
+        keys: &Column<'a, C::Scalar>,
+        selection: &[bool],
+    ) -> &'a [bool] {
+        let mut rows = Vec::from_iter((0..selection.len()).filter(|&i| selection[i]));
+        rows.sort_by(|&i, &j| {
+            let ordering = compare_indexes_by_columns(slice::from_ref(keys), i, j);
+            match self.direction {
+                OrderByDirection::Asc => ordering,
+                OrderByDirection::Desc => ordering.reverse(),
+            }
+        });
+        let chosen = alloc.alloc_slice_fill_copy(selection.len(), false);
+        for i in rows.into_iter().take(self.limit as usize) {
+            chosen[i] = true;
+        }
+        chosen
+    }
+
+    /// Returns -1 when the query is ordered in ascending order and 1 otherwise,
+    /// so that `factor * (bound - key)` is never negative for the selected rows that are not chosen.
+    fn factor<S: Scalar>(&self) -> S {
+        match self.direction {
+            OrderByDirection::Asc => -S::one(),
+            OrderByDirection::Desc => S::one(),
+        }
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for TopKExpr<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        self.where_clause.count(builder)?;
+        for aliased_expr in self.aliased_results.iter() {
+            aliased_expr.expr.count(builder)?;
+            builder.count_result_columns(1);
+        }
+        builder.count_first_round_mles(1);
+        builder.count_intermediate_mles(3);
+        builder.count_subpolynomials(7);
+        builder.count_degree(3);
+        builder.count_post_result_challenges(2);
+        count_sign(builder)?;
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_length(self.table.table_ref)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_offset(self.table.table_ref)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<(), ProofError> {
+        // 1. selection
+        let selection_eval = self.where_clause.verifier_evaluate(builder, accessor)?;
+        // 2. columns
+        let columns_evals = self
+            .aliased_results
+            .iter()
+            .map(|aliased_expr| aliased_expr.expr.verifier_evaluate(builder, accessor))
+            .collect::<Result<Vec<_>, _>>()?;
+        let key_eval = *columns_evals
+            .get(self.order_index)
+            .ok_or(ProofError::VerificationError("invalid order by index"))?;
+        // 3. chosen rows
+        let chosen_eval = builder.consume_first_round_mle();
+        // 4. indexes
+        let indexes_eval = builder
+            .mle_evaluations
+            .result_indexes_evaluation
+            .ok_or(ProofError::VerificationError("invalid indexes"))?;
+        // The TopKExpr is the root node of the proof plan, so the result can be checked in the clear.
+        let result = builder.result_table().ok_or(ProofError::VerificationError(
+            "top k result could not be decoded",
+        ))?;
+        if indexes_eval
+            != compute_truncated_lagrange_basis_sum(
+                result.num_rows(),
+                builder.mle_evaluations.evaluation_point,
+            )
+        {
+            return Err(ProofError::VerificationError("top k result must be dense"));
+        }
+        if result.num_rows() as u64 > self.limit {
+            return Err(ProofError::VerificationError(
+                "top k result has more rows than the limit",
+            ));
+        }
+        let keys = result
+            .inner_table()
+            .values()
+            .nth(self.order_index)
+            .ok_or(ProofError::VerificationError("top k result has no keys"))?;
+        // An empty result is only valid when no row is selected, so any bound works.
+        let bound = match result_bound(keys, self.direction) {
+            Some(bound) => bound,
+            None if result.num_rows() == 0 => Zero::zero(),
+            None => {
+                return Err(ProofError::VerificationError(
+                    "top k result keys can not be ordered",
+                ))
+            }
+        };
+        let missing_rows = C::Scalar::from(i128::from(self.limit) - result.num_rows() as i128);
+        // 5. filtered_columns
+        let filtered_columns_evals = Vec::from_iter(
+            repeat_with(|| builder.consume_result_mle()).take(self.aliased_results.len()),
+        );
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        verify_filter(
+            builder,
+            alpha,
+            beta,
+            columns_evals,
+            chosen_eval,
+            filtered_columns_evals,
+        )?;
+        verify_top_k(
+            builder,
+            (selection_eval, chosen_eval, key_eval),
+            (self.factor(), bound, missing_rows),
+        )
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.aliased_results
+            .iter()
+            .map(|aliased_expr| ColumnField::new(aliased_expr.alias, aliased_expr.expr.data_type()))
+            .collect()
+    }
+
+    fn get_column_references(&self) -> HashSet<ColumnRef> {
+        let mut columns = HashSet::new();
+
+        for aliased_expr in self.aliased_results.iter() {
+            aliased_expr.expr.get_column_references(&mut columns);
+        }
+
+        self.where_clause.get_column_references(&mut columns);
+
+        columns
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for TopKExpr<C> {
+    #[tracing::instrument(name = "TopKExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        builder: &mut ResultBuilder<'a>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        // 1. selection
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause
+                .result_evaluate(builder.table_length(), alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+
+        // 2. columns
+        let columns = Vec::from_iter(self.aliased_results.iter().map(|aliased_expr| {
+            aliased_expr
+                .expr
+                .result_evaluate(builder.table_length(), alloc, accessor)
+        }));
+        // 3. chosen rows
+        let chosen = self.chosen_rows(alloc, &columns[self.order_index], selection);
+        // Compute filtered_columns and indexes
+        let (filtered_columns, result_len) = filter_columns(alloc, &columns, chosen);
+        // 4. set indexes
+        builder.set_result_indexes(Indexes::Dense(0..(result_len as u64)));
+        // 5. set filtered_columns
+        for col in filtered_columns {
+            builder.produce_result_column(col);
+        }
+        builder.request_post_result_challenges(2);
+    }
+
+    #[tracing::instrument(name = "TopKExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let table_length = accessor.get_length(self.table.table_ref);
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause
+                .result_evaluate(table_length, alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+        let keys = self.aliased_results[self.order_index].expr.result_evaluate(
+            table_length,
+            alloc,
+            accessor,
+        );
+        builder.produce_first_round_mle(self.chosen_rows(alloc, &keys, selection));
+    }
+
+    #[tracing::instrument(name = "TopKExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        // 1. selection
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause.prover_evaluate(builder, alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+
+        // 2. columns
+        let columns = Vec::from_iter(
+            self.aliased_results
+                .iter()
+                .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, accessor)),
+        );
+        let keys = &columns[self.order_index];
+        // 3. chosen rows
+        let chosen = self.chosen_rows(alloc, keys, selection);
+        builder.produce_first_round_mle(chosen);
+        // Compute filtered_columns and indexes
+        let (filtered_columns, result_len) = filter_columns(alloc, &columns, chosen);
+        let key_scalars = keys.to_scalar_with_scaling(0);
+        let bound = (0..chosen.len())
+            .filter(|&i| chosen[i])
+            .min_by(|&i, &j| {
+                let ordering = compare_indexes_by_columns(slice::from_ref(keys), i, j);
+                match self.direction {
+                    OrderByDirection::Asc => ordering.reverse(),
+                    OrderByDirection::Desc => ordering,
+                }
+            })
+            .map_or(Zero::zero(), |i| key_scalars[i]);
+        let missing_rows = C::Scalar::from(i128::from(self.limit) - result_len as i128);
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        prove_filter::<C::Scalar>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &columns,
+            chosen,
+            &filtered_columns,
+            result_len,
+        );
+        prove_top_k(
+            builder,
+            alloc,
+            (selection, chosen, alloc.alloc_slice_copy(&key_scalars)),
+            (self.factor(), bound, missing_rows),
+        );
+    }
+}
+
+/// Returns the last key of the result in the requested order, i.e. the largest key for `ASC` and the smallest one
+/// for `DESC`, or `None` if the result is empty or its keys are not of a type that can be ordered by a `TopKExpr`.
+fn result_bound<S: Scalar>(keys: &OwnedColumn<S>, direction: OrderByDirection) -> Option<S> {
+    fn last_index<T: Ord>(keys: &[T], direction: OrderByDirection) -> Option<usize> {
+        let indexes = 0..keys.len();
+        match direction {
+            OrderByDirection::Asc => indexes.max_by_key(|&i| &keys[i]),
+            OrderByDirection::Desc => indexes.min_by_key(|&i| &keys[i]),
+        }
+    }
+    let index = match keys {
+        OwnedColumn::SmallInt(keys) => last_index(keys, direction),
+        OwnedColumn::Int(keys) | OwnedColumn::Date(keys) => last_index(keys, direction),
+        OwnedColumn::BigInt(keys) | OwnedColumn::TimeStamp(_, _, keys) => {
+            last_index(keys, direction)
+        }
+        OwnedColumn::Int128(keys) => last_index(keys, direction),
+        _ => None,
+    };
+    keys.scalar_at(index?)
+}
+
+/// Verifies that the chosen rows are the first selected rows in the requested order.
+///
+/// See [`prove_top_k`].
+fn verify_top_k<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    (selection_eval, chosen_eval, key_eval): (C::Scalar, C::Scalar, C::Scalar),
+    (factor, bound, missing_rows): (C::Scalar, C::Scalar, C::Scalar),
+) -> Result<(), ProofError> {
+    let one_eval = builder.mle_evaluations.one_evaluation;
+    let rand_eval = builder.mle_evaluations.random_evaluation;
+
+    // chosen * chosen - chosen = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval * (chosen_eval * chosen_eval - chosen_eval)),
+    );
+
+    // chosen * selection - chosen = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval * (chosen_eval * selection_eval - chosen_eval)),
+    );
+
+    // diff - factor * (selection - chosen) * (bound - key) = 0
+    let diff_eval = builder.consume_intermediate_mle();
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(rand_eval * (diff_eval - factor * (selection_eval - chosen_eval) * (bound - key_eval))),
+    );
+
+    // sign(diff) == 0
+    let sign_eval = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
+    if sign_eval != Zero::zero() {
+        return Err(ProofError::VerificationError(
+            "a selected row that is not part of the result comes before the top k",
+        ));
+    }
+
+    // sum missing_rows * (selection - chosen) = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &(missing_rows * (selection_eval - chosen_eval)),
+    );
+
+    Ok(())
+}
+
+/// Proves that the chosen rows are the first selected rows in the requested order.
+///
+/// This proves that
+/// 1. `chosen` is a boolean column that is only true for selected rows,
+/// 2. `diff`, which is `factor * (selection - chosen) * (bound - key)`, is never negative, and
+/// 3. every selected row is chosen, unless the result has `limit` rows, i.e. `missing_rows` is zero.
+fn prove_top_k<'a, S: Scalar>(
+    builder: &mut ProofBuilder<'a, S>,
+    alloc: &'a Bump,
+    (selection, chosen, keys): (&'a [bool], &'a [bool], &'a [S]),
+    (factor, bound, missing_rows): (S, S, S),
+) {
+    // chosen * chosen - chosen = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(chosen), Box::new(chosen)]),
+            (-S::one(), vec![Box::new(chosen)]),
+        ],
+    );
+
+    // chosen * selection - chosen = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(chosen), Box::new(selection)]),
+            (-S::one(), vec![Box::new(chosen)]),
+        ],
+    );
+
+    // diff = factor * (selection - chosen) * (bound - key)
+    let diff = alloc.alloc_slice_fill_with(keys.len(), |i| {
+        if selection[i] && !chosen[i] {
+            factor * (bound - keys[i])
+        } else {
+            Zero::zero()
+        }
+    });
+    builder.produce_intermediate_mle(diff as &[_]);
+
+    // diff - factor * (selection - chosen) * (bound - key) = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(diff as &[_])]),
+            (-factor * bound, vec![Box::new(selection)]),
+            (factor, vec![Box::new(selection), Box::new(keys)]),
+            (factor * bound, vec![Box::new(chosen)]),
+            (-factor, vec![Box::new(chosen), Box::new(keys)]),
+        ],
+    );
+
+    // sign(diff) == 0
+    prover_evaluate_sign(builder, alloc, diff);
+
+    // sum missing_rows * (selection - chosen) = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (missing_rows, vec![Box::new(selection)]),
+            (-missing_rows, vec![Box::new(chosen)]),
+        ],
+    );
+}
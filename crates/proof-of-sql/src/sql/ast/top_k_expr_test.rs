@@ -0,0 +1,113 @@
+use super::test_utility::{
+    aliased_plan, cols_expr_plan, column, const_bigint, gte, multiply, tab, top_k,
+};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    },
+    sql::proof::{exercise_verification, VerifiableQueryResult},
+};
+use proof_of_sql_parser::intermediate_ast::OrderByDirection::{Asc, Desc};
+
+#[test]
+fn we_can_prove_a_top_k_query_in_descending_order() {
+    let data = owned_table([
+        bigint("a", [5, 9, 1, 7, 3, 8]),
+        bigint("b", [1, 1, 1, 0, 1, 1]),
+        varchar("c", ["e", "i", "a", "g", "c", "h"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // SELECT a, c FROM sxt.t WHERE b >= 1 ORDER BY a DESC LIMIT 3
+    let expr = top_k(
+        cols_expr_plan(t, &["a", "c"], &accessor),
+        tab(t),
+        gte(column(t, "b", &accessor), const_bigint(1)),
+        0,
+        Desc,
+        3,
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("a", [5, 9, 8]), varchar("c", ["e", "i", "h"])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_top_k_query_in_ascending_order_of_an_expression_with_ties() {
+    let data = owned_table([
+        bigint("a", [2, 1, 3, 1, 2, 1]),
+        bigint("b", [3, 5, 1, 4, 2, 6]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    // SELECT b, a * 10 AS k FROM sxt.t WHERE b >= 2 ORDER BY k ASC LIMIT 2
+    let expr = top_k(
+        vec![
+            aliased_plan(column(t, "b", &accessor), "b"),
+            aliased_plan(multiply(column(t, "a", &accessor), const_bigint(10)), "k"),
+        ],
+        tab(t),
+        gte(column(t, "b", &accessor), const_bigint(2)),
+        1,
+        Asc,
+        2,
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    // The three rows with a = 1 are tied, so the first two of them in the table are picked.
+    let expected = owned_table([bigint("b", [5, 4]), bigint("k", [10, 10])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_top_k_query_with_fewer_selected_rows_than_the_limit() {
+    let data = owned_table([bigint("a", [5, 9, 1, 7]), bigint("b", [1, 0, 1, 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    for (where_value, expected) in [
+        (1, owned_table([bigint("a", [5, 1])])),
+        (2, owned_table([bigint("a", [0; 0])])),
+    ] {
+        let expr = top_k(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            gte(column(t, "b", &accessor), const_bigint(where_value)),
+            0,
+            Desc,
+            3,
+        );
+        let res = VerifiableQueryResult::new(&expr, &accessor, &());
+        exercise_verification(&res, &expr, &accessor, t);
+        let res = res.verify(&expr, &accessor, &()).unwrap().table;
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_top_k_result_against_a_smaller_limit_or_another_order() {
+    let data = owned_table([bigint("a", [5, 9, 1, 7]), bigint("b", [1, 1, 1, 1])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let top_k_expr = |direction, limit| {
+        top_k(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            gte(column(t, "b", &accessor), const_bigint(1)),
+            0,
+            direction,
+            limit,
+        )
+    };
+    let expr = top_k_expr(Desc, 2);
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    assert!(res.verify(&top_k_expr(Desc, 1), &accessor, &()).is_err());
+    assert!(res.verify(&top_k_expr(Asc, 2), &accessor, &()).is_err());
+}
@@ -0,0 +1,381 @@
+use super::{
+    dense_filter_expr::{prove_filter_on_rows, verify_filter_on_rows},
+    filter_columns,
+    union_util::{concat_columns, ShiftedAccessor},
+    DenseFilterExpr, ProvableExpr,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+        },
+        polynomial::compute_truncated_lagrange_basis_sum,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        CountBuilder, FirstRoundBuilder, Indexes, ProofBuilder, ProofExpr, ProverEvaluate,
+        ResultBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use core::iter::repeat_with;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <result_expr1>, ..., <result_exprN> FROM <table1> WHERE <where_clause1>
+///     UNION ALL
+///     SELECT <result_expr1>, ..., <result_exprN> FROM <table2> WHERE <where_clause2>
+///     ...
+/// ```
+///
+/// The result is the concatenation of the results of the inputs, which are dense filters, in order.
+/// The result columns are named after those of the first input, and every input must have results of the same types.
+///
+/// The tables may have different lengths and live at different generator offsets. As for a [`super::JoinExpr`],
+/// the proof is done over a domain that starts at the smaller of the offsets, where each table is shifted by the
+/// difference between its offset and that one, and padded with zeros.
+///
+/// Along with the result, the prover sends the number of rows of the result that come from each input.
+/// For each input, the proof then establishes that
+/// 1. the `kept` rows are the selected rows that are within the table, rather than in its padding, and
+/// 2. the kept rows are the rows of the result that come from that input, like for a [`DenseFilterExpr`].
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnionExpr<C: Commitment> {
+    pub(super) inputs: Vec<DenseFilterExpr<C>>,
+}
+
+impl<C: Commitment> UnionExpr<C> {
+    /// Creates a new union expression of dense filters that are not sliced.
+    pub fn new(inputs: Vec<DenseFilterExpr<C>>) -> Self {
+        Self { inputs }
+    }
+
+    /// The shift of each table within the domain of the proof.
+    fn get_shifts(&self, accessor: &(impl MetadataAccessor + ?Sized)) -> Vec<usize> {
+        let offsets = Vec::from_iter(
+            self.inputs
+                .iter()
+                .map(|input| accessor.get_offset(input.table.table_ref)),
+        );
+        let offset = offsets.iter().copied().min().unwrap_or(0);
+        offsets
+            .into_iter()
+            .map(|input_offset| input_offset - offset)
+            .collect()
+    }
+
+    /// Computes the selected rows of each input, which are the rows of the result that come from it.
+    fn selections<'a>(
+        &self,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<&'a [bool]> {
+        self.inputs
+            .iter()
+            .map(|input| {
+                let table_length = accessor.get_length(input.table.table_ref);
+                let selection_column: Column<'a, C::Scalar> =
+                    input
+                        .where_clause
+                        .result_evaluate(table_length, alloc, accessor);
+                selection_column
+                    .as_boolean()
+                    .expect("selection is not boolean")
+            })
+            .collect()
+    }
+
+    /// Returns the number of result columns, which is the same for every input.
+    fn num_result_columns(&self) -> usize {
+        self.inputs
+            .first()
+            .map_or(0, |input| input.aliased_results.len())
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for UnionExpr<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        for input in self.inputs.iter() {
+            if input.slice.is_some() || input.aliased_results.len() != self.num_result_columns() {
+                return Err(ProofError::VerificationError("invalid union input"));
+            }
+            input.where_clause.count(builder)?;
+            for aliased_expr in input.aliased_results.iter() {
+                aliased_expr.expr.count(builder)?;
+            }
+        }
+        builder.count_result_columns(self.num_result_columns());
+        builder.count_public_columns(1);
+        builder.count_intermediate_mles(3 * self.inputs.len());
+        builder.count_subpolynomials(4 * self.inputs.len());
+        builder.count_degree(3);
+        builder.count_post_result_challenges(2);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.get_shifts(accessor)
+            .into_iter()
+            .zip(self.inputs.iter())
+            .map(|(shift, input)| shift + accessor.get_length(input.table.table_ref))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.inputs
+            .iter()
+            .map(|input| accessor.get_offset(input.table.table_ref))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn get_proof_length(&self, accessor: &dyn MetadataAccessor, result_length: usize) -> usize {
+        core::cmp::max(self.get_length(accessor), result_length)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<(), ProofError> {
+        // 1. selections and columns
+        let mut inputs_evals = Vec::with_capacity(self.inputs.len());
+        for input in self.inputs.iter() {
+            let selection_eval = input.where_clause.verifier_evaluate(builder, accessor)?;
+            let columns_evals = input
+                .aliased_results
+                .iter()
+                .map(|aliased_expr| aliased_expr.expr.verifier_evaluate(builder, accessor))
+                .collect::<Result<Vec<_>, _>>()?;
+            inputs_evals.push((selection_eval, columns_evals));
+        }
+        // 2. row counts
+        let row_counts = builder
+            .consume_public_column()
+            .iter()
+            .map(|&count| {
+                TryInto::<i64>::try_into(count)
+                    .ok()
+                    .and_then(|count| usize::try_from(count).ok())
+                    .ok_or(ProofError::VerificationError("invalid union row count"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if row_counts.len() != self.inputs.len() {
+            return Err(ProofError::VerificationError("invalid union row counts"));
+        }
+        // 3. filtered_columns
+        let filtered_columns_evals = Vec::from_iter(
+            repeat_with(|| builder.consume_result_mle()).take(self.num_result_columns()),
+        );
+        // The UnionExpr is the root node of the proof plan, so the result can be checked in the clear.
+        let result = builder.result_table().ok_or(ProofError::VerificationError(
+            "union result could not be decoded",
+        ))?;
+        let point = builder.mle_evaluations.evaluation_point;
+        if builder.mle_evaluations.result_indexes_evaluation
+            != Some(compute_truncated_lagrange_basis_sum(
+                result.num_rows(),
+                point,
+            ))
+        {
+            return Err(ProofError::VerificationError("union result must be dense"));
+        }
+        if row_counts.iter().sum::<usize>() != result.num_rows() {
+            return Err(ProofError::VerificationError(
+                "union row counts do not add up to the result",
+            ));
+        }
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        let rand_eval = builder.mle_evaluations.random_evaluation;
+        let mut first_result_row = 0;
+        for (((input, (selection_eval, columns_evals)), shift), row_count) in self
+            .inputs
+            .iter()
+            .zip(inputs_evals)
+            .zip(self.get_shifts(accessor))
+            .zip(row_counts)
+        {
+            let table_length = accessor.get_length(input.table.table_ref);
+            let in_table_eval = compute_truncated_lagrange_basis_sum(shift + table_length, point)
+                - compute_truncated_lagrange_basis_sum(shift, point);
+            let chi_eval =
+                compute_truncated_lagrange_basis_sum(first_result_row + row_count, point)
+                    - compute_truncated_lagrange_basis_sum(first_result_row, point);
+            first_result_row += row_count;
+
+            // kept - selection * in_table = 0
+            let kept_eval = builder.consume_intermediate_mle();
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &(rand_eval * (kept_eval - selection_eval * in_table_eval)),
+            );
+
+            verify_filter_on_rows(
+                builder,
+                alpha,
+                beta,
+                columns_evals,
+                kept_eval,
+                filtered_columns_evals.clone(),
+                chi_eval,
+            );
+        }
+        Ok(())
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.inputs
+            .first()
+            .map(|input| input.get_column_result_fields())
+            .unwrap_or_default()
+    }
+
+    fn get_column_references(&self) -> HashSet<ColumnRef> {
+        self.inputs
+            .iter()
+            .flat_map(|input| input.get_column_references())
+            .collect()
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for UnionExpr<C> {
+    #[tracing::instrument(name = "UnionExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        builder: &mut ResultBuilder<'a>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let mut inputs_filtered_columns = vec![Vec::new(); self.num_result_columns()];
+        let mut result_len = 0;
+        for (input, selection) in self.inputs.iter().zip(self.selections(alloc, accessor)) {
+            let table_length = accessor.get_length(input.table.table_ref);
+            let columns = Vec::from_iter(input.aliased_results.iter().map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .result_evaluate(table_length, alloc, accessor)
+            }));
+            let (filtered_columns, input_result_len) = filter_columns(alloc, &columns, selection);
+            for (input_filtered_columns, col) in
+                inputs_filtered_columns.iter_mut().zip(filtered_columns)
+            {
+                input_filtered_columns.push(col);
+            }
+            result_len += input_result_len;
+        }
+        builder.set_result_indexes(Indexes::Dense(0..(result_len as u64)));
+        for input_filtered_columns in inputs_filtered_columns {
+            builder.produce_result_column(concat_columns(alloc, &input_filtered_columns));
+        }
+        builder.request_post_result_challenges(2);
+    }
+
+    #[tracing::instrument(name = "UnionExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let row_counts = self
+            .selections(alloc, accessor)
+            .into_iter()
+            .map(|selection| C::Scalar::from(selection.iter().filter(|&&row| row).count() as i64))
+            .collect();
+        builder.produce_public_column(row_counts);
+    }
+
+    #[tracing::instrument(name = "UnionExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let n = builder.table_length();
+        // 1. selections and columns, lined up with their tables within the domain of the proof
+        let mut inputs = Vec::with_capacity(self.inputs.len());
+        for (input, shift) in self.inputs.iter().zip(self.get_shifts(accessor)) {
+            let shifted_accessor: &'a ShiftedAccessor<'a, C::Scalar> =
+                alloc.alloc(ShiftedAccessor::new(alloc, accessor, shift, n));
+            let selection_column: Column<'a, C::Scalar> =
+                input
+                    .where_clause
+                    .prover_evaluate(builder, alloc, shifted_accessor);
+            let selection = selection_column
+                .as_boolean()
+                .expect("selection is not boolean");
+            let columns = Vec::from_iter(input.aliased_results.iter().map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .prover_evaluate(builder, alloc, shifted_accessor)
+            }));
+            let table_length = accessor.get_length(input.table.table_ref);
+            let in_table: &'a [bool] =
+                alloc.alloc_slice_fill_with(n, |i| shift <= i && i < shift + table_length);
+            let kept: &'a [bool] = alloc.alloc_slice_fill_with(n, |i| selection[i] && in_table[i]);
+            inputs.push((selection, columns, in_table, kept));
+        }
+        // Compute filtered_columns
+        let mut inputs_filtered_columns = vec![Vec::new(); self.num_result_columns()];
+        let mut row_counts = Vec::with_capacity(inputs.len());
+        for (_, columns, _, kept) in inputs.iter() {
+            let (filtered_columns, row_count) = filter_columns(alloc, columns, kept);
+            for (input_filtered_columns, col) in
+                inputs_filtered_columns.iter_mut().zip(filtered_columns)
+            {
+                input_filtered_columns.push(col);
+            }
+            row_counts.push(row_count);
+        }
+        let filtered_columns = Vec::from_iter(
+            inputs_filtered_columns
+                .iter()
+                .map(|input_filtered_columns| concat_columns(alloc, input_filtered_columns)),
+        );
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        let mut first_result_row = 0;
+        for ((selection, columns, in_table, kept), row_count) in inputs.into_iter().zip(row_counts)
+        {
+            // kept - selection * in_table = 0
+            builder.produce_intermediate_mle(kept);
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (C::Scalar::one(), vec![Box::new(kept)]),
+                    (
+                        -C::Scalar::one(),
+                        vec![Box::new(selection), Box::new(in_table)],
+                    ),
+                ],
+            );
+
+            prove_filter_on_rows::<C::Scalar>(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                &columns,
+                kept,
+                &filtered_columns,
+                first_result_row..first_result_row + row_count,
+            );
+            first_result_row += row_count;
+        }
+    }
+}
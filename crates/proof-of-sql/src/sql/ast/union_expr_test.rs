@@ -0,0 +1,199 @@
+use crate::{
+    base::{
+        database::{owned_table_utility::*, ColumnField, ColumnRef, ColumnType, OwnedTable},
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::test_utility::{
+            aliased_plan, cols_expr_plan, column, const_bigint, const_varchar, dense_filter, equal,
+            lte, tab, two_table_accessor, union,
+        },
+        proof::{
+            exercise_verification, Indexes, ProofExpr, ProvableQueryResult, ProvableResultColumn,
+            VerifiableQueryResult,
+        },
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use std::collections::HashSet;
+
+fn sales() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("amount", [10, 0, 30, 40, 0, 60]),
+        varchar("region", ["n", "s", "n", "e", "w", "s"]),
+    ])
+}
+
+fn returns() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("refund", [5, 0, 15]),
+        varchar("area", ["s", "n", "n"]),
+    ])
+}
+
+#[test]
+fn we_can_correctly_fetch_the_result_fields_and_column_references() {
+    let (accessor, t, u) = two_table_accessor(sales(), 0, returns(), 0);
+    let expr = union::<RistrettoPoint>(vec![
+        dense_filter(
+            cols_expr_plan(t, &["amount", "region"], &accessor),
+            tab(t),
+            equal(column(t, "region", &accessor), const_varchar("n")),
+        ),
+        dense_filter(
+            cols_expr_plan(u, &["refund", "area"], &accessor),
+            tab(u),
+            lte(column(u, "refund", &accessor), const_bigint(10)),
+        ),
+    ]);
+    assert_eq!(
+        expr.get_column_result_fields(),
+        vec![
+            ColumnField::new("amount".parse().unwrap(), ColumnType::BigInt),
+            ColumnField::new("region".parse().unwrap(), ColumnType::VarChar),
+        ]
+    );
+    assert_eq!(
+        expr.get_column_references(),
+        HashSet::from([
+            ColumnRef::new(t, "amount".parse().unwrap(), ColumnType::BigInt),
+            ColumnRef::new(t, "region".parse().unwrap(), ColumnType::VarChar),
+            ColumnRef::new(u, "refund".parse().unwrap(), ColumnType::BigInt),
+            ColumnRef::new(u, "area".parse().unwrap(), ColumnType::VarChar),
+        ])
+    );
+}
+
+#[test]
+fn we_can_prove_a_union_of_tables_with_different_lengths_and_offsets() {
+    for (first_offset, second_offset) in [(0, 0), (0, 4), (5, 0), (7, 9)] {
+        let (accessor, t, u) = two_table_accessor(sales(), first_offset, returns(), second_offset);
+        let expr = union(vec![
+            dense_filter(
+                cols_expr_plan(t, &["amount", "region"], &accessor),
+                tab(t),
+                equal(column(t, "region", &accessor), const_varchar("n")),
+            ),
+            dense_filter(
+                cols_expr_plan(u, &["refund", "area"], &accessor),
+                tab(u),
+                lte(column(u, "refund", &accessor), const_bigint(10)),
+            ),
+        ]);
+        let res = VerifiableQueryResult::new(&expr, &accessor, &());
+        exercise_verification(&res, &expr, &accessor, t);
+        exercise_verification(&res, &expr, &accessor, u);
+        let res = res.verify(&expr, &accessor, &()).unwrap().table;
+        let expected = owned_table([
+            bigint("amount", [10, 30, 5, 0]),
+            varchar("region", ["n", "n", "s", "n"]),
+        ]);
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_do_not_select_the_padding_of_a_shorter_table() {
+    // The shorter table is padded with zeros within the domain of the proof, which satisfy the where clause.
+    let (accessor, t, u) = two_table_accessor(sales(), 0, returns(), 2);
+    let expr = union(vec![
+        dense_filter(
+            vec![aliased_plan(column(t, "amount", &accessor), "value")],
+            tab(t),
+            equal(column(t, "amount", &accessor), const_bigint(0)),
+        ),
+        dense_filter(
+            vec![aliased_plan(column(u, "refund", &accessor), "value")],
+            tab(u),
+            equal(column(u, "refund", &accessor), const_bigint(0)),
+        ),
+    ]);
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, u);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("value", [0, 0, 0])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_union_whose_result_is_longer_than_both_tables() {
+    let (accessor, t, u) = two_table_accessor(sales(), 0, returns(), 1);
+    let expr = union(vec![
+        dense_filter(
+            cols_expr_plan(t, &["amount"], &accessor),
+            tab(t),
+            lte(column(t, "amount", &accessor), const_bigint(100)),
+        ),
+        dense_filter(
+            cols_expr_plan(u, &["refund"], &accessor),
+            tab(u),
+            lte(column(u, "refund", &accessor), const_bigint(100)),
+        ),
+    ]);
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("amount", [10, 0, 30, 40, 0, 60, 5, 0, 15])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_union_with_an_empty_table() {
+    let empty = owned_table([bigint("refund", [0; 0]), varchar("area", [""; 0])]);
+    let (accessor, t, u) = two_table_accessor(sales(), 0, empty, 0);
+    let expr = union(vec![
+        dense_filter(
+            cols_expr_plan(u, &["refund"], &accessor),
+            tab(u),
+            lte(column(u, "refund", &accessor), const_bigint(10)),
+        ),
+        dense_filter(
+            cols_expr_plan(t, &["amount"], &accessor),
+            tab(t),
+            equal(column(t, "region", &accessor), const_varchar("s")),
+        ),
+    ]);
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("refund", [0, 60])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_verify_a_union_result_that_is_missing_reordering_or_adding_rows() {
+    let (accessor, t, u) = two_table_accessor(sales(), 0, returns(), 3);
+    let expr = union::<RistrettoPoint>(vec![
+        dense_filter(
+            cols_expr_plan(t, &["amount"], &accessor),
+            tab(t),
+            equal(column(t, "region", &accessor), const_varchar("s")),
+        ),
+        dense_filter(
+            cols_expr_plan(u, &["refund"], &accessor),
+            tab(u),
+            equal(column(u, "area", &accessor), const_varchar("n")),
+        ),
+    ]);
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    assert!(res.verify(&expr, &accessor, &()).is_ok());
+
+    let tampered_results: [&[i64]; 4] = [
+        // missing a row
+        &[0, 60, 0],
+        // rows of the inputs in another order
+        &[0, 15, 0, 60],
+        // rows of an input swapped with each other
+        &[60, 0, 0, 15],
+        // an extra row
+        &[0, 60, 0, 15, 0],
+    ];
+    for amount in tampered_results {
+        let mut res_p = res.clone();
+        let indexes = Indexes::Dense(0..(amount.len() as u64));
+        let columns: [Box<dyn ProvableResultColumn>; 1] = [Box::new(amount)];
+        res_p.provable_result = Some(ProvableQueryResult::new(&indexes, &columns));
+        assert!(res_p.verify(&expr, &accessor, &()).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Contains the utility functions for the `UnionExpr` node.
+
+use crate::base::{
+    database::{Column, ColumnRef, DataAccessor, MetadataAccessor, TableRef},
+    scalar::Scalar,
+};
+use bumpalo::Bump;
+use num_traits::Zero;
+
+/// This function places the values of a column after `shift` default values and pads them
+/// with default values up to `length`, so that the column lines up with its table within the domain of the proof.
+pub fn shift_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: &Column<'a, S>,
+    shift: usize,
+    length: usize,
+) -> Column<'a, S> {
+    fn shift_values<'a, T: Copy>(
+        alloc: &'a Bump,
+        values: &[T],
+        shift: usize,
+        length: usize,
+        default: T,
+    ) -> &'a [T] {
+        let res = alloc.alloc_slice_fill_copy(length, default);
+        res[shift..shift + values.len()].copy_from_slice(values);
+        res
+    }
+    match column {
+        Column::Boolean(col) => Column::Boolean(shift_values(alloc, col, shift, length, false)),
+        Column::SmallInt(col) => Column::SmallInt(shift_values(alloc, col, shift, length, 0)),
+        Column::Int(col) => Column::Int(shift_values(alloc, col, shift, length, 0)),
+        Column::BigInt(col) => Column::BigInt(shift_values(alloc, col, shift, length, 0)),
+        Column::Int128(col) => Column::Int128(shift_values(alloc, col, shift, length, 0)),
+        Column::VarChar((col, scals)) => Column::VarChar((
+            shift_values(alloc, col, shift, length, ""),
+            shift_values(alloc, scals, shift, length, S::zero()),
+        )),
+        Column::Scalar(col) => Column::Scalar(shift_values(alloc, col, shift, length, S::zero())),
+        Column::Decimal75(precision, scale, col) => Column::Decimal75(
+            *precision,
+            *scale,
+            shift_values(alloc, col, shift, length, S::zero()),
+        ),
+        Column::TimeStamp(unit, timezone, col) => {
+            Column::TimeStamp(*unit, *timezone, shift_values(alloc, col, shift, length, 0))
+        }
+        Column::Date(col) => Column::Date(shift_values(alloc, col, shift, length, 0)),
+    }
+}
+
+/// This function concatenates columns of the same type.
+///
+/// # Panics
+/// Panics if `columns` is empty or if the columns are not all of the type of the first one.
+pub fn concat_columns<'a, S: Scalar>(alloc: &'a Bump, columns: &[Column<'a, S>]) -> Column<'a, S> {
+    fn concat_values<'a, T: Copy + 'a>(
+        alloc: &'a Bump,
+        values: impl Iterator<Item = &'a [T]>,
+    ) -> &'a [T] {
+        alloc.alloc_slice_copy(&values.flatten().copied().collect::<Vec<_>>())
+    }
+    fn mismatch() -> ! {
+        panic!("columns of different types can not be concatenated")
+    }
+    macro_rules! concat_variant {
+        ($variant:ident) => {
+            Column::$variant(concat_values(
+                alloc,
+                columns.iter().map(|column| match column {
+                    Column::$variant(col) => *col,
+                    _ => mismatch(),
+                }),
+            ))
+        };
+    }
+    match columns
+        .first()
+        .expect("there must be columns to concatenate")
+    {
+        Column::Boolean(_) => concat_variant!(Boolean),
+        Column::SmallInt(_) => concat_variant!(SmallInt),
+        Column::Int(_) => concat_variant!(Int),
+        Column::BigInt(_) => concat_variant!(BigInt),
+        Column::Int128(_) => concat_variant!(Int128),
+        Column::Scalar(_) => concat_variant!(Scalar),
+        Column::Date(_) => concat_variant!(Date),
+        Column::VarChar(_) => {
+            let (cols, scals): (Vec<_>, Vec<_>) = columns
+                .iter()
+                .map(|column| match column {
+                    Column::VarChar((col, scals)) => (*col, *scals),
+                    _ => mismatch(),
+                })
+                .unzip();
+            Column::VarChar((
+                concat_values(alloc, cols.into_iter()),
+                concat_values(alloc, scals.into_iter()),
+            ))
+        }
+        Column::Decimal75(precision, scale, _) => Column::Decimal75(
+            *precision,
+            *scale,
+            concat_values(
+                alloc,
+                columns.iter().map(|column| match column {
+                    Column::Decimal75(p, s, col) if p == precision && s == scale => *col,
+                    _ => mismatch(),
+                }),
+            ),
+        ),
+        Column::TimeStamp(unit, timezone, _) => Column::TimeStamp(
+            *unit,
+            *timezone,
+            concat_values(
+                alloc,
+                columns.iter().map(|column| match column {
+                    Column::TimeStamp(u, tz, col) if u == unit && tz == timezone => *col,
+                    _ => mismatch(),
+                }),
+            ),
+        ),
+    }
+}
+
+/// A data accessor whose columns are shifted and padded by [`shift_column`],
+/// so that a table can be evaluated within the domain of a proof over several tables.
+pub struct ShiftedAccessor<'a, S: Scalar> {
+    alloc: &'a Bump,
+    accessor: &'a dyn DataAccessor<S>,
+    shift: usize,
+    length: usize,
+}
+
+impl<'a, S: Scalar> ShiftedAccessor<'a, S> {
+    /// Creates an accessor whose columns start after `shift` rows and have `length` rows.
+    pub fn new(
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<S>,
+        shift: usize,
+        length: usize,
+    ) -> Self {
+        Self {
+            alloc,
+            accessor,
+            shift,
+            length,
+        }
+    }
+}
+
+impl<S: Scalar> MetadataAccessor for ShiftedAccessor<'_, S> {
+    fn get_length(&self, _table_ref: TableRef) -> usize {
+        self.length
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.accessor.get_offset(table_ref) - self.shift
+    }
+}
+
+impl<S: Scalar> DataAccessor<S> for ShiftedAccessor<'_, S> {
+    fn get_column(&self, column: ColumnRef) -> Column<S> {
+        shift_column(
+            self.alloc,
+            &self.accessor.get_column(column),
+            self.shift,
+            self.length,
+        )
+    }
+}
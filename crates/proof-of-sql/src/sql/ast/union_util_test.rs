@@ -0,0 +1,44 @@
+use super::union_util::{concat_columns, shift_column};
+use crate::base::{database::Column, scalar::Curve25519Scalar};
+use bumpalo::Bump;
+
+#[test]
+fn we_can_shift_and_pad_columns() {
+    let alloc = Bump::new();
+    let column: Column<Curve25519Scalar> = Column::BigInt(&[1, 2, 3]);
+    assert_eq!(
+        shift_column(&alloc, &column, 2, 6),
+        Column::BigInt(&[0, 0, 1, 2, 3, 0])
+    );
+    assert_eq!(shift_column(&alloc, &column, 0, 3), column);
+    let scalars = [Curve25519Scalar::from("a"), Curve25519Scalar::from("b")];
+    let column = Column::VarChar((&["a", "b"], &scalars));
+    assert_eq!(
+        shift_column(&alloc, &column, 1, 4),
+        Column::VarChar((
+            &["", "a", "b", ""],
+            &[
+                Curve25519Scalar::from(0),
+                scalars[0],
+                scalars[1],
+                Curve25519Scalar::from(0)
+            ]
+        ))
+    );
+}
+
+#[test]
+fn we_can_concatenate_columns() {
+    let alloc = Bump::new();
+    let columns: [Column<Curve25519Scalar>; 3] =
+        [Column::Int(&[1, 2]), Column::Int(&[]), Column::Int(&[3])];
+    assert_eq!(concat_columns(&alloc, &columns), Column::Int(&[1, 2, 3]));
+}
+
+#[test]
+#[should_panic]
+fn we_cannot_concatenate_columns_of_different_types() {
+    let alloc = Bump::new();
+    let columns: [Column<Curve25519Scalar>; 2] = [Column::Int(&[1, 2]), Column::BigInt(&[3])];
+    concat_columns(&alloc, &columns);
+}
@@ -9,10 +9,18 @@ pub enum ConversionError {
     /// The column is missing in the table
     MissingColumn(Box<Identifier>, Box<ResourceId>),
 
+    #[error("Column '{0}' is ambiguous because it exists in more than one table")]
+    /// The column exists in more than one of the referenced tables
+    AmbiguousColumn(Box<Identifier>),
+
     #[error("Column '{0}' was not found")]
     /// The column is missing (without table information)
     MissingColumnWithoutTable(Box<Identifier>),
 
+    #[error("Table '{0}' is not in the FROM clause")]
+    /// A column is qualified by a table, e.g. `t.a`, that is not selected from
+    MissingTable(String),
+
     #[error("Expected '{expected}' but found '{actual}'")]
     /// Invalid data type received
     InvalidDataType {
@@ -22,6 +30,15 @@ pub enum ConversionError {
         actual: ColumnType,
     },
 
+    #[error("Cannot cast '{from}' to '{to}'")]
+    /// The cast is not supported between the two data types
+    InvalidCast {
+        /// Data type of the expression
+        from: ColumnType,
+        /// Data type it is cast to
+        to: ColumnType,
+    },
+
     #[error("Left side has '{1}' type but right side has '{0}' type")]
     /// Data types do not match
     DataTypeMismatch(String, String),
@@ -2,17 +2,22 @@ use super::{where_expr_builder::WhereExprBuilder, ConversionError};
 use crate::{
     base::{
         commitment::Commitment,
-        database::{ColumnRef, LiteralValue, TableRef},
+        database::{validity_column_id, ColumnRef, LiteralValue, TableRef},
     },
-    sql::ast::{ColumnExpr, DenseFilterExpr, ProvableExprPlan, TableExpr},
+    sql::ast::{
+        AliasedProvableExprPlan, ColumnExpr, DenseFilterExpr, ProvableExprPlan, TableExpr, TopKExpr,
+    },
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{Expression, OrderByDirection},
+    Identifier,
 };
-use proof_of_sql_parser::{intermediate_ast::Expression, Identifier};
 use std::collections::{HashMap, HashSet};
 
 pub struct FilterExprBuilder<C: Commitment> {
     table_expr: Option<TableExpr>,
     where_expr: Option<ProvableExprPlan<C>>,
-    filter_result_expr_list: Vec<ColumnExpr<C>>,
+    filter_result_expr_list: Vec<AliasedProvableExprPlan<C>>,
     column_mapping: HashMap<Identifier, ColumnRef>,
 }
 
@@ -46,13 +51,50 @@ impl<C: Commitment> FilterExprBuilder<C> {
         columns.sort();
 
         columns.into_iter().for_each(|column| {
-            let column = *self.column_mapping.get(&column).unwrap();
-            self.filter_result_expr_list.push(ColumnExpr::new(column));
+            let column_ref = *self.column_mapping.get(&column).unwrap();
+            self.filter_result_expr_list.push(AliasedProvableExprPlan {
+                expr: ProvableExprPlan::Column(ColumnExpr::new(column_ref)),
+                alias: column,
+            });
+            // The validity column of a nullable column is returned along with it.
+            let validity_column = validity_column_id(column)
+                .ok()
+                .and_then(|validity_id| self.column_mapping.get(&validity_id));
+            if let Some(validity_column_ref) = validity_column {
+                self.filter_result_expr_list.push(AliasedProvableExprPlan {
+                    expr: ProvableExprPlan::Column(ColumnExpr::new(*validity_column_ref)),
+                    alias: validity_column_ref.column_id(),
+                });
+            }
         });
 
         self
     }
 
+    pub fn add_result_columns(mut self, aliased_results: Vec<AliasedProvableExprPlan<C>>) -> Self {
+        self.filter_result_expr_list.extend(aliased_results);
+        self
+    }
+
+    /// Builds a `TopKExpr` that only returns the `limit` first selected rows
+    /// in the order of the result column at `order_index`.
+    pub fn build_top_k(
+        self,
+        order_index: usize,
+        direction: OrderByDirection,
+        limit: u64,
+    ) -> TopKExpr<C> {
+        TopKExpr::new(
+            self.filter_result_expr_list,
+            self.table_expr.expect("Table expr is required"),
+            self.where_expr
+                .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true))),
+            order_index,
+            direction,
+            limit,
+        )
+    }
+
     pub fn build(self) -> DenseFilterExpr<C> {
         DenseFilterExpr::new(
             self.filter_result_expr_list,
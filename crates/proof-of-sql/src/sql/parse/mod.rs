@@ -17,7 +17,10 @@ mod filter_expr_builder;
 pub(crate) use filter_expr_builder::FilterExprBuilder;
 
 pub(crate) mod query_context;
-pub(crate) use query_context::QueryContext;
+pub(crate) use query_context::{
+    average_count_column_id, window_column_id, CteContext, CteSchemaAccessor, Ctes, JoinContext,
+    ProvenCte, QueryContext, SubqueryContext,
+};
 
 mod query_context_builder;
 pub(crate) use query_context_builder::{type_check_binary_operation, QueryContextBuilder};
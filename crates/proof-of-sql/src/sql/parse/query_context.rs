@@ -1,18 +1,151 @@
 use crate::{
     base::{
         commitment::Commitment,
-        database::{ColumnField, ColumnRef, ColumnType, LiteralValue, TableRef},
+        database::{
+            validity_column_id, ColumnField, ColumnRef, ColumnType, LiteralValue, SchemaAccessor,
+            TableRef,
+        },
     },
     sql::{
-        ast::{ColumnExpr, GroupByExpr, ProvableExprPlan, TableExpr},
+        ast::{
+            AliasedProvableExprPlan, ColumnExpr, GroupByExpr, JoinExpr, ProvableExpr,
+            ProvableExprPlan, RunningSumExpr, SubqueryComparison, TableExpr,
+        },
         parse::{ConversionError, ConversionResult, WhereExprBuilder},
     },
 };
 use proof_of_sql_parser::{
-    intermediate_ast::{AggregationOperator, AliasedResultExpr, Expression, OrderBy, Slice},
-    Identifier,
+    intermediate_ast::{
+        AggregationOperator, AliasedResultExpr, Expression, OrderBy, OrderByDirection,
+        SetExpression, Slice, WindowFunction,
+    },
+    Identifier, ParseError, ResourceId,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    iter::once,
 };
-use std::collections::{HashMap, HashSet};
+
+/// The right table of a join and the columns that the two tables are joined on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JoinContext {
+    pub right_table: TableRef,
+    pub left_key: ColumnRef,
+    pub right_key: ColumnRef,
+}
+
+/// A condition of the `WHERE` clause on the result of an uncorrelated subquery.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubqueryContext {
+    /// `<expr> [NOT] IN (<subquery>)`
+    In {
+        expr: Expression,
+        subquery: SetExpression,
+        negated: bool,
+    },
+    /// `[NOT] EXISTS (<subquery>)`
+    Exists {
+        subquery: SetExpression,
+        negated: bool,
+    },
+    /// `<expr> <op> (<subquery>)`, where the subquery is a single aggregation
+    Compare {
+        expr: Expression,
+        op: SubqueryComparison,
+        subquery: SetExpression,
+    },
+}
+
+/// A common table expression, which is selected from as if it were a table.
+///
+/// A filter of a single table has its columns and condition as expressions over that table,
+/// which are substituted into the queries that select from it. Any other common table expression
+/// is proven on its own, see [`ProvenCte`], and is a filter without condition of the table of its result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CteContext {
+    /// The name of the common table expression, qualified by the default schema
+    pub name: ResourceId,
+    pub table_ref: TableRef,
+    /// Each column of the common table expression with the expression over the table that it is
+    pub columns: Vec<(Identifier, Expression)>,
+    pub where_expr: Option<Box<Expression>>,
+}
+
+impl CteContext {
+    /// Returns the expression over the table that a column of the common table expression is.
+    pub fn get_column_expr(&self, column: Identifier) -> ConversionResult<&Expression> {
+        self.columns
+            .iter()
+            .find(|(name, _)| *name == column)
+            .map(|(_, expr)| expr)
+            .ok_or_else(|| ConversionError::MissingColumn(Box::new(column), Box::new(self.name)))
+    }
+}
+
+/// The common table expressions of a `WITH` clause that are in the scope of a query.
+#[derive(Debug, Clone, Default)]
+pub struct Ctes {
+    /// Each common table expression by name
+    pub contexts: HashMap<Identifier, CteContext>,
+    /// The common table expressions that are proven on their own, by the table of their result
+    pub proven: HashMap<TableRef, ProvenCte>,
+}
+
+/// A common table expression with aggregations, `GROUP BY`, `DISTINCT`, `JOIN`, `UNION`, window functions
+/// or subqueries, which is proven by a plan of its own.
+///
+/// The queries that select from it are computed by the post-processing step over its proven result,
+/// which they look up as a table of the given schema.
+#[derive(Debug, Clone)]
+pub struct ProvenCte {
+    pub query: SetExpression,
+    /// The common table expressions in the scope of `query`
+    pub ctes: Ctes,
+    pub schema: Vec<(Identifier, ColumnType)>,
+}
+
+/// A `SchemaAccessor` that looks up the results of the proven common table expressions
+/// as tables, and every other table with the given `SchemaAccessor`.
+pub struct CteSchemaAccessor<'a> {
+    schema_accessor: &'a dyn SchemaAccessor,
+    proven: &'a HashMap<TableRef, ProvenCte>,
+}
+
+impl<'a> CteSchemaAccessor<'a> {
+    pub fn new(schema_accessor: &'a dyn SchemaAccessor, ctes: &'a Ctes) -> Self {
+        Self {
+            schema_accessor,
+            proven: &ctes.proven,
+        }
+    }
+}
+
+impl SchemaAccessor for CteSchemaAccessor<'_> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        match self.proven.get(&table_ref) {
+            Some(proven_cte) => proven_cte
+                .schema
+                .iter()
+                .find(|(column, _)| *column == column_id)
+                .map(|(_, column_type)| *column_type),
+            None => self.schema_accessor.lookup_column(table_ref, column_id),
+        }
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        match self.proven.get(&table_ref) {
+            Some(proven_cte) => proven_cte.schema.clone(),
+            None => self.schema_accessor.lookup_schema(table_ref),
+        }
+    }
+
+    fn is_strictly_increasing(&self, table_ref: TableRef, column_id: Identifier) -> bool {
+        !self.proven.contains_key(&table_ref)
+            && self
+                .schema_accessor
+                .is_strictly_increasing(table_ref, column_id)
+    }
+}
 
 #[derive(Default, Debug)]
 pub struct QueryContext {
@@ -21,14 +154,19 @@ pub struct QueryContext {
     slice_expr: Option<Slice>,
     col_ref_counter: usize,
     table: Option<TableRef>,
+    join: Option<JoinContext>,
+    subqueries: Vec<SubqueryContext>,
     in_result_scope: bool,
     has_visited_group_by: bool,
     order_by_exprs: Vec<OrderBy>,
     fixed_col_ref_counter: usize,
-    group_by_exprs: Vec<Identifier>,
+    group_by_exprs: Vec<Expression>,
     where_expr: Option<Box<Expression>>,
     result_column_set: HashSet<Identifier>,
     res_aliased_exprs: Vec<AliasedResultExpr>,
+    averages: Vec<(Identifier, ColumnType)>,
+    having_expr: Option<Box<Expression>>,
+    num_having_result_exprs: usize,
     column_mapping: HashMap<Identifier, ColumnRef>,
     first_result_col_out_agg_scope: Option<Identifier>,
 }
@@ -45,6 +183,25 @@ impl QueryContext {
             .expect("Table should already have been set")
     }
 
+    pub fn set_join(&mut self, join: JoinContext) {
+        assert!(self.join.is_none());
+        self.join = Some(join);
+    }
+
+    pub fn get_join(&self) -> Option<&JoinContext> {
+        self.join.as_ref()
+    }
+
+    /// Adds a subquery condition, which is joined to the `WHERE` clause by `AND`.
+    pub fn push_subquery(&mut self, subquery: SubqueryContext) {
+        self.subqueries.push(subquery);
+    }
+
+    /// Returns the subquery conditions of the `WHERE` clause.
+    pub fn get_subqueries(&self) -> &[SubqueryContext] {
+        &self.subqueries
+    }
+
     pub fn set_where_expr(&mut self, where_expr: Option<Box<Expression>>) {
         self.where_expr = where_expr;
     }
@@ -101,6 +258,31 @@ impl QueryContext {
         self.column_mapping.insert(column, column_ref);
     }
 
+    /// Returns the column that a column name of the query has been resolved to, if any.
+    pub fn get_column_ref(&self, column: &Identifier) -> Option<&ColumnRef> {
+        self.column_mapping.get(column)
+    }
+
+    /// Registers the validity column of a nullable column. It is not a result column by itself.
+    pub fn push_validity_column_ref(&mut self, column: Identifier, column_ref: ColumnRef) {
+        self.column_mapping.insert(column, column_ref);
+    }
+
+    /// Registers the order key column of a `VarChar` column. It is not a result column by itself.
+    pub fn push_order_key_column_ref(&mut self, column: Identifier, column_ref: ColumnRef) {
+        self.column_mapping.insert(column, column_ref);
+    }
+
+    /// Returns the validity column of the given column, if it is nullable.
+    fn get_validity_column_ref(&self, column: &Identifier) -> Option<ColumnRef> {
+        let validity_id = validity_column_id(*column).ok()?;
+        self.column_mapping.get(&validity_id).copied()
+    }
+
+    pub fn is_nullable(&self, column: &Identifier) -> bool {
+        self.get_validity_column_ref(column).is_some()
+    }
+
     fn push_result_column_ref(&mut self, column: Identifier) {
         if self.is_in_result_scope() {
             self.result_column_set.insert(column);
@@ -134,15 +316,37 @@ impl QueryContext {
         Ok(())
     }
 
-    pub fn set_group_by_exprs(&mut self, exprs: Vec<Identifier>) {
-        self.group_by_exprs = exprs;
+    /// Records that the result expression with the given alias is an average of the given type.
+    pub fn push_average(&mut self, alias: Identifier, column_type: ColumnType) {
+        self.averages.push((alias, column_type));
+    }
 
-        // Add the group by columns to the result column set
-        // to ensure their integrity in the filter expression.
-        for group_column in &self.group_by_exprs {
-            self.result_column_set.insert(*group_column);
-        }
+    /// Returns the aliases and types of the averages of the result.
+    pub fn get_averages(&self) -> &[(Identifier, ColumnType)] {
+        &self.averages
+    }
+
+    /// Sets the `HAVING` condition, which references the last given number of result expressions.
+    pub fn set_having_expr(&mut self, having_expr: Box<Expression>, num_result_exprs: usize) {
+        self.having_expr = Some(having_expr);
+        self.num_having_result_exprs = num_result_exprs;
+    }
+
+    /// Returns the `HAVING` condition, if any.
+    pub fn get_having_expr(&self) -> Option<&Expression> {
+        self.having_expr.as_deref()
+    }
+
+    /// Returns the number of result expressions that are only computed for the `HAVING` condition.
+    ///
+    /// They are the last result expressions and they are dropped once the condition is applied.
+    pub fn get_num_having_result_exprs(&self) -> usize {
+        self.num_having_result_exprs
+    }
 
+    /// Sets the group by expressions, whose columns must already be in the result column set.
+    pub fn set_group_by_exprs(&mut self, exprs: Vec<Expression>) {
+        self.group_by_exprs = exprs;
         self.has_visited_group_by = true;
     }
 
@@ -170,12 +374,20 @@ impl QueryContext {
 
         // Result column references outside aggregation must appear in the group by
         self.group_by_exprs
-            .iter()
-            .find(|group_column| *group_column == column)
-            .map(|_| true)
+            .contains(&Expression::Column(*column))
+            .then_some(true)
             .ok_or(ConversionError::InvalidGroupByColumnRef(column.to_string()))
     }
 
+    /// Returns true if the given result expression, outside of any aggregation, is one of the
+    /// group by expressions that are not plain columns, e.g. `a + 1` in `GROUP BY a + 1`.
+    pub fn is_grouped_expr(&self, expr: &Expression) -> bool {
+        self.is_in_result_scope()
+            && !self.is_in_agg_scope()
+            && !matches!(expr, Expression::Column(_))
+            && self.group_by_exprs.contains(expr)
+    }
+
     pub fn get_aliased_result_exprs(&self) -> ConversionResult<&[AliasedResultExpr]> {
         assert!(!self.res_aliased_exprs.is_empty(), "empty aliased exprs");
 
@@ -205,6 +417,202 @@ impl QueryContext {
         Ok(&self.res_aliased_exprs)
     }
 
+    /// Returns true if the query groups rows, either with a `GROUP BY` clause or with aggregations.
+    pub fn is_aggregation(&self) -> bool {
+        !self.group_by_exprs.is_empty() || self.agg_counter > 0
+    }
+
+    /// Converts the result expressions into provable expressions.
+    ///
+    /// A nullable result expression is followed by its validity, which is named after the validity column of its alias.
+    ///
+    /// Returns `None` if the query has aggregations or window functions or if any
+    /// result expression can not be proven (e.g. a division).
+    pub fn get_provable_result_exprs<C: Commitment>(
+        &self,
+    ) -> ConversionResult<Option<Vec<AliasedProvableExprPlan<C>>>> {
+        if self.is_aggregation() || self.has_windows() {
+            return Ok(None);
+        }
+        Ok(self.build_provable_result_exprs(self.get_aliased_result_exprs()?))
+    }
+
+    /// Converts the given result expressions into provable expressions, see [`Self::get_provable_result_exprs`].
+    fn build_provable_result_exprs<C: Commitment>(
+        &self,
+        aliased_exprs: &[AliasedResultExpr],
+    ) -> Option<Vec<AliasedProvableExprPlan<C>>> {
+        let builder =
+            WhereExprBuilder::new(&self.column_mapping).with_selection(self.where_expr.as_deref());
+        let mut provable_exprs = Vec::new();
+        for aliased_expr in aliased_exprs {
+            let (Ok(expr), Ok(validity)) = (
+                builder.build_result_expr((*aliased_expr.expr).clone()),
+                builder.build_result_validity(&aliased_expr.expr),
+            ) else {
+                return None;
+            };
+            provable_exprs.push(AliasedProvableExprPlan {
+                expr,
+                alias: aliased_expr.alias,
+            });
+            if let Some(validity) = validity {
+                provable_exprs.push(AliasedProvableExprPlan {
+                    expr: validity,
+                    alias: validity_column_id(aliased_expr.alias).ok()?,
+                });
+            }
+        }
+        Some(provable_exprs)
+    }
+
+    /// Returns true if any result expression is a window function.
+    pub fn has_windows(&self) -> bool {
+        self.res_aliased_exprs
+            .iter()
+            .any(|aliased_expr| matches!(*aliased_expr.expr, Expression::Window { .. }))
+    }
+
+    /// Converts a query whose only window function is a running sum into a `RunningSumExpr`.
+    ///
+    /// This is the case for `SUM(x) OVER (ORDER BY k)`, without a `PARTITION BY` or a descending order,
+    /// where `k` is an integer, a timestamp or a date column and `x` is a numeric column, neither of which is nullable.
+    /// The proof only verifies if `k` is strictly increasing in the order of the table,
+    /// so `k` must also be known to be so by the schema accessor.
+    /// Returns `None` if the query does not have this shape, has a join or aggregations
+    /// or if any other result expression can not be proven.
+    pub fn get_running_sum_expr<C: Commitment>(
+        &self,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Option<RunningSumExpr<C>>> {
+        if self.is_aggregation() || self.join.is_some() {
+            return Ok(None);
+        }
+        let aliased_exprs = self.get_aliased_result_exprs()?;
+        let window_indexes = Vec::from_iter(
+            aliased_exprs
+                .iter()
+                .enumerate()
+                .filter(|(_, aliased_expr)| matches!(*aliased_expr.expr, Expression::Window { .. }))
+                .map(|(index, _)| index),
+        );
+        let [window_index] = window_indexes[..] else {
+            return Ok(None);
+        };
+        let Expression::Window {
+            function: WindowFunction::Sum(sum_expr),
+            partition_by,
+            order_by,
+        } = &*aliased_exprs[window_index].expr
+        else {
+            return Ok(None);
+        };
+        let (
+            Expression::Column(sum_column),
+            [],
+            [OrderBy {
+                expr: order_column,
+                direction: OrderByDirection::Asc,
+            }],
+        ) = (&**sum_expr, &partition_by[..], &order_by[..])
+        else {
+            return Ok(None);
+        };
+        if self.is_nullable(sum_column) || self.is_nullable(order_column) {
+            return Ok(None);
+        }
+        let (Some(sum_column_ref), Some(order_column_ref)) = (
+            self.column_mapping.get(sum_column),
+            self.column_mapping.get(order_column),
+        ) else {
+            return Ok(None);
+        };
+        let order_type = order_column_ref.column_type();
+        if !order_type.is_integer()
+            && !matches!(order_type, ColumnType::TimeStamp(_, _) | ColumnType::Date)
+        {
+            return Ok(None);
+        }
+        if !schema_accessor
+            .is_strictly_increasing(order_column_ref.table_ref(), order_column_ref.column_id())
+        {
+            return Ok(None);
+        }
+        let (Some(preceding_results), Some(following_results)) = (
+            self.build_provable_result_exprs(&aliased_exprs[..window_index]),
+            self.build_provable_result_exprs(&aliased_exprs[window_index + 1..]),
+        ) else {
+            return Ok(None);
+        };
+        let sum_index = preceding_results.len();
+        let where_clause = WhereExprBuilder::new(&self.column_mapping)
+            .build(self.where_expr.clone())?
+            .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
+        Ok(Some(RunningSumExpr::new(
+            [preceding_results, following_results].concat(),
+            TableExpr {
+                table_ref: *self.get_table_ref(),
+            },
+            where_clause,
+            ColumnExpr::new(*order_column_ref),
+            AliasedProvableExprPlan {
+                expr: ProvableExprPlan::Column(ColumnExpr::new(*sum_column_ref)),
+                alias: aliased_exprs[window_index].alias,
+            },
+            sum_index,
+        )))
+    }
+
+    /// Returns the index of the result expression that the query is ordered by, the direction and the limit
+    /// when the query can be proven by a `TopKExpr`.
+    ///
+    /// This is the case when the query has a positive `LIMIT` without an `OFFSET` and is ordered by a single
+    /// result expression that is an integer, a timestamp or a date and is never null.
+    pub fn get_top_k<C: Commitment>(
+        &self,
+        aliased_results: &[AliasedProvableExprPlan<C>],
+    ) -> Option<(usize, OrderByDirection, u64)> {
+        let [order_by] = &self.order_by_exprs[..] else {
+            return None;
+        };
+        let Some(Slice {
+            number_rows,
+            offset_value: 0,
+        }) = self.slice_expr
+        else {
+            return None;
+        };
+        if number_rows == 0 || number_rows == u64::MAX {
+            return None;
+        }
+        let order_index = aliased_results
+            .iter()
+            .position(|aliased_expr| aliased_expr.alias == order_by.expr)?;
+        let validity_id = validity_column_id(order_by.expr).ok();
+        let is_nullable = aliased_results
+            .iter()
+            .any(|aliased_expr| Some(aliased_expr.alias) == validity_id);
+        let data_type = aliased_results[order_index].expr.data_type();
+        let is_ordered = data_type.is_integer()
+            || matches!(data_type, ColumnType::TimeStamp(_, _) | ColumnType::Date);
+        (is_ordered && !is_nullable).then_some((order_index, order_by.direction, number_rows))
+    }
+
+    /// Returns the limit and the offset of the query when its slice can be proven by a `DenseFilterExpr`.
+    ///
+    /// This is the case when the query has a `LIMIT` or a non negative `OFFSET` and is not ordered.
+    pub fn get_provable_slice(&self) -> Option<(u64, u64)> {
+        if !self.order_by_exprs.is_empty() {
+            return None;
+        }
+        let Slice {
+            number_rows,
+            offset_value,
+        } = self.slice_expr?;
+        let offset = u64::try_from(offset_value).ok()?;
+        (number_rows != u64::MAX || offset != 0).then_some((number_rows, offset))
+    }
+
     pub fn get_order_by_exprs(&self) -> ConversionResult<Vec<OrderBy>> {
         // Order by must reference only aliases in the result schema
         for by_expr in &self.order_by_exprs {
@@ -223,7 +631,7 @@ impl QueryContext {
         &self.slice_expr
     }
 
-    pub fn get_group_by_exprs(&self) -> &[Identifier] {
+    pub fn get_group_by_exprs(&self) -> &[Expression] {
         &self.group_by_exprs
     }
 
@@ -234,125 +642,364 @@ impl QueryContext {
     pub fn get_column_mapping(&self) -> HashMap<Identifier, ColumnRef> {
         self.column_mapping.clone()
     }
-}
 
-/// Converts a `QueryContext` into a `Option<GroupByExpr>`.
-///
-/// We use Some if the query is provable and None if it is not
-/// We error out if the query is wrong
-impl<C: Commitment> TryFrom<&QueryContext> for Option<GroupByExpr<C>> {
-    type Error = ConversionError;
-
-    fn try_from(value: &QueryContext) -> Result<Option<GroupByExpr<C>>, Self::Error> {
+    /// Returns the table, the `WHERE` clause and the grouping expressions of a `GroupByExpr` that groups the rows
+    /// by the given aliased expressions, or `None` if the query has no `WHERE` clause
+    /// or if a grouping expression can not be proven.
+    ///
+    /// Rows are grouped by the validity of the nullable group by expressions too, so that nulls form their own group.
+    #[allow(clippy::type_complexity)]
+    fn get_group_by_parts<C: Commitment>(
+        &self,
+        group_by_exprs: &[AliasedResultExpr],
+    ) -> ConversionResult<
+        Option<(
+            TableExpr,
+            ProvableExprPlan<C>,
+            Vec<AliasedProvableExprPlan<C>>,
+        )>,
+    > {
         // Currently if there is no where clause, we can't prove the query
-        if value.where_expr.is_none() {
+        if self.where_expr.is_none() {
             return Ok(None);
         }
-        let where_clause = WhereExprBuilder::new(&value.column_mapping)
-            .build(value.where_expr.clone())?
+        let where_clause = WhereExprBuilder::new(&self.column_mapping)
+            .build(self.where_expr.clone())?
             .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
-        let table = value.table.map(|table_ref| TableExpr { table_ref }).ok_or(
+        let table = self.table.map(|table_ref| TableExpr { table_ref }).ok_or(
             ConversionError::InvalidExpression("QueryContext has no table_ref".to_owned()),
         )?;
-        let resource_id = table.table_ref.resource_id();
-        let group_by_exprs = value
+        let builder =
+            WhereExprBuilder::new(&self.column_mapping).with_selection(self.where_expr.as_deref());
+        let mut provable_exprs = Vec::with_capacity(group_by_exprs.len());
+        let mut validity_exprs = Vec::new();
+        for aliased_expr in group_by_exprs {
+            let (Ok(expr), Ok(validity)) = (
+                builder.build_result_expr((*aliased_expr.expr).clone()),
+                builder.build_result_validity(&aliased_expr.expr),
+            ) else {
+                return Ok(None);
+            };
+            provable_exprs.push(AliasedProvableExprPlan {
+                expr,
+                alias: aliased_expr.alias,
+            });
+            if let Some(validity) = validity {
+                let Ok(alias) = validity_column_id(aliased_expr.alias) else {
+                    return Ok(None);
+                };
+                validity_exprs.push(AliasedProvableExprPlan {
+                    expr: validity,
+                    alias,
+                });
+            }
+        }
+        provable_exprs.extend(validity_exprs);
+        Ok(Some((table, where_clause, provable_exprs)))
+    }
+
+    /// Returns the group by expressions, each named after its column or else after its result alias,
+    /// together with the result expressions after them.
+    ///
+    /// Returns `None` if the result does not start with all the group by expressions in the correct order.
+    fn get_group_by_keys(&self) -> Option<(Vec<AliasedResultExpr>, &[AliasedResultExpr])> {
+        let num_group_by_exprs = self.group_by_exprs.len();
+        if self.res_aliased_exprs.len() < num_group_by_exprs {
+            return None;
+        }
+        let (res_group_by_exprs, aggregate_columns) =
+            self.res_aliased_exprs.split_at(num_group_by_exprs);
+        let group_by_keys = self
             .group_by_exprs
             .iter()
-            .map(|expr| -> Result<ColumnExpr<C>, ConversionError> {
-                value
-                    .column_mapping
-                    .get(expr)
-                    .ok_or(ConversionError::MissingColumn(
-                        Box::new(*expr),
-                        Box::new(resource_id),
-                    ))
-                    .map(|column_ref| ColumnExpr::<C>::new(*column_ref))
+            .zip(res_group_by_exprs.iter())
+            .map(|(group_by_expr, res)| {
+                //TODO: This is due to a workaround related to polars
+                //Need to remove it when possible (PROOF-850)
+                match &*res.expr {
+                    Expression::Aggregation {
+                        op: AggregationOperator::First,
+                        expr,
+                    } if **expr == *group_by_expr => Some(AliasedResultExpr {
+                        expr: expr.clone(),
+                        alias: match group_by_expr {
+                            Expression::Column(ident) => *ident,
+                            _ => res.alias,
+                        },
+                    }),
+                    _ => None,
+                }
             })
-            .collect::<Result<Vec<ColumnExpr<C>>, ConversionError>>()?;
-        // For a query to be provable the result columns must be of one of three kinds below:
-        // 1. Group by columns (it is mandatory to have all of them in the correct order)
-        // 2. Sum(col) expressions (it is optional to have any)
-        // 3. count(*) with an alias (it is mandatory to have one and only one)
-        let num_group_by_columns = group_by_exprs.len();
-        let num_result_columns = value.res_aliased_exprs.len();
-        if num_result_columns < num_group_by_columns + 1 {
+            .collect::<Option<Vec<_>>>()?;
+        Some((group_by_keys, aggregate_columns))
+    }
+
+    /// Without a count(*) in the query, the count column of the result gets a name
+    /// that can not clash with any of the other result columns.
+    fn get_hidden_count_alias(&self) -> Option<Identifier> {
+        Identifier::try_new("__count__")
+            .ok()
+            .filter(|alias| self.res_aliased_exprs.iter().all(|res| res.alias != *alias))
+    }
+
+    /// Converts a query whose aggregations are all `COUNT(DISTINCT x)` of the same column `x` into a `GroupByExpr`
+    /// that groups the rows by the group by columns and `x`.
+    ///
+    /// Each row of its result is a distinct value of `x` within a group, so the post-processing step
+    /// only has to count the non-null values of `x` in each group.
+    /// Returns `None` if the query does not have this shape, has no `WHERE` clause
+    /// or is grouped by expressions that are not columns.
+    pub fn get_count_distinct_group_by_expr<C: Commitment>(
+        &self,
+    ) -> ConversionResult<Option<GroupByExpr<C>>> {
+        let Some((mut group_by_keys, aggregate_columns)) = self.get_group_by_keys() else {
+            return Ok(None);
+        };
+        if group_by_keys
+            .iter()
+            .any(|key| !matches!(*key.expr, Expression::Column(_)))
+        {
             return Ok(None);
         }
-        let res_group_by_columns = &value.res_aliased_exprs[..num_group_by_columns].to_vec();
-        let sum_expr_columns =
-            &value.res_aliased_exprs[num_group_by_columns..num_result_columns - 1].to_vec();
-        // Check group by columns
-        let group_by_compliance = value
-            .group_by_exprs
+        let Some(distinct_columns) = aggregate_columns
             .iter()
-            .zip(res_group_by_columns.iter())
-            .all(|(ident, res)| {
-                //TODO: This is due to a workaround related to polars
-                //Need to remove it when possible (PROOF-850)
-                if let Expression::Aggregation {
-                    op: AggregationOperator::First,
+            .map(|res| match &*res.expr {
+                Expression::Aggregation {
+                    op: AggregationOperator::CountDistinct,
                     expr,
-                } = (*res.expr).clone()
-                {
-                    if let Expression::Column(res_ident) = *expr {
-                        res_ident == *ident
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
+                } => match **expr {
+                    Expression::Column(ident) => Some(ident),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Ok(None);
+        };
+        let distinct_column = match distinct_columns.first() {
+            Some(column)
+                if distinct_columns.iter().all(|other| other == column)
+                    && !self.group_by_exprs.contains(&Expression::Column(*column)) =>
+            {
+                *column
+            }
+            _ => return Ok(None),
+        };
+        group_by_keys.push(AliasedResultExpr {
+            expr: Box::new(Expression::Column(distinct_column)),
+            alias: distinct_column,
+        });
+        let (Some((table, where_clause, group_by_exprs)), Some(count_alias)) = (
+            self.get_group_by_parts(&group_by_keys)?,
+            self.get_hidden_count_alias(),
+        ) else {
+            return Ok(None);
+        };
+        Ok(Some(GroupByExpr::new(
+            group_by_exprs,
+            vec![],
+            vec![],
+            vec![],
+            count_alias,
+            table,
+            where_clause,
+        )))
+    }
+}
+
+/// Returns the identifier of the hidden column that holds the number of values averaged by the result
+/// expression with the given alias.
+pub(crate) fn average_count_column_id(alias: Identifier) -> Result<Identifier, ParseError> {
+    Identifier::try_new(format!("__count__{alias}"))
+}
+
+/// Returns the identifier of the hidden column that holds the values of the window function of the result
+/// expression with the given alias.
+pub(crate) fn window_column_id(alias: Identifier) -> Result<Identifier, ParseError> {
+    Identifier::try_new(format!("__window__{alias}"))
+}
+
+/// Converts a `QueryContext` into a `Option<GroupByExpr>`.
+///
+/// We use Some if the query is provable and None if it is not
+/// We error out if the query is wrong
+impl<C: Commitment> TryFrom<&QueryContext> for Option<GroupByExpr<C>> {
+    type Error = ConversionError;
+
+    fn try_from(value: &QueryContext) -> Result<Option<GroupByExpr<C>>, Self::Error> {
+        // For a query to be provable the result columns must be of one of the kinds below:
+        // 1. Group by expressions (it is mandatory to have all of them in the correct order)
+        // 2. Sum(expr), Min(col), Max(col) and Count(expr) expressions, in any order, where sums are over
+        //    numeric expressions, mins and maxes are over non-nullable integer columns and counts are over
+        //    nullable expressions (it is optional to have any)
+        // 3. count(*) (it is optional to have one, but there can not be more than one)
+        // 4. Group by expressions again (it is optional to have any)
+        let Some((group_by_keys, aggregate_columns)) = value.get_group_by_keys() else {
+            return Ok(None);
+        };
+        let Some((table, where_clause, group_by_exprs)) =
+            value.get_group_by_parts(&group_by_keys)?
+        else {
+            return Ok(None);
+        };
+        let builder = WhereExprBuilder::new(&value.column_mapping)
+            .with_selection(value.where_expr.as_deref());
+        // Check count(*), which counts an expression that is never null
+        let (count_columns, aggregate_columns): (Vec<_>, Vec<_>) =
+            aggregate_columns.iter().partition(|res| match &*res.expr {
+                Expression::Aggregation {
+                    op: AggregationOperator::Count,
+                    expr,
+                } => matches!(builder.build_result_validity::<C>(expr), Ok(None)),
+                _ => false,
             });
-        // Check sums
-        let sum_expr = sum_expr_columns
+        let count_alias = match count_columns[..] {
+            [] => value.get_hidden_count_alias(),
+            [count_column] => Some(count_column.alias),
+            _ => None,
+        };
+        // Check sums, mins, maxes and counts
+        let aggregate_expr = aggregate_columns
             .iter()
             .map(|res| {
-                if let Expression::Aggregation {
-                    op: AggregationOperator::Sum,
-                    expr,
-                } = (*res.expr).clone()
-                {
-                    if let Expression::Column(ident) = *expr {
-                        // For sums the outgoing ColumnType is the same as the incoming ColumnType
-                        let column_type = *value
-                            .column_mapping
-                            .get(&ident)
-                            .expect("QueryContext should never allow unknown cols to be in sum")
-                            .column_type();
-                        let res_column_field = ColumnField::new(res.alias, column_type);
-                        let column_expr =
-                            ColumnExpr::new(ColumnRef::new(table.table_ref, ident, column_type));
-                        Some((column_expr, res_column_field))
-                    } else {
+                let Expression::Aggregation { op, expr } = (*res.expr).clone() else {
+                    return None;
+                };
+                // A group by expression may be repeated, e.g. to be referenced by a HAVING clause.
+                if op == AggregationOperator::First {
+                    return value.group_by_exprs.contains(&*expr).then(Vec::new);
+                }
+                let provable_expr: ProvableExprPlan<C> =
+                    builder.build_result_expr((*expr).clone()).ok()?;
+                // For sums, mins and maxes the outgoing ColumnType is the same as the incoming ColumnType
+                let data_type = provable_expr.data_type();
+                let field = ColumnField::new(res.alias, data_type);
+                // Summing the validity of a nullable expression counts its non-null values.
+                let validity_expr = builder.build_result_validity(&expr).ok()?;
+                match (&op, validity_expr) {
+                    (AggregationOperator::Sum | AggregationOperator::Avg, _)
+                        if !data_type.is_numeric() =>
+                    {
                         None
                     }
-                } else {
-                    None
+                    (AggregationOperator::Sum, None) => Some(vec![(op, (provable_expr, field))]),
+                    // The number of non-null values is returned as the validity of the sum,
+                    // so that a sum of nulls only is null.
+                    (AggregationOperator::Sum, Some(validity_expr)) => {
+                        let validity_field = ColumnField::new(
+                            validity_column_id(res.alias).ok()?,
+                            ColumnType::BigInt,
+                        );
+                        Some(vec![
+                            (op.clone(), (provable_expr, field)),
+                            (op, (validity_expr, validity_field)),
+                        ])
+                    }
+                    // An average is a sum that is divided by the number of rows of its group,
+                    // or by the number of non-null values when the expression is nullable.
+                    (AggregationOperator::Avg, None) => {
+                        Some(vec![(AggregationOperator::Sum, (provable_expr, field))])
+                    }
+                    (AggregationOperator::Avg, Some(validity_expr)) => {
+                        let count_field = ColumnField::new(
+                            average_count_column_id(res.alias).ok()?,
+                            ColumnType::BigInt,
+                        );
+                        Some(vec![
+                            (AggregationOperator::Sum, (provable_expr, field)),
+                            (AggregationOperator::Sum, (validity_expr, count_field)),
+                        ])
+                    }
+                    (AggregationOperator::Count, Some(validity_expr)) => Some(vec![(
+                        AggregationOperator::Sum,
+                        (
+                            validity_expr,
+                            ColumnField::new(res.alias, ColumnType::BigInt),
+                        ),
+                    )]),
+                    (AggregationOperator::Min | AggregationOperator::Max, None)
+                        if matches!(provable_expr, ProvableExprPlan::Column(_))
+                            && data_type.is_integer() =>
+                    {
+                        Some(vec![(op, (provable_expr, field))])
+                    }
+                    _ => None,
                 }
             })
-            .collect::<Option<Vec<(ColumnExpr<C>, ColumnField)>>>();
-
-        // Check count(*)
-        let count_column = &value.res_aliased_exprs[num_result_columns - 1];
-        let count_column_compliant = if let Expression::Aggregation {
-            op: AggregationOperator::Count,
-            expr,
-        } = (*count_column.expr).clone()
-        {
-            //TODO: This is due to a workaround related to polars
-            matches!(*expr, Expression::Column(_))
-        } else {
-            false
+            .collect::<Option<Vec<_>>>()
+            .map(|aggregate_exprs| aggregate_exprs.concat());
+        let (aggregate_expr, count_alias) = match (aggregate_expr, count_alias) {
+            (Some(aggregate_expr), Some(count_alias)) => (aggregate_expr, count_alias),
+            _ => return Ok(None),
+        };
+        let aggregate_exprs_by_op = |op| {
+            aggregate_expr
+                .iter()
+                .filter(move |(aggregate_op, _)| *aggregate_op == op)
+                .map(|(_, expr)| expr.clone())
+        };
+        // Mins and maxes are only ever over columns.
+        let column_exprs_by_op = |op| {
+            aggregate_exprs_by_op(op)
+                .filter_map(|(expr, field)| match expr {
+                    ProvableExprPlan::Column(column_expr) => Some((column_expr, field)),
+                    _ => None,
+                })
+                .collect()
         };
-        if !group_by_compliance || sum_expr.is_none() || !count_column_compliant {
-            return Ok(None);
-        }
         Ok(Some(GroupByExpr::new(
             group_by_exprs,
-            sum_expr.expect("the none case was just checked"),
-            count_column.alias,
+            aggregate_exprs_by_op(AggregationOperator::Sum).collect(),
+            column_exprs_by_op(AggregationOperator::Min),
+            column_exprs_by_op(AggregationOperator::Max),
+            count_alias,
             table,
             where_clause,
         )))
     }
 }
+
+/// Converts a `QueryContext` into a `Option<JoinExpr>`.
+///
+/// We use Some if the query joins two tables and None if it does not.
+/// We error out if the join can not be proven.
+impl<C: Commitment> TryFrom<&QueryContext> for Option<JoinExpr<C>> {
+    type Error = ConversionError;
+
+    fn try_from(value: &QueryContext) -> Result<Option<JoinExpr<C>>, Self::Error> {
+        let Some(join) = value.join else {
+            return Ok(None);
+        };
+        if value.where_expr.is_some() {
+            return Err(ConversionError::Unprovable(
+                "WHERE clauses on joins are not supported".to_string(),
+            ));
+        }
+        let left_table = *value.get_table_ref();
+
+        // Sorting is required to make the relative order of the columns deterministic
+        // The validity columns of nullable columns are part of the result as well.
+        let mut columns = Vec::from_iter(value.result_column_set.iter().flat_map(|column| {
+            once(value.column_mapping[column]).chain(value.get_validity_column_ref(column))
+        }));
+        columns.sort_by_key(|column| column.column_id());
+        let (left_columns, right_columns): (Vec<_>, Vec<_>) = columns
+            .into_iter()
+            .filter(|column| *column != join.left_key)
+            .partition(|column| column.table_ref() == left_table);
+
+        Ok(Some(JoinExpr::new(
+            TableExpr {
+                table_ref: left_table,
+            },
+            TableExpr {
+                table_ref: join.right_table,
+            },
+            ColumnExpr::new(join.left_key),
+            ColumnExpr::new(join.right_key),
+            left_columns.into_iter().map(ColumnExpr::new).collect(),
+            right_columns.into_iter().map(ColumnExpr::new).collect(),
+        )))
+    }
+}
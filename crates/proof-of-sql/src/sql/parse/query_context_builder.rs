@@ -1,23 +1,52 @@
-use super::QueryContext;
+use super::{CteContext, JoinContext, QueryContext, SubqueryContext};
 use crate::{
     base::{
-        database::{ColumnRef, ColumnType, SchemaAccessor, TableRef},
+        database::{
+            order_key_column_id, ordered_column_id, validated_column_id, validity_column_id,
+            ColumnRef, ColumnType, SchemaAccessor, TableRef,
+        },
         math::decimal::Precision,
     },
-    sql::parse::{ConversionError, ConversionResult},
+    sql::{
+        ast::{
+            check_cast_column_types, try_average_column_type, try_common_column_type,
+            SubqueryComparison,
+        },
+        parse::{ConversionError, ConversionResult},
+    },
 };
 use proof_of_sql_parser::{
     intermediate_ast::{
-        AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal, OrderBy,
-        SelectResultExpr, Slice, TableExpression, UnaryOperator,
+        AggregationOperator, AliasedResultExpr, BinaryOperator, DataType, Expression, JoinColumn,
+        JoinCondition, Literal, OrderBy, QualifiedColumn, SelectResultExpr, Slice, TableExpression,
+        UnaryOperator, WindowFunction,
     },
     Identifier, ResourceId,
 };
-use std::ops::Deref;
+use std::{collections::HashMap, ops::Deref};
 
 pub struct QueryContextBuilder<'a> {
     context: QueryContext,
     schema_accessor: &'a dyn SchemaAccessor,
+    ctes: Option<&'a HashMap<Identifier, CteContext>>,
+    cte: Option<&'a CteContext>,
+    qualifiers: Vec<TableQualifier>,
+}
+
+/// A name that the columns of a table of the `FROM` clause can be qualified by.
+///
+/// A table is named by its alias, if it has one, and otherwise by its name, possibly qualified by its schema.
+#[derive(Debug, Clone, Copy)]
+struct TableQualifier {
+    table_ref: TableRef,
+    name: Identifier,
+    schema: Option<Identifier>,
+}
+
+impl TableQualifier {
+    fn matches(&self, schema: Option<Identifier>, table: Identifier) -> bool {
+        self.name == table && schema.map_or(true, |schema| self.schema == Some(schema))
+    }
 }
 
 // Public interface
@@ -26,31 +55,140 @@ impl<'a> QueryContextBuilder<'a> {
         Self {
             context: QueryContext::default(),
             schema_accessor,
+            ctes: None,
+            cte: None,
+            qualifiers: Vec::new(),
         }
     }
 
+    /// The common table expressions that an unqualified table name of the query may refer to.
+    pub fn visit_ctes(mut self, ctes: &'a HashMap<Identifier, CteContext>) -> Self {
+        self.ctes = Some(ctes);
+        self
+    }
+
     #[allow(clippy::vec_box)]
     pub fn visit_table_expr(
         mut self,
         table_expr: Vec<Box<TableExpression>>,
         default_schema: Identifier,
-    ) -> Self {
+    ) -> ConversionResult<Self> {
         assert_eq!(table_expr.len(), 1);
-        match *table_expr[0] {
-            TableExpression::Named { table, schema } => {
-                self.context.set_table_ref(TableRef::new(ResourceId::new(
-                    schema.unwrap_or(default_schema),
-                    table,
-                )));
+        let table_expr = table_expr
+            .into_iter()
+            .next()
+            .expect("there is one table expression");
+        match *table_expr {
+            // A common table expression is selected from as the table that it selects from.
+            TableExpression::Named {
+                table,
+                schema,
+                alias,
+            } => match self.lookup_cte(table, schema) {
+                Some(cte) => {
+                    self.context.set_table_ref(cte.table_ref);
+                    self.cte = Some(cte);
+                    self.push_qualifier(cte.table_ref, table, None, alias);
+                }
+                None => {
+                    let table_ref =
+                        TableRef::new(ResourceId::new(schema.unwrap_or(default_schema), table));
+                    self.context.set_table_ref(table_ref);
+                    self.push_qualifier(table_ref, table, Some(table_ref.schema_id()), alias);
+                }
+            },
+            TableExpression::Join { left, right, on } => {
+                let (left, right) = match (*left, *right) {
+                    (
+                        TableExpression::Named {
+                            table: left_table,
+                            schema: left_schema,
+                            ..
+                        },
+                        TableExpression::Named {
+                            table: right_table,
+                            schema: right_schema,
+                            ..
+                        },
+                    ) if self.lookup_cte(left_table, left_schema).is_some()
+                        || self.lookup_cte(right_table, right_schema).is_some() =>
+                    {
+                        return Err(ConversionError::Unprovable(
+                            "joins with common table expressions are not supported".to_string(),
+                        ))
+                    }
+                    (
+                        TableExpression::Named {
+                            table: left_table,
+                            schema: left_schema,
+                            alias: left_alias,
+                        },
+                        TableExpression::Named {
+                            table: right_table,
+                            schema: right_schema,
+                            alias: right_alias,
+                        },
+                    ) => {
+                        let left = TableRef::new(ResourceId::new(
+                            left_schema.unwrap_or(default_schema),
+                            left_table,
+                        ));
+                        let right = TableRef::new(ResourceId::new(
+                            right_schema.unwrap_or(default_schema),
+                            right_table,
+                        ));
+                        self.push_qualifier(left, left_table, Some(left.schema_id()), left_alias);
+                        self.push_qualifier(
+                            right,
+                            right_table,
+                            Some(right.schema_id()),
+                            right_alias,
+                        );
+                        (left, right)
+                    }
+                    _ => {
+                        return Err(ConversionError::Unprovable(
+                            "joins of more than two tables are not supported".to_string(),
+                        ))
+                    }
+                };
+                if left == right {
+                    return Err(ConversionError::Unprovable(
+                        "joins of a table with itself are not supported".to_string(),
+                    ));
+                }
+                self.context.set_table_ref(left);
+                let join = self.visit_join_condition(*on, left, right)?;
+                self.context.set_join(join);
             }
         }
-        self
+        Ok(self)
     }
 
+    /// The subquery conditions that the `WHERE` clause is made of, joined by `AND`, are taken out of it.
+    ///
+    /// The rows of a common table expression are those that meet its own condition too.
     pub fn visit_where_expr(
         mut self,
-        mut where_expr: Option<Box<Expression>>,
+        where_expr: Option<Box<Expression>>,
     ) -> ConversionResult<Self> {
+        let mut where_expr = match where_expr {
+            Some(mut expr) => {
+                self.resolve_columns(&mut expr)?;
+                self.visit_subquery_conditions(*expr)?
+            }
+            None => None,
+        };
+        if let Some(cte_where_expr) = self.cte.and_then(|cte| cte.where_expr.clone()) {
+            where_expr = Some(match where_expr {
+                Some(expr) => Box::new(Expression::Binary {
+                    op: BinaryOperator::And,
+                    left: cte_where_expr,
+                    right: expr,
+                }),
+                None => cte_where_expr,
+            });
+        }
         if let Some(expr) = where_expr.as_deref_mut() {
             self.visit_expr(expr)?;
         }
@@ -74,6 +212,36 @@ impl<'a> QueryContextBuilder<'a> {
         Ok(self)
     }
 
+    /// The aggregations and the group by columns of the `HAVING` condition are computed as hidden result
+    /// expressions, named `__having__0`, `__having__1`, etc., which the condition is rewritten to reference.
+    pub fn visit_having_expr(
+        mut self,
+        having_expr: Option<Box<Expression>>,
+    ) -> ConversionResult<Self> {
+        let Some(mut having_expr) = having_expr else {
+            return Ok(self);
+        };
+        self.resolve_columns(&mut having_expr)?;
+        let dtype = self.visit_expr(&mut having_expr.clone())?;
+        if dtype != ColumnType::Boolean {
+            return Err(ConversionError::InvalidDataType {
+                expected: ColumnType::Boolean,
+                actual: dtype,
+            });
+        }
+        let mut num_result_exprs = 0;
+        self.context.toggle_result_scope();
+        self.visit_having_operands(&mut having_expr, &mut num_result_exprs)?;
+        self.context.toggle_result_scope();
+        if !self.context.is_aggregation() || num_result_exprs == 0 {
+            return Err(ConversionError::InvalidExpression(
+                "HAVING must reference aggregations or group by columns".to_string(),
+            ));
+        }
+        self.context.set_having_expr(having_expr, num_result_exprs);
+        Ok(self)
+    }
+
     pub fn visit_order_by_exprs(mut self, order_by_exprs: Vec<OrderBy>) -> Self {
         self.context.set_order_by_exprs(order_by_exprs);
         self
@@ -84,17 +252,87 @@ impl<'a> QueryContextBuilder<'a> {
         self
     }
 
+    /// An integer literal is replaced by the result expression at that position, e.g. `GROUP BY 1`
+    /// groups the rows by the first result expression.
+    ///
+    /// The columns of the group by expressions are visited as result columns
+    /// to ensure their integrity in the filter expression.
+    #[allow(clippy::vec_box)]
     pub fn visit_group_by_exprs(
         mut self,
-        group_by_exprs: Vec<Identifier>,
+        group_by_exprs: Vec<Box<Expression>>,
+        result_exprs: &[SelectResultExpr],
     ) -> ConversionResult<Self> {
-        for id in &group_by_exprs {
-            self.visit_column_identifier(*id)?;
+        let mut exprs = Vec::with_capacity(group_by_exprs.len());
+        self.context.toggle_result_scope();
+        for group_by_expr in group_by_exprs {
+            let mut expr = match *group_by_expr {
+                Expression::Literal(Literal::BigInt(position)) => {
+                    resolve_group_by_position(position, result_exprs)?
+                }
+                expr => expr,
+            };
+            self.resolve_columns(&mut expr)?;
+            if !is_supported_in_post_processing(&expr) {
+                return Err(ConversionError::InvalidExpression(
+                    "aggregations, IN, BETWEEN, LIKE and literals of decimals, timestamps or dates are not supported in GROUP BY"
+                        .to_string(),
+                ));
+            }
+            self.visit_expr(&mut expr)?;
+            exprs.push(expr);
         }
-        self.context.set_group_by_exprs(group_by_exprs);
+        self.context.toggle_result_scope();
+        self.context.set_group_by_exprs(exprs);
         Ok(self)
     }
 
+    /// `SELECT DISTINCT` is turned into a `GROUP BY` over all its result columns,
+    /// which must be plain columns, possibly qualified by their table.
+    pub fn visit_distinct(
+        self,
+        distinct: bool,
+        result_exprs: &[SelectResultExpr],
+    ) -> ConversionResult<Self> {
+        if !distinct {
+            return Ok(self);
+        }
+        if !self.context.get_group_by_exprs().is_empty() {
+            return Err(ConversionError::InvalidExpression(
+                "SELECT DISTINCT can not be combined with GROUP BY".to_owned(),
+            ));
+        }
+        let mut columns: Vec<Expression> = Vec::new();
+        for result_expr in result_exprs {
+            let result_columns = match result_expr {
+                SelectResultExpr::ALL => Vec::from_iter(
+                    self.lookup_column_names()
+                        .into_iter()
+                        .map(Expression::Column),
+                ),
+                SelectResultExpr::AliasedResultExpr(AliasedResultExpr { expr, .. }) => {
+                    match &**expr {
+                        Expression::Column(column_name) => vec![Expression::Column(*column_name)],
+                        Expression::QualifiedColumn(column) => {
+                            vec![self.resolve_qualified_column(column)?]
+                        }
+                        _ => {
+                            return Err(ConversionError::InvalidExpression(
+                                "SELECT DISTINCT is only supported over columns".to_owned(),
+                            ))
+                        }
+                    }
+                }
+            };
+            for column in result_columns {
+                if !columns.contains(&column) {
+                    columns.push(column);
+                }
+            }
+        }
+        self.visit_group_by_exprs(columns.into_iter().map(Box::new).collect(), result_exprs)
+    }
+
     pub fn build(self) -> ConversionResult<QueryContext> {
         Ok(self.context)
     }
@@ -104,21 +342,438 @@ impl<'a> QueryContextBuilder<'a> {
 impl<'a> QueryContextBuilder<'a> {
     fn lookup_schema(&self) -> Vec<(Identifier, ColumnType)> {
         let table_ref = self.context.get_table_ref();
-        let columns = self.schema_accessor.lookup_schema(*table_ref);
+        let mut columns = self.schema_accessor.lookup_schema(*table_ref);
         assert!(!columns.is_empty(), "At least one column must exist");
+        if let Some(join) = self.context.get_join() {
+            // A right key with the same name as the left key is the same column of the join result.
+            let right_columns = self.schema_accessor.lookup_schema(join.right_table);
+            columns.extend(right_columns.into_iter().filter(|(column_name, _)| {
+                *column_name != join.right_key.column_id()
+                    || *column_name != join.left_key.column_id()
+            }));
+        }
+        // Validity and order key columns are part of the columns they validate and order.
+        let column_names = Vec::from_iter(columns.iter().map(|(column_name, _)| *column_name));
+        columns.retain(|(column_name, _)| {
+            validated_column_id(*column_name)
+                .or_else(|| ordered_column_id(*column_name))
+                .map_or(true, |column_id| !column_names.contains(&column_id))
+        });
         columns
     }
 
+    /// Returns the names of the columns that `*` selects, which are those of the common table expression, if any.
+    fn lookup_column_names(&self) -> Vec<Identifier> {
+        match self.cte {
+            Some(cte) => Vec::from_iter(cte.columns.iter().map(|(column_name, _)| *column_name)),
+            None => Vec::from_iter(
+                self.lookup_schema()
+                    .into_iter()
+                    .map(|(column_name, _)| column_name),
+            ),
+        }
+    }
+
+    /// Returns the common table expression that a table name refers to, if any.
+    ///
+    /// A name qualified by a schema always refers to a table.
+    fn lookup_cte(&self, table: Identifier, schema: Option<Identifier>) -> Option<&'a CteContext> {
+        match schema {
+            Some(_) => None,
+            None => self.ctes.and_then(|ctes| ctes.get(&table)),
+        }
+    }
+
+    /// Registers the name that the columns of a table of the `FROM` clause are qualified by,
+    /// which is its alias if it has one.
+    fn push_qualifier(
+        &mut self,
+        table_ref: TableRef,
+        table: Identifier,
+        schema: Option<Identifier>,
+        alias: Option<Identifier>,
+    ) {
+        self.qualifiers.push(match alias {
+            Some(alias) => TableQualifier {
+                table_ref,
+                name: alias,
+                schema: None,
+            },
+            None => TableQualifier {
+                table_ref,
+                name: table,
+                schema,
+            },
+        });
+    }
+
+    /// Returns the table of the `FROM` clause that a qualified column, e.g. `t.a` or `sxt.t.a`, is a column of.
+    fn lookup_qualified_table(&self, column: &QualifiedColumn) -> ConversionResult<TableRef> {
+        let table = column.table;
+        let matches = Vec::from_iter(
+            self.qualifiers
+                .iter()
+                .filter(|qualifier| qualifier.matches(column.schema, table)),
+        );
+        match matches[..] {
+            [qualifier] => Ok(qualifier.table_ref),
+            [] => Err(ConversionError::MissingTable(match column.schema {
+                Some(schema) => format!("{schema}.{table}"),
+                None => table.to_string(),
+            })),
+            _ => Err(ConversionError::InvalidExpression(format!(
+                "table '{table}' is ambiguous because more than one table of the join is named so"
+            ))),
+        }
+    }
+
+    /// Resolves a column qualified by its table, e.g. `t.a`, to the column itself.
+    ///
+    /// A column of a join whose other table has a column of the same name is kept qualified,
+    /// so that it is resolved to the right table when it is visited.
+    fn resolve_qualified_column(&self, column: &QualifiedColumn) -> ConversionResult<Expression> {
+        let table_ref = self.lookup_qualified_table(column)?;
+        let column_name = column.column;
+        // The columns of a common table expression are checked when they are substituted.
+        if self.cte.is_some() {
+            return Ok(Expression::Column(column_name));
+        }
+        if self
+            .schema_accessor
+            .lookup_column(table_ref, column_name)
+            .is_none()
+        {
+            return Err(ConversionError::MissingColumn(
+                Box::new(column_name),
+                Box::new(table_ref.resource_id()),
+            ));
+        }
+        let is_ambiguous = self.context.get_join().is_some_and(|join| {
+            let other_table_ref = if table_ref == join.right_table {
+                *self.context.get_table_ref()
+            } else {
+                join.right_table
+            };
+            let is_join_key = join.left_key.column_id() == column_name
+                && join.right_key.column_id() == column_name;
+            !is_join_key
+                && self
+                    .schema_accessor
+                    .lookup_column(other_table_ref, column_name)
+                    .is_some()
+        });
+        Ok(if is_ambiguous {
+            Expression::QualifiedColumn(column.clone())
+        } else {
+            Expression::Column(column_name)
+        })
+    }
+
+    /// Resolves the columns that are qualified by their table, and then replaces the columns of
+    /// the common table expression that is selected from, if any, with the expressions over its table.
+    ///
+    /// Subqueries select from tables of their own, so they are left as they are.
+    fn resolve_columns(&self, expr: &mut Expression) -> ConversionResult<()> {
+        match expr {
+            Expression::Column(column_name) => {
+                if let Some(cte) = self.cte {
+                    *expr = cte.get_column_expr(*column_name)?.clone();
+                }
+            }
+            Expression::QualifiedColumn(column) => {
+                *expr = match (self.resolve_qualified_column(column)?, self.cte) {
+                    (Expression::Column(column_name), Some(cte)) => {
+                        cte.get_column_expr(column_name)?.clone()
+                    }
+                    (expr, _) => expr,
+                };
+            }
+            Expression::Literal(_)
+            | Expression::Wildcard
+            | Expression::Exists(_)
+            | Expression::Subquery(_) => {}
+            Expression::Unary { expr, .. }
+            | Expression::InList { expr, .. }
+            | Expression::InSubquery { expr, .. }
+            | Expression::Like { expr, .. }
+            | Expression::Cast { expr, .. }
+            | Expression::Aggregation { expr, .. } => self.resolve_columns(expr)?,
+            Expression::Binary { left, right, .. } => {
+                self.resolve_columns(left)?;
+                self.resolve_columns(right)?;
+            }
+            Expression::Between { expr, low, high } => {
+                for expr in [expr, low, high] {
+                    self.resolve_columns(expr)?;
+                }
+            }
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => {
+                for (when_expr, then_expr) in when_then {
+                    self.resolve_columns(when_expr)?;
+                    self.resolve_columns(then_expr)?;
+                }
+                if let Some(else_expr) = else_expr {
+                    self.resolve_columns(else_expr)?;
+                }
+            }
+            // The rows of a window are partitioned and ordered by columns, so those of a common table expression
+            // have to be columns of its table.
+            Expression::Window {
+                function,
+                partition_by,
+                order_by,
+            } => {
+                if let WindowFunction::Sum(expr) = function {
+                    self.resolve_columns(expr)?;
+                }
+                let Some(cte) = self.cte else {
+                    return Ok(());
+                };
+                for column_name in partition_by
+                    .iter_mut()
+                    .chain(order_by.iter_mut().map(|order_by| &mut order_by.expr))
+                {
+                    let Expression::Column(table_column) = cte.get_column_expr(*column_name)?
+                    else {
+                        return Err(ConversionError::InvalidExpression(format!(
+                            "column {column_name} of a common table expression is not a column of its table, so windows can not be partitioned or ordered by it"
+                        )));
+                    };
+                    *column_name = *table_column;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the validity column of the given column, if it is nullable.
+    fn lookup_validity_column(&self, column: &ColumnRef) -> Option<ColumnRef> {
+        let validity_id = validity_column_id(column.column_id()).ok()?;
+        self.schema_accessor
+            .lookup_column(column.table_ref(), validity_id)
+            .filter(|column_type| *column_type == ColumnType::Boolean)
+            .map(|column_type| ColumnRef::new(column.table_ref(), validity_id, column_type))
+    }
+
+    /// Returns the order key column of the given column, if it is a `VarChar` column that has one.
+    fn lookup_order_key_column(&self, column: &ColumnRef) -> Option<ColumnRef> {
+        if *column.column_type() != ColumnType::VarChar {
+            return None;
+        }
+        let order_key_id = order_key_column_id(column.column_id()).ok()?;
+        self.schema_accessor
+            .lookup_column(column.table_ref(), order_key_id)
+            .filter(|column_type| *column_type == ColumnType::Int128)
+            .map(|column_type| ColumnRef::new(column.table_ref(), order_key_id, column_type))
+    }
+
     fn visit_select_all_expr(&mut self) -> ConversionResult<()> {
-        for (column_name, _) in self.lookup_schema() {
+        for column_name in self.lookup_column_names() {
             let col_expr = Expression::Column(column_name);
             self.visit_aliased_expr(AliasedResultExpr::new(col_expr, column_name))?;
         }
         Ok(())
     }
 
+    /// Resolves the columns of a join condition, which must equate a column of each table.
+    fn visit_join_condition(
+        &self,
+        on: JoinCondition,
+        left: TableRef,
+        right: TableRef,
+    ) -> ConversionResult<JoinContext> {
+        let first = self.resolve_join_column(&on.left, left, right)?;
+        let second = self.resolve_join_column(&on.right, left, right)?;
+        let (left_key, right_key) = match (first.table_ref() == left, second.table_ref() == left) {
+            (true, false) => (first, second),
+            (false, true) => (second, first),
+            _ => {
+                return Err(ConversionError::InvalidExpression(
+                    "a join condition must equate a column of each table".to_string(),
+                ))
+            }
+        };
+        // NULL never equals NULL, while the join would match the default values that nulls are stored as.
+        if self.lookup_validity_column(&left_key).is_some()
+            || self.lookup_validity_column(&right_key).is_some()
+        {
+            return Err(ConversionError::Unprovable(
+                "joins on nullable columns are not supported".to_string(),
+            ));
+        }
+        let left_type = *left_key.column_type();
+        let right_type = *right_key.column_type();
+        let are_both_integers = [left_type, right_type].iter().all(|column_type| {
+            matches!(
+                column_type,
+                ColumnType::SmallInt | ColumnType::Int | ColumnType::BigInt | ColumnType::Int128
+            )
+        });
+        if left_type != right_type && !are_both_integers {
+            return Err(ConversionError::DataTypeMismatch(
+                left_type.to_string(),
+                right_type.to_string(),
+            ));
+        }
+        Ok(JoinContext {
+            right_table: right,
+            left_key,
+            right_key,
+        })
+    }
+
+    fn resolve_join_column(
+        &self,
+        column: &JoinColumn,
+        left: TableRef,
+        right: TableRef,
+    ) -> ConversionResult<ColumnRef> {
+        let candidates = match column {
+            JoinColumn::Qualified(column) => vec![self.lookup_qualified_table(column)?],
+            JoinColumn::Column(_) => vec![left, right],
+        };
+        let column_name = column.column();
+        let matches = Vec::from_iter(candidates.into_iter().filter_map(|table_ref| {
+            self.schema_accessor
+                .lookup_column(table_ref, column_name)
+                .map(|column_type| ColumnRef::new(table_ref, column_name, column_type))
+        }));
+        match (&matches[..], &candidates[..]) {
+            ([column_ref], _) => Ok(*column_ref),
+            ([], [table_ref]) => Err(ConversionError::MissingColumn(
+                Box::new(column_name),
+                Box::new(table_ref.resource_id()),
+            )),
+            ([], _) => Err(ConversionError::MissingColumnWithoutTable(Box::new(
+                column_name,
+            ))),
+            _ => Err(ConversionError::AmbiguousColumn(Box::new(column_name))),
+        }
+    }
+
+    /// Takes the subquery conditions out of a `WHERE` clause, and returns what is left of it.
+    fn visit_subquery_conditions(
+        &mut self,
+        expr: Expression,
+    ) -> ConversionResult<Option<Box<Expression>>> {
+        match expr {
+            Expression::Binary {
+                op: BinaryOperator::And,
+                left,
+                right,
+            } => {
+                let left = self.visit_subquery_conditions(*left)?;
+                let right = self.visit_subquery_conditions(*right)?;
+                Ok(match (left, right) {
+                    (Some(left), Some(right)) => Some(Box::new(Expression::Binary {
+                        op: BinaryOperator::And,
+                        left,
+                        right,
+                    })),
+                    (left, right) => left.or(right),
+                })
+            }
+            expr if is_subquery_condition(&expr) => {
+                let mut subquery = subquery_condition(expr);
+                if let SubqueryContext::In { expr, .. } | SubqueryContext::Compare { expr, .. } =
+                    &mut subquery
+                {
+                    self.visit_expr(expr)?;
+                }
+                self.context.push_subquery(subquery);
+                Ok(None)
+            }
+            expr => Ok(Some(Box::new(expr))),
+        }
+    }
+
+    /// Replaces each aggregation and column of a `HAVING` condition with a hidden result column.
+    fn visit_having_operands(
+        &mut self,
+        expr: &mut Expression,
+        num_result_exprs: &mut usize,
+    ) -> ConversionResult<()> {
+        match expr {
+            // A group by expression, e.g. `a + 1` in `GROUP BY a + 1`, is an operand as a whole.
+            _ if matches!(
+                expr,
+                Expression::Aggregation { .. }
+                    | Expression::Column(_)
+                    | Expression::QualifiedColumn(_)
+            )
+                || self.context.is_grouped_expr(expr) =>
+            {
+                let alias = Identifier::try_new(format!("__having__{num_result_exprs}"))
+                    .map_err(|e| ConversionError::InvalidExpression(e.to_string()))?;
+                let mut operand = Box::new(expr.clone());
+                self.visit_expr(&mut operand)?;
+                self.context
+                    .push_aliased_result_expr(AliasedResultExpr {
+                        expr: operand,
+                        alias,
+                    })?;
+                *num_result_exprs += 1;
+                *expr = Expression::Column(alias);
+            }
+            Expression::Literal(
+                Literal::Boolean(_) | Literal::BigInt(_) | Literal::Int128(_) | Literal::VarChar(_),
+            ) => {}
+            Expression::Unary { expr, .. } | Expression::Cast { expr, .. } => {
+                self.visit_having_operands(expr, num_result_exprs)?;
+            }
+            Expression::Binary { left, right, .. } => {
+                self.visit_having_operands(left, num_result_exprs)?;
+                self.visit_having_operands(right, num_result_exprs)?;
+            }
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => {
+                for (when_expr, then_expr) in when_then {
+                    self.visit_having_operands(when_expr, num_result_exprs)?;
+                    self.visit_having_operands(then_expr, num_result_exprs)?;
+                }
+                if let Some(else_expr) = else_expr {
+                    self.visit_having_operands(else_expr, num_result_exprs)?;
+                }
+            }
+            _ => {
+                return Err(ConversionError::InvalidExpression(
+                    "IN, BETWEEN, LIKE and literals of decimals, timestamps or dates are not supported in HAVING"
+                        .to_string(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
     fn visit_aliased_expr(&mut self, mut aliased_expr: AliasedResultExpr) -> ConversionResult<()> {
-        self.visit_expr(aliased_expr.expr.as_mut())?;
+        self.resolve_columns(&mut aliased_expr.expr)?;
+        match aliased_expr.expr.as_mut() {
+            // An average is computed exactly from a sum and a count after the query,
+            // so it has to be a whole result expression.
+            Expression::Aggregation {
+                op: op @ AggregationOperator::Avg,
+                expr,
+            } => {
+                let column_type = self.visit_agg_expr(op, expr)?;
+                self.context.push_average(aliased_expr.alias, column_type);
+            }
+            // A window function is computed over all the rows of the result, so it has to be a whole result expression.
+            Expression::Window {
+                function,
+                partition_by,
+                order_by,
+            } => {
+                self.visit_window_expr(function, partition_by, order_by)?;
+            }
+            expr => {
+                self.visit_expr(expr)?;
+            }
+        }
         self.context.push_aliased_result_expr(aliased_expr)?;
         Ok(())
     }
@@ -128,13 +783,46 @@ impl<'a> QueryContextBuilder<'a> {
     /// This function accepts the expression as a mutable reference because certain expressions
     /// require replacement, such as `count(*)` being replaced with `count(some_column)`.
     fn visit_expr(&mut self, expr: &mut Expression) -> ConversionResult<ColumnType> {
+        // Like a group by column, a group by expression in the result is remapped to an aggregation.
+        // Its columns are visited as if they were aggregated, since they need not be in the group by.
+        if self.context.is_grouped_expr(expr) {
+            self.context.set_in_agg_scope(true)?;
+            let dtype = self.visit_expr(expr)?;
+            self.context.set_in_agg_scope(false)?;
+            *expr = *expr.clone().first();
+            return Ok(dtype);
+        }
         match expr {
             Expression::Wildcard => self.visit_wildcard_expr(expr),
             Expression::Literal(literal) => self.visit_literal(literal.deref()),
             Expression::Column(_) => self.visit_column_expr(expr),
+            Expression::QualifiedColumn(_) => self.visit_qualified_column_expr(expr),
             Expression::Unary { op, expr } => self.visit_unary_expr(op, expr),
             Expression::Binary { op, left, right } => self.visit_binary_expr(op, left, right),
+            Expression::Aggregation {
+                op: AggregationOperator::Avg,
+                ..
+            } => Err(ConversionError::InvalidExpression(
+                "AVG can only be used as a whole result expression".to_string(),
+            )),
             Expression::Aggregation { op, expr } => self.visit_agg_expr(op, expr),
+            Expression::InList { expr, list } => self.visit_in_list_expr(expr, list),
+            Expression::Between { expr, low, high } => self.visit_between_expr(expr, low, high),
+            Expression::Like { expr, .. } => self.visit_like_expr(expr),
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => self.visit_case_expr(when_then, else_expr),
+            Expression::Cast { expr, data_type } => self.visit_cast_expr(expr, *data_type),
+            Expression::InSubquery { .. } | Expression::Exists(_) | Expression::Subquery(_) => {
+                Err(ConversionError::Unprovable(
+                    "subqueries are only supported as conditions of the WHERE clause joined by AND"
+                        .to_string(),
+                ))
+            }
+            Expression::Window { .. } => Err(ConversionError::InvalidExpression(
+                "window functions can only be used as whole result expressions".to_string(),
+            )),
         }
     }
 
@@ -151,6 +839,15 @@ impl<'a> QueryContextBuilder<'a> {
         // Visit the column to ensure its inclusion in the result column set.
         self.visit_column_expr(expr)?;
 
+        // `count(col_name)` does not count the nulls of `col_name`, unlike `count(*)`.
+        // So a nullable column is replaced with `count(col_name IS NOT NULL)`, which is never null.
+        if self.context.is_nullable(&col_name) {
+            *expr = Expression::Unary {
+                op: UnaryOperator::IsNotNull,
+                expr: Box::new(expr.clone()),
+            };
+        }
+
         // Return the column type
         Ok(col_type)
     }
@@ -171,6 +868,51 @@ impl<'a> QueryContextBuilder<'a> {
         self.visit_column_identifier(identifier)
     }
 
+    /// Visits a column of a join that is qualified by its table because the other table has a column
+    /// of the same name. Only one of the two can be referenced, since the columns of a join are named after
+    /// the columns themselves.
+    fn visit_qualified_column_expr(
+        &mut self,
+        expr: &mut Expression,
+    ) -> ConversionResult<ColumnType> {
+        let column = match expr {
+            Expression::QualifiedColumn(column) => column.clone(),
+            _ => panic!("Must be a qualified column expression"),
+        };
+        let table_ref = self.lookup_qualified_table(&column)?;
+        let column_name = column.column;
+        let column_type = self
+            .schema_accessor
+            .lookup_column(table_ref, column_name)
+            .ok_or_else(|| {
+                ConversionError::MissingColumn(
+                    Box::new(column_name),
+                    Box::new(table_ref.resource_id()),
+                )
+            })?;
+        // The left key is always a column of the join.
+        let left_key = self.context.get_join().map(|join| join.left_key);
+        if left_key
+            .iter()
+            .chain(self.context.get_column_ref(&column_name))
+            .any(|column_ref| {
+                column_ref.column_id() == column_name && column_ref.table_ref() != table_ref
+            })
+        {
+            return Err(ConversionError::Unprovable(format!(
+                "the columns '{column_name}' of both tables of a join can not be referenced together"
+            )));
+        }
+
+        *expr = Expression::Column(column_name);
+        if self.context.is_in_group_by_exprs(&column_name)? {
+            *expr = *Expression::Column(column_name).first();
+        }
+
+        self.push_column_ref(ColumnRef::new(table_ref, column_name, column_type));
+        Ok(column_type)
+    }
+
     fn visit_binary_expr(
         &mut self,
         op: &BinaryOperator,
@@ -193,6 +935,86 @@ impl<'a> QueryContextBuilder<'a> {
         }
     }
 
+    fn visit_in_list_expr(
+        &mut self,
+        expr: &mut Expression,
+        list: &[Literal],
+    ) -> ConversionResult<ColumnType> {
+        let dtype = self.visit_expr(expr)?;
+        for literal in list {
+            check_dtypes(dtype, self.visit_literal(literal)?, BinaryOperator::Equal)?;
+        }
+        Ok(ColumnType::Boolean)
+    }
+
+    fn visit_between_expr(
+        &mut self,
+        expr: &mut Expression,
+        low: &mut Expression,
+        high: &mut Expression,
+    ) -> ConversionResult<ColumnType> {
+        let dtype = self.visit_expr(expr)?;
+        check_dtypes(
+            dtype,
+            self.visit_expr(low)?,
+            BinaryOperator::LessThanOrEqual,
+        )?;
+        check_dtypes(
+            dtype,
+            self.visit_expr(high)?,
+            BinaryOperator::LessThanOrEqual,
+        )?;
+        Ok(ColumnType::Boolean)
+    }
+
+    fn visit_like_expr(&mut self, expr: &mut Expression) -> ConversionResult<ColumnType> {
+        let dtype = self.visit_expr(expr)?;
+        if dtype != ColumnType::VarChar {
+            return Err(ConversionError::InvalidDataType {
+                expected: ColumnType::VarChar,
+                actual: dtype,
+            });
+        }
+        Ok(ColumnType::Boolean)
+    }
+
+    fn visit_case_expr(
+        &mut self,
+        when_then: &mut [(Box<Expression>, Box<Expression>)],
+        else_expr: &mut Option<Box<Expression>>,
+    ) -> ConversionResult<ColumnType> {
+        let mut result_dtypes = Vec::with_capacity(when_then.len() + 1);
+        for (when, then) in when_then {
+            let dtype = self.visit_expr(when)?;
+            if dtype != ColumnType::Boolean {
+                return Err(ConversionError::InvalidDataType {
+                    expected: ColumnType::Boolean,
+                    actual: dtype,
+                });
+            }
+            result_dtypes.push(self.visit_expr(then)?);
+        }
+        if let Some(else_expr) = else_expr {
+            result_dtypes.push(self.visit_expr(else_expr)?);
+        }
+        let mut result_dtypes = result_dtypes.into_iter();
+        let first_dtype = result_dtypes
+            .next()
+            .expect("the parser ensures that there is at least one result");
+        result_dtypes.try_fold(first_dtype, try_common_column_type)
+    }
+
+    fn visit_cast_expr(
+        &mut self,
+        expr: &mut Expression,
+        data_type: DataType,
+    ) -> ConversionResult<ColumnType> {
+        let from_dtype = self.visit_expr(expr)?;
+        let to_dtype = ColumnType::try_from(data_type)?;
+        check_cast_column_types(from_dtype, to_dtype)?;
+        Ok(to_dtype)
+    }
+
     fn visit_unary_expr(
         &mut self,
         op: &UnaryOperator,
@@ -209,6 +1031,10 @@ impl<'a> QueryContextBuilder<'a> {
                 }
                 Ok(ColumnType::Boolean)
             }
+            UnaryOperator::IsNull | UnaryOperator::IsNotNull => {
+                self.visit_expr(expr)?;
+                Ok(ColumnType::Boolean)
+            }
         }
     }
 
@@ -221,8 +1047,15 @@ impl<'a> QueryContextBuilder<'a> {
 
         let expr_dtype = self.visit_expr(expr)?;
 
-        // We only support sum/max/min aggregations on numeric columns.
-        if op != &AggregationOperator::Count && expr_dtype == ColumnType::VarChar {
+        // We only support sum/max/min aggregations on numeric columns, and max/min on temporal columns.
+        let is_temporal = matches!(expr_dtype, ColumnType::TimeStamp(_, _) | ColumnType::Date);
+        let is_count = matches!(
+            op,
+            AggregationOperator::Count | AggregationOperator::CountDistinct
+        );
+        if !is_count && expr_dtype == ColumnType::VarChar
+            || op == &AggregationOperator::Sum && is_temporal
+        {
             return Err(ConversionError::non_numeric_expr_in_agg(
                 expr_dtype.to_string(),
                 op.to_string(),
@@ -231,11 +1064,55 @@ impl<'a> QueryContextBuilder<'a> {
 
         self.context.set_in_agg_scope(false)?;
 
-        // Count aggregation always results in an integer type
-        if op == &AggregationOperator::Count {
-            Ok(ColumnType::BigInt)
-        } else {
-            Ok(expr_dtype)
+        match op {
+            // Count aggregation always results in an integer type
+            AggregationOperator::Count | AggregationOperator::CountDistinct => {
+                Ok(ColumnType::BigInt)
+            }
+            AggregationOperator::Avg => try_average_column_type(expr_dtype),
+            _ => Ok(expr_dtype),
+        }
+    }
+
+    /// Visits the columns that a window function partitions and orders the rows by and returns its data type.
+    ///
+    /// Only a running `SUM` of a numeric column is supported, besides `ROW_NUMBER()` and `RANK()`.
+    fn visit_window_expr(
+        &mut self,
+        function: &WindowFunction,
+        partition_by: &[Identifier],
+        order_by: &[OrderBy],
+    ) -> ConversionResult<ColumnType> {
+        for column in partition_by
+            .iter()
+            .chain(order_by.iter().map(|order_by| &order_by.expr))
+        {
+            self.visit_column_identifier(*column)?;
+        }
+        match function {
+            WindowFunction::RowNumber | WindowFunction::Rank => {
+                // `ROW_NUMBER() OVER ()` references no column, but the rows of the table still have to be returned.
+                if partition_by.is_empty() && order_by.is_empty() {
+                    let (column_name, _) = self.lookup_schema().into_iter().next().unwrap();
+                    self.visit_column_identifier(column_name)?;
+                }
+                Ok(ColumnType::BigInt)
+            }
+            WindowFunction::Sum(expr) => {
+                let Expression::Column(column_name) = **expr else {
+                    return Err(ConversionError::InvalidExpression(
+                        "only columns can be summed by a window function".to_string(),
+                    ));
+                };
+                let dtype = self.visit_column_identifier(column_name)?;
+                if !dtype.is_numeric() {
+                    return Err(ConversionError::non_numeric_expr_in_agg(
+                        dtype.to_string(),
+                        AggregationOperator::Sum.to_string(),
+                    ));
+                }
+                Ok(dtype)
+            }
         }
     }
 
@@ -249,22 +1126,67 @@ impl<'a> QueryContextBuilder<'a> {
                 let precision = Precision::new(d.precision())?;
                 Ok(ColumnType::Decimal75(precision, d.scale()))
             }
+            Literal::Timestamp(its) => Ok(ColumnType::TimeStamp(its.unit(), its.timezone())),
+            Literal::Date(_) => Ok(ColumnType::Date),
         }
     }
 
     fn visit_column_identifier(&mut self, column_name: Identifier) -> ConversionResult<ColumnType> {
-        let table_ref = self.context.get_table_ref();
-        let column_type = self.schema_accessor.lookup_column(*table_ref, column_name);
-
-        let column_type = column_type.ok_or_else(|| {
-            ConversionError::MissingColumn(Box::new(column_name), Box::new(table_ref.resource_id()))
-        })?;
-
-        let column = ColumnRef::new(*table_ref, column_name, column_type);
+        let table_ref = *self.context.get_table_ref();
+        let column_type = self.schema_accessor.lookup_column(table_ref, column_name);
 
-        self.context.push_column_ref(column_name, column);
+        let column = match (column_type, self.context.get_join().copied()) {
+            (Some(column_type), None) => ColumnRef::new(table_ref, column_name, column_type),
+            (column_type, Some(join)) => {
+                let right_column_type = self
+                    .schema_accessor
+                    .lookup_column(join.right_table, column_name);
+                match (column_type, right_column_type) {
+                    (Some(column_type), None) => {
+                        ColumnRef::new(table_ref, column_name, column_type)
+                    }
+                    (None, Some(column_type)) => {
+                        ColumnRef::new(join.right_table, column_name, column_type)
+                    }
+                    // Joining on columns with the same name makes them the same column of the result.
+                    (Some(_), Some(_))
+                        if join.left_key.column_id() == column_name
+                            && join.right_key.column_id() == column_name =>
+                    {
+                        join.left_key
+                    }
+                    (Some(_), Some(_)) => {
+                        return Err(ConversionError::AmbiguousColumn(Box::new(column_name)))
+                    }
+                    (None, None) => {
+                        return Err(ConversionError::MissingColumnWithoutTable(Box::new(
+                            column_name,
+                        )))
+                    }
+                }
+            }
+            (None, None) => {
+                return Err(ConversionError::MissingColumn(
+                    Box::new(column_name),
+                    Box::new(table_ref.resource_id()),
+                ))
+            }
+        };
+        self.push_column_ref(column);
+        Ok(*column.column_type())
+    }
 
-        Ok(column_type)
+    /// Registers a column that the query references, together with its validity and order key columns.
+    fn push_column_ref(&mut self, column: ColumnRef) {
+        self.context.push_column_ref(column.column_id(), column);
+        if let Some(validity_column) = self.lookup_validity_column(&column) {
+            self.context
+                .push_validity_column_ref(validity_column.column_id(), validity_column);
+        }
+        if let Some(order_key_column) = self.lookup_order_key_column(&column) {
+            self.context
+                .push_order_key_column_ref(order_key_column.column_id(), order_key_column);
+        }
     }
 }
 
@@ -285,6 +1207,8 @@ pub(crate) fn type_check_binary_operation(
                 (left_dtype, right_dtype),
                 (ColumnType::VarChar, ColumnType::VarChar)
                     | (ColumnType::Boolean, ColumnType::Boolean)
+                    | (ColumnType::TimeStamp(_, _), ColumnType::TimeStamp(_, _))
+                    | (ColumnType::Date, ColumnType::Date)
                     | (_, ColumnType::Scalar)
                     | (ColumnType::Scalar, _)
             ) || (left_dtype.is_numeric() && right_dtype.is_numeric())
@@ -308,6 +1232,8 @@ pub(crate) fn type_check_binary_operation(
                 || matches!(
                     (left_dtype, right_dtype),
                     (ColumnType::Boolean, ColumnType::Boolean)
+                        | (ColumnType::TimeStamp(_, _), ColumnType::TimeStamp(_, _))
+                        | (ColumnType::Date, ColumnType::Date)
                 )
         }
         BinaryOperator::Multiply
@@ -322,7 +1248,15 @@ fn check_dtypes(
     right_dtype: ColumnType,
     binary_operator: BinaryOperator,
 ) -> ConversionResult<()> {
-    if type_check_binary_operation(&left_dtype, &right_dtype, binary_operator) {
+    // `VarChar` values are compared through their order keys.
+    let is_varchar_comparison = matches!(
+        binary_operator,
+        BinaryOperator::GreaterThanOrEqual | BinaryOperator::LessThanOrEqual
+    ) && left_dtype == ColumnType::VarChar
+        && right_dtype == ColumnType::VarChar;
+    if is_varchar_comparison
+        || type_check_binary_operation(&left_dtype, &right_dtype, binary_operator)
+    {
         Ok(())
     } else {
         Err(ConversionError::DataTypeMismatch(
@@ -331,3 +1265,135 @@ fn check_dtypes(
         ))
     }
 }
+
+/// Returns the result expression at the given 1-based position of a `GROUP BY` clause.
+fn resolve_group_by_position(
+    position: i64,
+    result_exprs: &[SelectResultExpr],
+) -> ConversionResult<Expression> {
+    let result_expr = usize::try_from(position)
+        .ok()
+        .and_then(|position| position.checked_sub(1))
+        .and_then(|index| result_exprs.get(index));
+    match result_expr {
+        Some(SelectResultExpr::AliasedResultExpr(AliasedResultExpr { expr, .. })) => {
+            Ok((**expr).clone())
+        }
+        Some(SelectResultExpr::ALL) => Err(ConversionError::InvalidExpression(format!(
+            "GROUP BY position {position} can not refer to *"
+        ))),
+        None => Err(ConversionError::InvalidExpression(format!(
+            "GROUP BY position {position} is not in the select list"
+        ))),
+    }
+}
+
+/// Returns the comparison with a subquery that a binary operator is, if any.
+fn subquery_comparison(op: BinaryOperator, negated: bool) -> Option<SubqueryComparison> {
+    match (op, negated) {
+        (BinaryOperator::Equal, false) => Some(SubqueryComparison::Equal),
+        (BinaryOperator::Equal, true) => Some(SubqueryComparison::NotEqual),
+        (BinaryOperator::LessThanOrEqual, false) => Some(SubqueryComparison::LessThanOrEqual),
+        (BinaryOperator::LessThanOrEqual, true) => Some(SubqueryComparison::GreaterThan),
+        (BinaryOperator::GreaterThanOrEqual, false) => Some(SubqueryComparison::GreaterThanOrEqual),
+        (BinaryOperator::GreaterThanOrEqual, true) => Some(SubqueryComparison::LessThan),
+        _ => None,
+    }
+}
+
+/// Returns true if the expression is `[NOT] IN (<subquery>)`, `[NOT] EXISTS (<subquery>)`
+/// or a comparison with a subquery, possibly negated.
+fn is_subquery_condition(expr: &Expression) -> bool {
+    let (expr, negated) = match expr {
+        Expression::Unary {
+            op: UnaryOperator::Not,
+            expr,
+        } => (expr.as_ref(), true),
+        expr => (expr, false),
+    };
+    match expr {
+        Expression::InSubquery { .. } | Expression::Exists(_) => true,
+        Expression::Binary { op, left, right } => {
+            subquery_comparison(*op, negated).is_some()
+                && [left, right]
+                    .iter()
+                    .any(|operand| matches!(***operand, Expression::Subquery(_)))
+        }
+        _ => false,
+    }
+}
+
+/// Converts a subquery condition, see [`is_subquery_condition`].
+///
+/// A comparison with a subquery on its left is turned around, e.g. `(SELECT ...) <= a` is `a >= (SELECT ...)`.
+fn subquery_condition(expr: Expression) -> SubqueryContext {
+    let (expr, negated) = match expr {
+        Expression::Unary {
+            op: UnaryOperator::Not,
+            expr,
+        } => (*expr, true),
+        expr => (expr, false),
+    };
+    match expr {
+        Expression::InSubquery { expr, subquery } => SubqueryContext::In {
+            expr: *expr,
+            subquery: *subquery,
+            negated,
+        },
+        Expression::Exists(subquery) => SubqueryContext::Exists {
+            subquery: *subquery,
+            negated,
+        },
+        Expression::Binary { op, left, right } => {
+            let (op, expr, subquery) = match (*left, *right) {
+                (expr, Expression::Subquery(subquery)) => (op, expr, subquery),
+                (Expression::Subquery(subquery), expr) => match op {
+                    BinaryOperator::LessThanOrEqual => {
+                        (BinaryOperator::GreaterThanOrEqual, expr, subquery)
+                    }
+                    BinaryOperator::GreaterThanOrEqual => {
+                        (BinaryOperator::LessThanOrEqual, expr, subquery)
+                    }
+                    op => (op, expr, subquery),
+                },
+                _ => unreachable!("a comparison with a subquery has a subquery operand"),
+            };
+            SubqueryContext::Compare {
+                expr,
+                op: subquery_comparison(op, negated).expect("the operator is a comparison"),
+                subquery: *subquery,
+            }
+        }
+        _ => unreachable!("the expression is a subquery condition"),
+    }
+}
+
+/// Returns true if the post-processing step is able to evaluate the expression,
+/// which is the case for columns, literals of simple types and the operators over them.
+fn is_supported_in_post_processing(expr: &Expression) -> bool {
+    match expr {
+        Expression::Column(_)
+        | Expression::QualifiedColumn(_)
+        | Expression::Literal(
+            Literal::Boolean(_) | Literal::BigInt(_) | Literal::Int128(_) | Literal::VarChar(_),
+        ) => true,
+        Expression::Unary { expr, .. } | Expression::Cast { expr, .. } => {
+            is_supported_in_post_processing(expr)
+        }
+        Expression::Binary { left, right, .. } => {
+            is_supported_in_post_processing(left) && is_supported_in_post_processing(right)
+        }
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => {
+            when_then.iter().all(|(when_expr, then_expr)| {
+                is_supported_in_post_processing(when_expr)
+                    && is_supported_in_post_processing(then_expr)
+            }) && else_expr
+                .as_deref()
+                .map_or(true, is_supported_in_post_processing)
+        }
+        _ => false,
+    }
+}
@@ -1,13 +1,35 @@
-use super::{FilterExprBuilder, QueryContextBuilder, ResultExprBuilder};
+use super::{
+    average_count_column_id, window_column_id, CteContext, CteSchemaAccessor, Ctes,
+    FilterExprBuilder, ProvenCte, QueryContext, QueryContextBuilder, ResultExprBuilder,
+    SubqueryContext, WhereExprBuilder,
+};
 use crate::{
-    base::{commitment::Commitment, database::SchemaAccessor},
+    base::{
+        commitment::Commitment,
+        database::{
+            ColumnField, ColumnType, LiteralValue, OwnedColumn, OwnedTable, SchemaAccessor,
+            TableRef,
+        },
+    },
     sql::{
-        ast::{GroupByExpr, ProofPlan},
-        parse::ConversionResult,
+        ast::{
+            AliasedProvableExprPlan, DenseFilterExpr, GroupByExpr, JoinExpr, ProofPlan,
+            ProvableExpr, ProvableExprPlan, SubqueryAggregation, SubqueryCondition,
+            SubqueryFilterExpr, UnionExpr,
+        },
+        parse::{ConversionError, ConversionResult},
+        proof::ProofExpr,
         transform::ResultExpr,
     },
 };
-use proof_of_sql_parser::{intermediate_ast::SetExpression, Identifier, SelectStatement};
+use arrow::{array::new_empty_array, datatypes::DataType, record_batch::RecordBatch};
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AggregationOperator, AliasedResultExpr, CommonTableExpression, Expression, OrderBy,
+        SetExpression, Slice,
+    },
+    Identifier, ResourceId, SelectStatement,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -43,29 +65,283 @@ impl<C: Commitment> QueryExpr<C> {
         default_schema: Identifier,
         schema_accessor: &dyn SchemaAccessor,
     ) -> ConversionResult<Self> {
-        let context = match *ast.expr {
+        let ctes = build_ctes::<C>(ast.ctes, default_schema, schema_accessor)?;
+        Self::try_new_with_ctes(
+            *ast.expr,
+            ast.order_by,
+            ast.slice,
+            &ctes,
+            default_schema,
+            schema_accessor,
+        )
+    }
+
+    /// Parse a query, with the common table expressions that are in its scope, into a `QueryExpr`.
+    fn try_new_with_ctes(
+        expr: SetExpression,
+        order_by: Vec<OrderBy>,
+        slice: Option<Slice>,
+        ctes: &Ctes,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        // The results of the common table expressions that are proven on their own are looked up as tables.
+        let cte_schema_accessor = CteSchemaAccessor::new(schema_accessor, ctes);
+        let schema_accessor: &dyn SchemaAccessor = &cte_schema_accessor;
+        let context = match expr {
             SetExpression::Query {
                 result_exprs,
                 from,
                 where_expr,
                 group_by,
+                having,
+                distinct,
             } => QueryContextBuilder::new(schema_accessor)
-                .visit_table_expr(from, default_schema)
-                .visit_group_by_exprs(group_by)?
+                .visit_ctes(&ctes.contexts)
+                .visit_table_expr(from, default_schema)?
+                .visit_group_by_exprs(group_by, &result_exprs)?
+                .visit_distinct(distinct, &result_exprs)?
                 .visit_result_exprs(result_exprs)?
+                .visit_having_expr(having)?
                 .visit_where_expr(where_expr)?
-                .visit_order_by_exprs(ast.order_by)
-                .visit_slice_expr(ast.slice)
+                .visit_order_by_exprs(order_by)
+                .visit_slice_expr(slice)
                 .build()?,
+            SetExpression::Union { left, right, all } => {
+                let mut queries = Vec::new();
+                push_union_queries(*left, all, &mut queries)?;
+                push_union_queries(*right, all, &mut queries)?;
+                return Self::try_new_union(
+                    queries,
+                    all,
+                    &order_by,
+                    &slice,
+                    ctes,
+                    default_schema,
+                    schema_accessor,
+                );
+            }
         };
         let result_aliased_exprs = context.get_aliased_result_exprs()?;
-        let group_by = context.get_group_by_exprs();
-        if !group_by.is_empty() {
+        // The result expressions of the HAVING clause are dropped once it is applied.
+        let (selected_aliased_exprs, having_aliased_exprs) = result_aliased_exprs
+            .split_at(result_aliased_exprs.len() - context.get_num_having_result_exprs());
+        let averages = get_averages(&context, result_aliased_exprs)?;
+        // Averages are computed by the post-processing step from sums and counts.
+        let split_aliased_exprs = split_averages(result_aliased_exprs, &averages);
+        // A common table expression that is proven on its own only proves its result,
+        // so the query that selects from it is done by the post-processing step, as it would be for a join.
+        if let Some(proven_cte) = ctes.proven.get(context.get_table_ref()) {
+            if context.has_windows() || !context.get_subqueries().is_empty() {
+                return Err(ConversionError::Unprovable(
+                    "window functions and subqueries are not supported in queries that select from a common table expression with aggregations, GROUP BY, DISTINCT, JOIN, UNION, window functions or subqueries"
+                        .to_owned(),
+                ));
+            }
+            let cte_query = Self::try_new_with_ctes(
+                proven_cte.query.clone(),
+                Vec::new(),
+                None,
+                &proven_cte.ctes,
+                default_schema,
+                schema_accessor,
+            )?;
+            let mut columns = Vec::from_iter(context.get_column_mapping().into_keys());
+            columns.sort();
+            let filter = ResultExprBuilder::default()
+                .add_where_expr(context.get_where_expr().as_deref(), columns)
+                .build();
+            let result = ResultExprBuilder::default()
+                .add_group_by_exprs(context.get_group_by_exprs(), &split_aliased_exprs)
+                .add_select_exprs(&split_aliased_exprs)
+                .add_average_exprs(averages, result_aliased_exprs)
+                .add_having_expr(
+                    context.get_having_expr(),
+                    having_aliased_exprs,
+                    selected_aliased_exprs,
+                )
+                .add_order_by_exprs(context.get_order_by_exprs()?)
+                .add_slice_expr(context.get_slice_expr())
+                .build();
+            return Ok(Self {
+                proof_expr: cte_query.proof_expr,
+                result: cte_query.result.chain(filter).chain(result),
+            });
+        }
+        // Window functions are computed over all the rows of the query, so they are only supported in filters.
+        if context.has_windows() {
+            if context.is_aggregation()
+                || context.get_join().is_some()
+                || !context.get_subqueries().is_empty()
+            {
+                return Err(ConversionError::Unprovable(
+                    "window functions are not supported in queries with aggregations, joins or subqueries"
+                        .to_owned(),
+                ));
+            }
+            // A running sum over a strictly increasing column is proven along with the filter of the rows.
+            if let Some(running_sum_expr) = context.get_running_sum_expr(schema_accessor)? {
+                return Ok(Self {
+                    proof_expr: ProofPlan::RunningSum(running_sum_expr),
+                    result: ResultExprBuilder::default()
+                        .add_order_by_exprs(context.get_order_by_exprs()?)
+                        .add_slice_expr(context.get_slice_expr())
+                        .build(),
+                });
+            }
+            let (windows, selection) = split_windows(result_aliased_exprs)?;
+            let filter = FilterExprBuilder::new(context.get_column_mapping())
+                .add_table_expr(*context.get_table_ref())
+                .add_where_expr(context.get_where_expr().clone())?
+                .add_result_column_set(context.get_result_column_set())
+                .build();
+            let result = ResultExprBuilder::default()
+                .add_window_exprs(windows, &selection)
+                .add_order_by_exprs(context.get_order_by_exprs()?)
+                .add_slice_expr(context.get_slice_expr())
+                .build();
+            return Ok(Self {
+                proof_expr: ProofPlan::DenseFilter(filter),
+                result,
+            });
+        }
+        // The subquery conditions of the WHERE clause are proven along with the filter of the rows,
+        // so the rest of the query is done as it would be for a filter.
+        if !context.get_subqueries().is_empty() {
+            if context.get_join().is_some() {
+                return Err(ConversionError::Unprovable(
+                    "subqueries are not supported in queries with joins".to_owned(),
+                ));
+            }
+            let conditions = context
+                .get_subqueries()
+                .iter()
+                .map(|subquery| {
+                    build_subquery_condition(
+                        subquery,
+                        &context,
+                        ctes,
+                        default_schema,
+                        schema_accessor,
+                    )
+                })
+                .collect::<ConversionResult<Vec<_>>>()?;
+            let filter = FilterExprBuilder::new(context.get_column_mapping())
+                .add_table_expr(*context.get_table_ref())
+                .add_where_expr(context.get_where_expr().clone())?;
+            let (filter, result) = match context.get_provable_result_exprs()? {
+                Some(aliased_results) => (
+                    filter.add_result_columns(aliased_results).build(),
+                    ResultExprBuilder::default()
+                        .add_order_by_exprs(context.get_order_by_exprs()?)
+                        .add_slice_expr(context.get_slice_expr())
+                        .build(),
+                ),
+                None => (
+                    filter
+                        .add_result_column_set(context.get_result_column_set())
+                        .build(),
+                    ResultExprBuilder::default()
+                        .add_group_by_exprs(context.get_group_by_exprs(), &split_aliased_exprs)
+                        .add_select_exprs(&split_aliased_exprs)
+                        .add_average_exprs(averages, result_aliased_exprs)
+                        .add_having_expr(
+                            context.get_having_expr(),
+                            having_aliased_exprs,
+                            selected_aliased_exprs,
+                        )
+                        .add_order_by_exprs(context.get_order_by_exprs()?)
+                        .add_slice_expr(context.get_slice_expr())
+                        .build(),
+                ),
+            };
+            return Ok(Self {
+                proof_expr: ProofPlan::SubqueryFilter(SubqueryFilterExpr::new(filter, conditions)),
+                result,
+            });
+        }
+        // A join only proves the joined rows, so the rest of the query is done by the post-processing step.
+        if let Some(join_expr) = Option::<JoinExpr<C>>::try_from(&context)? {
+            let result = ResultExprBuilder::default()
+                .add_group_by_exprs(context.get_group_by_exprs(), &split_aliased_exprs)
+                .add_select_exprs(&split_aliased_exprs)
+                .add_average_exprs(averages, result_aliased_exprs)
+                .add_having_expr(
+                    context.get_having_expr(),
+                    having_aliased_exprs,
+                    selected_aliased_exprs,
+                )
+                .add_order_by_exprs(context.get_order_by_exprs()?)
+                .add_slice_expr(context.get_slice_expr())
+                .build();
+            return Ok(Self {
+                proof_expr: ProofPlan::Join(Box::new(join_expr)),
+                result,
+            });
+        }
+        // Queries with aggregations, whether or not they have a GROUP BY, may be proven by a `GroupByExpr`.
+        if context.is_aggregation() {
             if let Some(group_by_expr) = Option::<GroupByExpr<C>>::try_from(&context)? {
+                let averages = group_by_averages(
+                    &group_by_expr.get_column_result_fields(),
+                    averages,
+                    group_by_expr.count_alias(),
+                );
+                // The count columns of the averages are kept until the averages are computed.
+                let mut selection = group_by_result_selection(result_aliased_exprs);
+                for (_, count, _) in &averages {
+                    if selection
+                        .iter()
+                        .all(|aliased_expr| aliased_expr.alias != *count)
+                    {
+                        selection.push(AliasedResultExpr {
+                            expr: Box::new(Expression::Column(*count)),
+                            alias: *count,
+                        });
+                    }
+                }
+                // Without GROUP BY, the result is a single row even when no row is selected.
+                let result = if context.get_group_by_exprs().is_empty() {
+                    ResultExprBuilder::default().add_empty_aggregation_expr(
+                        group_by_expr.count_alias(),
+                        group_by_count_columns(
+                            result_aliased_exprs,
+                            &averages,
+                            group_by_expr.count_alias(),
+                        ),
+                    )
+                } else {
+                    ResultExprBuilder::default()
+                };
+                return Ok(Self {
+                    proof_expr: ProofPlan::GroupBy(group_by_expr),
+                    result: result
+                        .add_select_exprs(&selection)
+                        .add_average_exprs(averages, result_aliased_exprs)
+                        .add_having_expr(
+                            context.get_having_expr(),
+                            having_aliased_exprs,
+                            selected_aliased_exprs,
+                        )
+                        .add_order_by_exprs(context.get_order_by_exprs()?)
+                        .add_slice_expr(context.get_slice_expr())
+                        .build(),
+                });
+            }
+            // Distinct counts are proven by grouping the rows by the counted column too,
+            // which leaves the post-processing step with counting the proven distinct values.
+            if let Some(group_by_expr) = context.get_count_distinct_group_by_expr::<C>()? {
+                let counted_aliased_exprs = count_distinct_values(result_aliased_exprs);
                 return Ok(Self {
                     proof_expr: ProofPlan::GroupBy(group_by_expr),
                     result: ResultExprBuilder::default()
-                        .add_select_exprs(result_aliased_exprs)
+                        .add_group_by_exprs(context.get_group_by_exprs(), &counted_aliased_exprs)
+                        .add_select_exprs(&counted_aliased_exprs)
+                        .add_having_expr(
+                            context.get_having_expr(),
+                            having_aliased_exprs,
+                            selected_aliased_exprs,
+                        )
                         .add_order_by_exprs(context.get_order_by_exprs()?)
                         .add_slice_expr(context.get_slice_expr())
                         .build(),
@@ -73,14 +349,52 @@ impl<C: Commitment> QueryExpr<C> {
             }
         }
 
+        // When every result expression is provable, the projection is done by the filter itself,
+        // so only the ordering and slicing are left to the post-processing step.
+        if let Some(aliased_results) = context.get_provable_result_exprs()? {
+            let top_k = context.get_top_k(&aliased_results);
+            let provable_slice = context.get_provable_slice();
+            let filter = FilterExprBuilder::new(context.get_column_mapping())
+                .add_table_expr(*context.get_table_ref())
+                .add_where_expr(context.get_where_expr().clone())?
+                .add_result_columns(aliased_results);
+            // An ORDER BY with a LIMIT is proven as a top k when it can be, so that only the rows
+            // of the result are sent, while still being ordered by the post-processing step.
+            // Without an ORDER BY, the slice itself is proven by the filter.
+            let proof_expr = match (top_k, provable_slice) {
+                (Some((order_index, direction, limit)), _) => {
+                    ProofPlan::TopK(filter.build_top_k(order_index, direction, limit))
+                }
+                (None, Some((limit, offset))) => {
+                    ProofPlan::DenseFilter(filter.build().with_slice(limit, offset))
+                }
+                (None, None) => ProofPlan::DenseFilter(filter.build()),
+            };
+            let slice = match provable_slice {
+                Some(_) => &None,
+                None => context.get_slice_expr(),
+            };
+            let result = ResultExprBuilder::default()
+                .add_order_by_exprs(context.get_order_by_exprs()?)
+                .add_slice_expr(slice)
+                .build();
+            return Ok(Self { proof_expr, result });
+        }
+
         let filter = FilterExprBuilder::new(context.get_column_mapping())
             .add_table_expr(*context.get_table_ref())
             .add_where_expr(context.get_where_expr().clone())?
             .add_result_column_set(context.get_result_column_set())
             .build();
         let result = ResultExprBuilder::default()
-            .add_group_by_exprs(context.get_group_by_exprs(), result_aliased_exprs)
-            .add_select_exprs(result_aliased_exprs)
+            .add_group_by_exprs(context.get_group_by_exprs(), &split_aliased_exprs)
+            .add_select_exprs(&split_aliased_exprs)
+            .add_average_exprs(averages, result_aliased_exprs)
+            .add_having_expr(
+                context.get_having_expr(),
+                having_aliased_exprs,
+                selected_aliased_exprs,
+            )
             .add_order_by_exprs(context.get_order_by_exprs()?)
             .add_slice_expr(context.get_slice_expr())
             .build();
@@ -91,6 +405,121 @@ impl<C: Commitment> QueryExpr<C> {
         })
     }
 
+    /// Builds a `UnionExpr` out of the queries of a `UNION` or a `UNION ALL`, each of which must be provable
+    /// by a `DenseFilterExpr` on its own.
+    ///
+    /// The post-processing step removes the duplicate rows of a `UNION`, then orders and slices the result.
+    fn try_new_union(
+        queries: Vec<SetExpression>,
+        all: bool,
+        order_by: &[OrderBy],
+        slice: &Option<Slice>,
+        ctes: &Ctes,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let mut inputs: Vec<DenseFilterExpr<C>> = Vec::with_capacity(queries.len());
+        for query in queries {
+            let SetExpression::Query {
+                result_exprs,
+                from,
+                where_expr,
+                group_by,
+                having,
+                distinct,
+            } = query
+            else {
+                unreachable!("the queries of a union are flattened")
+            };
+            let context = QueryContextBuilder::new(schema_accessor)
+                .visit_ctes(&ctes.contexts)
+                .visit_table_expr(from, default_schema)?
+                .visit_group_by_exprs(group_by, &result_exprs)?
+                .visit_distinct(distinct, &result_exprs)?
+                .visit_result_exprs(result_exprs)?
+                .visit_having_expr(having)?
+                .visit_where_expr(where_expr)?
+                .build()?;
+            check_not_proven_cte(&context, ctes, "the queries of a UNION")?;
+            let aliased_results = match context.get_provable_result_exprs()? {
+                Some(aliased_results) if context.get_join().is_none() => aliased_results,
+                _ => {
+                    return Err(ConversionError::Unprovable(
+                        "each query of a UNION must be a provable filter without aggregations"
+                            .to_owned(),
+                    ))
+                }
+            };
+            let input = FilterExprBuilder::new(context.get_column_mapping())
+                .add_table_expr(*context.get_table_ref())
+                .add_where_expr(context.get_where_expr().clone())?
+                .add_result_columns(aliased_results)
+                .build();
+            if let Some(first_input) = inputs.first() {
+                let first_fields = first_input.get_column_result_fields();
+                let fields = input.get_column_result_fields();
+                if first_fields.len() != fields.len() {
+                    return Err(ConversionError::InvalidExpression(
+                        "each query of a UNION must have the same number of result columns"
+                            .to_owned(),
+                    ));
+                }
+                for (first_field, field) in first_fields.iter().zip(fields.iter()) {
+                    if first_field.data_type() != field.data_type() {
+                        return Err(ConversionError::DataTypeMismatch(
+                            field.data_type().to_string(),
+                            first_field.data_type().to_string(),
+                        ));
+                    }
+                }
+            }
+            inputs.push(input);
+        }
+        let union_expr = UnionExpr::new(inputs);
+        let result_fields = union_expr.get_column_result_fields();
+        // ORDER BY must reference only the result columns, which are named after those of the first query.
+        for by_expr in order_by {
+            if !result_fields
+                .iter()
+                .any(|field| field.name() == by_expr.expr)
+            {
+                return Err(ConversionError::InvalidOrderBy(
+                    by_expr.expr.as_str().to_string(),
+                ));
+            }
+        }
+        // A UNION keeps a single row out of the rows that are equal in every result column.
+        let (by_exprs, aliased_exprs): (Vec<_>, Vec<_>) = if all {
+            (Vec::new(), Vec::new())
+        } else {
+            result_fields
+                .iter()
+                .map(|field| {
+                    let column = Expression::Column(field.name());
+                    let aliased_expr = AliasedResultExpr {
+                        expr: Box::new(Expression::Aggregation {
+                            op: AggregationOperator::First,
+                            expr: Box::new(column.clone()),
+                        }),
+                        alias: field.name(),
+                    };
+                    (column, aliased_expr)
+                })
+                .unzip()
+        };
+        let mut result = ResultExprBuilder::default().add_group_by_exprs(&by_exprs, &aliased_exprs);
+        if !aliased_exprs.is_empty() {
+            result = result.add_select_exprs(&aliased_exprs);
+        }
+        Ok(Self {
+            proof_expr: ProofPlan::Union(union_expr),
+            result: result
+                .add_order_by_exprs(order_by.to_vec())
+                .add_slice_expr(slice)
+                .build(),
+        })
+    }
+
     /// Immutable access to this query's provable filter expression.
     pub fn proof_expr(&self) -> &ProofPlan<C> {
         &self.proof_expr
@@ -101,3 +530,589 @@ impl<C: Commitment> QueryExpr<C> {
         &self.result
     }
 }
+
+/// Pushes the queries of a `UNION`, or of a `UNION ALL` when `all` is set, in order.
+///
+/// A `UNION` of queries that are themselves combined with a `UNION ALL`, or the other way around, is not supported.
+fn push_union_queries(
+    expr: SetExpression,
+    all: bool,
+    queries: &mut Vec<SetExpression>,
+) -> ConversionResult<()> {
+    match expr {
+        SetExpression::Union {
+            left,
+            right,
+            all: inner_all,
+        } => {
+            if inner_all != all {
+                return Err(ConversionError::InvalidExpression(
+                    "UNION and UNION ALL can not be combined".to_owned(),
+                ));
+            }
+            push_union_queries(*left, all, queries)?;
+            push_union_queries(*right, all, queries)
+        }
+        query => {
+            queries.push(query);
+            Ok(())
+        }
+    }
+}
+
+/// Builds a condition of a `SubqueryFilterExpr` out of a subquery condition of the WHERE clause.
+///
+/// The subquery of an `IN` must be a provable filter with a single result expression that is never null,
+/// while the subquery of a comparison must be a single `COUNT` or `SUM` of integers without a `GROUP BY`.
+/// The expression of the outer query must never be null either.
+///
+/// `EXISTS (<subquery>)` is built as `0 IN (SELECT 0 ...)`.
+fn build_subquery_condition<C: Commitment>(
+    subquery: &SubqueryContext,
+    context: &QueryContext,
+    ctes: &Ctes,
+    default_schema: Identifier,
+    schema_accessor: &dyn SchemaAccessor,
+) -> ConversionResult<SubqueryCondition<C>> {
+    let column_mapping = context.get_column_mapping();
+    let builder = WhereExprBuilder::new(&column_mapping);
+    let build_expr = |expr: &Expression| -> ConversionResult<ProvableExprPlan<C>> {
+        if builder.build_result_validity::<C>(expr)?.is_some() {
+            return Err(ConversionError::Unprovable(
+                "expressions that are compared with subqueries must not be nullable".to_owned(),
+            ));
+        }
+        builder.build_result_expr::<C>(expr.clone())
+    };
+    match subquery {
+        SubqueryContext::In {
+            expr,
+            subquery,
+            negated,
+        } => {
+            let expr = build_expr(expr)?;
+            let subquery_context =
+                build_subquery_context(subquery, ctes, default_schema, schema_accessor)?;
+            let aliased_results = match subquery_context.get_provable_result_exprs::<C>()? {
+                Some(aliased_results) if aliased_results.len() == 1 => aliased_results,
+                _ => {
+                    return Err(ConversionError::Unprovable(
+                        "the subquery of IN must be a provable filter without aggregations and with a single result expression that is never null"
+                            .to_owned(),
+                    ))
+                }
+            };
+            let expr_type = expr.data_type();
+            let subquery_type = aliased_results[0].expr.data_type();
+            if expr_type != subquery_type && !(expr_type.is_integer() && subquery_type.is_integer())
+            {
+                return Err(ConversionError::DataTypeMismatch(
+                    expr_type.to_string(),
+                    subquery_type.to_string(),
+                ));
+            }
+            Ok(SubqueryCondition::In {
+                expr,
+                subquery: build_subquery_filter(&subquery_context, aliased_results)?,
+                negated: *negated,
+            })
+        }
+        SubqueryContext::Exists { subquery, negated } => {
+            let subquery_context =
+                build_subquery_context(subquery, ctes, default_schema, schema_accessor)?;
+            if subquery_context.is_aggregation() {
+                return Err(ConversionError::Unprovable(
+                    "the subquery of EXISTS must not have aggregations".to_owned(),
+                ));
+            }
+            let alias = Identifier::try_new("__exists__")
+                .map_err(|e| ConversionError::InvalidExpression(e.to_string()))?;
+            let aliased_results = vec![AliasedProvableExprPlan {
+                expr: ProvableExprPlan::new_literal(LiteralValue::BigInt(0)),
+                alias,
+            }];
+            Ok(SubqueryCondition::In {
+                expr: ProvableExprPlan::new_literal(LiteralValue::BigInt(0)),
+                subquery: build_subquery_filter(&subquery_context, aliased_results)?,
+                negated: *negated,
+            })
+        }
+        SubqueryContext::Compare { expr, op, subquery } => {
+            let expr = build_expr(expr)?;
+            let subquery_context =
+                build_subquery_context(subquery, ctes, default_schema, schema_accessor)?;
+            let unprovable = || {
+                ConversionError::Unprovable(
+                    "the subquery of a comparison must be a single COUNT or SUM of values that are never null, without GROUP BY"
+                        .to_owned(),
+                )
+            };
+            if !subquery_context.get_group_by_exprs().is_empty()
+                || subquery_context.get_having_expr().is_some()
+            {
+                return Err(unprovable());
+            }
+            let [aliased_expr] = subquery_context.get_aliased_result_exprs()? else {
+                return Err(unprovable());
+            };
+            let Expression::Aggregation {
+                op: aggregation_op,
+                expr: argument,
+            } = &*aliased_expr.expr
+            else {
+                return Err(unprovable());
+            };
+            if !matches!(
+                aggregation_op,
+                AggregationOperator::Count | AggregationOperator::Sum
+            ) {
+                return Err(ConversionError::Unprovable(format!(
+                    "the subquery of a comparison can only be a COUNT or a SUM, not {}",
+                    aggregation_op.to_string().to_uppercase()
+                )));
+            }
+            let subquery_column_mapping = subquery_context.get_column_mapping();
+            let subquery_builder = WhereExprBuilder::new(&subquery_column_mapping);
+            if subquery_builder
+                .build_result_validity::<C>(argument)?
+                .is_some()
+            {
+                return Err(unprovable());
+            }
+            // A `COUNT` is the sum of a `1` for each selected row.
+            let (aggregation, subquery_expr) = match aggregation_op {
+                AggregationOperator::Count => (
+                    SubqueryAggregation::Count,
+                    ProvableExprPlan::new_literal(LiteralValue::BigInt(1)),
+                ),
+                _ => (
+                    SubqueryAggregation::Sum,
+                    subquery_builder.build_result_expr((**argument).clone())?,
+                ),
+            };
+            if !expr.data_type().is_integer() || !subquery_expr.data_type().is_integer() {
+                return Err(ConversionError::Unprovable(
+                    "only integers can be compared with the result of a subquery, which must be a COUNT or a SUM of integers"
+                        .to_owned(),
+                ));
+            }
+            let aliased_results = vec![AliasedProvableExprPlan {
+                expr: subquery_expr,
+                alias: aliased_expr.alias,
+            }];
+            Ok(SubqueryCondition::Compare {
+                expr,
+                op: *op,
+                aggregation,
+                subquery: build_subquery_filter(&subquery_context, aliased_results)?,
+            })
+        }
+    }
+}
+
+/// Builds the context of a subquery, which must be a single query over a single table
+/// without subqueries of its own.
+fn build_subquery_context(
+    subquery: &SetExpression,
+    ctes: &Ctes,
+    default_schema: Identifier,
+    schema_accessor: &dyn SchemaAccessor,
+) -> ConversionResult<QueryContext> {
+    let SetExpression::Query {
+        result_exprs,
+        from,
+        where_expr,
+        group_by,
+        having,
+        distinct,
+    } = subquery.clone()
+    else {
+        return Err(ConversionError::Unprovable(
+            "subqueries with UNION are not supported".to_owned(),
+        ));
+    };
+    let context = QueryContextBuilder::new(schema_accessor)
+        .visit_ctes(&ctes.contexts)
+        .visit_table_expr(from, default_schema)?
+        .visit_group_by_exprs(group_by, &result_exprs)?
+        .visit_distinct(distinct, &result_exprs)?
+        .visit_result_exprs(result_exprs)?
+        .visit_having_expr(having)?
+        .visit_where_expr(where_expr)?
+        .build()?;
+    if context.get_join().is_some() || !context.get_subqueries().is_empty() {
+        return Err(ConversionError::Unprovable(
+            "subqueries with joins or subqueries of their own are not supported".to_owned(),
+        ));
+    }
+    check_not_proven_cte(&context, ctes, "subqueries")?;
+    Ok(context)
+}
+
+/// Checks that a query, which is part of the plan of another query, does not select from a common table expression
+/// that is proven on its own, since the result of such a common table expression is not a table that can be proven over.
+fn check_not_proven_cte(
+    context: &QueryContext,
+    ctes: &Ctes,
+    queries: &str,
+) -> ConversionResult<()> {
+    if ctes.proven.contains_key(context.get_table_ref()) {
+        return Err(ConversionError::Unprovable(format!(
+            "{queries} can not select from a common table expression with aggregations, GROUP BY, DISTINCT, JOIN, UNION, window functions or subqueries"
+        )));
+    }
+    Ok(())
+}
+
+/// Resolves the common table expressions of a `WITH` clause in order, so that each may select from those before it.
+///
+/// A filter of a single table has its columns and condition substituted into the queries that select from it,
+/// so its rows are proven as part of the plans of those queries.
+///
+/// Any other common table expression, i.e. with aggregations, `GROUP BY`, `DISTINCT`, `JOIN`, `UNION`,
+/// window functions or subqueries, is built into a `ProofPlan` of its own. A query that selects from it
+/// is proven by that plan, and the post-processing step computes the query over the proven result.
+/// So such a common table expression may only be selected from by the `FROM` clause of a query without a join,
+/// or by another common table expression.
+fn build_ctes<C: Commitment>(
+    ctes: Vec<CommonTableExpression>,
+    default_schema: Identifier,
+    schema_accessor: &dyn SchemaAccessor,
+) -> ConversionResult<Ctes> {
+    let mut scope = Ctes::default();
+    for CommonTableExpression { name, query } in ctes {
+        if scope.contexts.contains_key(&name) {
+            return Err(ConversionError::InvalidExpression(format!(
+                "common table expression '{name}' is defined more than once"
+            )));
+        }
+        let cte = match build_filter_cte(&query, name, &scope, default_schema, schema_accessor)? {
+            Some(cte) => cte,
+            None => {
+                let query_expr = QueryExpr::<C>::try_new_with_ctes(
+                    (*query).clone(),
+                    Vec::new(),
+                    None,
+                    &scope,
+                    default_schema,
+                    schema_accessor,
+                )?;
+                let schema = result_schema(&query_expr).ok_or_else(|| {
+                    ConversionError::Unprovable(format!(
+                        "the result of common table expression '{name}' has columns of unsupported types"
+                    ))
+                })?;
+                // The result is looked up as a table of a schema that is not that of any table.
+                let table_ref = TableRef::new(ResourceId::new(proven_cte_schema_id(), name));
+                let cte = CteContext {
+                    name: ResourceId::new(default_schema, name),
+                    table_ref,
+                    columns: schema
+                        .iter()
+                        .map(|(column, _)| (*column, Expression::Column(*column)))
+                        .collect(),
+                    where_expr: None,
+                };
+                let proven_cte = ProvenCte {
+                    query: *query,
+                    ctes: scope.clone(),
+                    schema,
+                };
+                scope.proven.insert(table_ref, proven_cte);
+                cte
+            }
+        };
+        scope.contexts.insert(name, cte);
+    }
+    Ok(scope)
+}
+
+/// Builds a common table expression that is a filter of a single table, or returns `None` if it is not one.
+fn build_filter_cte(
+    query: &SetExpression,
+    name: Identifier,
+    ctes: &Ctes,
+    default_schema: Identifier,
+    schema_accessor: &dyn SchemaAccessor,
+) -> ConversionResult<Option<CteContext>> {
+    let SetExpression::Query {
+        result_exprs,
+        from,
+        where_expr,
+        group_by,
+        having,
+        distinct,
+    } = query.clone()
+    else {
+        return Ok(None);
+    };
+    let cte_schema_accessor = CteSchemaAccessor::new(schema_accessor, ctes);
+    let context = QueryContextBuilder::new(&cte_schema_accessor)
+        .visit_ctes(&ctes.contexts)
+        .visit_table_expr(from, default_schema)?
+        .visit_group_by_exprs(group_by, &result_exprs)?
+        .visit_distinct(distinct, &result_exprs)?
+        .visit_result_exprs(result_exprs)?
+        .visit_having_expr(having)?
+        .visit_where_expr(where_expr)?
+        .build()?;
+    if context.is_aggregation()
+        || context.has_windows()
+        || context.get_join().is_some()
+        || !context.get_subqueries().is_empty()
+    {
+        return Ok(None);
+    }
+    let columns = context
+        .get_aliased_result_exprs()?
+        .iter()
+        .map(|aliased_expr| (aliased_expr.alias, (*aliased_expr.expr).clone()))
+        .collect();
+    Ok(Some(CteContext {
+        name: ResourceId::new(default_schema, name),
+        table_ref: *context.get_table_ref(),
+        columns,
+        where_expr: context.get_where_expr().clone(),
+    }))
+}
+
+/// The schema of the tables that the results of the proven common table expressions are looked up as.
+fn proven_cte_schema_id() -> Identifier {
+    Identifier::try_new("__cte__")
+        .expect("the schema of common table expressions is a valid identifier")
+}
+
+/// Returns the columns of the result of a query and their types, which are those that the post-processing step
+/// gives to an empty result of its plan, or `None` if a column is of a type that can not be selected from.
+fn result_schema<C: Commitment>(
+    query_expr: &QueryExpr<C>,
+) -> Option<Vec<(Identifier, ColumnType)>> {
+    let empty_result = OwnedTable::<C::Scalar>::try_from_iter(
+        query_expr
+            .proof_expr()
+            .get_column_result_fields()
+            .iter()
+            .map(|field| {
+                let data_type = match field.data_type() {
+                    ColumnType::Scalar => return None,
+                    column_type => DataType::from(&column_type),
+                };
+                let column = OwnedColumn::try_from(new_empty_array(&data_type)).ok()?;
+                Some((field.name(), column))
+            })
+            .collect::<Option<Vec<_>>>()?,
+    )
+    .ok()?;
+    let result = query_expr
+        .result()
+        .transform_results(RecordBatch::try_from(empty_result).ok()?)?;
+    result
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| {
+            let column = Identifier::try_new(field.name()).ok()?;
+            let column_type = ColumnType::try_from(field.data_type().clone()).ok()?;
+            Some((column, column_type))
+        })
+        .collect()
+}
+
+/// Builds the filter of the selected rows of a subquery, with the given result expressions.
+fn build_subquery_filter<C: Commitment>(
+    context: &QueryContext,
+    aliased_results: Vec<AliasedProvableExprPlan<C>>,
+) -> ConversionResult<DenseFilterExpr<C>> {
+    Ok(FilterExprBuilder::new(context.get_column_mapping())
+        .add_table_expr(*context.get_table_ref())
+        .add_where_expr(context.get_where_expr().clone())?
+        .add_result_columns(aliased_results)
+        .build())
+}
+
+/// The result of a `GroupByExpr` already holds the grouped and aggregated columns, so the post-processing
+/// step only has to pick them, in the order of the query, and give them their aliases.
+///
+/// The group by columns are named after the columns themselves, while the other group by expressions
+/// and the aggregations are named after their aliases.
+fn group_by_result_selection(result_aliased_exprs: &[AliasedResultExpr]) -> Vec<AliasedResultExpr> {
+    result_aliased_exprs
+        .iter()
+        .map(|aliased_expr| {
+            let name = match &*aliased_expr.expr {
+                Expression::Aggregation {
+                    op: AggregationOperator::First,
+                    expr,
+                } => match **expr {
+                    Expression::Column(ident) => ident,
+                    _ => aliased_expr.alias,
+                },
+                _ => aliased_expr.alias,
+            };
+            AliasedResultExpr {
+                expr: Box::new(Expression::Column(name)),
+                alias: aliased_expr.alias,
+            }
+        })
+        .collect()
+}
+
+/// Returns the columns of the result of a `GroupByExpr` that hold counts, other than its count column,
+/// i.e. the `COUNT`s of the query and the counts of the values of its averages.
+fn group_by_count_columns(
+    result_aliased_exprs: &[AliasedResultExpr],
+    averages: &[(Identifier, Identifier, ColumnType)],
+    count_alias: Identifier,
+) -> Vec<Identifier> {
+    let counts = result_aliased_exprs
+        .iter()
+        .filter(|aliased_expr| {
+            matches!(
+                *aliased_expr.expr,
+                Expression::Aggregation {
+                    op: AggregationOperator::Count,
+                    ..
+                }
+            )
+        })
+        .map(|aliased_expr| aliased_expr.alias);
+    counts
+        .chain(averages.iter().map(|(_, count, _)| *count))
+        .filter(|count| *count != count_alias)
+        .collect()
+}
+
+/// Replaces each `COUNT(DISTINCT expr)` by `COUNT(expr)`, for rows that are already distinct.
+fn count_distinct_values(result_aliased_exprs: &[AliasedResultExpr]) -> Vec<AliasedResultExpr> {
+    result_aliased_exprs
+        .iter()
+        .map(|aliased_expr| match &*aliased_expr.expr {
+            Expression::Aggregation {
+                op: AggregationOperator::CountDistinct,
+                expr,
+            } => AliasedResultExpr {
+                expr: Box::new(Expression::Aggregation {
+                    op: AggregationOperator::Count,
+                    expr: expr.clone(),
+                }),
+                alias: aliased_expr.alias,
+            },
+            _ => aliased_expr.clone(),
+        })
+        .collect()
+}
+
+/// Returns the alias of each average of the result, together with the name of the hidden column
+/// that holds the number of its values and the type of the average.
+fn get_averages(
+    context: &QueryContext,
+    result_aliased_exprs: &[AliasedResultExpr],
+) -> ConversionResult<Vec<(Identifier, Identifier, ColumnType)>> {
+    context
+        .get_averages()
+        .iter()
+        .map(|(alias, column_type)| {
+            let count_id = average_count_column_id(*alias).map_err(|_| {
+                ConversionError::InvalidExpression(format!(
+                    "The alias of the average '{alias}' is too long"
+                ))
+            })?;
+            if result_aliased_exprs
+                .iter()
+                .any(|aliased_expr| aliased_expr.alias == count_id)
+            {
+                return Err(ConversionError::DuplicateResultAlias(count_id.to_string()));
+            }
+            Ok((*alias, count_id, *column_type))
+        })
+        .collect()
+}
+
+/// Splits the window functions out of the result expressions, each named after the hidden column of its values,
+/// which the result expressions select instead.
+fn split_windows(
+    result_aliased_exprs: &[AliasedResultExpr],
+) -> ConversionResult<(Vec<AliasedResultExpr>, Vec<AliasedResultExpr>)> {
+    let mut windows = Vec::new();
+    let mut selection = Vec::with_capacity(result_aliased_exprs.len());
+    for aliased_expr in result_aliased_exprs {
+        if !matches!(*aliased_expr.expr, Expression::Window { .. }) {
+            selection.push(aliased_expr.clone());
+            continue;
+        }
+        let alias = aliased_expr.alias;
+        let window_id = window_column_id(alias).map_err(|_| {
+            ConversionError::InvalidExpression(format!(
+                "The alias of the window function '{alias}' is too long"
+            ))
+        })?;
+        windows.push(AliasedResultExpr {
+            expr: aliased_expr.expr.clone(),
+            alias: window_id,
+        });
+        selection.push(AliasedResultExpr {
+            expr: Box::new(Expression::Column(window_id)),
+            alias,
+        });
+    }
+    Ok((windows, selection))
+}
+
+/// Replaces each `AVG(expr)` by `SUM(expr)` and appends the matching `COUNT(expr)`,
+/// named after the count column of the average.
+fn split_averages(
+    result_aliased_exprs: &[AliasedResultExpr],
+    averages: &[(Identifier, Identifier, ColumnType)],
+) -> Vec<AliasedResultExpr> {
+    let mut counts = Vec::new();
+    let mut split_aliased_exprs = Vec::from_iter(result_aliased_exprs.iter().map(|aliased_expr| {
+        let Expression::Aggregation {
+            op: AggregationOperator::Avg,
+            expr,
+        } = &*aliased_expr.expr
+        else {
+            return aliased_expr.clone();
+        };
+        if let Some((_, count_id, _)) = averages
+            .iter()
+            .find(|(alias, _, _)| *alias == aliased_expr.alias)
+        {
+            counts.push(AliasedResultExpr {
+                expr: Box::new(Expression::Aggregation {
+                    op: AggregationOperator::Count,
+                    expr: expr.clone(),
+                }),
+                alias: *count_id,
+            });
+        }
+        AliasedResultExpr {
+            expr: Box::new(Expression::Aggregation {
+                op: AggregationOperator::Sum,
+                expr: expr.clone(),
+            }),
+            alias: aliased_expr.alias,
+        }
+    }));
+    split_aliased_exprs.extend(counts);
+    split_aliased_exprs
+}
+
+/// Returns the averages of a query proven by a `GroupByExpr`, each with the column of its result that counts its values.
+///
+/// The values of a nullable expression are counted by the sum of its validity, which is named after the count column
+/// of the average and is then part of the result. Otherwise, every row of a group is a value, so the count of the
+/// group is used.
+fn group_by_averages(
+    result_fields: &[ColumnField],
+    averages: Vec<(Identifier, Identifier, ColumnType)>,
+    count_alias: Identifier,
+) -> Vec<(Identifier, Identifier, ColumnType)> {
+    averages
+        .into_iter()
+        .map(|(alias, count_id, column_type)| {
+            let is_nullable = result_fields.iter().any(|field| field.name() == count_id);
+            let count = if is_nullable { count_id } else { count_alias };
+            (alias, count, column_type)
+        })
+        .collect()
+}
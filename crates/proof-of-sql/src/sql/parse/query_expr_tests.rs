@@ -1,17 +1,34 @@
 use super::ConversionError;
 use crate::{
-    base::database::{ColumnType, RecordBatchTestAccessor, TableRef, TestAccessor},
+    base::{
+        database::{
+            order_key, prefix_order_key_range, ColumnField, ColumnType, LiteralValue,
+            RecordBatchTestAccessor, TableRef, TestAccessor,
+        },
+        math::decimal::Precision,
+    },
     record_batch,
     sql::{
-        ast::{test_utility::*, ProofPlan},
+        ast::{test_utility::*, ProofPlan, SubqueryAggregation, SubqueryComparison},
         parse::QueryExpr,
-        transform::test_utility::{col as pc, *},
+        transform::{
+            test_utility::{col as pc, *},
+            AverageExpr, EmptyAggregationExpr,
+        },
     },
 };
-use arrow::record_batch::RecordBatch;
+use arrow::{
+    array::{ArrayRef, Date32Array, Int64Array, TimestampMillisecondArray},
+    record_batch::RecordBatch,
+};
 use curve25519_dalek::RistrettoPoint;
 use itertools::Itertools;
-use proof_of_sql_parser::{intermediate_ast::OrderByDirection::*, sql::SelectStatementParser};
+use proof_of_sql_parser::{
+    intermediate_ast::{BinaryOperator, Expression, OrderByDirection::*, WindowFunction},
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    sql::SelectStatementParser,
+};
+use std::sync::Arc;
 
 fn query_to_provable_ast(
     table: TableRef,
@@ -62,7 +79,7 @@ fn get_test_accessor() -> (TableRef, RecordBatchTestAccessor) {
 }
 
 macro_rules! query {
-    (select: $select:expr $(, filter: $filter:expr)? $(, group: $groupby:expr)? $(, order: $orderby:expr)? $(, limit: $limit:expr)? $(, offset: $offset:expr)? $(, should_err: $should_err:tt)? $(,)?) => {{
+    (select: $select:expr $(, filter: $filter:expr)? $(, group: $groupby:expr)? $(, having: $having:expr)? $(, order: $orderby:expr)? $(, limit: $limit:expr)? $(, offset: $offset:expr)? $(, should_err: $should_err:tt)? $(,)?) => {{
         let (t, accessor) = get_test_accessor();
         let mut query = String::new();
         query.push_str(&format!("select {} from t", $select.join(", ")));
@@ -74,6 +91,10 @@ macro_rules! query {
             () => {}; ($expr:expr) => { query.push_str(&format!(" group by {}", $expr.clone().join(", "))) };
         }
         groupby_str!($($groupby)?);
+        macro_rules! having_str {
+            () => {}; ($expr:expr) => { query.push_str(&format!(" having {}", $expr)) };
+        }
+        having_str!($($having)?);
         macro_rules! orderby_str {
             () => {}; ($expr:expr) => { query.push_str(&format!(" order by {}", $expr.clone().join(", "))) };
         }
@@ -126,8 +147,8 @@ macro_rules! expected_query {
         orderby_macro!($($order_by)?, $($order_dirs)?);
 
         macro_rules! filter_macro {
-            () => {dense_filter(cols_expr(t, &$result_columns, &accessor), tab(t), const_bool(true))};
-            ($expr:expr) => { dense_filter(cols_expr(t, &$result_columns, &accessor), tab(t), $expr) };
+            () => {dense_filter(cols_expr_plan(t, &$result_columns, &accessor), tab(t), const_bool(true))};
+            ($expr:expr) => { dense_filter(cols_expr_plan(t, &$result_columns, &accessor), tab(t), $expr) };
         }
         let filter = filter_macro!($($filter)?);
 
@@ -148,11 +169,11 @@ fn we_can_convert_an_ast_with_one_column() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where a = 3", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -170,11 +191,11 @@ fn we_can_convert_an_ast_with_one_column_and_i128_data() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where a = 3", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3_i64)),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -192,11 +213,11 @@ fn we_can_convert_an_ast_with_one_column_and_a_filter_by_a_string_literal() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where a = 'abc'", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_varchar("abc")),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -243,11 +264,14 @@ fn we_dont_have_duplicate_filter_result_expressions() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            vec![
+                aliased_col_expr_plan(t, "a", "b", &accessor),
+                aliased_col_expr_plan(t, "a", "c", &accessor),
+            ],
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
-        result(&[("a", "b"), ("a", "c")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -267,11 +291,11 @@ fn we_can_convert_an_ast_with_two_columns() {
     let ast = query_to_provable_ast(t, "select a,  b from sxt_tab where c = 123", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a", "b"], &accessor),
+            cols_expr_plan(t, &["a", "b"], &accessor),
             tab(t),
             equal(column(t, "c", &accessor), const_bigint(123)),
         ),
-        result(&[("a", "a"), ("b", "b")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -290,11 +314,11 @@ fn we_can_parse_all_result_columns_with_select_star() {
     let ast = query_to_provable_ast(t, "select * from sxt_tab where a = 3", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a", "b"], &accessor),
+            cols_expr_plan(t, &["b", "a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
-        result(&[("b", "b"), ("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -313,11 +337,11 @@ fn we_can_convert_an_ast_with_one_positive_cond() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where b = +4", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "b", &accessor), const_bigint(4)),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -336,11 +360,11 @@ fn we_can_convert_an_ast_with_one_not_equals_cond() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where b <> +4", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             not(equal(column(t, "b", &accessor), const_bigint(4))),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -359,11 +383,11 @@ fn we_can_convert_an_ast_with_one_negative_cond() {
     let ast = query_to_provable_ast(t, "select a from sxt_tab where b <= -4", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             lte(column(t, "b", &accessor), const_bigint(-4)),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -387,14 +411,14 @@ fn we_can_convert_an_ast_with_cond_and() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             and(
                 equal(column(t, "b", &accessor), const_bigint(3)),
                 lte(column(t, "c", &accessor), const_bigint(-2)),
             ),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -418,14 +442,14 @@ fn we_can_convert_an_ast_with_cond_or() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             or(
                 equal(column(t, "b", &accessor), const_bigint(3)),
                 equal(column(t, "c", &accessor), const_bigint(-2)),
             ),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -449,14 +473,14 @@ fn we_can_convert_an_ast_with_conds_or_not() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             or(
                 lte(column(t, "b", &accessor), const_bigint(3)),
                 not(gte(column(t, "c", &accessor), const_bigint(-2))),
             ),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -481,7 +505,7 @@ fn we_can_convert_an_ast_with_conds_not_and_or() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             not(and(
                 or(
@@ -491,7 +515,7 @@ fn we_can_convert_an_ast_with_conds_not_and_or() {
                 equal(column(t, "b", &accessor), const_bigint(3)),
             )),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -513,11 +537,11 @@ fn we_can_convert_an_ast_with_the_min_i128_filter_value() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_int128(i128::MIN)),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -539,11 +563,11 @@ fn we_can_convert_an_ast_with_the_max_i128_filter_value() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_int128(i128::MAX)),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -566,11 +590,11 @@ fn we_can_convert_an_ast_using_an_aliased_column() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            vec![col_expr(t, "a", &accessor)],
+            vec![aliased_col_expr_plan(t, "a", "b_rename", &accessor)],
             tab(t),
             gte(column(t, "b", &accessor), const_bigint(4)),
         ),
-        result(&[("a", "b_rename")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -614,11 +638,11 @@ fn we_can_convert_an_ast_with_a_schema() {
     let ast = query_to_provable_ast(t, "select a from eth.sxt_tab where a = 3", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
-        result(&[("a", "a")]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -634,8 +658,12 @@ fn we_can_convert_an_ast_without_any_dense_filter() {
         0,
     );
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
-        result(&[("a", "a")]),
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![]),
     );
     let queries = ["select * from eth.sxt_tab", "select a from eth.sxt_tab"];
     for query in queries {
@@ -661,14 +689,11 @@ fn we_can_parse_order_by_with_a_single_column() {
     let ast = query_to_provable_ast(t, "select * from sxt_tab where a = 3 order by b", &accessor);
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a", "b"], &accessor),
+            cols_expr_plan(t, &["b", "a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
-        composite_result(vec![
-            select(&[pc("b").alias("b"), pc("a").alias("a")]),
-            orders(&["b"], &[Asc]),
-        ]),
+        composite_result(vec![orders(&["b"], &[Asc])]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -691,14 +716,11 @@ fn we_can_parse_order_by_with_multiple_columns() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a", "b"], &accessor),
+            cols_expr_plan(t, &["a", "b"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(3)),
         ),
-        composite_result(vec![
-            select(&[pc("a").alias("a"), pc("b").alias("b")]),
-            orders(&["b", "a"], &[Desc, Asc]),
-        ]),
+        composite_result(vec![orders(&["b", "a"], &[Desc, Asc])]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -723,16 +745,13 @@ fn we_can_parse_order_by_referencing_an_alias_associated_with_column_b_but_with_
     let expected_ast = QueryExpr::new(
         dense_filter(
             vec![
-                col_expr(t, "name", &accessor),
-                col_expr(t, "salary", &accessor),
+                aliased_col_expr_plan(t, "salary", "s", &accessor),
+                aliased_col_expr_plan(t, "name", "salary", &accessor),
             ],
             tab(t),
             equal(column(t, "salary", &accessor), const_bigint(5)),
         ),
-        composite_result(vec![
-            select(&[pc("salary").alias("s"), pc("name").alias("salary")]),
-            orders(&["salary"], &[Desc]),
-        ]),
+        composite_result(vec![orders(&["salary"], &[Desc])]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -836,20 +855,14 @@ fn we_can_parse_order_by_queries_with_the_same_column_name_appearing_more_than_o
         let expected_ast = QueryExpr::new(
             dense_filter(
                 vec![
-                    col_expr(t, "name", &accessor),
-                    col_expr(t, "salary", &accessor),
+                    aliased_col_expr_plan(t, "salary", "s", &accessor),
+                    col_expr_plan(t, "name", &accessor),
+                    aliased_col_expr_plan(t, "salary", "d", &accessor),
                 ],
                 tab(t),
                 const_bool(true),
             ),
-            composite_result(vec![
-                select(&[
-                    pc("salary").alias("s"),
-                    pc("name").alias("name"),
-                    pc("salary").alias("d"),
-                ]),
-                orders(&[order_by], &[Asc]),
-            ]),
+            composite_result(vec![orders(&[order_by], &[Asc])]),
         );
         assert_eq!(ast, expected_ast);
     }
@@ -872,8 +885,14 @@ fn we_can_parse_a_query_having_a_simple_limit_clause() {
 
     let ast = query_to_provable_ast(t, "select a from sxt_tab limit 3", &accessor);
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
-        composite_result(vec![select(&[pc("a").alias("a")]), slice(3, 0)]),
+        sliced_dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+            3,
+            0,
+        ),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -891,8 +910,12 @@ fn no_slice_is_applied_when_limit_is_u64_max_and_offset_is_zero() {
 
     let ast = query_to_provable_ast(t, "select a from sxt_tab offset 0", &accessor);
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
-        composite_result(vec![select(&[pc("a").alias("a")])]),
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -910,8 +933,14 @@ fn we_can_parse_a_query_having_a_simple_positive_offset_clause() {
 
     let ast = query_to_provable_ast(t, "select a from sxt_tab offset 7", &accessor);
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
-        composite_result(vec![select(&[pc("a").alias("a")]), slice(u64::MAX, 7)]),
+        sliced_dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+            u64::MAX,
+            7,
+        ),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -929,8 +958,12 @@ fn we_can_parse_a_query_having_a_negative_offset_clause() {
 
     let ast = query_to_provable_ast(t, "select a from sxt_tab offset -7", &accessor);
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
-        composite_result(vec![select(&[pc("a").alias("a")]), slice(u64::MAX, -7)]),
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![slice(u64::MAX, -7)]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -948,8 +981,14 @@ fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause() {
 
     let ast = query_to_provable_ast(t, "select a from sxt_tab limit 55 offset 3", &accessor);
     let expected_ast = QueryExpr::new(
-        dense_filter(cols_expr(t, &["a"], &accessor), tab(t), const_bool(true)),
-        composite_result(vec![select(&[pc("a").alias("a")]), slice(55, 3)]),
+        sliced_dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+            55,
+            3,
+        ),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -976,19 +1015,78 @@ fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause_preceded_by_wher
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a"], &accessor),
+            cols_expr_plan(t, &["a"], &accessor),
             tab(t),
             equal(column(t, "a", &accessor), const_bigint(-3)),
         ),
-        composite_result(vec![
-            select(&[pc("a").alias("a")]),
-            orders(&["a"], &[Desc]),
-            slice(55, 3),
-        ]),
+        composite_result(vec![orders(&["a"], &[Desc]), slice(55, 3)]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_prove_an_order_by_with_a_limit_as_a_top_k() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [5_i64],
+            "b" => ["x"],
+        ),
+        0,
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select b, a + 1 as c from sxt_tab where a = 5 order by c desc limit 3",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        top_k(
+            vec![
+                aliased_plan(column(t, "b", &accessor), "b"),
+                aliased_plan(add(column(t, "a", &accessor), const_bigint(1)), "c"),
+            ],
+            tab(t),
+            equal(column(t, "a", &accessor), const_bigint(5)),
+            1,
+            Desc,
+            3,
+        ),
+        composite_result(vec![orders(&["c"], &[Desc]), slice(3, 0)]),
     );
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_order_and_limit_in_the_post_processing_step_when_there_is_no_single_integer_key() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [5_i64],
+            "b" => ["x"],
+        ),
+        0,
+    );
+    for query in [
+        "select a, b from sxt_tab where a = 5 order by b limit 3",
+        "select a, b from sxt_tab where a = 5 order by a, b limit 3",
+        "select a, b from sxt_tab where a = 5 order by a limit 3 offset 1",
+        "select a, b from sxt_tab where a = 5 order by a",
+        "select a, b from sxt_tab where a = 5 order by a limit 0",
+    ] {
+        let ast = query_to_provable_ast(t, query, &accessor);
+        assert!(matches!(ast.proof_expr(), ProofPlan::DenseFilter(_)));
+    }
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select a from sxt_tab where b = 2 order by a limit 1",
+        &accessor,
+    );
+    assert!(matches!(ast.proof_expr(), ProofPlan::DenseFilter(_)));
+}
+
 ///////////////////////////
 // Group By Expressions - Prover
 ///////////////////////////
@@ -1012,7 +1110,7 @@ fn we_can_do_provable_group_by() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             sums_expr(
                 t,
                 &["salary"],
@@ -1025,9 +1123,9 @@ fn we_can_do_provable_group_by() {
             const_bool(true),
         ),
         composite_result(vec![select(&[
-            pc("department").first().alias("department"),
-            pc("salary").sum().alias("total_salary"),
-            pc("department").count().alias("num_employee"),
+            pc("department").alias("department"),
+            pc("total_salary").alias("total_salary"),
+            pc("num_employee").alias("num_employee"),
         ])]),
     );
     assert_eq!(ast, expected_ast);
@@ -1052,15 +1150,15 @@ fn we_can_do_provable_group_by_without_sum() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             vec![],
             "num_employee",
             tab(t),
             const_bool(true),
         ),
         composite_result(vec![select(&[
-            pc("department").first().alias("department"),
-            pc("department").count().alias("num_employee"),
+            pc("department").alias("department"),
+            pc("num_employee").alias("num_employee"),
         ])]),
     );
     assert_eq!(ast, expected_ast);
@@ -1086,7 +1184,7 @@ fn we_can_do_provable_group_by_with_two_group_by_columns() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["state", "department"], &accessor),
+            cols_expr_plan(t, &["state", "department"], &accessor),
             sums_expr(
                 t,
                 &["salary"],
@@ -1099,10 +1197,10 @@ fn we_can_do_provable_group_by_with_two_group_by_columns() {
             const_bool(true),
         ),
         composite_result(vec![select(&[
-            pc("state").first().alias("state"),
-            pc("department").first().alias("department"),
-            pc("salary").sum().alias("total_salary"),
-            pc("department").count().alias("num_employee"),
+            pc("state").alias("state"),
+            pc("department").alias("department"),
+            pc("total_salary").alias("total_salary"),
+            pc("num_employee").alias("num_employee"),
         ])]),
     );
     assert_eq!(ast, expected_ast);
@@ -1128,7 +1226,7 @@ fn we_can_do_provable_group_by_with_two_sums_and_dense_filter() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             sums_expr(
                 t,
                 &["salary", "tax"],
@@ -1141,14 +1239,172 @@ fn we_can_do_provable_group_by_with_two_sums_and_dense_filter() {
             lte(column(t, "tax", &accessor), const_bigint(1)),
         ),
         composite_result(vec![select(&[
-            pc("department").first().alias("department"),
-            pc("salary").sum().alias("total_salary"),
-            pc("tax").sum().alias("total_tax"),
-            pc("department").count().alias("num_employee"),
+            pc("department").alias("department"),
+            pc("total_salary").alias("total_salary"),
+            pc("total_tax").alias("total_tax"),
+            pc("num_employee").alias("num_employee"),
+        ])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+#[test]
+fn we_can_do_provable_group_by_with_min_and_max() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "tax" => [1_i64, 2, 1, 1, 1, 1, 2],
+            "salary" => [4_i64, 7, 2, 3, 4, 5, 7],
+            "department" => [5_i64, 5, 2, 5, 2, 5, 2],
+        ),
+        0,
+    );
+
+    let ast = query_to_provable_ast(
+        t,
+        "select department, sum(tax) as total_tax, min(salary) as min_salary, max(salary) as max_salary, max(tax) as max_tax, count(*) as num_employee from employees where tax <= 1 group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by_with_min_max(
+            cols_expr_plan(t, &["department"], &accessor),
+            sums_expr(
+                t,
+                &["tax"],
+                &["total_tax"],
+                &[ColumnType::BigInt],
+                &accessor,
+            ),
+            aliased_cols_expr(t, &["salary"], &["min_salary"], &accessor),
+            aliased_cols_expr(t, &["salary", "tax"], &["max_salary", "max_tax"], &accessor),
+            "num_employee",
+            tab(t),
+            lte(column(t, "tax", &accessor), const_bigint(1)),
+        ),
+        composite_result(vec![select(&[
+            pc("department").alias("department"),
+            pc("total_tax").alias("total_tax"),
+            pc("min_salary").alias("min_salary"),
+            pc("max_salary").alias("max_salary"),
+            pc("max_tax").alias("max_tax"),
+            pc("num_employee").alias("num_employee"),
+        ])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_do_provable_group_by_with_aggregations_in_any_order_and_without_count() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "tax" => [1_i64, 2, 1, 1, 1, 1, 2],
+            "salary" => [4_i64, 7, 2, 3, 4, 5, 7],
+            "department" => [5_i64, 5, 2, 5, 2, 5, 2],
+        ),
+        0,
+    );
+
+    let ast = query_to_provable_ast(
+        t,
+        "select department as dept, max(salary) as max_salary, sum(tax) as total_tax, min(salary) as min_salary from employees where tax <= 1 group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by_with_min_max(
+            cols_expr_plan(t, &["department"], &accessor),
+            sums_expr(
+                t,
+                &["tax"],
+                &["total_tax"],
+                &[ColumnType::BigInt],
+                &accessor,
+            ),
+            aliased_cols_expr(t, &["salary"], &["min_salary"], &accessor),
+            aliased_cols_expr(t, &["salary"], &["max_salary"], &accessor),
+            "__count__",
+            tab(t),
+            lte(column(t, "tax", &accessor), const_bigint(1)),
+        ),
+        composite_result(vec![select(&[
+            pc("department").alias("dept"),
+            pc("max_salary").alias("max_salary"),
+            pc("total_tax").alias("total_tax"),
+            pc("min_salary").alias("min_salary"),
         ])]),
     );
     assert_eq!(ast, expected_ast);
 }
+
+#[test]
+fn we_can_do_provable_aggregations_without_group_by() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "tax" => [1_i64, 2, 1, 1, 1, 1, 2],
+            "salary" => [4_i64, 7, 2, 3, 4, 5, 7],
+        ),
+        0,
+    );
+
+    let ast = query_to_provable_ast(
+        t,
+        "select sum(salary) as total_salary, count(*) as num_employee from employees where tax = 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            vec![],
+            sums_expr(
+                t,
+                &["salary"],
+                &["total_salary"],
+                &[ColumnType::BigInt],
+                &accessor,
+            ),
+            "num_employee",
+            tab(t),
+            equal(column(t, "tax", &accessor), const_bigint(1)),
+        ),
+        composite_result(vec![
+            Box::new(EmptyAggregationExpr::new(
+                "num_employee".parse().unwrap(),
+                vec![],
+            )),
+            select(&[
+                pc("total_salary").alias("total_salary"),
+                pc("num_employee").alias("num_employee"),
+            ]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_do_not_prove_aggregations_that_are_not_supported() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "tax" => [1_i64, 2, 1, 1, 1, 1, 2],
+            "salary" => [4_i64, 7, 2, 3, 4, 5, 7],
+        ),
+        0,
+    );
+
+    for query in [
+        "select count(*) as a, count(*) as b from employees where tax = 1",
+        "select sum(salary) as __count__ from employees where tax = 1",
+        "select sum(salary * 2) as total_salary from employees where tax = 1",
+        "select sum(salary) as total_salary, count(*) as num_employee from employees",
+    ] {
+        let ast = query_to_provable_ast(t, query, &accessor);
+        assert!(matches!(ast.proof_expr(), ProofPlan::DenseFilter(_)));
+    }
+}
+
 ///////////////////////////
 // Group By Expressions - Polars
 ///////////////////////////
@@ -1171,7 +1427,7 @@ fn we_can_group_by_without_using_aggregate_functions() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1328,7 +1584,7 @@ fn we_can_parse_a_query_having_group_by_with_the_same_name_as_the_aggregation_ex
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["bonus", "department"], &accessor),
+            cols_expr_plan(t, &["bonus", "department"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1362,7 +1618,7 @@ fn count_aggregate_functions_can_be_used_with_non_numeric_columns() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["bonus", "department"], &accessor),
+            cols_expr_plan(t, &["bonus", "department"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1400,7 +1656,7 @@ fn count_all_uses_the_first_group_by_identifier_as_default_result_column() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             tab(t),
             equal(column(t, "salary", &accessor), const_bigint(4)),
         ),
@@ -1454,7 +1710,7 @@ fn we_can_use_the_same_result_columns_with_different_aliases_and_associate_it_wi
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1516,19 +1772,25 @@ fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_ex
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["a", "b", "f", "h"], &accessor),
+            vec![
+                aliased_plan(
+                    add(column(t, "a", &accessor), column(t, "b", &accessor)),
+                    "__expr__",
+                ),
+                aliased_plan(multiply(const_bigint(2), column(t, "f", &accessor)), "f2"),
+                aliased_plan(
+                    subtract(const_bigint(-77), column(t, "h", &accessor)),
+                    "col",
+                ),
+                aliased_plan(
+                    add(column(t, "a", &accessor), column(t, "f", &accessor)),
+                    "af",
+                ),
+            ],
             tab(t),
             const_bool(true),
         ),
-        composite_result(vec![select(&[
-            (pc("a") + pc("b")).alias("__expr__"),
-            (lit_i64(2) * pc("f")).alias("f2"),
-            ((-77_i64).to_lit() - pc("h")).alias("col"),
-            (pc("a") + pc("f")).alias("af"),
-            // TODO: add `a / b as a_div_b` result expr once polars properly
-            // supports decimal division without panicking in production
-            // (pc("a") / pc("b")).alias("a_div_b"),
-        ])]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -1554,15 +1816,38 @@ fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_preceden
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["c", "f", "g", "h"], &accessor),
+            vec![
+                aliased_plan(
+                    multiply(
+                        add(const_bigint(2), column(t, "f", &accessor)),
+                        add(
+                            add(column(t, "c", &accessor), column(t, "g", &accessor)),
+                            multiply(const_bigint(2), column(t, "h", &accessor)),
+                        ),
+                    ),
+                    "__expr__",
+                ),
+                aliased_plan(
+                    multiply(
+                        add(
+                            add(
+                                multiply(
+                                    subtract(column(t, "h", &accessor), column(t, "g", &accessor)),
+                                    const_bigint(2),
+                                ),
+                                column(t, "c", &accessor),
+                            ),
+                            column(t, "g", &accessor),
+                        ),
+                        add(column(t, "f", &accessor), const_bigint(2)),
+                    ),
+                    "d",
+                ),
+            ],
             tab(t),
             const_bool(true),
         ),
-        composite_result(vec![select(&[
-            ((lit_i64(2) + pc("f")) * (pc("c") + pc("g") + lit_i64(2) * pc("h"))).alias("__expr__"),
-            (((pc("h") - pc("g")) * lit_i64(2) + pc("c") + pc("g")) * (pc("f") + lit_i64(2)))
-                .alias("d"),
-        ])]),
+        composite_result(vec![]),
     );
     assert_eq!(ast, expected_ast);
 }
@@ -1587,7 +1872,7 @@ fn we_can_parse_arithmetic_expression_within_aggregations_in_the_result_expr() {
     );
     let expected_ast = QueryExpr::new(
         dense_filter(
-            cols_expr(t, &["c", "f"], &accessor),
+            cols_expr_plan(t, &["c", "f"], &accessor),
             tab(t),
             const_bool(true),
         ),
@@ -1937,3 +2222,1877 @@ fn we_can_serialize_list_of_filters_from_query_expr() {
     assert_eq!(filter_exprs.len(), deserialized_as_ref.len());
     assert_eq!(filter_exprs[0], deserialized_as_ref[0]);
 }
+
+///////////////////////////
+// Join
+///////////////////////////
+fn get_join_test_accessor() -> (TableRef, TableRef, RecordBatchTestAccessor) {
+    let facts = "sxt.facts".parse().unwrap();
+    let dims = "sxt.dims".parse().unwrap();
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    accessor.add_table(
+        facts,
+        record_batch!(
+            "k" => [1_i64, 2],
+            "amount" => [3_i64, 4],
+            "note" => ["a", "b"],
+        ),
+        0,
+    );
+    accessor.add_table(
+        dims,
+        record_batch!(
+            "dk" => [1_i64, 2],
+            "k" => [5_i64, 6],
+            "weight" => [7_i128, 8],
+            "name" => ["c", "d"],
+        ),
+        0,
+    );
+    (facts, dims, accessor)
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_join() {
+    let (facts, dims, accessor) = get_join_test_accessor();
+    let ast = query_to_provable_ast(
+        facts,
+        "select weight, amount as a from facts join dims on facts.k = dims.dk order by a",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        join(
+            tab(facts),
+            tab(dims),
+            col_expr(facts, "k", &accessor),
+            col_expr(dims, "dk", &accessor),
+            cols_expr(facts, &["amount"], &accessor),
+            cols_expr(dims, &["weight"], &accessor),
+        ),
+        composite_result(vec![
+            select(&[pc("weight").alias("weight"), pc("amount").alias("a")]),
+            orders(&["a"], &[Asc]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_join_with_the_condition_in_any_order() {
+    let (facts, dims, accessor) = get_join_test_accessor();
+    let expected_ast = QueryExpr::new(
+        join(
+            tab(facts),
+            tab(dims),
+            col_expr(facts, "amount", &accessor),
+            col_expr(dims, "dk", &accessor),
+            vec![],
+            cols_expr(dims, &["dk", "name"], &accessor),
+        ),
+        composite_result(vec![select(&[
+            pc("name").alias("name"),
+            pc("dk").alias("dk"),
+        ])]),
+    );
+    for query in [
+        "select name, dk from facts join dims on facts.amount = dims.dk",
+        "select name, dk from facts join dims on dims.dk = facts.amount",
+        "select name, dk from facts inner join dims on dk = amount",
+    ] {
+        assert_eq!(query_to_provable_ast(facts, query, &accessor), expected_ast);
+    }
+}
+
+#[test]
+fn we_can_select_all_columns_of_a_join_on_columns_with_the_same_name() {
+    let facts = "sxt.facts".parse().unwrap();
+    let dims = "sxt.dims".parse().unwrap();
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    accessor.add_table(facts, record_batch!("k" => [1_i64], "a" => [2_i64]), 0);
+    accessor.add_table(dims, record_batch!("b" => [3_i64], "k" => [1_i64]), 0);
+    let ast = query_to_provable_ast(
+        facts,
+        "select * from facts join dims on facts.k = dims.k",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        join(
+            tab(facts),
+            tab(dims),
+            col_expr(facts, "k", &accessor),
+            col_expr(dims, "k", &accessor),
+            cols_expr(facts, &["a"], &accessor),
+            cols_expr(dims, &["b"], &accessor),
+        ),
+        composite_result(vec![select(&[
+            pc("k").alias("k"),
+            pc("a").alias("a"),
+            pc("b").alias("b"),
+        ])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_group_the_result_of_a_join() {
+    let (facts, dims, accessor) = get_join_test_accessor();
+    let ast = query_to_provable_ast(
+        facts,
+        "select name, sum(amount) as total from facts join dims on facts.k = dims.dk group by name",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        join(
+            tab(facts),
+            tab(dims),
+            col_expr(facts, "k", &accessor),
+            col_expr(dims, "dk", &accessor),
+            cols_expr(facts, &["amount"], &accessor),
+            cols_expr(dims, &["name"], &accessor),
+        ),
+        composite_result(vec![
+            groupby(
+                vec![pc("name")],
+                vec![
+                    pc("name").first().alias("name"),
+                    pc("amount").sum().alias("total"),
+                ],
+            ),
+            select(&[pc("name"), pc("total")]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_invalid_join() {
+    let (facts, _, accessor) = get_join_test_accessor();
+    let invalid_queries = [
+        // `k` exists in both tables but only `facts.k` is a key
+        "select k from facts join dims on facts.k = dims.dk",
+        "select * from facts join dims on facts.k = dims.dk",
+        "select amount from facts join dims on k = dk",
+        // both columns of the condition belong to the same table
+        "select amount from facts join dims on facts.k = facts.amount",
+        // the keys must have comparable types
+        "select amount from facts join dims on facts.note = dims.dk",
+        // unknown tables and columns
+        "select amount from facts join dims on facts.k = other.dk",
+        "select amount from facts join dims on facts.k = dims.missing",
+        "select missing from facts join dims on facts.k = dims.dk",
+        "select amount from facts join missing on facts.k = missing.dk",
+        // self joins and joins of more than two tables
+        "select amount from facts join facts on facts.k = facts.k",
+        "select amount from facts join dims on facts.k = dims.dk join other on dims.k = other.k",
+    ];
+    for query in invalid_queries {
+        invalid_query_to_provable_ast(facts, query, &accessor);
+    }
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_a_filtered_join() {
+    let (facts, _, accessor) = get_join_test_accessor();
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select amount from facts join dims on facts.k = dims.dk where weight = 7")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, facts.schema_id(), &accessor),
+        Err(ConversionError::Unprovable(_))
+    ));
+}
+
+fn get_nullable_test_accessor() -> (TableRef, RecordBatchTestAccessor) {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        RecordBatch::try_from_iter([
+            (
+                "a",
+                Arc::new(Int64Array::from(vec![Some(1), None])) as ArrayRef,
+            ),
+            ("b", Arc::new(Int64Array::from(vec![2, 3]))),
+        ])
+        .unwrap(),
+        0,
+    );
+    (t, accessor)
+}
+
+#[test]
+fn we_return_the_validity_of_nullable_result_columns() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(t, "select a, b from sxt_tab where b = 2", &accessor);
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a", "__valid__a", "b"], &accessor),
+            tab(t),
+            equal(column(t, "b", &accessor), const_bigint(2)),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_filter_by_whether_a_column_is_null() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(t, "select b from sxt_tab where a is null", &accessor);
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["b"], &accessor),
+            tab(t),
+            not(column(t, "__valid__a", &accessor)),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_sum_and_count_a_nullable_column_in_a_provable_group_by() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select b, sum(a) as s, count(a) as n from sxt_tab where b = 2 group by b",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(t, &["b"], &accessor),
+            sums_expr(
+                t,
+                &["a", "__valid__a", "__valid__a"],
+                &["s", "__valid__s", "n"],
+                &[ColumnType::BigInt; 3],
+                &accessor,
+            ),
+            "__count__",
+            tab(t),
+            equal(column(t, "b", &accessor), const_bigint(2)),
+        ),
+        composite_result(vec![select(&[
+            pc("b").alias("b"),
+            pc("s").alias("s"),
+            pc("n").alias("n"),
+        ])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_sum_count_and_average_expressions_in_a_provable_group_by() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select b, sum(a * b) as p, count(case when b = 2 then 1 end) as h, avg(b + 1) as x \
+            from sxt_tab where b = 2 group by b",
+        &accessor,
+    );
+    let decimal = ColumnType::Decimal75(Precision::new(25).unwrap(), 6);
+    let field = |alias: &str| ColumnField::new(alias.parse().unwrap(), ColumnType::BigInt);
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(t, &["b"], &accessor),
+            vec![
+                (
+                    multiply(column(t, "a", &accessor), column(t, "b", &accessor)),
+                    field("p"),
+                ),
+                (column(t, "__valid__a", &accessor), field("__valid__p")),
+                (
+                    case(
+                        vec![(
+                            equal(column(t, "b", &accessor), const_bigint(2)),
+                            const_bool(true),
+                        )],
+                        const_bool(false),
+                    ),
+                    field("h"),
+                ),
+                (add(column(t, "b", &accessor), const_bigint(1)), field("x")),
+            ],
+            "__count__",
+            tab(t),
+            equal(column(t, "b", &accessor), const_bigint(2)),
+        ),
+        composite_result(vec![
+            select(&[
+                pc("b").alias("b"),
+                pc("p").alias("p"),
+                pc("h").alias("h"),
+                pc("x").alias("x"),
+                pc("__count__").alias("__count__"),
+            ]),
+            Box::new(AverageExpr::new(vec![(
+                "x".parse().unwrap(),
+                "__count__".parse().unwrap(),
+                decimal,
+            )])),
+            select(&[
+                pc("b").alias("b"),
+                pc("p").alias("p"),
+                pc("h").alias("h"),
+                pc("x").alias("x"),
+            ]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn select_wildcard_does_not_list_validity_columns_as_result_columns() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(t, "select * from sxt_tab where b = 2", &accessor);
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a", "__valid__a", "b"], &accessor),
+            tab(t),
+            equal(column(t, "b", &accessor), const_bigint(2)),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+fn get_temporal_test_accessor() -> (TableRef, RecordBatchTestAccessor) {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        RecordBatch::try_from_iter([
+            ("a", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+            (
+                "t",
+                Arc::new(
+                    TimestampMillisecondArray::from(vec![1_704_067_200_000, 1_704_067_200_500])
+                        .with_timezone("+00:00"),
+                ),
+            ),
+            ("d", Arc::new(Date32Array::from(vec![19_723, 19_724]))),
+        ])
+        .unwrap(),
+        0,
+    );
+    (t, accessor)
+}
+
+#[test]
+fn we_can_filter_by_a_timestamp_range() {
+    let (t, accessor) = get_temporal_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select a from sxt_tab where t >= timestamp '2024-01-01T00:00:00Z' \
+         and t <= timestamp '2024-01-01T00:00:00.250Z'",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            and(
+                gte(
+                    column(t, "t", &accessor),
+                    const_timestamp(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, 1_704_067_200),
+                ),
+                lte(
+                    column(t, "t", &accessor),
+                    const_timestamp(
+                        PoSQLTimeUnit::Millisecond,
+                        PoSQLTimeZone::Utc,
+                        1_704_067_200_250,
+                    ),
+                ),
+            ),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_filter_by_a_date() {
+    let (t, accessor) = get_temporal_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select a from sxt_tab where d = date '2024-01-01'",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            equal(column(t, "d", &accessor), const_date(19_723)),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_compare_timestamps_or_dates_with_other_types() {
+    let (t, accessor) = get_temporal_test_accessor();
+    invalid_query_to_provable_ast(
+        t,
+        "select a from sxt_tab where t = 1704067200000",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select a from sxt_tab where t >= date '2024-01-01'",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select a from sxt_tab where d <= timestamp '2024-01-01T00:00:00Z'",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where t + 1 = t", &accessor);
+    invalid_query_to_provable_ast(t, "select sum(d) from sxt_tab", &accessor);
+}
+
+#[test]
+fn we_can_filter_by_an_in_list() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(t, "select b from sxt_tab where b in (2, 4)", &accessor);
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["b"], &accessor),
+            tab(t),
+            in_list(
+                column(t, "b", &accessor),
+                vec![LiteralValue::BigInt(2), LiteralValue::BigInt(4)],
+            ),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_do_not_select_nulls_with_a_not_in_list() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(t, "select b from sxt_tab where a not in (1)", &accessor);
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["b"], &accessor),
+            tab(t),
+            and(
+                not(in_list(
+                    column(t, "a", &accessor),
+                    vec![LiteralValue::BigInt(1)],
+                )),
+                column(t, "__valid__a", &accessor),
+            ),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_filter_by_an_in_list_of_literals_of_another_type() {
+    let (t, accessor) = get_nullable_test_accessor();
+    invalid_query_to_provable_ast(t, "select b from sxt_tab where b in ('2')", &accessor);
+    invalid_query_to_provable_ast(t, "select b from sxt_tab where b in (2, true)", &accessor);
+}
+
+#[test]
+fn we_can_filter_by_a_between_range() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select b from sxt_tab where b not between 1 and 2 + 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["b"], &accessor),
+            tab(t),
+            not(between(
+                column(t, "b", &accessor),
+                const_bigint(1),
+                add(const_bigint(2), const_bigint(1)),
+            )),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_do_not_select_nulls_with_a_between_range_unless_a_known_bound_excludes_the_row() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select b from sxt_tab where b between a and 3",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["b"], &accessor),
+            tab(t),
+            and(
+                between(
+                    column(t, "b", &accessor),
+                    column(t, "a", &accessor),
+                    const_bigint(3),
+                ),
+                or(
+                    column(t, "__valid__a", &accessor),
+                    not(lte(column(t, "b", &accessor), const_bigint(3))),
+                ),
+            ),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_filter_by_a_between_range_with_bounds_of_another_type() {
+    let (t, accessor) = get_nullable_test_accessor();
+    invalid_query_to_provable_ast(
+        t,
+        "select b from sxt_tab where b between '1' and 2",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select b from sxt_tab where b between 1 and true",
+        &accessor,
+    );
+}
+
+fn get_varchar_test_accessor() -> (TableRef, RecordBatchTestAccessor) {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = record_batch_to_accessor(
+        t,
+        record_batch!(
+            "a" => [1_i64, 2],
+            "s" => ["SKU-0100", "SKU-0250"],
+            "t" => ["x", "y"],
+        ),
+        0,
+    );
+    (t, accessor)
+}
+
+#[test]
+fn we_can_filter_by_comparing_a_varchar_column_with_strings() {
+    let (t, accessor) = get_varchar_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select a from sxt_tab where s >= 'SKU-0100' and 'SKU-0200' > s",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            and(
+                gte(
+                    column(t, "__order__s", &accessor),
+                    const_int128(order_key("SKU-0100")),
+                ),
+                not(lte(
+                    const_int128(order_key("SKU-0200")),
+                    column(t, "__order__s", &accessor),
+                )),
+            ),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_filter_by_a_between_range_of_strings() {
+    let (t, accessor) = get_varchar_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select * from sxt_tab where s between 'SKU-0100' and 'SKU-0199'",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a", "s", "t"], &accessor),
+            tab(t),
+            between(
+                column(t, "__order__s", &accessor),
+                const_int128(order_key("SKU-0100")),
+                const_int128(order_key("SKU-0199")),
+            ),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_compare_varchar_columns_with_anything_but_short_string_literals() {
+    let (t, accessor) = get_varchar_test_accessor();
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where s <= t", &accessor);
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where 'a' <= 'b'", &accessor);
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where s <= 1", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select a from sxt_tab where s between 'a' and t",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select a from sxt_tab where s <= 'abcdefghijklmno'",
+        &accessor,
+    );
+    query_to_provable_ast(
+        t,
+        "select a from sxt_tab where s <= 'abcdefghijklmn'",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_filter_by_prefix_and_exact_like_patterns() {
+    let (t, accessor) = get_varchar_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select a from sxt_tab where s like 'SKU-01%' and not t like 'x'",
+        &accessor,
+    );
+    let (low, high) = prefix_order_key_range("SKU-01").unwrap();
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            and(
+                between(
+                    column(t, "__order__s", &accessor),
+                    const_int128(low),
+                    const_int128(high),
+                ),
+                not(equal(column(t, "t", &accessor), const_varchar("x"))),
+            ),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_filter_by_unsupported_like_patterns() {
+    let (t, accessor) = get_varchar_test_accessor();
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where a like '1%'", &accessor);
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where s like '%0'", &accessor);
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where s like 'S_U%'", &accessor);
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where s like 'SKU\\%'", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select a from sxt_tab where s like 'abcdefghijklmnop%'",
+        &accessor,
+    );
+    query_to_provable_ast(
+        t,
+        "select a from sxt_tab where s like 'abcdefghijklmno%'",
+        &accessor,
+    );
+    query_to_provable_ast(t, "select a from sxt_tab where s like '%'", &accessor);
+}
+
+#[test]
+fn we_can_filter_and_select_with_case_expressions() {
+    let (t, accessor) = get_varchar_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select case when a >= 2 then 'big' else 'small' end as size from sxt_tab \
+         where case s when 'SKU-0100' then a else 0 end = 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![aliased_plan(
+                case(
+                    vec![(
+                        gte(column(t, "a", &accessor), const_bigint(2)),
+                        const_varchar("big"),
+                    )],
+                    const_varchar("small"),
+                ),
+                "size",
+            )],
+            tab(t),
+            equal(
+                case(
+                    vec![(
+                        equal(column(t, "s", &accessor), const_varchar("SKU-0100")),
+                        column(t, "a", &accessor),
+                    )],
+                    const_bigint(0),
+                ),
+                const_bigint(1),
+            ),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_return_the_validity_of_case_expressions_that_may_be_null() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select case when a = 1 then b end as c from sxt_tab",
+        &accessor,
+    );
+    let when = || {
+        and(
+            equal(column(t, "a", &accessor), const_bigint(1)),
+            column(t, "__valid__a", &accessor),
+        )
+    };
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![
+                aliased_plan(
+                    case(
+                        vec![(when(), column(t, "b", &accessor))],
+                        column(t, "b", &accessor),
+                    ),
+                    "c",
+                ),
+                aliased_plan(
+                    case(vec![(when(), const_bool(true))], const_bool(false)),
+                    "__valid__c",
+                ),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_use_case_expressions_with_invalid_conditions_or_results() {
+    let (t, accessor) = get_varchar_test_accessor();
+    invalid_query_to_provable_ast(
+        t,
+        "select a from sxt_tab where case when a then true else false end",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select case when a = 1 then a else s end as x from sxt_tab",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_filter_and_select_with_casts() {
+    let (t, accessor) = get_varchar_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select cast(a as decimal(10, 2)) as d, cast(a as smallint) as e from sxt_tab \
+         where cast(a - 1 as boolean)",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![
+                // The casts may overflow, so they are only checked on the selected rows.
+                aliased_plan(
+                    guarded_cast(
+                        column(t, "a", &accessor),
+                        ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+                        cast(
+                            subtract(column(t, "a", &accessor), const_bigint(1)),
+                            ColumnType::Boolean,
+                        ),
+                    ),
+                    "d",
+                ),
+                aliased_plan(
+                    guarded_cast(
+                        column(t, "a", &accessor),
+                        ColumnType::SmallInt,
+                        cast(
+                            subtract(column(t, "a", &accessor), const_bigint(1)),
+                            ColumnType::Boolean,
+                        ),
+                    ),
+                    "e",
+                ),
+            ],
+            tab(t),
+            cast(
+                subtract(column(t, "a", &accessor), const_bigint(1)),
+                ColumnType::Boolean,
+            ),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_return_the_validity_of_casts_that_may_be_null() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(t, "select cast(a as int) as c from sxt_tab", &accessor);
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![
+                aliased_plan(cast(column(t, "a", &accessor), ColumnType::Int), "c"),
+                aliased_plan(column(t, "__valid__a", &accessor), "__valid__c"),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_use_invalid_casts() {
+    let (t, accessor) = get_varchar_test_accessor();
+    invalid_query_to_provable_ast(t, "select cast(s as bigint) as x from sxt_tab", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select cast(cast(a as decimal(10, 2)) as bigint) as x from sxt_tab",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select a from sxt_tab where cast(s as boolean)",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_average_columns_in_a_provable_group_by() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select b, avg(a) as x, avg(b) as y from sxt_tab where b = 2 group by b",
+        &accessor,
+    );
+    let decimal = ColumnType::Decimal75(Precision::new(25).unwrap(), 6);
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(t, &["b"], &accessor),
+            sums_expr(
+                t,
+                &["a", "__valid__a", "b"],
+                &["x", "__count__x", "y"],
+                &[ColumnType::BigInt; 3],
+                &accessor,
+            ),
+            "__count__",
+            tab(t),
+            equal(column(t, "b", &accessor), const_bigint(2)),
+        ),
+        composite_result(vec![
+            select(&[
+                pc("b").alias("b"),
+                pc("x").alias("x"),
+                pc("y").alias("y"),
+                pc("__count__x").alias("__count__x"),
+                pc("__count__").alias("__count__"),
+            ]),
+            Box::new(AverageExpr::new(vec![
+                ("x".parse().unwrap(), "__count__x".parse().unwrap(), decimal),
+                ("y".parse().unwrap(), "__count__".parse().unwrap(), decimal),
+            ])),
+            select(&[pc("b").alias("b"), pc("x").alias("x"), pc("y").alias("y")]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_average_in_the_post_processing_step_using_a_sum_and_a_count() {
+    let ast = query!(select: ["i", "avg(i) as x"], group: ["i"]);
+    let (t, accessor) = get_test_accessor();
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["i"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![
+            groupby(
+                vec![pc("i")],
+                vec![
+                    pc("i").first().alias("i"),
+                    pc("i").sum().alias("x"),
+                    pc("i").count().alias("__count__x"),
+                ],
+            ),
+            select(&[pc("i"), pc("x"), pc("__count__x")]),
+            Box::new(AverageExpr::new(vec![(
+                "x".parse().unwrap(),
+                "__count__x".parse().unwrap(),
+                ColumnType::Decimal75(Precision::new(25).unwrap(), 6),
+            )])),
+            select(&[pc("i").alias("i"), pc("x").alias("x")]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_use_averages_inside_expressions_or_of_non_numeric_columns() {
+    let (t, accessor) = get_varchar_test_accessor();
+    invalid_query_to_provable_ast(t, "select avg(s) as x from sxt_tab", &accessor);
+    invalid_query_to_provable_ast(t, "select avg(a) + 1 as x from sxt_tab", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select avg(a) as x, count(a) as __count__x from sxt_tab",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_prove_select_distinct_as_a_group_by() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select distinct a, b from sxt_tab where b = 2",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(t, &["a", "b", "__valid__a"], &accessor),
+            vec![],
+            "__count__",
+            tab(t),
+            equal(column(t, "b", &accessor), const_bigint(2)),
+        ),
+        composite_result(vec![select(&[pc("a").alias("a"), pc("b").alias("b")])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_prove_distinct_counts_by_grouping_by_the_counted_column() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select b, count(distinct a) as x from sxt_tab where b = 2 group by b",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(t, &["b", "a", "__valid__a"], &accessor),
+            vec![],
+            "__count__",
+            tab(t),
+            equal(column(t, "b", &accessor), const_bigint(2)),
+        ),
+        composite_result(vec![
+            groupby(
+                vec![pc("b")],
+                vec![pc("b").first().alias("b"), pc("a").count().alias("x")],
+            ),
+            select(&[pc("b"), pc("x")]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_count_distinct_values_in_the_post_processing_step() {
+    let ast = query!(select: ["count(distinct i) as x"]);
+    let (t, accessor) = get_test_accessor();
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["i"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![select(&[pc("i").count_distinct().alias("x")])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_use_select_distinct_over_expressions_or_with_a_group_by() {
+    let (t, accessor) = get_test_accessor();
+    invalid_query_to_provable_ast(t, "select distinct i + 1 as x from t", &accessor);
+    invalid_query_to_provable_ast(t, "select distinct i from t group by i", &accessor);
+}
+
+#[test]
+fn we_can_apply_a_having_clause_to_a_provable_group_by() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select b, sum(a) as s from sxt_tab where b = 2 group by b having count(*) >= 2 and b = 2",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(t, &["b"], &accessor),
+            sums_expr(
+                t,
+                &["a", "__valid__a"],
+                &["s", "__valid__s"],
+                &[ColumnType::BigInt; 2],
+                &accessor,
+            ),
+            "__having__0",
+            tab(t),
+            equal(column(t, "b", &accessor), const_bigint(2)),
+        ),
+        composite_result(vec![
+            select(&[
+                pc("b").alias("b"),
+                pc("s").alias("s"),
+                pc("__having__0").alias("__having__0"),
+                pc("b").alias("__having__1"),
+            ]),
+            having(
+                Box::new(Expression::Binary {
+                    op: BinaryOperator::And,
+                    left: Box::new(Expression::Binary {
+                        op: BinaryOperator::GreaterThanOrEqual,
+                        left: pc("__having__0"),
+                        right: lit(2_i64),
+                    }),
+                    right: Box::new(Expression::Binary {
+                        op: BinaryOperator::Equal,
+                        left: pc("__having__1"),
+                        right: lit(2_i64),
+                    }),
+                }),
+                &["__having__0", "__having__1"],
+            ),
+            select(&[pc("b").alias("b"), pc("s").alias("s")]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_apply_a_having_clause_in_the_post_processing_step() {
+    let ast = query!(select: ["i", "sum(d) as s"], group: ["i"], having: "max(d) >= 2");
+    let (t, accessor) = get_test_accessor();
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["d", "i"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![
+            groupby(
+                vec![pc("i")],
+                vec![
+                    pc("i").first().alias("i"),
+                    pc("d").sum().alias("s"),
+                    pc("d").max().alias("__having__0"),
+                ],
+            ),
+            select(&[pc("i"), pc("s"), pc("__having__0")]),
+            having(
+                Box::new(Expression::Binary {
+                    op: BinaryOperator::GreaterThanOrEqual,
+                    left: pc("__having__0"),
+                    right: lit(2_i64),
+                }),
+                &["__having__0"],
+            ),
+            select(&[pc("i").alias("i"), pc("s").alias("s")]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_use_having_without_aggregations_or_over_other_columns() {
+    let (t, accessor) = get_test_accessor();
+    invalid_query_to_provable_ast(t, "select i from t having i >= 1", &accessor);
+    invalid_query_to_provable_ast(t, "select sum(i) as s from t having d >= 1", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select i, count(*) as c from t group by i having d >= 1",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select i, count(*) as c from t group by i having sum(i)",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select i, count(*) as c from t group by i having avg(i) >= 1",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select i, count(*) as c from t group by i having i in (1, 2)",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_prove_a_group_by_on_an_expression_or_its_position() {
+    let (t, accessor) = get_test_accessor();
+    let expected_ast = QueryExpr::new(
+        group_by(
+            vec![aliased_plan(
+                add(column(t, "i", &accessor), const_bigint(1)),
+                "j",
+            )],
+            sums_expr(t, &["d"], &["s"], &[ColumnType::Int128], &accessor),
+            "c",
+            tab(t),
+            gte(column(t, "i", &accessor), const_bigint(0)),
+        ),
+        composite_result(vec![select(&[
+            pc("j").alias("j"),
+            pc("s").alias("s"),
+            pc("c").alias("c"),
+        ])]),
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select i + 1 as j, sum(d) as s, count(*) as c from t where i >= 0 group by i + 1",
+        &accessor,
+    );
+    assert_eq!(ast, expected_ast);
+    let ast = query_to_provable_ast(
+        t,
+        "select i + 1 as j, sum(d) as s, count(*) as c from t where i >= 0 group by 1",
+        &accessor,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_group_by_a_case_bucket_in_the_post_processing_step() {
+    let ast = query!(
+        select: ["case when i >= 1 then 'x' else 'y' end as k", "count(*) as c"],
+        group: ["1"]
+    );
+    let (t, accessor) = get_test_accessor();
+    let bucket = Box::new(Expression::Case {
+        when_then: vec![(
+            Box::new(Expression::Binary {
+                op: BinaryOperator::GreaterThanOrEqual,
+                left: pc("i"),
+                right: lit(1_i64),
+            }),
+            lit("x"),
+        )],
+        else_expr: Some(lit("y")),
+    });
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["i"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![
+            groupby(
+                vec![bucket.clone()],
+                vec![bucket.first().alias("k"), pc("i").count().alias("c")],
+            ),
+            select(&[pc("k"), pc("c")]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_group_by_invalid_expressions_or_positions() {
+    let (t, accessor) = get_test_accessor();
+    invalid_query_to_provable_ast(t, "select i, count(*) as c from t group by 0", &accessor);
+    invalid_query_to_provable_ast(t, "select i, count(*) as c from t group by 3", &accessor);
+    invalid_query_to_provable_ast(t, "select * from t group by 1", &accessor);
+    invalid_query_to_provable_ast(t, "select i, count(*) as c from t group by 2", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select i, count(*) as c from t group by i in (1, 2)",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(t, "select i from t group by i + 1", &accessor);
+    invalid_query_to_provable_ast(t, "select i + 2 as j from t group by i + 1", &accessor);
+}
+
+fn get_union_test_accessor() -> (TableRef, TableRef, RecordBatchTestAccessor) {
+    let sales = "sxt.sales".parse().unwrap();
+    let returns = "sxt.returns".parse().unwrap();
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    accessor.add_table(
+        sales,
+        record_batch!(
+            "amount" => [1_i64, 2],
+            "region" => ["a", "b"],
+        ),
+        0,
+    );
+    accessor.add_table(
+        returns,
+        record_batch!(
+            "refund" => [3_i64, 4, 5],
+            "area" => ["c", "d", "e"],
+            "weight" => [6_i128, 7, 8],
+        ),
+        2,
+    );
+    (sales, returns, accessor)
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_union_all() {
+    let (sales, returns, accessor) = get_union_test_accessor();
+    let ast = query_to_provable_ast(
+        sales,
+        "select amount, region from sales where amount = 1 union all select refund, area from returns order by amount limit 2",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        union(vec![
+            dense_filter(
+                cols_expr_plan(sales, &["amount", "region"], &accessor),
+                tab(sales),
+                equal(column(sales, "amount", &accessor), const_bigint(1)),
+            ),
+            dense_filter(
+                cols_expr_plan(returns, &["refund", "area"], &accessor),
+                tab(returns),
+                const_bool(true),
+            ),
+        ]),
+        composite_result(vec![orders(&["amount"], &[Asc]), slice(2, 0)]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_union_of_more_than_two_queries() {
+    let (sales, returns, accessor) = get_union_test_accessor();
+    let ast = query_to_provable_ast(
+        sales,
+        "select amount as x from sales union select refund from returns union select amount + 1 as y from sales",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        union(vec![
+            dense_filter(
+                vec![aliased_plan(column(sales, "amount", &accessor), "x")],
+                tab(sales),
+                const_bool(true),
+            ),
+            dense_filter(
+                cols_expr_plan(returns, &["refund"], &accessor),
+                tab(returns),
+                const_bool(true),
+            ),
+            dense_filter(
+                vec![aliased_plan(
+                    add(column(sales, "amount", &accessor), const_bigint(1)),
+                    "y",
+                )],
+                tab(sales),
+                const_bool(true),
+            ),
+        ]),
+        composite_result(vec![
+            groupby(vec![pc("x")], vec![pc("x").first().alias("x")]),
+            select(&[pc("x")]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_unprovable_or_mismatched_union() {
+    let (sales, _, accessor) = get_union_test_accessor();
+    // different numbers of result columns
+    invalid_query_to_provable_ast(
+        sales,
+        "select amount from sales union all select refund, area from returns",
+        &accessor,
+    );
+    // different types of result columns
+    invalid_query_to_provable_ast(
+        sales,
+        "select amount from sales union all select weight from returns",
+        &accessor,
+    );
+    // aggregations
+    invalid_query_to_provable_ast(
+        sales,
+        "select sum(amount) as s from sales union all select refund from returns",
+        &accessor,
+    );
+    // a mix of UNION and UNION ALL
+    invalid_query_to_provable_ast(
+        sales,
+        "select amount from sales union select refund from returns union all select amount from sales",
+        &accessor,
+    );
+    // an order by on a column of another query than the first one
+    invalid_query_to_provable_ast(
+        sales,
+        "select amount from sales union all select refund from returns order by refund",
+        &accessor,
+    );
+}
+
+fn get_subquery_test_accessor() -> (TableRef, TableRef, RecordBatchTestAccessor) {
+    let users = "sxt.users".parse().unwrap();
+    let allowed = "sxt.allowed".parse().unwrap();
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    accessor.add_table(
+        users,
+        record_batch!(
+            "user_id" => [1_i64, 2, 3],
+            "score" => [10_i64, 20, 30],
+            "name" => ["a", "b", "c"],
+        ),
+        0,
+    );
+    accessor.add_table(
+        allowed,
+        record_batch!(
+            "id" => [2_i64, 3],
+            "tier" => ["gold", "silver"],
+        ),
+        1,
+    );
+    (users, allowed, accessor)
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_in_subquery() {
+    let (users, allowed, accessor) = get_subquery_test_accessor();
+    let ast = query_to_provable_ast(
+        users,
+        "select user_id from users where score >= 20 and user_id in (select id from allowed where tier = 'gold')",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        subquery_filter(
+            dense_filter(
+                cols_expr_plan(users, &["user_id"], &accessor),
+                tab(users),
+                gte(column(users, "score", &accessor), const_bigint(20)),
+            ),
+            vec![in_subquery(
+                column(users, "user_id", &accessor),
+                dense_filter(
+                    cols_expr_plan(allowed, &["id"], &accessor),
+                    tab(allowed),
+                    equal(column(allowed, "tier", &accessor), const_varchar("gold")),
+                ),
+                false,
+            )],
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_exists_and_scalar_subqueries() {
+    let (users, allowed, accessor) = get_subquery_test_accessor();
+    let ast = query_to_provable_ast(
+        users,
+        "select name from users where not exists (select id from allowed where tier = 'gold') \
+         and (select count(*) as c from allowed) < user_id \
+         and score >= (select sum(id) as s from allowed where tier = 'silver') order by name",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        subquery_filter(
+            dense_filter(
+                cols_expr_plan(users, &["name"], &accessor),
+                tab(users),
+                const_bool(true),
+            ),
+            vec![
+                in_subquery(
+                    const_bigint(0),
+                    dense_filter(
+                        vec![aliased_plan(const_bigint(0), "__exists__")],
+                        tab(allowed),
+                        equal(column(allowed, "tier", &accessor), const_varchar("gold")),
+                    ),
+                    true,
+                ),
+                compare_subquery(
+                    column(users, "user_id", &accessor),
+                    SubqueryComparison::GreaterThan,
+                    SubqueryAggregation::Count,
+                    dense_filter(
+                        vec![aliased_plan(const_bigint(1), "c")],
+                        tab(allowed),
+                        const_bool(true),
+                    ),
+                ),
+                compare_subquery(
+                    column(users, "score", &accessor),
+                    SubqueryComparison::GreaterThanOrEqual,
+                    SubqueryAggregation::Sum,
+                    dense_filter(
+                        vec![aliased_plan(column(allowed, "id", &accessor), "s")],
+                        tab(allowed),
+                        equal(column(allowed, "tier", &accessor), const_varchar("silver")),
+                    ),
+                ),
+            ],
+        ),
+        composite_result(vec![orders(&["name"], &[Asc])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_unprovable_subquery() {
+    let (users, _, accessor) = get_subquery_test_accessor();
+    for query in [
+        // a subquery outside of the conditions of the WHERE clause joined by AND
+        "select user_id, (select count(*) as c from allowed) as c from users",
+        "select user_id from users where score = 10 or user_id in (select id from allowed)",
+        // a subquery of IN with several result expressions or with aggregations
+        "select user_id from users where user_id in (select id, tier from allowed)",
+        "select user_id from users where user_id in (select sum(id) as s from allowed)",
+        // a subquery of IN whose result has another type
+        "select user_id from users where user_id in (select tier from allowed)",
+        // a comparison with an aggregation other than COUNT or SUM, or with a GROUP BY
+        "select user_id from users where user_id = (select max(id) as m from allowed)",
+        "select user_id from users where user_id = (select id from allowed)",
+        "select user_id from users where user_id = (select sum(id) as s from allowed group by tier)",
+        // a comparison of something else than integers
+        "select user_id from users where name = (select count(*) as c from allowed)",
+        // a subquery of a subquery
+        "select user_id from users where user_id in (select id from allowed where exists (select name from users))",
+    ] {
+        invalid_query_to_provable_ast(users, query, &accessor);
+    }
+}
+
+#[test]
+fn we_list_the_supported_aggregations_when_comparing_with_another_aggregation() {
+    let (users, _, accessor) = get_subquery_test_accessor();
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select user_id from users where user_id = (select max(id) as m from allowed)")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, users.schema_id(), &accessor),
+        Err(ConversionError::Unprovable(message))
+            if message == "the subquery of a comparison can only be a COUNT or a SUM, not MAX"
+    ));
+}
+
+#[test]
+fn we_can_convert_an_ast_with_common_table_expressions() {
+    let (users, allowed, accessor) = get_subquery_test_accessor();
+    let ast = query_to_provable_ast(
+        users,
+        "with recent as (select user_id, score * 2 as twice from users where score >= 20) \
+         select twice from recent where user_id = 3",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![aliased_plan(
+                multiply(column(users, "score", &accessor), const_bigint(2)),
+                "twice",
+            )],
+            tab(users),
+            and(
+                gte(column(users, "score", &accessor), const_bigint(20)),
+                equal(column(users, "user_id", &accessor), const_bigint(3)),
+            ),
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+
+    // a common table expression may select from those before it
+    let ast = query_to_provable_ast(
+        users,
+        "with a as (select user_id as id, score from users where score >= 20), \
+         b as (select * from a where score <= 30) select * from b order by id",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![
+                aliased_plan(column(users, "user_id", &accessor), "id"),
+                aliased_plan(column(users, "score", &accessor), "score"),
+            ],
+            tab(users),
+            and(
+                gte(column(users, "score", &accessor), const_bigint(20)),
+                lte(column(users, "score", &accessor), const_bigint(30)),
+            ),
+        ),
+        composite_result(vec![orders(&["id"], &[Asc])]),
+    );
+    assert_eq!(ast, expected_ast);
+
+    // the subquery of a condition may select from a common table expression too
+    let ast = query_to_provable_ast(
+        users,
+        "with gold as (select id from allowed where tier = 'gold') \
+         select user_id from users where user_id in (select id from gold)",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        subquery_filter(
+            dense_filter(
+                cols_expr_plan(users, &["user_id"], &accessor),
+                tab(users),
+                const_bool(true),
+            ),
+            vec![in_subquery(
+                column(users, "user_id", &accessor),
+                dense_filter(
+                    cols_expr_plan(allowed, &["id"], &accessor),
+                    tab(allowed),
+                    equal(column(allowed, "tier", &accessor), const_varchar("gold")),
+                ),
+                false,
+            )],
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_common_table_expressions_that_are_proven_on_their_own() {
+    let (users, _, accessor) = get_subquery_test_accessor();
+    // The plan of the query is that of the common table expression, over whose result the query is computed.
+    for (query, cte_query) in [
+        (
+            "with r as (select sum(score) as s from users where score >= 20) select s from r where s > 10",
+            "select sum(score) as s from users where score >= 20",
+        ),
+        (
+            "with r as (select distinct name from users) select name from r order by name desc",
+            "select distinct name from users",
+        ),
+        (
+            "with r as (select user_id from users union all select id from allowed) \
+             select user_id, count(*) as n from r group by user_id",
+            "select user_id from users union all select id from allowed",
+        ),
+        (
+            "with r as (select user_id from users where user_id in (select id from allowed)) \
+             select user_id from r limit 1",
+            "select user_id from users where user_id in (select id from allowed)",
+        ),
+        // a common table expression that selects from one that is proven on its own is computed over its result too
+        (
+            "with r as (select name, count(*) as n from users where score >= 10 group by name), \
+             s as (select name from r where n > 1) select name from s",
+            "select name, count(*) as n from users where score >= 10 group by name",
+        ),
+    ] {
+        let ast = query_to_provable_ast(users, query, &accessor);
+        let cte_ast = query_to_provable_ast(users, cte_query, &accessor);
+        assert_eq!(ast.proof_expr(), cte_ast.proof_expr());
+    }
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_unprovable_or_invalid_common_table_expressions() {
+    let (users, _, accessor) = get_subquery_test_accessor();
+    for query in [
+        // a common table expression that is proven on its own can not be part of another plan
+        "with r as (select distinct user_id from users) select user_id from r union all select id from allowed",
+        "with r as (select distinct user_id from users) select id from allowed where id in (select user_id from r)",
+        "with r as (select distinct user_id from users) \
+         select user_id, sum(user_id) over (order by user_id) as t from r",
+        // a join with a common table expression
+        "with r as (select id from allowed) select user_id from users join r on users.user_id = r.id",
+        // a name that is defined twice
+        "with r as (select user_id from users), r as (select id from allowed) select id from r",
+        // a common table expression that selects from a later one
+        "with r as (select user_id from s), s as (select user_id from users) select user_id from r",
+    ] {
+        invalid_query_to_provable_ast(users, query, &accessor);
+    }
+    // a column that is not one of the common table expression
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("with r as (select user_id from users) select score from r")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, users.schema_id(), &accessor),
+        Err(ConversionError::MissingColumn(..))
+    ));
+}
+
+fn get_window_test_accessor() -> (TableRef, RecordBatchTestAccessor) {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = RecordBatchTestAccessor::new_empty();
+    accessor.add_table(
+        t,
+        record_batch!(
+            "ts" => [1_i64, 2, 3],
+            "k" => ["x", "y", "x"],
+            "a" => [5_i64, 6, 7],
+        ),
+        0,
+    );
+    accessor.set_strictly_increasing(t, "ts".parse().unwrap());
+    (t, accessor)
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_provable_running_sum() {
+    let (t, accessor) = get_window_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select k, sum(a) over (order by ts) as s, ts from t where a >= 6 order by s desc limit 2",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        running_sum(
+            cols_expr_plan(t, &["k", "ts"], &accessor),
+            tab(t),
+            gte(column(t, "a", &accessor), const_bigint(6)),
+            col_expr(t, "ts", &accessor),
+            aliased_plan(column(t, "a", &accessor), "s"),
+            1,
+        ),
+        composite_result(vec![orders(&["s"], &[Desc]), slice(2, 0)]),
+    );
+    assert_eq!(ast, expected_ast);
+
+    // the ordering column of a common table expression is that of its table
+    let ast = query_to_provable_ast(
+        t,
+        "with r as (select ts as time, a from t) select sum(a) over (order by time) as s from r",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        running_sum(
+            vec![],
+            tab(t),
+            const_bool(true),
+            col_expr(t, "ts", &accessor),
+            aliased_plan(column(t, "a", &accessor), "s"),
+            0,
+        ),
+        composite_result(vec![]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_compute_window_functions_in_the_post_processing_step() {
+    let (t, accessor) = get_window_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select k, row_number() over (partition by k order by ts desc) as n from t where a >= 6",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["k", "ts"], &accessor),
+            tab(t),
+            gte(column(t, "a", &accessor), const_bigint(6)),
+        ),
+        composite_result(vec![
+            windows(vec![window(
+                WindowFunction::RowNumber,
+                &["k"],
+                &[("ts", Desc)],
+            )
+            .alias("__window__n")]),
+            select(&[pc("k").alias("k"), pc("__window__n").alias("n")]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+
+    // a running sum within partitions is not provable
+    let ast = query_to_provable_ast(
+        t,
+        "select sum(a) over (partition by k order by ts) as s from t order by s",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a", "k", "ts"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![
+            windows(vec![window(
+                WindowFunction::Sum(pc("a")),
+                &["k"],
+                &[("ts", Asc)],
+            )
+            .alias("__window__s")]),
+            select(&[pc("__window__s").alias("s")]),
+            orders(&["s"], &[Asc]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+
+    // a running sum over a column that is not known to be strictly increasing is not provable
+    let ast = query_to_provable_ast(t, "select sum(a) over (order by a) as s from t", &accessor);
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![
+            windows(vec![window(
+                WindowFunction::Sum(pc("a")),
+                &[],
+                &[("a", Asc)],
+            )
+            .alias("__window__s")]),
+            select(&[pc("__window__s").alias("s")]),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_invalid_or_unprovable_window_functions() {
+    let (t, accessor) = get_window_test_accessor();
+    for query in [
+        // a window function within an expression or a condition
+        "select a + row_number() over () as n from t",
+        "select a from t where rank() over (order by ts) = 1",
+        // a running sum of something else than a numeric column
+        "select sum(k) over (order by ts) as s from t",
+        "select sum(a + 1) over (order by ts) as s from t",
+        // a window function along with aggregations or subqueries
+        "select sum(a) as total, row_number() over () as n from t",
+        "select row_number() over () as n from t where a in (select a from t)",
+        // a common table expression with a window function, or a window ordered by an expression of one
+        "with r as (select row_number() over () as n from t) select n from r",
+        "with r as (select a, ts + 1 as u from t) select sum(a) over (order by u) as s from r",
+    ] {
+        invalid_query_to_provable_ast(t, query, &accessor);
+    }
+}
+
+///////////////////////////
+// Qualified columns
+///////////////////////////
+#[test]
+fn we_can_qualify_columns_by_their_table_and_schema_or_by_an_alias() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a", "__valid__a", "b"], &accessor),
+            tab(t),
+            equal(column(t, "b", &accessor), const_bigint(2)),
+        ),
+        composite_result(vec![]),
+    );
+    for query in [
+        "select sxt_tab.a, sxt.sxt_tab.b from sxt.sxt_tab where sxt_tab.b = 2",
+        "select x.a, x.b from sxt_tab as x where x.b = 2",
+        "select x.a, b from sxt.sxt_tab x where x.b = 2",
+        "with r as (select a, b from sxt_tab) select r.a, b from r where r.b = 2",
+        "with r as (select a, b from sxt_tab) select y.a, y.b from r as y where y.b = 2",
+    ] {
+        assert_eq!(query_to_provable_ast(t, query, &accessor), expected_ast);
+    }
+}
+
+#[test]
+fn we_can_tell_the_columns_of_a_join_with_the_same_name_apart_by_their_table() {
+    let (facts, dims, accessor) = get_join_test_accessor();
+    let ast = query_to_provable_ast(
+        facts,
+        "select d.k as dim_k, f.note from sxt.facts as f join dims d on f.amount = d.dk",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        join(
+            tab(facts),
+            tab(dims),
+            col_expr(facts, "amount", &accessor),
+            col_expr(dims, "dk", &accessor),
+            cols_expr(facts, &["note"], &accessor),
+            cols_expr(dims, &["k"], &accessor),
+        ),
+        composite_result(vec![select(&[
+            pc("k").alias("dim_k"),
+            pc("note").alias("note"),
+        ])]),
+    );
+    assert_eq!(ast, expected_ast);
+
+    // the left key is the column of the join that its name refers to
+    let ast = query_to_provable_ast(
+        facts,
+        "select facts.k, name from facts join dims on facts.k = dims.dk",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        join(
+            tab(facts),
+            tab(dims),
+            col_expr(facts, "k", &accessor),
+            col_expr(dims, "dk", &accessor),
+            vec![],
+            cols_expr(dims, &["name"], &accessor),
+        ),
+        composite_result(vec![select(&[
+            pc("k").alias("k"),
+            pc("name").alias("name"),
+        ])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_columns_of_missing_tables_or_missing_columns_of_tables() {
+    let (facts, _, accessor) = get_join_test_accessor();
+    let try_new = |query| {
+        let intermediate_ast = SelectStatementParser::new().parse(query).unwrap();
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, facts.schema_id(), &accessor)
+    };
+    for query in [
+        "select other.amount from facts",
+        "select other.facts.amount from facts",
+        // an alias hides the name of its table
+        "select facts.amount from facts as f",
+        "select amount from facts join dims on facts.k = other.dk",
+        "select facts.amount from facts as f join dims on f.k = dims.dk",
+    ] {
+        assert!(matches!(
+            try_new(query),
+            Err(ConversionError::MissingTable(_))
+        ));
+    }
+    for query in [
+        "select f.weight from facts as f",
+        "select f.weight from facts f join dims d on f.k = d.dk",
+        "select amount from facts f join dims d on f.k = d.missing",
+    ] {
+        assert!(matches!(
+            try_new(query),
+            Err(ConversionError::MissingColumn(..))
+        ));
+    }
+    // both columns of the same name, one of them along with the left key of that name, or without its table
+    for query in [
+        "select f.k, d.k as dim_k from facts f join dims d on f.amount = d.dk",
+        "select d.k from facts f join dims d on f.k = d.dk",
+    ] {
+        assert!(matches!(
+            try_new(query),
+            Err(ConversionError::Unprovable(_))
+        ));
+    }
+    assert!(matches!(
+        try_new("select d.k, k as other_k from facts f join dims d on f.amount = d.dk"),
+        Err(ConversionError::AmbiguousColumn(_))
+    ));
+}
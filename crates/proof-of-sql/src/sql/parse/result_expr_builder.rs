@@ -1,4 +1,10 @@
-use crate::sql::transform::{CompositionExpr, GroupByExpr, OrderByExprs, SelectExpr, SliceExpr};
+use crate::{
+    base::database::ColumnType,
+    sql::transform::{
+        AverageExpr, CompositionExpr, EmptyAggregationExpr, GroupByExpr, HavingExpr, OrderByExprs,
+        SelectExpr, SliceExpr, WindowExpr,
+    },
+};
 use proof_of_sql_parser::{
     intermediate_ast::{AliasedResultExpr, Expression, OrderBy, Slice},
     Identifier,
@@ -14,7 +20,7 @@ impl ResultExprBuilder {
     /// Chain a new `GroupByExpr` to the current `ResultExpr`.
     pub fn add_group_by_exprs(
         mut self,
-        by_exprs: &[Identifier],
+        by_exprs: &[Expression],
         aliased_exprs: &[AliasedResultExpr],
     ) -> Self {
         if by_exprs.is_empty() {
@@ -25,12 +31,25 @@ impl ResultExprBuilder {
         self
     }
 
+    /// Chain a new `EmptyAggregationExpr` to the current `ResultExpr`, for a proven aggregation without `GROUP BY`.
+    pub fn add_empty_aggregation_expr(
+        mut self,
+        count_alias: Identifier,
+        count_columns: Vec<Identifier>,
+    ) -> Self {
+        self.composition.add(Box::new(EmptyAggregationExpr::new(
+            count_alias,
+            count_columns,
+        )));
+        self
+    }
+
     /// Chain a new `SelectExpr` to the current `ResultExpr`.
     pub fn add_select_exprs(mut self, aliased_exprs: &[AliasedResultExpr]) -> Self {
         assert!(!aliased_exprs.is_empty());
         if !self.composition.is_empty() {
-            // The only transformation before a select is a group by.
-            // GROUP BY modifies the schema, so we need to
+            // The only transformations before a select are a group by, or an empty aggregation
+            // which keeps the schema. GROUP BY modifies the schema, so we need to
             // update the code to reflect the changes.
             let exprs: Vec<_> = aliased_exprs
                 .iter()
@@ -47,6 +66,93 @@ impl ResultExprBuilder {
         self
     }
 
+    /// Chain a new `AverageExpr` to the current `ResultExpr`, followed by a `SelectExpr`
+    /// that drops the counts of the averages.
+    pub fn add_average_exprs(
+        mut self,
+        averages: Vec<(Identifier, Identifier, ColumnType)>,
+        aliased_exprs: &[AliasedResultExpr],
+    ) -> Self {
+        if averages.is_empty() {
+            return self;
+        }
+        self.composition.add(Box::new(AverageExpr::new(averages)));
+        // Selecting each column under its own name keeps the decimal averages out of polars.
+        let exprs: Vec<_> = aliased_exprs
+            .iter()
+            .map(|aliased_expr| AliasedResultExpr {
+                expr: Box::new(Expression::Column(aliased_expr.alias)),
+                alias: aliased_expr.alias,
+            })
+            .collect();
+        self.composition
+            .add(Box::new(SelectExpr::new_from_aliased_result_exprs(&exprs)));
+        self
+    }
+
+    /// Chain a new `WindowExpr` to the current `ResultExpr`, followed by a `SelectExpr`
+    /// of the result expressions, which select the columns of the window functions.
+    pub fn add_window_exprs(
+        mut self,
+        windows: Vec<AliasedResultExpr>,
+        aliased_exprs: &[AliasedResultExpr],
+    ) -> Self {
+        if windows.is_empty() {
+            return self;
+        }
+        self.composition.add(Box::new(WindowExpr::new(windows)));
+        self.composition
+            .add(Box::new(SelectExpr::new_from_aliased_result_exprs(
+                aliased_exprs,
+            )));
+        self
+    }
+
+    /// Chain a new `HavingExpr` to the current `ResultExpr` that keeps the rows for which the `WHERE` condition
+    /// is true, when the rows are not filtered by the plan, where `columns` are the columns of the rows
+    /// that the condition may reference.
+    pub fn add_where_expr(
+        mut self,
+        where_expr: Option<&Expression>,
+        columns: Vec<Identifier>,
+    ) -> Self {
+        if let Some(where_expr) = where_expr {
+            self.composition
+                .add(Box::new(HavingExpr::new(where_expr, columns)));
+        }
+        self
+    }
+
+    /// Chain a new `HavingExpr` to the current `ResultExpr`, followed by a `SelectExpr`
+    /// that drops the result columns that are only computed for the `HAVING` condition.
+    pub fn add_having_expr(
+        mut self,
+        having_expr: Option<&Expression>,
+        having_aliased_exprs: &[AliasedResultExpr],
+        aliased_exprs: &[AliasedResultExpr],
+    ) -> Self {
+        let Some(having_expr) = having_expr else {
+            return self;
+        };
+        self.composition.add(Box::new(HavingExpr::new(
+            having_expr,
+            having_aliased_exprs
+                .iter()
+                .map(|aliased_expr| aliased_expr.alias)
+                .collect(),
+        )));
+        let exprs: Vec<_> = aliased_exprs
+            .iter()
+            .map(|aliased_expr| AliasedResultExpr {
+                expr: Box::new(Expression::Column(aliased_expr.alias)),
+                alias: aliased_expr.alias,
+            })
+            .collect();
+        self.composition
+            .add(Box::new(SelectExpr::new_from_aliased_result_exprs(&exprs)));
+        self
+    }
+
     /// Chain a new `OrderByExprs` to the current `ResultExpr`.
     pub fn add_order_by_exprs(mut self, by_exprs: Vec<OrderBy>) -> Self {
         if !by_exprs.is_empty() {
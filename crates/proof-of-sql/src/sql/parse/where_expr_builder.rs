@@ -2,8 +2,12 @@ use super::ConversionError;
 use crate::{
     base::{
         commitment::Commitment,
-        database::{ColumnRef, ColumnType, LiteralValue},
+        database::{
+            order_key, order_key_column_id, prefix_order_key_range, validity_column_id, ColumnRef,
+            ColumnType, LiteralValue, MAX_ORDER_KEY_LITERAL_LENGTH, ORDER_KEY_LENGTH,
+        },
         math::decimal::{try_into_to_scalar, Precision},
+        scalar::Scalar,
     },
     sql::ast::{ColumnExpr, ProvableExpr, ProvableExprPlan},
 };
@@ -15,13 +19,30 @@ use std::collections::HashMap;
 
 /// Builder that enables building a `proof_of_sql::sql::ast::ProvableExprPlan` from a `proof_of_sql_parser::intermediate_ast::Expression` that is
 /// intended to be used as the where clause in a filter expression or group by expression.
+#[derive(Clone)]
 pub struct WhereExprBuilder<'a> {
     column_mapping: &'a HashMap<Identifier, ColumnRef>,
+    /// The conditions under which the value of the expression being built is used, each along with
+    /// whether it has to be true or false. Casts that may overflow are only checked where all of them are met.
+    guards: Vec<(Expression, bool)>,
 }
 impl<'a> WhereExprBuilder<'a> {
     /// Creates a new `WhereExprBuilder` with the given column mapping.
     pub fn new(column_mapping: &'a HashMap<Identifier, ColumnRef>) -> Self {
-        Self { column_mapping }
+        Self {
+            column_mapping,
+            guards: Vec::new(),
+        }
+    }
+
+    /// Only uses the result expressions that are built on the rows selected by the given where clause,
+    /// so that the casts in them are only checked to be in range on those rows.
+    ///
+    /// This must not be used to build the where clause itself.
+    pub fn with_selection(mut self, where_expr: Option<&Expression>) -> Self {
+        self.guards
+            .extend(where_expr.map(|where_expr| (where_expr.clone(), true)));
+        self
     }
     /// Builds a `proof_of_sql::sql::ast::ProvableExprPlan` from a `proof_of_sql_parser::intermediate_ast::Expression` that is
     /// intended to be used as the where clause in a filter expression or group by expression.
@@ -31,17 +52,39 @@ impl<'a> WhereExprBuilder<'a> {
     ) -> Result<Option<ProvableExprPlan<C>>, ConversionError> {
         where_expr
             .map(|where_expr| {
+                let validity = self.visit_validity(&where_expr)?;
                 let expr_plan = self.visit_expr(*where_expr)?;
                 // Ensure that the expression is a boolean expression
-                match expr_plan.data_type() {
-                    ColumnType::Boolean => Ok(expr_plan),
-                    _ => Err(ConversionError::NonbooleanWhereClause(
-                        expr_plan.data_type(),
-                    )),
+                match (expr_plan.data_type(), validity) {
+                    (ColumnType::Boolean, None) => Ok(expr_plan),
+                    // Rows where the where clause is null are not selected.
+                    (ColumnType::Boolean, Some(validity)) => {
+                        ProvableExprPlan::try_new_and(expr_plan, validity)
+                    }
+                    (data_type, _) => Err(ConversionError::NonbooleanWhereClause(data_type)),
                 }
             })
             .transpose()
     }
+
+    /// Builds a `proof_of_sql::sql::ast::ProvableExprPlan` from a `proof_of_sql_parser::intermediate_ast::Expression` that is
+    /// intended to be used as a result expression in a filter expression.
+    pub fn build_result_expr<C: Commitment>(
+        &self,
+        result_expr: Expression,
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        self.visit_expr(result_expr)
+    }
+
+    /// Builds a `proof_of_sql::sql::ast::ProvableExprPlan` that is true where the given result expression is not null.
+    ///
+    /// Returns `None` if the result expression is never null.
+    pub fn build_result_validity<C: Commitment>(
+        &self,
+        result_expr: &Expression,
+    ) -> Result<Option<ProvableExprPlan<C>>, ConversionError> {
+        self.visit_validity(result_expr)
+    }
 }
 
 // Private interface
@@ -55,10 +98,138 @@ impl WhereExprBuilder<'_> {
             Expression::Literal(lit) => self.visit_literal(lit),
             Expression::Binary { op, left, right } => self.visit_binary_expr(op, *left, *right),
             Expression::Unary { op, expr } => self.visit_unary_expr(op, *expr),
+            Expression::InList { expr, list } => self.visit_in_list_expr(*expr, list),
+            Expression::Between { expr, low, high } => self.visit_between_expr(*expr, *low, *high),
+            Expression::Like { expr, pattern } => self.visit_like_expr(*expr, pattern),
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => self.visit_case_expr(when_then, else_expr),
+            Expression::Cast { expr, data_type } => {
+                let expr = self.visit_expr(*expr)?;
+                let data_type = ColumnType::try_from(data_type)?;
+                match self.visit_guard()? {
+                    Some(guard) => ProvableExprPlan::try_new_guarded_cast(expr, data_type, guard),
+                    None => ProvableExprPlan::try_new_cast(expr, data_type),
+                }
+            }
             _ => panic!("The parser must ensure that the expression is a boolean expression"),
         }
     }
 
+    /// Returns an expression that is true where the given expression is not null, or `None` if it is never null.
+    ///
+    /// Nulls follow the three-valued logic of SQL: `NULL AND FALSE` is `FALSE` and `NULL OR TRUE` is `TRUE`.
+    /// Any other expression is null as soon as one of its operands is.
+    fn visit_validity<C: Commitment>(
+        &self,
+        expr: &Expression,
+    ) -> Result<Option<ProvableExprPlan<C>>, ConversionError> {
+        match expr {
+            Expression::Column(identifier) => Ok(validity_column_id(*identifier)
+                .ok()
+                .and_then(|validity_id| self.column_mapping.get(&validity_id))
+                .map(|column_ref| ProvableExprPlan::Column(ColumnExpr::new(*column_ref)))),
+            Expression::Unary {
+                op: UnaryOperator::Not,
+                expr,
+            } => self.visit_validity(expr),
+            Expression::Unary {
+                op: UnaryOperator::IsNull | UnaryOperator::IsNotNull,
+                ..
+            } => Ok(None),
+            Expression::Binary {
+                op: op @ (BinaryOperator::And | BinaryOperator::Or),
+                left,
+                right,
+            } => {
+                let left_validity = self.visit_validity(left)?;
+                let right_validity = self.visit_validity(right)?;
+                let is_left_nullable = left_validity.is_some();
+                let is_right_nullable = right_validity.is_some();
+                let mut validity = try_and_all(left_validity.into_iter().chain(right_validity))?;
+                // A valid operand that is false (resp. true) decides the result of an `AND` (resp. `OR`) on its own.
+                // This only needs to be accounted for when the other operand is nullable.
+                for (operand, is_other_nullable) in
+                    [(left, is_right_nullable), (right, is_left_nullable)]
+                {
+                    if is_other_nullable {
+                        let value = self.visit_expr(operand.as_ref().clone())?;
+                        let value = match op {
+                            BinaryOperator::And => ProvableExprPlan::try_new_not(value)?,
+                            _ => value,
+                        };
+                        let is_decisive =
+                            try_and_all(self.visit_validity(operand)?.into_iter().chain([value]))?
+                                .expect("there is at least one expression");
+                        validity = validity
+                            .map(|validity| ProvableExprPlan::try_new_or(validity, is_decisive))
+                            .transpose()?;
+                    }
+                }
+                Ok(validity)
+            }
+            // The literals of the list and the pattern are never null.
+            Expression::InList { expr, .. } | Expression::Like { expr, .. } => {
+                self.visit_validity(expr)
+            }
+            // A `CAST` is null exactly when its operand is.
+            Expression::Cast { expr, .. } => self.visit_validity(expr),
+            // `a BETWEEN x AND y` is null exactly when `a >= x AND a <= y` is.
+            Expression::Between { expr, low, high } => self.visit_validity(&Expression::Binary {
+                op: BinaryOperator::And,
+                left: Box::new(Expression::Binary {
+                    op: BinaryOperator::GreaterThanOrEqual,
+                    left: expr.clone(),
+                    right: low.clone(),
+                }),
+                right: Box::new(Expression::Binary {
+                    op: BinaryOperator::LessThanOrEqual,
+                    left: expr.clone(),
+                    right: high.clone(),
+                }),
+            }),
+            // A `CASE` is null exactly when the result it picks is, and a missing `ELSE` is `NULL`.
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => {
+                let mut is_nullable = else_expr.is_none();
+                let mut validity_when_then = Vec::with_capacity(when_then.len());
+                for (when, then) in when_then {
+                    let then_validity = self.visit_validity(then)?;
+                    is_nullable |= then_validity.is_some();
+                    validity_when_then.push((
+                        self.visit_case_condition(when)?,
+                        then_validity
+                            .unwrap_or(ProvableExprPlan::new_literal(LiteralValue::Boolean(true))),
+                    ));
+                }
+                let else_validity = match else_expr {
+                    Some(else_expr) => self.visit_validity(else_expr)?,
+                    None => Some(ProvableExprPlan::new_literal(LiteralValue::Boolean(false))),
+                };
+                is_nullable |= else_validity.is_some();
+                is_nullable
+                    .then(|| {
+                        ProvableExprPlan::try_new_case(
+                            validity_when_then,
+                            else_validity.unwrap_or(ProvableExprPlan::new_literal(
+                                LiteralValue::Boolean(true),
+                            )),
+                        )
+                    })
+                    .transpose()
+            }
+            Expression::Binary { left, right, .. } => try_and_all(
+                [self.visit_validity(left)?, self.visit_validity(right)?]
+                    .into_iter()
+                    .flatten(),
+            ),
+            _ => Ok(None),
+        }
+    }
+
     fn visit_column<C: Commitment>(
         &self,
         identifier: Identifier,
@@ -74,35 +245,257 @@ impl WhereExprBuilder<'_> {
         &self,
         lit: Literal,
     ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        Ok(ProvableExprPlan::new_literal(
+            self.visit_literal_value(lit)?,
+        ))
+    }
+
+    fn visit_literal_value<S: Scalar>(
+        &self,
+        lit: Literal,
+    ) -> Result<LiteralValue<S>, ConversionError> {
         match lit {
-            Literal::Boolean(b) => Ok(ProvableExprPlan::new_literal(LiteralValue::Boolean(b))),
-            Literal::BigInt(i) => Ok(ProvableExprPlan::new_literal(LiteralValue::BigInt(i))),
-            Literal::Int128(i) => Ok(ProvableExprPlan::new_literal(LiteralValue::Int128(i))),
+            Literal::Boolean(b) => Ok(LiteralValue::Boolean(b)),
+            Literal::BigInt(i) => Ok(LiteralValue::BigInt(i)),
+            Literal::Int128(i) => Ok(LiteralValue::Int128(i)),
             Literal::Decimal(d) => {
                 let scale = d.scale();
                 let precision = Precision::new(d.precision())
                     .map_err(|_| ConversionError::InvalidPrecision(d.precision()))?;
-                Ok(ProvableExprPlan::new_literal(LiteralValue::Decimal75(
+                Ok(LiteralValue::Decimal75(
                     precision,
                     scale,
                     try_into_to_scalar(&d, precision, scale)?,
-                )))
+                ))
             }
-            Literal::VarChar(s) => Ok(ProvableExprPlan::new_literal(LiteralValue::VarChar((
-                s.clone(),
-                s.into(),
-            )))),
+            Literal::VarChar(s) => Ok(LiteralValue::VarChar((s.clone(), s.into()))),
+            Literal::Timestamp(its) => Ok(LiteralValue::TimeStamp(
+                its.unit(),
+                its.timezone(),
+                its.timestamp(),
+            )),
+            Literal::Date(days) => Ok(LiteralValue::Date(days)),
         }
     }
 
+    fn visit_in_list_expr<C: Commitment>(
+        &self,
+        expr: Expression,
+        list: Vec<Literal>,
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        let list = list
+            .into_iter()
+            .map(|lit| self.visit_literal_value(lit))
+            .collect::<Result<_, _>>()?;
+        ProvableExprPlan::try_new_in_list(self.visit_expr(expr)?, list)
+    }
+
+    fn visit_between_expr<C: Commitment>(
+        &self,
+        expr: Expression,
+        low: Expression,
+        high: Expression,
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        let low = self.visit_comparison_operands(expr.clone(), low);
+        let high = self.visit_comparison_operands(expr, high);
+        let ((expr, low), (_, high)) = (low?, high?);
+        ProvableExprPlan::try_new_between(expr, low, high)
+    }
+
+    fn visit_case_expr<C: Commitment>(
+        &self,
+        when_then: Vec<(Box<Expression>, Box<Expression>)>,
+        else_expr: Option<Box<Expression>>,
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        // A result is only used where its condition is met and none of the conditions before it are.
+        let not_met = |when_then: &[(Box<Expression>, Box<Expression>)]| {
+            when_then
+                .iter()
+                .map(|(when, _)| ((**when).clone(), false))
+                .collect::<Vec<_>>()
+        };
+        // Without an `ELSE`, the rows where no condition is true are `NULL`, which is tracked by the validity
+        // of the expression, so their value does not matter and may be taken from any branch.
+        let else_plan = match else_expr {
+            Some(else_expr) => self
+                .with_guards(not_met(&when_then))
+                .visit_expr(*else_expr)?,
+            None => self
+                .with_guards([(Expression::Literal(Literal::Boolean(false)), true)])
+                .visit_expr(
+                    *when_then
+                        .last()
+                        .expect("the parser ensures that there is at least one branch")
+                        .1
+                        .clone(),
+                )?,
+        };
+        let when_then_plans = when_then
+            .iter()
+            .enumerate()
+            .map(|(i, (when, then))| {
+                let then_builder = self
+                    .with_guards(not_met(&when_then[..i]))
+                    .with_guards([((**when).clone(), true)]);
+                Ok((
+                    self.visit_case_condition(when)?,
+                    then_builder.visit_expr((**then).clone())?,
+                ))
+            })
+            .collect::<Result<_, ConversionError>>()?;
+        ProvableExprPlan::try_new_case(when_then_plans, else_plan)
+    }
+
+    /// Returns a builder for an expression whose value is only used where the given conditions
+    /// are met, on top of the ones of this builder.
+    fn with_guards(&self, guards: impl IntoIterator<Item = (Expression, bool)>) -> Self {
+        let mut builder = self.clone();
+        builder.guards.extend(guards);
+        builder
+    }
+
+    /// Builds the expression that is true where all the guards of this builder are met,
+    /// or returns `None` if there are none.
+    fn visit_guard<C: Commitment>(&self) -> Result<Option<ProvableExprPlan<C>>, ConversionError> {
+        // The casts in the guards are checked on every row.
+        let builder = WhereExprBuilder::new(self.column_mapping);
+        let guards = self
+            .guards
+            .iter()
+            .map(|(condition, is_met)| {
+                let condition = builder.visit_case_condition(condition)?;
+                if *is_met {
+                    Ok(condition)
+                } else {
+                    ProvableExprPlan::try_new_not(condition)
+                }
+            })
+            .collect::<Result<Vec<_>, ConversionError>>()?;
+        try_and_all(guards)
+    }
+
+    /// Builds a condition of a `CASE`, which is only met where it is true, so not where it is null.
+    fn visit_case_condition<C: Commitment>(
+        &self,
+        when: &Expression,
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        let condition = self.visit_expr(when.clone())?;
+        match self.visit_validity(when)? {
+            Some(validity) => ProvableExprPlan::try_new_and(condition, validity),
+            None => Ok(condition),
+        }
+    }
+
+    /// Builds a `LIKE` with an exact pattern (i.e. without wildcards) or a prefix pattern (i.e. `'abc%'`).
+    ///
+    /// An exact pattern is an equality. A string starts with a prefix exactly when its order key is in
+    /// the [`prefix_order_key_range`] of the prefix, which is proven as a range check of the order key column.
+    fn visit_like_expr<C: Commitment>(
+        &self,
+        expr: Expression,
+        pattern: String,
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        let expr_plan = self.visit_expr(expr.clone())?;
+        let prefix = pattern.trim_end_matches('%');
+        if prefix.contains(['%', '_', '\\']) {
+            return Err(ConversionError::Unprovable(format!(
+                "only exact and prefix LIKE patterns are supported, not '{pattern}'"
+            )));
+        }
+        if prefix.len() == pattern.len() {
+            return ProvableExprPlan::try_new_equals(
+                expr_plan,
+                ProvableExprPlan::new_literal(LiteralValue::VarChar((
+                    pattern.clone(),
+                    pattern.into(),
+                ))),
+            );
+        }
+        let Expression::Column(column) = expr else {
+            return Err(ConversionError::Unprovable(
+                "prefix LIKE patterns can only be matched by VarChar columns".to_string(),
+            ));
+        };
+        let (low, high) = prefix_order_key_range(prefix).ok_or_else(|| {
+            ConversionError::Unprovable(format!(
+                "the prefixes of LIKE patterns can be at most {ORDER_KEY_LENGTH} bytes"
+            ))
+        })?;
+        ProvableExprPlan::try_new_between(
+            self.visit_order_key_column(column)?,
+            ProvableExprPlan::new_literal(LiteralValue::Int128(low)),
+            ProvableExprPlan::new_literal(LiteralValue::Int128(high)),
+        )
+    }
+
+    /// Builds the operands of an inequality.
+    ///
+    /// `VarChar` values are only committed to as hashes, so a `VarChar` column is compared
+    /// with a string literal through their order keys instead.
+    fn visit_comparison_operands<C: Commitment>(
+        &self,
+        left: Expression,
+        right: Expression,
+    ) -> Result<(ProvableExprPlan<C>, ProvableExprPlan<C>), ConversionError> {
+        let left_expr = self.visit_expr(left.clone());
+        let right_expr = self.visit_expr(right.clone());
+        let (left_expr, right_expr) = (left_expr?, right_expr?);
+        if left_expr.data_type() != ColumnType::VarChar
+            || right_expr.data_type() != ColumnType::VarChar
+        {
+            return Ok((left_expr, right_expr));
+        }
+        match (left, right) {
+            (Expression::Column(column), Expression::Literal(Literal::VarChar(s))) => Ok((
+                self.visit_order_key_column(column)?,
+                visit_order_key_literal(s)?,
+            )),
+            (Expression::Literal(Literal::VarChar(s)), Expression::Column(column)) => Ok((
+                visit_order_key_literal(s)?,
+                self.visit_order_key_column(column)?,
+            )),
+            _ => Err(ConversionError::Unprovable(
+                "VarChar columns can only be compared with string literals".to_string(),
+            )),
+        }
+    }
+
+    fn visit_order_key_column<C: Commitment>(
+        &self,
+        identifier: Identifier,
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        order_key_column_id(identifier)
+            .ok()
+            .and_then(|order_key_id| self.column_mapping.get(&order_key_id))
+            .map(|column_ref| ProvableExprPlan::Column(ColumnExpr::new(*column_ref)))
+            .ok_or_else(|| {
+                ConversionError::Unprovable(format!(
+                    "the VarChar column {identifier} has no order key column"
+                ))
+            })
+    }
+
     fn visit_unary_expr<C: Commitment>(
         &self,
         op: UnaryOperator,
         expr: Expression,
     ) -> Result<ProvableExprPlan<C>, ConversionError> {
-        let expr = self.visit_expr(expr);
         match op {
-            UnaryOperator::Not => ProvableExprPlan::try_new_not(expr?),
+            UnaryOperator::Not => ProvableExprPlan::try_new_not(self.visit_expr(expr)?),
+            UnaryOperator::IsNull | UnaryOperator::IsNotNull => {
+                let validity = self.visit_validity(&expr)?;
+                // Only the validity of the expression matters, but it still has to be a valid expression.
+                self.visit_expr::<C>(expr)?;
+                match (op, validity) {
+                    (UnaryOperator::IsNull, Some(validity)) => {
+                        ProvableExprPlan::try_new_not(validity)
+                    }
+                    (_, Some(validity)) => Ok(validity),
+                    (_, None) => Ok(ProvableExprPlan::new_literal(LiteralValue::Boolean(
+                        op == UnaryOperator::IsNotNull,
+                    ))),
+                }
+            }
         }
     }
 
@@ -129,22 +522,59 @@ impl WhereExprBuilder<'_> {
                 ProvableExprPlan::try_new_equals(left?, right?)
             }
             BinaryOperator::GreaterThanOrEqual => {
+                let (left, right) = self.visit_comparison_operands(left, right)?;
+                ProvableExprPlan::try_new_inequality(left, right, false)
+            }
+            BinaryOperator::LessThanOrEqual => {
+                let (left, right) = self.visit_comparison_operands(left, right)?;
+                ProvableExprPlan::try_new_inequality(left, right, true)
+            }
+            BinaryOperator::Add => {
                 let left = self.visit_expr(left);
                 let right = self.visit_expr(right);
-                ProvableExprPlan::try_new_inequality(left?, right?, false)
+                ProvableExprPlan::try_new_add(left?, right?)
             }
-            BinaryOperator::LessThanOrEqual => {
+            BinaryOperator::Subtract => {
+                let left = self.visit_expr(left);
+                let right = self.visit_expr(right);
+                ProvableExprPlan::try_new_subtract(left?, right?)
+            }
+            BinaryOperator::Multiply => {
                 let left = self.visit_expr(left);
                 let right = self.visit_expr(right);
-                ProvableExprPlan::try_new_inequality(left?, right?, true)
+                ProvableExprPlan::try_new_multiply(left?, right?)
             }
-            BinaryOperator::Add
-            | BinaryOperator::Subtract
-            | BinaryOperator::Multiply
-            | BinaryOperator::Division => Err(ConversionError::Unprovable(format!(
+            BinaryOperator::Division => Err(ConversionError::Unprovable(format!(
                 "Binary operator {:?} is not supported in the where clause",
                 op
             ))),
         }
     }
 }
+
+/// Returns the order key of a string literal that a `VarChar` column is compared with.
+fn visit_order_key_literal<C: Commitment>(
+    s: String,
+) -> Result<ProvableExprPlan<C>, ConversionError> {
+    if s.len() > MAX_ORDER_KEY_LITERAL_LENGTH {
+        return Err(ConversionError::Unprovable(format!(
+            "VarChar columns can only be compared with strings of at most {MAX_ORDER_KEY_LITERAL_LENGTH} bytes"
+        )));
+    }
+    Ok(ProvableExprPlan::new_literal(LiteralValue::Int128(
+        order_key(&s),
+    )))
+}
+
+/// Returns the conjunction of the given boolean expressions, or `None` if there are none.
+fn try_and_all<C: Commitment>(
+    exprs: impl IntoIterator<Item = ProvableExprPlan<C>>,
+) -> Result<Option<ProvableExprPlan<C>>, ConversionError> {
+    let mut exprs = exprs.into_iter();
+    let Some(first) = exprs.next() else {
+        return Ok(None);
+    };
+    exprs
+        .try_fold(first, ProvableExprPlan::try_new_and)
+        .map(Some)
+}
@@ -7,7 +7,10 @@ mod tests {
         },
         record_batch,
         sql::{
-            ast::{ColumnExpr, LiteralExpr, ProvableExprPlan},
+            ast::{
+                test_utility::{and, const_bigint, const_bool, equal, not, or},
+                ColumnExpr, LiteralExpr, ProvableExprPlan,
+            },
             parse::{
                 query_expr_tests::record_batch_to_accessor, ConversionError, QueryExpr,
                 WhereExprBuilder,
@@ -16,7 +19,7 @@ mod tests {
     };
     use curve25519_dalek::RistrettoPoint;
     use proof_of_sql_parser::{
-        intermediate_ast::{BinaryOperator, Expression, Literal},
+        intermediate_ast::{BinaryOperator, Expression, Literal, UnaryOperator},
         intermediate_decimal::IntermediateDecimal,
         Identifier, SelectStatement,
     };
@@ -389,4 +392,243 @@ mod tests {
         )
         .is_ok());
     }
+
+    #[test]
+    fn we_can_directly_check_arithmetic_on_columns_and_literals() {
+        let column_mapping = get_column_mappings_for_testing();
+
+        // (bigint_column + decimal_column) * 2 = int128_column - 1.5
+        let expr = Expression::Binary {
+            op: BinaryOperator::Equal,
+            left: Box::new(Expression::Binary {
+                op: BinaryOperator::Multiply,
+                left: Box::new(Expression::Binary {
+                    op: BinaryOperator::Add,
+                    left: Box::new(Expression::Column(
+                        Identifier::try_new("bigint_column").unwrap(),
+                    )),
+                    right: Box::new(Expression::Column(
+                        Identifier::try_new("decimal_column").unwrap(),
+                    )),
+                }),
+                right: Box::new(Expression::Literal(Literal::BigInt(2))),
+            }),
+            right: Box::new(Expression::Binary {
+                op: BinaryOperator::Subtract,
+                left: Box::new(Expression::Column(
+                    Identifier::try_new("int128_column").unwrap(),
+                )),
+                right: Box::new(Expression::Literal(Literal::Decimal(
+                    IntermediateDecimal::try_from("1.5").unwrap(),
+                ))),
+            }),
+        };
+        run_test_case(&column_mapping, expr);
+
+        // bigint_column - 1.5 <= decimal_column
+        let expr = Expression::Binary {
+            op: BinaryOperator::LessThanOrEqual,
+            left: Box::new(Expression::Binary {
+                op: BinaryOperator::Subtract,
+                left: Box::new(Expression::Column(
+                    Identifier::try_new("bigint_column").unwrap(),
+                )),
+                right: Box::new(Expression::Literal(Literal::Decimal(
+                    IntermediateDecimal::try_from("1.5").unwrap(),
+                ))),
+            }),
+            right: Box::new(Expression::Column(
+                Identifier::try_new("decimal_column").unwrap(),
+            )),
+        };
+        run_test_case(&column_mapping, expr);
+    }
+
+    #[test]
+    fn we_can_not_have_arithmetic_on_non_numeric_columns() {
+        let column_mapping = get_column_mappings_for_testing();
+        let builder = WhereExprBuilder::new(&column_mapping);
+
+        let expr = Expression::Binary {
+            op: BinaryOperator::Equal,
+            left: Box::new(Expression::Binary {
+                op: BinaryOperator::Add,
+                left: Box::new(Expression::Column(
+                    Identifier::try_new("varchar_column").unwrap(),
+                )),
+                right: Box::new(Expression::Literal(Literal::BigInt(1))),
+            }),
+            right: Box::new(Expression::Literal(Literal::BigInt(2))),
+        };
+        let res = builder.build::<RistrettoPoint>(Some(Box::new(expr)));
+        assert!(matches!(
+            res,
+            Result::Err(ConversionError::DataTypeMismatch(_, _))
+        ));
+    }
+
+    #[test]
+    fn we_can_not_have_division_in_the_where_clause() {
+        let column_mapping = get_column_mappings_for_testing();
+        let builder = WhereExprBuilder::new(&column_mapping);
+
+        let expr = Expression::Binary {
+            op: BinaryOperator::Equal,
+            left: Box::new(Expression::Binary {
+                op: BinaryOperator::Division,
+                left: Box::new(Expression::Column(
+                    Identifier::try_new("bigint_column").unwrap(),
+                )),
+                right: Box::new(Expression::Literal(Literal::BigInt(2))),
+            }),
+            right: Box::new(Expression::Literal(Literal::BigInt(2))),
+        };
+        let res = builder.build::<RistrettoPoint>(Some(Box::new(expr)));
+        assert!(matches!(res, Result::Err(ConversionError::Unprovable(_))));
+    }
+
+    #[test]
+    fn we_can_not_compare_a_varchar_column_without_an_order_key_column() {
+        let column_mapping = get_column_mappings_for_testing();
+        let builder = WhereExprBuilder::new(&column_mapping);
+
+        let expr = Expression::Binary {
+            op: BinaryOperator::LessThanOrEqual,
+            left: Box::new(Expression::Column(
+                Identifier::try_new("varchar_column").unwrap(),
+            )),
+            right: Box::new(Expression::Literal(Literal::VarChar("abc".to_string()))),
+        };
+        let res = builder.build::<RistrettoPoint>(Some(Box::new(expr)));
+        assert!(matches!(res, Result::Err(ConversionError::Unprovable(_))));
+    }
+
+    #[test]
+    fn we_can_only_match_exact_like_patterns_without_an_order_key_column() {
+        let column_mapping = get_column_mappings_for_testing();
+        let like = |pattern: &str| Expression::Like {
+            expr: Box::new(Expression::Column(
+                Identifier::try_new("varchar_column").unwrap(),
+            )),
+            pattern: pattern.to_string(),
+        };
+
+        let builder = WhereExprBuilder::new(&column_mapping);
+        let res = builder.build::<RistrettoPoint>(Some(Box::new(like("abc"))));
+        assert!(res.is_ok());
+        let builder = WhereExprBuilder::new(&column_mapping);
+        let res = builder.build::<RistrettoPoint>(Some(Box::new(like("abc%"))));
+        assert!(matches!(res, Result::Err(ConversionError::Unprovable(_))));
+    }
+
+    fn get_column_mappings_with_a_nullable_column_for_testing() -> HashMap<Identifier, ColumnRef> {
+        let mut column_mapping = get_column_mappings_for_testing();
+        for (name, column_type) in [
+            ("nullable_column", ColumnType::BigInt),
+            ("__valid__nullable_column", ColumnType::Boolean),
+        ] {
+            column_mapping.insert(
+                Identifier::try_new(name).unwrap(),
+                ColumnRef::new(
+                    "sxt.sxt_tab".parse().unwrap(),
+                    Identifier::try_new(name).unwrap(),
+                    column_type,
+                ),
+            );
+        }
+        column_mapping
+    }
+
+    fn column_plan(
+        column_mapping: &HashMap<Identifier, ColumnRef>,
+        name: &str,
+    ) -> ProvableExprPlan<RistrettoPoint> {
+        ProvableExprPlan::Column(ColumnExpr::new(
+            column_mapping[&Identifier::try_new(name).unwrap()],
+        ))
+    }
+
+    fn column(name: &str) -> Box<Expression> {
+        Box::new(Expression::Column(Identifier::try_new(name).unwrap()))
+    }
+
+    #[test]
+    fn we_only_select_rows_where_the_where_clause_is_not_null() {
+        let column_mapping = get_column_mappings_with_a_nullable_column_for_testing();
+        let builder = WhereExprBuilder::new(&column_mapping);
+        let expr = Expression::Binary {
+            op: BinaryOperator::Equal,
+            left: column("nullable_column"),
+            right: Box::new(Expression::Literal(Literal::BigInt(1))),
+        };
+        let expected = and(
+            equal(
+                column_plan(&column_mapping, "nullable_column"),
+                const_bigint(1),
+            ),
+            column_plan(&column_mapping, "__valid__nullable_column"),
+        );
+        assert_eq!(builder.build(Some(Box::new(expr))).unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn we_can_check_whether_a_column_is_null() {
+        let column_mapping = get_column_mappings_with_a_nullable_column_for_testing();
+        let build = |expr| {
+            WhereExprBuilder::new(&column_mapping)
+                .build::<RistrettoPoint>(Some(Box::new(expr)))
+                .unwrap()
+        };
+        let is_null = |name| Expression::Unary {
+            op: UnaryOperator::IsNull,
+            expr: column(name),
+        };
+        let is_not_null = |name| Expression::Unary {
+            op: UnaryOperator::IsNotNull,
+            expr: column(name),
+        };
+        assert_eq!(
+            build(is_null("nullable_column")),
+            Some(not(column_plan(
+                &column_mapping,
+                "__valid__nullable_column"
+            )))
+        );
+        assert_eq!(
+            build(is_not_null("nullable_column")),
+            Some(column_plan(&column_mapping, "__valid__nullable_column"))
+        );
+        // Columns without a validity column are never null
+        assert_eq!(build(is_null("bigint_column")), Some(const_bool(false)));
+        assert_eq!(build(is_not_null("bigint_column")), Some(const_bool(true)));
+    }
+
+    #[test]
+    fn a_true_operand_of_an_or_is_enough_to_select_a_row_with_nulls() {
+        let column_mapping = get_column_mappings_with_a_nullable_column_for_testing();
+        let builder = WhereExprBuilder::new(&column_mapping);
+        let expr = Expression::Binary {
+            op: BinaryOperator::Or,
+            left: Box::new(Expression::Binary {
+                op: BinaryOperator::Equal,
+                left: column("nullable_column"),
+                right: Box::new(Expression::Literal(Literal::BigInt(1))),
+            }),
+            right: column("boolean_column"),
+        };
+        let expected = and(
+            or(
+                equal(
+                    column_plan(&column_mapping, "nullable_column"),
+                    const_bigint(1),
+                ),
+                column_plan(&column_mapping, "boolean_column"),
+            ),
+            or(
+                column_plan(&column_mapping, "__valid__nullable_column"),
+                column_plan(&column_mapping, "boolean_column"),
+            ),
+        );
+        assert_eq!(builder.build(Some(Box::new(expr))).unwrap(), Some(expected));
+    }
 }
@@ -50,6 +50,14 @@ impl<'a> CountBuilder<'a> {
         self.counts.intermediate_mles += cnt;
     }
 
+    pub fn count_first_round_mles(&mut self, cnt: usize) {
+        self.counts.first_round_mles += cnt;
+    }
+
+    pub fn count_public_columns(&mut self, cnt: usize) {
+        self.counts.public_columns += cnt;
+    }
+
     pub fn count_degree(&mut self, degree: usize) {
         self.counts.sumcheck_max_multiplicands =
             max(self.counts.sumcheck_max_multiplicands, degree);
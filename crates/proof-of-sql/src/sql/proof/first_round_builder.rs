@@ -0,0 +1,66 @@
+use crate::base::{
+    commitment::{CommittableColumn, VecCommitmentExt},
+    scalar::Scalar,
+};
+
+/// Track the components of a query's proof that are sent along with the result,
+/// before the verifier draws any post-result challenges.
+///
+/// This is needed when a constraint relies on a challenge that the prover must not know
+/// when it picks some of its witness columns (e.g. a lookup into a public table).
+pub struct FirstRoundBuilder<'a, S: Scalar> {
+    commitment_descriptor: Vec<CommittableColumn<'a>>,
+    /// Columns that are sent to the verifier in the clear.
+    public_columns: Vec<Vec<S>>,
+}
+
+impl<'a, S: Scalar> Default for FirstRoundBuilder<'a, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, S: Scalar> FirstRoundBuilder<'a, S> {
+    pub fn new() -> Self {
+        Self {
+            commitment_descriptor: Vec::new(),
+            public_columns: Vec::new(),
+        }
+    }
+
+    /// Produce an MLE that is committed to before the post-result challenges are drawn.
+    ///
+    /// Note: the same data must be passed to `ProofBuilder::produce_first_round_mle`
+    /// so that it can be referenced in sumcheck.
+    pub fn produce_first_round_mle(&mut self, data: impl Into<CommittableColumn<'a>>) {
+        self.commitment_descriptor.push(data.into());
+    }
+
+    /// Produce a column that is sent to the verifier in the clear.
+    pub fn produce_public_column(&mut self, column: Vec<S>) {
+        self.public_columns.push(column);
+    }
+
+    /// Compute commitments of all the first round MLEs
+    #[tracing::instrument(
+        name = "FirstRoundBuilder::commit_first_round_mles",
+        level = "debug",
+        skip_all
+    )]
+    pub fn commit_first_round_mles<V: VecCommitmentExt>(
+        &self,
+        offset_generators: usize,
+        setup: &V::CommitmentPublicSetup,
+    ) -> V {
+        V::from_commitable_columns_with_offset(
+            &self.commitment_descriptor,
+            offset_generators,
+            setup,
+        )
+    }
+
+    /// Consume the builder, returning the public columns
+    pub fn into_public_columns(self) -> Vec<Vec<S>> {
+        self.public_columns
+    }
+}
@@ -85,3 +85,6 @@ mod indexes_test;
 
 mod result_builder;
 pub(crate) use result_builder::ResultBuilder;
+
+mod first_round_builder;
+pub(crate) use first_round_builder::FirstRoundBuilder;
@@ -82,6 +82,13 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
         self.produce_anchored_mle(data);
     }
 
+    /// Produce an MLE that was committed to in the first round so that we can reference it in sumcheck.
+    ///
+    /// The commitment is computed by the `FirstRoundBuilder`, before the post-result challenges are drawn.
+    pub fn produce_first_round_mle(&mut self, data: impl MultilinearExtension<S> + 'a) {
+        self.produce_anchored_mle(data);
+    }
+
     /// Produce a subpolynomial to be aggegated into sumcheck where the sum across binary
     /// values of the variables is zero.
     pub fn produce_sumcheck_subpolynomial(
@@ -8,6 +8,8 @@ pub struct ProofCounts {
     pub result_columns: usize,
     pub anchored_mles: usize,
     pub intermediate_mles: usize,
+    pub first_round_mles: usize,
+    pub public_columns: usize,
     pub sumcheck_subpolynomials: usize,
 
     /// The number of challenges used in the proof.
@@ -27,6 +29,8 @@ impl ProofCounts {
         tracing::info!("result_columns = {:?}", self.result_columns);
         tracing::info!("anchored_mles = {:?}", self.anchored_mles);
         tracing::info!("intermediate_mles = {:?}", self.intermediate_mles);
+        tracing::info!("first_round_mles = {:?}", self.first_round_mles);
+        tracing::info!("public_columns = {:?}", self.public_columns);
         tracing::info!(
             "sumcheck_subpolynomials = {:?}",
             self.sumcheck_subpolynomials
@@ -1,4 +1,4 @@
-use super::{CountBuilder, ProofBuilder, ResultBuilder, VerificationBuilder};
+use super::{CountBuilder, FirstRoundBuilder, ProofBuilder, ResultBuilder, VerificationBuilder};
 use crate::base::{
     commitment::Commitment,
     database::{ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor},
@@ -23,6 +23,15 @@ pub trait ProofExpr<C: Commitment>: Debug + Send + Sync + ProverEvaluate<C::Scal
     /// The offset of the query, that is, how many rows to skip before starting to read the input table
     fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize;
 
+    /// The length of the domain the query is proven over.
+    ///
+    /// This is the length of the input table, unless the result of the query
+    /// can have more rows than its input (e.g. a join), in which case the domain
+    /// needs to be extended to index every row of the result.
+    fn get_proof_length(&self, accessor: &dyn MetadataAccessor, _result_length: usize) -> usize {
+        self.get_length(accessor)
+    }
+
     /// Check if the input table is empty
     fn is_empty(&self, accessor: &dyn MetadataAccessor) -> bool {
         self.get_length(accessor) == 0
@@ -51,6 +60,18 @@ pub trait ProverEvaluate<S: Scalar> {
         accessor: &'a dyn DataAccessor<S>,
     );
 
+    /// Evaluate the query and modify `FirstRoundBuilder` to track the components of the proof
+    /// that are sent along with the result, before any post-result challenges are drawn.
+    ///
+    /// Most queries don't need such components, so by default this does nothing.
+    fn first_round_evaluate<'a>(
+        &self,
+        _builder: &mut FirstRoundBuilder<'a, S>,
+        _alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) {
+    }
+
     /// Evaluate the query and modify `ProofBuilder` to store an intermediate representation
     /// of the query result and track all the components needed to form the query's proof.
     ///
@@ -115,6 +115,10 @@ impl ProvableQueryResult {
 
                     ColumnType::Scalar => decode_and_convert::<S, S>(&self.data[offset..]),
                     ColumnType::VarChar => decode_and_convert::<&str, S>(&self.data[offset..]),
+                    ColumnType::TimeStamp(_, _) => {
+                        decode_and_convert::<i64, S>(&self.data[offset..])
+                    }
+                    ColumnType::Date => decode_and_convert::<i32, S>(&self.data[offset..]),
                 }?;
 
                 val += evaluation_vec[index as usize] * x;
@@ -194,6 +198,18 @@ impl ProvableQueryResult {
                         offset += num_read;
                         Ok((field.name(), OwnedColumn::Decimal75(precision, scale, col)))
                     }
+                    ColumnType::TimeStamp(unit, timezone) => {
+                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)
+                            .ok_or(QueryError::Overflow)?;
+                        offset += num_read;
+                        Ok((field.name(), OwnedColumn::TimeStamp(unit, timezone, col)))
+                    }
+                    ColumnType::Date => {
+                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)
+                            .ok_or(QueryError::Overflow)?;
+                        offset += num_read;
+                        Ok((field.name(), OwnedColumn::Date(col)))
+                    }
                 })
                 .collect::<Result<_, QueryError>>()?,
         )?;
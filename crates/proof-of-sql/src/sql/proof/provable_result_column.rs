@@ -42,6 +42,8 @@ impl<S: Scalar> ProvableResultColumn for Column<'_, S> {
             Column::Decimal75(_, _, col) => col.num_bytes(selection),
             Column::Scalar(col) => col.num_bytes(selection),
             Column::VarChar((col, _)) => col.num_bytes(selection),
+            Column::TimeStamp(_, _, col) => col.num_bytes(selection),
+            Column::Date(col) => col.num_bytes(selection),
         }
     }
 
@@ -55,6 +57,8 @@ impl<S: Scalar> ProvableResultColumn for Column<'_, S> {
             Column::Decimal75(_, _, col) => col.write(out, selection),
             Column::Scalar(col) => col.write(out, selection),
             Column::VarChar((col, _)) => col.write(out, selection),
+            Column::TimeStamp(_, _, col) => col.write(out, selection),
+            Column::Date(col) => col.write(out, selection),
         }
     }
 }
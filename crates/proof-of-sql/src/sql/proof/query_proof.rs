@@ -1,6 +1,6 @@
 use super::{
-    CountBuilder, ProofBuilder, ProofCounts, ProofExpr, ProvableQueryResult, QueryResult,
-    SumcheckMleEvaluations, SumcheckRandomScalars, VerificationBuilder,
+    CountBuilder, FirstRoundBuilder, ProofBuilder, ProofCounts, ProofExpr, ProvableQueryResult,
+    QueryResult, SumcheckMleEvaluations, SumcheckRandomScalars, VerificationBuilder,
 };
 use crate::{
     base::{
@@ -29,6 +29,10 @@ use std::cmp;
 pub struct QueryProof<CP: CommitmentEvaluationProof> {
     /// Bit distributions
     pub bit_distributions: Vec<BitDistribution>,
+    /// Commitments to the MLEs that are fixed before the post-result challenges are drawn
+    pub first_round_commitments: Vec<CP::Commitment>,
+    /// Columns sent to the verifier in the clear
+    pub public_columns: Vec<Vec<CP::Scalar>>,
     /// Commitments
     pub commitments: Vec<CP::Commitment>,
     /// Sumcheck Proof
@@ -48,18 +52,33 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         setup: &CP::ProverPublicSetup,
     ) -> (Self, ProvableQueryResult) {
         let table_length = expr.get_length(accessor);
-        let num_sumcheck_variables = cmp::max(log2_up(table_length), 1);
         let generator_offset = expr.get_offset(accessor);
-        assert!(num_sumcheck_variables > 0);
 
         let alloc = Bump::new();
         let mut result_builder = ResultBuilder::new(table_length);
         expr.result_evaluate(&mut result_builder, &alloc, accessor);
         let provable_result = result_builder.make_provable_query_result();
 
+        // the proof must be large enough to index every row of the result
+        let table_length = expr.get_proof_length(accessor, provable_result.indexes().len());
+        let num_sumcheck_variables = cmp::max(log2_up(table_length), 1);
+        assert!(num_sumcheck_variables > 0);
+
+        let mut first_round_builder = FirstRoundBuilder::new();
+        expr.first_round_evaluate(&mut first_round_builder, &alloc, accessor);
+        let first_round_commitments: Vec<CP::Commitment> =
+            first_round_builder.commit_first_round_mles(generator_offset, setup);
+        let public_columns = first_round_builder.into_public_columns();
+
         // construct a transcript for the proof
-        let mut transcript: Transcript =
-            make_transcript(expr, &provable_result, table_length, generator_offset);
+        let mut transcript: Transcript = make_transcript(
+            expr,
+            &provable_result,
+            table_length,
+            generator_offset,
+            &first_round_commitments,
+            &public_columns,
+        );
 
         // These are the challenges that will be consumed by the proof
         // Specifically, these are the challenges that the verifier sends to
@@ -77,13 +96,22 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             ProofBuilder::new(table_length, num_sumcheck_variables, post_result_challenges);
         expr.prover_evaluate(&mut builder, &alloc, accessor);
 
-        let proof = QueryProof::new_from_builder(builder, generator_offset, transcript, setup);
+        let proof = QueryProof::new_from_builder(
+            builder,
+            generator_offset,
+            first_round_commitments,
+            public_columns,
+            transcript,
+            setup,
+        );
         (proof, provable_result)
     }
 
     pub(crate) fn new_from_builder(
         builder: ProofBuilder<CP::Scalar>,
         generator_offset: usize,
+        first_round_commitments: Vec<CP::Commitment>,
+        public_columns: Vec<Vec<CP::Scalar>>,
         mut transcript: Transcript,
         setup: &CP::ProverPublicSetup,
     ) -> Self {
@@ -141,6 +169,8 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         let proof = Self {
             bit_distributions: builder.bit_distributions().to_vec(),
+            first_round_commitments,
+            public_columns,
             commitments,
             sumcheck_proof,
             pre_result_mle_evaluations,
@@ -158,7 +188,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         result: &ProvableQueryResult,
         setup: &CP::VerifierPublicSetup,
     ) -> QueryResult<CP::Scalar> {
-        let table_length = expr.get_length(accessor);
+        let table_length = expr.get_proof_length(accessor, result.indexes().len());
         let generator_offset = expr.get_offset(accessor);
         let num_sumcheck_variables = cmp::max(log2_up(table_length), 1);
         assert!(num_sumcheck_variables > 0);
@@ -188,9 +218,22 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
                 .ok_or(ProofError::VerificationError(
                     "commitment failed to decompress",
                 ))?;
+        let first_round_commitments =
+            self.first_round_commitments
+                .to_decompressed()
+                .ok_or(ProofError::VerificationError(
+                    "commitment failed to decompress",
+                ))?;
 
         // construct a transcript for the proof
-        let mut transcript = make_transcript(expr, result, table_length, generator_offset);
+        let mut transcript = make_transcript(
+            expr,
+            result,
+            table_length,
+            generator_offset,
+            &self.first_round_commitments,
+            &self.public_columns,
+        );
 
         // These are the challenges that will be consumed by the proof
         // Specifically, these are the challenges that the verifier sends to
@@ -253,6 +296,9 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             ))?,
         };
 
+        // decode the result so that it can be checked in the clear
+        let result_table = result.to_owned_table(&column_result_fields[..]);
+
         // pass over the provable AST to fill in the verification builder
         let sumcheck_evaluations = SumcheckMleEvaluations::new(
             table_length,
@@ -270,6 +316,9 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             sumcheck_random_scalars.subpolynomial_multipliers,
             &evaluation_random_scalars,
             post_result_challenges,
+            &first_round_commitments,
+            &self.public_columns,
+            result_table.as_ref().ok(),
         );
         expr.verifier_evaluate(&mut builder, accessor)?;
 
@@ -302,19 +351,19 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             MessageLabel::VerificationHash.as_bytes(),
             &mut verification_hash,
         );
-        result
-            .to_owned_table(&column_result_fields[..])
-            .map(|table| QueryData {
-                table,
-                verification_hash,
-            })
+        result_table.map(|table| QueryData {
+            table,
+            verification_hash,
+        })
     }
 
     fn validate_sizes(&self, counts: &ProofCounts, result: &ProvableQueryResult) -> bool {
         result.num_columns() == counts.result_columns
             && self.commitments.num_commitments() == counts.intermediate_mles
+            && self.first_round_commitments.num_commitments() == counts.first_round_mles
+            && self.public_columns.len() == counts.public_columns
             && self.pre_result_mle_evaluations.len()
-                == counts.intermediate_mles + counts.anchored_mles
+                == counts.intermediate_mles + counts.anchored_mles + counts.first_round_mles
     }
 }
 
@@ -337,21 +386,29 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 ///
 /// * `generator_offset` - The offset of the generator used in the proof, as a `usize`.
 ///
+/// * `first_round_commitments` - The commitments the prover sends along with the result.
+///
+/// * `public_columns` - The columns the prover sends in the clear along with the result.
+///
 /// # Returns
 /// This function returns a `merlin::Transcript`. The transcript is a record
 /// of all the operations and data involved in creating a proof.
 /// ```
-pub fn make_transcript<C: Commitment>(
+pub fn make_transcript<C: Commitment + Serialize>(
     expr: &(impl ProofExpr<C> + Serialize),
     result: &ProvableQueryResult,
     table_length: usize,
     generator_offset: usize,
+    first_round_commitments: &[C],
+    public_columns: &[Vec<C::Scalar>],
 ) -> merlin::Transcript {
     let mut transcript = Transcript::new(MessageLabel::QueryProof.as_bytes());
     transcript.append_auto(MessageLabel::QueryResultData, result);
     transcript.append_auto(MessageLabel::ProofExpr, expr);
     transcript.append_auto(MessageLabel::TableLength, &table_length);
     transcript.append_auto(MessageLabel::GeneratorOffset, &generator_offset);
+    transcript.append_auto(MessageLabel::QueryFirstRoundCommit, first_round_commitments);
+    transcript.append_canonical_serialize(MessageLabel::QueryPublicColumns, public_columns);
     transcript
 }
 
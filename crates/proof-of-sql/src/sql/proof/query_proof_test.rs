@@ -538,6 +538,8 @@ fn verify_a_proof_with_an_intermediate_commitment_and_given_offset(offset_genera
         sumcheck_subpolynomials: 2,
         anchored_mles: 1,
         intermediate_mles: 1,
+        first_round_mles: 0,
+        public_columns: 0,
         post_result_challenges: 0,
     };
     fn result_eval<'a>(
@@ -661,6 +663,8 @@ fn verify_fails_if_an_intermediate_commitment_doesnt_match() {
         sumcheck_subpolynomials: 2,
         anchored_mles: 1,
         intermediate_mles: 1,
+        first_round_mles: 0,
+        public_columns: 0,
         post_result_challenges: 0,
     };
     fn result_eval<'a>(
@@ -746,6 +750,8 @@ fn verify_fails_if_an_intermediate_equation_isnt_satified() {
         sumcheck_subpolynomials: 2,
         anchored_mles: 1,
         intermediate_mles: 1,
+        first_round_mles: 0,
+        public_columns: 0,
         post_result_challenges: 0,
     };
     fn result_eval<'a>(
@@ -830,6 +836,8 @@ fn verify_fails_the_result_doesnt_satisfy_an_intermediate_equation() {
         sumcheck_subpolynomials: 2,
         anchored_mles: 1,
         intermediate_mles: 1,
+        first_round_mles: 0,
+        public_columns: 0,
         post_result_challenges: 0,
     };
     fn result_eval<'a>(
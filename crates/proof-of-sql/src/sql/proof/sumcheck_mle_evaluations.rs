@@ -13,6 +13,10 @@ pub struct SumcheckMleEvaluations<'a, S: Scalar> {
     pub table_length: usize,
     /// The number of sumcheck variables.
     pub num_sumcheck_variables: usize,
+    /// The random point generated by sumcheck at which the MLEs are evaluated.
+    ///
+    /// This is needed to evaluate MLEs that the verifier can compute itself, e.g. the indicator of a range of rows.
+    pub evaluation_point: &'a [S],
     /// The evaluation (at the random point generated by sumcheck) of an MLE {x_i} where
     ///     x_i = 1, if i < table_length;
     ///         = 0, otherwise
@@ -44,7 +48,7 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
     /// - `result_indexes` - the indexes of the entries in the result columns. This can be sparse or dense
     pub fn new(
         table_length: usize,
-        evaluation_point: &[S],
+        evaluation_point: &'a [S],
         sumcheck_random_scalars: &SumcheckRandomScalars<S>,
         pre_result_evaluations: &'a [S],
         result_evaluations: &'a [S],
@@ -67,6 +71,7 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
         Self {
             table_length,
             num_sumcheck_variables: evaluation_point.len(),
+            evaluation_point,
             one_evaluation,
             random_evaluation,
             pre_result_evaluations,
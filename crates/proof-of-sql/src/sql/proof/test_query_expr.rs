@@ -60,6 +60,8 @@ impl ProofExpr<RistrettoPoint> for TestQueryExpr {
         builder.count_result_columns(self.counts.result_columns);
         builder.count_anchored_mles(self.counts.anchored_mles);
         builder.count_intermediate_mles(self.counts.intermediate_mles);
+        builder.count_first_round_mles(self.counts.first_round_mles);
+        builder.count_public_columns(self.counts.public_columns);
         builder.count_subpolynomials(self.counts.sumcheck_subpolynomials);
         builder.count_post_result_challenges(self.counts.post_result_challenges);
         Ok(())
@@ -163,6 +163,10 @@ fn make_empty_query_result<S: Scalar>(result_fields: Vec<ColumnField>) -> QueryR
                         }
                         ColumnType::Scalar => OwnedColumn::Scalar(vec![]),
                         ColumnType::VarChar => OwnedColumn::VarChar(vec![]),
+                        ColumnType::TimeStamp(unit, timezone) => {
+                            OwnedColumn::TimeStamp(unit, timezone, vec![])
+                        }
+                        ColumnType::Date => OwnedColumn::Date(vec![]),
                     },
                 )
             })
@@ -52,6 +52,25 @@ pub fn exercise_verification(
         assert!(res_p.verify(expr, accessor, &()).is_err());
     }
 
+    // try changing first round commitments
+    for i in 0..proof.first_round_commitments.len() {
+        let mut res_p = res.clone();
+        res_p.proof.as_mut().unwrap().first_round_commitments[i] = commit_p;
+        assert!(res_p.verify(expr, accessor, &()).is_err());
+    }
+
+    // try changing public columns
+    for i in 0..proof.public_columns.len() {
+        let mut res_p = res.clone();
+        res_p.proof.as_mut().unwrap().public_columns[i].push(Curve25519Scalar::one());
+        assert!(res_p.verify(expr, accessor, &()).is_err());
+        if !proof.public_columns[i].is_empty() {
+            let mut res_p = res.clone();
+            res_p.proof.as_mut().unwrap().public_columns[i][0] += Curve25519Scalar::one();
+            assert!(res_p.verify(expr, accessor, &()).is_err());
+        }
+    }
+
     // try changing the offset
     //
     // Note: in the n = 1 case with proof.commmitments all the identity element,
@@ -1,5 +1,5 @@
 use super::SumcheckMleEvaluations;
-use crate::base::{bit::BitDistribution, commitment::Commitment};
+use crate::base::{bit::BitDistribution, commitment::Commitment, database::OwnedTable};
 use num_traits::Zero;
 
 /// Track components used to verify a query's proof
@@ -25,9 +25,18 @@ pub struct VerificationBuilder<'a, C: Commitment> {
     /// Note: this vector is treated as a stack and the first
     /// challenge is the last entry in the vector.
     post_result_challenges: Vec<C::Scalar>,
+    first_round_commitments: &'a [C],
+    consumed_first_round_mles: usize,
+    public_columns: &'a [Vec<C::Scalar>],
+    consumed_public_columns: usize,
+    /// The decoded result of the query, if it could be decoded.
+    ///
+    /// This allows properties of the result to be checked in the clear.
+    result_table: Option<&'a OwnedTable<C::Scalar>>,
 }
 
 impl<'a, C: Commitment> VerificationBuilder<'a, C> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         generator_offset: usize,
         mle_evaluations: SumcheckMleEvaluations<'a, C::Scalar>,
@@ -36,6 +45,9 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
         subpolynomial_multipliers: &'a [C::Scalar],
         inner_product_multipliers: &'a [C::Scalar],
         post_result_challenges: Vec<C::Scalar>,
+        first_round_commitments: &'a [C],
+        public_columns: &'a [Vec<C::Scalar>],
+        result_table: Option<&'a OwnedTable<C::Scalar>>,
     ) -> Self {
         assert_eq!(
             inner_product_multipliers.len(),
@@ -56,6 +68,11 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
             consumed_intermediate_mles: 0,
             produced_subpolynomials: 0,
             post_result_challenges,
+            first_round_commitments,
+            consumed_first_round_mles: 0,
+            public_columns,
+            consumed_public_columns: 0,
+            result_table,
         }
     }
 
@@ -97,6 +114,27 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
         self.consume_anchored_mle(commitment)
     }
 
+    /// Consume the evaluation of a first round MLE used in sumcheck
+    ///
+    /// A first round MLE is one that the prover commits to before the post-result challenges are drawn
+    pub fn consume_first_round_mle(&mut self) -> C::Scalar {
+        let commitment = self.first_round_commitments[self.consumed_first_round_mles];
+        self.consumed_first_round_mles += 1;
+        self.consume_anchored_mle(commitment)
+    }
+
+    /// Consume a column that the prover sent in the clear
+    pub fn consume_public_column(&mut self) -> &'a [C::Scalar] {
+        let res = &self.public_columns[self.consumed_public_columns];
+        self.consumed_public_columns += 1;
+        res
+    }
+
+    /// Get the decoded result of the query, if it could be decoded
+    pub fn result_table(&self) -> Option<&'a OwnedTable<C::Scalar>> {
+        self.result_table
+    }
+
     /// Consume the evaluation of the MLE for a result column used in sumcheck
     pub fn consume_result_mle(&mut self) -> C::Scalar {
         let index = self.consumed_result_mles;
@@ -141,6 +179,8 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
         self.bit_distributions.is_empty()
             && self.produced_subpolynomials == self.subpolynomial_multipliers.len()
             && self.consumed_intermediate_mles == self.intermediate_commitments.len()
+            && self.consumed_first_round_mles == self.first_round_commitments.len()
+            && self.consumed_public_columns == self.public_columns.len()
             && self.consumed_pre_result_mles == self.mle_evaluations.pre_result_evaluations.len()
             && self.consumed_result_mles == self.mle_evaluations.result_evaluations.len()
             && self.post_result_challenges.is_empty()
@@ -19,6 +19,9 @@ fn an_empty_sumcheck_polynomial_evaluates_to_zero() {
         &[][..],
         &[][..],
         Vec::new(),
+        &[][..],
+        &[],
+        None,
     );
     assert_eq!(builder.sumcheck_evaluation(), Curve25519Scalar::zero());
     assert_eq!(builder.pre_result_commitments(), &[]);
@@ -44,6 +47,9 @@ fn we_build_up_a_sumcheck_polynomial_evaluation_from_subpolynomial_evaluations()
         &subpolynomial_multipliers,
         &[][..],
         Vec::new(),
+        &[][..],
+        &[],
+        None,
     );
     builder.produce_sumcheck_subpolynomial_evaluation(&Curve25519Scalar::from(2u64));
     builder.produce_sumcheck_subpolynomial_evaluation(&Curve25519Scalar::from(3u64));
@@ -80,6 +86,9 @@ fn we_build_up_the_folded_pre_result_commitment() {
         &[][..],
         &inner_product_multipliers,
         Vec::new(),
+        &[][..],
+        &[],
+        None,
     );
     let eval = builder.consume_anchored_mle(commit1);
     assert_eq!(eval, Curve25519Scalar::from(123u64));
@@ -119,6 +128,9 @@ fn we_can_consume_result_evaluations() {
         &[][..],
         &[][..],
         Vec::new(),
+        &[][..],
+        &[],
+        None,
     );
     assert_eq!(builder.consume_result_mle(), Curve25519Scalar::from(123u64));
     assert_eq!(builder.consume_result_mle(), Curve25519Scalar::from(456u64));
@@ -138,6 +150,9 @@ fn we_can_consume_post_result_challenges_in_proof_builder() {
             Curve25519Scalar::from(456),
             Curve25519Scalar::from(789),
         ],
+        &[][..],
+        &[],
+        None,
     );
     assert_eq!(
         Curve25519Scalar::from(789),
@@ -0,0 +1,127 @@
+use super::record_batch_expr::RecordBatchExpr;
+use crate::base::database::ColumnType;
+use arrow::{
+    array::{Array, ArrayRef, Decimal256Array, Int64Array},
+    compute::cast,
+    datatypes::{i256, DataType, DECIMAL256_MAX_PRECISION},
+    record_batch::RecordBatch,
+};
+use dyn_partial_eq::DynPartialEq;
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Signed};
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A node that replaces columns of sums by the averages of their values.
+///
+/// Each average is given by the column of its sums, which it replaces, the column of the number of values
+/// that were summed, and its `Decimal75` type. The averages are exact: each sum is divided by its count
+/// and rounded half away from zero to the scale of the type. An average of no values is `NULL`.
+#[derive(Debug, DynPartialEq, PartialEq, Serialize, Deserialize)]
+pub struct AverageExpr {
+    averages: Vec<(Identifier, Identifier, ColumnType)>,
+}
+
+impl AverageExpr {
+    /// Create a new `AverageExpr` node from the sum column, count column and type of each average.
+    pub fn new(averages: Vec<(Identifier, Identifier, ColumnType)>) -> Self {
+        Self { averages }
+    }
+}
+
+fn i256_to_big_int(value: i256) -> BigInt {
+    BigInt::from_signed_bytes_le(&value.to_le_bytes())
+}
+
+fn big_int_to_i256(value: &BigInt) -> Option<i256> {
+    let bytes = value.to_signed_bytes_le();
+    if bytes.len() > 32 {
+        return None;
+    }
+    let mut padded = [if value.sign() == Sign::Minus { 0xff } else { 0 }; 32];
+    padded[..bytes.len()].copy_from_slice(&bytes);
+    Some(i256::from_le_bytes(padded))
+}
+
+/// Divides by a positive denominator, rounding half away from zero.
+fn divide_and_round(numerator: BigInt, denominator: BigInt) -> BigInt {
+    let rounding = if numerator.is_negative() {
+        -denominator.clone()
+    } else {
+        denominator.clone()
+    };
+    (numerator * 2 + rounding) / (denominator * 2)
+}
+
+/// Computes the averages of the given type from arrays of sums and counts.
+fn average_array(sums: &ArrayRef, counts: &ArrayRef, column_type: ColumnType) -> Option<ArrayRef> {
+    let ColumnType::Decimal75(precision, scale) = column_type else {
+        return None;
+    };
+    let sum_scale = match sums.data_type() {
+        DataType::Decimal128(_, sum_scale) | DataType::Decimal256(_, sum_scale) => *sum_scale,
+        _ => 0,
+    };
+    let sums = cast(
+        sums,
+        &DataType::Decimal256(DECIMAL256_MAX_PRECISION, sum_scale),
+    )
+    .ok()?;
+    let sums = sums.as_any().downcast_ref::<Decimal256Array>()?;
+    let counts = cast(counts, &DataType::Int64).ok()?;
+    let counts = counts.as_any().downcast_ref::<Int64Array>()?;
+
+    // The sums are scaled up, or the counts are scaled up when the average has fewer decimal places than the sums.
+    let scaling = i32::from(scale) - i32::from(sum_scale);
+    let factor = BigInt::from(10).pow(scaling.unsigned_abs());
+    let (sum_factor, count_factor) = if scaling >= 0 {
+        (factor, BigInt::one())
+    } else {
+        (BigInt::one(), factor)
+    };
+    let averages = sums
+        .iter()
+        .zip(counts.iter())
+        .map(|(sum, count)| match (sum, count) {
+            (Some(sum), Some(count)) if count > 0 => big_int_to_i256(&divide_and_round(
+                i256_to_big_int(sum) * &sum_factor,
+                BigInt::from(count) * &count_factor,
+            ))
+            .map(Some),
+            _ => Some(None),
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let averages = Decimal256Array::from(averages)
+        .with_precision_and_scale(precision.value(), scale)
+        .ok()?;
+    Some(Arc::new(averages))
+}
+
+#[typetag::serde]
+impl RecordBatchExpr for AverageExpr {
+    fn apply_transformation(&self, record_batch: RecordBatch) -> Option<RecordBatch> {
+        let schema = record_batch.schema();
+        let columns = schema
+            .fields()
+            .iter()
+            .zip(record_batch.columns())
+            .map(|(field, array)| {
+                let average = self
+                    .averages
+                    .iter()
+                    .find(|(sum, _, _)| sum.as_str() == field.name());
+                match average {
+                    Some((_, count, column_type)) => {
+                        let counts = record_batch.column_by_name(count.as_str())?;
+                        let average = average_array(array, counts, *column_type)?;
+                        let is_nullable = field.is_nullable() || average.null_count() > 0;
+                        Some((field.name().clone(), average, is_nullable))
+                    }
+                    None => Some((field.name().clone(), array.clone(), field.is_nullable())),
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        RecordBatch::try_from_iter_with_nullable(columns).ok()
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    base::{database::ColumnType, math::decimal::Precision},
+    record_batch,
+    sql::transform::{test_utility::*, AverageExpr},
+};
+use arrow::{
+    array::{ArrayRef, Decimal256Array, Int64Array},
+    datatypes::i256,
+    record_batch::RecordBatch,
+};
+use std::sync::Arc;
+
+fn average(sum: &str, count: &str, precision: u8, scale: i8) -> AverageExpr {
+    AverageExpr::new(vec![(
+        sum.parse().unwrap(),
+        count.parse().unwrap(),
+        ColumnType::Decimal75(Precision::new(precision).unwrap(), scale),
+    )])
+}
+
+fn decimal_array(values: Vec<Option<i128>>, precision: u8, scale: i8) -> ArrayRef {
+    Arc::new(
+        Decimal256Array::from(Vec::from_iter(
+            values.into_iter().map(|value| value.map(i256::from_i128)),
+        ))
+        .with_precision_and_scale(precision, scale)
+        .unwrap(),
+    )
+}
+
+fn transform(data: RecordBatch, average: AverageExpr) -> RecordBatch {
+    composite_result(vec![Box::new(average)])
+        .transform_results(data)
+        .unwrap()
+}
+
+#[test]
+fn we_can_average_integer_sums_rounding_half_away_from_zero() {
+    let data = record_batch!(
+        "a" => ["x", "y", "z", "w"],
+        "s" => [7_i64, -7, 10, 5],
+        "c" => [2_i64, 2, 4, 3],
+    );
+    let data = transform(data, average("s", "c", 20, 0));
+    assert_eq!(data.num_columns(), 3);
+    assert_eq!(
+        data.column_by_name("s").unwrap(),
+        &decimal_array(vec![Some(4), Some(-4), Some(3), Some(2)], 20, 0)
+    );
+    assert!(!data.schema().field_with_name("s").unwrap().is_nullable());
+    assert_eq!(
+        data.column_by_name("c").unwrap(),
+        &(Arc::new(Int64Array::from(vec![2, 2, 4, 3])) as ArrayRef)
+    );
+}
+
+#[test]
+fn we_can_average_integer_sums_with_decimal_places() {
+    let data = record_batch!("s" => [1_i64, 2, -2], "c" => [3_i64, 3, 3]);
+    let data = transform(data, average("s", "c", 25, 6));
+    assert_eq!(
+        data.column_by_name("s").unwrap(),
+        &decimal_array(vec![Some(333_333), Some(666_667), Some(-666_667)], 25, 6)
+    );
+}
+
+#[test]
+fn we_can_average_decimal_sums_to_a_larger_or_smaller_scale() {
+    let data = RecordBatch::try_from_iter([
+        ("s", decimal_array(vec![Some(125), Some(-125)], 10, 2)),
+        ("t", decimal_array(vec![Some(125), Some(-125)], 10, 2)),
+        ("c", Arc::new(Int64Array::from(vec![2, 2])) as ArrayRef),
+    ])
+    .unwrap();
+    let data = transform(
+        data,
+        AverageExpr::new(vec![
+            (
+                "s".parse().unwrap(),
+                "c".parse().unwrap(),
+                ColumnType::Decimal75(Precision::new(16).unwrap(), 4),
+            ),
+            (
+                "t".parse().unwrap(),
+                "c".parse().unwrap(),
+                ColumnType::Decimal75(Precision::new(16).unwrap(), 1),
+            ),
+        ]),
+    );
+    assert_eq!(
+        data.column_by_name("s").unwrap(),
+        &decimal_array(vec![Some(6250), Some(-6250)], 16, 4)
+    );
+    assert_eq!(
+        data.column_by_name("t").unwrap(),
+        &decimal_array(vec![Some(6), Some(-6)], 16, 1)
+    );
+}
+
+#[test]
+fn we_get_null_averages_of_no_values() {
+    let data = record_batch!("s" => [0_i64, 4], "c" => [0_i64, 2]);
+    let data = transform(data, average("s", "c", 25, 6));
+    assert_eq!(
+        data.column_by_name("s").unwrap(),
+        &decimal_array(vec![None, Some(2_000_000)], 25, 6)
+    );
+    assert!(data.schema().field_with_name("s").unwrap().is_nullable());
+}
+
+#[test]
+fn we_cannot_average_without_a_count_column() {
+    let data = record_batch!("s" => [1_i64, 4]);
+    assert!(composite_result(vec![Box::new(average("s", "c", 25, 6))])
+        .transform_results(data)
+        .is_none());
+}
@@ -0,0 +1,73 @@
+use super::record_batch_expr::RecordBatchExpr;
+use arrow::{
+    array::{new_null_array, Array, ArrayRef, Int64Array},
+    compute::cast,
+    datatypes::DataType,
+    record_batch::RecordBatch,
+};
+use dyn_partial_eq::DynPartialEq;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A node that gives an aggregation without `GROUP BY` its SQL result when no row is selected.
+///
+/// Such an aggregation is proven as a single row of zero sums and counts when no row is selected,
+/// or as no row at all when it has a `MIN` or `MAX`. In SQL, the result is a single row where the counts are zero
+/// and every other aggregation is `NULL`. So, when the count of the selected rows is zero or there is no row,
+/// the result is replaced by such a row, where the count columns are given by `count_columns`.
+#[derive(Debug, DynPartialEq, PartialEq, Serialize, Deserialize)]
+pub struct EmptyAggregationExpr {
+    count_alias: Identifier,
+    count_columns: Vec<Identifier>,
+}
+
+impl EmptyAggregationExpr {
+    /// Create a new `EmptyAggregationExpr` node from the column that counts the selected rows
+    /// and the other columns that hold counts.
+    pub fn new(count_alias: Identifier, count_columns: Vec<Identifier>) -> Self {
+        Self {
+            count_alias,
+            count_columns,
+        }
+    }
+
+    fn is_count_column(&self, name: &str) -> bool {
+        self.count_alias.as_str() == name
+            || self
+                .count_columns
+                .iter()
+                .any(|count| count.as_str() == name)
+    }
+}
+
+#[typetag::serde]
+impl RecordBatchExpr for EmptyAggregationExpr {
+    fn apply_transformation(&self, record_batch: RecordBatch) -> Option<RecordBatch> {
+        let counts = record_batch.column_by_name(self.count_alias.as_str())?;
+        let counts = cast(counts, &DataType::Int64).ok()?;
+        let counts = counts.as_any().downcast_ref::<Int64Array>()?;
+        match counts.len() {
+            0 => {}
+            1 if counts.value(0) == 0 => {}
+            1 => return Some(record_batch),
+            _ => return None,
+        }
+        let zero: ArrayRef = Arc::new(Int64Array::from(vec![0]));
+        let schema = record_batch.schema();
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                if self.is_count_column(field.name()) {
+                    let count = cast(&zero, field.data_type()).ok()?;
+                    Some((field.name().clone(), count, field.is_nullable()))
+                } else {
+                    let null = new_null_array(field.data_type(), 1);
+                    Some((field.name().clone(), null, true))
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        RecordBatch::try_from_iter_with_nullable(columns).ok()
+    }
+}
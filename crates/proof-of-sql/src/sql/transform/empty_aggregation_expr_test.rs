@@ -0,0 +1,49 @@
+use crate::{
+    record_batch,
+    sql::transform::{test_utility::composite_result, EmptyAggregationExpr},
+};
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray},
+    record_batch::RecordBatch,
+};
+use std::sync::Arc;
+
+fn transform(data: RecordBatch) -> RecordBatch {
+    composite_result(vec![Box::new(EmptyAggregationExpr::new(
+        "c".parse().unwrap(),
+        vec!["n".parse().unwrap()],
+    ))])
+    .transform_results(data)
+    .unwrap()
+}
+
+#[test]
+fn we_keep_an_aggregation_of_selected_rows() {
+    let data = record_batch!("s" => [7_i64], "n" => [2_i64], "c" => [3_i64]);
+    assert_eq!(transform(data.clone()), data);
+}
+
+#[test]
+fn we_turn_the_aggregations_of_no_rows_into_nulls() {
+    let expected = RecordBatch::try_from_iter([
+        (
+            "s",
+            Arc::new(Int64Array::from(vec![None::<i64>])) as ArrayRef,
+        ),
+        ("m", Arc::new(StringArray::from(vec![None::<&str>]))),
+        ("n", Arc::new(Int64Array::from(vec![0]))),
+        ("c", Arc::new(Int64Array::from(vec![0]))),
+    ])
+    .unwrap();
+    // a sum of no rows is proven as zero
+    let data = record_batch!("s" => [0_i64], "m" => [""], "n" => [0_i64], "c" => [0_i64]);
+    assert_eq!(transform(data), expected);
+    // an aggregation with a MIN or MAX of no rows is proven as no row
+    let data = record_batch!(
+        "s" => Vec::<i64>::new(),
+        "m" => Vec::<String>::new(),
+        "n" => Vec::<i64>::new(),
+        "c" => Vec::<i64>::new(),
+    );
+    assert_eq!(transform(data), expected);
+}
@@ -4,7 +4,7 @@ use super::ToPolarsExpr;
 use crate::base::database::{INT128_PRECISION, INT128_SCALE};
 use dyn_partial_eq::DynPartialEq;
 use polars::prelude::{col, DataType, Expr, GetOutput, LazyFrame, NamedFrom, Series};
-use proof_of_sql_parser::{intermediate_ast::AliasedResultExpr, Identifier};
+use proof_of_sql_parser::intermediate_ast::{AliasedResultExpr, Expression};
 use serde::{Deserialize, Serialize};
 
 /// A group by expression
@@ -19,8 +19,8 @@ pub struct GroupByExpr {
 
 impl GroupByExpr {
     /// Create a new group by expression containing the group by and aggregation expressions
-    pub fn new(by_ids: &[Identifier], aliased_exprs: &[AliasedResultExpr]) -> Self {
-        let by_exprs = Vec::from_iter(by_ids.iter().map(|id| col(id.as_str())));
+    pub fn new(by_exprs: &[Expression], aliased_exprs: &[AliasedResultExpr]) -> Self {
+        let by_exprs = Vec::from_iter(by_exprs.iter().map(ToPolarsExpr::to_polars_expr));
         let agg_exprs = Vec::from_iter(aliased_exprs.iter().map(ToPolarsExpr::to_polars_expr));
         assert!(!agg_exprs.is_empty(), "Agg expressions must not be empty");
         assert!(
@@ -104,11 +104,11 @@ fn group_by_map_to_utf8_if_decimal(expr: Expr) -> Expr {
     expr.map(
         |series| match series.dtype().clone() {
             DataType::Decimal(Some(INT128_PRECISION), Some(INT128_SCALE)) => {
-                let utf8_data: Vec<_> = series
+                let utf8_data: Vec<Option<String>> = series
                     .decimal()
                     .unwrap()
-                    .into_no_null_iter()
-                    .map(group_by_map_i128_to_utf8)
+                    .into_iter()
+                    .map(|v| v.map(group_by_map_i128_to_utf8))
                     .collect();
                 Ok(Some(Series::new(series.name(), &utf8_data)))
             }
@@ -1,10 +1,15 @@
 use super::group_by_map_i128_to_utf8;
 use crate::{
     record_batch,
-    sql::transform::test_utility::{col, composite_result, groupby, lit},
+    sql::transform::test_utility::{col, composite_result, groupby, lit, lit_i64},
 };
-use arrow::record_batch::RecordBatch;
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray},
+    record_batch::RecordBatch,
+};
+use proof_of_sql_parser::intermediate_ast::{DataType, Expression};
 use rand::Rng;
+use std::sync::Arc;
 
 #[test]
 fn we_can_transform_batch_using_group_by_with_a_varchar_column() {
@@ -292,6 +297,63 @@ fn we_can_transform_batch_using_arithmetic_outside_the_aggregation_exprs() {
     assert_eq!(data, expected_data);
 }
 
+#[test]
+fn we_can_transform_batch_using_group_by_on_an_arithmetic_expression() {
+    let data = record_batch!(
+        "c" => [1_i64, -5, 2, -5, 7],
+        "d" => [10_i64, 20, 30, 40, 50]
+    );
+    let by_exprs = vec![col("c") * lit_i64(2)];
+    let agg_exprs = vec![
+        (col("c") * lit_i64(2)).first().alias("c2"),
+        col("d").sum().alias("sum_d"),
+    ];
+    let result_expr = composite_result(vec![groupby(by_exprs, agg_exprs)]);
+    let data = result_expr.transform_results(data).unwrap();
+    let expected_data = record_batch!(
+        "c2" => [2_i64, -10, 4, 14],
+        "sum_d" => [10_i64, 60, 30, 50],
+    );
+    assert_eq!(data, expected_data);
+}
+
+#[test]
+fn we_can_transform_batch_using_case_expressions_in_the_aggregation() {
+    let data = record_batch!(
+        "a" => ["x", "y", "x", "y", "x"],
+        "b" => [true, false, false, true, true],
+        "c" => [1_i64, 2, 3, 4, 5]
+    );
+    let by_exprs = vec![col("a")];
+    let case_expr = Box::new(Expression::Case {
+        when_then: vec![(col("b"), col("c"))],
+        else_expr: Some(lit_i64(0)),
+    });
+    let agg_exprs = vec![case_expr.sum().alias("c_sum")];
+    let result_expr = composite_result(vec![groupby(by_exprs, agg_exprs)]);
+    let data = result_expr.transform_results(data).unwrap();
+    let expected_data = record_batch!("c_sum" => [6_i64, 4]);
+    assert_eq!(data, expected_data);
+}
+
+#[test]
+fn we_can_transform_batch_using_casts_in_the_aggregation() {
+    let data = record_batch!(
+        "a" => ["x", "y", "x", "y", "x"],
+        "b" => [true, false, false, true, true]
+    );
+    let by_exprs = vec![col("a")];
+    let cast_expr = Box::new(Expression::Cast {
+        expr: col("b"),
+        data_type: DataType::BigInt,
+    });
+    let agg_exprs = vec![cast_expr.sum().alias("b_sum")];
+    let result_expr = composite_result(vec![groupby(by_exprs, agg_exprs)]);
+    let data = result_expr.transform_results(data).unwrap();
+    let expected_data = record_batch!("b_sum" => [2_i64, 1]);
+    assert_eq!(data, expected_data);
+}
+
 #[test]
 fn we_can_use_decimal_columns_inside_group_by() {
     let nines: i128 = "9".repeat(38).parse::<i128>().unwrap();
@@ -309,6 +371,48 @@ fn we_can_use_decimal_columns_inside_group_by() {
     assert_eq!(data, expected_data);
 }
 
+#[test]
+fn we_can_group_by_a_column_with_nulls_and_aggregate_a_column_with_nulls() {
+    let data = RecordBatch::try_from_iter([
+        (
+            "a",
+            Arc::new(StringArray::from(vec![
+                Some("x"),
+                None,
+                Some("x"),
+                None,
+                Some("y"),
+            ])) as ArrayRef,
+        ),
+        (
+            "b",
+            Arc::new(Int64Array::from(vec![Some(1), Some(2), None, None, None])),
+        ),
+    ])
+    .unwrap();
+    let by_exprs = vec![col("a")];
+    let agg_exprs = vec![
+        col("a").first().alias("a"),
+        col("b").sum().alias("b_sum"),
+        col("b").count().alias("b_count"),
+    ];
+    let result_expr = composite_result(vec![groupby(by_exprs, agg_exprs)]);
+    let data = result_expr.transform_results(data).unwrap();
+    let expected_data = RecordBatch::try_from_iter([
+        (
+            "a",
+            Arc::new(StringArray::from(vec![Some("x"), None, Some("y")])) as ArrayRef,
+        ),
+        (
+            "b_sum",
+            Arc::new(Int64Array::from(vec![Some(1), Some(2), None])),
+        ),
+        ("b_count", Arc::new(Int64Array::from(vec![1, 1, 0]))),
+    ])
+    .unwrap();
+    assert_eq!(data, expected_data);
+}
+
 #[test]
 fn transforming_a_batch_of_size_zero_with_min_max_agg_and_decimal_column_is_fine() {
     let data = record_batch!("h" => [-1_i128], "i" => [2_i128], "j" => [2_i128], "k" => [2_i64]);
@@ -0,0 +1,56 @@
+use super::{
+    record_batch_expr::RecordBatchExpr,
+    result_expr::{lazy_frame_to_record_batch, record_batch_to_lazy_frame},
+    ToPolarsExpr,
+};
+use arrow::{array::BooleanArray, compute::filter_record_batch, record_batch::RecordBatch};
+use dyn_partial_eq::DynPartialEq;
+use polars::prelude::Expr;
+use proof_of_sql_parser::{intermediate_ast::Expression, Identifier};
+use serde::{Deserialize, Serialize};
+
+/// A node that keeps the rows of a grouped result for which the `HAVING` condition is true.
+///
+/// It also keeps the rows of the proven result of a common table expression for which
+/// the `WHERE` condition of the query that selects from it is true.
+///
+/// Only the columns that the condition references are used to evaluate it,
+/// so the other columns may be of types that polars does not support, e.g. averages.
+#[derive(Debug, DynPartialEq, PartialEq, Serialize, Deserialize)]
+pub struct HavingExpr {
+    condition: Expr,
+    columns: Vec<Identifier>,
+}
+
+impl HavingExpr {
+    /// Create a new `HavingExpr` node from the condition and the columns that it references.
+    pub fn new(condition: &Expression, columns: Vec<Identifier>) -> Self {
+        assert!(!columns.is_empty());
+        Self {
+            condition: condition.to_polars_expr(),
+            columns,
+        }
+    }
+}
+
+#[typetag::serde]
+impl RecordBatchExpr for HavingExpr {
+    fn apply_transformation(&self, record_batch: RecordBatch) -> Option<RecordBatch> {
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| {
+                Some((
+                    column.as_str(),
+                    record_batch.column_by_name(column.as_str())?.clone(),
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let (lazy_frame, _) =
+            record_batch_to_lazy_frame(RecordBatch::try_from_iter(columns).ok()?)?;
+        let mask = lazy_frame_to_record_batch(lazy_frame.select(&[self.condition.clone()]))?;
+        // A row whose condition is NULL is filtered out, as in SQL.
+        let mask = mask.column(0).as_any().downcast_ref::<BooleanArray>()?;
+        filter_record_batch(&record_batch, mask).ok()
+    }
+}
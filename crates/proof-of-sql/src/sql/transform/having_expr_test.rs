@@ -0,0 +1,96 @@
+use crate::{
+    record_batch,
+    sql::transform::test_utility::{col, composite_result, having, lit},
+};
+use arrow::{
+    array::{ArrayRef, Decimal256Array, Int64Array},
+    datatypes::i256,
+    record_batch::RecordBatch,
+};
+use proof_of_sql_parser::intermediate_ast::{BinaryOperator, Expression, UnaryOperator};
+use std::sync::Arc;
+
+fn binary(op: BinaryOperator, left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Binary { op, left, right })
+}
+
+#[test]
+fn we_can_keep_the_rows_for_which_the_condition_is_true() {
+    let data = record_batch!(
+        "a" => ["x", "y", "z", "w"],
+        "c" => [3_i64, 1, 5, 2],
+        "s" => [10_i64, 20, 30, 40],
+    );
+    let result_expr = composite_result(vec![having(
+        binary(
+            BinaryOperator::And,
+            binary(BinaryOperator::GreaterThanOrEqual, col("c"), lit(2_i64)),
+            binary(BinaryOperator::LessThanOrEqual, col("s"), lit(30_i64)),
+        ),
+        &["c", "s"],
+    )]);
+    let data = result_expr.transform_results(data).unwrap();
+    assert_eq!(
+        data,
+        record_batch!(
+            "a" => ["x", "z"],
+            "c" => [3_i64, 5],
+            "s" => [10_i64, 30],
+        )
+    );
+}
+
+#[test]
+fn we_can_compare_strings_and_filter_out_all_rows() {
+    let data = record_batch!("a" => ["x", "y"], "c" => [3_i64, 1]);
+    let result_expr = composite_result(vec![having(
+        binary(BinaryOperator::Equal, col("a"), lit("y")),
+        &["a"],
+    )]);
+    assert_eq!(
+        result_expr.transform_results(data.clone()).unwrap(),
+        record_batch!("a" => ["y"], "c" => [1_i64])
+    );
+    let result_expr = composite_result(vec![having(
+        binary(BinaryOperator::Equal, col("c"), lit(2_i64)),
+        &["c"],
+    )]);
+    assert_eq!(result_expr.transform_results(data).unwrap().num_rows(), 0);
+}
+
+#[test]
+fn we_filter_out_rows_whose_condition_is_null_without_converting_other_columns() {
+    let averages: ArrayRef = Arc::new(
+        Decimal256Array::from(vec![Some(i256::from_i128(15)), None, None])
+            .with_precision_and_scale(25, 6)
+            .unwrap(),
+    );
+    let data = RecordBatch::try_from_iter([
+        ("x", averages.clone()),
+        (
+            "s",
+            Arc::new(Int64Array::from(vec![Some(1), Some(2), None])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    let result_expr = composite_result(vec![having(
+        Box::new(Expression::Unary {
+            op: UnaryOperator::Not,
+            expr: binary(BinaryOperator::Equal, col("s"), lit(2_i64)),
+        }),
+        &["s"],
+    )]);
+    let data = result_expr.transform_results(data).unwrap();
+    assert_eq!(data.num_rows(), 1);
+    assert_eq!(data.column_by_name("x").unwrap(), &averages.slice(0, 1));
+}
+
+#[test]
+fn we_cannot_filter_by_a_missing_column() {
+    let data = record_batch!("c" => [3_i64, 1]);
+    let result_expr = composite_result(vec![having(
+        binary(BinaryOperator::Equal, col("d"), lit(2_i64)),
+        &["d"],
+    )]);
+    assert!(result_expr.transform_results(data).is_none());
+}
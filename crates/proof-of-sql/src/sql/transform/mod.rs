@@ -47,6 +47,30 @@ pub use group_by_expr::GroupByExpr;
 #[cfg(test)]
 mod group_by_expr_test;
 
+mod average_expr;
+pub use average_expr::AverageExpr;
+
+#[cfg(test)]
+mod average_expr_test;
+
+mod window_expr;
+pub use window_expr::WindowExpr;
+
+#[cfg(test)]
+mod window_expr_test;
+
+mod having_expr;
+pub use having_expr::HavingExpr;
+
+#[cfg(test)]
+mod having_expr_test;
+
+mod empty_aggregation_expr;
+pub use empty_aggregation_expr::EmptyAggregationExpr;
+
+#[cfg(test)]
+mod empty_aggregation_expr_test;
+
 mod polars_conversions;
 pub use polars_conversions::LiteralConversion;
 
@@ -1,6 +1,6 @@
 use crate::{
     base::database::{dataframe_to_record_batch, record_batch_to_dataframe},
-    sql::transform::RecordBatchExpr,
+    sql::transform::{CompositionExpr, RecordBatchExpr},
 };
 use arrow::record_batch::RecordBatch;
 use dyn_partial_eq::DynPartialEq;
@@ -21,6 +21,13 @@ impl ResultExpr {
     pub fn new(transformation: Box<dyn RecordBatchExpr>) -> Self {
         Self { transformation }
     }
+
+    /// Create a new `ResultExpr` node that applies the transformation of `self`, then that of `next`.
+    pub fn chain(self, next: ResultExpr) -> Self {
+        let mut composition = CompositionExpr::new(self.transformation);
+        composition.add(next.transformation);
+        Self::new(Box::new(composition))
+    }
 }
 
 pub(super) fn record_batch_to_lazy_frame(result_batch: RecordBatch) -> Option<(LazyFrame, usize)> {
@@ -29,17 +36,6 @@ pub(super) fn record_batch_to_lazy_frame(result_batch: RecordBatch) -> Option<(L
     Some((df.lazy(), num_input_rows))
 }
 pub(super) fn lazy_frame_to_record_batch(lazy_frame: LazyFrame) -> Option<RecordBatch> {
-    // We're currently excluding NULLs in post-processing due to a lack of
-    // prover support, aiming to avoid future complexities.
-    // The drawback is that users won't get NULL results in aggregations on empty data.
-    // For example, the query `SELECT MAX(i), COUNT(i), SUM(i), MIN(i) FROM table WHERE s = 'nonexist'`
-    // will now omit the entire row (before, it would return `null, 0, 0, null`).
-    // This choice is acceptable, as `SELECT MAX(i), COUNT(i), SUM(i) FROM table WHERE s = 'nonexist' GROUP BY f`
-    // has the same outcome.
-    //
-    // TODO: Revisit if we add NULL support to the prover.
-    let lazy_frame = lazy_frame.drop_nulls(None);
-
     dataframe_to_record_batch(lazy_frame.collect().ok()?)
 }
 
@@ -2,7 +2,11 @@ use crate::{
     record_batch,
     sql::transform::{test_utility::*, ResultExpr},
 };
-use arrow::record_batch::RecordBatch;
+use arrow::{
+    array::{ArrayRef, Decimal128Array, Int64Array, StringArray},
+    record_batch::RecordBatch,
+};
+use std::sync::Arc;
 
 #[test]
 fn we_can_filter_out_record_batch_columns() {
@@ -109,31 +113,95 @@ fn using_count_with_an_empty_batch_will_return_zero() {
 }
 
 #[test]
-fn using_sum_with_an_empty_batch_will_return_zero() {
+fn using_sum_with_an_empty_batch_will_return_null() {
     let data = record_batch!("i" => [-5_i64], "d" => [3_i128]);
     let empty_data = RecordBatch::new_empty(data.schema());
     let result_expr = ResultExpr::new(select(&[col("i").sum(), col("d").sum()]));
     let data = result_expr.transform_results(empty_data).unwrap();
-    let expected_data = record_batch!("i" => [0_i64], "d" => [0_i128]);
+    let expected_data = RecordBatch::try_from_iter([
+        ("i", Arc::new(Int64Array::from(vec![None])) as ArrayRef),
+        (
+            "d",
+            Arc::new(
+                Decimal128Array::from(vec![None])
+                    .with_precision_and_scale(38, 0)
+                    .unwrap(),
+            ),
+        ),
+    ])
+    .unwrap();
     assert_eq!(data, expected_data);
 }
 
 #[test]
-fn using_min_with_an_empty_batch_will_return_empty_even_with_count_or_sum_in_the_result() {
+fn using_min_with_an_empty_batch_will_return_null_along_with_count_and_sum() {
     let data = record_batch!("i" => [-5_i64], "d" => [3_i128], "i1" => [3_i64]);
     let empty_data = RecordBatch::new_empty(data.schema());
     let result_expr = ResultExpr::new(select(&[col("i").count(), col("d").sum(), col("i1").min()]));
-    let data = result_expr.transform_results(empty_data.clone()).unwrap();
-    let expected_data = empty_data;
+    let data = result_expr.transform_results(empty_data).unwrap();
+    let expected_data = RecordBatch::try_from_iter([
+        ("i", Arc::new(Int64Array::from(vec![0])) as ArrayRef),
+        (
+            "d",
+            Arc::new(
+                Decimal128Array::from(vec![None])
+                    .with_precision_and_scale(38, 0)
+                    .unwrap(),
+            ),
+        ),
+        ("i1", Arc::new(Int64Array::from(vec![None]))),
+    ])
+    .unwrap();
     assert_eq!(data, expected_data);
 }
 
 #[test]
-fn using_max_with_an_empty_batch_will_return_empty_even_with_count_or_sum_in_the_result() {
+fn using_max_with_an_empty_batch_will_return_null_along_with_count_and_sum() {
     let data = record_batch!("i" => [-5_i64], "d" => [3_i128], "i1" => [3_i64]);
     let empty_data = RecordBatch::new_empty(data.schema());
     let result_expr = ResultExpr::new(select(&[col("i").count(), col("d").sum(), col("i1").max()]));
-    let data = result_expr.transform_results(empty_data.clone()).unwrap();
-    let expected_data = empty_data;
+    let data = result_expr.transform_results(empty_data).unwrap();
+    let expected_data = RecordBatch::try_from_iter([
+        ("i", Arc::new(Int64Array::from(vec![0])) as ArrayRef),
+        (
+            "d",
+            Arc::new(
+                Decimal128Array::from(vec![None])
+                    .with_precision_and_scale(38, 0)
+                    .unwrap(),
+            ),
+        ),
+        ("i1", Arc::new(Int64Array::from(vec![None]))),
+    ])
+    .unwrap();
+    assert_eq!(data, expected_data);
+}
+
+#[test]
+fn aggregations_ignore_null_values() {
+    let data = RecordBatch::try_from_iter([
+        (
+            "i",
+            Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+        ),
+        (
+            "s",
+            Arc::new(StringArray::from(vec![None, None, Some("a")])),
+        ),
+    ])
+    .unwrap();
+    let result_expr = ResultExpr::new(select(&[
+        col("i").count().alias("i_count"),
+        col("i").sum().alias("i_sum"),
+        col("i").min().alias("i_min"),
+        col("s").count().alias("s_count"),
+    ]));
+    let data = result_expr.transform_results(data).unwrap();
+    let expected_data = record_batch!(
+        "i_count" => [2_i64],
+        "i_sum" => [4_i64],
+        "i_min" => [1_i64],
+        "s_count" => [1_i64],
+    );
     assert_eq!(data, expected_data);
 }
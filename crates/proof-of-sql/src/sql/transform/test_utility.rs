@@ -67,6 +67,31 @@ pub fn orders(cols: &[&str], directions: &[OrderByDirection]) -> Box<dyn RecordB
     Box::new(OrderByExprs::new(by_exprs))
 }
 
+pub fn window(
+    function: WindowFunction,
+    partition_by: &[&str],
+    order_by: &[(&str, OrderByDirection)],
+) -> Box<Expression> {
+    Box::new(Expression::Window {
+        function,
+        partition_by: partition_by
+            .iter()
+            .map(|column| column.parse().unwrap())
+            .collect(),
+        order_by: order_by
+            .iter()
+            .map(|(column, direction)| OrderBy {
+                expr: column.parse().unwrap(),
+                direction: *direction,
+            })
+            .collect(),
+    })
+}
+
+pub fn windows(windows: Vec<AliasedResultExpr>) -> Box<dyn RecordBatchExpr> {
+    Box::new(WindowExpr::new(windows))
+}
+
 pub fn groupby<
     T: IntoIterator<Item = Box<Expression>>,
     A: IntoIterator<Item = AliasedResultExpr>,
@@ -75,10 +100,17 @@ pub fn groupby<
     agg_exprs: A,
 ) -> Box<dyn RecordBatchExpr> {
     Box::new(GroupByExpr::new(
-        &Vec::from_iter(by_exprs.into_iter().map(|expr| match *expr {
-            Expression::Column(c) => c,
-            _ => panic!("Expected column expression"),
-        })),
+        &Vec::from_iter(by_exprs.into_iter().map(|expr| *expr)),
         &Vec::from_iter(agg_exprs),
     ))
 }
+
+pub fn having(condition: Box<Expression>, columns: &[&str]) -> Box<dyn RecordBatchExpr> {
+    Box::new(HavingExpr::new(
+        &condition,
+        columns
+            .iter()
+            .map(|column| column.parse().unwrap())
+            .collect(),
+    ))
+}
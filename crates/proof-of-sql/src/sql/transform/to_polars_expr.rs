@@ -1,5 +1,6 @@
 use super::{polars_arithmetic::SafeDivision, polars_conversions::LiteralConversion};
-use polars::prelude::{col, Expr};
+use crate::base::database::{INT128_PRECISION, INT128_SCALE};
+use polars::prelude::{col, lit, when, DataType as PolarsDataType, Expr, Null};
 use proof_of_sql_parser::intermediate_ast::*;
 pub(crate) trait ToPolarsExpr {
     fn to_polars_expr(&self) -> Expr;
@@ -28,10 +29,21 @@ impl ToPolarsExpr for Expression {
                 Literal::Boolean(value) => value.to_lit(),
                 Literal::BigInt(value) => value.to_lit(),
                 Literal::Int128(value) => value.to_lit(),
-                Literal::VarChar(_) => panic!("Expression not supported"),
+                Literal::VarChar(value) => lit(value.as_str()),
+                Literal::Timestamp(_) | Literal::Date(_) => {
+                    panic!("Expression not supported")
+                }
                 Literal::Decimal(_) => todo!(),
             },
             Expression::Column(identifier) => col(identifier.as_str()),
+            Expression::Unary { op, expr } => {
+                let expr = expr.to_polars_expr();
+                match op {
+                    UnaryOperator::Not => expr.not(),
+                    UnaryOperator::IsNull => expr.is_null(),
+                    UnaryOperator::IsNotNull => expr.is_not_null(),
+                }
+            }
             Expression::Binary { op, left, right } => {
                 let left = left.to_polars_expr();
                 let right = right.to_polars_expr();
@@ -40,19 +52,64 @@ impl ToPolarsExpr for Expression {
                     BinaryOperator::Subtract => left - right,
                     BinaryOperator::Multiply => left * right,
                     BinaryOperator::Division => left.checked_div(right),
-                    _ => panic!("Operation not supported yet"),
+                    BinaryOperator::Equal => left.eq(right),
+                    BinaryOperator::GreaterThanOrEqual => left.gt_eq(right),
+                    BinaryOperator::LessThanOrEqual => left.lt_eq(right),
+                    BinaryOperator::And => left.and(right),
+                    BinaryOperator::Or => left.or(right),
                 }
             }
             Expression::Aggregation { op, expr } => {
                 let expr = expr.to_polars_expr();
                 match op {
-                    AggregationOperator::Count => expr.count(),
-                    AggregationOperator::Sum => expr.sum(),
+                    // Polars counts nulls, unlike SQL.
+                    AggregationOperator::Count => expr.is_not_null().sum(),
+                    // Polars counts null as a distinct value, unlike SQL.
+                    AggregationOperator::CountDistinct => expr.drop_nulls().n_unique(),
+                    // Polars sums nulls only to zero, unlike SQL.
+                    AggregationOperator::Sum => when(expr.clone().is_not_null().sum().gt(lit(0)))
+                        .then(expr.sum())
+                        .otherwise(lit(Null {})),
                     AggregationOperator::Min => expr.min(),
                     AggregationOperator::Max => expr.max(),
                     AggregationOperator::First => expr.first(),
+                    AggregationOperator::Avg => {
+                        panic!("Averages are computed from a sum and a count by an AverageExpr")
+                    }
                 }
             }
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => when_then.iter().rev().fold(
+                else_expr
+                    .as_ref()
+                    .map_or(lit(Null {}), |else_expr| else_expr.to_polars_expr()),
+                |otherwise, (when_expr, then_expr)| {
+                    when(when_expr.to_polars_expr())
+                        .then(then_expr.to_polars_expr())
+                        .otherwise(otherwise)
+                },
+            ),
+            // Casts that overflow are errors rather than nulls, as in the provable cast.
+            Expression::Cast { expr, data_type } => {
+                expr.to_polars_expr().strict_cast(match data_type {
+                    DataType::Boolean => PolarsDataType::Boolean,
+                    DataType::SmallInt => PolarsDataType::Int16,
+                    DataType::Int => PolarsDataType::Int32,
+                    DataType::BigInt => PolarsDataType::Int64,
+                    DataType::Int128 => {
+                        PolarsDataType::Decimal(Some(INT128_PRECISION), Some(INT128_SCALE))
+                    }
+                    DataType::Decimal75(precision, scale) => PolarsDataType::Decimal(
+                        Some(usize::from(*precision).min(INT128_PRECISION)),
+                        Some(
+                            usize::try_from(*scale)
+                                .expect("Negative scales are not supported in post-processing"),
+                        ),
+                    ),
+                })
+            }
             _ => panic!("Operation not supported"),
         }
     }
@@ -0,0 +1,200 @@
+use super::record_batch_expr::RecordBatchExpr;
+use arrow::{
+    array::{Array, ArrayRef, Decimal256Array, Int64Array},
+    compute::{cast, cast_with_options, CastOptions, SortOptions},
+    datatypes::{i256, DataType, DECIMAL256_MAX_PRECISION},
+    record_batch::RecordBatch,
+    row::{RowConverter, Rows, SortField},
+};
+use core::cmp::Ordering;
+use dyn_partial_eq::DynPartialEq;
+use proof_of_sql_parser::{
+    intermediate_ast::{AliasedResultExpr, Expression, OrderBy, OrderByDirection, WindowFunction},
+    Identifier,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A node that adds a column of the values of each window function to the record batch, named after its alias.
+///
+/// The rows are partitioned and ordered as the window says, with nulls first as in `ORDER BY`.
+/// Rows of a partition that are equal in the window order are peers: they share a `RANK()`,
+/// and the running `SUM` of a row includes all its peers. `ROW_NUMBER()` numbers the peers in the order of the rows.
+#[derive(Debug, DynPartialEq, PartialEq, Serialize, Deserialize)]
+pub struct WindowExpr {
+    windows: Vec<AliasedResultExpr>,
+}
+
+impl WindowExpr {
+    /// Create a new `WindowExpr` node from window functions of columns.
+    pub fn new(windows: Vec<AliasedResultExpr>) -> Self {
+        Self { windows }
+    }
+}
+
+/// Converts the given columns of the record batch into rows that compare as the columns are ordered.
+///
+/// Without any columns, all the rows are equal, which is represented by `Some(None)`.
+fn sort_rows(
+    record_batch: &RecordBatch,
+    columns: &[(Identifier, SortOptions)],
+) -> Option<Option<Rows>> {
+    if columns.is_empty() {
+        return Some(None);
+    }
+    let arrays = columns
+        .iter()
+        .map(|(column, _)| record_batch.column_by_name(column.as_str()).cloned())
+        .collect::<Option<Vec<_>>>()?;
+    let mut converter = RowConverter::new(
+        arrays
+            .iter()
+            .zip(columns)
+            .map(|(array, (_, options))| {
+                SortField::new_with_options(array.data_type().clone(), *options)
+            })
+            .collect(),
+    )
+    .ok()?;
+    converter.convert_columns(&arrays).ok().map(Some)
+}
+
+fn compare_rows(rows: &Option<Rows>, i: usize, j: usize) -> Ordering {
+    rows.as_ref()
+        .map_or(Ordering::Equal, |rows| rows.row(i).cmp(&rows.row(j)))
+}
+
+/// Splits the rows, in the order of the given indexes, into the runs of rows that are equal.
+fn split_equal_rows<'a>(
+    indexes: &'a [usize],
+    rows: &'a Option<Rows>,
+) -> impl Iterator<Item = &'a [usize]> {
+    indexes.chunk_by(move |&i, &j| compare_rows(rows, i, j) == Ordering::Equal)
+}
+
+/// Computes the running sums of the values, where the sum of a row includes its peers.
+///
+/// The sums are computed exactly and cast back to the type of the values, failing if one of them does not fit.
+fn running_sum_array(
+    values: &ArrayRef,
+    partitions: &[&[usize]],
+    orders: &Option<Rows>,
+) -> Option<ArrayRef> {
+    let scale = match values.data_type() {
+        DataType::Decimal128(_, scale) | DataType::Decimal256(_, scale) => *scale,
+        DataType::Int16 | DataType::Int32 | DataType::Int64 => 0,
+        _ => return None,
+    };
+    let decimal_type = DataType::Decimal256(DECIMAL256_MAX_PRECISION, scale);
+    let decimals = cast(values, &decimal_type).ok()?;
+    let decimals = decimals.as_any().downcast_ref::<Decimal256Array>()?;
+    let mut sums = vec![None; values.len()];
+    for partition in partitions {
+        let mut sum: Option<i256> = None;
+        for peers in split_equal_rows(partition, orders) {
+            for &i in peers {
+                if let Some(value) = decimals.is_valid(i).then(|| decimals.value(i)) {
+                    sum = Some(sum.unwrap_or(i256::ZERO).checked_add(value)?);
+                }
+            }
+            for &i in peers {
+                sums[i] = sum;
+            }
+        }
+    }
+    let sums: ArrayRef = Arc::new(
+        Decimal256Array::from(sums)
+            .with_precision_and_scale(DECIMAL256_MAX_PRECISION, scale)
+            .ok()?,
+    );
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    cast_with_options(&sums, values.data_type(), &options).ok()
+}
+
+/// Computes the values of a window function over the record batch.
+fn window_array(
+    record_batch: &RecordBatch,
+    function: &WindowFunction,
+    partition_by: &[Identifier],
+    order_by: &[OrderBy],
+) -> Option<ArrayRef> {
+    let partition_columns = Vec::from_iter(
+        partition_by
+            .iter()
+            .map(|column| (*column, SortOptions::default())),
+    );
+    let order_columns = Vec::from_iter(order_by.iter().map(|order_by| {
+        let options = SortOptions {
+            descending: order_by.direction == OrderByDirection::Desc,
+            nulls_first: true,
+        };
+        (order_by.expr, options)
+    }));
+    let partition_rows = sort_rows(record_batch, &partition_columns)?;
+    let order_rows = sort_rows(record_batch, &order_columns)?;
+
+    // The sort is stable, so that the rows of a partition that are peers are kept in the order of the rows.
+    let mut indexes = Vec::from_iter(0..record_batch.num_rows());
+    indexes.sort_by(|&i, &j| {
+        compare_rows(&partition_rows, i, j).then_with(|| compare_rows(&order_rows, i, j))
+    });
+    let partitions = Vec::from_iter(split_equal_rows(&indexes, &partition_rows));
+
+    match function {
+        WindowFunction::RowNumber | WindowFunction::Rank => {
+            let mut numbers = vec![0; indexes.len()];
+            for partition in &partitions {
+                let mut row_number = 0;
+                for peers in split_equal_rows(partition, &order_rows) {
+                    let rank = row_number + 1;
+                    for &i in peers {
+                        row_number += 1;
+                        numbers[i] = match function {
+                            WindowFunction::Rank => rank,
+                            _ => row_number,
+                        };
+                    }
+                }
+            }
+            Some(Arc::new(Int64Array::from(numbers)))
+        }
+        WindowFunction::Sum(expr) => {
+            let Expression::Column(column) = **expr else {
+                return None;
+            };
+            let values = record_batch.column_by_name(column.as_str())?;
+            running_sum_array(values, &partitions, &order_rows)
+        }
+    }
+}
+
+#[typetag::serde]
+impl RecordBatchExpr for WindowExpr {
+    fn apply_transformation(&self, record_batch: RecordBatch) -> Option<RecordBatch> {
+        let schema = record_batch.schema();
+        let mut columns = Vec::from_iter(
+            schema
+                .fields()
+                .iter()
+                .zip(record_batch.columns())
+                .map(|(field, array)| (field.name().clone(), array.clone(), field.is_nullable())),
+        );
+        for window in &self.windows {
+            let Expression::Window {
+                function,
+                partition_by,
+                order_by,
+            } = window.expr.as_ref()
+            else {
+                return None;
+            };
+            let array = window_array(&record_batch, function, partition_by, order_by)?;
+            let is_nullable = array.null_count() > 0;
+            columns.push((window.alias.as_str().to_string(), array, is_nullable));
+        }
+        RecordBatch::try_from_iter_with_nullable(columns).ok()
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{record_batch, sql::transform::test_utility::*};
+use arrow::{
+    array::{ArrayRef, Decimal128Array, Int64Array},
+    record_batch::RecordBatch,
+};
+use proof_of_sql_parser::intermediate_ast::{
+    AliasedResultExpr,
+    OrderByDirection::{self, Asc, Desc},
+    WindowFunction,
+};
+use std::sync::Arc;
+
+fn transform(data: RecordBatch, window_exprs: Vec<AliasedResultExpr>) -> RecordBatch {
+    composite_result(vec![windows(window_exprs)])
+        .transform_results(data)
+        .unwrap()
+}
+
+fn int64_array(values: Vec<Option<i64>>) -> ArrayRef {
+    Arc::new(Int64Array::from(values))
+}
+
+#[test]
+fn we_can_number_and_rank_the_rows_of_each_partition() {
+    let data = record_batch!(
+        "k" => ["x", "y", "x", "x", "y", "x"],
+        "ts" => [3_i64, 1, 1, 3, 2, 2],
+    );
+    let data = transform(
+        data,
+        vec![
+            window(WindowFunction::RowNumber, &["k"], &[("ts", Asc)]).alias("n"),
+            window(WindowFunction::Rank, &["k"], &[("ts", Asc)]).alias("r"),
+            window(WindowFunction::Rank, &[], &[("ts", Desc)]).alias("d"),
+            window(WindowFunction::RowNumber, &[], &[]).alias("m"),
+        ],
+    );
+    assert_eq!(data.num_columns(), 6);
+    // Peers are numbered in the order of the rows.
+    assert_eq!(
+        data.column_by_name("n").unwrap(),
+        &int64_array(vec![Some(3), Some(1), Some(1), Some(4), Some(2), Some(2)])
+    );
+    assert_eq!(
+        data.column_by_name("r").unwrap(),
+        &int64_array(vec![Some(3), Some(1), Some(1), Some(3), Some(2), Some(2)])
+    );
+    assert_eq!(
+        data.column_by_name("d").unwrap(),
+        &int64_array(vec![Some(1), Some(5), Some(5), Some(1), Some(3), Some(3)])
+    );
+    assert_eq!(
+        data.column_by_name("m").unwrap(),
+        &int64_array(vec![Some(1), Some(2), Some(3), Some(4), Some(5), Some(6)])
+    );
+    assert!(!data.schema().field_with_name("n").unwrap().is_nullable());
+}
+
+#[test]
+fn we_can_compute_running_sums_that_include_the_peers_of_each_row() {
+    let data = record_batch!(
+        "k" => ["x", "y", "x", "x", "y", "x"],
+        "ts" => [3_i64, 1, 1, 3, 2, 2],
+        "a" => [1_i64, 10, 100, 1000, 10000, 100000],
+    );
+    let sum = |partition_by: &[&str], order_by: &[(&str, OrderByDirection)]| {
+        window(WindowFunction::Sum(col("a")), partition_by, order_by)
+    };
+    let data = transform(
+        data,
+        vec![
+            sum(&["k"], &[("ts", Asc)]).alias("s"),
+            sum(&[], &[("ts", Desc)]).alias("t"),
+            sum(&["k"], &[]).alias("u"),
+        ],
+    );
+    assert_eq!(
+        data.column_by_name("s").unwrap(),
+        &int64_array(vec![
+            Some(101101),
+            Some(10),
+            Some(100),
+            Some(101101),
+            Some(10010),
+            Some(100100)
+        ])
+    );
+    assert_eq!(
+        data.column_by_name("t").unwrap(),
+        &int64_array(vec![
+            Some(1001),
+            Some(111111),
+            Some(111111),
+            Some(1001),
+            Some(111001),
+            Some(111001)
+        ])
+    );
+    assert_eq!(
+        data.column_by_name("u").unwrap(),
+        &int64_array(vec![
+            Some(101101),
+            Some(10010),
+            Some(101101),
+            Some(101101),
+            Some(10010),
+            Some(101101)
+        ])
+    );
+}
+
+#[test]
+fn we_can_compute_running_sums_of_nullable_decimals() {
+    let data = RecordBatch::try_from_iter([
+        (
+            "ts",
+            Arc::new(Int64Array::from(vec![1, 2, 3, 4])) as ArrayRef,
+        ),
+        (
+            "a",
+            Arc::new(
+                Decimal128Array::from(vec![None, Some(125), None, Some(-25)])
+                    .with_precision_and_scale(10, 2)
+                    .unwrap(),
+            ) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    let data = transform(
+        data,
+        vec![window(WindowFunction::Sum(col("a")), &[], &[("ts", Asc)]).alias("s")],
+    );
+    assert_eq!(
+        data.column_by_name("s").unwrap(),
+        &(Arc::new(
+            Decimal128Array::from(vec![None, Some(125), Some(125), Some(100)])
+                .with_precision_and_scale(10, 2)
+                .unwrap()
+        ) as ArrayRef)
+    );
+    assert!(data.schema().field_with_name("s").unwrap().is_nullable());
+}
+
+#[test]
+fn we_cannot_compute_running_sums_that_overflow_or_of_missing_columns() {
+    let data = record_batch!("ts" => [1_i64, 2], "a" => [i64::MAX, 1]);
+    let window_expr = windows(vec![window(
+        WindowFunction::Sum(col("a")),
+        &[],
+        &[("ts", Asc)],
+    )
+    .alias("s")]);
+    assert!(composite_result(vec![window_expr])
+        .transform_results(data.clone())
+        .is_none());
+    let window_expr = windows(vec![
+        window(WindowFunction::RowNumber, &["b"], &[]).alias("n")
+    ]);
+    assert!(composite_result(vec![window_expr])
+        .transform_results(data)
+        .is_none());
+}
@@ -1,7 +1,12 @@
 #![cfg(feature = "test")]
 use ark_std::test_rng;
-use arrow::record_batch::RecordBatch;
-use curve25519_dalek::RistrettoPoint;
+use arrow::{
+    array::{
+        ArrayRef, Date32Array, Decimal256Array, Int64Array, StringArray, TimestampMillisecondArray,
+    },
+    datatypes::i256,
+    record_batch::RecordBatch,
+};
 #[cfg(feature = "blitzar")]
 use proof_of_sql::base::commitment::InnerProductProof;
 use proof_of_sql::{
@@ -16,6 +21,8 @@ use proof_of_sql::{
         proof::QueryProof,
     },
 };
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+use std::sync::Arc;
 
 #[test]
 #[cfg(feature = "blitzar")]
@@ -160,26 +167,67 @@ fn we_can_prove_a_basic_inequality_query_with_curve25519() {
     assert_eq!(owned_table_result, expected_result);
 }
 
-//TODO: Once arithmetic is supported, this test should be updated to use arithmetic.
 #[test]
 #[cfg(feature = "blitzar")]
-fn we_cannot_prove_a_query_with_arithmetic_in_where_clause_with_curve25519() {
+fn we_can_prove_a_query_with_arithmetic_in_where_clause_with_curve25519() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(
         "sxt.table".parse().unwrap(),
-        owned_table([bigint("a", [1, 2, 3]), bigint("b", [1, 0, 2])]),
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 1, 2])]),
         0,
     );
-    let res_query = QueryExpr::<RistrettoPoint>::try_new(
+    let query = QueryExpr::try_new(
         "SELECT * FROM table WHERE b >= a + 1".parse().unwrap(),
         "sxt".parse().unwrap(),
         &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let owned_table_result = proof
+        .verify(query.proof_expr(), &accessor, &serialized_result, &())
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [1]), bigint("b", [4])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_with_arithmetic_in_where_clause_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
     );
-    assert!(matches!(res_query, Err(ConversionError::Unprovable(_))));
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [1, -3, 2])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE b >= -(a)".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [1, 3]), bigint("b", [1, 2])]);
+    assert_eq!(owned_table_result, expected_result);
 }
 
 #[test]
-fn we_cannot_prove_a_query_with_arithmetic_in_where_clause_with_dory() {
+fn we_cannot_prove_a_query_with_division_in_where_clause_with_dory() {
     let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
     let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
         dory_prover_setup.clone(),
@@ -190,7 +238,7 @@ fn we_cannot_prove_a_query_with_arithmetic_in_where_clause_with_dory() {
         0,
     );
     let res_query = QueryExpr::<DoryCommitment>::try_new(
-        "SELECT * FROM table WHERE b >= -(a)".parse().unwrap(),
+        "SELECT * FROM table WHERE b >= a / 2".parse().unwrap(),
         "sxt".parse().unwrap(),
         &accessor,
     );
@@ -433,3 +481,1656 @@ fn we_can_prove_a_basic_group_by_query_with_dory() {
     ]);
     assert_eq!(owned_table_result, expected_result);
 }
+
+#[test]
+fn we_can_prove_a_group_by_query_with_min_and_max_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2, 1]),
+            int("b", [1, 0, 4, 2, -3, 8]),
+            bigint("c", [-2, 2, 1, 0, 1, 5]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, sum(c) as s, min(b) as lo, max(b) as hi, count(*) as e FROM table WHERE c >= 0 group by a"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("s", [7, 2, 0]),
+        int("lo", [0, -3, 2]),
+        int("hi", [8, 4, 2]),
+        bigint("e", [2, 2, 1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_an_aggregation_query_without_group_by_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2, 1]),
+            bigint("b", [1, 0, 1, 1, 0, 1]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT count(*) as n, sum(a) as s FROM table WHERE b = 1",
+            record_batch!("n" => [4i64], "s" => [7i64]),
+        ),
+        // An aggregation of no rows is a single row of NULLs, except for the counts, which are zero.
+        (
+            "SELECT sum(a) as s FROM table WHERE b = 2",
+            RecordBatch::try_from_iter([(
+                "s",
+                Arc::new(Int64Array::from(vec![None::<i64>])) as ArrayRef,
+            )])
+            .unwrap(),
+        ),
+        (
+            "SELECT min(a) as lo, max(a) as hi, count(*) as n FROM table WHERE b = 2",
+            RecordBatch::try_from_iter([
+                (
+                    "lo",
+                    Arc::new(Int64Array::from(vec![None::<i64>])) as ArrayRef,
+                ),
+                ("hi", Arc::new(Int64Array::from(vec![None::<i64>]))),
+                ("n", Arc::new(Int64Array::from(vec![0]))),
+            ])
+            .unwrap(),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_join_query_with_curve25519() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.sales".parse().unwrap(),
+        owned_table([
+            bigint("store_id", [1, 2, 1, 3, 2, 4]),
+            bigint("amount", [10, 20, 30, 45, 50, 60]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.stores".parse().unwrap(),
+        owned_table([
+            bigint("id", [3, 1, 2]),
+            varchar("city", ["Paris", "Lima", "Oslo"]),
+        ]),
+        2,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT city, sum(amount) as total FROM sales JOIN stores ON sales.store_id = stores.id GROUP BY city ORDER BY total"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let owned_table_result: OwnedTable<Curve25519Scalar> = proof
+        .verify(query.proof_expr(), &accessor, &serialized_result, &())
+        .unwrap()
+        .table;
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result: RecordBatch = record_batch!(
+        "city" => ["Lima", "Paris", "Oslo"],
+        "total" => [40_i64, 45, 70],
+    );
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_join_query_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.facts".parse().unwrap(),
+        owned_table([bigint("k", [1, 2, 1, 3]), bigint("a", [4, 5, 6, 7])]),
+        2,
+    );
+    accessor.add_table(
+        "sxt.dims".parse().unwrap(),
+        owned_table([bigint("k", [2, 1]), bigint("b", [8, 9])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM facts INNER JOIN dims ON facts.k = dims.k"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("k", [1, 1, 2]),
+        bigint("a", [4, 6, 5]),
+        bigint("b", [9, 9, 8]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_queries_on_a_nullable_column_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    // The nulls of `b` are stored as zeros, along with the validity column `__valid__b`.
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2, 1]),
+            bigint("b", [5, 0, 0, 4, 0, 2]),
+            boolean("__valid__b", [true, false, false, true, false, true]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT a FROM table WHERE b IS NULL",
+            record_batch!("a" => [1i64, 2, 2]),
+        ),
+        (
+            "SELECT a, b FROM table WHERE b > 3",
+            RecordBatch::try_from_iter_with_nullable([
+                ("a", Arc::new(Int64Array::from(vec![1, 3])) as ArrayRef, false),
+                ("b", Arc::new(Int64Array::from(vec![5, 4])), true),
+            ])
+            .unwrap(),
+        ),
+        (
+            "SELECT a, b FROM table WHERE NOT b > 3 OR a = 3",
+            RecordBatch::try_from_iter_with_nullable([
+                ("a", Arc::new(Int64Array::from(vec![3, 1])) as ArrayRef, false),
+                ("b", Arc::new(Int64Array::from(vec![4, 2])), true),
+            ])
+            .unwrap(),
+        ),
+        (
+            "SELECT a, sum(b) as s, count(b) as n, count(*) as c FROM table WHERE a >= 0 GROUP BY a",
+            RecordBatch::try_from_iter_with_nullable([
+                ("a", Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef, false),
+                (
+                    "s",
+                    Arc::new(Int64Array::from(vec![Some(7), None, Some(4)])),
+                    true,
+                ),
+                ("n", Arc::new(Int64Array::from(vec![2, 0, 1])), false),
+                ("c", Arc::new(Int64Array::from(vec![3, 2, 1])), false),
+            ])
+            .unwrap(),
+        ),
+        (
+            "SELECT b, count(*) as c FROM table WHERE a >= 0 GROUP BY b",
+            RecordBatch::try_from_iter_with_nullable([
+                (
+                    "b",
+                    Arc::new(Int64Array::from(vec![None, Some(2), Some(4), Some(5)])) as ArrayRef,
+                    true,
+                ),
+                ("c", Arc::new(Int64Array::from(vec![3, 1, 1, 1])), false),
+            ])
+            .unwrap(),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_time_range_queries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    // 2023-12-31T23:59:59.999Z, 2024-01-01T00:00:00Z, 2024-01-01T12:00:00Z and 2024-01-02T00:00:00Z
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4]),
+            timestamptz(
+                "t",
+                PoSQLTimeUnit::Millisecond,
+                PoSQLTimeZone::Utc,
+                [
+                    1_704_067_199_999,
+                    1_704_067_200_000,
+                    1_704_110_400_000,
+                    1_704_153_600_000,
+                ],
+            ),
+            date("d", [19_722, 19_723, 19_723, 19_724]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT a FROM table WHERE t >= TIMESTAMP '2024-01-01T00:00:00Z' \
+             AND t < TIMESTAMP '2024-01-02T00:00:00Z'",
+            record_batch!("a" => [2i64, 3]),
+        ),
+        (
+            "SELECT a, t FROM table WHERE t <= TIMESTAMP '2024-01-01T01:00:00+01:00'",
+            RecordBatch::try_from_iter([
+                ("a", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+                (
+                    "t",
+                    Arc::new(
+                        TimestampMillisecondArray::from(vec![1_704_067_199_999, 1_704_067_200_000])
+                            .with_timezone("+00:00"),
+                    ),
+                ),
+            ])
+            .unwrap(),
+        ),
+        (
+            "SELECT a, d FROM table WHERE d = DATE '2024-01-01'",
+            RecordBatch::try_from_iter([
+                ("a", Arc::new(Int64Array::from(vec![2, 3])) as ArrayRef),
+                ("d", Arc::new(Date32Array::from(vec![19_723, 19_723]))),
+            ])
+            .unwrap(),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_in_list_queries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6]),
+            varchar("s", ["x", "y", "z", "x", "w", "y"]),
+            bigint("b", [5, 0, 0, 4, 2, 2]),
+            boolean("__valid__b", [true, false, true, true, true, true]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT a FROM table WHERE s IN ('x', 'z', 'v')",
+            record_batch!("a" => [1i64, 3, 4]),
+        ),
+        (
+            "SELECT a FROM table WHERE b NOT IN (0, 5)",
+            record_batch!("a" => [4i64, 5, 6]),
+        ),
+        (
+            "SELECT s, count(*) as c FROM table WHERE b IN (2, 5) OR a IN (3) GROUP BY s",
+            record_batch!("s" => ["w", "x", "y", "z"], "c" => [1i64, 1, 1, 1]),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_between_queries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6]),
+            varchar("s", ["x", "y", "z", "x", "w", "y"]),
+            bigint("b", [5, 0, 0, 4, 2, 2]),
+            boolean("__valid__b", [true, false, true, true, true, true]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT a FROM table WHERE b BETWEEN 1 AND 4",
+            record_batch!("a" => [4i64, 5, 6]),
+        ),
+        (
+            "SELECT a FROM table WHERE b NOT BETWEEN 1 AND 4",
+            record_batch!("a" => [1i64, 3]),
+        ),
+        (
+            "SELECT a FROM table WHERE a NOT BETWEEN b AND 4",
+            record_batch!("a" => [1i64, 5, 6]),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_varchar_comparison_queries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    let skus = [
+        "B-20",
+        "A-7",
+        "",
+        "B-3",
+        "B",
+        "C-1",
+        "B-20-LONG-NAME-SUFFIX",
+    ];
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6, 7]),
+            varchar("sku", skus),
+            varchar_order_key("sku", skus),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT a FROM table WHERE sku >= 'B-20' AND sku < 'C'",
+            record_batch!("a" => [1i64, 4, 7]),
+        ),
+        (
+            "SELECT a FROM table WHERE sku > 'B-20'",
+            record_batch!("a" => [4i64, 6, 7]),
+        ),
+        (
+            "SELECT a FROM table WHERE sku BETWEEN 'A' AND 'B'",
+            record_batch!("a" => [2i64, 5]),
+        ),
+        (
+            "SELECT a, sku FROM table WHERE sku <= 'B-3' ORDER BY sku DESC",
+            record_batch!(
+                "a" => [4i64, 7, 1, 5, 2, 3],
+                "sku" => ["B-3", "B-20-LONG-NAME-SUFFIX", "B-20", "B", "A-7", ""],
+            ),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_like_queries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    let skus = [
+        "B-20",
+        "A-7",
+        "",
+        "B-3",
+        "B",
+        "C-1",
+        "B-20-LONG-NAME-SUFFIX",
+    ];
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6, 7]),
+            varchar("sku", skus),
+            varchar_order_key("sku", skus),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT a FROM table WHERE sku LIKE 'B-20%'",
+            record_batch!("a" => [1i64, 7]),
+        ),
+        (
+            "SELECT a FROM table WHERE sku LIKE 'B%'",
+            record_batch!("a" => [1i64, 4, 5, 7]),
+        ),
+        (
+            "SELECT a FROM table WHERE sku NOT LIKE 'B%'",
+            record_batch!("a" => [2i64, 3, 6]),
+        ),
+        (
+            "SELECT a FROM table WHERE sku LIKE 'B-20-LONG-NAME-%'",
+            record_batch!("a" => [7i64]),
+        ),
+        (
+            "SELECT a FROM table WHERE sku LIKE 'B-3' OR sku LIKE ''",
+            record_batch!("a" => [3i64, 4]),
+        ),
+        (
+            "SELECT a FROM table WHERE sku LIKE '%'",
+            record_batch!("a" => [1i64, 2, 3, 4, 5, 6, 7]),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_case_queries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 20, 3, 400, 50, 6]),
+            varchar("kind", ["x", "y", "x", "z", "y", "x"]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT a, CASE WHEN a >= 100 THEN 'big' WHEN a >= 10 THEN 'medium' ELSE 'small' END AS size \
+             FROM table ORDER BY a",
+            record_batch!(
+                "a" => [1i64, 3, 6, 20, 50, 400],
+                "size" => ["small", "small", "small", "medium", "medium", "big"],
+            ),
+        ),
+        (
+            "SELECT a FROM table WHERE CASE kind WHEN 'x' THEN a WHEN 'y' THEN a - 40 ELSE 0 END >= 3",
+            record_batch!("a" => [3i64, 50, 6]),
+        ),
+        (
+            "SELECT a FROM table WHERE CASE WHEN kind = 'z' THEN false ELSE a > 10 END",
+            record_batch!("a" => [20i64, 50]),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_cast_queries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 0, -300, 40000]),
+            boolean("b", [true, false, false, true]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT CAST(a AS INT) AS a, CAST(b AS SMALLINT) AS b FROM table WHERE CAST(a AS BOOLEAN)",
+            record_batch!(
+                "a" => [1i32, -300, 40000],
+                "b" => [1i16, 0, 1],
+            ),
+        ),
+        (
+            "SELECT a FROM table WHERE CAST(a AS DECIMAL(10, 2)) >= 0.5",
+            record_batch!("a" => [1i64, 40000]),
+        ),
+        // 40000 does not fit in a SMALLINT, but it is not selected.
+        (
+            "SELECT CAST(a AS SMALLINT) AS a FROM table WHERE a < 1000",
+            record_batch!("a" => [1i16, 0, -300]),
+        ),
+        (
+            "SELECT CASE WHEN a < 1000 THEN CAST(a AS SMALLINT) ELSE CAST(0 AS SMALLINT) END AS a \
+             FROM table WHERE b",
+            record_batch!("a" => [1i16, 0]),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+
+    // 40000 does not fit in a SMALLINT, so the proof must not verify.
+    let query = QueryExpr::try_new(
+        "SELECT CAST(a AS SMALLINT) AS a FROM table"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    assert!(proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .is_err());
+}
+
+#[test]
+fn we_can_prove_avg_queries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6]),
+            varchar("s", ["x", "y", "x", "y", "x", "z"]),
+            bigint("b", [5, 0, 2, 4, 3, 0]),
+            boolean("__valid__b", [true, false, true, true, true, false]),
+        ]),
+        0,
+    );
+    // Averages of BIGINT columns are DECIMAL(25, 6).
+    let decimal = |values: Vec<Option<i128>>| -> ArrayRef {
+        Arc::new(
+            Decimal256Array::from(Vec::from_iter(
+                values.into_iter().map(|value| value.map(i256::from_i128)),
+            ))
+            .with_precision_and_scale(25, 6)
+            .unwrap(),
+        )
+    };
+    for (query, expected_result) in [
+        (
+            "SELECT s, AVG(a) AS x, AVG(b) AS y FROM table WHERE a >= 1 GROUP BY s",
+            RecordBatch::try_from_iter([
+                (
+                    "s",
+                    Arc::new(StringArray::from(vec!["x", "y", "z"])) as ArrayRef,
+                ),
+                (
+                    "x",
+                    decimal(vec![Some(3_000_000), Some(3_000_000), Some(6_000_000)]),
+                ),
+                ("y", decimal(vec![Some(3_333_333), Some(4_000_000), None])),
+            ])
+            .unwrap(),
+        ),
+        (
+            "SELECT AVG(b) AS y FROM table WHERE a >= 4",
+            RecordBatch::try_from_iter([("y", decimal(vec![Some(3_500_000)]))]).unwrap(),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_distinct_queries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6]),
+            varchar("s", ["x", "y", "x", "y", "x", "z"]),
+            bigint("b", [5, 0, 2, 4, 3, 0]),
+            boolean("__valid__b", [true, false, true, true, true, false]),
+            bigint("c", [1, 2, 1, 2, 1, 3]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT DISTINCT c FROM table WHERE a >= 2",
+            record_batch!("c" => [1_i64, 2, 3]),
+        ),
+        (
+            "SELECT s, COUNT(DISTINCT b) AS n FROM table WHERE a >= 1 GROUP BY s",
+            record_batch!("s" => ["x", "y", "z"], "n" => [3_i64, 1, 0]),
+        ),
+        (
+            "SELECT COUNT(DISTINCT b) AS n FROM table WHERE a >= 1",
+            record_batch!("n" => [4_i64]),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_group_by_queries_with_having_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6]),
+            varchar("s", ["x", "y", "x", "y", "x", "z"]),
+            bigint("b", [5, 0, 2, 4, 3, 0]),
+            boolean("__valid__b", [true, false, true, true, true, false]),
+        ]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT s, SUM(a) AS t FROM table WHERE a >= 1 GROUP BY s HAVING COUNT(*) >= 2",
+            record_batch!("s" => ["x", "y"], "t" => [9_i64, 6]),
+        ),
+        (
+            "SELECT s, COUNT(*) AS c FROM table WHERE a >= 1 GROUP BY s HAVING SUM(b) >= 4 AND s = 'x'",
+            record_batch!("s" => ["x"], "c" => [3_i64]),
+        ),
+        (
+            "SELECT SUM(a) AS t FROM table WHERE a >= 5 HAVING COUNT(*) >= 3",
+            record_batch!("t" => Vec::<i64>::new()),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_group_by_queries_on_expressions_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3, 4, 5, 6])]),
+        0,
+    );
+    for (query, expected_result) in [
+        (
+            "SELECT CASE WHEN a >= 4 THEN 'high' ELSE 'low' END AS bucket, SUM(a) AS t, COUNT(*) AS c FROM table WHERE a >= 1 GROUP BY 1",
+            record_batch!("bucket" => ["high", "low"], "t" => [15_i64, 6], "c" => [3_i64, 3]),
+        ),
+        (
+            "SELECT a * 2 - 1 AS k, COUNT(*) AS c FROM table WHERE a >= 5 GROUP BY a * 2 - 1",
+            record_batch!("k" => [9_i64, 11], "c" => [1_i64, 1]),
+        ),
+    ] {
+        let query =
+            QueryExpr::try_new(query.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result, expected_result);
+    }
+}
+
+#[test]
+fn we_can_prove_group_by_queries_on_decimal_keys_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6]),
+            bigint("k", [-2, 3, -2, 0, 3, 3]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT CAST(k AS DECIMAL(10, 2)) AS d, COUNT(*) AS c FROM table WHERE a >= 1 GROUP BY CAST(k AS DECIMAL(10, 2))"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    // Decimal keys are grouped in signed order, so negative keys come first.
+    let expected_result = RecordBatch::try_from_iter([
+        (
+            "d",
+            Arc::new(
+                Decimal256Array::from(vec![
+                    i256::from_i128(-200),
+                    i256::from_i128(0),
+                    i256::from_i128(300),
+                ])
+                .with_precision_and_scale(10, 2)
+                .unwrap(),
+            ) as ArrayRef,
+        ),
+        ("c", Arc::new(Int64Array::from(vec![2, 1, 3])) as ArrayRef),
+    ])
+    .unwrap();
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_sums_and_counts_of_expressions_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("cat", [1, 1, 2, 2, 2]),
+            bigint("price", [10, 20, 30, 40, 50]),
+            bigint("qty", [1, 3, 2, 5, 4]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT cat, SUM(price * qty) AS revenue, COUNT(CASE WHEN qty >= 3 THEN 1 END) AS bulk, COUNT(*) AS c FROM table WHERE qty >= 1 GROUP BY cat"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!(
+        "cat" => [1_i64, 2],
+        "revenue" => [70_i64, 460],
+        "bulk" => [1_i64, 2],
+        "c" => [2_i64, 3]
+    );
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_an_order_by_with_a_limit_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("amount", [30, 10, 50, 20, 40, 60]),
+            varchar("name", ["c", "a", "e", "b", "d", "f"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT name, amount FROM table WHERE amount <= 50 ORDER BY amount DESC LIMIT 2"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    // Only the top two rows are sent by the prover, in the order of the table.
+    assert_eq!(
+        owned_table_result,
+        owned_table([varchar("name", ["e", "d"]), bigint("amount", [50, 40])])
+    );
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!(
+        "name" => ["e", "d"],
+        "amount" => [50_i64, 40]
+    );
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_limit_with_an_offset_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("amount", [30, 10, 50, 20, 40, 60]),
+            varchar("name", ["c", "a", "e", "b", "d", "f"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT name, amount FROM table WHERE amount >= 20 LIMIT 2 OFFSET 1"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    // Only the rows of the slice are sent by the prover.
+    assert_eq!(
+        owned_table_result,
+        owned_table([varchar("name", ["e", "b"]), bigint("amount", [50, 20])])
+    );
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!(
+        "name" => ["e", "b"],
+        "amount" => [50_i64, 20]
+    );
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_union_of_two_tables_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.sales".parse().unwrap(),
+        owned_table([
+            bigint("amount", [30, 10, 50, 20]),
+            varchar("name", ["c", "a", "e", "b"]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.returns".parse().unwrap(),
+        owned_table([
+            bigint("refund", [20, 5, 40]),
+            varchar("customer", ["b", "g", "d"]),
+        ]),
+        3,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT name, amount FROM sales WHERE amount >= 20 UNION SELECT customer, refund FROM returns WHERE refund >= 10 ORDER BY amount"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    // The proven result is the concatenation of the filtered tables.
+    assert_eq!(
+        owned_table_result,
+        owned_table([
+            varchar("name", ["c", "e", "b", "b", "d"]),
+            bigint("amount", [30, 50, 20, 20, 40])
+        ])
+    );
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!(
+        "name" => ["b", "c", "d", "e"],
+        "amount" => [20_i64, 30, 40, 50]
+    );
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_with_subqueries_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.users".parse().unwrap(),
+        owned_table([
+            bigint("user_id", [1, 2, 3, 4, 5]),
+            varchar("name", ["a", "b", "c", "d", "e"]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.allowed".parse().unwrap(),
+        owned_table([
+            bigint("id", [4, 2, 5, 1]),
+            varchar("tier", ["gold", "gold", "silver", "gold"]),
+        ]),
+        2,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT name FROM users WHERE user_id IN (SELECT id FROM allowed WHERE tier = 'gold') AND user_id > (SELECT COUNT(*) AS c FROM allowed WHERE tier = 'silver') ORDER BY name DESC"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    assert_eq!(
+        owned_table_result,
+        owned_table([varchar("name", ["b", "d"])])
+    );
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!(
+        "name" => ["d", "b"]
+    );
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_with_common_table_expressions_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.sales".parse().unwrap(),
+        owned_table([
+            bigint("amount", [5, 30, 12, 48, 7, 21]),
+            varchar("region", ["n", "s", "n", "e", "s", "n"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "WITH large AS (SELECT amount AS value, region FROM sales WHERE amount >= 10), \
+         north AS (SELECT value FROM large WHERE region = 'n') \
+         SELECT * FROM north ORDER BY value DESC"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    assert_eq!(owned_table_result, owned_table([bigint("value", [12, 21])]));
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!(
+        "value" => [21_i64, 12]
+    );
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_over_an_aggregated_common_table_expression_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.sales".parse().unwrap(),
+        owned_table([
+            bigint("store", [1, 2, 1, 3, 2, 1]),
+            bigint("amount", [5, 30, 12, 48, 7, 21]),
+        ]),
+        0,
+    );
+    // The totals are proven by their own plan, and the rest of the query is computed over them.
+    let query = QueryExpr::try_new(
+        "WITH totals AS (SELECT store, SUM(amount) AS total, COUNT(*) AS n FROM sales \
+         WHERE amount >= 0 GROUP BY store) \
+         SELECT store, total FROM totals WHERE n >= 2 ORDER BY total DESC"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!(
+        "store" => [1_i64, 2],
+        "total" => [38_i64, 37],
+    );
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_running_sum_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.events".parse().unwrap(),
+        owned_table([
+            bigint("ts", [1, 2, 4, 7, 8, 9]),
+            bigint("amount", [5, -3, 1, 7, 2, 4]),
+        ]),
+        0,
+    );
+    accessor.set_strictly_increasing("sxt.events".parse().unwrap(), "ts".parse().unwrap());
+    let query = QueryExpr::try_new(
+        "SELECT ts, SUM(amount) OVER (ORDER BY ts) AS total FROM events \
+         WHERE amount >= 0 ORDER BY ts DESC LIMIT 3"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    assert_eq!(
+        owned_table_result,
+        owned_table([
+            bigint("ts", [1, 4, 7, 8, 9]),
+            bigint("total", [5, 6, 13, 15, 19])
+        ])
+    );
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!(
+        "ts" => [9_i64, 8, 7],
+        "total" => [19_i64, 15, 13]
+    );
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_get_the_same_running_sums_whether_they_are_proven_or_computed_in_post_processing_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    // The running sum is proven when `ts` is declared strictly increasing, and computed in post-processing otherwise.
+    let run = |amount: [i16; 4], is_strictly_increasing: bool| -> Option<RecordBatch> {
+        let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+            dory_prover_setup.clone(),
+        );
+        accessor.add_table(
+            "sxt.events".parse().unwrap(),
+            owned_table([bigint("ts", [1, 2, 4, 7]), smallint("amount", amount)]),
+            0,
+        );
+        if is_strictly_increasing {
+            accessor.set_strictly_increasing("sxt.events".parse().unwrap(), "ts".parse().unwrap());
+        }
+        let query = QueryExpr::try_new(
+            "SELECT ts, SUM(amount) OVER (ORDER BY ts) AS total FROM events WHERE amount >= 0"
+                .parse()
+                .unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor,
+        )
+        .unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .ok()?
+            .table;
+        query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+    };
+    // The running sums are of the type of the summed column either way.
+    let proven_result = run([5, 3, 1, 7], true).unwrap();
+    let computed_result = run([5, 3, 1, 7], false).unwrap();
+    assert_eq!(proven_result.schema(), computed_result.schema());
+    assert_eq!(proven_result, computed_result);
+    assert_eq!(
+        proven_result,
+        record_batch!("ts" => [1_i64, 2, 4, 7], "total" => [5_i16, 8, 9, 16])
+    );
+    // So a running sum that does not fit in that type is an error either way.
+    assert!(run([30000, 3, 1, 30000], true).is_none());
+    assert!(run([30000, 3, 1, 30000], false).is_none());
+}
+
+#[test]
+fn we_can_compute_a_running_sum_over_duplicate_or_unsorted_keys_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.events".parse().unwrap(),
+        owned_table([
+            bigint("ts", [4, 1, 7, 4, 9, 2]),
+            bigint("amount", [5, -3, 1, 7, 2, 4]),
+        ]),
+        0,
+    );
+    // `ts` is not declared strictly increasing, so the running sum is computed in post-processing.
+    let query = QueryExpr::try_new(
+        "SELECT ts, SUM(amount) OVER (ORDER BY ts) AS total FROM events \
+         WHERE amount >= 0 ORDER BY ts"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    // Rows with the same `ts` are peers, so they share their running sum,
+    // which is a DECIMAL(76, 0) when computed in post-processing.
+    let expected_result = RecordBatch::try_from_iter([
+        (
+            "ts",
+            Arc::new(Int64Array::from(vec![2, 4, 4, 7, 9])) as ArrayRef,
+        ),
+        (
+            "total",
+            Arc::new(
+                Decimal256Array::from(Vec::from_iter([4, 16, 16, 17, 19].map(i256::from_i128)))
+                    .with_precision_and_scale(76, 0)
+                    .unwrap(),
+            ) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_with_a_row_number_with_dory() {
+    let dory_prover_setup = DoryProverPublicSetup::rand(4, 3, &mut test_rng());
+    let dory_verifier_setup = (&dory_prover_setup).into();
+
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(
+        dory_prover_setup.clone(),
+    );
+    accessor.add_table(
+        "sxt.events".parse().unwrap(),
+        owned_table([
+            bigint("ts", [1, 2, 4, 7, 8, 9]),
+            varchar("region", ["n", "s", "n", "s", "n", "s"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT region, ROW_NUMBER() OVER (PARTITION BY region ORDER BY ts DESC) AS n FROM events"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(owned_table_result.try_into().unwrap())
+        .unwrap();
+    let expected_result = record_batch!(
+        "region" => ["n", "s", "n", "s", "n", "s"],
+        "n" => [3_i64, 3, 2, 2, 1, 1]
+    );
+    assert_eq!(transformed_result, expected_result);
+}